@@ -30,6 +30,54 @@ fn single_spectrum(c: &mut Criterion) {
     });
 }
 
+fn ignore_regions(c: &mut Criterion) {
+    let data_dir = workspace_dir().join("data").join("bruker");
+    let blood_path = data_dir.join("blood").join("blood_01");
+    let blood_spectrum = Bruker::read_spectrum(blood_path, 10, 10, (-2.2, 11.8)).unwrap();
+    let mut deconvoluter = Deconvoluter::default();
+    deconvoluter
+        .add_ignore_region((1.5, 2.0))
+        .unwrap();
+    deconvoluter
+        .add_ignore_region((4.5, 5.0))
+        .unwrap();
+    deconvoluter
+        .add_ignore_region((7.0, 7.5))
+        .unwrap();
+
+    // Peak selection is split into segments at the ignore regions
+    c.bench_function("deconvolute_blood_spectrum_ignore_regions", |b| {
+        b.iter(|| deconvoluter.deconvolute_spectrum(&blood_spectrum))
+    });
+    c.bench_function("parallel_deconvolute_blood_spectrum_ignore_regions", |b| {
+        b.iter(|| deconvoluter.par_deconvolute_spectrum(&blood_spectrum))
+    });
+}
+
+fn static_dispatch(c: &mut Criterion) {
+    let data_dir = workspace_dir().join("data").join("bruker");
+    let sim_path = data_dir.join("sim").join("sim_01");
+    let blood_path = data_dir.join("blood").join("blood_01");
+    let sim_spectrum = Bruker::read_spectrum(sim_path, 10, 10, (3.34, 3.56)).unwrap();
+    let blood_spectrum = Bruker::read_spectrum(blood_path, 10, 10, (-2.2, 11.8)).unwrap();
+    let dynamic = Deconvoluter::default();
+    let monomorphized = StaticDeconvoluter::default();
+
+    // Same pipeline with trait objects and with concrete types
+    c.bench_function("dynamic_deconvolute_sim_spectrum", |b| {
+        b.iter(|| dynamic.deconvolute_spectrum(&sim_spectrum))
+    });
+    c.bench_function("static_deconvolute_sim_spectrum", |b| {
+        b.iter(|| monomorphized.deconvolute_spectrum(&sim_spectrum))
+    });
+    c.bench_function("dynamic_deconvolute_blood_spectrum", |b| {
+        b.iter(|| dynamic.deconvolute_spectrum(&blood_spectrum))
+    });
+    c.bench_function("static_deconvolute_blood_spectrum", |b| {
+        b.iter(|| monomorphized.deconvolute_spectrum(&blood_spectrum))
+    });
+}
+
 fn multiple_spectra(c: &mut Criterion) {
     let data_dir = workspace_dir().join("data").join("bruker");
     let sim_path = data_dir.join("sim");
@@ -50,7 +98,7 @@ fn multiple_spectra(c: &mut Criterion) {
 criterion_group! {
     name = deconvoluter;
     config = Criterion::default().sample_size(50);
-    targets = single_spectrum, multiple_spectra
+    targets = single_spectrum, ignore_regions, static_dispatch, multiple_spectra
 }
 
 criterion_main!(deconvoluter);
@@ -0,0 +1,55 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use metabodecon::deconvolution::*;
+
+fn precompute(c: &mut Criterion) {
+    let lorentzians = (0..10_000)
+        .map(|i| {
+            let hw = 0.001 + 0.0001 * (i % 7) as f64;
+            Lorentzian::new((1.0 + (i % 5) as f64) * hw, hw.powi(2), 0.001 * i as f64)
+        })
+        .collect::<Vec<Lorentzian>>();
+    let precomputed = lorentzians
+        .iter()
+        .map(Lorentzian::precompute)
+        .collect::<Vec<PrecomputedLorentzian>>();
+
+    // Untransformed parameters, width and area of each signal, as in a peak table
+    c.bench_function("lorentzian_peak_table", |b| {
+        b.iter(|| {
+            lorentzians
+                .iter()
+                .map(|lorentzian| {
+                    (
+                        lorentzian.sf(),
+                        lorentzian.hw(),
+                        lorentzian.fwhm(),
+                        lorentzian.integral(),
+                    )
+                })
+                .collect::<Vec<(f64, f64, f64, f64)>>()
+        })
+    });
+    c.bench_function("precomputed_lorentzian_peak_table", |b| {
+        b.iter(|| {
+            precomputed
+                .iter()
+                .map(|lorentzian| {
+                    (
+                        lorentzian.sf(),
+                        lorentzian.hw(),
+                        lorentzian.fwhm(),
+                        lorentzian.integral(),
+                    )
+                })
+                .collect::<Vec<(f64, f64, f64, f64)>>()
+        })
+    });
+}
+
+criterion_group! {
+    name = lorentzian;
+    config = Criterion::default().sample_size(50);
+    targets = precompute
+}
+
+criterion_main!(lorentzian);
@@ -0,0 +1,62 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use metabodecon::deconvolution::*;
+use metabodecon::spectrum::*;
+
+mod workspace_dir;
+use workspace_dir::workspace_dir;
+
+fn precision(c: &mut Criterion) {
+    let data_dir = workspace_dir().join("data").join("bruker");
+    let blood_path = data_dir.join("blood").join("blood_01");
+    let blood_spectrum = Bruker::read_spectrum(blood_path, 10, 10, (-2.2, 11.8)).unwrap();
+    let intensities_f64 = blood_spectrum.intensities().to_vec();
+    let intensities_f32 = intensities_f64
+        .iter()
+        .map(|&intensity| intensity as f32)
+        .collect::<Vec<f32>>();
+    let moving_average = SmoothingSettings::default();
+    let whittaker = SmoothingSettings::Whittaker {
+        lambda: 1e2,
+        order: 2,
+    };
+
+    // Moving average
+    c.bench_function("moving_average_blood_spectrum_f64", |b| {
+        b.iter(|| {
+            let mut values = intensities_f64.clone();
+            moving_average.smooth_values(&mut values).unwrap();
+            values
+        })
+    });
+    c.bench_function("moving_average_blood_spectrum_f32", |b| {
+        b.iter(|| {
+            let mut values = intensities_f32.clone();
+            moving_average.smooth_values(&mut values).unwrap();
+            values
+        })
+    });
+
+    // Whittaker
+    c.bench_function("whittaker_blood_spectrum_f64", |b| {
+        b.iter(|| {
+            let mut values = intensities_f64.clone();
+            whittaker.smooth_values(&mut values).unwrap();
+            values
+        })
+    });
+    c.bench_function("whittaker_blood_spectrum_f32", |b| {
+        b.iter(|| {
+            let mut values = intensities_f32.clone();
+            whittaker.smooth_values(&mut values).unwrap();
+            values
+        })
+    });
+}
+
+criterion_group! {
+    name = smoothing;
+    config = Criterion::default().sample_size(50);
+    targets = precision
+}
+
+criterion_main!(smoothing);
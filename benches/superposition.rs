@@ -0,0 +1,40 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use metabodecon::deconvolution::*;
+
+fn lorentzian(c: &mut Criterion) {
+    let chemical_shifts = (0..1 << 17)
+        .map(|i| i as f64 * 10.0 / (1 << 17) as f64)
+        .collect::<Vec<f64>>();
+    let lorentzians = (0..500)
+        .map(|i| {
+            let hw = 0.001 + 0.0001 * (i % 7) as f64;
+            Lorentzian::new((1.0 + (i % 5) as f64) * hw, hw.powi(2), 0.02 * i as f64)
+        })
+        .collect::<Vec<Lorentzian>>();
+
+    // Scalar reference, one chemical shift at a time
+    c.bench_function("scalar_lorentzian_superposition", |b| {
+        b.iter(|| {
+            chemical_shifts
+                .iter()
+                .map(|&x| Lorentzian::superposition(x, &lorentzians))
+                .collect::<Vec<f64>>()
+        })
+    });
+
+    // Vectorized if the simd feature is enabled
+    c.bench_function("lorentzian_superposition", |b| {
+        b.iter(|| Lorentzian::superposition_vec(&chemical_shifts, &lorentzians))
+    });
+    c.bench_function("parallel_lorentzian_superposition", |b| {
+        b.iter(|| Lorentzian::par_superposition_vec(&chemical_shifts, &lorentzians))
+    });
+}
+
+criterion_group! {
+    name = superposition;
+    config = Criterion::default().sample_size(20);
+    targets = lorentzian
+}
+
+criterion_main!(superposition);
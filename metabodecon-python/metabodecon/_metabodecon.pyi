@@ -16,18 +16,38 @@ class Deconvoluter:
     def set_detector_only(self) -> None:
         ...
 
-    def set_noise_score_selector(self, threshold: float) -> None:
+    def set_noise_score_selector(
+        self, threshold: float, max_peaks: int | None = None, robust: bool = False
+    ) -> None:
         ...
 
     def set_analytical_fitter(self, iterations: int) -> None:
         ...
 
+    def set_uniform_mse(self) -> None:
+        ...
+
+    def set_intensity_weighted_mse(self) -> None:
+        ...
+
     def add_ignore_region(self, boundaries: tuple[float, float]) -> None:
         ...
 
     def clear_ignore_regions(self) -> None:
         ...
 
+    def add_boost_region(self, boundaries: tuple[float, float], factor: float) -> None:
+        ...
+
+    def clear_boost_regions(self) -> None:
+        ...
+
+    def set_noise_region(self, boundaries: tuple[float, float]) -> None:
+        ...
+
+    def clear_noise_region(self) -> None:
+        ...
+
     def set_threads(self, threads: int) -> None:
         ...
 
@@ -40,6 +60,12 @@ class Deconvoluter:
     def par_deconvolute_spectrum(self, spectrum: "Spectrum") -> "Deconvolution":
         ...
 
+    def refine(self, spectrum: "Spectrum", previous: "Deconvolution") -> "Deconvolution":
+        ...
+
+    def smoothed_intensities(self, spectrum: "Spectrum") -> np.ndarray:
+        ...
+
     def deconvolute_spectra(self, spectra: list["Spectrum"]) -> list["Deconvolution"]:
         ...
 
@@ -49,10 +75,28 @@ class Deconvoluter:
     def optimize_settings(self, reference: "Spectrum") -> float:
         ...
 
+    def optimize_settings_in_region(self, reference: "Spectrum",
+                                    boundaries: tuple[float, float]) -> float:
+        ...
+
 
 class Deconvolution:
     lorentzians: list["Lorentzian"]
     mse: float
+    mse_weighting: str
+    noise_estimate: float | None
+    parameter_uncertainties: list[tuple[float, float, float]] | None
+    labels: list[str | None]
+    warnings: list[str]
+
+    def label_peak(self, index: int, name: str) -> None:
+        ...
+
+    def label_by_position(self, ppm: float, tolerance: float, name: str) -> int | None:
+        ...
+
+    def validate(self) -> None:
+        ...
 
     def superposition(self, x: float) -> float:
         ...
@@ -63,6 +107,28 @@ class Deconvolution:
     def par_superposition_vec(self, x: np.ndarray) -> np.ndarray:
         ...
 
+    def component_curves(self, x: np.ndarray) -> list[np.ndarray]:
+        ...
+
+    def par_component_curves(self, x: np.ndarray) -> list[np.ndarray]:
+        ...
+
+    def summary_string(self, top_n: int) -> str:
+        ...
+
+    def __str__(self) -> str:
+        ...
+
+    def adjacent_overlaps(self) -> list[float] | None:
+        ...
+
+    def merge_close_peaks(self, min_separation: float) -> "Deconvolution":
+        ...
+
+    def merge_close_peaks_with_mse(self, spectrum: "Spectrum",
+                                   min_separation: float) -> "Deconvolution":
+        ...
+
     def write_json(self, path: str) -> None:
         ...
 
@@ -90,15 +156,42 @@ class Lorentzian:
     def from_transformed(sfhw: float, hw2: float, maxp: float) -> "Lorentzian":
         ...
 
+    def fwhm(self) -> float:
+        ...
+
+    def max_intensity(self) -> float:
+        ...
+
+    def value_at_half_max(self) -> float:
+        ...
+
     def evaluate(self, x: float) -> float:
         ...
 
     def evaluate_vec(self, x: np.ndarray) -> np.ndarray:
         ...
 
+    def derivative(self, x: float) -> float:
+        ...
+
+    def derivative_vec(self, x: np.ndarray) -> np.ndarray:
+        ...
+
+    def second_derivative(self, x: float) -> float:
+        ...
+
+    def second_derivative_vec(self, x: np.ndarray) -> np.ndarray:
+        ...
+
     def integral(self) -> float:
         ...
 
+    def integral_between(self, a: float, b: float) -> float:
+        ...
+
+    def overlap(self, other: "Lorentzian") -> float:
+        ...
+
     @staticmethod
     def superposition(x: float, lorentzians: list["Lorentzian"]) -> float:
         ...
@@ -115,20 +208,41 @@ class Lorentzian:
 class Spectrum:
     chemical_shifts: np.ndarray
     intensities: np.ndarray
+    chemical_shifts_hz: np.ndarray
     signal_boundaries: tuple[float, float]
+    signal_boundaries_hz: tuple[float, float]
     nucleus: str
     frequency: float
+    sample_name: str | None
+    acquisition_date: str | None
+    description: str | None
     reference_compound: dict
 
     def __init__(self, chemical_shifts: np.ndarray, intensities: np.ndarray,
                  signal_boundaries: tuple[float, float]) -> None:
         ...
 
+    @staticmethod
+    def from_nonuniform(chemical_shifts: np.ndarray, intensities: np.ndarray,
+                        signal_boundaries: tuple[float, float]) -> "Spectrum":
+        ...
+
     @staticmethod
     def read_bruker(path: str, experiment: int, processing: int,
                     signal_boundaries: tuple[float, float]) -> "Spectrum":
         ...
 
+    @staticmethod
+    def read_bruker_complex(path: str, experiment: int, processing: int,
+                            signal_boundaries: tuple[float, float]) -> tuple[
+        "Spectrum", np.ndarray | None]:
+        ...
+
+    @staticmethod
+    def read_bruker_any(path: str, experiment: int,
+                        signal_boundaries: tuple[float, float]) -> "Spectrum":
+        ...
+
     @staticmethod
     def read_bruker_set(path: str, experiment: int, processing: int,
                         signal_boundaries: tuple[float, float]) -> list[
@@ -143,6 +257,16 @@ class Spectrum:
     def read_jcampdx_set(path: str, signal_boundaries: tuple[float, float]) -> list["Spectrum"]:
         ...
 
+    def calibrate_to_peak(self, search_range: tuple[float, float], target_shift: float,
+                          name: str | None = None) -> None:
+        ...
+
+    def in_hz(self) -> "Spectrum":
+        ...
+
+    def reversed(self) -> "Spectrum":
+        ...
+
     def write_json(self, path: str) -> None:
         ...
 
@@ -68,6 +68,110 @@ class InvalidSignalBoundaries(SpectrumError):
     ...
 
 
+class InvalidCropRange(SpectrumError):
+    """
+    Range to crop a Spectrum to is invalid.
+    """
+
+    ...
+
+
+class InvalidResampling(SpectrumError):
+    """
+    Parameters to resample a Spectrum with are invalid.
+    """
+
+    ...
+
+
+class InvalidBucketWidth(SpectrumError):
+    """
+    Width of the buckets to divide a Spectrum into is invalid.
+    """
+
+    ...
+
+
+class InvalidNormalization(SpectrumError):
+    """
+    Intensities of a Spectrum can't be normalized.
+    """
+
+    ...
+
+
+class InvalidPhaseCorrection(SpectrumError):
+    """
+    Phase correction angles for a Spectrum are invalid.
+    """
+
+    ...
+
+
+class InvalidNoiseMultiple(SpectrumError):
+    """
+    Multiple of the noise level for signal region detection is invalid.
+    """
+
+    ...
+
+
+class NoSignalRegionDetected(SpectrumError):
+    """
+    No signal region could be detected in a Spectrum.
+    """
+
+    ...
+
+
+class InvalidNoiseRange(SpectrumError):
+    """
+    Noise range to estimate the signal-to-noise ratio from is invalid.
+    """
+
+    ...
+
+
+class InvalidCalibration(SpectrumError):
+    """
+    Spectrum could not be calibrated to a reference peak.
+    """
+
+    ...
+
+
+class InvalidWindowFunction(SpectrumError):
+    """
+    Window function could not be applied to the spectrum.
+    """
+
+    ...
+
+
+class InvalidFrequency(SpectrumError):
+    """
+    Spectrometer frequency is unset or invalid for converting Hz to ppm.
+    """
+
+    ...
+
+
+class GridMismatch(SpectrumError):
+    """
+    Spectra combined by arithmetic don't share the same grid.
+    """
+
+    ...
+
+
+class InvalidDecimation(SpectrumError):
+    """
+    Factor to decimate a Spectrum by is invalid.
+    """
+
+    ...
+
+
 class MissingMetadata(SpectrumError):
     """
     Metadata is missing from NMR format-related file.
@@ -100,6 +204,23 @@ class MalformedData(SpectrumError):
     ...
 
 
+class UnsupportedJcampDxFile(SpectrumError):
+    """
+    The JCAMP-DX file uses a data type or layout that is not supported.
+    """
+
+    ...
+
+
+class UntransformableFid(SpectrumError):
+    """
+    The JCAMP-DX file contains an FID that can't be transformed into a spectrum
+    because the library was built without the `fft` feature.
+    """
+
+    ...
+
+
 class DeconvolutionError(Error):
     """
     An exception raised for errors during the deconvolution process.
@@ -108,6 +229,14 @@ class DeconvolutionError(Error):
     ...
 
 
+class InvalidBaselineSettings(DeconvolutionError):
+    """
+    Baseline correction settings are invalid.
+    """
+
+    ...
+
+
 class InvalidSmoothingSettings(DeconvolutionError):
     """
     Smoothing settings are invalid.
@@ -140,6 +269,54 @@ class InvalidIgnoreRegion(DeconvolutionError):
     ...
 
 
+class InvalidNoiseRegion(DeconvolutionError):
+    """
+    Noise region boundaries are invalid.
+    """
+
+    ...
+
+
+class InvalidBoostRegion(DeconvolutionError):
+    """
+    Boost region boundaries or factor are invalid.
+    """
+
+    ...
+
+
+class InvalidDeconvolutionRegion(DeconvolutionError):
+    """
+    Deconvolution region boundaries are invalid.
+    """
+
+    ...
+
+
+class InvalidRegionUpdate(DeconvolutionError):
+    """
+    Region to be updated doesn't match the provided intensities.
+    """
+
+    ...
+
+
+class EmptyOptimizationGrid(DeconvolutionError):
+    """
+    Optimization grid lacks candidates for a deconvolution step.
+    """
+
+    ...
+
+
+class InvalidSignal(DeconvolutionError):
+    """
+    Signal doesn't represent a valid peak shape.
+    """
+
+    ...
+
+
 class NoPeaksDetected(DeconvolutionError):
     """
     No peaks were detected in the spectrum.
@@ -162,3 +339,27 @@ class EmptySignalFreeRegion(DeconvolutionError):
     """
 
     ...
+
+
+class Cancelled(DeconvolutionError):
+    """
+    Deconvolution was cancelled.
+    """
+
+    ...
+
+
+class UnsupportedRefinement(DeconvolutionError):
+    """
+    Fitting settings don't support refining a previous deconvolution.
+    """
+
+    ...
+
+
+class InvalidTemplate(DeconvolutionError):
+    """
+    Template positions or tolerance are invalid.
+    """
+
+    ...
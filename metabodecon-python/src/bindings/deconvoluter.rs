@@ -1,6 +1,7 @@
 use crate::bindings::{Deconvolution, Spectrum};
 use crate::error::{MetabodeconError, ThreadPoolError};
 use metabodecon::deconvolution;
+use numpy::PyArray1;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use rayon::{ThreadPool, ThreadPoolBuilder};
@@ -56,11 +57,23 @@ impl Deconvoluter {
         }
     }
 
-    pub(crate) fn set_noise_score_selector(&mut self, threshold: f64) -> PyResult<()> {
+    #[pyo3(signature = (threshold, max_peaks = None, robust = false))]
+    pub(crate) fn set_noise_score_selector(
+        &mut self,
+        threshold: f64,
+        max_peaks: Option<usize>,
+        robust: bool,
+    ) -> PyResult<()> {
+        let noise_estimator = match robust {
+            true => deconvolution::NoiseEstimator::Mad,
+            false => deconvolution::NoiseEstimator::StdDev,
+        };
         match self.inner.set_selection_settings(
             deconvolution::SelectionSettings::NoiseScoreFilter {
                 scoring_method: deconvolution::ScoringMethod::MinimumSum,
                 threshold,
+                max_peaks,
+                noise_estimator,
             },
         ) {
             Ok(_) => Ok(()),
@@ -78,6 +91,16 @@ impl Deconvoluter {
         }
     }
 
+    pub(crate) fn set_uniform_mse(&mut self) {
+        self.inner
+            .set_mse_weighting(deconvolution::MseWeighting::Uniform);
+    }
+
+    pub(crate) fn set_intensity_weighted_mse(&mut self) {
+        self.inner
+            .set_mse_weighting(deconvolution::MseWeighting::IntensityWeighted);
+    }
+
     pub(crate) fn add_ignore_region(&mut self, new: (f64, f64)) -> PyResult<()> {
         match self.inner.add_ignore_region(new) {
             Ok(_) => Ok(()),
@@ -89,6 +112,28 @@ impl Deconvoluter {
         self.inner.clear_ignore_regions();
     }
 
+    pub(crate) fn add_boost_region(&mut self, region: (f64, f64), factor: f64) -> PyResult<()> {
+        match self.inner.add_boost_region(region, factor) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
+    pub(crate) fn clear_boost_regions(&mut self) {
+        self.inner.clear_boost_regions();
+    }
+
+    pub(crate) fn set_noise_region(&mut self, region: (f64, f64)) -> PyResult<()> {
+        match self.inner.set_noise_region(region) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
+    pub(crate) fn clear_noise_region(&mut self) {
+        self.inner.clear_noise_region();
+    }
+
     pub(crate) fn set_threads(&mut self, threads: usize) -> PyResult<()> {
         if threads <= 1 {
             return Err(PyValueError::new_err(
@@ -133,6 +178,28 @@ impl Deconvoluter {
         }
     }
 
+    pub(crate) fn refine(
+        &self,
+        spectrum: &Spectrum,
+        previous: &Deconvolution,
+    ) -> PyResult<Deconvolution> {
+        match self
+            .inner
+            .refine(spectrum.as_ref(), previous.as_ref())
+        {
+            Ok(deconvolution) => Ok(deconvolution.into()),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
+    pub(crate) fn smoothed_intensities<'py>(
+        &self,
+        py: Python<'py>,
+        spectrum: &Spectrum,
+    ) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_vec(py, self.inner.smoothed_intensities(spectrum.as_ref()))
+    }
+
     pub(crate) fn deconvolute_spectra(
         &self,
         spectra: Vec<Spectrum>,
@@ -170,4 +237,18 @@ impl Deconvoluter {
             Err(error) => Err(MetabodeconError::from(error).into()),
         }
     }
+
+    pub(crate) fn optimize_settings_in_region(
+        &mut self,
+        reference: &Spectrum,
+        boundaries: (f64, f64),
+    ) -> PyResult<f64> {
+        match self
+            .inner
+            .optimize_settings_in_region(reference.as_ref(), boundaries)
+        {
+            Ok(mse) => Ok(mse),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
 }
@@ -1,7 +1,8 @@
-use crate::bindings::Lorentzian;
-use crate::error::SerializationError;
+use crate::bindings::{Lorentzian, Spectrum};
+use crate::error::{MetabodeconError, SerializationError};
 use metabodecon::deconvolution;
 use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyIndexError;
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 
@@ -42,8 +43,73 @@ impl Deconvolution {
         self.inner.mse()
     }
 
+    #[getter]
+    pub(crate) fn mse_weighting(&self) -> String {
+        self.inner.mse_weighting().to_string()
+    }
+
+    #[getter]
+    pub(crate) fn noise_estimate(&self) -> Option<f64> {
+        self.inner.noise_estimate()
+    }
+
+    #[getter]
+    pub(crate) fn parameter_uncertainties(&self) -> Option<Vec<(f64, f64, f64)>> {
+        self.inner
+            .parameter_uncertainties()
+            .map(|uncertainties| {
+                uncertainties
+                    .iter()
+                    .map(|uncertainty| (uncertainty.sf(), uncertainty.hw(), uncertainty.maxp()))
+                    .collect()
+            })
+    }
+
+    #[getter]
+    pub(crate) fn labels(&self) -> Vec<Option<String>> {
+        self.inner.labels().to_vec()
+    }
+
+    #[getter]
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.inner
+            .warnings()
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect()
+    }
+
+    pub(crate) fn label_peak(&mut self, index: usize, name: String) -> PyResult<()> {
+        if index >= self.inner.len() {
+            return Err(PyIndexError::new_err(format!(
+                "index {} is out of bounds for {} peaks",
+                index,
+                self.inner.len()
+            )));
+        }
+        self.inner.label_peak(index, name);
+
+        Ok(())
+    }
+
+    pub(crate) fn label_by_position(
+        &mut self,
+        ppm: f64,
+        tolerance: f64,
+        name: String,
+    ) -> Option<usize> {
+        self.inner.label_by_position(ppm, tolerance, name)
+    }
+
+    pub(crate) fn validate(&self) -> PyResult<()> {
+        match self.inner.validate() {
+            Ok(_) => Ok(()),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
     pub(crate) fn superposition(&self, chemical_shift: f64) -> f64 {
-        deconvolution::Lorentzian::superposition(chemical_shift, self.inner.lorentzians())
+        self.inner.superposition_vec(&[chemical_shift])[0]
     }
 
     pub(crate) fn superposition_vec<'py>(
@@ -53,10 +119,9 @@ impl Deconvolution {
     ) -> Bound<'py, PyArray1<f64>> {
         PyArray1::from_slice(
             py,
-            &deconvolution::Lorentzian::superposition_vec(
-                chemical_shifts.as_slice().unwrap(),
-                self.inner.lorentzians(),
-            ),
+            &self
+                .inner
+                .superposition_vec(chemical_shifts.as_slice().unwrap()),
         )
     }
 
@@ -67,13 +132,64 @@ impl Deconvolution {
     ) -> Bound<'py, PyArray1<f64>> {
         PyArray1::from_slice(
             py,
-            &deconvolution::Lorentzian::par_superposition_vec(
-                chemical_shifts.as_slice().unwrap(),
-                self.inner.lorentzians(),
-            ),
+            &self
+                .inner
+                .par_superposition_vec(chemical_shifts.as_slice().unwrap()),
         )
     }
 
+    pub(crate) fn component_curves<'py>(
+        &self,
+        py: Python<'py>,
+        chemical_shifts: PyReadonlyArray1<'_, f64>,
+    ) -> Vec<Bound<'py, PyArray1<f64>>> {
+        self.inner
+            .component_curves(chemical_shifts.as_slice().unwrap())
+            .into_iter()
+            .map(|component| PyArray1::from_vec(py, component))
+            .collect()
+    }
+
+    pub(crate) fn par_component_curves<'py>(
+        &self,
+        py: Python<'py>,
+        chemical_shifts: PyReadonlyArray1<'_, f64>,
+    ) -> Vec<Bound<'py, PyArray1<f64>>> {
+        self.inner
+            .par_component_curves(chemical_shifts.as_slice().unwrap())
+            .into_iter()
+            .map(|component| PyArray1::from_vec(py, component))
+            .collect()
+    }
+
+    pub(crate) fn summary_string(&self, top_n: usize) -> String {
+        self.inner.summary_string(top_n)
+    }
+
+    pub(crate) fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    pub(crate) fn adjacent_overlaps(&self) -> Option<Vec<f64>> {
+        self.inner.adjacent_overlaps()
+    }
+
+    pub(crate) fn merge_close_peaks(&self, min_separation: f64) -> Deconvolution {
+        self.inner
+            .merge_close_peaks(min_separation)
+            .into()
+    }
+
+    pub(crate) fn merge_close_peaks_with_mse(
+        &self,
+        spectrum: &Spectrum,
+        min_separation: f64,
+    ) -> Deconvolution {
+        self.inner
+            .merge_close_peaks_with_mse(spectrum.as_ref(), min_separation)
+            .into()
+    }
+
     pub(crate) fn write_json(&self, path: &str) -> PyResult<()> {
         let serialized = match serde_json::to_string_pretty(self.as_ref()) {
             Ok(serialized) => serialized,
@@ -95,7 +211,7 @@ impl Deconvolution {
     }
 
     pub(crate) fn write_bin(&self, path: &str) -> PyResult<()> {
-        let serialized = match rmp_serde::to_vec(self.as_ref()) {
+        let serialized = match rmp_serde::to_vec_named(self.as_ref()) {
             Ok(serialized) => serialized,
             Err(error) => return Err(SerializationError::new_err(error.to_string())),
         };
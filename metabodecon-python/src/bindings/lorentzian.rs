@@ -66,6 +66,18 @@ impl Lorentzian {
         self.inner.set_maxp(maxp);
     }
 
+    pub(crate) fn fwhm(&self) -> f64 {
+        self.inner.fwhm()
+    }
+
+    pub(crate) fn max_intensity(&self) -> f64 {
+        self.inner.max_intensity()
+    }
+
+    pub(crate) fn value_at_half_max(&self) -> f64 {
+        self.inner.value_at_half_max()
+    }
+
     pub(crate) fn evaluate(&self, x: f64) -> f64 {
         self.inner.evaluate(x)
     }
@@ -78,10 +90,47 @@ impl Lorentzian {
         PyArray1::from_slice(py, &self.inner.evaluate_vec(x.as_slice().unwrap()))
     }
 
+    pub(crate) fn derivative(&self, x: f64) -> f64 {
+        self.inner.derivative(x)
+    }
+
+    pub(crate) fn derivative_vec<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<'_, f64>,
+    ) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.inner.derivative_vec(x.as_slice().unwrap()))
+    }
+
+    pub(crate) fn second_derivative(&self, x: f64) -> f64 {
+        self.inner.second_derivative(x)
+    }
+
+    pub(crate) fn second_derivative_vec<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<'_, f64>,
+    ) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(
+            py,
+            &self
+                .inner
+                .second_derivative_vec(x.as_slice().unwrap()),
+        )
+    }
+
     pub(crate) fn integral(&self) -> f64 {
         self.inner.integral()
     }
 
+    pub(crate) fn integral_between(&self, a: f64, b: f64) -> f64 {
+        self.inner.integral_between(a, b)
+    }
+
+    pub(crate) fn overlap(&self, other: Lorentzian) -> f64 {
+        self.inner.overlap(&other.inner)
+    }
+
     #[staticmethod]
     pub(crate) fn superposition(x: f64, lorentzians: Vec<Lorentzian>) -> f64 {
         deconvolution::Lorentzian::superposition(x, &lorentzians)
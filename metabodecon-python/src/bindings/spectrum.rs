@@ -37,6 +37,18 @@ impl Spectrum {
         }
     }
 
+    #[staticmethod]
+    pub(crate) fn from_nonuniform(
+        chemical_shifts: Vec<f64>,
+        intensities: Vec<f64>,
+        signal_boundaries: (f64, f64),
+    ) -> PyResult<Self> {
+        match spectrum::Spectrum::from_nonuniform(chemical_shifts, intensities, signal_boundaries) {
+            Ok(spectrum) => Ok(spectrum.into()),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
     #[staticmethod]
     pub(crate) fn read_bruker(
         path: &str,
@@ -50,6 +62,40 @@ impl Spectrum {
         }
     }
 
+    #[staticmethod]
+    pub(crate) fn read_bruker_complex<'py>(
+        py: Python<'py>,
+        path: &str,
+        experiment: u32,
+        processing: u32,
+        signal_boundaries: (f64, f64),
+    ) -> PyResult<(Self, Option<Bound<'py, PyArray1<f64>>>)> {
+        match spectrum::Bruker::read_spectrum_complex(
+            path,
+            experiment,
+            processing,
+            signal_boundaries,
+        ) {
+            Ok((spectrum, imaginary)) => Ok((
+                spectrum.into(),
+                imaginary.map(|imaginary| PyArray1::from_vec(py, imaginary)),
+            )),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
+    #[staticmethod]
+    pub(crate) fn read_bruker_any(
+        path: &str,
+        experiment: u32,
+        signal_boundaries: (f64, f64),
+    ) -> PyResult<Self> {
+        match spectrum::Bruker::read_spectrum_any(path, experiment, signal_boundaries) {
+            Ok(spectrum) => Ok(spectrum.into()),
+            Err(error) => Err(MetabodeconError::from(error).into()),
+        }
+    }
+
     #[staticmethod]
     pub(crate) fn read_bruker_set(
         path: &str,
@@ -103,6 +149,25 @@ impl Spectrum {
         self.inner.signal_boundaries()
     }
 
+    #[getter]
+    pub(crate) fn chemical_shifts_hz<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        match self.inner.chemical_shifts_hz() {
+            Ok(chemical_shifts) => Ok(PyArray1::from_vec(py, chemical_shifts)),
+            Err(e) => Err(MetabodeconError::from(e).into()),
+        }
+    }
+
+    #[getter]
+    pub(crate) fn signal_boundaries_hz(&self) -> PyResult<(f64, f64)> {
+        match self.inner.signal_boundaries_hz() {
+            Ok(signal_boundaries) => Ok(signal_boundaries),
+            Err(e) => Err(MetabodeconError::from(e).into()),
+        }
+    }
+
     #[getter]
     pub(crate) fn nucleus(&self) -> String {
         self.inner.nucleus().to_string()
@@ -113,6 +178,21 @@ impl Spectrum {
         self.inner.frequency()
     }
 
+    #[getter]
+    pub(crate) fn sample_name(&self) -> Option<String> {
+        self.inner.sample_name().map(str::to_string)
+    }
+
+    #[getter]
+    pub(crate) fn acquisition_date(&self) -> Option<String> {
+        self.inner.acquisition_date().map(str::to_string)
+    }
+
+    #[getter]
+    pub(crate) fn description(&self) -> Option<String> {
+        self.inner.description().map(str::to_string)
+    }
+
     #[getter]
     pub(crate) fn reference_compound<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
         let dict = PyDict::new(py);
@@ -142,6 +222,20 @@ impl Spectrum {
         }
     }
 
+    #[setter]
+    pub(crate) fn set_signal_boundaries_hz(
+        &mut self,
+        signal_boundaries: (f64, f64),
+    ) -> PyResult<()> {
+        match self
+            .inner
+            .set_signal_boundaries_hz(signal_boundaries)
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(MetabodeconError::from(e).into()),
+        }
+    }
+
     #[setter]
     pub(crate) fn set_nucleus(&mut self, nucleus: &str) {
         self.inner.set_nucleus(nucleus);
@@ -152,6 +246,21 @@ impl Spectrum {
         self.inner.set_frequency(frequency);
     }
 
+    #[setter]
+    pub(crate) fn set_sample_name(&mut self, sample_name: Option<&str>) {
+        self.inner.set_sample_name(sample_name);
+    }
+
+    #[setter]
+    pub(crate) fn set_acquisition_date(&mut self, acquisition_date: Option<&str>) {
+        self.inner.set_acquisition_date(acquisition_date);
+    }
+
+    #[setter]
+    pub(crate) fn set_description(&mut self, description: Option<&str>) {
+        self.inner.set_description(description);
+    }
+
     #[setter]
     pub(crate) fn set_reference_compound(&mut self, reference: Bound<'_, PyDict>) -> PyResult<()> {
         let reference = reference.as_any();
@@ -191,6 +300,33 @@ impl Spectrum {
         Ok(())
     }
 
+    #[pyo3(signature = (search_range, target_shift, name = None))]
+    pub(crate) fn calibrate_to_peak(
+        &mut self,
+        search_range: (f64, f64),
+        target_shift: f64,
+        name: Option<&str>,
+    ) -> PyResult<()> {
+        match self
+            .inner
+            .calibrate_to_peak(search_range, target_shift, name)
+        {
+            Ok(_) => Ok(()),
+            Err(e) => Err(MetabodeconError::from(e).into()),
+        }
+    }
+
+    pub(crate) fn in_hz(&self) -> PyResult<Self> {
+        match self.inner.in_hz() {
+            Ok(spectrum) => Ok(spectrum.into()),
+            Err(e) => Err(MetabodeconError::from(e).into()),
+        }
+    }
+
+    pub(crate) fn reversed(&self) -> Self {
+        self.inner.reversed().into()
+    }
+
     pub(crate) fn write_json(&self, path: &str) -> PyResult<()> {
         let serialized = match serde_json::to_string_pretty(self.as_ref()) {
             Ok(serialized) => serialized,
@@ -13,19 +13,44 @@ create_exception!(metabodecon, DataLengthMismatch, SpectrumError);
 create_exception!(metabodecon, NonUniformSpacing, SpectrumError);
 create_exception!(metabodecon, InvalidIntensities, SpectrumError);
 create_exception!(metabodecon, InvalidSignalBoundaries, SpectrumError);
+create_exception!(metabodecon, InvalidCropRange, SpectrumError);
+create_exception!(metabodecon, InvalidResampling, SpectrumError);
+create_exception!(metabodecon, InvalidBucketWidth, SpectrumError);
+create_exception!(metabodecon, InvalidNormalization, SpectrumError);
+create_exception!(metabodecon, InvalidPhaseCorrection, SpectrumError);
+create_exception!(metabodecon, InvalidNoiseMultiple, SpectrumError);
+create_exception!(metabodecon, NoSignalRegionDetected, SpectrumError);
+create_exception!(metabodecon, InvalidNoiseRange, SpectrumError);
+create_exception!(metabodecon, InvalidCalibration, SpectrumError);
+create_exception!(metabodecon, InvalidWindowFunction, SpectrumError);
+create_exception!(metabodecon, InvalidFrequency, SpectrumError);
+create_exception!(metabodecon, GridMismatch, SpectrumError);
+create_exception!(metabodecon, InvalidDecimation, SpectrumError);
 create_exception!(metabodecon, MissingMetadata, SpectrumError);
 create_exception!(metabodecon, MalformedMetadata, SpectrumError);
 create_exception!(metabodecon, MissingData, SpectrumError);
 create_exception!(metabodecon, MalformedData, SpectrumError);
+create_exception!(metabodecon, UnsupportedJcampDxFile, SpectrumError);
+create_exception!(metabodecon, UntransformableFid, SpectrumError);
 
 create_exception!(metabodecon, DeconvolutionError, Error);
+create_exception!(metabodecon, InvalidBaselineSettings, DeconvolutionError);
 create_exception!(metabodecon, InvalidSmoothingSettings, DeconvolutionError);
 create_exception!(metabodecon, InvalidSelectionSettings, DeconvolutionError);
 create_exception!(metabodecon, InvalidFittingSettings, DeconvolutionError);
 create_exception!(metabodecon, InvalidIgnoreRegion, DeconvolutionError);
+create_exception!(metabodecon, InvalidNoiseRegion, DeconvolutionError);
+create_exception!(metabodecon, InvalidBoostRegion, DeconvolutionError);
+create_exception!(metabodecon, InvalidDeconvolutionRegion, DeconvolutionError);
+create_exception!(metabodecon, InvalidRegionUpdate, DeconvolutionError);
+create_exception!(metabodecon, EmptyOptimizationGrid, DeconvolutionError);
+create_exception!(metabodecon, InvalidSignal, DeconvolutionError);
 create_exception!(metabodecon, NoPeaksDetected, DeconvolutionError);
 create_exception!(metabodecon, EmptySignalRegion, DeconvolutionError);
 create_exception!(metabodecon, EmptySignalFreeRegion, DeconvolutionError);
+create_exception!(metabodecon, Cancelled, DeconvolutionError);
+create_exception!(metabodecon, UnsupportedRefinement, DeconvolutionError);
+create_exception!(metabodecon, InvalidTemplate, DeconvolutionError);
 
 pub(crate) struct MetabodeconError(metabodecon::Error);
 
@@ -61,40 +86,108 @@ impl From<MetabodeconError> for PyErr {
                 SpecErrKind::InvalidSignalBoundaries { .. } => {
                     InvalidSignalBoundaries::new_err(inner.to_string())
                 }
+                SpecErrKind::InvalidCropRange { .. } => {
+                    InvalidCropRange::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidResampling { .. } => {
+                    InvalidResampling::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidBucketWidth { .. } => {
+                    InvalidBucketWidth::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidNormalization { .. } => {
+                    InvalidNormalization::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidPhaseCorrection { .. } => {
+                    InvalidPhaseCorrection::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidNoiseMultiple { .. } => {
+                    InvalidNoiseMultiple::new_err(inner.to_string())
+                }
+                SpecErrKind::NoSignalRegionDetected { .. } => {
+                    NoSignalRegionDetected::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidNoiseRange { .. } => {
+                    InvalidNoiseRange::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidCalibration { .. } => {
+                    InvalidCalibration::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidWindowFunction { .. } => {
+                    InvalidWindowFunction::new_err(inner.to_string())
+                }
+                SpecErrKind::InvalidFrequency { .. } => {
+                    InvalidFrequency::new_err(inner.to_string())
+                }
+                SpecErrKind::GridMismatch { .. } => GridMismatch::new_err(inner.to_string()),
+                SpecErrKind::InvalidDecimation { .. } => {
+                    InvalidDecimation::new_err(inner.to_string())
+                }
                 SpecErrKind::MissingMetadata { .. } => MissingMetadata::new_err(inner.to_string()),
                 SpecErrKind::MalformedMetadata { .. } => {
                     MalformedMetadata::new_err(inner.to_string())
                 }
-                SpecErrKind::MissingData { .. } => MissingData::new_err(inner.to_string()),
-                SpecErrKind::MalformedData { .. } => MalformedData::new_err(inner.to_string()),
-                _ => UnexpectedError::new_err(format!("unexpected error: {}", value)),
-            },
-            metabodecon::Error::Deconvolution(ref inner) => match inner.kind() {
-                DecErrKind::InvalidSmoothingSettings { .. } => {
-                    InvalidSmoothingSettings::new_err(inner.to_string())
-                }
-                DecErrKind::InvalidSelectionSettings { .. } => {
-                    InvalidSelectionSettings::new_err(inner.to_string())
+                SpecErrKind::MissingData { .. } | SpecErrKind::NoReadableProcessing { .. } => {
+                    MissingData::new_err(inner.to_string())
                 }
-                DecErrKind::InvalidFittingSettings { .. } => {
-                    InvalidFittingSettings::new_err(inner.to_string())
+                SpecErrKind::MalformedData { .. } | SpecErrKind::MalformedLine { .. } => {
+                    MalformedData::new_err(inner.to_string())
                 }
-                DecErrKind::InvalidIgnoreRegion { .. } => {
-                    InvalidIgnoreRegion::new_err(inner.to_string())
+                SpecErrKind::UnsupportedJcampDxFile => {
+                    UnsupportedJcampDxFile::new_err(inner.to_string())
                 }
-                DecErrKind::NoPeaksDetected => NoPeaksDetected::new_err(inner.to_string()),
-                DecErrKind::EmptySignalRegion => EmptySignalRegion::new_err(inner.to_string()),
-                DecErrKind::EmptySignalFreeRegion => {
-                    EmptySignalFreeRegion::new_err(inner.to_string())
+                SpecErrKind::UntransformableFid { .. } => {
+                    UntransformableFid::new_err(inner.to_string())
                 }
                 _ => UnexpectedError::new_err(format!("unexpected error: {}", value)),
             },
+            metabodecon::Error::Deconvolution(ref inner) => match inner.kind() {
+                DecErrKind::SpectrumInBatch { source, .. } => {
+                    deconvolution_error(source.kind(), inner.to_string())
+                }
+                kind => deconvolution_error(kind, inner.to_string()),
+            },
             metabodecon::Error::IoError(inner) => PyIOError::new_err(inner.to_string()),
+            metabodecon::Error::MessagePackEncode(inner) => {
+                SerializationError::new_err(inner.to_string())
+            }
+            metabodecon::Error::MessagePackDecode(inner) => {
+                SerializationError::new_err(inner.to_string())
+            }
             _ => UnexpectedError::new_err(format!("unexpected error: {}", value)),
         }
     }
 }
 
+/// Internal helper function to convert a deconvolution error to the matching
+/// Python exception with the given message.
+fn deconvolution_error(kind: &metabodecon::deconvolution::error::Kind, message: String) -> PyErr {
+    use metabodecon::deconvolution::error::Kind as DecErrKind;
+
+    match kind {
+        DecErrKind::InvalidBaselineSettings { .. } => InvalidBaselineSettings::new_err(message),
+        DecErrKind::InvalidSmoothingSettings { .. } => InvalidSmoothingSettings::new_err(message),
+        DecErrKind::InvalidSelectionSettings { .. } => InvalidSelectionSettings::new_err(message),
+        DecErrKind::InvalidFittingSettings { .. } => InvalidFittingSettings::new_err(message),
+        DecErrKind::InvalidIgnoreRegion { .. } => InvalidIgnoreRegion::new_err(message),
+        DecErrKind::InvalidNoiseRegion { .. } => InvalidNoiseRegion::new_err(message),
+        DecErrKind::InvalidBoostRegion { .. } => InvalidBoostRegion::new_err(message),
+        DecErrKind::InvalidDeconvolutionRegion { .. } => {
+            InvalidDeconvolutionRegion::new_err(message)
+        }
+        DecErrKind::InvalidRegionUpdate { .. } => InvalidRegionUpdate::new_err(message),
+        DecErrKind::EmptyOptimizationGrid => EmptyOptimizationGrid::new_err(message),
+        DecErrKind::InvalidSignal { .. } => InvalidSignal::new_err(message),
+        DecErrKind::NoPeaksDetected => NoPeaksDetected::new_err(message),
+        DecErrKind::EmptySignalRegion => EmptySignalRegion::new_err(message),
+        DecErrKind::EmptySignalFreeRegion => EmptySignalFreeRegion::new_err(message),
+        DecErrKind::Cancelled => Cancelled::new_err(message),
+        DecErrKind::UnsupportedRefinement { .. } => UnsupportedRefinement::new_err(message),
+        DecErrKind::InvalidTemplate { .. } => InvalidTemplate::new_err(message),
+        _ => UnexpectedError::new_err(format!("unexpected error: {}", message)),
+    }
+}
+
 pub(crate) fn error_module(py: Python) -> PyResult<Bound<PyModule>> {
     let exceptions = PyModule::new(py, "exceptions")?;
     exceptions.add("Error", py.get_type::<Error>())?;
@@ -110,11 +203,48 @@ pub(crate) fn error_module(py: Python) -> PyResult<Bound<PyModule>> {
         "InvalidSignalBoundaries",
         py.get_type::<InvalidSignalBoundaries>(),
     )?;
+    exceptions.add("InvalidCropRange", py.get_type::<InvalidCropRange>())?;
+    exceptions.add("InvalidResampling", py.get_type::<InvalidResampling>())?;
+    exceptions.add("InvalidBucketWidth", py.get_type::<InvalidBucketWidth>())?;
+    exceptions.add(
+        "InvalidNormalization",
+        py.get_type::<InvalidNormalization>(),
+    )?;
+    exceptions.add(
+        "InvalidPhaseCorrection",
+        py.get_type::<InvalidPhaseCorrection>(),
+    )?;
+    exceptions.add(
+        "InvalidNoiseMultiple",
+        py.get_type::<InvalidNoiseMultiple>(),
+    )?;
+    exceptions.add(
+        "NoSignalRegionDetected",
+        py.get_type::<NoSignalRegionDetected>(),
+    )?;
+    exceptions.add("InvalidNoiseRange", py.get_type::<InvalidNoiseRange>())?;
+    exceptions.add("InvalidCalibration", py.get_type::<InvalidCalibration>())?;
+    exceptions.add(
+        "InvalidWindowFunction",
+        py.get_type::<InvalidWindowFunction>(),
+    )?;
+    exceptions.add("InvalidFrequency", py.get_type::<InvalidFrequency>())?;
+    exceptions.add("GridMismatch", py.get_type::<GridMismatch>())?;
+    exceptions.add("InvalidDecimation", py.get_type::<InvalidDecimation>())?;
     exceptions.add("MissingMetadata", py.get_type::<MissingMetadata>())?;
     exceptions.add("MalformedMetadata", py.get_type::<MalformedMetadata>())?;
     exceptions.add("MissingData", py.get_type::<MissingData>())?;
     exceptions.add("MalformedData", py.get_type::<MalformedData>())?;
+    exceptions.add(
+        "UnsupportedJcampDxFile",
+        py.get_type::<UnsupportedJcampDxFile>(),
+    )?;
+    exceptions.add("UntransformableFid", py.get_type::<UntransformableFid>())?;
     exceptions.add("DeconvolutionError", py.get_type::<DeconvolutionError>())?;
+    exceptions.add(
+        "InvalidBaselineSettings",
+        py.get_type::<InvalidBaselineSettings>(),
+    )?;
     exceptions.add(
         "InvalidSmoothingSettings",
         py.get_type::<InvalidSmoothingSettings>(),
@@ -128,12 +258,30 @@ pub(crate) fn error_module(py: Python) -> PyResult<Bound<PyModule>> {
         py.get_type::<InvalidFittingSettings>(),
     )?;
     exceptions.add("InvalidIgnoreRegion", py.get_type::<InvalidIgnoreRegion>())?;
+    exceptions.add("InvalidNoiseRegion", py.get_type::<InvalidNoiseRegion>())?;
+    exceptions.add("InvalidBoostRegion", py.get_type::<InvalidBoostRegion>())?;
+    exceptions.add(
+        "InvalidDeconvolutionRegion",
+        py.get_type::<InvalidDeconvolutionRegion>(),
+    )?;
+    exceptions.add("InvalidRegionUpdate", py.get_type::<InvalidRegionUpdate>())?;
+    exceptions.add(
+        "EmptyOptimizationGrid",
+        py.get_type::<EmptyOptimizationGrid>(),
+    )?;
+    exceptions.add("InvalidSignal", py.get_type::<InvalidSignal>())?;
     exceptions.add("NoPeaksDetected", py.get_type::<NoPeaksDetected>())?;
     exceptions.add("EmptySignalRegion", py.get_type::<EmptySignalRegion>())?;
     exceptions.add(
         "EmptySignalFreeRegion",
         py.get_type::<EmptySignalFreeRegion>(),
     )?;
+    exceptions.add("Cancelled", py.get_type::<Cancelled>())?;
+    exceptions.add(
+        "UnsupportedRefinement",
+        py.get_type::<UnsupportedRefinement>(),
+    )?;
+    exceptions.add("InvalidTemplate", py.get_type::<InvalidTemplate>())?;
 
     Ok(exceptions)
 }
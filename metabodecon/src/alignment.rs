@@ -0,0 +1,67 @@
+//! Alignment of the deconvolutions of multiple spectra.
+//!
+//! # Key Components
+//!
+//! - [`Aligner`]: Configuration for the alignment algorithm.
+//! - [`Alignment`]: Table of aligned signal areas, the result of the algorithm.
+//!
+//! # The Algorithm
+//!
+//! The positions of the same signal in different spectra usually vary slightly,
+//! for example due to differences in pH or temperature. To make the
+//! deconvolutions of multiple spectra comparable, the signals of all
+//! [`Deconvolution`]s are sorted by their position and grouped into clusters,
+//! where each signal that is within the tolerance of its predecessor joins the
+//! cluster of the predecessor. Each cluster corresponds to one consensus
+//! signal, whose position is the mean position of the signals within it. The
+//! result is a table of signal areas, with one row per spectrum and one column
+//! per consensus signal.
+//!
+//! Alternatively, the spectra themselves can be aligned to a reference spectrum
+//! before the deconvolution, by shifting segments of their intensities such
+//! that the correlation with the reference is maximized, similar to the
+//! [icoshift] algorithm.
+//!
+//! [`Deconvolution`]: crate::deconvolution::Deconvolution
+//! [icoshift]: https://doi.org/10.1016/j.jmr.2009.11.012
+//!
+//! # Example: Aligning Deconvolutions
+//!
+//! ```
+//! use metabodecon::alignment::Aligner;
+//! use metabodecon::deconvolution::Deconvoluter;
+//! use metabodecon::spectrum::Bruker;
+//!
+//! # fn main() -> metabodecon::Result<()> {
+//! // Read all spectra from Bruker TopSpin format directories within the root.
+//! let path = "path/to/root";
+//! # let path = "../data/bruker/sim";
+//! let spectra = Bruker::read_spectra(
+//!     path,
+//!     // Experiment number
+//!     10,
+//!     // Processing number
+//!     10,
+//!     // Signal boundaries
+//!     (3.339, 3.553),
+//! )?;
+//!
+//! // Deconvolute the spectra.
+//! let deconvoluter = Deconvoluter::default();
+//! let deconvolutions = deconvoluter.deconvolute_spectra(&spectra)?;
+//!
+//! // Align the signals that are within 0.005 ppm of each other.
+//! let aligner = Aligner::new(0.005)?;
+//! let alignment = aligner.align_deconvolutions(&deconvolutions);
+//! assert_eq!(alignment.sample_count(), spectra.len());
+//! # Ok(())
+//! # }
+//! ```
+
+mod aligner;
+pub use aligner::Aligner;
+
+mod alignment;
+pub use alignment::Alignment;
+
+pub mod error;
@@ -0,0 +1,608 @@
+use crate::Result;
+use crate::alignment::Alignment;
+use crate::alignment::error::{Error, Kind};
+use crate::deconvolution::{Deconvolution, PeakShape};
+use crate::spectrum::Spectrum;
+
+/// Alignment algorithms that make multiple spectra or their deconvolutions
+/// comparable.
+///
+/// # Aligning Deconvolutions
+///
+/// [`Aligner::align_deconvolutions`] combines the deconvolutions of multiple
+/// spectra into a common table of signal areas. The signals of all
+/// [`Deconvolution`]s are sorted by their position and grouped into clusters,
+/// where each signal that is within the tolerance (in ppm) of its predecessor
+/// joins the cluster of the predecessor. Each cluster becomes a column of the
+/// resulting [`Alignment`], positioned at the mean position of its signals. If
+/// a spectrum has multiple signals within the same cluster, their areas are
+/// summed. If it has none, the area is 0.
+///
+/// # Aligning Spectra
+///
+/// [`Aligner::align_spectra`] corrects small shifts of the signals between
+/// spectra before deconvolution, similar to the [icoshift] algorithm. Each
+/// spectrum is divided into the configured number of segments, and each
+/// segment is shifted by up to the configured maximum number of data points,
+/// such that its correlation with the same segment of a reference spectrum is
+/// maximized.
+///
+/// [icoshift]: https://doi.org/10.1016/j.jmr.2009.11.012
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::alignment::Aligner;
+/// use metabodecon::deconvolution::{
+///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+/// };
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let deconvolution = |positions: &[f64]| {
+///     Deconvolution::new(
+///         positions
+///             .iter()
+///             .map(|position| Lorentzian::new(0.045, 0.0225, *position))
+///             .collect(),
+///         SmoothingSettings::default(),
+///         SelectionSettings::default(),
+///         FittingSettings::default(),
+///         0.0,
+///     )
+/// };
+/// let deconvolutions = [
+///     deconvolution(&[3.0, 5.0]),
+///     deconvolution(&[3.002, 7.0]),
+/// ];
+/// let aligner = Aligner::new(0.01)?;
+/// let alignment = aligner.align_deconvolutions(&deconvolutions);
+///
+/// assert_eq!(alignment.peak_count(), 3);
+/// assert_eq!(alignment.area(1, 1), 0.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Aligner {
+    /// Maximum distance between neighboring signals within a cluster.
+    tolerance: f64,
+    /// Number of segments the spectra are divided into.
+    segments: usize,
+    /// Maximum shift of a segment in data points.
+    max_shift: usize,
+}
+
+impl Default for Aligner {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.01,
+            segments: 1,
+            max_shift: 100,
+        }
+    }
+}
+
+impl Aligner {
+    /// Constructs a new `Aligner` with the given clustering tolerance in ppm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidTolerance`](Kind::InvalidTolerance) error if the
+    /// tolerance is not finite and positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::alignment::Aligner;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let aligner = Aligner::new(0.005)?;
+    ///
+    /// assert_eq!(aligner.tolerance(), 0.005);
+    /// assert!(Aligner::new(0.0).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(tolerance: f64) -> Result<Self> {
+        Self::validate_tolerance(tolerance)?;
+
+        Ok(Self {
+            tolerance,
+            ..Self::default()
+        })
+    }
+
+    /// Returns the clustering tolerance in ppm.
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Sets the clustering tolerance in ppm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidTolerance`](Kind::InvalidTolerance) error if the
+    /// tolerance is not finite and positive.
+    pub fn set_tolerance(&mut self, tolerance: f64) -> Result<()> {
+        Self::validate_tolerance(tolerance)?;
+        self.tolerance = tolerance;
+
+        Ok(())
+    }
+
+    /// Returns the number of segments the spectra are divided into for
+    /// [`align_spectra`](Aligner::align_spectra).
+    pub fn segments(&self) -> usize {
+        self.segments
+    }
+
+    /// Sets the number of segments the spectra are divided into for
+    /// [`align_spectra`](Aligner::align_spectra).
+    ///
+    /// The segments are of equal size, up to rounding. If a spectrum has fewer
+    /// data points than segments, each data point forms its own segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidSegmentCount`](Kind::InvalidSegmentCount) error if
+    /// the number of segments is 0.
+    pub fn set_segments(&mut self, segments: usize) -> Result<()> {
+        if segments == 0 {
+            return Err(Error::new(Kind::InvalidSegmentCount { segments }).into());
+        }
+        self.segments = segments;
+
+        Ok(())
+    }
+
+    /// Returns the maximum shift of a segment in data points for
+    /// [`align_spectra`](Aligner::align_spectra).
+    pub fn max_shift(&self) -> usize {
+        self.max_shift
+    }
+
+    /// Sets the maximum shift of a segment in data points for
+    /// [`align_spectra`](Aligner::align_spectra).
+    pub fn set_max_shift(&mut self, max_shift: usize) {
+        self.max_shift = max_shift;
+    }
+
+    /// Aligns the signals of the given deconvolutions into a common table of
+    /// signal areas.
+    ///
+    /// The rows of the resulting [`Alignment`] are in the order of the
+    /// deconvolutions, and the columns are sorted by their position. Signals
+    /// of any peak shape are supported.
+    pub fn align_deconvolutions<D: AsRef<Deconvolution>>(&self, deconvolutions: &[D]) -> Alignment {
+        let mut signals = deconvolutions
+            .iter()
+            .enumerate()
+            .flat_map(|(sample, deconvolution)| {
+                let deconvolution = deconvolution.as_ref();
+                deconvolution
+                    .signals()
+                    .iter()
+                    .map(|signal| signal.maxp())
+                    .zip(deconvolution.signal_areas())
+                    .map(move |(position, area)| (position, sample, area))
+            })
+            .collect::<Vec<(f64, usize, f64)>>();
+        signals.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut clusters: Vec<Vec<(f64, usize, f64)>> = Vec::new();
+        for signal in signals {
+            match clusters.last_mut() {
+                Some(cluster) if signal.0 - cluster.last().unwrap().0 <= self.tolerance => {
+                    cluster.push(signal)
+                }
+                _ => clusters.push(vec![signal]),
+            }
+        }
+        let positions = clusters
+            .iter()
+            .map(|cluster| {
+                cluster
+                    .iter()
+                    .map(|(position, _, _)| position)
+                    .sum::<f64>()
+                    / cluster.len() as f64
+            })
+            .collect::<Vec<f64>>();
+        let mut areas = vec![0.0; deconvolutions.len() * clusters.len()];
+        clusters
+            .iter()
+            .enumerate()
+            .for_each(|(peak, cluster)| {
+                cluster
+                    .iter()
+                    .for_each(|(_, sample, area)| areas[sample * clusters.len() + peak] += area);
+            });
+
+        Alignment::new(positions, areas, deconvolutions.len())
+    }
+
+    /// Aligns the given spectra to the reference spectrum by shifting segments
+    /// of their intensities.
+    ///
+    /// Each spectrum is divided into segments, and each segment is shifted by
+    /// the number of data points, up to the maximum shift, that maximizes the
+    /// Pearson correlation with the same segment of the reference. Among equal
+    /// correlations, the smallest shift is chosen. The shifted intensities are
+    /// taken from the neighboring data points of the spectrum, and from the
+    /// first or last data point at the ends of the spectrum, such that the
+    /// aligned spectra keep the chemical shifts and metadata of the original
+    /// spectra and only contain finite intensities.
+    ///
+    /// The spectra are expected to be sampled at the same chemical shifts as
+    /// the reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IncompatibleSpectrum`](Kind::IncompatibleSpectrum) error
+    /// if a spectrum doesn't have the same number of data points as the
+    /// reference.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::alignment::Aligner;
+    /// use metabodecon::deconvolution::Lorentzian;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let chemical_shifts = (0..1000)
+    ///     .map(|i| i as f64 * 0.01)
+    ///     .collect::<Vec<f64>>();
+    /// let spectrum = |position: f64| {
+    ///     let signal = Lorentzian::new(0.01, 0.0025, position);
+    ///     let intensities = signal.evaluate_vec(&chemical_shifts);
+    ///     Spectrum::new(chemical_shifts.clone(), intensities, (1.0, 9.0))
+    /// };
+    /// let reference = spectrum(5.0)?;
+    /// let shifted = spectrum(5.1)?;
+    ///
+    /// let mut aligner = Aligner::default();
+    /// aligner.set_max_shift(20);
+    /// let aligned = aligner.align_spectra(&[shifted], &reference)?;
+    ///
+    /// // The maximum is moved back to the position of the reference maximum.
+    /// let argmax = |intensities: &[f64]| {
+    ///     (0..intensities.len())
+    ///         .max_by(|a, b| intensities[*a].total_cmp(&intensities[*b]))
+    ///         .unwrap()
+    /// };
+    /// assert_eq!(argmax(aligned[0].intensities()), argmax(reference.intensities()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn align_spectra<S: AsRef<Spectrum>>(
+        &self,
+        spectra: &[S],
+        reference: &Spectrum,
+    ) -> Result<Vec<Spectrum>> {
+        let len = reference.intensities().len();
+        spectra
+            .iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                let spectrum = spectrum.as_ref();
+                let found = spectrum.intensities().len();
+                if found != len {
+                    return Err(Error::new(Kind::IncompatibleSpectrum {
+                        index,
+                        expected: len,
+                        found,
+                    })
+                    .into());
+                }
+                let segments = usize::min(self.segments, len);
+                let intensities = (0..segments)
+                    .flat_map(|segment| {
+                        let (start, end) =
+                            (segment * len / segments, (segment + 1) * len / segments);
+                        let shift = self.best_shift(
+                            &reference.intensities()[start..end],
+                            spectrum.intensities(),
+                            start,
+                        );
+                        (start..end).map(move |i| Self::shifted(spectrum.intensities(), i, shift))
+                    })
+                    .collect::<Vec<f64>>();
+
+                Ok(spectrum.with_intensities(intensities))
+            })
+            .collect()
+    }
+
+    /// Internal helper function to find the shift of the segment starting at
+    /// `start` that maximizes the correlation with the reference segment.
+    fn best_shift(&self, reference: &[f64], intensities: &[f64], start: usize) -> isize {
+        let max_shift = self.max_shift as isize;
+        let mut best = (0, f64::NEG_INFINITY);
+        (0..=max_shift)
+            .flat_map(|magnitude| match magnitude {
+                0 => vec![0],
+                _ => vec![-magnitude, magnitude],
+            })
+            .for_each(|shift| {
+                let segment = (start..start + reference.len())
+                    .map(|i| Self::shifted(intensities, i, shift))
+                    .collect::<Vec<f64>>();
+                let correlation = Self::correlation(reference, &segment);
+                if correlation > best.1 {
+                    best = (shift, correlation);
+                }
+            });
+
+        best.0
+    }
+
+    /// Internal helper function to look up the intensity that is moved to
+    /// position `i` by the given shift, clamped to the ends of the spectrum.
+    fn shifted(intensities: &[f64], i: usize, shift: isize) -> f64 {
+        let source = (i as isize - shift).clamp(0, intensities.len() as isize - 1);
+
+        intensities[source as usize]
+    }
+
+    /// Internal helper function to compute the Pearson correlation of two
+    /// sequences of the same length. Returns 0 if either is constant.
+    fn correlation(a: &[f64], b: &[f64]) -> f64 {
+        let len = a.len() as f64;
+        let mean_a = a.iter().sum::<f64>() / len;
+        let mean_b = b.iter().sum::<f64>() / len;
+        let (covariance, variance_a, variance_b) = a.iter().zip(b.iter()).fold(
+            (0.0, 0.0, 0.0),
+            |(covariance, variance_a, variance_b), (a, b)| {
+                let (da, db) = (a - mean_a, b - mean_b);
+                (
+                    covariance + da * db,
+                    variance_a + da * da,
+                    variance_b + db * db,
+                )
+            },
+        );
+        if variance_a <= 0.0 || variance_b <= 0.0 {
+            return 0.0;
+        }
+
+        covariance / (variance_a * variance_b).sqrt()
+    }
+
+    /// Internal helper function to validate the clustering tolerance.
+    fn validate_tolerance(tolerance: f64) -> Result<()> {
+        if !(tolerance.is_finite() && tolerance > 0.0) {
+            return Err(Error::new(Kind::InvalidTolerance { tolerance }).into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::{
+        FittingSettings, Gaussian, Lorentzian, SelectionSettings, SmoothingSettings,
+    };
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    fn deconvolution(signals: &[(f64, f64)]) -> Deconvolution {
+        Deconvolution::new(
+            signals
+                .iter()
+                .map(|(sf, maxp)| Lorentzian::new(sf * 0.01, 0.01_f64.powi(2), *maxp))
+                .collect(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        )
+    }
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Aligner);
+        assert_sync!(Aligner);
+    }
+
+    #[test]
+    fn invalid_tolerance() {
+        let mut aligner = Aligner::default();
+        [0.0, -0.01, f64::NAN, f64::INFINITY]
+            .into_iter()
+            .for_each(|tolerance| {
+                let errors = [
+                    Aligner::new(tolerance).unwrap_err(),
+                    aligner.set_tolerance(tolerance).unwrap_err(),
+                ];
+                errors.into_iter().for_each(|error| match error {
+                    crate::Error::Alignment(inner) => match inner.kind() {
+                        Kind::InvalidTolerance { tolerance: t } => {
+                            assert!(t.is_nan() && tolerance.is_nan() || *t == tolerance)
+                        }
+                        _ => panic!("unexpected kind: {:?}", inner),
+                    },
+                    _ => panic!("unexpected error: {:?}", error),
+                });
+            });
+        assert_approx_eq!(f64, aligner.tolerance(), 0.01);
+    }
+
+    #[test]
+    fn align_deconvolutions() {
+        let deconvolutions = [
+            deconvolution(&[(1.0, 3.0), (2.0, 5.0), (0.5, 7.0)]),
+            deconvolution(&[(1.5, 3.004), (2.5, 4.997)]),
+            deconvolution(&[(0.5, 2.998), (1.0, 5.002), (0.8, 8.0)]),
+        ];
+        let alignment = Aligner::new(0.01)
+            .unwrap()
+            .align_deconvolutions(&deconvolutions);
+        assert_eq!(alignment.sample_count(), 3);
+        assert_eq!(alignment.peak_count(), 4);
+        let expected_positions = [
+            (3.0 + 3.004 + 2.998) / 3.0,
+            (5.0 + 4.997 + 5.002) / 3.0,
+            7.0,
+            8.0,
+        ];
+        alignment
+            .positions()
+            .iter()
+            .zip(expected_positions.iter())
+            .for_each(|(position, expected)| assert_approx_eq!(f64, *position, *expected));
+        let pi = std::f64::consts::PI;
+        let expected_areas = [
+            [1.0, 2.0, 0.5, 0.0],
+            [1.5, 2.5, 0.0, 0.0],
+            [0.5, 1.0, 0.0, 0.8],
+        ];
+        expected_areas
+            .iter()
+            .enumerate()
+            .for_each(|(sample, expected)| {
+                alignment
+                    .row(sample)
+                    .iter()
+                    .zip(expected.iter())
+                    .for_each(|(area, expected)| assert_approx_eq!(f64, *area, expected * pi));
+            });
+
+        let alignment = Aligner::new(0.001)
+            .unwrap()
+            .align_deconvolutions(&deconvolutions);
+        assert_eq!(alignment.peak_count(), 8);
+    }
+
+    #[test]
+    fn align_duplicates_and_shapes() {
+        let gaussians = Deconvolution::from_gaussians(
+            vec![Gaussian::new(2.0, 0.01, 5.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.0,
+        );
+        let split = deconvolution(&[(1.0, 4.999), (1.0, 5.001)]);
+        let alignment = Aligner::default().align_deconvolutions(&[gaussians.clone(), split]);
+        assert_eq!(alignment.peak_count(), 1);
+        assert_approx_eq!(
+            f64,
+            alignment.area(0, 0),
+            gaussians.gaussians()[0].integral()
+        );
+        assert_approx_eq!(f64, alignment.area(1, 0), 2.0 * std::f64::consts::PI);
+
+        let empty = Aligner::default().align_deconvolutions::<Deconvolution>(&[]);
+        assert_eq!(empty.sample_count(), 0);
+        assert_eq!(empty.peak_count(), 0);
+    }
+
+    fn shifted_spectrum(positions: &[f64]) -> Spectrum {
+        let chemical_shifts = (0..2000)
+            .map(|i| i as f64 * 0.005)
+            .collect::<Vec<f64>>();
+        let signals = positions
+            .iter()
+            .map(|position| Lorentzian::new(0.02 * 0.02, 0.02_f64.powi(2), *position))
+            .collect::<Vec<Lorentzian>>();
+        let intensities = Lorentzian::superposition_vec(&chemical_shifts, &signals);
+        Spectrum::new(chemical_shifts, intensities, (0.5, 9.5)).unwrap()
+    }
+
+    fn argmax(values: &[f64]) -> usize {
+        (0..values.len())
+            .max_by(|a, b| values[*a].total_cmp(&values[*b]))
+            .unwrap()
+    }
+
+    #[test]
+    fn align_spectra() {
+        let reference = shifted_spectrum(&[2.5, 7.5]);
+        let global = shifted_spectrum(&[2.535, 7.535]);
+        let segmented = shifted_spectrum(&[2.46, 7.525]);
+        let mut aligner = Aligner::default();
+        aligner.set_max_shift(20);
+        aligner.set_segments(2).unwrap();
+        let aligned = aligner
+            .align_spectra(&[global.clone(), segmented.clone()], &reference)
+            .unwrap();
+        assert_eq!(aligned.len(), 2);
+        [(0, 1000), (1000, 2000)]
+            .into_iter()
+            .for_each(|(start, end)| {
+                let expected = argmax(&reference.intensities()[start..end]);
+                aligned.iter().for_each(|spectrum| {
+                    assert_eq!(spectrum.chemical_shifts(), reference.chemical_shifts());
+                    assert!(
+                        spectrum
+                            .intensities()
+                            .iter()
+                            .all(|x| x.is_finite())
+                    );
+                    let found = argmax(&spectrum.intensities()[start..end]);
+                    assert!(found.abs_diff(expected) <= 1);
+                });
+            });
+
+        aligner.set_segments(1).unwrap();
+        let aligned = aligner
+            .align_spectra(&[segmented], &reference)
+            .unwrap();
+        assert!(
+            [(0, 1000), (1000, 2000)]
+                .into_iter()
+                .any(|(start, end)| {
+                    argmax(&aligned[0].intensities()[start..end])
+                        .abs_diff(argmax(&reference.intensities()[start..end]))
+                        > 1
+                })
+        );
+
+        aligner.set_max_shift(0);
+        let aligned = aligner
+            .align_spectra(std::slice::from_ref(&global), &reference)
+            .unwrap();
+        assert_eq!(aligned[0].intensities(), global.intensities());
+    }
+
+    #[test]
+    fn align_spectra_errors() {
+        let mut aligner = Aligner::default();
+        match aligner.set_segments(0).unwrap_err() {
+            crate::Error::Alignment(inner) => match inner.kind() {
+                Kind::InvalidSegmentCount { segments } => assert_eq!(*segments, 0),
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(aligner.segments(), 1);
+        let reference = shifted_spectrum(&[5.0]);
+        let short = Spectrum::new(
+            reference.chemical_shifts()[..1000].to_vec(),
+            reference.intensities()[..1000].to_vec(),
+            (0.5, 4.5),
+        )
+        .unwrap();
+        let error = aligner
+            .align_spectra(&[reference.clone(), short], &reference)
+            .unwrap_err();
+        match error {
+            crate::Error::Alignment(inner) => match inner.kind() {
+                Kind::IncompatibleSpectrum {
+                    index,
+                    expected,
+                    found,
+                } => {
+                    assert_eq!(*index, 1);
+                    assert_eq!(*expected, 2000);
+                    assert_eq!(*found, 1000);
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+/// Data structure representing the result of an alignment.
+///
+/// An `Alignment` is a table of signal areas, with one row per aligned
+/// [`Deconvolution`] (sample) and one column per consensus signal (peak). The
+/// columns are sorted by the positions of the consensus signals. Samples that
+/// don't have a signal within a cluster have an area of 0 in the respective
+/// column.
+///
+/// This type is the output of [`Aligner::align_deconvolutions`].
+///
+/// [`Deconvolution`]: crate::deconvolution::Deconvolution
+/// [`Aligner::align_deconvolutions`]: crate::alignment::Aligner::align_deconvolutions
+#[derive(Clone, Debug)]
+pub struct Alignment {
+    /// Positions of the consensus signals in ppm.
+    positions: Vec<f64>,
+    /// Signal areas, stored row-wise.
+    areas: Vec<f64>,
+    /// Number of aligned samples.
+    sample_count: usize,
+}
+
+impl Alignment {
+    /// Internal helper function to construct a new `Alignment` from row-wise
+    /// stored areas.
+    pub(crate) fn new(positions: Vec<f64>, areas: Vec<f64>, sample_count: usize) -> Self {
+        Self {
+            positions,
+            areas,
+            sample_count,
+        }
+    }
+
+    /// Returns the positions of the consensus signals in ppm.
+    pub fn positions(&self) -> &[f64] {
+        &self.positions
+    }
+
+    /// Returns the number of aligned samples, i.e. the number of rows.
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Returns the number of consensus signals, i.e. the number of columns.
+    pub fn peak_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the signal areas of all samples, stored row-wise.
+    pub fn areas(&self) -> &[f64] {
+        &self.areas
+    }
+
+    /// Returns the signal areas of the given sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample` is out of bounds.
+    pub fn row(&self, sample: usize) -> &[f64] {
+        assert!(sample < self.sample_count, "sample index out of bounds");
+        let peak_count = self.peak_count();
+
+        &self.areas[sample * peak_count..(sample + 1) * peak_count]
+    }
+
+    /// Returns the area of the given consensus signal in the given sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample` or `peak` is out of bounds.
+    pub fn area(&self, sample: usize, peak: usize) -> f64 {
+        self.row(sample)[peak]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Alignment);
+        assert_sync!(Alignment);
+    }
+
+    #[test]
+    fn table_access() {
+        let alignment = Alignment::new(vec![1.0, 2.0, 3.0], (0..6).map(f64::from).collect(), 2);
+        assert_eq!(alignment.sample_count(), 2);
+        assert_eq!(alignment.peak_count(), 3);
+        assert_eq!(alignment.row(0), [0.0, 1.0, 2.0]);
+        assert_eq!(alignment.row(1), [3.0, 4.0, 5.0]);
+        assert_eq!(alignment.area(1, 2), 5.0);
+        assert_eq!(alignment.areas().len(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_bounds() {
+        let alignment = Alignment::new(Vec::new(), Vec::new(), 2);
+        alignment.row(2);
+    }
+}
@@ -0,0 +1,93 @@
+//! Error types for the alignment process.
+
+/// An `Error` that occurred during the alignment process.
+///
+/// See the [`Kind`] enum for the different kinds of errors that can occur.
+#[derive(Clone, Debug)]
+pub struct Error {
+    /// The `Kind` of error that occurred.
+    kind: Kind,
+}
+
+impl Error {
+    /// Constructs a new `Error` from the given `Kind`.
+    pub fn new(kind: Kind) -> Self {
+        kind.into()
+    }
+
+    /// Returns the `Kind` of the `Error`.
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+}
+
+/// The kind of `Error` that can occur during the [`alignment`] process.
+///
+/// Marked as non-exhaustive to allow for new variants to be added in the future
+/// without breaking compatibility.
+///
+/// [`alignment`]: crate::alignment
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum Kind {
+    /// The provided tolerance for clustering signals is invalid.
+    ///
+    /// The tolerance must be a finite, positive floating point number.
+    InvalidTolerance {
+        /// The provided tolerance.
+        tolerance: f64,
+    },
+    /// The provided number of segments for aligning spectra is invalid.
+    ///
+    /// The number of segments must be at least 1.
+    InvalidSegmentCount {
+        /// The provided number of segments.
+        segments: usize,
+    },
+    /// A [`Spectrum`] to be aligned is incompatible with the reference.
+    ///
+    /// The spectra must have the same number of data points as the reference.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    IncompatibleSpectrum {
+        /// Position of the spectrum within the batch.
+        index: usize,
+        /// The number of data points of the reference.
+        expected: usize,
+        /// The number of data points of the spectrum.
+        found: usize,
+    },
+}
+
+impl std::error::Error for Error {}
+
+impl From<Kind> for Error {
+    fn from(value: Kind) -> Self {
+        Self { kind: value }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let description = match &self.kind {
+            Kind::InvalidTolerance { tolerance } => format!(
+                "alignment tolerance must be finite and positive, got {}",
+                tolerance
+            ),
+            Kind::InvalidSegmentCount { segments } => format!(
+                "number of alignment segments must be at least 1, got {}",
+                segments
+            ),
+            Kind::IncompatibleSpectrum {
+                index,
+                expected,
+                found,
+            } => format!(
+                "spectrum at index {} has {} data points, but the reference has {}",
+                index, found, expected
+            ),
+        };
+
+        write!(f, "{}", description)
+    }
+}
@@ -3,8 +3,24 @@
 //! # Key Components
 //!
 //! - [`Lorentzian`]: Data structure representing a Lorentzian function.
+//! - [`PrecomputedLorentzian`]: [`Lorentzian`] with cached untransformed
+//!   parameters.
+//! - [`Gaussian`]: Data structure representing a Gaussian function.
+//! - [`PseudoVoigt`]: Data structure representing a pseudo-Voigt function.
+//! - [`PeakShape`]: Common interface of the fitted peak shapes.
+//! - [`Signal`]: Deconvoluted signal of any of the peak shapes.
+//! - [`FittedSignals`]: Deconvoluted signals stored as their peak shape.
 //! - [`Deconvolution`]: Container for results of the algorithm.
+//! - [`DeconvolutionWarning`]: Quality issue of a [`Deconvolution`].
 //! - [`Deconvoluter`]: Configuration for the deconvolution algorithm.
+//! - [`DeconvoluterBuilder`]: Fluent construction of a [`Deconvoluter`].
+//! - [`StreamingDeconvoluter`]: Incremental deconvolution of a changing
+//!   [`Spectrum`].
+//! - [`StaticDeconvoluter`]: [`Deconvoluter`] with the smoothing, peak
+//!   selection and fitting methods fixed at compile time.
+//! - [`OptimizationGrid`]: Candidate settings for the optimization of a
+//!   [`Deconvoluter`].
+//! - [`OptimizationResult`]: Evaluated combination of settings and its MSE.
 //!
 //! # The Algorithm
 //!
@@ -16,11 +32,24 @@
 //!
 //! [`Spectrum`]: crate::spectrum::Spectrum
 //!
+//! Optionally, the baseline of the [`Spectrum`] can be estimated and
+//! subtracted from the intensities before the first step, which is configured
+//! with [`BaselineSettings`]. This is disabled by default.
+//!
+//! [`Spectrum`]: crate::spectrum::Spectrum
+//!
 //! ## Smoothing
 //!
 //! In order to reduce noise and improve the accuracy of peak detection, the
 //! intensities of the [`Spectrum`] are smoothed using the selected method.
-//! Currently, only the Moving Average filter is supported.
+//! Currently, the Moving Average filter and the Whittaker smoother are
+//! supported. The [`Deconvoluter`] smooths in `f64`, but the smoothing methods
+//! can also be applied on their own to `f32` values with
+//! [`SmoothingSettings::smooth_values`], trading precision for memory. See
+//! [`SmoothingFloat`] for details. The building blocks of the Moving Average
+//! filter, [`CircularBuffer`] and [`MovingSum`], are available for custom
+//! streaming filters. Custom smoothing algorithms can be used by implementing
+//! the [`Smoother`] trait and passing them to [`Deconvoluter::with_smoother`].
 //!
 //! [`Spectrum`]: crate::spectrum::Spectrum
 //!
@@ -44,7 +73,10 @@
 //! the peak points are adjusted by multiplying their intensity by the ratio of
 //! the superposition of the Lorentzian functions to the original [`Spectrum`]
 //! at that point. A maximum number of iterations serves as a stopping
-//! criterion. In the future, other fitting methods may be implemented.
+//! criterion. Alternatively, [`Gaussian`] functions can be fitted in the same
+//! way, which is better suited for spectra that were apodized with a Gaussian
+//! window function. For mixed line shapes, [`PseudoVoigt`] functions can be
+//! fitted, which are refined numerically.
 //!
 //! [`Spectrum`]: crate::spectrum::Spectrum
 //!
@@ -112,15 +144,17 @@
 //!
 //! # Example: Configuring the [`Deconvoluter`]
 //!
-//! [`Deconvoluter`] is modular and allows you to configure the smoothing, peak
-//! selection, and fitting settings independently, though currently only one
-//! method is implemented for each. Additionally, you can specify regions to be
-//! ignored during the deconvolution. This may be useful for compounds like
+//! [`Deconvoluter`] is modular and allows you to choose the smoothing, peak
+//! selection, and fitting methods independently. The available methods are
+//! the variants of [`SmoothingSettings`], [`SelectionSettings`], and
+//! [`FittingSettings`]. Additionally, you can specify regions to be ignored
+//! during the deconvolution. This may be useful for compounds like
 //! stabilizing agents or a water signal.
 //!
 //! ```
 //! use metabodecon::deconvolution::{
-//!     Deconvoluter, FittingSettings, ScoringMethod, SelectionSettings, SmoothingSettings,
+//!     Deconvoluter, FittingSettings, NoiseEstimator, ScoringMethod, SelectionSettings,
+//!     SmoothingSettings,
 //! };
 //!
 //! # fn main() -> metabodecon::Result<()> {
@@ -133,6 +167,8 @@
 //!     SelectionSettings::NoiseScoreFilter {
 //!         scoring_method: ScoringMethod::MinimumSum,
 //!         threshold: 5.0,
+//!         max_peaks: None,
+//!         noise_estimator: NoiseEstimator::StdDev,
 //!     },
 //!     FittingSettings::Analytical { iterations: 20 },
 //! )?;
@@ -146,24 +182,83 @@
 mod deconvoluter;
 pub use deconvoluter::Deconvoluter;
 
+mod deconvoluter_builder;
+pub use deconvoluter_builder::DeconvoluterBuilder;
+
+#[cfg(feature = "serde")]
+mod deconvoluter_config;
+#[cfg(feature = "serde")]
+pub use deconvoluter_config::DeconvoluterConfig;
+
+mod streaming_deconvoluter;
+pub use streaming_deconvoluter::StreamingDeconvoluter;
+
+mod static_deconvoluter;
+pub use static_deconvoluter::StaticDeconvoluter;
+
 mod deconvolution;
 pub use deconvolution::Deconvolution;
 
+mod deconvolution_warning;
+pub use deconvolution_warning::DeconvolutionWarning;
+
+mod optimization_grid;
+pub use optimization_grid::OptimizationGrid;
+
+mod optimization_result;
+pub use optimization_result::OptimizationResult;
+
 mod lorentzian;
 pub use lorentzian::Lorentzian;
 
+mod precomputed_lorentzian;
+pub use precomputed_lorentzian::PrecomputedLorentzian;
+
+mod gaussian;
+pub use gaussian::Gaussian;
+
+mod pseudo_voigt;
+pub use pseudo_voigt::PseudoVoigt;
+
+mod peak_shape;
+pub use peak_shape::PeakShape;
+
+mod signal;
+pub use signal::Signal;
+
+mod fitted_signals;
+pub use fitted_signals::{FittedSignals, SignalIter};
+
+mod mse_weighting;
+pub use mse_weighting::MseWeighting;
+
 #[cfg(feature = "serde")]
 mod serialized_representations;
 #[cfg(feature = "serde")]
 pub(crate) use serialized_representations::{SerializedDeconvolution, SerializedLorentzian};
 
+mod evaluation_regions;
+
+mod banded;
+
+mod baseline;
+pub use baseline::BaselineSettings;
+
 mod fitting;
-pub use fitting::FittingSettings;
+pub use fitting::{
+    FitDiagnostics, FitterAnalytical, FitterGaussian, FitterLevenbergMarquardt, FitterPseudoVoigt,
+    FittingSettings, ParameterUncertainty,
+};
 
 mod peak_selection;
-pub use peak_selection::{ScoringMethod, SelectionSettings};
+pub use peak_selection::{
+    DetectorOnly, NoiseEstimator, NoiseScoreFilter, PeakInfo, ScoringMethod, SelectionSettings,
+};
 
 mod smoothing;
-pub use smoothing::SmoothingSettings;
+pub use smoothing::{
+    CircularBuffer, Identity, MovingAverage, MovingSum, Smoother, SmoothingFloat,
+    SmoothingSettings, Whittaker,
+};
 
 pub mod error;
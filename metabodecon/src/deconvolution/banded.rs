@@ -0,0 +1,121 @@
+use num_traits::{Float, FromPrimitive};
+use std::ops::{AddAssign, SubAssign};
+
+/// Internal helper function to compute the bands of `lambda * D^T D`, where
+/// `D` is the difference matrix of the given order for `len` values.
+///
+/// Returns the main diagonal and the `order` upper diagonals, stored row-wise
+/// such that the element at `(i, i + m)` is at index `i * (order + 1) + m`.
+/// This is the layout expected by [`solve_banded`].
+pub(crate) fn penalty_bands<T>(len: usize, order: usize, lambda: f64) -> Vec<T>
+where
+    T: Float + FromPrimitive + AddAssign,
+{
+    let width = order + 1;
+    let lambda = T::from_f64(lambda).unwrap();
+    let coefficients = (0..=order)
+        .map(|k| {
+            let binomial = (0..k).fold(T::one(), |acc, j| {
+                acc * T::from_usize(order - j).unwrap() / T::from_usize(j + 1).unwrap()
+            });
+            match (order - k) % 2 {
+                0 => binomial,
+                _ => -binomial,
+            }
+        })
+        .collect::<Vec<T>>();
+    let mut bands = vec![T::zero(); len * width];
+    (0..len.saturating_sub(order)).for_each(|row| {
+        for k in 0..=order {
+            for l in k..=order {
+                bands[(row + k) * width + (l - k)] += lambda * coefficients[k] * coefficients[l];
+            }
+        }
+    });
+
+    bands
+}
+
+/// Internal helper function to solve a symmetric positive definite banded
+/// system of linear equations using an LDL^T decomposition.
+///
+/// The matrix is given by its main diagonal and `bandwidth` upper diagonals,
+/// stored row-wise as returned by [`penalty_bands`]. The bands are overwritten
+/// by the decomposition. The right hand side is consumed and returned as the
+/// solution.
+pub(crate) fn solve_banded<T>(mut bands: Vec<T>, bandwidth: usize, rhs: Vec<T>) -> Vec<T>
+where
+    T: Float + SubAssign,
+{
+    let width = bandwidth + 1;
+    let len = rhs.len();
+    for i in 0..len {
+        for m in (1..=usize::min(bandwidth, i)).rev() {
+            let j = i - m;
+            let mut value = bands[j * width + m];
+            for k in (i.saturating_sub(bandwidth))..j {
+                value -= bands[k * width + (i - k)] * bands[k * width + (j - k)] * bands[k * width];
+            }
+            bands[j * width + m] = value / bands[j * width];
+        }
+        let mut diagonal = bands[i * width];
+        for k in (i.saturating_sub(bandwidth))..i {
+            diagonal -= bands[k * width + (i - k)].powi(2) * bands[k * width];
+        }
+        bands[i * width] = diagonal;
+    }
+
+    let mut solution = rhs;
+    for i in 0..len {
+        for k in (i.saturating_sub(bandwidth))..i {
+            let correction = bands[k * width + (i - k)] * solution[k];
+            solution[i] -= correction;
+        }
+    }
+    (0..len).for_each(|i| solution[i] = solution[i] / bands[i * width]);
+    for i in (0..len).rev() {
+        for k in (i + 1)..usize::min(i + width, len) {
+            let correction = bands[i * width + (k - i)] * solution[k];
+            solution[i] -= correction;
+        }
+    }
+
+    solution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn solve_banded_system() {
+        (1..=3).for_each(|order| {
+            let len = 12;
+            let width = order + 1;
+            let mut bands = penalty_bands::<f64>(len, order, 2.5);
+            (0..len).for_each(|i| bands[i * width] += 1.0);
+            let expected = (0..len)
+                .map(|i| (i as f64 * 0.7).sin() + 0.1 * i as f64)
+                .collect::<Vec<f64>>();
+            let rhs = (0..len)
+                .map(|i| {
+                    (0..len)
+                        .filter(|j| i.abs_diff(*j) <= order)
+                        .map(|j| {
+                            let (row, col) = (usize::min(i, j), usize::max(i, j));
+                            bands[row * width + (col - row)] * expected[j]
+                        })
+                        .sum::<f64>()
+                })
+                .collect::<Vec<f64>>();
+            let solution = solve_banded(bands, order, rhs);
+            solution
+                .iter()
+                .zip(expected.iter())
+                .for_each(|(solution, expected)| {
+                    assert_approx_eq!(f64, *solution, *expected, epsilon = 1e-10);
+                });
+        });
+    }
+}
@@ -0,0 +1,9 @@
+mod asymmetric_least_squares;
+pub(crate) use asymmetric_least_squares::AsymmetricLeastSquares;
+
+mod baseline_corrector;
+pub(crate) use baseline_corrector::BaselineCorrector;
+pub use baseline_corrector::BaselineSettings;
+
+mod uncorrected;
+pub(crate) use uncorrected::Uncorrected;
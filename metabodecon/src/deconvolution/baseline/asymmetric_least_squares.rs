@@ -0,0 +1,143 @@
+use crate::deconvolution::banded::{penalty_bands, solve_banded};
+use crate::deconvolution::baseline::{BaselineCorrector, BaselineSettings};
+
+/// Asymmetric least squares baseline corrector.
+///
+/// # Algorithm
+///
+/// The baseline `z` is the solution of the penalized least squares problem
+///
+/// ```text
+/// (W + lambda * D^T D) z = W y
+/// ```
+///
+/// where `y` are the values, `D` is the second order difference matrix and `W`
+/// is a diagonal matrix of weights. The weights are initialized to 1 and then
+/// updated after each iteration to `p` for values above the current baseline
+/// and `1 - p` for values below it. Because `D^T D` is pentadiagonal, the
+/// system is solved in linear time with a banded LDL^T decomposition.
+#[derive(Debug)]
+pub(crate) struct AsymmetricLeastSquares {
+    /// Smoothness penalty of the baseline.
+    lambda: f64,
+    /// Asymmetry weight of the values above the baseline.
+    p: f64,
+    /// Number of reweighting iterations.
+    iterations: usize,
+}
+
+impl BaselineCorrector for AsymmetricLeastSquares {
+    fn correct_baseline(&self, values: &mut [f64]) {
+        let baseline = self.estimate_baseline(values);
+        values
+            .iter_mut()
+            .zip(baseline)
+            .for_each(|(value, baseline)| *value -= baseline);
+    }
+
+    fn settings(&self) -> BaselineSettings {
+        BaselineSettings::AsymmetricLeastSquares {
+            lambda: self.lambda,
+            p: self.p,
+            iterations: self.iterations,
+        }
+    }
+}
+
+impl AsymmetricLeastSquares {
+    /// Creates a new `AsymmetricLeastSquares` baseline corrector with the given
+    /// parameters.
+    pub(crate) fn new(lambda: f64, p: f64, iterations: usize) -> Self {
+        Self {
+            lambda,
+            p,
+            iterations,
+        }
+    }
+
+    /// Internal helper function to estimate the baseline of the given values.
+    ///
+    /// Sequences with fewer than 3 values have no second order differences, so
+    /// their baseline is 0.
+    fn estimate_baseline(&self, values: &[f64]) -> Vec<f64> {
+        let len = values.len();
+        if len < 3 {
+            return vec![0.0; len];
+        }
+        let penalty = penalty_bands::<f64>(len, 2, self.lambda);
+        let mut weights = vec![1.0; len];
+        let mut baseline = values.to_vec();
+        for _ in 0..self.iterations {
+            let mut bands = penalty.clone();
+            weights
+                .iter()
+                .enumerate()
+                .for_each(|(i, weight)| bands[i * 3] += weight);
+            let rhs = values
+                .iter()
+                .zip(weights.iter())
+                .map(|(value, weight)| value * weight)
+                .collect::<Vec<f64>>();
+            baseline = solve_banded(bands, 2, rhs);
+            weights
+                .iter_mut()
+                .zip(values.iter().zip(baseline.iter()))
+                .for_each(|(weight, (value, baseline))| {
+                    *weight = if value > baseline {
+                        self.p
+                    } else {
+                        1.0 - self.p
+                    }
+                });
+        }
+
+        baseline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(AsymmetricLeastSquares);
+        assert_sync!(AsymmetricLeastSquares);
+    }
+
+    #[test]
+    fn recover_polynomial_baseline() {
+        let corrector = AsymmetricLeastSquares::new(1e7, 0.001, 10);
+        let positions = (0..1000)
+            .map(|i| i as f64 / 999.0)
+            .collect::<Vec<f64>>();
+        let baseline = positions
+            .iter()
+            .map(|x| 2.0 + 3.0 * x - 4.0 * x.powi(2))
+            .collect::<Vec<f64>>();
+        let peaks = positions
+            .iter()
+            .map(|x| {
+                [(0.3_f64, 0.005_f64), (0.6, 0.01)]
+                    .iter()
+                    .map(|(center, width)| {
+                        50.0 * width.powi(2) / (width.powi(2) + (x - center).powi(2))
+                    })
+                    .sum::<f64>()
+            })
+            .collect::<Vec<f64>>();
+        let mut values = baseline
+            .iter()
+            .zip(peaks.iter())
+            .map(|(baseline, peak)| baseline + peak)
+            .collect::<Vec<f64>>();
+        corrector.correct_baseline(&mut values);
+        values
+            .iter()
+            .zip(peaks.iter())
+            .for_each(|(corrected, peak)| {
+                assert!((corrected - peak).abs() < 0.5);
+            });
+    }
+}
@@ -0,0 +1,119 @@
+use crate::Settings;
+use crate::deconvolution::error::{Error, Kind};
+use crate::error::Result;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trait interface for baseline correction algorithms.
+pub(crate) trait BaselineCorrector: Send + Sync + std::fmt::Debug {
+    /// Estimates the baseline of the given sequence of values and subtracts it
+    /// in place.
+    fn correct_baseline(&self, values: &mut [f64]);
+
+    /// Returns the settings of the trait object.
+    fn settings(&self) -> BaselineSettings;
+}
+
+/// Baseline correction settings for configuring the [`Deconvoluter`].
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "method", rename_all_fields = "camelCase")
+)]
+pub enum BaselineSettings {
+    /// No baseline correction.
+    ///
+    /// This option disables the baseline correction, which is appropriate for
+    /// spectra that were already baseline corrected during processing.
+    #[default]
+    None,
+    /// Asymmetric least squares smoothing.
+    ///
+    /// The baseline is estimated by fitting a smooth curve to the intensities,
+    /// where points above the curve are weighted by `p` and points below it by
+    /// `1 - p`. Since `p` is chosen to be small, the curve follows the lower
+    /// envelope of the spectrum and ignores the peaks. The weights are updated
+    /// iteratively, after which the baseline is subtracted from the
+    /// intensities. See [Eilers and Boelens (2005)] for details.
+    ///
+    /// [Eilers and Boelens (2005)]: https://www.researchgate.net/publication/228961729
+    AsymmetricLeastSquares {
+        /// Smoothness penalty of the baseline. Larger values result in a
+        /// stiffer baseline.
+        lambda: f64,
+        /// Asymmetry weight of the points above the baseline, within (0, 1).
+        p: f64,
+        /// The number of reweighting iterations.
+        iterations: usize,
+    },
+}
+
+impl std::fmt::Display for BaselineSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaselineSettings::None => write!(f, "No Baseline Correction"),
+            BaselineSettings::AsymmetricLeastSquares {
+                lambda,
+                p,
+                iterations,
+            } => write!(
+                f,
+                "Asymmetric Least Squares [smoothness penalty: {}, asymmetry weight: {}, \
+                 number of iterations: {}]",
+                lambda, p, iterations
+            ),
+        }
+    }
+}
+
+impl Settings for BaselineSettings {
+    fn validate(&self) -> Result<()> {
+        match self {
+            BaselineSettings::None => {}
+            BaselineSettings::AsymmetricLeastSquares {
+                lambda,
+                p,
+                iterations,
+            } => {
+                if !(lambda.is_finite() && *lambda > 0.0 && *p > 0.0 && *p < 1.0)
+                    || *iterations == 0
+                {
+                    return Err(
+                        Error::new(Kind::InvalidBaselineSettings { settings: *self }).into(),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn compare(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BaselineSettings::None, BaselineSettings::None) => true,
+            (
+                BaselineSettings::AsymmetricLeastSquares {
+                    lambda: lambda1,
+                    p: p1,
+                    iterations: iterations1,
+                },
+                BaselineSettings::AsymmetricLeastSquares {
+                    lambda: lambda2,
+                    p: p2,
+                    iterations: iterations2,
+                },
+            ) => {
+                float_cmp::approx_eq!(f64, *lambda1, *lambda2)
+                    && float_cmp::approx_eq!(f64, *p1, *p2)
+                    && *iterations1 == *iterations2
+            }
+            _ => false,
+        }
+    }
+}
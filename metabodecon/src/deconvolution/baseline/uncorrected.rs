@@ -0,0 +1,32 @@
+use crate::deconvolution::baseline::{BaselineCorrector, BaselineSettings};
+
+/// Baseline corrector that does not modify the input values.
+#[derive(Debug)]
+pub(crate) struct Uncorrected;
+
+impl BaselineCorrector for Uncorrected {
+    fn correct_baseline(&self, _values: &mut [f64]) {}
+
+    fn settings(&self) -> BaselineSettings {
+        BaselineSettings::None
+    }
+}
+
+impl Uncorrected {
+    /// Creates a new `Uncorrected` baseline corrector.
+    pub(crate) fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Uncorrected);
+        assert_sync!(Uncorrected);
+    }
+}
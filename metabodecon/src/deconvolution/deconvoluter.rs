@@ -1,17 +1,37 @@
-use crate::deconvolution::Deconvolution;
+use crate::deconvolution::baseline::{
+    AsymmetricLeastSquares, BaselineCorrector, BaselineSettings, Uncorrected,
+};
 use crate::deconvolution::error::{Error, Kind};
-use crate::deconvolution::fitting::{Fitter, FitterAnalytical, FittingSettings};
-use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::evaluation_regions::{
+    evaluation_regions, ignore_region_indices, region_indices,
+};
+use crate::deconvolution::fitting::{
+    Fitter, FitterAnalytical, FitterGaussian, FitterLevenbergMarquardt, FitterPseudoVoigt,
+    FittingSettings,
+};
 use crate::deconvolution::peak_selection::{
-    DetectorOnly, NoiseScoreFilter, ScoringMethod, SelectionSettings, Selector,
+    DetectorOnly, NoiseScoreFilter, Peak, PeakInfo, SelectionSettings, Selector,
+};
+use crate::deconvolution::smoothing::{
+    Identity, MovingAverage, Smoother, SmoothingSettings, Whittaker,
+};
+use crate::deconvolution::{
+    DeconvoluterBuilder, Deconvolution, DeconvolutionWarning, MseWeighting,
 };
-use crate::deconvolution::smoothing::{Identity, MovingAverage, Smoother, SmoothingSettings};
 use crate::spectrum::Spectrum;
 use crate::{Result, Settings};
+use std::borrow::Cow;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+#[cfg(feature = "serde")]
+use crate::deconvolution::DeconvoluterConfig;
+#[cfg(feature = "parallel")]
+use crate::deconvolution::{OptimizationGrid, OptimizationResult};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use std::sync::Mutex;
 
 /// Deconvolution pipeline that applies smoothing, peak selection, and fitting
 /// to a spectrum to deconvolute it into individual signals.
@@ -84,15 +104,17 @@ use rayon::prelude::*;
 ///
 /// # Example: Configuring the `Deconvoluter`
 ///
-/// `Deconvoluter` is modular and allows you to configure the smoothing, peak
-/// selection, and fitting settings independently, though currently only one
-/// method is implemented for each. Additionally, you can specify regions to be
-/// ignored during the deconvolution. This may be useful for compounds like
+/// `Deconvoluter` is modular and allows you to choose the smoothing, peak
+/// selection, and fitting methods independently. The available methods are
+/// the variants of [`SmoothingSettings`], [`SelectionSettings`], and
+/// [`FittingSettings`]. Additionally, you can specify regions to be ignored
+/// during the deconvolution. This may be useful for compounds like
 /// stabilizing agents or a water signal.
 ///
 /// ```
 /// use metabodecon::deconvolution::{
-///     Deconvoluter, FittingSettings, ScoringMethod, SelectionSettings, SmoothingSettings,
+///     Deconvoluter, FittingSettings, NoiseEstimator, ScoringMethod, SelectionSettings,
+///     SmoothingSettings,
 /// };
 ///
 /// # fn main() -> metabodecon::Result<()> {
@@ -105,6 +127,8 @@ use rayon::prelude::*;
 ///     SelectionSettings::NoiseScoreFilter {
 ///         scoring_method: ScoringMethod::MinimumSum,
 ///         threshold: 5.0,
+///         max_peaks: None,
+///         noise_estimator: NoiseEstimator::StdDev,
 ///     },
 ///     FittingSettings::Analytical { iterations: 20 },
 /// )?;
@@ -114,8 +138,38 @@ use rayon::prelude::*;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// The same configuration can also be built in a single expression with the
+/// [`DeconvoluterBuilder`], which validates all settings at once.
+///
+/// ```
+/// use metabodecon::deconvolution::{
+///     Deconvoluter, FittingSettings, NoiseEstimator, ScoringMethod, SelectionSettings,
+///     SmoothingSettings,
+/// };
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let deconvoluter = Deconvoluter::builder()
+///     .smoothing(SmoothingSettings::MovingAverage {
+///         iterations: 3,
+///         window_size: 3,
+///     })
+///     .selection(SelectionSettings::NoiseScoreFilter {
+///         scoring_method: ScoringMethod::MinimumSum,
+///         threshold: 5.0,
+///         max_peaks: None,
+///         noise_estimator: NoiseEstimator::StdDev,
+///     })
+///     .fitting(FittingSettings::Analytical { iterations: 20 })
+///     .ignore_region((4.7, 4.9))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Clone, Debug)]
 pub struct Deconvoluter {
+    /// Baseline correction settings.
+    baseline_corrector: Arc<dyn BaselineCorrector>,
     /// Smoothing settings.
     smoother: Arc<dyn Smoother<f64>>,
     /// Peak selection settings.
@@ -124,6 +178,12 @@ pub struct Deconvoluter {
     fitter: Arc<dyn Fitter>,
     /// Regions to ignore during deconvolution.
     ignore_regions: Option<Vec<(f64, f64)>>,
+    /// Regions with a lowered peak selection threshold, and their factors.
+    boost_regions: Option<Vec<((f64, f64), f64)>>,
+    /// Region used to estimate the noise level.
+    noise_region: Option<(f64, f64)>,
+    /// Weighting of the residuals used to compute the MSE.
+    mse_weighting: MseWeighting,
 }
 
 impl Default for Deconvoluter {
@@ -149,12 +209,17 @@ impl Deconvoluter {
     /// - Negative `threshold`s for a noise score filter wouldn't make sense.
     /// - 0 `iterations` for the analytical fitting algorithm would mean that
     ///   the fitting algorithm doesn't do anything.
+    /// - [`SmoothingSettings::Custom`] doesn't contain the smoother, use
+    ///   [`with_smoother`] instead.
+    ///
+    /// [`with_smoother`]: Deconvoluter::with_smoother
     ///
     /// # Example
     ///
     /// ```
     /// use metabodecon::deconvolution::{
-    ///     Deconvoluter, FittingSettings, ScoringMethod, SelectionSettings, SmoothingSettings,
+    ///     Deconvoluter, FittingSettings, NoiseEstimator, ScoringMethod, SelectionSettings,
+    ///     SmoothingSettings,
     /// };
     ///
     /// let deconvoluter = Deconvoluter::new(
@@ -165,6 +230,8 @@ impl Deconvoluter {
     ///     SelectionSettings::NoiseScoreFilter {
     ///         scoring_method: ScoringMethod::MinimumSum,
     ///         threshold: 5.0,
+    ///         max_peaks: None,
+    ///         noise_estimator: NoiseEstimator::StdDev,
     ///     },
     ///     FittingSettings::Analytical { iterations: 20 },
     /// );
@@ -174,38 +241,243 @@ impl Deconvoluter {
         selection_settings: SelectionSettings,
         fitting_settings: FittingSettings,
     ) -> Result<Self> {
-        smoothing_settings.validate()?;
-        selection_settings.validate()?;
-        fitting_settings.validate()?;
-
         let smoother: Arc<dyn Smoother<f64>> = match smoothing_settings {
             SmoothingSettings::Identity => Arc::new(Identity::new()),
             SmoothingSettings::MovingAverage {
                 iterations,
                 window_size,
-            } => Arc::new(MovingAverage::<f64>::new(iterations, window_size)),
+            } => Arc::new(MovingAverage::<f64>::new(iterations, window_size)?),
+            SmoothingSettings::Whittaker { lambda, order } => {
+                Arc::new(Whittaker::new(lambda, order)?)
+            }
+            SmoothingSettings::Custom => {
+                return Err(Error::new(Kind::InvalidSmoothingSettings {
+                    settings: smoothing_settings,
+                })
+                .into());
+            }
         };
         let selector: Arc<dyn Selector> = match selection_settings {
             SelectionSettings::DetectorOnly => Arc::new(DetectorOnly::new()),
             SelectionSettings::NoiseScoreFilter {
                 scoring_method,
                 threshold,
-            } => Arc::new(NoiseScoreFilter::new(scoring_method, threshold)),
+                max_peaks,
+                noise_estimator,
+            } => Arc::new(NoiseScoreFilter::new(
+                scoring_method,
+                threshold,
+                max_peaks,
+                noise_estimator,
+            )?),
         };
         let fitter: Arc<dyn Fitter> = match fitting_settings {
             FittingSettings::Analytical { iterations } => {
-                Arc::new(FitterAnalytical::new(iterations))
+                Arc::new(FitterAnalytical::new(iterations)?)
             }
+            FittingSettings::Gaussian { iterations } => Arc::new(FitterGaussian::new(iterations)?),
+            FittingSettings::LevenbergMarquardt {
+                max_iterations,
+                tolerance,
+                lambda_init,
+            } => Arc::new(FitterLevenbergMarquardt::new(
+                max_iterations,
+                tolerance,
+                lambda_init,
+            )?),
+            FittingSettings::PseudoVoigt {
+                iterations,
+                eta_init,
+            } => Arc::new(FitterPseudoVoigt::new(iterations, eta_init)?),
         };
 
         Ok(Self {
+            baseline_corrector: Arc::new(Uncorrected::new()),
             smoother,
             selector,
             fitter,
             ignore_regions: None,
+            boost_regions: None,
+            noise_region: None,
+            mse_weighting: MseWeighting::Uniform,
         })
     }
 
+    /// Constructs a new `Deconvoluter` with a user-provided [`Smoother`] and
+    /// the default peak selection and fitting settings.
+    ///
+    /// This allows custom smoothing algorithms to be used in the deconvolution
+    /// pipeline. The remaining options can be configured with the setters as
+    /// usual. The [`smoothing_settings`] of the `Deconvoluter` and of the
+    /// resulting [`Deconvolution`]s are the settings reported by the smoother,
+    /// which is [`SmoothingSettings::Custom`] unless it overrides
+    /// [`Smoother::settings`]. Calling [`set_smoothing_settings`] replaces the
+    /// custom smoother with a built-in one.
+    ///
+    /// [`smoothing_settings`]: Deconvoluter::smoothing_settings
+    /// [`set_smoothing_settings`]: Deconvoluter::set_smoothing_settings
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, Smoother, SmoothingSettings};
+    /// use std::sync::Arc;
+    ///
+    /// /// Clamps negative values to zero.
+    /// #[derive(Debug)]
+    /// struct NonNegative;
+    ///
+    /// impl Smoother<f64> for NonNegative {
+    ///     fn smooth_values(&self, values: &mut [f64]) {
+    ///         values
+    ///             .iter_mut()
+    ///             .for_each(|value| *value = value.max(0.0));
+    ///     }
+    /// }
+    ///
+    /// let deconvoluter = Deconvoluter::with_smoother(Arc::new(NonNegative));
+    ///
+    /// match deconvoluter.smoothing_settings() {
+    ///     SmoothingSettings::Custom => {}
+    ///     _ => panic!("Unexpected smoothing settings"),
+    /// };
+    /// ```
+    pub fn with_smoother(smoother: Arc<dyn Smoother<f64>>) -> Self {
+        Self {
+            smoother,
+            ..Self::default()
+        }
+    }
+
+    /// Returns a [`DeconvoluterBuilder`] to configure a new `Deconvoluter` in a
+    /// single expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, FittingSettings, SmoothingSettings};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let deconvoluter = Deconvoluter::builder()
+    ///     .smoothing(SmoothingSettings::Identity)
+    ///     .fitting(FittingSettings::Analytical { iterations: 20 })
+    ///     .ignore_region((4.7, 4.9))
+    ///     .build()?;
+    ///
+    /// assert_eq!(deconvoluter.ignore_regions().unwrap().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> DeconvoluterBuilder {
+        DeconvoluterBuilder::new()
+    }
+
+    /// Constructs a `Deconvoluter` from a previously saved configuration.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any of the deconvolution settings, ignore
+    /// regions, boost regions, or the noise region of the configuration are
+    /// invalid, as with [`DeconvoluterBuilder::build`]. The configuration of a
+    /// `Deconvoluter` with a custom [`Smoother`] can't be reloaded, as the
+    /// smoother isn't part of it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, DeconvoluterConfig};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let json = r#"{
+    ///     "smoothingSettings": { "method": "Identity" },
+    ///     "ignoreRegions": [[4.7, 4.9]]
+    /// }"#;
+    /// let config = serde_json::from_str::<DeconvoluterConfig>(json).unwrap();
+    /// let deconvoluter = Deconvoluter::from_config(config)?;
+    ///
+    /// assert_eq!(deconvoluter.ignore_regions().unwrap(), &[(4.7, 4.9)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_config(config: DeconvoluterConfig) -> Result<Self> {
+        let builder = DeconvoluterBuilder::new()
+            .baseline(config.baseline_settings)
+            .smoothing(config.smoothing_settings)
+            .selection(config.selection_settings)
+            .fitting(config.fitting_settings)
+            .mse_weighting(config.mse_weighting);
+        let builder = config
+            .ignore_regions
+            .into_iter()
+            .fold(builder, |builder, region| builder.ignore_region(region));
+        let builder = config
+            .boost_regions
+            .into_iter()
+            .fold(builder, |builder, (region, factor)| {
+                builder.boost_region(region, factor)
+            });
+        let builder = match config.noise_region {
+            Some(region) => builder.noise_region(region),
+            None => builder,
+        };
+
+        builder.build()
+    }
+
+    /// Returns the configuration of the `Deconvoluter`, which can be
+    /// serialized to save and reload it with [`from_config`].
+    ///
+    /// [`from_config`]: Deconvoluter::from_config
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let deconvoluter = Deconvoluter::builder()
+    ///     .noise_region((10.0, 11.0))
+    ///     .build()?;
+    /// let config = deconvoluter.to_config();
+    ///
+    /// assert_eq!(config.noise_region(), Some((10.0, 11.0)));
+    /// assert!(config.ignore_regions().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_config(&self) -> DeconvoluterConfig {
+        DeconvoluterConfig {
+            baseline_settings: self.baseline_settings(),
+            smoothing_settings: self.smoothing_settings(),
+            selection_settings: self.selection_settings(),
+            fitting_settings: self.fitting_settings(),
+            ignore_regions: self.ignore_regions.clone().unwrap_or_default(),
+            boost_regions: self.boost_regions.clone().unwrap_or_default(),
+            noise_region: self.noise_region,
+            mse_weighting: self.mse_weighting,
+        }
+    }
+
+    /// Returns the baseline correction settings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{BaselineSettings, Deconvoluter};
+    ///
+    /// let deconvoluter = Deconvoluter::default();
+    ///
+    /// match deconvoluter.baseline_settings() {
+    ///     BaselineSettings::None => {}
+    ///     _ => panic!("Unexpected baseline correction settings"),
+    /// };
+    /// ```
+    pub fn baseline_settings(&self) -> BaselineSettings {
+        self.baseline_corrector.settings()
+    }
+
     /// Returns the smoothing settings.
     ///
     /// # Example
@@ -236,7 +508,9 @@ impl Deconvoluter {
     ///
     /// ```
     /// use float_cmp::assert_approx_eq;
-    /// use metabodecon::deconvolution::{Deconvoluter, ScoringMethod, SelectionSettings};
+    /// use metabodecon::deconvolution::{
+    ///     Deconvoluter, NoiseEstimator, ScoringMethod, SelectionSettings,
+    /// };
     ///
     /// let deconvoluter = Deconvoluter::default();
     ///
@@ -244,12 +518,19 @@ impl Deconvoluter {
     ///     SelectionSettings::NoiseScoreFilter {
     ///         scoring_method,
     ///         threshold,
+    ///         max_peaks,
+    ///         noise_estimator,
     ///     } => {
     ///         match scoring_method {
     ///             ScoringMethod::MinimumSum => {}
     ///             _ => panic!("Unexpected scoring method"),
     ///         };
     ///         assert_approx_eq!(f64, threshold, 5.0);
+    ///         assert_eq!(max_peaks, None);
+    ///         match noise_estimator {
+    ///             NoiseEstimator::StdDev => {}
+    ///             _ => panic!("Unexpected noise estimator"),
+    ///         };
     ///     }
     ///     _ => panic!("Unexpected peak selection settings"),
     /// };
@@ -297,13 +578,104 @@ impl Deconvoluter {
         self.ignore_regions.as_deref()
     }
 
+    /// Returns the regions with a lowered peak selection threshold, together
+    /// with the factors the threshold is divided by within them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    ///
+    /// let deconvoluter = Deconvoluter::default();
+    ///
+    /// assert!(deconvoluter.boost_regions().is_none());
+    /// ```
+    pub fn boost_regions(&self) -> Option<&[((f64, f64), f64)]> {
+        self.boost_regions.as_deref()
+    }
+
+    /// Returns the region used to estimate the noise level.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    ///
+    /// let deconvoluter = Deconvoluter::default();
+    ///
+    /// assert!(deconvoluter.noise_region().is_none());
+    /// ```
+    pub fn noise_region(&self) -> Option<(f64, f64)> {
+        self.noise_region
+    }
+
+    /// Returns the weighting of the residuals used to compute the MSE.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, MseWeighting};
+    ///
+    /// let deconvoluter = Deconvoluter::default();
+    ///
+    /// assert_eq!(deconvoluter.mse_weighting(), MseWeighting::Uniform);
+    /// ```
+    pub fn mse_weighting(&self) -> MseWeighting {
+        self.mse_weighting
+    }
+
+    /// Sets the baseline correction settings.
+    ///
+    /// The baseline correction is disabled by default. When enabled, the
+    /// baseline is estimated and subtracted from the intensities before they
+    /// are smoothed, and the signals are fitted to the corrected intensities.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the provided baseline correction settings are
+    /// invalid. For example, a non-positive `lambda` for asymmetric least
+    /// squares wouldn't make sense.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{BaselineSettings, Deconvoluter};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut deconvoluter = Deconvoluter::default();
+    ///
+    /// deconvoluter.set_baseline_settings(BaselineSettings::AsymmetricLeastSquares {
+    ///     lambda: 1e7,
+    ///     p: 0.01,
+    ///     iterations: 10,
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_baseline_settings(&mut self, baseline_settings: BaselineSettings) -> Result<()> {
+        baseline_settings.validate()?;
+        self.baseline_corrector = match baseline_settings {
+            BaselineSettings::None => Arc::new(Uncorrected::new()),
+            BaselineSettings::AsymmetricLeastSquares {
+                lambda,
+                p,
+                iterations,
+            } => Arc::new(AsymmetricLeastSquares::new(lambda, p, iterations)),
+        };
+
+        Ok(())
+    }
+
     /// Sets the smoothing settings.
     ///
     /// # Errors
     ///
     /// An error is returned if the provided smoothing settings are invalid. For
     /// example, a `window_size` of 0 for a moving average filter would mean
-    /// that no smoothing is applied.
+    /// that no smoothing is applied. [`SmoothingSettings::Custom`] is rejected
+    /// as well, use [`with_smoother`] to provide a custom smoother.
+    ///
+    /// [`with_smoother`]: Deconvoluter::with_smoother
     ///
     /// # Example
     ///
@@ -321,13 +693,21 @@ impl Deconvoluter {
     /// # }
     /// ```
     pub fn set_smoothing_settings(&mut self, smoothing_settings: SmoothingSettings) -> Result<()> {
-        smoothing_settings.validate()?;
         self.smoother = match smoothing_settings {
             SmoothingSettings::Identity => Arc::new(Identity::new()),
             SmoothingSettings::MovingAverage {
                 iterations,
                 window_size,
-            } => Arc::new(MovingAverage::<f64>::new(iterations, window_size)),
+            } => Arc::new(MovingAverage::<f64>::new(iterations, window_size)?),
+            SmoothingSettings::Whittaker { lambda, order } => {
+                Arc::new(Whittaker::new(lambda, order)?)
+            }
+            SmoothingSettings::Custom => {
+                return Err(Error::new(Kind::InvalidSmoothingSettings {
+                    settings: smoothing_settings,
+                })
+                .into());
+            }
         };
 
         Ok(())
@@ -344,7 +724,9 @@ impl Deconvoluter {
     /// # Example
     ///
     /// ```
-    /// use metabodecon::deconvolution::{Deconvoluter, ScoringMethod, SelectionSettings};
+    /// use metabodecon::deconvolution::{
+    ///     Deconvoluter, NoiseEstimator, ScoringMethod, SelectionSettings,
+    /// };
     ///
     /// # fn main() -> metabodecon::Result<()> {
     /// let mut deconvoluter = Deconvoluter::default();
@@ -352,18 +734,26 @@ impl Deconvoluter {
     /// deconvoluter.set_selection_settings(SelectionSettings::NoiseScoreFilter {
     ///     scoring_method: ScoringMethod::MinimumSum,
     ///     threshold: 5.0,
+    ///     max_peaks: None,
+    ///     noise_estimator: NoiseEstimator::StdDev,
     /// })?;
     /// # Ok(())
     /// # }
     /// ```
     pub fn set_selection_settings(&mut self, selection_settings: SelectionSettings) -> Result<()> {
-        selection_settings.validate()?;
         self.selector = match selection_settings {
             SelectionSettings::DetectorOnly => Arc::new(DetectorOnly::new()),
             SelectionSettings::NoiseScoreFilter {
                 scoring_method,
                 threshold,
-            } => Arc::new(NoiseScoreFilter::new(scoring_method, threshold)),
+                max_peaks,
+                noise_estimator,
+            } => Arc::new(NoiseScoreFilter::new(
+                scoring_method,
+                threshold,
+                max_peaks,
+                noise_estimator,
+            )?),
         };
 
         Ok(())
@@ -390,11 +780,24 @@ impl Deconvoluter {
     /// # }
     /// ```
     pub fn set_fitting_settings(&mut self, fitting_settings: FittingSettings) -> Result<()> {
-        fitting_settings.validate()?;
         self.fitter = match fitting_settings {
             FittingSettings::Analytical { iterations } => {
-                Arc::new(FitterAnalytical::new(iterations))
+                Arc::new(FitterAnalytical::new(iterations)?)
             }
+            FittingSettings::Gaussian { iterations } => Arc::new(FitterGaussian::new(iterations)?),
+            FittingSettings::LevenbergMarquardt {
+                max_iterations,
+                tolerance,
+                lambda_init,
+            } => Arc::new(FitterLevenbergMarquardt::new(
+                max_iterations,
+                tolerance,
+                lambda_init,
+            )?),
+            FittingSettings::PseudoVoigt {
+                iterations,
+                eta_init,
+            } => Arc::new(FitterPseudoVoigt::new(iterations, eta_init)?),
         };
 
         Ok(())
@@ -491,128 +894,161 @@ impl Deconvoluter {
         self.ignore_regions = None;
     }
 
-    /// Deconvolutes the provided spectrum into individual signals.
+    /// Adds a region in which the peak selection threshold is lowered.
+    ///
+    /// Some regions of a spectrum are known to contain weak signals of
+    /// interest, which the global noise threshold would discard. Within a
+    /// boost region, the threshold of the [`NoiseScoreFilter`] is divided by
+    /// the given factor, such that these signals are kept. If a peak lies
+    /// within multiple boost regions, the largest factor is used. Other peak
+    /// selection methods are not affected.
+    ///
+    /// [`NoiseScoreFilter`]: SelectionSettings::NoiseScoreFilter
     ///
     /// # Errors
     ///
-    /// During the deconvolution process, the algorithm relies on finding peaks
-    /// in the `Spectrum`. If no peaks are found, an error is returned. The
-    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
-    /// out noise within the signal region. If no peaks are found outside or
-    /// within the signal region, an error is returned.
+    /// An error is returned if the start or end value is not finite or if they
+    /// are (nearly) equal. The factor must be finite and at least 1.
     ///
     /// # Example
     ///
     /// ```
-    /// use metabodecon::deconvolution::{Deconvoluter, Deconvolution, Lorentzian};
-    /// use metabodecon::spectrum::Bruker;
+    /// use metabodecon::deconvolution::Deconvoluter;
     ///
     /// # fn main() -> metabodecon::Result<()> {
-    /// // Read a spectrum in Bruker TopSpin format.
-    /// let path = "path/to/spectrum";
-    /// # let path = "../data/bruker/blood/blood_01";
-    /// let spectrum = Bruker::read_spectrum(
-    ///     path,
-    ///     // Experiment number
-    ///     10,
-    ///     // Processing number
-    ///     10,
-    ///     // Signal boundaries
-    ///     (-2.2, 11.8),
-    /// )?;
+    /// let mut deconvoluter = Deconvoluter::default();
     ///
-    /// // Deconvolute the spectrum.
-    /// let deconvoluter = Deconvoluter::default();
-    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // Keep weaker peaks between 3.4 and 3.45 ppm.
+    /// deconvoluter.add_boost_region((3.4, 3.45), 4.0)?;
+    /// assert_eq!(deconvoluter.boost_regions().unwrap().len(), 1);
+    ///
+    /// // The factor may not be smaller than 1.
+    /// assert!(deconvoluter.add_boost_region((3.5, 3.55), 0.5).is_err());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn deconvolute_spectrum(&self, spectrum: &Spectrum) -> Result<Deconvolution> {
-        let mut intensities = spectrum.intensities().to_vec();
-        self.smoother.smooth_values(&mut intensities);
-        let ignore_regions = self.ignore_region_indices(spectrum);
-        let peaks = self.selector.select_peaks(
-            &intensities,
-            spectrum.signal_boundaries_indices(),
-            ignore_regions.as_deref(),
-        )?;
-        let lorentzians = self.fitter.fit_lorentzian(spectrum, &peaks);
-        let mse = self.compute_mse(
-            spectrum,
-            Lorentzian::superposition_vec(spectrum.chemical_shifts(), &lorentzians),
-        );
+    pub fn add_boost_region(&mut self, region: (f64, f64), factor: f64) -> Result<()> {
+        if !region.0.is_finite()
+            || !region.1.is_finite()
+            || f64::abs(region.0 - region.1) < crate::CHECK_PRECISION
+            || !factor.is_finite()
+            || factor < 1.0
+        {
+            return Err(Error::new(Kind::InvalidBoostRegion { region, factor }).into());
+        }
+        let region = (f64::min(region.0, region.1), f64::max(region.0, region.1));
+        self.boost_regions
+            .get_or_insert_with(Vec::new)
+            .push((region, factor));
 
-        Ok(Deconvolution::new(
-            lorentzians,
-            self.smoother.settings(),
-            self.selector.settings(),
-            self.fitter.settings(),
-            mse,
-        ))
+        Ok(())
     }
 
-    /// Deconvolutes the provided spectrum into individual signals in parallel.
-    ///
-    /// # Errors
-    ///
-    /// During the deconvolution process, the algorithm relies on finding peaks
-    /// in the `Spectrum`. If no peaks are found, an error is returned. The
-    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
-    /// out noise within the signal region. If no peaks are found outside or
-    /// within the signal region, an error is returned.
+    /// Clears the regions in which the peak selection threshold is lowered.
     ///
     /// # Example
     ///
     /// ```
     /// use metabodecon::deconvolution::Deconvoluter;
-    /// use metabodecon::spectrum::Bruker;
     ///
     /// # fn main() -> metabodecon::Result<()> {
-    /// // Read a spectrum in Bruker TopSpin format.
-    /// let path = "path/to/spectrum";
-    /// # let path = "../data/bruker/blood/blood_01";
-    /// let spectrum = Bruker::read_spectrum(
-    ///     path,
-    ///     // Experiment number
-    ///     10,
-    ///     // Processing number
-    ///     10,
-    ///     // Signal boundaries
-    ///     (-2.2, 11.8),
-    /// )?;
+    /// let mut deconvoluter = Deconvoluter::default();
     ///
-    /// // Deconvolute the spectrum in parallel.
-    /// let deconvoluter = Deconvoluter::default();
-    /// let deconvolution = deconvoluter.par_deconvolute_spectrum(&spectrum)?;
+    /// deconvoluter.add_boost_region((3.4, 3.45), 4.0)?;
+    /// deconvoluter.clear_boost_regions();
+    /// assert!(deconvoluter.boost_regions().is_none());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "parallel")]
-    pub fn par_deconvolute_spectrum(&self, spectrum: &Spectrum) -> Result<Deconvolution> {
-        let mut intensities = spectrum.intensities().to_vec();
-        self.smoother.smooth_values(&mut intensities);
-        let ignore_regions = self.ignore_region_indices(spectrum);
-        let peaks = self.selector.select_peaks(
-            &intensities,
-            spectrum.signal_boundaries_indices(),
-            ignore_regions.as_deref(),
-        )?;
-        let lorentzians = self.fitter.par_fit_lorentzian(spectrum, &peaks);
-        let mse = self.compute_mse(
-            spectrum,
-            Lorentzian::par_superposition_vec(spectrum.chemical_shifts(), &lorentzians),
-        );
-
-        Ok(Deconvolution::new(
-            lorentzians,
-            self.smoother.settings(),
-            self.selector.settings(),
-            self.fitter.settings(),
-            mse,
-        ))
+    pub fn clear_boost_regions(&mut self) {
+        self.boost_regions = None;
     }
 
-    /// Deconvolutes the provided spectra into individual signals.
+    /// Sets the region used to estimate the noise level.
+    ///
+    /// By default, the noise level is estimated from the peaks outside the
+    /// signal boundaries. Some spectra contain artifacts there, such as solvent
+    /// tails or reference signals, which inflate the estimate. In that case, a
+    /// region that only contains noise can be set explicitly. This only affects
+    /// the [`NoiseScoreFilter`] peak selection method.
+    ///
+    /// [`NoiseScoreFilter`]: SelectionSettings::NoiseScoreFilter
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the start or end value is not finite or if they
+    /// are (nearly) equal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut deconvoluter = Deconvoluter::default();
+    ///
+    /// deconvoluter.set_noise_region((10.0, 11.0))?;
+    /// assert!(deconvoluter.noise_region().is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_noise_region(&mut self, region: (f64, f64)) -> Result<()> {
+        if !region.0.is_finite()
+            || !region.1.is_finite()
+            || f64::abs(region.0 - region.1) < crate::CHECK_PRECISION
+        {
+            return Err(Error::new(Kind::InvalidNoiseRegion { region }).into());
+        }
+        self.noise_region = Some((f64::min(region.0, region.1), f64::max(region.0, region.1)));
+
+        Ok(())
+    }
+
+    /// Clears the region used to estimate the noise level, such that the peaks
+    /// outside the signal boundaries are used again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut deconvoluter = Deconvoluter::default();
+    ///
+    /// deconvoluter.set_noise_region((10.0, 11.0))?;
+    /// deconvoluter.clear_noise_region();
+    /// assert!(deconvoluter.noise_region().is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_noise_region(&mut self) {
+        self.noise_region = None;
+    }
+
+    /// Sets the weighting of the residuals used to compute the MSE.
+    ///
+    /// By default, all residuals within the signal region are weighted
+    /// equally. See [`MseWeighting`] for the available options. The weighting
+    /// is recorded in the resulting [`Deconvolution`] and also affects the MSE
+    /// that is minimized by [`optimize_settings`].
+    ///
+    /// [`optimize_settings`]: Deconvoluter::optimize_settings
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, MseWeighting};
+    ///
+    /// let mut deconvoluter = Deconvoluter::default();
+    ///
+    /// deconvoluter.set_mse_weighting(MseWeighting::IntensityWeighted);
+    /// assert_eq!(deconvoluter.mse_weighting(), MseWeighting::IntensityWeighted);
+    /// ```
+    pub fn set_mse_weighting(&mut self, mse_weighting: MseWeighting) {
+        self.mse_weighting = mse_weighting;
+    }
+
+    /// Deconvolutes the provided spectrum into individual signals.
     ///
     /// # Errors
     ///
@@ -629,10 +1065,10 @@ impl Deconvoluter {
     /// use metabodecon::spectrum::Bruker;
     ///
     /// # fn main() -> metabodecon::Result<()> {
-    /// // Read all spectra from Bruker TopSpin format directories within the root.
-    /// let path = "path/to/root";
-    /// # let path = "../data/bruker/blood";
-    /// let spectra = Bruker::read_spectra(
+    /// // Read a spectrum in Bruker TopSpin format.
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/blood/blood_01";
+    /// let spectrum = Bruker::read_spectrum(
     ///     path,
     ///     // Experiment number
     ///     10,
@@ -642,25 +1078,28 @@ impl Deconvoluter {
     ///     (-2.2, 11.8),
     /// )?;
     ///
-    /// // Deconvolute the spectra.
+    /// // Deconvolute the spectrum.
     /// let deconvoluter = Deconvoluter::default();
-    /// let deconvolution = deconvoluter.deconvolute_spectra(&spectra)?;
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn deconvolute_spectra<S: AsRef<Spectrum>>(
-        &self,
-        spectra: &[S],
-    ) -> Result<Vec<Deconvolution>> {
-        let deconvolutions = spectra
-            .iter()
-            .map(|spectrum| self.deconvolute_spectrum(spectrum.as_ref()))
-            .collect::<Result<Vec<Deconvolution>>>()?;
-
-        Ok(deconvolutions)
+    pub fn deconvolute_spectrum(&self, spectrum: &Spectrum) -> Result<Deconvolution> {
+        self.deconvolute_spectrum_using(
+            spectrum,
+            self.smoother.as_ref(),
+            self.selector.as_ref(),
+            self.fitter.as_ref(),
+            None,
+        )
     }
 
-    /// Deconvolutes the provided spectra into individual signals in parallel.
+    /// Deconvolutes the provided spectrum into individual signals in parallel.
+    ///
+    /// Peak detection is parallelized across the segments of the spectrum
+    /// delimited by the ignore regions, and fitting is parallelized across the
+    /// selected peaks. The result is identical to
+    /// [`deconvolute_spectrum`](Deconvoluter::deconvolute_spectrum).
     ///
     /// # Errors
     ///
@@ -673,14 +1112,14 @@ impl Deconvoluter {
     /// # Example
     ///
     /// ```
-    /// use metabodecon::deconvolution::{Deconvoluter, Deconvolution, Lorentzian};
+    /// use metabodecon::deconvolution::Deconvoluter;
     /// use metabodecon::spectrum::Bruker;
     ///
     /// # fn main() -> metabodecon::Result<()> {
-    /// // Read all spectra from Bruker TopSpin format directories within the root.
-    /// let path = "path/to/root";
-    /// # let path = "../data/bruker/blood";
-    /// let spectra = Bruker::read_spectra(
+    /// // Read a spectrum in Bruker TopSpin format.
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/blood/blood_01";
+    /// let spectrum = Bruker::read_spectrum(
     ///     path,
     ///     // Experiment number
     ///     10,
@@ -690,56 +1129,165 @@ impl Deconvoluter {
     ///     (-2.2, 11.8),
     /// )?;
     ///
-    /// // Deconvolute the spectra.
+    /// // Deconvolute the spectrum in parallel.
     /// let deconvoluter = Deconvoluter::default();
-    /// let deconvolution = deconvoluter.par_deconvolute_spectra(&spectra)?;
+    /// let deconvolution = deconvoluter.par_deconvolute_spectrum(&spectrum)?;
     /// # Ok(())
     /// # }
     /// ```
     #[cfg(feature = "parallel")]
-    pub fn par_deconvolute_spectra<S: AsRef<Spectrum> + Send + Sync>(
+    pub fn par_deconvolute_spectrum(&self, spectrum: &Spectrum) -> Result<Deconvolution> {
+        self.par_deconvolute_spectrum_using(
+            spectrum,
+            self.smoother.as_ref(),
+            self.selector.as_ref(),
+            self.fitter.as_ref(),
+            None,
+        )
+    }
+
+    /// Deconvolutes the provided spectrum within the given region only.
+    ///
+    /// Peak selection and fitting are restricted to the peaks within the
+    /// region, which is useful to deconvolute a few multiplets without
+    /// processing the entire spectrum. The noise level is still estimated from
+    /// the entire spectrum, and the MSE is computed within the intersection of
+    /// the region and the signal region. The deconvoluted signals are located
+    /// at their absolute chemical shifts.
+    ///
+    /// Note that signals just outside the region are not fitted, so their
+    /// tails may slightly distort the signals at the edges of the region.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the start or end value of the region is not
+    /// finite or if they are (nearly) equal. Otherwise, the same errors as for
+    /// [`deconvolute_spectrum`] are possible, where the signal region is
+    /// restricted to the given region.
+    ///
+    /// [`deconvolute_spectrum`]: Deconvoluter::deconvolute_spectrum
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read a spectrum in Bruker TopSpin format.
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Deconvolute a part of the spectrum.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolution = deconvoluter.deconvolute_region(&spectrum, (3.4, 3.45))?;
+    ///
+    /// for lorentzian in deconvolution.lorentzians() {
+    ///     assert!(lorentzian.maxp() > 3.4 && lorentzian.maxp() < 3.45);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deconvolute_region(
         &self,
-        spectra: &[S],
-    ) -> Result<Vec<Deconvolution>> {
-        let deconvolutions = spectra
-            .par_iter()
-            .map(|spectrum| self.par_deconvolute_spectrum(spectrum.as_ref()))
-            .collect::<Result<Vec<Deconvolution>>>()?;
+        spectrum: &Spectrum,
+        region: (f64, f64),
+    ) -> Result<Deconvolution> {
+        if !region.0.is_finite()
+            || !region.1.is_finite()
+            || f64::abs(region.0 - region.1) < crate::CHECK_PRECISION
+        {
+            return Err(Error::new(Kind::InvalidDeconvolutionRegion { region }).into());
+        }
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let (mut peaks, noise_estimate, _) = self.selected_peaks(
+            spectrum,
+            self.smoother.as_ref(),
+            self.selector.as_ref(),
+            NoiseLevel::Estimated,
+        )?;
+        let region = region_indices(spectrum, region);
+        peaks.retain(|peak| peak.center() >= region.0 && peak.center() < region.1);
+        if peaks.is_empty() {
+            return Err(Error::new(Kind::EmptySignalRegion).into());
+        }
+        let (signals, fit_diagnostics) = self.fitter.fit_with_diagnostics(spectrum, &peaks);
+        let parameter_uncertainties = self
+            .fitter
+            .parameter_uncertainties(spectrum, &signals);
+        let mse = self.compute_mse(
+            spectrum,
+            signals.superposition_vec(spectrum.chemical_shifts()),
+            Some(region),
+        );
+        let deconvolution = signals
+            .into_deconvolution(
+                self.smoother.settings(),
+                self.selector.settings(),
+                self.fitter.settings(),
+                mse,
+            )
+            .with_noise_estimate(noise_estimate)
+            .with_mse_weighting(self.mse_weighting)
+            .with_fit_diagnostics(fit_diagnostics)
+            .with_parameter_uncertainties(parameter_uncertainties);
+        // The signals outside the region are not fitted, so the quality is
+        // only checked within the intersection of the region and the signal
+        // region, like the MSE.
+        let (start, end) = spectrum.signal_boundaries_indices();
+        let (start, end) = (usize::max(start, region.0), usize::min(end, region.1));
+        let mut bounded = spectrum.clone();
+        bounded.set_signal_boundaries((
+            spectrum.chemical_shifts()[start],
+            spectrum.chemical_shifts()[end],
+        ))?;
 
-        Ok(deconvolutions)
+        Ok(self.with_warnings(&bounded, deconvolution))
     }
 
-    /// Optimizes the deconvolution settings.
+    /// Refines a previous deconvolution of the provided spectrum.
     ///
-    /// To determine the optimal deconvolution settings, a reference spectrum is
-    /// used to evaluate the mean squared error (MSE) across different
-    /// combinations of smoothing, peak selection, and fitting settings. Each
-    /// stage of the algorithm is tested using a predefined set of settings,
-    /// selected from a broader range that was assessed on spectra with varying
-    /// resolutions, noise levels, and peak counts. The combination yielding the
-    /// lowest MSE is chosen as the optimal configuration.
+    /// Peak detection and selection are skipped, and the Lorentzians of the
+    /// previous [`Deconvolution`] are used as the starting parameters of the
+    /// fitting step, which is run with the fitting settings of this
+    /// `Deconvoluter`. This is useful to continue a fit that was stopped early,
+    /// or to polish an analytical fit with the Levenberg-Marquardt method,
+    /// without repeating the entire deconvolution.
+    ///
+    /// The baseline correction, ignore regions and MSE weighting of this
+    /// `Deconvoluter` are applied as usual. The returned `Deconvolution`
+    /// records the fitting settings of this `Deconvoluter`, while the smoothing
+    /// and peak selection settings, as well as the noise estimate, are taken
+    /// from the previous `Deconvolution`, since these steps are not repeated.
     ///
     /// # Errors
     ///
-    /// During the deconvolution process, the algorithm relies on finding peaks
-    /// in the `Spectrum`. If no peaks are found, an error is returned. The
-    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
-    /// out noise within the signal region. If no peaks are found outside or
-    /// within the signal region, an error is returned. If any parameter
-    /// combination returns an error, there is likely an issue with the data,
-    /// so the optimization process is aborted and the error is returned.
+    /// Refinement is only supported for Lorentzian signals fitted with the
+    /// analytical or Levenberg-Marquardt method. An error is returned if the
+    /// fitting settings use a different method, or if the previous
+    /// `Deconvolution` doesn't contain any Lorentzians.
     ///
     /// # Example
     ///
     /// ```
-    /// use metabodecon::deconvolution::{Deconvoluter, Deconvolution, Lorentzian};
+    /// use metabodecon::deconvolution::{Deconvoluter, FittingSettings};
     /// use metabodecon::spectrum::Bruker;
     ///
     /// # fn main() -> metabodecon::Result<()> {
-    /// // Read all spectra from Bruker TopSpin format directories within the root.
-    /// let path = "path/to/root";
-    /// # let path = "../data/bruker/sim";
-    /// let spectra = Bruker::read_spectra(
+    /// // Read a spectrum in Bruker TopSpin format.
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(
     ///     path,
     ///     // Experiment number
     ///     10,
@@ -749,368 +1297,3622 @@ impl Deconvoluter {
     ///     (3.339, 3.553),
     /// )?;
     ///
-    /// // Optimize the deconvolution settings using the first spectrum as a reference.
+    /// // Deconvolute the spectrum with the default settings.
     /// let mut deconvoluter = Deconvoluter::default();
-    /// deconvoluter.optimize_settings(&spectra[0])?;
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
     ///
-    /// // Deconvolute the spectra with the optimized settings.
-    /// let deconvolution = deconvoluter.par_deconvolute_spectra(&spectra)?;
+    /// // Refine the fit with the Levenberg-Marquardt method.
+    /// deconvoluter.set_fitting_settings(FittingSettings::LevenbergMarquardt {
+    ///     max_iterations: 50,
+    ///     tolerance: 1e-8,
+    ///     lambda_init: 1e-3,
+    /// })?;
+    /// let refined = deconvoluter.refine(&spectrum, &deconvolution)?;
+    /// assert!(refined.mse() <= deconvolution.mse());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "parallel")]
-    pub fn optimize_settings(&mut self, reference: &Spectrum) -> Result<f64> {
-        let smoothing_settings = (2..=10)
-            .flat_map(|iterations| {
-                (3..=7)
-                    .step_by(2)
-                    .map(move |window_size| SmoothingSettings::MovingAverage {
-                        iterations,
-                        window_size,
-                    })
-            })
-            .collect::<Vec<SmoothingSettings>>();
-        let selection_settings = (0..10)
-            .map(|coefficient| SelectionSettings::NoiseScoreFilter {
-                scoring_method: ScoringMethod::MinimumSum,
-                threshold: 5.0 + (coefficient as f64) * (8.0 - 5.0) / 9.0,
+    pub fn refine(&self, spectrum: &Spectrum, previous: &Deconvolution) -> Result<Deconvolution> {
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let refined = match previous.lorentzians().is_empty() {
+            true => None,
+            false => self
+                .fitter
+                .refine(spectrum, previous.lorentzians()),
+        };
+        let Some((signals, fit_diagnostics)) = refined else {
+            return Err(Error::new(Kind::UnsupportedRefinement {
+                settings: self.fitter.settings(),
             })
-            .collect::<Vec<SelectionSettings>>();
-        let fitting_settings = (5..=15)
-            .step_by(5)
-            .map(|iterations| FittingSettings::Analytical { iterations })
-            .collect::<Vec<FittingSettings>>();
-
-        let optimal_settings = smoothing_settings
-            .par_iter()
-            .map(|smoothing| {
-                let mut deconvoluter = self.clone();
-                deconvoluter
-                    .set_smoothing_settings(*smoothing)
-                    .unwrap();
-
-                selection_settings
-                    .iter()
-                    .map(|selection| {
-                        deconvoluter
-                            .set_selection_settings(*selection)
-                            .unwrap();
-
-                        fitting_settings
-                            .iter()
-                            .map(|fitting| {
-                                deconvoluter
-                                    .set_fitting_settings(*fitting)
-                                    .unwrap();
-                                let deconvolution = deconvoluter.deconvolute_spectrum(reference)?;
+            .into());
+        };
+        let parameter_uncertainties = self
+            .fitter
+            .parameter_uncertainties(spectrum, &signals);
+        let mse = self.compute_mse(
+            spectrum,
+            signals.superposition_vec(spectrum.chemical_shifts()),
+            None,
+        );
 
-                                Ok((deconvolution.mse(), *fitting, *selection, *smoothing))
-                            })
-                            .collect::<Result<Vec<_>>>()
-                    })
-                    .collect::<Result<Vec<_>>>()
-            })
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .flatten()
-            .flatten()
-            .min_by(|(mse_1, ..), (mse_2, ..)| f64::partial_cmp(mse_1, mse_2).unwrap())
-            .unwrap();
-        let (mse, fitting, selection, smoothing) = optimal_settings;
-        self.set_smoothing_settings(smoothing)?;
-        self.set_selection_settings(selection)?;
-        self.set_fitting_settings(fitting)?;
+        let deconvolution = signals
+            .into_deconvolution(
+                previous.smoothing_settings(),
+                previous.selection_settings(),
+                self.fitter.settings(),
+                mse,
+            )
+            .with_noise_estimate(previous.noise_estimate())
+            .with_mse_weighting(self.mse_weighting)
+            .with_fit_diagnostics(fit_diagnostics)
+            .with_parameter_uncertainties(parameter_uncertainties);
 
-        Ok(mse)
+        Ok(self.with_warnings(spectrum, deconvolution))
     }
 
-    /// Internal helper function to compute the MSE within the signal region.
-    fn compute_mse(&self, spectrum: &Spectrum, superpositions: Vec<f64>) -> f64 {
-        let regions = match self.ignore_region_indices(spectrum) {
-            Some(ignore_regions) => {
-                let iter = std::iter::once(spectrum.signal_boundaries_indices().0)
-                    .chain(
-                        ignore_regions
-                            .iter()
-                            .flat_map(|(start, end)| vec![*start, *end]),
-                    )
-                    .chain(std::iter::once(spectrum.signal_boundaries_indices().1));
-
-                iter.clone()
-                    .step_by(2)
-                    .zip(iter.skip(1).step_by(2))
-                    .collect::<Vec<(usize, usize)>>()
-            }
-            None => vec![spectrum.signal_boundaries_indices()],
-        };
-        let residuals = regions
-            .iter()
-            .map(|(start, end)| {
-                superpositions[*start..*end]
-                    .iter()
-                    .zip(spectrum.intensities()[*start..*end].iter())
-                    .map(|(superposition, intensity)| (superposition - intensity).powi(2))
-                    .sum::<f64>()
-            })
-            .sum::<f64>();
-        let length = regions
-            .iter()
-            .map(|(start, end)| end - start)
-            .sum::<usize>();
+    /// Detects and selects peaks in the provided spectrum without fitting any
+    /// peak shapes to them.
+    ///
+    /// This runs the baseline correction, smoothing and peak selection steps
+    /// of the deconvolution, but skips the fitting. Each selected peak is
+    /// described by a [`PeakInfo`], which contains the position of its center,
+    /// the intensity at the center and the score assigned by the peak
+    /// selection algorithm.
+    ///
+    /// # Errors
+    ///
+    /// The algorithm relies on finding peaks in the `Spectrum`. If no peaks are
+    /// found, an error is returned. The peaks outside the signal boundaries of
+    /// the `Spectrum` are used to filter out noise within the signal region. If
+    /// no peaks are found outside or within the signal region, an error is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read a spectrum in Bruker TopSpin format.
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Detect the peaks without fitting them.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let peaks = deconvoluter.detect_peaks(&spectrum)?;
+    ///
+    /// for peak in peaks {
+    ///     println!("{} ppm: {}", peak.chemical_shift(), peak.intensity());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_peaks(&self, spectrum: &Spectrum) -> Result<Vec<PeakInfo>> {
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let (peaks, _, _) = self.selected_peaks(
+            spectrum,
+            self.smoother.as_ref(),
+            self.selector.as_ref(),
+            NoiseLevel::Estimated,
+        )?;
 
-        residuals / (length as f64)
+        Ok(peaks
+            .iter()
+            .map(|peak| PeakInfo::new(peak, spectrum))
+            .collect())
     }
 
-    /// Internal helper function to convert the ignore regions to indices.
-    fn ignore_region_indices(&self, spectrum: &Spectrum) -> Option<Vec<(usize, usize)>> {
-        if let Some(ignore_regions) = self.ignore_regions.as_ref() {
-            let step = spectrum.step();
-            let first = spectrum.chemical_shifts()[0];
-            let boundaries = spectrum.signal_boundaries();
-            let (lower_boundary, upper_boundary) = (
-                f64::min(boundaries.0, boundaries.1),
-                f64::max(boundaries.0, boundaries.1),
-            );
-            let boundary_indices = spectrum.signal_boundaries_indices();
-            let (lower, upper) = (
-                usize::min(boundary_indices.0, boundary_indices.1),
-                usize::max(boundary_indices.0, boundary_indices.1),
-            );
-            let indices = ignore_regions
-                .iter()
-                .filter(|(start, end)| {
-                    !(*start < lower_boundary && *end < lower_boundary
-                        || *start > upper_boundary && *end > upper_boundary)
-                })
-                .filter_map(|(start, end)| {
-                    let first_index = usize::max(((*start - first) / step).floor() as usize, lower);
-                    let second_index = usize::min(((*end - first) / step).ceil() as usize, upper);
-                    let boundaries = (
-                        usize::min(first_index, second_index),
-                        usize::max(first_index, second_index),
-                    );
-                    if boundaries.0 < boundaries.1 - 1 {
-                        Some(boundaries)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+    /// Detects and selects peaks in the provided spectrum and integrates the
+    /// measured intensities around each of them, without fitting any peak
+    /// shapes.
+    ///
+    /// This is a fast alternative to the deconvolution for a first-pass
+    /// quantification. The selected peaks are the same as with
+    /// [`detect_peaks`]. Each peak extends from its center to the nearest
+    /// local minimum of the smoothed intensities on either side, without
+    /// entering an ignore region or the bounds of a neighbouring peak. Within
+    /// these bounds, the intensities after baseline correction, but before
+    /// smoothing, are integrated with the trapezoidal rule. The areas are in
+    /// the same units as the integrals of the fitted signals, such as
+    /// [`Lorentzian::integral`].
+    ///
+    /// As no model is fitted, the tails of each signal beyond its bounds are
+    /// not included, and overlapping signals are split at the local minimum
+    /// between them rather than resolved. The areas are therefore only
+    /// accurate for signals that are separated by a baseline region.
+    ///
+    /// [`detect_peaks`]: Deconvoluter::detect_peaks
+    /// [`Lorentzian::integral`]: crate::deconvolution::Lorentzian::integral
+    ///
+    /// # Errors
+    ///
+    /// The algorithm relies on finding peaks in the `Spectrum`. If no peaks are
+    /// found, an error is returned. The peaks outside the signal boundaries of
+    /// the `Spectrum` are used to filter out noise within the signal region. If
+    /// no peaks are found outside or within the signal region, an error is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    ///
+    /// // Integrate the peaks without fitting them.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let areas = deconvoluter.integrate_peaks(&spectrum)?;
+    ///
+    /// for (peak, area) in areas {
+    ///     println!("{} ppm: {}", peak.chemical_shift(), area);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn integrate_peaks(&self, spectrum: &Spectrum) -> Result<Vec<(PeakInfo, f64)>> {
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let (peaks, _, smoothed) = self.selected_peaks(
+            spectrum,
+            self.smoother.as_ref(),
+            self.selector.as_ref(),
+            NoiseLevel::Estimated,
+        )?;
+        if peaks.is_empty() {
+            return Err(Error::new(Kind::NoPeaksDetected).into());
+        }
+        let intensities = spectrum.intensities();
+        let step = spectrum.step().abs();
+        let ignore_regions = self
+            .ignore_region_indices(spectrum)
+            .unwrap_or_default();
+        let ignored = |index: usize| {
+            ignore_regions
+                .iter()
+                .any(|(start, end)| *start <= index && index <= *end)
+        };
+        let mut previous = 0;
+        let bounds = peaks
+            .iter()
+            .map(|peak| {
+                let mut left = peak.center();
+                while left > previous && smoothed[left - 1] <= smoothed[left] && !ignored(left - 1)
+                {
+                    left -= 1;
+                }
+                let mut right = peak.center();
+                while right < smoothed.len() - 1
+                    && smoothed[right + 1] <= smoothed[right]
+                    && !ignored(right + 1)
+                {
+                    right += 1;
+                }
+                previous = right;
+
+                (left, right)
+            })
+            .collect::<Vec<(usize, usize)>>();
+
+        Ok(peaks
+            .iter()
+            .zip(bounds)
+            .map(|(peak, (left, right))| {
+                let area = intensities[left..=right]
+                    .windows(2)
+                    .map(|pair| 0.5 * (pair[0] + pair[1]) * step)
+                    .sum::<f64>();
+                (PeakInfo::new(peak, spectrum), area)
+            })
+            .collect())
+    }
+
+    /// Returns the intensities of the provided spectrum after the baseline
+    /// correction and smoothing steps of the deconvolution.
+    ///
+    /// These are the intensities the peak selection operates on, which is
+    /// useful to visually compare the raw and smoothed signals when tuning the
+    /// smoothing settings. Neither peak selection nor fitting are run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, SmoothingSettings};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read a spectrum in Bruker TopSpin format.
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Without smoothing, the intensities are unchanged.
+    /// let mut deconvoluter = Deconvoluter::default();
+    /// deconvoluter.set_smoothing_settings(SmoothingSettings::Identity)?;
+    /// let smoothed = deconvoluter.smoothed_intensities(&spectrum);
+    /// assert_eq!(smoothed, spectrum.intensities());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn smoothed_intensities(&self, spectrum: &Spectrum) -> Vec<f64> {
+        let spectrum = self.correct_baseline(spectrum);
+        let mut intensities = spectrum.intensities().to_vec();
+        self.smoother.smooth_values(&mut intensities);
+
+        intensities
+    }
+
+    /// Deconvolutes the provided spectra into individual signals.
+    ///
+    /// # Errors
+    ///
+    /// During the deconvolution process, the algorithm relies on finding peaks
+    /// in the `Spectrum`. If no peaks are found, an error is returned. The
+    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
+    /// out noise within the signal region. If no peaks are found outside or
+    /// within the signal region, an error is returned.
+    ///
+    /// The first error that occurs is wrapped with the position of the failed
+    /// spectrum within the batch, see [`SpectrumInBatch`].
+    ///
+    /// [`SpectrumInBatch`]: crate::deconvolution::error::Kind::SpectrumInBatch
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, Deconvolution, Lorentzian};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/blood";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    ///
+    /// // Deconvolute the spectra.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolution = deconvoluter.deconvolute_spectra(&spectra)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deconvolute_spectra<S: AsRef<Spectrum>>(
+        &self,
+        spectra: &[S],
+    ) -> Result<Vec<Deconvolution>> {
+        let deconvolutions = spectra
+            .iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                self.deconvolute_spectrum(spectrum.as_ref())
+                    .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals, ignoring
+    /// additional regions that are computed for each spectrum.
+    ///
+    /// The `ignore_fn` closure is invoked with each spectrum and returns the
+    /// regions to ignore for that spectrum, in addition to the ignore regions
+    /// of the `Deconvoluter`. This allows masking signals whose position varies
+    /// between samples, such as the water signal. This is otherwise identical
+    /// to [`Deconvoluter::deconvolute_spectra`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if one of the computed ignore regions is invalid,
+    /// see [`Deconvoluter::add_ignore_region`]. See
+    /// [`Deconvoluter::deconvolute_spectra`] for the other errors. All errors
+    /// are wrapped with the position of the failed spectrum within the batch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Ignore the most intense signal of each spectrum.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions = deconvoluter.deconvolute_spectra_with(&spectra, |spectrum| {
+    ///     let maximum = spectrum
+    ///         .intensities()
+    ///         .iter()
+    ///         .enumerate()
+    ///         .max_by(|a, b| a.1.total_cmp(b.1))
+    ///         .map(|(index, _)| spectrum.chemical_shifts()[index])
+    ///         .unwrap();
+    ///     vec![(maximum - 0.005, maximum + 0.005)]
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deconvolute_spectra_with<S, F>(
+        &self,
+        spectra: &[S],
+        ignore_fn: F,
+    ) -> Result<Vec<Deconvolution>>
+    where
+        S: AsRef<Spectrum>,
+        F: Fn(&Spectrum) -> Vec<(f64, f64)>,
+    {
+        let deconvolutions = spectra
+            .iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                let spectrum = spectrum.as_ref();
+                let mut deconvoluter = self.clone();
+                ignore_fn(spectrum)
+                    .into_iter()
+                    .try_for_each(|region| deconvoluter.add_ignore_region(region))
+                    .and_then(|_| deconvoluter.deconvolute_spectrum(spectrum))
+                    .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals and matches
+    /// them to a template of expected signal positions.
+    ///
+    /// The resulting [`Deconvolution`]s contain exactly one [`Lorentzian`] per
+    /// position of the template, in the same order, such that the signals at
+    /// the same index correspond to each other across all spectra. Each
+    /// position is matched to the closest signal within the tolerance, where
+    /// closer pairs are matched first and each signal is matched at most once.
+    /// Positions without a matching signal are filled with placeholders of
+    /// zero area, and unmatched signals are discarded. Signals fitted with
+    /// other peak shapes are not matched.
+    ///
+    /// The MSE of the [`Deconvolution`]s refers to the fit of all signals,
+    /// and the fit diagnostics, parameter uncertainties and warnings are not
+    /// retained. Labels are kept for the matched signals.
+    ///
+    /// [`Lorentzian`]: crate::deconvolution::Lorentzian
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any of the positions is not finite, or if the
+    /// tolerance is not finite and positive. See
+    /// [`Deconvoluter::deconvolute_spectra`] for the other errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Match the signals within 0.005 ppm of the expected positions.
+    /// let template = [3.37, 3.4, 3.45, 3.5, 3.52];
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions = deconvoluter.deconvolute_spectra_templated(&spectra, &template, 0.005)?;
+    ///
+    /// for deconvolution in &deconvolutions {
+    ///     assert_eq!(deconvolution.len(), template.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deconvolute_spectra_templated<S: AsRef<Spectrum>>(
+        &self,
+        spectra: &[S],
+        template: &[f64],
+        tolerance: f64,
+    ) -> Result<Vec<Deconvolution>> {
+        if !tolerance.is_finite()
+            || tolerance <= 0.0
+            || template
+                .iter()
+                .any(|position| !position.is_finite())
+        {
+            return Err(Error::new(Kind::InvalidTemplate {
+                template: template.to_vec(),
+                tolerance,
+            })
+            .into());
+        }
+        let deconvolutions = self
+            .deconvolute_spectra(spectra)?
+            .iter()
+            .map(|deconvolution| deconvolution.matched_to_template(template, tolerance))
+            .collect();
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals, without
+    /// aborting the batch if the deconvolution of a spectrum fails.
+    ///
+    /// Returns one result per spectrum, in the same order as the spectra. See
+    /// [`Deconvoluter::deconvolute_spectra`] for details on the errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Deconvolute the spectra and report the ones that failed.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions = deconvoluter
+    ///     .try_deconvolute_spectra(&spectra)
+    ///     .into_iter()
+    ///     .filter_map(|result| match result {
+    ///         Ok(deconvolution) => Some(deconvolution),
+    ///         Err(error) => {
+    ///             eprintln!("{}", error);
+    ///             None
+    ///         }
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_deconvolute_spectra<S: AsRef<Spectrum>>(
+        &self,
+        spectra: &[S],
+    ) -> Vec<Result<Deconvolution>> {
+        spectra
+            .iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                self.deconvolute_spectrum(spectrum.as_ref())
+                    .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect()
+    }
+
+    /// Deconvolutes the provided spectra into individual signals, reporting the
+    /// progress after each spectrum.
+    ///
+    /// The `progress` callback is invoked with the number of completed spectra
+    /// and the total number of spectra after each spectrum was deconvoluted.
+    /// This is otherwise identical to [`Deconvoluter::deconvolute_spectra`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Deconvoluter::deconvolute_spectra`]. If an error occurs, the batch
+    /// is aborted and the callback is not invoked for the failed spectrum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Deconvolute the spectra and print the progress.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions =
+    ///     deconvoluter.deconvolute_spectra_with_progress(&spectra, |completed, total| {
+    ///         println!("{}/{} spectra deconvoluted", completed, total);
+    ///     })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deconvolute_spectra_with_progress<S, F>(
+        &self,
+        spectra: &[S],
+        mut progress: F,
+    ) -> Result<Vec<Deconvolution>>
+    where
+        S: AsRef<Spectrum>,
+        F: FnMut(usize, usize),
+    {
+        let total = spectra.len();
+        let deconvolutions = spectra
+            .iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                let deconvolution = self
+                    .deconvolute_spectrum(spectrum.as_ref())
+                    .map_err(|error| Self::batch_error(index, error))?;
+                progress(index + 1, total);
+
+                Ok(deconvolution)
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals, aborting if
+    /// the cancellation flag is set.
+    ///
+    /// The flag is checked before each spectrum is started and between the
+    /// iterations of the fit, so a long fit is aborted without running to
+    /// completion. The smoothing and peak selection of a spectrum in progress
+    /// are not interrupted. This is otherwise identical to
+    /// [`Deconvoluter::deconvolute_spectra`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Kind::Cancelled`] if the flag was set before the fit of the
+    /// last spectrum was completed. See [`Deconvoluter::deconvolute_spectra`]
+    /// for the other errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // The flag may be set from another thread to abort the batch.
+    /// let cancel = AtomicBool::new(false);
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions = deconvoluter.deconvolute_spectra_cancellable(&spectra, &cancel)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deconvolute_spectra_cancellable<S: AsRef<Spectrum>>(
+        &self,
+        spectra: &[S],
+        cancel: &AtomicBool,
+    ) -> Result<Vec<Deconvolution>> {
+        let deconvolutions = spectra
+            .iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(Error::new(Kind::Cancelled).into());
+                }
+                self.deconvolute_spectrum_using(
+                    spectrum.as_ref(),
+                    self.smoother.as_ref(),
+                    self.selector.as_ref(),
+                    self.fitter.as_ref(),
+                    Some(cancel),
+                )
+                .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals in parallel.
+    ///
+    /// # Errors
+    ///
+    /// During the deconvolution process, the algorithm relies on finding peaks
+    /// in the `Spectrum`. If no peaks are found, an error is returned. The
+    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
+    /// out noise within the signal region. If no peaks are found outside or
+    /// within the signal region, an error is returned.
+    ///
+    /// An error that occurs is wrapped with the position of the failed spectrum
+    /// within the batch, see [`SpectrumInBatch`].
+    ///
+    /// [`SpectrumInBatch`]: crate::deconvolution::error::Kind::SpectrumInBatch
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, Deconvolution, Lorentzian};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/blood";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    ///
+    /// // Deconvolute the spectra.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolution = deconvoluter.par_deconvolute_spectra(&spectra)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_deconvolute_spectra<S: AsRef<Spectrum> + Send + Sync>(
+        &self,
+        spectra: &[S],
+    ) -> Result<Vec<Deconvolution>> {
+        let deconvolutions = spectra
+            .par_iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                self.par_deconvolute_spectrum(spectrum.as_ref())
+                    .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals in parallel,
+    /// without aborting the batch if the deconvolution of a spectrum fails.
+    ///
+    /// Returns one result per spectrum, in the same order as the spectra. See
+    /// [`Deconvoluter::par_deconvolute_spectra`] for details on the errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Deconvolute the spectra in parallel and count the ones that failed.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let failed = deconvoluter
+    ///     .par_try_deconvolute_spectra(&spectra)
+    ///     .iter()
+    ///     .filter(|result| result.is_err())
+    ///     .count();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_try_deconvolute_spectra<S: AsRef<Spectrum> + Send + Sync>(
+        &self,
+        spectra: &[S],
+    ) -> Vec<Result<Deconvolution>> {
+        spectra
+            .par_iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                self.par_deconvolute_spectrum(spectrum.as_ref())
+                    .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect()
+    }
+
+    /// Deconvolutes the provided spectra into individual signals in parallel,
+    /// reporting the progress after each spectrum.
+    ///
+    /// The `progress` callback is invoked with the number of completed spectra
+    /// and the total number of spectra after each spectrum was deconvoluted.
+    /// The spectra are completed in an arbitrary order, but the callback is
+    /// guarded by a lock together with the counter of completed spectra, such
+    /// that the reported progress is strictly increasing. This is otherwise
+    /// identical to [`Deconvoluter::par_deconvolute_spectra`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Deconvoluter::par_deconvolute_spectra`]. The callback is not
+    /// invoked for failed spectra.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Deconvolute the spectra in parallel and print the progress.
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions =
+    ///     deconvoluter.par_deconvolute_spectra_with_progress(&spectra, |completed, total| {
+    ///         println!("{}/{} spectra deconvoluted", completed, total);
+    ///     })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_deconvolute_spectra_with_progress<S, F>(
+        &self,
+        spectra: &[S],
+        progress: F,
+    ) -> Result<Vec<Deconvolution>>
+    where
+        S: AsRef<Spectrum> + Send + Sync,
+        F: FnMut(usize, usize) + Send,
+    {
+        let total = spectra.len();
+        let progress = Mutex::new((0, progress));
+        let deconvolutions = spectra
+            .par_iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                let deconvolution = self
+                    .par_deconvolute_spectrum(spectrum.as_ref())
+                    .map_err(|error| Self::batch_error(index, error))?;
+                let mut guard = progress.lock().unwrap();
+                let (completed, callback) = &mut *guard;
+                *completed += 1;
+                callback(*completed, total);
+
+                Ok(deconvolution)
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Deconvolutes the provided spectra into individual signals in parallel,
+    /// aborting if the cancellation flag is set.
+    ///
+    /// The flag is checked before each spectrum is started, such that no new
+    /// work is started once the cancellation was observed, and between the
+    /// iterations of the fit, such that the fits in progress are aborted as
+    /// well. The smoothing and peak selection of the spectra in progress are
+    /// not interrupted. This is otherwise identical to
+    /// [`Deconvoluter::par_deconvolute_spectra`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Kind::Cancelled`] if the flag was set before the fit of the
+    /// last spectrum was completed. See
+    /// [`Deconvoluter::par_deconvolute_spectra`] for the other errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // The flag may be set from another thread to abort the batch.
+    /// let cancel = AtomicBool::new(false);
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolutions = deconvoluter.par_deconvolute_spectra_cancellable(&spectra, &cancel)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_deconvolute_spectra_cancellable<S: AsRef<Spectrum> + Send + Sync>(
+        &self,
+        spectra: &[S],
+        cancel: &AtomicBool,
+    ) -> Result<Vec<Deconvolution>> {
+        let deconvolutions = spectra
+            .par_iter()
+            .enumerate()
+            .map(|(index, spectrum)| {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(Error::new(Kind::Cancelled).into());
+                }
+                self.par_deconvolute_spectrum_using(
+                    spectrum.as_ref(),
+                    self.smoother.as_ref(),
+                    self.selector.as_ref(),
+                    self.fitter.as_ref(),
+                    Some(cancel),
+                )
+                .map_err(|error| Self::batch_error(index, error))
+            })
+            .collect::<Result<Vec<Deconvolution>>>()?;
+
+        Ok(deconvolutions)
+    }
+
+    /// Optimizes the deconvolution settings.
+    ///
+    /// To determine the optimal deconvolution settings, a reference spectrum is
+    /// used to evaluate the mean squared error (MSE) across different
+    /// combinations of smoothing, peak selection, and fitting settings. Each
+    /// stage of the algorithm is tested using a predefined set of settings,
+    /// selected from a broader range that was assessed on spectra with varying
+    /// resolutions, noise levels, and peak counts. The combination yielding the
+    /// lowest MSE is chosen as the optimal configuration.
+    ///
+    /// The predefined settings are those of [`OptimizationGrid::default`]. Use
+    /// [`optimize_settings_with_grid`] to evaluate other candidate settings,
+    /// or [`optimization_report`] to inspect the MSE of every combination.
+    ///
+    /// The smoother is always replaced by the one of the optimal smoothing
+    /// settings. Custom smoothers can't be part of the candidate settings, so
+    /// a smoother set with [`with_smoother`] is discarded.
+    ///
+    /// [`optimize_settings_with_grid`]: Deconvoluter::optimize_settings_with_grid
+    /// [`optimization_report`]: Deconvoluter::optimization_report
+    /// [`with_smoother`]: Deconvoluter::with_smoother
+    ///
+    /// # Errors
+    ///
+    /// During the deconvolution process, the algorithm relies on finding peaks
+    /// in the `Spectrum`. If no peaks are found, an error is returned. The
+    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
+    /// out noise within the signal region. If no peaks are found outside or
+    /// within the signal region, an error is returned. If any parameter
+    /// combination returns an error, there is likely an issue with the data,
+    /// so the optimization process is aborted and the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, Deconvolution, Lorentzian};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Read all spectra from Bruker TopSpin format directories within the root.
+    /// let path = "path/to/root";
+    /// # let path = "../data/bruker/sim";
+    /// let spectra = Bruker::read_spectra(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (3.339, 3.553),
+    /// )?;
+    ///
+    /// // Optimize the deconvolution settings using the first spectrum as a reference.
+    /// let mut deconvoluter = Deconvoluter::default();
+    /// deconvoluter.optimize_settings(&spectra[0])?;
+    ///
+    /// // Deconvolute the spectra with the optimized settings.
+    /// let deconvolution = deconvoluter.par_deconvolute_spectra(&spectra)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn optimize_settings(&mut self, reference: &Spectrum) -> Result<f64> {
+        self.optimize_settings_with_grid(reference, OptimizationGrid::default())
+    }
+
+    /// Optimizes the deconvolution settings over the given candidate settings.
+    ///
+    /// Works like [`optimize_settings`], but evaluates every combination of
+    /// the settings of the given [`OptimizationGrid`] instead of the default
+    /// grid. This allows the search to be narrowed down to save time, or to be
+    /// extended to other smoothing, peak selection, or fitting methods. The
+    /// lowest MSE is returned. A custom smoother is discarded in the same way.
+    ///
+    /// [`optimize_settings`]: Deconvoluter::optimize_settings
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [`optimize_settings`] are possible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvoluter, FittingSettings, NoiseEstimator, OptimizationGrid, ScoringMethod,
+    ///     SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    ///
+    /// // Only tune the smoothing and the threshold of the peak selection.
+    /// let grid = OptimizationGrid::new(
+    ///     (1..=3)
+    ///         .map(|iterations| SmoothingSettings::MovingAverage {
+    ///             iterations,
+    ///             window_size: 5,
+    ///         })
+    ///         .collect(),
+    ///     [5.0, 6.4, 8.0]
+    ///         .into_iter()
+    ///         .map(|threshold| SelectionSettings::NoiseScoreFilter {
+    ///             scoring_method: ScoringMethod::MinimumSum,
+    ///             threshold,
+    ///             max_peaks: None,
+    ///             noise_estimator: NoiseEstimator::StdDev,
+    ///         })
+    ///         .collect(),
+    ///     vec![FittingSettings::Analytical { iterations: 10 }],
+    /// )?;
+    /// let mut deconvoluter = Deconvoluter::default();
+    /// let mse = deconvoluter.optimize_settings_with_grid(&spectrum, grid)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn optimize_settings_with_grid(
+        &mut self,
+        reference: &Spectrum,
+        grid: OptimizationGrid,
+    ) -> Result<f64> {
+        self.optimize_settings_with(&grid, |deconvoluter| {
+            deconvoluter.deconvolute_spectrum(reference)
+        })
+    }
+
+    /// Optimizes the deconvolution settings within the given region only.
+    ///
+    /// This evaluates the same combinations of settings as
+    /// [`optimize_settings`], but deconvolutes the reference spectrum with
+    /// [`deconvolute_region`], such that both the peak selection and the MSE
+    /// are restricted to the region. This allows the settings to be tuned on
+    /// a representative multiplet, when the quality of the spectrum varies
+    /// between regions. The lowest MSE within the region is returned. As with
+    /// [`optimize_settings`], a custom smoother is replaced.
+    ///
+    /// [`optimize_settings`]: Deconvoluter::optimize_settings
+    /// [`deconvolute_region`]: Deconvoluter::deconvolute_region
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [`deconvolute_region`] are possible. If any
+    /// parameter combination returns an error, the optimization process is
+    /// aborted and the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    ///
+    /// // Optimize the deconvolution settings on a single multiplet.
+    /// let mut deconvoluter = Deconvoluter::default();
+    /// let mse = deconvoluter.optimize_settings_in_region(&spectrum, (3.4, 3.45))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn optimize_settings_in_region(
+        &mut self,
+        reference: &Spectrum,
+        region: (f64, f64),
+    ) -> Result<f64> {
+        self.optimize_settings_with(&OptimizationGrid::default(), |deconvoluter| {
+            deconvoluter.deconvolute_region(reference, region)
+        })
+    }
+
+    /// Evaluates the MSE of every combination of the default settings.
+    ///
+    /// Deconvolutes the reference spectrum with every combination of settings
+    /// of [`OptimizationGrid::default`], like [`optimize_settings`], but
+    /// returns all evaluated combinations with their MSE instead of only
+    /// applying the best one. The report is sorted by ascending MSE, such that
+    /// the first entry holds the settings chosen by [`optimize_settings`]. The
+    /// settings of the `Deconvoluter` are not modified.
+    ///
+    /// This is useful to assess how sensitive the MSE is to the settings, for
+    /// example whether several distinct combinations perform equally well.
+    ///
+    /// [`optimize_settings`]: Deconvoluter::optimize_settings
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [`optimize_settings`] are possible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, OptimizationGrid};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    ///
+    /// let deconvoluter = Deconvoluter::default();
+    /// let report = deconvoluter.optimization_report(&spectrum)?;
+    /// assert_eq!(report.len(), OptimizationGrid::default().len());
+    ///
+    /// // Count the combinations that perform within 10% of the best one.
+    /// let best_mse = report[0].mse();
+    /// let close = report
+    ///     .iter()
+    ///     .take_while(|result| result.mse() <= 1.1 * best_mse)
+    ///     .count();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn optimization_report(&self, reference: &Spectrum) -> Result<Vec<OptimizationResult>> {
+        self.optimization_report_with(&OptimizationGrid::default(), |deconvoluter| {
+            deconvoluter.deconvolute_spectrum(reference)
+        })
+    }
+
+    /// Internal helper function to set the settings that yield the lowest MSE
+    /// of the given deconvolution function among the combinations of settings
+    /// in the grid.
+    #[cfg(feature = "parallel")]
+    fn optimize_settings_with<F>(&mut self, grid: &OptimizationGrid, deconvolute: F) -> Result<f64>
+    where
+        F: Fn(&Deconvoluter) -> Result<Deconvolution> + Sync,
+    {
+        let report = self.optimization_report_with(grid, deconvolute)?;
+        let optimal = report[0];
+        self.set_smoothing_settings(optimal.smoothing_settings())?;
+        self.set_selection_settings(optimal.selection_settings())?;
+        self.set_fitting_settings(optimal.fitting_settings())?;
+
+        Ok(optimal.mse())
+    }
+
+    /// Internal helper function to evaluate the MSE of the given deconvolution
+    /// function for each combination of settings in the grid, sorted by
+    /// ascending MSE.
+    #[cfg(feature = "parallel")]
+    fn optimization_report_with<F>(
+        &self,
+        grid: &OptimizationGrid,
+        deconvolute: F,
+    ) -> Result<Vec<OptimizationResult>>
+    where
+        F: Fn(&Deconvoluter) -> Result<Deconvolution> + Sync,
+    {
+        let mut report = grid
+            .smoothing_settings()
+            .par_iter()
+            .map(|smoothing| {
+                let mut deconvoluter = self.clone();
+                deconvoluter.set_smoothing_settings(*smoothing)?;
+
+                grid.selection_settings()
+                    .iter()
+                    .map(|selection| {
+                        deconvoluter.set_selection_settings(*selection)?;
+
+                        grid.fitting_settings()
+                            .iter()
+                            .map(|fitting| {
+                                deconvoluter.set_fitting_settings(*fitting)?;
+                                let deconvolution = deconvolute(&deconvoluter)?;
+
+                                Ok(OptimizationResult::new(
+                                    *smoothing,
+                                    *selection,
+                                    *fitting,
+                                    deconvolution.mse(),
+                                ))
+                            })
+                            .collect::<Result<Vec<_>>>()
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect::<Vec<OptimizationResult>>();
+        report.sort_by(|result_1, result_2| f64::total_cmp(&result_1.mse(), &result_2.mse()));
+
+        Ok(report)
+    }
+
+    /// Internal helper function to estimate the location and spread of the
+    /// scores of the noise peaks, or `None` if the peak selection method
+    /// doesn't estimate the noise level.
+    pub(crate) fn noise_statistics(&self, spectrum: &Spectrum) -> Result<Option<(f64, f64)>> {
+        let ignore_regions = self.ignore_region_indices(spectrum);
+
+        self.selector.noise_statistics(
+            &self.smoothed_intensities(spectrum),
+            spectrum.signal_boundaries_indices(),
+            ignore_regions.as_deref(),
+            self.noise_region
+                .map(|noise_region| region_indices(spectrum, noise_region)),
+        )
+    }
+
+    /// Internal helper function to deconvolute the given region of indices into
+    /// the spectrum again, using the given noise statistics instead of
+    /// estimating them. Only the peaks within the region are fitted, and the
+    /// signals of the previous deconvolution outside of it are kept. As the
+    /// signals are merged, no fit diagnostics are reported.
+    pub(crate) fn redeconvolute_region(
+        &self,
+        spectrum: &Spectrum,
+        previous: &Deconvolution,
+        region: (usize, usize),
+        noise_statistics: Option<(f64, f64)>,
+    ) -> Result<Deconvolution> {
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let peaks = self.selected_peaks_in_region(spectrum, region, noise_statistics)?;
+        let noise_estimate = noise_statistics.map(|(_, sd)| sd);
+        // The kept signals outside the region still contribute to the
+        // intensities within it, so the new signals are fitted to the rest.
+        let kept = previous
+            .signals()
+            .outside(spectrum, region)
+            .superposition_vec(spectrum.chemical_shifts());
+        let remainder = spectrum.with_intensities(
+            spectrum
+                .intensities()
+                .iter()
+                .zip(kept)
+                .map(|(intensity, kept)| intensity - kept)
+                .collect(),
+        );
+        let signals = self
+            .fitter
+            .fit(&remainder, &peaks)
+            .with_previous_outside(spectrum, previous, region);
+        let parameter_uncertainties = self
+            .fitter
+            .parameter_uncertainties(spectrum, &signals);
+        let mse = self.compute_mse(
+            spectrum,
+            signals.superposition_vec(spectrum.chemical_shifts()),
+            None,
+        );
+
+        let deconvolution = signals
+            .into_deconvolution(
+                self.smoother.settings(),
+                self.selector.settings(),
+                self.fitter.settings(),
+                mse,
+            )
+            .with_noise_estimate(noise_estimate)
+            .with_mse_weighting(self.mse_weighting)
+            .with_parameter_uncertainties(parameter_uncertainties);
+
+        Ok(self.with_warnings(spectrum, deconvolution))
+    }
+
+    /// Internal helper function that runs the deconvolution pipeline with the
+    /// given smoothing, peak selection and fitting methods.
+    ///
+    /// Shared by the [`Deconvoluter`], which passes its trait objects, and the
+    /// [`StaticDeconvoluter`], which passes concrete types such that the whole
+    /// pipeline is monomorphized. If a cancellation flag is given, it is
+    /// checked between the iterations of the fit, and [`Kind::Cancelled`] is
+    /// returned once it is set.
+    ///
+    /// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+    pub(crate) fn deconvolute_spectrum_using<S, Sel, F>(
+        &self,
+        spectrum: &Spectrum,
+        smoother: &S,
+        selector: &Sel,
+        fitter: &F,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Deconvolution>
+    where
+        S: Smoother<f64> + ?Sized,
+        Sel: Selector + ?Sized,
+        F: Fitter + ?Sized,
+    {
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let (peaks, noise_estimate, _) =
+            self.selected_peaks(spectrum, smoother, selector, NoiseLevel::Estimated)?;
+        let (signals, fit_diagnostics) = match cancel {
+            Some(cancel) => fitter.fit_cancellable(spectrum, &peaks, cancel)?,
+            None => fitter.fit_with_diagnostics(spectrum, &peaks),
+        };
+        let parameter_uncertainties = fitter.parameter_uncertainties(spectrum, &signals);
+        let mse = self.compute_mse(
+            spectrum,
+            signals.superposition_vec(spectrum.chemical_shifts()),
+            None,
+        );
+
+        let deconvolution = signals
+            .into_deconvolution(
+                smoother.settings(),
+                selector.settings(),
+                fitter.settings(),
+                mse,
+            )
+            .with_noise_estimate(noise_estimate)
+            .with_mse_weighting(self.mse_weighting)
+            .with_fit_diagnostics(fit_diagnostics)
+            .with_parameter_uncertainties(parameter_uncertainties);
+
+        Ok(self.with_warnings(spectrum, deconvolution))
+    }
+
+    /// Internal helper function that runs the parallelized deconvolution
+    /// pipeline with the given smoothing, peak selection and fitting methods,
+    /// optionally checking the cancellation flag during the fit.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_deconvolute_spectrum_using<S, Sel, F>(
+        &self,
+        spectrum: &Spectrum,
+        smoother: &S,
+        selector: &Sel,
+        fitter: &F,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Deconvolution>
+    where
+        S: Smoother<f64> + ?Sized,
+        Sel: Selector + ?Sized,
+        F: Fitter + ?Sized,
+    {
+        let spectrum = self.correct_baseline(spectrum);
+        let spectrum = spectrum.as_ref();
+        let (peaks, noise_estimate, _) =
+            self.selected_peaks(spectrum, smoother, selector, NoiseLevel::EstimatedParallel)?;
+        let (signals, fit_diagnostics) = match cancel {
+            Some(cancel) => fitter.par_fit_cancellable(spectrum, &peaks, cancel)?,
+            None => fitter.par_fit_with_diagnostics(spectrum, &peaks),
+        };
+        let parameter_uncertainties = fitter.parameter_uncertainties(spectrum, &signals);
+        let mse = self.compute_mse(
+            spectrum,
+            signals.par_superposition_vec(spectrum.chemical_shifts()),
+            None,
+        );
+
+        let deconvolution = signals
+            .into_deconvolution(
+                smoother.settings(),
+                selector.settings(),
+                fitter.settings(),
+                mse,
+            )
+            .with_noise_estimate(noise_estimate)
+            .with_mse_weighting(self.mse_weighting)
+            .with_fit_diagnostics(fit_diagnostics)
+            .with_parameter_uncertainties(parameter_uncertainties);
+
+        Ok(self.with_warnings(spectrum, deconvolution))
+    }
+
+    /// Internal helper function to wrap the error of a failed deconvolution
+    /// with the position of the spectrum within the batch. Cancellation
+    /// concerns the whole batch, so it is passed through unchanged.
+    fn batch_error(index: usize, error: crate::Error) -> crate::Error {
+        match error {
+            crate::Error::Deconvolution(source) if !matches!(source.kind(), Kind::Cancelled) => {
+                Error::new(Kind::SpectrumInBatch {
+                    index,
+                    source: Box::new(source),
+                })
+                .into()
+            }
+            error => error,
+        }
+    }
+
+    /// Internal helper function to subtract the estimated baseline from the
+    /// intensities. The spectrum is only copied if a correction is applied.
+    fn correct_baseline<'a>(&self, spectrum: &'a Spectrum) -> Cow<'a, Spectrum> {
+        match self.baseline_corrector.settings() {
+            BaselineSettings::None => Cow::Borrowed(spectrum),
+            _ => {
+                let mut intensities = spectrum.intensities().to_vec();
+                self.baseline_corrector
+                    .correct_baseline(&mut intensities);
+                Cow::Owned(spectrum.with_intensities(intensities))
+            }
+        }
+    }
+
+    /// Internal helper function to compute the MSE within the signal region,
+    /// optionally restricted to the given region, using the configured
+    /// weighting of the residuals.
+    fn compute_mse(
+        &self,
+        spectrum: &Spectrum,
+        superpositions: Vec<f64>,
+        region: Option<(usize, usize)>,
+    ) -> f64 {
+        let ignore_regions = self.ignore_region_indices(spectrum);
+        let regions = evaluation_regions(spectrum, ignore_regions.as_deref())
+            .into_iter()
+            .filter_map(|(start, end)| match region {
+                Some(region) => {
+                    let (start, end) = (usize::max(start, region.0), usize::min(end, region.1));
+                    (start < end).then_some((start, end))
+                }
+                None => Some((start, end)),
+            })
+            .collect::<Vec<(usize, usize)>>();
+
+        self.mse_weighting
+            .mse(&superpositions, spectrum.intensities(), &regions)
+    }
+
+    /// Internal helper function to check the deconvolution of the given
+    /// spectrum for quality issues and attach the resulting warnings.
+    fn with_warnings(&self, spectrum: &Spectrum, deconvolution: Deconvolution) -> Deconvolution {
+        let warnings = DeconvolutionWarning::check(&deconvolution, spectrum, self.ignore_regions());
+
+        deconvolution.with_warnings(warnings)
+    }
+
+    /// Internal helper function to smooth the intensities of the baseline
+    /// corrected spectrum with the given smoother and select the peaks within
+    /// them with the given selector, obtaining the noise level as requested.
+    /// Returns the peaks together with the noise level and the smoothed
+    /// intensities.
+    fn selected_peaks<S, Sel>(
+        &self,
+        spectrum: &Spectrum,
+        smoother: &S,
+        selector: &Sel,
+        noise_level: NoiseLevel,
+    ) -> Result<(Vec<Peak>, Option<f64>, Vec<f64>)>
+    where
+        S: Smoother<f64> + ?Sized,
+        Sel: Selector + ?Sized,
+    {
+        let mut intensities = spectrum.intensities().to_vec();
+        smoother.smooth_values(&mut intensities);
+        let signal_boundaries = spectrum.signal_boundaries_indices();
+        let ignore_regions = self.ignore_region_indices(spectrum);
+        let boost_regions = self.boost_region_indices(spectrum);
+        let noise_region = self
+            .noise_region
+            .map(|noise_region| region_indices(spectrum, noise_region));
+        let (peaks, noise_estimate) = match noise_level {
+            NoiseLevel::Estimated => selector.select_peaks_with_noise_estimate(
+                &intensities,
+                signal_boundaries,
+                ignore_regions.as_deref(),
+                boost_regions.as_deref(),
+                noise_region,
+            )?,
+            #[cfg(feature = "parallel")]
+            NoiseLevel::EstimatedParallel => selector.par_select_peaks_with_noise_estimate(
+                &intensities,
+                signal_boundaries,
+                ignore_regions.as_deref(),
+                boost_regions.as_deref(),
+                noise_region,
+            )?,
+            NoiseLevel::Given(noise_statistics) => (
+                selector.select_peaks_with_noise_statistics(
+                    &intensities,
+                    signal_boundaries,
+                    ignore_regions.as_deref(),
+                    boost_regions.as_deref(),
+                    noise_statistics,
+                )?,
+                noise_statistics.map(|(_, sd)| sd),
+            ),
+        };
+
+        Ok((peaks, noise_estimate, intensities))
+    }
+
+    /// Internal helper function to select the peaks centered within the given
+    /// region of indices with the given noise statistics.
+    ///
+    /// Only the region and a margin around it are smoothed and searched for
+    /// peaks. The margin starts at the reach of the smoother and is doubled
+    /// until the borders of the selected peaks lie where the intensities are
+    /// smoothed as within the entire spectrum, and until a detected peak
+    /// follows the region, as the selector takes the last peak for the signal
+    /// free region otherwise. A maximum number of peaks therefore applies to
+    /// the region and its margin. The entire spectrum is processed if the
+    /// reach of the smoother is unlimited or if the peaks aren't filtered by
+    /// their noise scores.
+    fn selected_peaks_in_region(
+        &self,
+        spectrum: &Spectrum,
+        region: (usize, usize),
+        noise_statistics: Option<(f64, f64)>,
+    ) -> Result<Vec<Peak>> {
+        let reach = match (
+            self.smoother.settings().reach(),
+            self.selector.settings(),
+            noise_statistics,
+        ) {
+            (Some(reach), SelectionSettings::NoiseScoreFilter { .. }, Some(_)) => reach,
+            _ => {
+                let (mut peaks, _, _) = self.selected_peaks(
+                    spectrum,
+                    self.smoother.as_ref(),
+                    self.selector.as_ref(),
+                    NoiseLevel::Given(noise_statistics),
+                )?;
+                peaks.retain(|peak| peak.center() >= region.0 && peak.center() < region.1);
+                return Ok(peaks);
+            }
+        };
+        let len = spectrum.intensities().len();
+        let signal_boundaries = spectrum.signal_boundaries_indices();
+        let ignore_regions = self.ignore_region_indices(spectrum);
+        let boost_regions = self.boost_region_indices(spectrum);
+        let mut margin = reach + 2;
+        loop {
+            let (start, end) = (
+                region.0.saturating_sub(margin),
+                usize::min(region.1 + margin, len),
+            );
+            let within = |(first, last): (usize, usize)| {
+                (
+                    first.clamp(start, end) - start,
+                    last.clamp(start, end) - start,
+                )
+            };
+            let mut intensities = spectrum.intensities()[start..end].to_vec();
+            self.smoother.smooth_values(&mut intensities);
+            let ignore_regions = ignore_regions.as_ref().map(|ignore_regions| {
+                ignore_regions
+                    .iter()
+                    .map(|ignore_region| within(*ignore_region))
+                    .collect::<Vec<_>>()
+            });
+            let boost_regions = boost_regions.as_ref().map(|boost_regions| {
+                boost_regions
+                    .iter()
+                    .map(|(boost_region, factor)| (within(*boost_region), *factor))
+                    .collect::<Vec<_>>()
+            });
+            // A region without any peaks above the noise is not an error, as
+            // the signals outside of it are kept.
+            let mut peaks = match self.selector.select_peaks_with_noise_statistics(
+                &intensities,
+                within(signal_boundaries),
+                ignore_regions.as_deref(),
+                boost_regions.as_deref(),
+                noise_statistics,
+            ) {
+                Ok(peaks) => peaks,
+                Err(crate::Error::Deconvolution(inner))
+                    if matches!(
+                        inner.kind(),
+                        Kind::NoPeaksDetected | Kind::EmptySignalRegion
+                    ) =>
+                {
+                    Vec::new()
+                }
+                Err(error) => return Err(error),
+            };
+            let (first, last) = within(region);
+            peaks.retain(|peak| peak.center() >= first && peak.center() < last);
+            // The second derivative at the peak borders and their neighbors has
+            // to be computed from exactly smoothed intensities.
+            let exact = peaks.iter().all(|peak| {
+                (start == 0 || peak.left() > reach + 1)
+                    && (end == len || peak.right() + reach + 2 < intensities.len())
+            });
+            // Without a detected peak after the signal region, the selector
+            // takes the last peak for the signal free region, which must not be
+            // one of the region.
+            let followed = end == len
+                || match NoiseScoreFilter::detect_peaks(&intensities, ignore_regions.as_deref()) {
+                    Ok((detected, _)) => detected.last().map_or(true, |peak| {
+                        peak.center() >= last || peak.center() > within(signal_boundaries).1
+                    }),
+                    Err(_) => true,
+                };
+            if exact && followed {
+                return Ok(peaks
+                    .into_iter()
+                    .map(|peak| peak.shifted(start))
+                    .collect());
+            }
+            margin *= 2;
+        }
+    }
+
+    /// Internal helper function to convert the ignore regions to indices.
+    fn ignore_region_indices(&self, spectrum: &Spectrum) -> Option<Vec<(usize, usize)>> {
+        self.ignore_regions
+            .as_ref()
+            .map(|ignore_regions| ignore_region_indices(spectrum, ignore_regions))
+    }
+
+    /// Internal helper function to convert the boost regions to indices,
+    /// keeping their factors.
+    fn boost_region_indices(&self, spectrum: &Spectrum) -> Option<Vec<((usize, usize), f64)>> {
+        self.boost_regions.as_ref().map(|boost_regions| {
+            boost_regions
+                .iter()
+                .map(|(region, factor)| (region_indices(spectrum, *region), *factor))
+                .collect()
+        })
+    }
+}
+
+/// Internal enum to choose how the noise level is obtained during the peak
+/// selection of the [`Deconvoluter`].
+#[derive(Clone, Copy, Debug)]
+enum NoiseLevel {
+    /// Estimate the noise level from the smoothed intensities.
+    Estimated,
+    /// Estimate the noise level from the smoothed intensities, detecting the
+    /// peaks in parallel.
+    #[cfg(feature = "parallel")]
+    EstimatedParallel,
+    /// Use the given location and spread of the scores of the noise peaks.
+    Given(Option<(f64, f64)>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::{Lorentzian, NoiseEstimator, ScoringMethod};
+    use crate::macros::{noisy_lorentzian_spectrum, uniform_noise};
+    use crate::{Error, assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
 
-            Some(indices)
-        } else {
-            None
+    #[test]
+    fn thread_safe() {
+        assert_send!(Deconvoluter);
+        assert_sync!(Deconvoluter);
+    }
+
+    #[test]
+    fn invalid_smoothing_settings() {
+        let mut deconvoluter = Deconvoluter::default();
+        let zero_iterations = SmoothingSettings::MovingAverage {
+            iterations: 0,
+            window_size: 3,
+        };
+        let zero_window_size = SmoothingSettings::MovingAverage {
+            iterations: 2,
+            window_size: 0,
+        };
+        let zero_both = SmoothingSettings::MovingAverage {
+            iterations: 0,
+            window_size: 0,
+        };
+        let zero_lambda = SmoothingSettings::Whittaker {
+            lambda: 0.0,
+            order: 2,
+        };
+        let invalid_order = SmoothingSettings::Whittaker {
+            lambda: 1e3,
+            order: 4,
+        };
+        let custom = SmoothingSettings::Custom;
+        let errors = [
+            deconvoluter
+                .set_smoothing_settings(zero_iterations)
+                .unwrap_err(),
+            deconvoluter
+                .set_smoothing_settings(zero_window_size)
+                .unwrap_err(),
+            deconvoluter
+                .set_smoothing_settings(zero_both)
+                .unwrap_err(),
+            deconvoluter
+                .set_smoothing_settings(zero_lambda)
+                .unwrap_err(),
+            deconvoluter
+                .set_smoothing_settings(invalid_order)
+                .unwrap_err(),
+            deconvoluter
+                .set_smoothing_settings(custom)
+                .unwrap_err(),
+        ];
+        let expected_context = [
+            zero_iterations,
+            zero_window_size,
+            zero_both,
+            zero_lambda,
+            invalid_order,
+            custom,
+        ];
+        errors
+            .into_iter()
+            .zip(expected_context)
+            .for_each(|(error, context)| match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidSmoothingSettings { settings } => {
+                        assert!(SmoothingSettings::compare(settings, &context));
+                    }
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn invalid_selection_settings() {
+        let mut deconvoluter = Deconvoluter::default();
+        let zero_threshold = SelectionSettings::NoiseScoreFilter {
+            scoring_method: ScoringMethod::default(),
+            threshold: 0.0,
+            max_peaks: None,
+            noise_estimator: NoiseEstimator::StdDev,
+        };
+        let nan_threshold = SelectionSettings::NoiseScoreFilter {
+            scoring_method: ScoringMethod::default(),
+            threshold: f64::NAN,
+            max_peaks: None,
+            noise_estimator: NoiseEstimator::StdDev,
+        };
+        let inf_threshold = SelectionSettings::NoiseScoreFilter {
+            scoring_method: ScoringMethod::default(),
+            threshold: f64::INFINITY,
+            max_peaks: None,
+            noise_estimator: NoiseEstimator::StdDev,
+        };
+        let neg_inf_threshold = SelectionSettings::NoiseScoreFilter {
+            scoring_method: ScoringMethod::default(),
+            threshold: f64::NEG_INFINITY,
+            max_peaks: None,
+            noise_estimator: NoiseEstimator::StdDev,
+        };
+        let zero_max_peaks = SelectionSettings::NoiseScoreFilter {
+            scoring_method: ScoringMethod::default(),
+            threshold: 5.0,
+            max_peaks: Some(0),
+            noise_estimator: NoiseEstimator::StdDev,
+        };
+        let errors = [
+            deconvoluter
+                .set_selection_settings(zero_threshold)
+                .unwrap_err(),
+            deconvoluter
+                .set_selection_settings(nan_threshold)
+                .unwrap_err(),
+            deconvoluter
+                .set_selection_settings(inf_threshold)
+                .unwrap_err(),
+            deconvoluter
+                .set_selection_settings(neg_inf_threshold)
+                .unwrap_err(),
+            deconvoluter
+                .set_selection_settings(zero_max_peaks)
+                .unwrap_err(),
+        ];
+        let expected_context = [
+            zero_threshold,
+            nan_threshold,
+            inf_threshold,
+            neg_inf_threshold,
+            zero_max_peaks,
+        ];
+        errors
+            .into_iter()
+            .zip(expected_context)
+            .for_each(|(error, context)| match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidSelectionSettings { settings } => {
+                        assert!(SelectionSettings::compare(settings, &context));
+                    }
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn invalid_fitting_settings() {
+        let mut deconvoluter = Deconvoluter::default();
+        let zero_iterations = FittingSettings::Analytical { iterations: 0 };
+        let zero_gaussian_iterations = FittingSettings::Gaussian { iterations: 0 };
+        let invalid_tolerance = FittingSettings::LevenbergMarquardt {
+            max_iterations: 100,
+            tolerance: -1.0,
+            lambda_init: 1e-3,
+        };
+        let invalid_eta = FittingSettings::PseudoVoigt {
+            iterations: 10,
+            eta_init: 1.5,
+        };
+        let errors = [
+            deconvoluter
+                .set_fitting_settings(zero_iterations)
+                .unwrap_err(),
+            deconvoluter
+                .set_fitting_settings(zero_gaussian_iterations)
+                .unwrap_err(),
+            deconvoluter
+                .set_fitting_settings(invalid_tolerance)
+                .unwrap_err(),
+            deconvoluter
+                .set_fitting_settings(invalid_eta)
+                .unwrap_err(),
+        ];
+        let expected_context = [
+            zero_iterations,
+            zero_gaussian_iterations,
+            invalid_tolerance,
+            invalid_eta,
+        ];
+        errors
+            .into_iter()
+            .zip(expected_context)
+            .for_each(|(error, context)| match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidFittingSettings { settings } => {
+                        assert!(FittingSettings::compare(settings, &context));
+                    }
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn add_ignore_region() {
+        let mut deconvoluter = Deconvoluter::default();
+        deconvoluter
+            .add_ignore_region((1.0, 2.0))
+            .unwrap();
+        deconvoluter
+            .add_ignore_region((3.0, 4.0))
+            .unwrap();
+        assert!(deconvoluter.ignore_regions().is_some());
+        assert_eq!(deconvoluter.ignore_regions().unwrap().len(), 2);
+        deconvoluter
+            .add_ignore_region((2.0, 3.0))
+            .unwrap();
+        assert_eq!(deconvoluter.ignore_regions().unwrap().len(), 1);
+        assert_approx_eq!(f64, deconvoluter.ignore_regions().unwrap()[0].0, 1.0);
+        assert_approx_eq!(f64, deconvoluter.ignore_regions().unwrap()[0].1, 4.0);
+    }
+
+    #[test]
+    fn invalid_ignore_region() {
+        let mut deconvoluter = Deconvoluter::default();
+        let errors = [
+            deconvoluter
+                .add_ignore_region((f64::NAN, 1.0))
+                .unwrap_err(),
+            deconvoluter
+                .add_ignore_region((1.0, f64::NAN))
+                .unwrap_err(),
+            deconvoluter
+                .add_ignore_region((f64::INFINITY, 1.0))
+                .unwrap_err(),
+            deconvoluter
+                .add_ignore_region((1.0, f64::INFINITY))
+                .unwrap_err(),
+            deconvoluter
+                .add_ignore_region((f64::NEG_INFINITY, 1.0))
+                .unwrap_err(),
+            deconvoluter
+                .add_ignore_region((1.0, f64::NEG_INFINITY))
+                .unwrap_err(),
+            deconvoluter
+                .add_ignore_region((1.0, 1.0))
+                .unwrap_err(),
+        ];
+        let expected_context = [
+            (f64::NAN, 1.0),
+            (1.0, f64::NAN),
+            (f64::INFINITY, 1.0),
+            (1.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 1.0),
+            (1.0, f64::NEG_INFINITY),
+            (1.0, 1.0),
+        ];
+        errors
+            .into_iter()
+            .zip(expected_context)
+            .for_each(|(error, context)| match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidIgnoreRegion { region } => {
+                        assert_approx_eq!(f64, region.0, context.0);
+                        assert_approx_eq!(f64, region.1, context.1);
+                    }
+                    _ => panic!("Unexpected kind: {:?}", inner.kind()),
+                },
+                _ => panic!("Unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn clear_ignore_regions() {
+        let mut deconvoluter = Deconvoluter::default();
+        deconvoluter
+            .add_ignore_region((1.0, 2.0))
+            .unwrap();
+        deconvoluter
+            .add_ignore_region((3.0, 4.0))
+            .unwrap();
+        deconvoluter.clear_ignore_regions();
+        assert!(deconvoluter.ignore_regions().is_none());
+    }
+
+    #[test]
+    fn boost_regions() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 5.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 7.0),
+            Lorentzian::new(0.0015 * 0.01, 0.01_f64.powi(2), 4.0),
+            Lorentzian::new(0.0015 * 0.01, 0.01_f64.powi(2), 6.0),
+        ];
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let is_near = |peaks: &[PeakInfo], position: f64| {
+            peaks
+                .iter()
+                .any(|peak| f64::abs(peak.chemical_shift() - position) < 0.01)
+        };
+        let mut deconvoluter = Deconvoluter::default();
+        let peaks = deconvoluter.detect_peaks(&spectrum).unwrap();
+        assert!(!is_near(&peaks, 4.0));
+        assert!(!is_near(&peaks, 6.0));
+        deconvoluter
+            .add_boost_region((3.9, 4.1), 4.0)
+            .unwrap();
+        let peaks = deconvoluter.detect_peaks(&spectrum).unwrap();
+        assert!(is_near(&peaks, 4.0));
+        assert!(!is_near(&peaks, 6.0));
+        [3.0, 5.0, 7.0]
+            .into_iter()
+            .for_each(|position| assert!(is_near(&peaks, position)));
+    }
+
+    #[test]
+    fn warnings() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 5.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let deconvolution = Deconvoluter::default()
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert_eq!(deconvolution.len(), 3);
+        assert!(deconvolution.warnings().is_empty());
+        let overfitting = Deconvoluter::new(
+            SmoothingSettings::Identity,
+            SelectionSettings::DetectorOnly,
+            FittingSettings::default(),
+        )
+        .unwrap();
+        let deconvolution = overfitting
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        match deconvolution
+            .warnings()
+            .iter()
+            .find(|warning| matches!(warning, DeconvolutionWarning::HighPeakCount { .. }))
+        {
+            Some(DeconvolutionWarning::HighPeakCount { peak_count, points }) => {
+                assert_eq!(*peak_count, deconvolution.len());
+                assert!(peak_count * DeconvolutionWarning::MIN_POINTS_PER_PEAK > *points);
+            }
+            _ => panic!("unexpected warnings: {:?}", deconvolution.warnings()),
+        }
+        assert!(
+            deconvolution
+                .warnings()
+                .iter()
+                .any(|warning| matches!(warning, DeconvolutionWarning::Unconverged { .. }))
+        );
+        assert!(
+            deconvolution
+                .merge_close_peaks(0.01)
+                .warnings()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn region_warnings() {
+        // A crowded triplet within the region and a signal outside of it, which
+        // is not fitted and must not count against the region.
+        let signals = [4.98, 5.0, 5.02, 7.0]
+            .into_iter()
+            .map(|maxp| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), maxp))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let region = (4.5, 5.5);
+        let mut deconvoluter = Deconvoluter::default();
+        let deconvolution = deconvoluter
+            .deconvolute_region(&spectrum, region)
+            .unwrap();
+        assert!(
+            !deconvolution
+                .warnings()
+                .iter()
+                .any(|warning| matches!(
+                    warning,
+                    DeconvolutionWarning::HighMse { .. }
+                        | DeconvolutionWarning::HighPeakCount { .. }
+                ))
+        );
+        deconvoluter
+            .set_fitting_settings(FittingSettings::Analytical { iterations: 1 })
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_region(&spectrum, region)
+            .unwrap();
+        match deconvolution
+            .warnings()
+            .iter()
+            .find(|warning| matches!(warning, DeconvolutionWarning::Unconverged { .. }))
+        {
+            Some(DeconvolutionWarning::Unconverged { count }) => assert!(*count >= 3),
+            _ => panic!("unexpected warnings: {:?}", deconvolution.warnings()),
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{Error, assert_send, assert_sync};
-    use float_cmp::assert_approx_eq;
+    #[test]
+    fn selected_peaks_in_region() {
+        let signals = (0..5)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0 + i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let mut deconvoluter = Deconvoluter::default();
+        [
+            SmoothingSettings::default(),
+            SmoothingSettings::MovingAverage {
+                iterations: 4,
+                window_size: 9,
+            },
+            SmoothingSettings::Whittaker {
+                lambda: 1e2,
+                order: 2,
+            },
+        ]
+        .into_iter()
+        .for_each(|settings| {
+            deconvoluter
+                .set_smoothing_settings(settings)
+                .unwrap();
+            let noise_statistics = deconvoluter.noise_statistics(&spectrum).unwrap();
+            let (all, _, _) = deconvoluter
+                .selected_peaks(
+                    &spectrum,
+                    deconvoluter.smoother.as_ref(),
+                    deconvoluter.selector.as_ref(),
+                    NoiseLevel::Given(noise_statistics),
+                )
+                .unwrap();
+            [(1390, 2410), (1600, 1601), (0, 4000), (3190, 4000)]
+                .into_iter()
+                .for_each(|region| {
+                    let peaks = deconvoluter
+                        .selected_peaks_in_region(&spectrum, region, noise_statistics)
+                        .unwrap()
+                        .iter()
+                        .map(|peak| (peak.left(), peak.center(), peak.right()))
+                        .collect::<Vec<_>>();
+                    let expected = all
+                        .iter()
+                        .filter(|peak| peak.center() >= region.0 && peak.center() < region.1)
+                        .map(|peak| (peak.left(), peak.center(), peak.right()))
+                        .collect::<Vec<_>>();
+                    assert_eq!(peaks, expected);
+                });
+        });
+    }
 
     #[test]
-    fn thread_safe() {
-        assert_send!(Deconvoluter);
-        assert_sync!(Deconvoluter);
+    fn detect_shoulder() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01_f64.powi(2), 0.01_f64.powi(2), 4.96),
+            Lorentzian::new(0.5 * 0.01_f64.powi(2), 0.01_f64.powi(2), 5.0),
+            Lorentzian::new(0.2 * 0.015_f64.powi(2), 0.015_f64.powi(2), 5.025),
+        ];
+        let chemical_shifts = (0..2001)
+            .map(|i| 4.0 + i as f64 * 0.001)
+            .collect::<Vec<f64>>();
+        let intensities = Lorentzian::superposition_vec(&chemical_shifts, &signals);
+        let maxima = intensities
+            .windows(3)
+            .filter(|w| w[1] > w[0] && w[1] > w[2])
+            .count();
+        assert_eq!(maxima, 2);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (4.5, 5.5)).unwrap();
+        [SmoothingSettings::Identity, SmoothingSettings::default()]
+            .into_iter()
+            .for_each(|smoothing_settings| {
+                let deconvoluter = Deconvoluter::new(
+                    smoothing_settings,
+                    SelectionSettings::DetectorOnly,
+                    FittingSettings::default(),
+                )
+                .unwrap();
+                let peaks = deconvoluter.detect_peaks(&spectrum).unwrap();
+                assert_eq!(peaks.len(), 3);
+                peaks
+                    .iter()
+                    .zip([4.96, 5.0, 5.025])
+                    .for_each(|(peak, position)| {
+                        assert_approx_eq!(f64, peak.chemical_shift(), position, epsilon = 0.005)
+                    });
+            });
     }
 
     #[test]
-    fn invalid_smoothing_settings() {
+    fn invalid_boost_region() {
         let mut deconvoluter = Deconvoluter::default();
-        let zero_iterations = SmoothingSettings::MovingAverage {
-            iterations: 0,
-            window_size: 3,
+        let invalid = [
+            ((f64::NAN, 1.0), 2.0),
+            ((1.0, f64::INFINITY), 2.0),
+            ((1.0, 1.0), 2.0),
+            ((1.0, 2.0), 0.5),
+            ((1.0, 2.0), f64::NAN),
+            ((1.0, 2.0), f64::INFINITY),
+        ];
+        invalid.into_iter().for_each(|(region, factor)| {
+            let error = deconvoluter
+                .add_boost_region(region, factor)
+                .unwrap_err();
+            match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidBoostRegion { .. } => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            }
+        });
+        assert!(deconvoluter.boost_regions().is_none());
+        deconvoluter
+            .add_boost_region((2.0, 1.0), 2.0)
+            .unwrap();
+        assert_eq!(deconvoluter.boost_regions().unwrap(), &[((1.0, 2.0), 2.0)]);
+        deconvoluter.clear_boost_regions();
+        assert!(deconvoluter.boost_regions().is_none());
+    }
+
+    #[test]
+    fn invalid_baseline_settings() {
+        let mut deconvoluter = Deconvoluter::default();
+        let zero_lambda = BaselineSettings::AsymmetricLeastSquares {
+            lambda: 0.0,
+            p: 0.01,
+            iterations: 10,
         };
-        let zero_window_size = SmoothingSettings::MovingAverage {
-            iterations: 2,
-            window_size: 0,
+        let negative_lambda = BaselineSettings::AsymmetricLeastSquares {
+            lambda: -1e5,
+            p: 0.01,
+            iterations: 10,
         };
-        let zero_both = SmoothingSettings::MovingAverage {
+        let nan_lambda = BaselineSettings::AsymmetricLeastSquares {
+            lambda: f64::NAN,
+            p: 0.01,
+            iterations: 10,
+        };
+        let invalid_p = BaselineSettings::AsymmetricLeastSquares {
+            lambda: 1e5,
+            p: 1.0,
+            iterations: 10,
+        };
+        let zero_iterations = BaselineSettings::AsymmetricLeastSquares {
+            lambda: 1e5,
+            p: 0.01,
             iterations: 0,
-            window_size: 0,
         };
         let errors = [
             deconvoluter
-                .set_smoothing_settings(zero_iterations)
+                .set_baseline_settings(zero_lambda)
                 .unwrap_err(),
             deconvoluter
-                .set_smoothing_settings(zero_window_size)
+                .set_baseline_settings(negative_lambda)
                 .unwrap_err(),
             deconvoluter
-                .set_smoothing_settings(zero_both)
+                .set_baseline_settings(nan_lambda)
+                .unwrap_err(),
+            deconvoluter
+                .set_baseline_settings(invalid_p)
                 .unwrap_err(),
+            deconvoluter
+                .set_baseline_settings(zero_iterations)
+                .unwrap_err(),
+        ];
+        let expected_context = [
+            zero_lambda,
+            negative_lambda,
+            nan_lambda,
+            invalid_p,
+            zero_iterations,
         ];
-        let expected_context = [zero_iterations, zero_window_size, zero_both];
         errors
             .into_iter()
             .zip(expected_context)
             .for_each(|(error, context)| match error {
                 Error::Deconvolution(inner) => match inner.kind() {
-                    Kind::InvalidSmoothingSettings { settings } => {
-                        assert!(SmoothingSettings::compare(settings, &context));
+                    Kind::InvalidBaselineSettings { settings } => {
+                        assert!(BaselineSettings::compare(settings, &context));
+                    }
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn baseline_correction() {
+        let signals = [
+            Lorentzian::new(0.03, 0.0009, 3.0),
+            Lorentzian::new(0.02, 0.0004, 5.0),
+            Lorentzian::new(0.04, 0.0016, 7.0),
+        ];
+        let chemical_shifts = (0..2000)
+            .map(|i| i as f64 * 10.0 / 1999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| Lorentzian::superposition(*x, &signals) + 2.0 + 0.5 * x - 0.08 * x.powi(2))
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        let mut deconvoluter = Deconvoluter::new(
+            SmoothingSettings::Identity,
+            SelectionSettings::DetectorOnly,
+            FittingSettings::Analytical { iterations: 10 },
+        )
+        .unwrap();
+        deconvoluter
+            .set_baseline_settings(BaselineSettings::AsymmetricLeastSquares {
+                lambda: 1e7,
+                p: 0.001,
+                iterations: 10,
+            })
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        signals.iter().for_each(|signal| {
+            let recovered = deconvolution
+                .lorentzians()
+                .iter()
+                .min_by(|a, b| {
+                    f64::abs(a.maxp() - signal.maxp())
+                        .partial_cmp(&f64::abs(b.maxp() - signal.maxp()))
+                        .unwrap()
+                })
+                .unwrap();
+            assert_approx_eq!(f64, recovered.maxp(), signal.maxp(), epsilon = 1e-3);
+            assert_approx_eq!(
+                f64,
+                recovered.sf() / recovered.hw(),
+                signal.sf() / signal.hw(),
+                epsilon = 0.02 * signal.sf() / signal.hw()
+            );
+        });
+    }
+
+    #[test]
+    fn noise_region() {
+        let signals = (3..=7)
+            .map(|i| Lorentzian::new(0.1 * 0.01, 0.01_f64.powi(2), i as f64))
+            .collect::<Vec<Lorentzian>>();
+        // An intense reference compound at 0 ppm and two impurities, all of them
+        // outside the signal region on a decreasing axis like in a real spectrum.
+        let artifacts = [
+            Lorentzian::new(200.0 * 0.005, 0.005_f64.powi(2), 0.0),
+            Lorentzian::new(20.0 * 0.01, 0.01_f64.powi(2), 0.9),
+            Lorentzian::new(20.0 * 0.01, 0.01_f64.powi(2), 1.5),
+        ];
+        let chemical_shifts = (0..4000)
+            .map(|i| 10.0 - i as f64 * 11.0 / 3999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .zip(uniform_noise(4000, 42))
+            .map(|(x, noise)| {
+                Lorentzian::superposition(*x, &signals)
+                    + Lorentzian::superposition(*x, &artifacts)
+                    + 0.1 * noise
+            })
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (8.0, 2.0)).unwrap();
+        let recovered = |deconvolution: &Deconvolution| {
+            signals
+                .iter()
+                .filter(|signal| {
+                    deconvolution
+                        .lorentzians()
+                        .iter()
+                        .any(|lorentzian| (lorentzian.maxp() - signal.maxp()).abs() < 0.01)
+                })
+                .count()
+        };
+        let mut deconvoluter = Deconvoluter::default();
+        match deconvoluter.deconvolute_spectrum(&spectrum) {
+            Ok(deconvolution) => assert!(recovered(&deconvolution) < signals.len()),
+            Err(Error::Deconvolution(inner)) => match inner.kind() {
+                Kind::EmptySignalRegion => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+        deconvoluter
+            .set_noise_region((8.5, 10.0))
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert_eq!(recovered(&deconvolution), signals.len());
+        deconvoluter.clear_noise_region();
+        assert!(deconvoluter.noise_region().is_none());
+    }
+
+    #[test]
+    fn invalid_noise_region() {
+        let mut deconvoluter = Deconvoluter::default();
+        let regions = [(f64::NAN, 1.0), (1.0, f64::INFINITY), (1.0, 1.0)];
+        regions.into_iter().for_each(|context| {
+            let error = deconvoluter
+                .set_noise_region(context)
+                .unwrap_err();
+            match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidNoiseRegion { region } => {
+                        assert!(region.0.to_bits() == context.0.to_bits());
+                        assert!(region.1.to_bits() == context.1.to_bits());
                     }
                     _ => panic!("unexpected kind: {:?}", inner),
                 },
                 _ => panic!("unexpected error: {:?}", error),
+            }
+        });
+        assert!(deconvoluter.noise_region().is_none());
+    }
+
+    #[test]
+    fn deconvolute_region() {
+        let signals = (3..=7)
+            .flat_map(|i| {
+                [-0.02, 0.02]
+                    .map(|offset| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), i as f64 + offset))
+            })
+            .collect::<Vec<Lorentzian>>();
+        // A decreasing axis that doesn't start at 0 ppm, so positions relative to
+        // the region would stand out.
+        let chemical_shifts = (0..5000)
+            .map(|i| 12.0 - i as f64 * 13.0 / 4999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .zip(uniform_noise(5000, 42))
+            .map(|(x, noise)| Lorentzian::superposition(*x, &signals) + 0.1 * noise)
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        let deconvoluter = Deconvoluter::default();
+        let full = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        let region = (4.5, 5.5);
+        let expected = full
+            .lorentzians()
+            .iter()
+            .filter(|lorentzian| lorentzian.maxp() > region.0 && lorentzian.maxp() < region.1)
+            .collect::<Vec<&Lorentzian>>();
+        let deconvolution = deconvoluter
+            .deconvolute_region(&spectrum, region)
+            .unwrap();
+        assert_eq!(expected.len(), 2);
+        assert_eq!(deconvolution.lorentzians().len(), expected.len());
+        deconvolution
+            .lorentzians()
+            .iter()
+            .zip(expected)
+            .for_each(|(lorentzian, expected)| {
+                assert_approx_eq!(f64, lorentzian.maxp(), expected.maxp(), epsilon = 1e-3);
+                assert_approx_eq!(
+                    f64,
+                    lorentzian.sf(),
+                    expected.sf(),
+                    epsilon = 0.02 * expected.sf()
+                );
+                assert_approx_eq!(
+                    f64,
+                    lorentzian.hw(),
+                    expected.hw(),
+                    epsilon = 0.02 * expected.hw()
+                );
+            });
+        let error = deconvoluter
+            .deconvolute_region(&spectrum, (5.0, 5.0))
+            .unwrap_err();
+        match error {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::InvalidDeconvolutionRegion { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bruker")]
+    fn refine() {
+        let path = "../data/bruker/sim/sim_01";
+        let spectrum = crate::spectrum::Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let mut deconvoluter = Deconvoluter::default();
+        let previous = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        let refined = deconvoluter.refine(&spectrum, &previous).unwrap();
+        assert!(refined.mse() <= previous.mse() * (1.0 + 1e-6));
+        assert_eq!(refined.lorentzians().len(), previous.lorentzians().len());
+        assert!(refined.fit_diagnostics().is_some());
+        let levenberg_marquardt = FittingSettings::LevenbergMarquardt {
+            max_iterations: 50,
+            tolerance: 1e-8,
+            lambda_init: 1e-3,
+        };
+        deconvoluter
+            .set_fitting_settings(levenberg_marquardt)
+            .unwrap();
+        let refined = deconvoluter.refine(&spectrum, &previous).unwrap();
+        assert!(refined.mse() <= previous.mse());
+        assert_eq!(
+            format!("{:?}", refined.fitting_settings()),
+            format!("{:?}", levenberg_marquardt)
+        );
+        assert_eq!(
+            format!("{:?}", refined.smoothing_settings()),
+            format!("{:?}", previous.smoothing_settings())
+        );
+        assert_eq!(
+            format!("{:?}", refined.selection_settings()),
+            format!("{:?}", previous.selection_settings())
+        );
+        let polished = deconvoluter.refine(&spectrum, &refined).unwrap();
+        assert!(polished.mse() <= refined.mse());
+        deconvoluter
+            .set_fitting_settings(FittingSettings::Gaussian { iterations: 10 })
+            .unwrap();
+        let error = deconvoluter
+            .refine(&spectrum, &previous)
+            .unwrap_err();
+        match error {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::UnsupportedRefinement { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bruker")]
+    fn detect_peaks() {
+        let path = "../data/bruker/sim/sim_01";
+        let spectrum = crate::spectrum::Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let deconvoluter = Deconvoluter::default();
+        let peaks = deconvoluter.detect_peaks(&spectrum).unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert_eq!(peaks.len(), deconvolution.lorentzians().len());
+        peaks.iter().for_each(|peak| {
+            assert!(peak.chemical_shift() > 3.34 && peak.chemical_shift() < 3.56);
+            assert_approx_eq!(
+                f64,
+                peak.chemical_shift(),
+                spectrum.chemical_shifts()[peak.center()]
+            );
+            assert_approx_eq!(f64, peak.intensity(), spectrum.intensities()[peak.center()]);
+            assert!(peak.score().unwrap() > 0.0);
+        });
+
+        let mut deconvoluter = Deconvoluter::default();
+        deconvoluter
+            .set_selection_settings(SelectionSettings::DetectorOnly)
+            .unwrap();
+        let peaks = deconvoluter.detect_peaks(&spectrum).unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert!(peaks.len() >= deconvolution.lorentzians().len());
+        assert!(peaks.iter().all(|peak| peak.score().is_none()));
+    }
+
+    #[test]
+    fn integrate_peaks() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(1.0 * 0.01, 0.01_f64.powi(2), 5.0),
+            Lorentzian::new(0.25 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.01, 42, (2.0, 8.0));
+        let deconvoluter = Deconvoluter::default();
+        let areas = deconvoluter.integrate_peaks(&spectrum).unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert_eq!(areas.len(), deconvolution.lorentzians().len());
+        signals.iter().for_each(|signal| {
+            let (_, area) = areas
+                .iter()
+                .min_by(|(a, _), (b, _)| {
+                    f64::abs(a.chemical_shift() - signal.maxp())
+                        .total_cmp(&f64::abs(b.chemical_shift() - signal.maxp()))
+                })
+                .unwrap();
+            let fitted = deconvolution
+                .lorentzians()
+                .iter()
+                .min_by(|a, b| {
+                    f64::abs(a.maxp() - signal.maxp())
+                        .total_cmp(&f64::abs(b.maxp() - signal.maxp()))
+                })
+                .unwrap();
+            assert!(f64::abs(area - signal.integral()) < 0.05 * signal.integral());
+            assert!(f64::abs(area - fitted.integral()) < 0.05 * fitted.integral());
+        });
+    }
+
+    #[test]
+    fn integrate_separated_peaks() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(1.0 * 0.01, 0.01_f64.powi(2), 4.0),
+            Lorentzian::new(1.0 * 0.01, 0.01_f64.powi(2), 6.0),
+            Lorentzian::new(0.25 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.01, 7, (2.0, 8.0));
+        let mut deconvoluter = Deconvoluter::default();
+        deconvoluter
+            .add_ignore_region((3.8, 4.2))
+            .unwrap();
+        deconvoluter
+            .add_ignore_region((5.8, 6.2))
+            .unwrap();
+        let areas = deconvoluter.integrate_peaks(&spectrum).unwrap();
+        [signals[0], signals[3]]
+            .iter()
+            .for_each(|signal| {
+                let (_, area) = areas
+                    .iter()
+                    .find(|(peak, _)| f64::abs(peak.chemical_shift() - signal.maxp()) < 0.01)
+                    .unwrap();
+                assert!(f64::abs(area - signal.integral()) < 0.05 * signal.integral());
+            });
+    }
+
+    #[test]
+    fn integrate_peaks_without_peaks() {
+        let chemical_shifts = (0..1000)
+            .map(|i| i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, vec![1.0; 1000], (2.0, 8.0)).unwrap();
+        let deconvoluter = Deconvoluter::default();
+        match deconvoluter
+            .integrate_peaks(&spectrum)
+            .unwrap_err()
+        {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::NoPeaksDetected => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn with_smoother() {
+        #[derive(Debug)]
+        struct Wrapped(MovingAverage<f64>);
+
+        impl Smoother<f64> for Wrapped {
+            fn smooth_values(&self, values: &mut [f64]) {
+                self.0.smooth_values(values);
+            }
+        }
+
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(1.0 * 0.01, 0.01_f64.powi(2), 5.0),
+            Lorentzian::new(0.25 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let deconvoluter = Deconvoluter::default();
+        let custom =
+            Deconvoluter::with_smoother(Arc::new(Wrapped(MovingAverage::new(3, 3).unwrap())));
+        assert!(SmoothingSettings::compare(
+            &custom.smoothing_settings(),
+            &SmoothingSettings::Custom
+        ));
+        assert_eq!(
+            custom.smoothed_intensities(&spectrum),
+            deconvoluter.smoothed_intensities(&spectrum)
+        );
+        let expected = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        let deconvolution = custom.deconvolute_spectrum(&spectrum).unwrap();
+        assert!(SmoothingSettings::compare(
+            &deconvolution.smoothing_settings(),
+            &SmoothingSettings::Custom
+        ));
+        assert_eq!(
+            deconvolution.lorentzians().len(),
+            expected.lorentzians().len()
+        );
+        deconvolution
+            .lorentzians()
+            .iter()
+            .zip(expected.lorentzians())
+            .for_each(|(a, b)| {
+                assert_approx_eq!(f64, a.sf(), b.sf());
+                assert_approx_eq!(f64, a.hw(), b.hw());
+                assert_approx_eq!(f64, a.maxp(), b.maxp());
+            });
+        #[cfg(feature = "serde")]
+        assert!(Deconvoluter::from_config(custom.to_config()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "bruker")]
+    fn smoothed_intensities() {
+        let path = "../data/bruker/sim/sim_01";
+        let spectrum = crate::spectrum::Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let variance = |intensities: &[f64]| {
+            intensities
+                .windows(2)
+                .map(|w| (w[1] - w[0]).powi(2))
+                .sum::<f64>()
+                / (intensities.len() - 1) as f64
+        };
+        let mut deconvoluter = Deconvoluter::default();
+        deconvoluter
+            .set_smoothing_settings(SmoothingSettings::Identity)
+            .unwrap();
+        let identity = deconvoluter.smoothed_intensities(&spectrum);
+        assert_eq!(identity, spectrum.intensities());
+        deconvoluter
+            .set_smoothing_settings(SmoothingSettings::MovingAverage {
+                iterations: 4,
+                window_size: 5,
+            })
+            .unwrap();
+        let moving_average = deconvoluter.smoothed_intensities(&spectrum);
+        assert_eq!(moving_average.len(), spectrum.len());
+        assert!(variance(&moving_average) < variance(spectrum.intensities()));
+    }
+
+    #[test]
+    fn batch_errors() {
+        let signals = (3..=7)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let peakless = Spectrum::new(
+            spectrum.chemical_shifts().to_vec(),
+            vec![1.0; 4000],
+            (2.0, 8.0),
+        )
+        .unwrap();
+        let spectra = [spectrum.clone(), peakless, spectrum];
+        let deconvoluter = Deconvoluter::default();
+        let check_error = |error: &Error| match error {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::SpectrumInBatch { index, source } => {
+                    assert_eq!(*index, 1);
+                    match source.kind() {
+                        Kind::NoPeaksDetected => {}
+                        _ => panic!("unexpected source kind: {:?}", source),
+                    }
+                    assert!(std::error::Error::source(inner).is_some());
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        };
+        let check_results = |results: Vec<Result<Deconvolution>>| {
+            assert_eq!(results.len(), 3);
+            assert!(results[0].is_ok());
+            check_error(results[1].as_ref().unwrap_err());
+            assert!(results[2].is_ok());
+        };
+        check_error(
+            &deconvoluter
+                .deconvolute_spectra(&spectra)
+                .unwrap_err(),
+        );
+        check_results(deconvoluter.try_deconvolute_spectra(&spectra));
+        #[cfg(feature = "parallel")]
+        {
+            check_error(
+                &deconvoluter
+                    .par_deconvolute_spectra(&spectra)
+                    .unwrap_err(),
+            );
+            check_results(deconvoluter.par_try_deconvolute_spectra(&spectra));
+        }
+    }
+
+    #[test]
+    fn deconvolute_spectra_with() {
+        let spectra = [4.0, 6.0]
+            .into_iter()
+            .zip(42..)
+            .map(|(water, seed)| {
+                let signals = [3.0, 5.0, 7.0]
+                    .into_iter()
+                    .map(|position| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), position))
+                    .chain([Lorentzian::new(5.0 * 0.02, 0.02_f64.powi(2), water)])
+                    .collect::<Vec<Lorentzian>>();
+                noisy_lorentzian_spectrum(&signals, 4000, 0.1, seed, (2.0, 8.0))
+            })
+            .collect::<Vec<Spectrum>>();
+        let water_region = |spectrum: &Spectrum| {
+            let maximum = spectrum
+                .intensities()
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(index, _)| spectrum.chemical_shifts()[index])
+                .unwrap();
+            vec![(maximum - 0.2, maximum + 0.2)]
+        };
+        let is_near = |deconvolution: &Deconvolution, position: f64| {
+            deconvolution
+                .lorentzians()
+                .iter()
+                .any(|lorentzian| f64::abs(lorentzian.maxp() - position) < 0.01)
+        };
+        let deconvoluter = Deconvoluter::default();
+        let deconvolutions = deconvoluter
+            .deconvolute_spectra(&spectra)
+            .unwrap();
+        assert!(is_near(&deconvolutions[0], 4.0));
+        assert!(is_near(&deconvolutions[1], 6.0));
+        let deconvolutions = deconvoluter
+            .deconvolute_spectra_with(&spectra, water_region)
+            .unwrap();
+        assert!(deconvoluter.ignore_regions().is_none());
+        assert!(!is_near(&deconvolutions[0], 4.0));
+        assert!(!is_near(&deconvolutions[1], 6.0));
+        deconvolutions.iter().for_each(|deconvolution| {
+            [3.0, 5.0, 7.0]
+                .into_iter()
+                .for_each(|position| assert!(is_near(deconvolution, position)));
+        });
+        let error = deconvoluter
+            .deconvolute_spectra_with(&spectra, |_| vec![(f64::NAN, 1.0)])
+            .unwrap_err();
+        match error {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::SpectrumInBatch { index, source } => {
+                    assert_eq!(*index, 0);
+                    match source.kind() {
+                        Kind::InvalidIgnoreRegion { .. } => {}
+                        _ => panic!("unexpected source kind: {:?}", source),
+                    }
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn deconvolute_spectra_templated() {
+        let positions = [vec![3.0, 5.0], vec![3.005, 5.004, 7.0], vec![2.996]];
+        let spectra = positions
+            .iter()
+            .zip(42..)
+            .map(|(positions, seed)| {
+                let signals = positions
+                    .iter()
+                    .map(|position| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), *position))
+                    .collect::<Vec<Lorentzian>>();
+                noisy_lorentzian_spectrum(&signals, 4000, 0.1, seed, (2.0, 8.0))
+            })
+            .collect::<Vec<Spectrum>>();
+        let template = [5.0, 3.0];
+        let deconvoluter = Deconvoluter::default();
+        let deconvolutions = deconvoluter
+            .deconvolute_spectra_templated(&spectra, &template, 0.02)
+            .unwrap();
+        assert_eq!(deconvolutions.len(), spectra.len());
+        deconvolutions
+            .iter()
+            .zip(positions.iter())
+            .for_each(|(deconvolution, positions)| {
+                assert_eq!(deconvolution.len(), template.len());
+                assert!(deconvolution.validate().is_ok());
+                template
+                    .iter()
+                    .zip(deconvolution.lorentzians())
+                    .for_each(|(expected, lorentzian)| {
+                        assert!(f64::abs(lorentzian.maxp() - expected) < 0.02);
+                        match positions
+                            .iter()
+                            .find(|position| f64::abs(*position - expected) < 0.02)
+                        {
+                            Some(position) => {
+                                assert!(f64::abs(lorentzian.maxp() - position) < 0.002);
+                                assert_approx_eq!(
+                                    f64,
+                                    lorentzian.integral(),
+                                    0.5 * std::f64::consts::PI,
+                                    epsilon = 0.1
+                                );
+                            }
+                            None => assert_approx_eq!(f64, lorentzian.integral(), 0.0),
+                        }
+                    });
             });
+        [
+            (vec![3.0, f64::NAN], 0.02),
+            (vec![3.0, 5.0], 0.0),
+            (vec![3.0, 5.0], f64::INFINITY),
+        ]
+        .into_iter()
+        .for_each(|(template, tolerance)| {
+            let error = deconvoluter
+                .deconvolute_spectra_templated(&spectra, &template, tolerance)
+                .unwrap_err();
+            match error {
+                Error::Deconvolution(inner) => match inner.kind() {
+                    Kind::InvalidTemplate { .. } => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            }
+        });
+    }
+
+    #[test]
+    fn progress() {
+        let signals = (3..=7)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let spectra = (42..47)
+            .map(|seed| noisy_lorentzian_spectrum(&signals, 4000, 0.1, seed, (2.0, 8.0)))
+            .collect::<Vec<Spectrum>>();
+        let deconvoluter = Deconvoluter::default();
+        let check_calls = |calls: &[(usize, usize)]| {
+            assert_eq!(calls.len(), spectra.len());
+            calls
+                .windows(2)
+                .for_each(|w| assert!(w[1].0 > w[0].0));
+            assert!(
+                calls
+                    .iter()
+                    .all(|(_, total)| *total == spectra.len())
+            );
+            assert_eq!(*calls.last().unwrap(), (spectra.len(), spectra.len()));
+        };
+        let mut calls = Vec::new();
+        let deconvolutions = deconvoluter
+            .deconvolute_spectra_with_progress(&spectra, |completed, total| {
+                calls.push((completed, total))
+            })
+            .unwrap();
+        assert_eq!(deconvolutions.len(), spectra.len());
+        check_calls(&calls);
+        #[cfg(feature = "parallel")]
+        {
+            let mut calls = Vec::new();
+            let deconvolutions = deconvoluter
+                .par_deconvolute_spectra_with_progress(&spectra, |completed, total| {
+                    calls.push((completed, total))
+                })
+                .unwrap();
+            assert_eq!(deconvolutions.len(), spectra.len());
+            check_calls(&calls);
+        }
+    }
+
+    #[test]
+    fn cancellation() {
+        struct CancelOnAccess<'a> {
+            spectrum: Spectrum,
+            cancel: Option<&'a AtomicBool>,
+        }
+
+        impl AsRef<Spectrum> for CancelOnAccess<'_> {
+            fn as_ref(&self) -> &Spectrum {
+                if let Some(cancel) = self.cancel {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+                &self.spectrum
+            }
+        }
+
+        let signals = (3..=7)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let deconvoluter = Deconvoluter::default();
+        let check_error = |error: &Error| match error {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::Cancelled => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        };
+        let cancel = AtomicBool::new(false);
+        let spectra = (0..3)
+            .map(|i| CancelOnAccess {
+                spectrum: spectrum.clone(),
+                cancel: (i == 0).then_some(&cancel),
+            })
+            .collect::<Vec<CancelOnAccess>>();
+        check_error(
+            &deconvoluter
+                .deconvolute_spectra_cancellable(&spectra, &cancel)
+                .unwrap_err(),
+        );
+        let cancel = AtomicBool::new(false);
+        let spectra = (0..3)
+            .map(|i| CancelOnAccess {
+                spectrum: spectrum.clone(),
+                cancel: (i == 2).then_some(&cancel),
+            })
+            .collect::<Vec<CancelOnAccess>>();
+        check_error(
+            &deconvoluter
+                .deconvolute_spectra_cancellable(&spectra, &cancel)
+                .unwrap_err(),
+        );
+        let spectra = [spectrum.clone(), spectrum];
+        let cancel = AtomicBool::new(false);
+        let deconvolutions = deconvoluter
+            .deconvolute_spectra_cancellable(&spectra, &cancel)
+            .unwrap();
+        assert_eq!(deconvolutions.len(), 2);
+        #[cfg(feature = "parallel")]
+        {
+            let deconvolutions = deconvoluter
+                .par_deconvolute_spectra_cancellable(&spectra, &cancel)
+                .unwrap();
+            assert_eq!(deconvolutions.len(), 2);
+            cancel.store(true, Ordering::Relaxed);
+            check_error(
+                &deconvoluter
+                    .par_deconvolute_spectra_cancellable(&spectra, &cancel)
+                    .unwrap_err(),
+            );
+        }
     }
 
     #[test]
-    fn invalid_selection_settings() {
-        let mut deconvoluter = Deconvoluter::default();
-        let zero_threshold = SelectionSettings::NoiseScoreFilter {
-            scoring_method: ScoringMethod::default(),
-            threshold: 0.0,
-        };
-        let nan_threshold = SelectionSettings::NoiseScoreFilter {
-            scoring_method: ScoringMethod::default(),
-            threshold: f64::NAN,
-        };
-        let inf_threshold = SelectionSettings::NoiseScoreFilter {
-            scoring_method: ScoringMethod::default(),
-            threshold: f64::INFINITY,
+    fn cancellation_during_fit() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 4.0),
+            Lorentzian::new(1.0 * 0.01, 0.01_f64.powi(2), 6.0),
+        ];
+        let spectra = [noisy_lorentzian_spectrum(
+            &signals,
+            2000,
+            0.01,
+            3,
+            (2.0, 8.0),
+        )];
+        let check_error = |error: Error| match error {
+            Error::Deconvolution(inner) => match inner.kind() {
+                Kind::Cancelled => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
         };
-        let neg_inf_threshold = SelectionSettings::NoiseScoreFilter {
-            scoring_method: ScoringMethod::default(),
-            threshold: f64::NEG_INFINITY,
+        let cancel_later = |cancel: &AtomicBool| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            cancel.store(true, Ordering::Relaxed);
         };
-        let errors = [
-            deconvoluter
-                .set_selection_settings(zero_threshold)
-                .unwrap_err(),
-            deconvoluter
-                .set_selection_settings(nan_threshold)
-                .unwrap_err(),
+        [
+            FittingSettings::Analytical {
+                iterations: usize::MAX,
+            },
+            FittingSettings::Gaussian {
+                iterations: usize::MAX,
+            },
+            FittingSettings::PseudoVoigt {
+                iterations: usize::MAX,
+                eta_init: 0.5,
+            },
+        ]
+        .into_iter()
+        .for_each(|fitting_settings| {
+            let mut deconvoluter = Deconvoluter::default();
             deconvoluter
-                .set_selection_settings(inf_threshold)
-                .unwrap_err(),
+                .set_fitting_settings(fitting_settings)
+                .unwrap();
+            let cancel = AtomicBool::new(false);
+            std::thread::scope(|scope| {
+                scope.spawn(|| cancel_later(&cancel));
+                check_error(
+                    deconvoluter
+                        .deconvolute_spectra_cancellable(&spectra, &cancel)
+                        .unwrap_err(),
+                );
+            });
+            #[cfg(feature = "parallel")]
+            {
+                let cancel = AtomicBool::new(false);
+                std::thread::scope(|scope| {
+                    scope.spawn(|| cancel_later(&cancel));
+                    check_error(
+                        deconvoluter
+                            .par_deconvolute_spectra_cancellable(&spectra, &cancel)
+                            .unwrap_err(),
+                    );
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn noise_estimate() {
+        use crate::deconvolution::peak_selection::{
+            Detector, Scorer, ScorerMinimumSum, second_derivative,
+        };
+
+        let signals = (3..=7)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), i as f64))
+            .collect::<Vec<Lorentzian>>();
+        // The flat noise region is quieter than the rest of the baseline, so an
+        // estimate taken from anywhere else would differ.
+        let noise_region = (8.5, 9.5);
+        let chemical_shifts = (0..4000)
+            .map(|i| i as f64 * 10.0 / 3999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .zip(uniform_noise(4000, 42))
+            .map(|(x, noise)| {
+                let amplitude = if (noise_region.0..noise_region.1).contains(x) {
+                    0.02
+                } else {
+                    0.1
+                };
+                Lorentzian::superposition(*x, &signals) + amplitude * noise
+            })
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (2.0, 8.0)).unwrap();
+        let mut deconvoluter = Deconvoluter::new(
+            SmoothingSettings::Identity,
+            SelectionSettings::default(),
+            FittingSettings::default(),
+        )
+        .unwrap();
+        let default_estimate = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap()
+            .noise_estimate()
+            .unwrap();
+        deconvoluter
+            .set_noise_region(noise_region)
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+
+        let second_derivative = second_derivative(spectrum.intensities());
+        let peaks = Detector::new(&second_derivative)
+            .detect_peaks()
+            .unwrap();
+        let abs_second_derivative = second_derivative
+            .iter()
+            .map(|d| d.abs())
+            .collect::<Vec<f64>>();
+        let scorer = ScorerMinimumSum::new(&abs_second_derivative);
+        let (start, end) = region_indices(&spectrum, noise_region);
+        let scores = peaks
+            .iter()
+            .filter(|peak| peak.center() >= start && peak.center() < end)
+            .map(|peak| scorer.score_peak(peak))
+            .collect::<Vec<f64>>();
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        let sd = (scores
+            .iter()
+            .map(|score| (score - mean).powi(2))
+            .sum::<f64>()
+            / scores.len() as f64)
+            .sqrt();
+        assert!(scores.len() > 10);
+        assert_approx_eq!(f64, deconvolution.noise_estimate().unwrap(), sd);
+        assert!(sd < 0.5 * default_estimate);
+        #[cfg(feature = "parallel")]
+        assert_approx_eq!(
+            f64,
             deconvoluter
-                .set_selection_settings(neg_inf_threshold)
-                .unwrap_err(),
-        ];
-        let expected_context = [
-            zero_threshold,
-            nan_threshold,
-            inf_threshold,
-            neg_inf_threshold,
-        ];
-        errors
-            .into_iter()
-            .zip(expected_context)
-            .for_each(|(error, context)| match error {
-                Error::Deconvolution(inner) => match inner.kind() {
-                    Kind::InvalidSelectionSettings { settings } => {
-                        assert!(SelectionSettings::compare(settings, &context));
-                    }
-                    _ => panic!("unexpected kind: {:?}", inner),
-                },
-                _ => panic!("unexpected error: {:?}", error),
+                .par_deconvolute_spectrum(&spectrum)
+                .unwrap()
+                .noise_estimate()
+                .unwrap(),
+            sd
+        );
+
+        deconvoluter
+            .set_selection_settings(SelectionSettings::DetectorOnly)
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert!(deconvolution.noise_estimate().is_none());
+    }
+
+    #[test]
+    fn mse_weighting() {
+        let signals = (0..3)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 9.9 + 0.1 * i as f64))
+            .collect::<Vec<Lorentzian>>();
+        // A single multiplet on a wide flat baseline, which dominates the
+        // uniform MSE.
+        let chemical_shifts = (0..8000)
+            .map(|i| i as f64 * 20.0 / 7999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .zip(uniform_noise(8000, 42))
+            .map(|(x, noise)| Lorentzian::superposition(*x, &signals) + 0.01 * noise)
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 19.0)).unwrap();
+        // A single iteration leaves the peak tops poorly fitted.
+        let mut deconvoluter = Deconvoluter::new(
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Analytical { iterations: 1 },
+        )
+        .unwrap();
+        let uniform = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        deconvoluter.set_mse_weighting(MseWeighting::IntensityWeighted);
+        let weighted = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert_eq!(uniform.mse_weighting(), MseWeighting::Uniform);
+        assert_eq!(weighted.mse_weighting(), MseWeighting::IntensityWeighted);
+        assert_eq!(uniform.len(), weighted.len());
+        uniform
+            .lorentzians()
+            .iter()
+            .zip(weighted.lorentzians().iter())
+            .for_each(|(uniform, weighted)| {
+                assert_approx_eq!(f64, uniform.maxp(), weighted.maxp());
+                assert_approx_eq!(f64, uniform.sfhw(), weighted.sfhw());
             });
+        let (start, end) = spectrum.signal_boundaries_indices();
+        let residuals = uniform
+            .superposition_vec(spectrum.chemical_shifts())
+            .iter()
+            .zip(spectrum.intensities().iter())
+            .skip(start)
+            .take(end - start)
+            .map(|(superposition, intensity)| (superposition - intensity, intensity.abs()))
+            .collect::<Vec<(f64, f64)>>();
+        let expected_uniform = residuals
+            .iter()
+            .map(|(residual, _)| residual.powi(2))
+            .sum::<f64>()
+            / residuals.len() as f64;
+        let expected_weighted = residuals
+            .iter()
+            .map(|(residual, weight)| weight * residual.powi(2))
+            .sum::<f64>()
+            / residuals
+                .iter()
+                .map(|(_, weight)| weight)
+                .sum::<f64>();
+        assert_approx_eq!(f64, uniform.mse(), expected_uniform, epsilon = 1e-12);
+        assert_approx_eq!(f64, weighted.mse(), expected_weighted, epsilon = 1e-12);
+        assert!(weighted.mse() > 2.0 * uniform.mse());
     }
 
     #[test]
-    fn invalid_fitting_settings() {
+    fn fit_diagnostics() {
+        // A crowded triplet that needs several iterations to be resolved and
+        // isolated singlets that are fitted right away.
+        let signals = (0..3)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 4.98 + 0.02 * i as f64))
+            .chain(
+                [3.0, 7.0]
+                    .into_iter()
+                    .map(|maxp| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), maxp)),
+            )
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (1.0, 9.0));
         let mut deconvoluter = Deconvoluter::default();
-        let zero_iterations = FittingSettings::Analytical { iterations: 0 };
-        let errors = [deconvoluter
-            .set_fitting_settings(zero_iterations)
-            .unwrap_err()];
-        let expected_context = [zero_iterations];
-        errors
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        let diagnostics = deconvolution.fit_diagnostics().unwrap();
+        assert_eq!(diagnostics.len(), deconvolution.len());
+        assert!(
+            diagnostics
+                .iter()
+                .all(|diagnostics| diagnostics.iterations() == 10)
+        );
+        #[cfg(feature = "parallel")]
+        assert_eq!(
+            deconvoluter
+                .par_deconvolute_spectrum(&spectrum)
+                .unwrap()
+                .fit_diagnostics()
+                .unwrap(),
+            diagnostics
+        );
+
+        deconvoluter
+            .set_fitting_settings(FittingSettings::Analytical { iterations: 1 })
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        let diagnostics = deconvolution.fit_diagnostics().unwrap();
+        assert_eq!(diagnostics.len(), deconvolution.len());
+        let diagnostics_near = |position: f64| {
+            deconvolution
+                .lorentzians()
+                .iter()
+                .zip(diagnostics)
+                .find(|(lorentzian, _)| (lorentzian.maxp() - position).abs() < 0.005)
+                .map(|(_, diagnostics)| diagnostics)
+                .unwrap()
+        };
+        [4.98, 5.0, 5.02]
             .into_iter()
-            .zip(expected_context)
-            .for_each(|(error, context)| match error {
-                Error::Deconvolution(inner) => match inner.kind() {
-                    Kind::InvalidFittingSettings { settings } => {
-                        assert!(FittingSettings::compare(settings, &context));
-                    }
-                    _ => panic!("unexpected kind: {:?}", inner),
-                },
-                _ => panic!("unexpected error: {:?}", error),
-            });
+            .for_each(|position| assert!(!diagnostics_near(position).converged()));
+        [3.0, 7.0]
+            .into_iter()
+            .for_each(|position| assert!(diagnostics_near(position).converged()));
+
+        deconvoluter
+            .set_fitting_settings(FittingSettings::Gaussian { iterations: 10 })
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert!(deconvolution.fit_diagnostics().is_none());
+        assert!(
+            deconvolution
+                .merge_close_peaks(0.01)
+                .fit_diagnostics()
+                .is_none()
+        );
     }
 
     #[test]
-    fn add_ignore_region() {
+    fn parameter_uncertainties() {
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 5.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 5.015),
+        ];
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
         let mut deconvoluter = Deconvoluter::default();
         deconvoluter
-            .add_ignore_region((1.0, 2.0))
+            .set_fitting_settings(FittingSettings::LevenbergMarquardt {
+                max_iterations: 100,
+                tolerance: 1e-12,
+                lambda_init: 1e-3,
+            })
             .unwrap();
-        deconvoluter
-            .add_ignore_region((3.0, 4.0))
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
             .unwrap();
-        assert!(deconvoluter.ignore_regions().is_some());
-        assert_eq!(deconvoluter.ignore_regions().unwrap().len(), 2);
+        let uncertainties = deconvolution.parameter_uncertainties().unwrap();
+        assert_eq!(uncertainties.len(), deconvolution.len());
+        let uncertainty = |maxp: f64| {
+            let (_, uncertainty) = deconvolution
+                .lorentzians()
+                .iter()
+                .zip(uncertainties)
+                .min_by(|(a, _), (b, _)| {
+                    f64::abs(a.maxp() - maxp).total_cmp(&f64::abs(b.maxp() - maxp))
+                })
+                .unwrap();
+            *uncertainty
+        };
+        let isolated = uncertainty(3.0);
+        let overlapping = [uncertainty(5.0), uncertainty(5.015)];
+        assert!(isolated.sf() < 1e-3);
+        assert!(isolated.hw() < 1e-4);
+        assert!(isolated.maxp() < 1e-4);
+        overlapping.iter().for_each(|overlapping| {
+            assert!(overlapping.sf() > isolated.sf());
+            assert!(overlapping.hw() > isolated.hw());
+            assert!(overlapping.maxp() > isolated.maxp());
+        });
+        #[cfg(feature = "parallel")]
+        assert_eq!(
+            deconvoluter
+                .par_deconvolute_spectrum(&spectrum)
+                .unwrap()
+                .parameter_uncertainties()
+                .unwrap(),
+            uncertainties
+        );
+        assert!(
+            deconvolution
+                .merge_close_peaks(0.01)
+                .parameter_uncertainties()
+                .is_none()
+        );
+
         deconvoluter
-            .add_ignore_region((2.0, 3.0))
+            .set_fitting_settings(FittingSettings::Analytical { iterations: 10 })
             .unwrap();
-        assert_eq!(deconvoluter.ignore_regions().unwrap().len(), 1);
-        assert_approx_eq!(f64, deconvoluter.ignore_regions().unwrap()[0].0, 1.0);
-        assert_approx_eq!(f64, deconvoluter.ignore_regions().unwrap()[0].1, 4.0);
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert!(deconvolution.parameter_uncertainties().is_none());
     }
 
     #[test]
-    fn invalid_ignore_region() {
+    #[cfg(feature = "parallel")]
+    fn optimize_settings_in_region() {
+        let signals = [3.98, 4.02, 6.98, 7.02]
+            .into_iter()
+            .map(|maxp| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), maxp))
+            .collect::<Vec<Lorentzian>>();
+        let chemical_shifts = (0..2000)
+            .map(|i| i as f64 * 10.0 / 1999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .zip(uniform_noise(2000, 42))
+            .map(|(x, noise)| {
+                let amplitude = if (6.0..8.0).contains(x) { 2.0 } else { 0.1 };
+                Lorentzian::superposition(*x, &signals) + amplitude * noise
+            })
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        let region = (3.5, 4.5);
+        let default_mse = Deconvoluter::default()
+            .deconvolute_region(&spectrum, region)
+            .unwrap()
+            .mse();
         let mut deconvoluter = Deconvoluter::default();
-        let errors = [
-            deconvoluter
-                .add_ignore_region((f64::NAN, 1.0))
-                .unwrap_err(),
-            deconvoluter
-                .add_ignore_region((1.0, f64::NAN))
-                .unwrap_err(),
-            deconvoluter
-                .add_ignore_region((f64::INFINITY, 1.0))
-                .unwrap_err(),
-            deconvoluter
-                .add_ignore_region((1.0, f64::INFINITY))
-                .unwrap_err(),
-            deconvoluter
-                .add_ignore_region((f64::NEG_INFINITY, 1.0))
-                .unwrap_err(),
-            deconvoluter
-                .add_ignore_region((1.0, f64::NEG_INFINITY))
-                .unwrap_err(),
+        let mse = deconvoluter
+            .optimize_settings_in_region(&spectrum, region)
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_region(&spectrum, region)
+            .unwrap();
+        assert_approx_eq!(f64, deconvolution.mse(), mse);
+        assert!(mse <= default_mse);
+        // The residuals within the clean region are at the level of the noise.
+        assert!(mse < 1.1 * 0.1_f64.powi(2) / 12.0);
+        assert!(
+            deconvolution
+                .lorentzians()
+                .iter()
+                .all(|lorentzian| (region.0..region.1).contains(&lorentzian.maxp()))
+        );
+        match deconvoluter.smoothing_settings() {
+            SmoothingSettings::MovingAverage { .. } => {}
+            _ => panic!("expected moving average"),
+        }
+        match deconvoluter.fitting_settings() {
+            FittingSettings::Analytical { iterations } => assert!((5..=15).contains(&iterations)),
+            _ => panic!("expected analytical fitting"),
+        }
+        assert!(
             deconvoluter
-                .add_ignore_region((1.0, 1.0))
-                .unwrap_err(),
-        ];
-        let expected_context = [
-            (f64::NAN, 1.0),
-            (1.0, f64::NAN),
-            (f64::INFINITY, 1.0),
-            (1.0, f64::INFINITY),
-            (f64::NEG_INFINITY, 1.0),
-            (1.0, f64::NEG_INFINITY),
-            (1.0, 1.0),
-        ];
-        errors
+                .optimize_settings_in_region(&spectrum, (4.0, 4.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn optimize_settings_with_grid() {
+        // A crowded triplet of narrow signals, which is blurred by heavy
+        // smoothing and needs several iterations to be resolved, so the best
+        // candidates are neither first nor last in the grid.
+        let signals = [4.98, 5.0, 5.02, 6.98, 7.02]
             .into_iter()
-            .zip(expected_context)
-            .for_each(|(error, context)| match error {
-                Error::Deconvolution(inner) => match inner.kind() {
-                    Kind::InvalidIgnoreRegion { region } => {
-                        assert_approx_eq!(f64, region.0, context.0);
-                        assert_approx_eq!(f64, region.1, context.1);
-                    }
-                    _ => panic!("Unexpected kind: {:?}", inner.kind()),
+            .map(|maxp| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), maxp))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.01, 42, (1.0, 9.0));
+        let grid = OptimizationGrid::new(
+            vec![
+                SmoothingSettings::MovingAverage {
+                    iterations: 4,
+                    window_size: 7,
                 },
-                _ => panic!("Unexpected error: {:?}", error),
-            });
+                SmoothingSettings::MovingAverage {
+                    iterations: 1,
+                    window_size: 3,
+                },
+            ],
+            [5.0, 7.0]
+                .into_iter()
+                .map(|threshold| SelectionSettings::NoiseScoreFilter {
+                    scoring_method: ScoringMethod::MinimumSum,
+                    threshold,
+                    max_peaks: None,
+                    noise_estimator: NoiseEstimator::StdDev,
+                })
+                .collect(),
+            vec![
+                FittingSettings::Analytical { iterations: 10 },
+                FittingSettings::Analytical { iterations: 1 },
+            ],
+        )
+        .unwrap();
+        let (selection_settings, fitting_settings) =
+            (grid.selection_settings(), grid.fitting_settings());
+        let mses = grid
+            .smoothing_settings()
+            .iter()
+            .flat_map(|smoothing| {
+                selection_settings
+                    .iter()
+                    .flat_map(move |selection| {
+                        fitting_settings
+                            .iter()
+                            .map(move |fitting| (*smoothing, *selection, *fitting))
+                    })
+            })
+            .map(|(smoothing, selection, fitting)| {
+                Deconvoluter::new(smoothing, selection, fitting)
+                    .unwrap()
+                    .deconvolute_spectrum(&spectrum)
+                    .unwrap()
+                    .mse()
+            })
+            .collect::<Vec<f64>>();
+        assert_eq!(mses.len(), grid.len());
+        let mut deconvoluter = Deconvoluter::default();
+        let mse = deconvoluter
+            .optimize_settings_with_grid(&spectrum, grid.clone())
+            .unwrap();
+        assert_approx_eq!(f64, mse, mses.iter().copied().fold(f64::INFINITY, f64::min));
+        match deconvoluter.smoothing_settings() {
+            SmoothingSettings::MovingAverage {
+                iterations,
+                window_size,
+            } => assert_eq!((iterations, window_size), (1, 3)),
+            _ => panic!("expected moving average"),
+        }
+        match deconvoluter.fitting_settings() {
+            FittingSettings::Analytical { iterations } => assert_eq!(iterations, 10),
+            _ => panic!("expected analytical fitting"),
+        }
+        // The settings don't implement PartialEq, so compare their debug output.
+        let contains = |candidates: Vec<String>, chosen: String| candidates.contains(&chosen);
+        assert!(contains(
+            grid.smoothing_settings()
+                .iter()
+                .map(|settings| format!("{:?}", settings))
+                .collect(),
+            format!("{:?}", deconvoluter.smoothing_settings())
+        ));
+        assert!(contains(
+            grid.selection_settings()
+                .iter()
+                .map(|settings| format!("{:?}", settings))
+                .collect(),
+            format!("{:?}", deconvoluter.selection_settings())
+        ));
+        assert!(contains(
+            grid.fitting_settings()
+                .iter()
+                .map(|settings| format!("{:?}", settings))
+                .collect(),
+            format!("{:?}", deconvoluter.fitting_settings())
+        ));
+        assert_approx_eq!(
+            f64,
+            deconvoluter
+                .deconvolute_spectrum(&spectrum)
+                .unwrap()
+                .mse(),
+            mse
+        );
     }
 
     #[test]
-    fn clear_ignore_regions() {
+    #[cfg(feature = "parallel")]
+    fn optimization_report() {
+        let signals = [3.98, 4.02, 6.98, 7.02]
+            .into_iter()
+            .map(|maxp| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), maxp))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 2000, 0.1, 42, (1.0, 9.0));
         let mut deconvoluter = Deconvoluter::default();
-        deconvoluter
-            .add_ignore_region((1.0, 2.0))
+        let report = deconvoluter
+            .optimization_report(&spectrum)
             .unwrap();
-        deconvoluter
-            .add_ignore_region((3.0, 4.0))
+        assert_eq!(report.len(), OptimizationGrid::default().len());
+        assert!(
+            report
+                .windows(2)
+                .all(|pair| pair[0].mse() <= pair[1].mse())
+        );
+        // The report doesn't modify the settings.
+        assert_eq!(
+            format!("{:?}", deconvoluter.smoothing_settings()),
+            format!("{:?}", SmoothingSettings::default())
+        );
+        let mse = deconvoluter.optimize_settings(&spectrum).unwrap();
+        assert_approx_eq!(f64, mse, report[0].mse());
+        assert_eq!(
+            format!("{:?}", deconvoluter.smoothing_settings()),
+            format!("{:?}", report[0].smoothing_settings())
+        );
+        assert_eq!(
+            format!("{:?}", deconvoluter.selection_settings()),
+            format!("{:?}", report[0].selection_settings())
+        );
+        assert_eq!(
+            format!("{:?}", deconvoluter.fitting_settings()),
+            format!("{:?}", report[0].fitting_settings())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn par_ignore_regions() {
+        // Doublets in the segments between the ignore regions and a singlet right
+        // next to one of them, so that peaks lost at the segment edges or merged
+        // out of order would show.
+        let signals = (3..=7)
+            .flat_map(|i| {
+                [-0.02, 0.02]
+                    .map(|offset| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), i as f64 + offset))
+            })
+            .chain([Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 5.65)])
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (2.0, 8.0));
+        let mut deconvoluter = Deconvoluter::default();
+        // Includes an overlapping pair and a region at the signal boundary.
+        [(3.9, 4.1), (5.5, 5.6), (5.55, 5.62), (6.9, 7.1), (7.9, 8.5)]
+            .into_iter()
+            .for_each(|region| deconvoluter.add_ignore_region(region).unwrap());
+        let expected = deconvoluter
+            .deconvolute_spectrum(&spectrum)
             .unwrap();
-        deconvoluter.clear_ignore_regions();
-        assert!(deconvoluter.ignore_regions().is_none());
+        let deconvolution = deconvoluter
+            .par_deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(deconvolution.len(), expected.len());
+        assert!(
+            deconvolution
+                .lorentzians()
+                .iter()
+                .all(|lorentzian| (lorentzian.maxp() - 4.0).abs() > 0.05
+                    && (lorentzian.maxp() - 7.0).abs() > 0.05)
+        );
+        [2.98, 3.02, 4.98, 5.02, 5.65, 5.98, 6.02]
+            .into_iter()
+            .for_each(|position| {
+                assert!(
+                    deconvolution
+                        .lorentzians()
+                        .iter()
+                        .any(|lorentzian| (lorentzian.maxp() - position).abs() < 0.005)
+                )
+            });
+        assert!(
+            deconvolution
+                .lorentzians()
+                .windows(2)
+                .all(|pair| pair[0].maxp() <= pair[1].maxp())
+        );
+        deconvolution
+            .lorentzians()
+            .iter()
+            .zip(expected.lorentzians())
+            .for_each(|(lorentzian, expected)| {
+                assert_approx_eq!(f64, lorentzian.sfhw(), expected.sfhw());
+                assert_approx_eq!(f64, lorentzian.hw2(), expected.hw2());
+                assert_approx_eq!(f64, lorentzian.maxp(), expected.maxp());
+            });
+        assert_approx_eq!(f64, deconvolution.mse(), expected.mse(), epsilon = 1e-12);
+        assert_eq!(deconvolution.noise_estimate(), expected.noise_estimate());
     }
 }
@@ -0,0 +1,290 @@
+use crate::Result;
+use crate::deconvolution::baseline::BaselineSettings;
+use crate::deconvolution::fitting::FittingSettings;
+use crate::deconvolution::peak_selection::SelectionSettings;
+use crate::deconvolution::smoothing::SmoothingSettings;
+use crate::deconvolution::{Deconvoluter, MseWeighting};
+
+/// Builder for configuring a [`Deconvoluter`] in a single expression.
+///
+/// The settings are only stored by the chained methods and validated when the
+/// [`Deconvoluter`] is built. Settings that are not provided keep their default
+/// values, as with [`Deconvoluter::default`]. Ignore regions are accumulated
+/// and overlapping regions are combined, as with
+/// [`Deconvoluter::add_ignore_region`].
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::{
+///     BaselineSettings, DeconvoluterBuilder, FittingSettings, NoiseEstimator, ScoringMethod,
+///     SelectionSettings, SmoothingSettings,
+/// };
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let deconvoluter = DeconvoluterBuilder::new()
+///     .smoothing(SmoothingSettings::MovingAverage {
+///         iterations: 3,
+///         window_size: 3,
+///     })
+///     .selection(SelectionSettings::NoiseScoreFilter {
+///         scoring_method: ScoringMethod::MinimumSum,
+///         threshold: 5.0,
+///         max_peaks: None,
+///         noise_estimator: NoiseEstimator::StdDev,
+///     })
+///     .fitting(FittingSettings::Analytical { iterations: 20 })
+///     .baseline(BaselineSettings::None)
+///     .ignore_region((4.7, 4.9))
+///     .ignore_region((4.8, 5.0))
+///     .noise_region((10.0, 11.0))
+///     .build()?;
+///
+/// // The overlapping ignore regions were combined.
+/// assert_eq!(deconvoluter.ignore_regions().unwrap(), &[(4.7, 5.0)]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DeconvoluterBuilder {
+    /// Baseline correction settings.
+    baseline_settings: BaselineSettings,
+    /// Smoothing settings.
+    smoothing_settings: SmoothingSettings,
+    /// Peak selection settings.
+    selection_settings: SelectionSettings,
+    /// Fitting settings.
+    fitting_settings: FittingSettings,
+    /// Regions to ignore during deconvolution.
+    ignore_regions: Vec<(f64, f64)>,
+    /// Regions with a lowered peak selection threshold, and their factors.
+    boost_regions: Vec<((f64, f64), f64)>,
+    /// Region used to estimate the noise level.
+    noise_region: Option<(f64, f64)>,
+    /// Weighting of the residuals used to compute the MSE.
+    mse_weighting: MseWeighting,
+}
+
+impl DeconvoluterBuilder {
+    /// Constructs a new `DeconvoluterBuilder` with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the baseline correction settings.
+    ///
+    /// See [`Deconvoluter::set_baseline_settings`] for details.
+    pub fn baseline(mut self, baseline_settings: BaselineSettings) -> Self {
+        self.baseline_settings = baseline_settings;
+        self
+    }
+
+    /// Sets the smoothing settings.
+    ///
+    /// See [`Deconvoluter::set_smoothing_settings`] for details.
+    pub fn smoothing(mut self, smoothing_settings: SmoothingSettings) -> Self {
+        self.smoothing_settings = smoothing_settings;
+        self
+    }
+
+    /// Sets the peak selection settings.
+    ///
+    /// See [`Deconvoluter::set_selection_settings`] for details.
+    pub fn selection(mut self, selection_settings: SelectionSettings) -> Self {
+        self.selection_settings = selection_settings;
+        self
+    }
+
+    /// Sets the fitting settings.
+    ///
+    /// See [`Deconvoluter::set_fitting_settings`] for details.
+    pub fn fitting(mut self, fitting_settings: FittingSettings) -> Self {
+        self.fitting_settings = fitting_settings;
+        self
+    }
+
+    /// Adds a region to ignore during deconvolution.
+    ///
+    /// See [`Deconvoluter::add_ignore_region`] for details.
+    pub fn ignore_region(mut self, region: (f64, f64)) -> Self {
+        self.ignore_regions.push(region);
+        self
+    }
+
+    /// Adds a region in which the peak selection threshold is lowered.
+    ///
+    /// See [`Deconvoluter::add_boost_region`] for details.
+    pub fn boost_region(mut self, region: (f64, f64), factor: f64) -> Self {
+        self.boost_regions.push((region, factor));
+        self
+    }
+
+    /// Sets the region used to estimate the noise level.
+    ///
+    /// See [`Deconvoluter::set_noise_region`] for details.
+    pub fn noise_region(mut self, region: (f64, f64)) -> Self {
+        self.noise_region = Some(region);
+        self
+    }
+
+    /// Sets the weighting of the residuals used to compute the MSE.
+    ///
+    /// See [`Deconvoluter::set_mse_weighting`] for details.
+    pub fn mse_weighting(mut self, mse_weighting: MseWeighting) -> Self {
+        self.mse_weighting = mse_weighting;
+        self
+    }
+
+    /// Builds the [`Deconvoluter`] from the provided settings.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any of the deconvolution settings, ignore
+    /// regions, boost regions, or the noise region are invalid. See the respective methods of
+    /// [`Deconvoluter`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{DeconvoluterBuilder, FittingSettings};
+    ///
+    /// let result = DeconvoluterBuilder::new()
+    ///     .fitting(FittingSettings::Analytical { iterations: 0 })
+    ///     .build();
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    pub fn build(self) -> Result<Deconvoluter> {
+        let mut deconvoluter = Deconvoluter::new(
+            self.smoothing_settings,
+            self.selection_settings,
+            self.fitting_settings,
+        )?;
+        deconvoluter.set_baseline_settings(self.baseline_settings)?;
+        for region in self.ignore_regions {
+            deconvoluter.add_ignore_region(region)?;
+        }
+        for (region, factor) in self.boost_regions {
+            deconvoluter.add_boost_region(region, factor)?;
+        }
+        if let Some(region) = self.noise_region {
+            deconvoluter.set_noise_region(region)?;
+        }
+        deconvoluter.set_mse_weighting(self.mse_weighting);
+
+        Ok(deconvoluter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use crate::deconvolution::error::Kind;
+    use crate::deconvolution::{NoiseEstimator, ScoringMethod};
+
+    #[test]
+    fn build() {
+        let deconvoluter = DeconvoluterBuilder::new()
+            .smoothing(SmoothingSettings::MovingAverage {
+                iterations: 2,
+                window_size: 5,
+            })
+            .selection(SelectionSettings::NoiseScoreFilter {
+                scoring_method: ScoringMethod::MinimumSum,
+                threshold: 4.0,
+                max_peaks: None,
+                noise_estimator: NoiseEstimator::StdDev,
+            })
+            .fitting(FittingSettings::Analytical { iterations: 15 })
+            .ignore_region((5.2, 5.6))
+            .ignore_region((4.9, 4.7))
+            .ignore_region((4.8, 5.4))
+            .ignore_region((8.0, 8.5))
+            .boost_region((3.45, 3.4), 4.0)
+            .noise_region((11.0, 10.0))
+            .build()
+            .unwrap();
+        match deconvoluter.smoothing_settings() {
+            SmoothingSettings::MovingAverage {
+                iterations,
+                window_size,
+            } => {
+                assert_eq!(iterations, 2);
+                assert_eq!(window_size, 5);
+            }
+            _ => panic!("unexpected smoothing settings"),
+        };
+        match deconvoluter.fitting_settings() {
+            FittingSettings::Analytical { iterations } => assert_eq!(iterations, 15),
+            _ => panic!("unexpected fitting settings"),
+        };
+        assert_eq!(
+            deconvoluter.ignore_regions().unwrap(),
+            &[(4.7, 5.6), (8.0, 8.5)]
+        );
+        assert_eq!(deconvoluter.boost_regions().unwrap(), &[((3.4, 3.45), 4.0)]);
+        assert_eq!(deconvoluter.noise_region(), Some((10.0, 11.0)));
+    }
+
+    #[test]
+    fn build_defaults() {
+        let deconvoluter = DeconvoluterBuilder::new().build().unwrap();
+        let default = Deconvoluter::default();
+        assert_eq!(
+            format!("{:?}", deconvoluter.smoothing_settings()),
+            format!("{:?}", default.smoothing_settings())
+        );
+        assert_eq!(
+            format!("{:?}", deconvoluter.selection_settings()),
+            format!("{:?}", default.selection_settings())
+        );
+        assert_eq!(
+            format!("{:?}", deconvoluter.fitting_settings()),
+            format!("{:?}", default.fitting_settings())
+        );
+        assert!(deconvoluter.ignore_regions().is_none());
+        assert!(deconvoluter.boost_regions().is_none());
+        assert!(deconvoluter.noise_region().is_none());
+    }
+
+    #[test]
+    fn build_invalid() {
+        let errors = [
+            DeconvoluterBuilder::new()
+                .smoothing(SmoothingSettings::MovingAverage {
+                    iterations: 2,
+                    window_size: 0,
+                })
+                .build()
+                .unwrap_err(),
+            DeconvoluterBuilder::new()
+                .ignore_region((4.7, f64::NAN))
+                .build()
+                .unwrap_err(),
+            DeconvoluterBuilder::new()
+                .noise_region((10.0, 10.0))
+                .build()
+                .unwrap_err(),
+            DeconvoluterBuilder::new()
+                .boost_region((3.4, 3.45), 0.5)
+                .build()
+                .unwrap_err(),
+        ];
+        errors
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, error)| {
+                match error {
+                    Error::Deconvolution(inner) => match (i, inner.kind()) {
+                        (0, Kind::InvalidSmoothingSettings { .. }) => {}
+                        (1, Kind::InvalidIgnoreRegion { .. }) => {}
+                        (2, Kind::InvalidNoiseRegion { .. }) => {}
+                        (3, Kind::InvalidBoostRegion { .. }) => {}
+                        _ => panic!("unexpected kind: {:?}", inner),
+                    },
+                    _ => panic!("unexpected error: {:?}", error),
+                };
+            });
+    }
+}
@@ -0,0 +1,203 @@
+use crate::deconvolution::MseWeighting;
+use crate::deconvolution::baseline::BaselineSettings;
+use crate::deconvolution::fitting::FittingSettings;
+use crate::deconvolution::peak_selection::SelectionSettings;
+use crate::deconvolution::smoothing::SmoothingSettings;
+use serde::{Deserialize, Serialize};
+
+/// Serializable configuration of a [`Deconvoluter`].
+///
+/// Captures all settings and regions of a [`Deconvoluter`], such that a tuned
+/// pipeline can be saved to a config file and reloaded later. Created with
+/// [`Deconvoluter::to_config`] and turned back into a [`Deconvoluter`] with
+/// [`Deconvoluter::from_config`], which validates the configuration.
+///
+/// Settings that are missing from a serialized configuration keep their
+/// default values, as with [`Deconvoluter::default`].
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+/// [`Deconvoluter::to_config`]: crate::deconvolution::Deconvoluter::to_config
+/// [`Deconvoluter::from_config`]: crate::deconvolution::Deconvoluter::from_config
+/// [`Deconvoluter::default`]: crate::deconvolution::Deconvoluter::default
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::{Deconvoluter, DeconvoluterConfig, FittingSettings};
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let deconvoluter = Deconvoluter::builder()
+///     .fitting(FittingSettings::Analytical { iterations: 20 })
+///     .ignore_region((4.7, 4.9))
+///     .build()?;
+///
+/// // Save the configuration as JSON.
+/// let json = serde_json::to_string_pretty(&deconvoluter.to_config()).unwrap();
+///
+/// // Reload the configuration into a new Deconvoluter.
+/// let config = serde_json::from_str::<DeconvoluterConfig>(&json).unwrap();
+/// let reloaded = Deconvoluter::from_config(config)?;
+///
+/// assert_eq!(reloaded.ignore_regions().unwrap(), &[(4.7, 4.9)]);
+/// match reloaded.fitting_settings() {
+///     FittingSettings::Analytical { iterations } => assert_eq!(iterations, 20),
+///     _ => panic!("Unexpected fitting settings"),
+/// };
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeconvoluterConfig {
+    /// Baseline correction settings.
+    #[serde(default)]
+    pub(crate) baseline_settings: BaselineSettings,
+    /// Smoothing settings.
+    #[serde(default)]
+    pub(crate) smoothing_settings: SmoothingSettings,
+    /// Peak selection settings.
+    #[serde(default)]
+    pub(crate) selection_settings: SelectionSettings,
+    /// Fitting settings.
+    #[serde(default)]
+    pub(crate) fitting_settings: FittingSettings,
+    /// Regions to ignore during deconvolution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) ignore_regions: Vec<(f64, f64)>,
+    /// Regions with a lowered peak selection threshold, and their factors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) boost_regions: Vec<((f64, f64), f64)>,
+    /// Region used to estimate the noise level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) noise_region: Option<(f64, f64)>,
+    /// Weighting of the residuals used to compute the MSE.
+    #[serde(default)]
+    pub(crate) mse_weighting: MseWeighting,
+}
+
+impl DeconvoluterConfig {
+    /// Returns the baseline correction settings.
+    pub fn baseline_settings(&self) -> BaselineSettings {
+        self.baseline_settings
+    }
+
+    /// Returns the smoothing settings.
+    pub fn smoothing_settings(&self) -> SmoothingSettings {
+        self.smoothing_settings
+    }
+
+    /// Returns the peak selection settings.
+    pub fn selection_settings(&self) -> SelectionSettings {
+        self.selection_settings
+    }
+
+    /// Returns the fitting settings.
+    pub fn fitting_settings(&self) -> FittingSettings {
+        self.fitting_settings
+    }
+
+    /// Returns the regions to ignore during deconvolution.
+    pub fn ignore_regions(&self) -> &[(f64, f64)] {
+        &self.ignore_regions
+    }
+
+    /// Returns the boost regions and their factors.
+    pub fn boost_regions(&self) -> &[((f64, f64), f64)] {
+        &self.boost_regions
+    }
+
+    /// Returns the region used to estimate the noise level, if set.
+    pub fn noise_region(&self) -> Option<(f64, f64)> {
+        self.noise_region
+    }
+
+    /// Returns the weighting of the residuals used to compute the MSE.
+    pub fn mse_weighting(&self) -> MseWeighting {
+        self.mse_weighting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Settings;
+    use crate::deconvolution::{Deconvoluter, NoiseEstimator, ScoringMethod};
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(DeconvoluterConfig);
+        assert_sync!(DeconvoluterConfig);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let deconvoluter = Deconvoluter::builder()
+            .baseline(BaselineSettings::AsymmetricLeastSquares {
+                lambda: 1e5,
+                p: 0.01,
+                iterations: 10,
+            })
+            .smoothing(SmoothingSettings::MovingAverage {
+                iterations: 2,
+                window_size: 5,
+            })
+            .selection(SelectionSettings::NoiseScoreFilter {
+                scoring_method: ScoringMethod::MinimumSum,
+                threshold: 4.5,
+                max_peaks: Some(100),
+                noise_estimator: NoiseEstimator::Mad,
+            })
+            .fitting(FittingSettings::LevenbergMarquardt {
+                max_iterations: 50,
+                tolerance: 1e-8,
+                lambda_init: 1e-3,
+            })
+            .ignore_region((4.7, 4.9))
+            .ignore_region((1.1, 1.3))
+            .boost_region((3.0, 3.2), 2.5)
+            .noise_region((10.0, 11.0))
+            .mse_weighting(MseWeighting::IntensityWeighted)
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&deconvoluter.to_config()).unwrap();
+        let config = serde_json::from_str::<DeconvoluterConfig>(&json).unwrap();
+        let reloaded = Deconvoluter::from_config(config).unwrap();
+        assert!(BaselineSettings::compare(
+            &reloaded.baseline_settings(),
+            &deconvoluter.baseline_settings()
+        ));
+        assert!(SmoothingSettings::compare(
+            &reloaded.smoothing_settings(),
+            &deconvoluter.smoothing_settings()
+        ));
+        assert!(SelectionSettings::compare(
+            &reloaded.selection_settings(),
+            &deconvoluter.selection_settings()
+        ));
+        assert!(FittingSettings::compare(
+            &reloaded.fitting_settings(),
+            &deconvoluter.fitting_settings()
+        ));
+        assert_eq!(reloaded.ignore_regions(), deconvoluter.ignore_regions());
+        assert_eq!(reloaded.boost_regions(), deconvoluter.boost_regions());
+        assert_eq!(reloaded.noise_region(), deconvoluter.noise_region());
+        assert_eq!(reloaded.mse_weighting(), deconvoluter.mse_weighting());
+        assert_eq!(serde_json::to_string(&reloaded.to_config()).unwrap(), json);
+    }
+
+    #[test]
+    fn defaults_and_validation() {
+        let config = serde_json::from_str::<DeconvoluterConfig>("{}").unwrap();
+        let deconvoluter = Deconvoluter::from_config(config).unwrap();
+        let default = Deconvoluter::default();
+        assert!(SelectionSettings::compare(
+            &deconvoluter.selection_settings(),
+            &default.selection_settings()
+        ));
+        assert!(deconvoluter.ignore_regions().is_none());
+        let json = r#"{"fittingSettings": {"method": "Analytical", "iterations": 0}}"#;
+        let config = serde_json::from_str::<DeconvoluterConfig>(json).unwrap();
+        assert!(Deconvoluter::from_config(config).is_err());
+    }
+}
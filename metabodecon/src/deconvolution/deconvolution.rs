@@ -1,9 +1,24 @@
-use crate::deconvolution::fitting::FittingSettings;
+use crate::Result;
+use crate::deconvolution::deconvolution_warning::DeconvolutionWarning;
+use crate::deconvolution::error::{Error, Kind};
+use crate::deconvolution::evaluation_regions::{evaluation_regions, ignore_region_indices};
+use crate::deconvolution::fitted_signals::FittedSignals;
+use crate::deconvolution::fitting::{FitDiagnostics, FittingSettings, ParameterUncertainty};
+use crate::deconvolution::gaussian::Gaussian;
 use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::mse_weighting::MseWeighting;
 use crate::deconvolution::peak_selection::SelectionSettings;
+use crate::deconvolution::peak_shape::PeakShape;
+use crate::deconvolution::pseudo_voigt::PseudoVoigt;
+use crate::deconvolution::signal::Signal;
 use crate::deconvolution::smoothing::SmoothingSettings;
+use crate::spectrum::Spectrum;
+use std::ops::Index;
 use std::sync::Arc;
 
+#[cfg(any(feature = "bruker", feature = "csv"))]
+use std::path::Path;
+
 #[cfg(feature = "serde")]
 use crate::deconvolution::SerializedDeconvolution;
 #[cfg(feature = "serde")]
@@ -17,6 +32,15 @@ use serde::{Deserialize, Serialize};
 ///
 /// [`Spectrum`]: crate::spectrum::Spectrum
 ///
+/// If the signals were fitted with a different peak shape, such as with
+/// [`FittingSettings::Gaussian`], the deconvoluted signals are stored as the
+/// respective type instead, e.g. [`Gaussian`] or [`PseudoVoigt`], and the
+/// [`Lorentzian`]s are empty. The signals are kept as [`FittedSignals`], and
+/// all methods cover the signals regardless of their shape, which are
+/// represented by [`Signal`] where needed. The exceptions are the accessors of
+/// the individual peak shapes and the [`Index`] and [`IntoIterator`]
+/// implementations, which lend the [`Lorentzian`]s.
+///
 /// While it is possible to construct a `Deconvolution` directly, this will
 /// almost never be what you want. This type is the output of the deconvolution
 /// functions of the [`Deconvoluter`] type.
@@ -43,8 +67,8 @@ use serde::{Deserialize, Serialize};
     serde(into = "SerializedDeconvolution", try_from = "SerializedDeconvolution")
 )]
 pub struct Deconvolution {
-    /// Deconvoluted signals.
-    lorentzians: Arc<[Lorentzian]>,
+    /// Deconvoluted signals, stored as the peak shape they were fitted with.
+    signals: FittedSignals,
     /// Smoothing parameters used.
     smoothing_settings: SmoothingSettings,
     /// Peak selection parameters used.
@@ -53,6 +77,19 @@ pub struct Deconvolution {
     fitting_settings: FittingSettings,
     /// Mean squared error of the deconvolution.
     mse: f64,
+    /// Estimated noise level used during the peak selection.
+    noise_estimate: Option<f64>,
+    /// Weighting of the residuals used to compute the MSE.
+    mse_weighting: MseWeighting,
+    /// Diagnostics of the fit of each signal, if reported by the fitter.
+    fit_diagnostics: Option<Arc<[FitDiagnostics]>>,
+    /// Standard errors of the parameters of each signal, if estimated by the
+    /// fitter.
+    parameter_uncertainties: Option<Arc<[ParameterUncertainty]>>,
+    /// Labels of the deconvoluted signals, e.g. names of metabolites.
+    labels: Vec<Option<String>>,
+    /// Quality issues found during the deconvolution.
+    warnings: Vec<DeconvolutionWarning>,
 }
 
 impl AsRef<Deconvolution> for Deconvolution {
@@ -61,6 +98,40 @@ impl AsRef<Deconvolution> for Deconvolution {
     }
 }
 
+/// Iterates over the deconvoluted [`Lorentzian`]s.
+///
+/// Signals fitted with other peak shapes are not included, iterate over
+/// [`Deconvolution::signals`] to cover signals of any peak shape.
+impl<'a> IntoIterator for &'a Deconvolution {
+    type Item = &'a Lorentzian;
+    type IntoIter = std::slice::Iter<'a, Lorentzian>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lorentzians().iter()
+    }
+}
+
+/// Indexes the deconvoluted [`Lorentzian`]s.
+///
+/// Panics if the index is out of bounds, which is the case for any index if
+/// the signals were fitted with another peak shape. Use [`Deconvolution::get`]
+/// to access signals of any peak shape.
+impl Index<usize> for Deconvolution {
+    type Output = Lorentzian;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.lorentzians()[index]
+    }
+}
+
+impl std::fmt::Display for Deconvolution {
+    /// Formats a summary of the `Deconvolution` with the 10 most intense
+    /// signals. See [`Deconvolution::summary_string`] for details.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.summary_string(10))
+    }
+}
+
 impl Deconvolution {
     /// Constructs a new `Deconvolution`.
     ///
@@ -78,19 +149,153 @@ impl Deconvolution {
         selection_settings: SelectionSettings,
         fitting_settings: FittingSettings,
         mse: f64,
+    ) -> Self {
+        Self::from_signals(
+            FittedSignals::Lorentzian(lorentzians.into()),
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+            mse,
+        )
+    }
+
+    /// Constructs a new `Deconvolution` from signals fitted as [`Gaussian`]s.
+    ///
+    /// Normally, this type is only instantiated by the deconvolution functions
+    /// of the [`Deconvoluter`] type, when the [`Gaussian`] fitting method is
+    /// used. The [`Lorentzian`]s of the resulting `Deconvolution` are empty.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    pub fn from_gaussians(
+        gaussians: Vec<Gaussian>,
+        smoothing_settings: SmoothingSettings,
+        selection_settings: SelectionSettings,
+        fitting_settings: FittingSettings,
+        mse: f64,
+    ) -> Self {
+        Self::from_signals(
+            FittedSignals::Gaussian(gaussians.into()),
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+            mse,
+        )
+    }
+
+    /// Constructs a new `Deconvolution` from signals fitted as
+    /// [`PseudoVoigt`]s.
+    ///
+    /// Normally, this type is only instantiated by the deconvolution functions
+    /// of the [`Deconvoluter`] type, when the [`PseudoVoigt`] fitting method is
+    /// used. The [`Lorentzian`]s of the resulting `Deconvolution` are empty.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    pub fn from_pseudo_voigts(
+        pseudo_voigts: Vec<PseudoVoigt>,
+        smoothing_settings: SmoothingSettings,
+        selection_settings: SelectionSettings,
+        fitting_settings: FittingSettings,
+        mse: f64,
+    ) -> Self {
+        Self::from_signals(
+            FittedSignals::PseudoVoigt(pseudo_voigts.into()),
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+            mse,
+        )
+    }
+
+    /// Internal helper function to construct a new `Deconvolution` from the
+    /// given signals of any peak shape.
+    pub(crate) fn from_signals(
+        signals: FittedSignals,
+        smoothing_settings: SmoothingSettings,
+        selection_settings: SelectionSettings,
+        fitting_settings: FittingSettings,
+        mse: f64,
     ) -> Self {
         Self {
-            lorentzians: lorentzians.into(),
+            labels: vec![None; signals.len()],
+            signals,
             smoothing_settings,
             selection_settings,
             fitting_settings,
             mse,
+            noise_estimate: None,
+            mse_weighting: MseWeighting::Uniform,
+            fit_diagnostics: None,
+            parameter_uncertainties: None,
+            warnings: Vec::new(),
         }
     }
 
+    /// Returns the deconvoluted signals, stored as the peak shape they were
+    /// fitted with.
+    pub fn signals(&self) -> &FittedSignals {
+        &self.signals
+    }
+
+    /// Returns the deconvoluted signal at the given index, or `None` if the
+    /// index is out of bounds.
+    ///
+    /// Unlike indexing the `Deconvolution`, which lends the [`Lorentzian`]s
+    /// and panics for signals fitted with other peak shapes, this covers
+    /// signals of any peak shape. The signal is returned by value, as the
+    /// signals are stored as the type they were fitted with and [`Signal`] is
+    /// cheap to copy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Gaussian, PeakShape, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::from_gaussians(
+    ///     vec![Gaussian::new(2.0, 0.15, 3.0), Gaussian::new(1.0, 0.1, 5.0)],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::Gaussian { iterations: 10 },
+    ///     0.5,
+    /// );
+    ///
+    /// assert_approx_eq!(f64, deconvolution.get(1).unwrap().maxp(), 5.0);
+    /// assert!(deconvolution.get(2).is_none());
+    /// ```
+    pub fn get(&self, index: usize) -> Option<Signal> {
+        self.signals.get(index)
+    }
+
     /// Returns the deconvoluted signals as a slice of [`Lorentzian`].
+    ///
+    /// This is empty unless the signals were fitted as [`Lorentzian`]s.
     pub fn lorentzians(&self) -> &[Lorentzian] {
-        &self.lorentzians
+        match &self.signals {
+            FittedSignals::Lorentzian(lorentzians) => lorentzians,
+            _ => &[],
+        }
+    }
+
+    /// Returns the deconvoluted signals as a slice of [`Gaussian`].
+    ///
+    /// This is empty unless the signals were fitted as [`Gaussian`]s.
+    pub fn gaussians(&self) -> &[Gaussian] {
+        match &self.signals {
+            FittedSignals::Gaussian(gaussians) => gaussians,
+            _ => &[],
+        }
+    }
+
+    /// Returns the deconvoluted signals as a slice of [`PseudoVoigt`].
+    ///
+    /// This is empty unless the signals were fitted as [`PseudoVoigt`]s.
+    pub fn pseudo_voigts(&self) -> &[PseudoVoigt] {
+        match &self.signals {
+            FittedSignals::PseudoVoigt(pseudo_voigts) => pseudo_voigts,
+            _ => &[],
+        }
     }
 
     /// Returns the smoothing settings used.
@@ -112,73 +317,2936 @@ impl Deconvolution {
     pub fn mse(&self) -> f64 {
         self.mse
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::deconvolution::ScoringMethod;
-    use crate::{assert_send, assert_sync};
-    use float_cmp::assert_approx_eq;
+    /// Returns the noise level estimated during the peak selection.
+    ///
+    /// This is the standard deviation of the scores of the peaks in the signal
+    /// free region, or in the noise region if one was set, as computed by
+    /// [`SelectionSettings::NoiseScoreFilter`], or the scaled median absolute
+    /// deviation with [`NoiseEstimator::Mad`]. Selected peaks have a score of
+    /// at least `mean + threshold * noise_estimate`, with the median in place
+    /// of the mean for the latter, so this can be used to tune the threshold.
+    /// Returns `None` if the selection method doesn't estimate the noise level
+    /// or if the `Deconvolution` was not created by the [`Deconvoluter`].
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`NoiseEstimator::Mad`]: crate::deconvolution::NoiseEstimator::Mad
+    pub fn noise_estimate(&self) -> Option<f64> {
+        self.noise_estimate
+    }
 
-    #[test]
-    fn thread_safety() {
-        assert_send!(Deconvolution);
-        assert_sync!(Deconvolution);
+    /// Internal helper function to set the estimated noise level.
+    pub(crate) fn with_noise_estimate(self, noise_estimate: Option<f64>) -> Self {
+        Self {
+            noise_estimate,
+            ..self
+        }
+    }
+
+    /// Returns the weighting of the residuals used to compute the MSE.
+    ///
+    /// This is [`MseWeighting::Uniform`] unless a different weighting was
+    /// configured for the [`Deconvoluter`].
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    pub fn mse_weighting(&self) -> MseWeighting {
+        self.mse_weighting
+    }
+
+    /// Internal helper function to set the weighting used to compute the MSE.
+    pub(crate) fn with_mse_weighting(self, mse_weighting: MseWeighting) -> Self {
+        Self {
+            mse_weighting,
+            ..self
+        }
+    }
+
+    /// Returns the diagnostics of the fit of each signal, in the same order as
+    /// the deconvoluted signals.
+    ///
+    /// The diagnostics report the number of refinement iterations, the final
+    /// residual and whether the fit converged. This can be used to identify
+    /// signals whose parameters are unreliable, for example due to strongly
+    /// overlapping peaks. Returns `None` if the fitting method doesn't report
+    /// diagnostics (currently only [`FittingSettings::Analytical`] does), or
+    /// if the `Deconvolution` was not created by the deconvolution functions
+    /// of the [`Deconvoluter`] or was modified afterwards, e.g. by
+    /// [`merge_close_peaks`].
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`merge_close_peaks`]: Deconvolution::merge_close_peaks
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    /// let deconvolution = Deconvoluter::default().deconvolute_spectrum(&spectrum)?;
+    /// let diagnostics = deconvolution.fit_diagnostics().unwrap();
+    /// assert_eq!(diagnostics.len(), deconvolution.len());
+    ///
+    /// // Signals whose fit didn't converge.
+    /// let unconverged = deconvolution
+    ///     .lorentzians()
+    ///     .iter()
+    ///     .zip(diagnostics)
+    ///     .filter(|(_, diagnostics)| !diagnostics.converged())
+    ///     .count();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fit_diagnostics(&self) -> Option<&[FitDiagnostics]> {
+        self.fit_diagnostics.as_deref()
+    }
+
+    /// Internal helper function to set the diagnostics of the fit.
+    pub(crate) fn with_fit_diagnostics(self, fit_diagnostics: Option<Vec<FitDiagnostics>>) -> Self {
+        Self {
+            fit_diagnostics: fit_diagnostics.map(Into::into),
+            ..self
+        }
+    }
+
+    /// Returns the standard errors of the parameters of the deconvoluted
+    /// signals, in the same order as the signals.
+    ///
+    /// The standard errors are estimated from the covariance matrix of the
+    /// least squares fit, see [`ParameterUncertainty`]. They can be used to
+    /// judge how well the parameters are determined by the data, e.g. signals
+    /// within strongly overlapping peaks have larger standard errors than
+    /// well-resolved ones. Returns `None` if the fitting method doesn't
+    /// estimate uncertainties (currently only
+    /// [`FittingSettings::LevenbergMarquardt`] does), or if the
+    /// `Deconvolution` was not created by the deconvolution functions of the
+    /// [`Deconvoluter`] or was modified afterwards, e.g. by
+    /// [`merge_close_peaks`].
+    ///
+    /// The [`FittingSettings::Analytical`] method solves a system of equations
+    /// at 3 data points of each peak instead of minimizing the residuals, so
+    /// the covariance matrix of its parameters is not available. Refining the
+    /// deconvolution with [`FittingSettings::LevenbergMarquardt`] yields
+    /// standard errors for the refined parameters.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`merge_close_peaks`]: Deconvolution::merge_close_peaks
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, FittingSettings};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    /// let mut deconvoluter = Deconvoluter::default();
+    /// deconvoluter.set_fitting_settings(FittingSettings::LevenbergMarquardt {
+    ///     max_iterations: 20,
+    ///     tolerance: 1e-8,
+    ///     lambda_init: 1e-3,
+    /// })?;
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// let uncertainties = deconvolution.parameter_uncertainties().unwrap();
+    /// assert_eq!(uncertainties.len(), deconvolution.len());
+    ///
+    /// for (lorentzian, uncertainty) in deconvolution.lorentzians().iter().zip(uncertainties) {
+    ///     println!("{:.4} ± {:.4} ppm", lorentzian.maxp(), uncertainty.maxp());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parameter_uncertainties(&self) -> Option<&[ParameterUncertainty]> {
+        self.parameter_uncertainties.as_deref()
+    }
+
+    /// Internal helper function to set the standard errors of the parameters.
+    pub(crate) fn with_parameter_uncertainties(
+        self,
+        parameter_uncertainties: Option<Vec<ParameterUncertainty>>,
+    ) -> Self {
+        Self {
+            parameter_uncertainties: parameter_uncertainties.map(Into::into),
+            ..self
+        }
     }
 
+    /// Returns the quality issues found during the deconvolution.
+    ///
+    /// The deconvolution functions of the [`Deconvoluter`] check the peak
+    /// count, the MSE and the [`FitDiagnostics`] of the fitted signals, and
+    /// report a [`DeconvolutionWarning`] for each issue found. Unlike errors,
+    /// warnings don't prevent the `Deconvolution` from being used, but its
+    /// signals should be inspected before relying on them. Returns an empty
+    /// slice if no issues were found, or if the `Deconvolution` was not created
+    /// by the deconvolution functions of the [`Deconvoluter`] or was modified
+    /// afterwards, e.g. by [`merge_close_peaks`]. Deconvolutions restricted to
+    /// a region with [`deconvolute_region`] are not checked, as their signals
+    /// only cover part of the signal region.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`deconvolute_region`]: crate::deconvolution::Deconvoluter::deconvolute_region
+    /// [`merge_close_peaks`]: Deconvolution::merge_close_peaks
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{Deconvoluter, DeconvolutionWarning};
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+    /// let deconvolution = Deconvoluter::default().deconvolute_spectrum(&spectrum)?;
+    ///
+    /// for warning in deconvolution.warnings() {
+    ///     match warning {
+    ///         DeconvolutionWarning::HighPeakCount { .. } => println!("over-fitted: {warning}"),
+    ///         _ => println!("{warning}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn warnings(&self) -> &[DeconvolutionWarning] {
+        &self.warnings
+    }
+
+    /// Internal helper function to set the quality issues found during the
+    /// deconvolution.
+    pub(crate) fn with_warnings(self, warnings: Vec<DeconvolutionWarning>) -> Self {
+        Self { warnings, ..self }
+    }
+
+    /// Returns the labels of the deconvoluted signals, in the same order as the
+    /// signals. Unlabeled signals are `None`.
+    pub fn labels(&self) -> &[Option<String>] {
+        &self.labels
+    }
+
+    /// Labels the deconvoluted signal at the given index, e.g. with the name of
+    /// the metabolite it was identified as. An existing label is replaced.
+    ///
+    /// Labels are included in the serialized form and the CSV export, such
+    /// that identification results are kept together with the quantitative
+    /// data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds, i.e. not smaller than the number
+    /// of deconvoluted signals, regardless of their peak shape.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let mut deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 1.33),
+    ///         Lorentzian::new(0.045, 0.0225, 3.03),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// deconvolution.label_peak(1, "creatine");
+    ///
+    /// assert_eq!(deconvolution.labels(), [None, Some("creatine".to_string())]);
+    /// ```
+    pub fn label_peak(&mut self, index: usize, name: impl Into<String>) {
+        self.labels[index] = Some(name.into());
+    }
+
+    /// Labels the deconvoluted signal whose maximum is nearest to the given
+    /// position (in ppm), if it is within the given tolerance.
+    ///
+    /// Returns the index of the labeled signal, or `None` if no maximum is
+    /// within the tolerance. See [`label_peak`] for details.
+    ///
+    /// [`label_peak`]: Deconvolution::label_peak
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let mut deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 1.33),
+    ///         Lorentzian::new(0.045, 0.0225, 3.03),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    ///
+    /// assert_eq!(deconvolution.label_by_position(1.32, 0.02, "lactate"), Some(0));
+    /// assert_eq!(deconvolution.label_by_position(2.0, 0.02, "unknown"), None);
+    /// assert_eq!(deconvolution.labels()[0].as_deref(), Some("lactate"));
+    /// ```
+    pub fn label_by_position(
+        &mut self,
+        ppm: f64,
+        tolerance: f64,
+        name: impl Into<String>,
+    ) -> Option<usize> {
+        let (index, distance) = self
+            .signals
+            .iter()
+            .map(|signal| (signal.maxp() - ppm).abs())
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if distance > tolerance {
+            return None;
+        }
+        self.label_peak(index, name);
+
+        Some(index)
+    }
+
+    /// Internal helper function to set the labels of the deconvoluted signals.
+    /// Missing labels are set to `None` and excess labels are discarded.
     #[cfg(feature = "serde")]
-    #[test]
-    fn serialization_round_trip() {
-        let lorentzians = vec![
-            Lorentzian::new(5.5, 0.25, 3.0),
-            Lorentzian::new(7.0, 0.16, 5.0),
-            Lorentzian::new(5.5, 0.25, 7.0),
-        ];
-        let deconvolution = Deconvolution::new(
-            lorentzians.clone(),
-            SmoothingSettings::default(),
-            SelectionSettings::default(),
-            FittingSettings::default(),
-            0.5,
-        );
-        let serialized = serde_json::to_string(&deconvolution).unwrap();
-        let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
-        deconvolution
-            .lorentzians
+    pub(crate) fn with_labels(self, mut labels: Vec<Option<String>>) -> Self {
+        labels.resize(self.signals.len(), None);
+
+        Self { labels, ..self }
+    }
+
+    /// Checks that all deconvoluted signals represent valid peak shapes.
+    ///
+    /// The transformed parameter `sfhw` of each [`Lorentzian`] must be finite
+    /// and non-negative, `hw2` must be finite and positive, and `maxp` must be
+    /// finite. Signals with a scale factor of zero are allowed, as they are
+    /// used as placeholders for missing signals. The same applies to the
+    /// parameters of [`Gaussian`]s and [`PseudoVoigt`]s, whose mixing
+    /// parameter must additionally be within `[0, 1]`. Signals returned by the
+    /// [`Deconvoluter`] are always valid, so this is mainly useful for manually
+    /// constructed instances. See the [Negative Transformed Parameters] section
+    /// of [`Lorentzian`].
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [Negative Transformed Parameters]: Lorentzian#negative-transformed-parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the index of the first invalid signal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 3.0),
+    ///         Lorentzian::new(0.045, -0.0225, 5.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    ///
+    /// assert!(deconvolution.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let invalid = self
+            .signals
             .iter()
-            .zip(deserialized.lorentzians())
-            .for_each(|(init, rec)| {
-                assert_approx_eq!(f64, init.sfhw(), rec.sfhw());
-                assert_approx_eq!(f64, init.hw2(), rec.hw2());
-                assert_approx_eq!(f64, init.maxp(), rec.maxp());
-            });
-        match deserialized.smoothing_settings() {
-            SmoothingSettings::Identity => panic!("expected moving average"),
-            SmoothingSettings::MovingAverage {
-                iterations,
-                window_size,
-            } => {
-                assert_eq!(iterations, 3);
-                assert_eq!(window_size, 3);
-            }
-        };
-        match deserialized.selection_settings() {
-            SelectionSettings::DetectorOnly => panic!("expected noise score filter"),
-            SelectionSettings::NoiseScoreFilter {
-                scoring_method,
-                threshold,
-            } => {
-                match scoring_method {
-                    ScoringMethod::MinimumSum => {}
-                }
-                assert_approx_eq!(f64, threshold, 5.0);
-            }
-        };
-        match deserialized.fitting_settings() {
-            FittingSettings::Analytical { iterations } => {
-                assert_eq!(iterations, 10);
-            }
-        };
+            .position(|signal| !signal.is_valid());
+
+        match invalid {
+            Some(index) => Err(Error::new(Kind::InvalidSignal { index }).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the number of deconvoluted signals, regardless of their peak
+    /// shape.
+    ///
+    /// Together with [`get`], this allows the `Deconvolution` to be used like a
+    /// collection of [`Signal`]s. The [`IntoIterator`] and [`Index`]
+    /// implementations only cover [`Lorentzian`]s, since they lend the signals
+    /// as the type they were fitted with.
+    ///
+    /// [`get`]: Deconvolution::get
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, PeakShape, SelectionSettings,
+    ///     SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.5, 0.25, 3.0),
+    ///         Lorentzian::new(0.6, 0.16, 5.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    ///
+    /// assert_eq!(deconvolution.len(), 2);
+    /// assert_approx_eq!(f64, deconvolution.get(1).unwrap().maxp(), 5.0);
+    /// assert_approx_eq!(f64, deconvolution[1].maxp(), 5.0);
+    /// for lorentzian in &deconvolution {
+    ///     assert!(lorentzian.hw() > 0.0);
+    /// }
+    /// ```
+    #[doc(alias = "peak_count")]
+    pub fn len(&self) -> usize {
+        self.signals.len()
+    }
+
+    /// Returns `true` if there are no deconvoluted signals, regardless of
+    /// their peak shape.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     Vec::new(),
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.0,
+    /// );
+    ///
+    /// assert!(deconvolution.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.signals.is_empty()
+    }
+
+    /// Evaluates the superposition of the deconvoluted signals at the given
+    /// chemical shifts, which reconstructs the model spectrum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let triplet = [
+    ///     Lorentzian::new(0.0003, 0.0001, 4.9),
+    ///     Lorentzian::new(0.0006, 0.0001, 5.0),
+    ///     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// ];
+    /// let chemical_shifts = (0..1001)
+    ///     .map(|i| 4.5 + i as f64 * 0.001)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// let deconvoluter = Deconvoluter::new(
+    ///     SmoothingSettings::Identity,
+    ///     SelectionSettings::DetectorOnly,
+    ///     FittingSettings::Analytical { iterations: 10 },
+    /// )?;
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// let centers = [4.9, 5.0, 5.1];
+    /// let reconstructed = deconvolution.superposition_vec(&centers);
+    ///
+    /// centers
+    ///     .iter()
+    ///     .zip(reconstructed.iter())
+    ///     .for_each(|(x, y)| {
+    ///         let expected = Lorentzian::superposition(*x, &triplet);
+    ///         assert_approx_eq!(f64, *y, expected, epsilon = 1e-3 * expected);
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn superposition_vec(&self, chemical_shifts: &[f64]) -> Vec<f64> {
+        self.signals.superposition_vec(chemical_shifts)
+    }
+
+    /// Evaluates the superposition of the deconvoluted signals at the given
+    /// chemical shifts in parallel, which reconstructs the model spectrum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    /// let centers = [4.9, 5.0, 5.1];
+    /// let reconstructed = deconvolution.par_superposition_vec(&centers);
+    ///
+    /// centers
+    ///     .iter()
+    ///     .zip(reconstructed.iter())
+    ///     .for_each(|(x, y)| {
+    ///         let expected = Lorentzian::superposition(*x, &triplet);
+    ///         assert_approx_eq!(f64, *y, expected, epsilon = 1e-3 * expected);
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_superposition_vec(&self, chemical_shifts: &[f64]) -> Vec<f64> {
+        self.signals
+            .par_superposition_vec(chemical_shifts)
+    }
+
+    /// Evaluates each deconvoluted signal separately at the given chemical
+    /// shifts, which is useful to plot the individual signals.
+    ///
+    /// Returns one vector of intensities per signal, in the same order as the
+    /// signals. The element-wise sum of these vectors equals the superposition
+    /// of the signals. Note that the result holds one value per signal and
+    /// chemical shift, so it can take up a lot of memory for deconvolutions
+    /// with many signals evaluated on fine grids. Consider restricting the
+    /// chemical shifts to the region of interest in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    /// let components = deconvolution.component_curves(spectrum.chemical_shifts());
+    /// let superposition = deconvolution.superposition_vec(spectrum.chemical_shifts());
+    ///
+    /// assert_eq!(components.len(), deconvolution.len());
+    /// superposition
+    ///     .iter()
+    ///     .enumerate()
+    ///     .for_each(|(i, y)| {
+    ///         let sum = components
+    ///             .iter()
+    ///             .map(|component| component[i])
+    ///             .sum::<f64>();
+    ///         assert_approx_eq!(f64, sum, *y, epsilon = 1e-12 * y.abs());
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn component_curves(&self, chemical_shifts: &[f64]) -> Vec<Vec<f64>> {
+        self.signals.component_curves(chemical_shifts)
+    }
+
+    /// Evaluates each deconvoluted signal separately at the given chemical
+    /// shifts in parallel.
+    ///
+    /// See [`Deconvolution::component_curves`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    /// let components = deconvolution.par_component_curves(spectrum.chemical_shifts());
+    /// let superposition = deconvolution.par_superposition_vec(spectrum.chemical_shifts());
+    ///
+    /// assert_eq!(components.len(), deconvolution.len());
+    /// superposition
+    ///     .iter()
+    ///     .enumerate()
+    ///     .for_each(|(i, y)| {
+    ///         let sum = components
+    ///             .iter()
+    ///             .map(|component| component[i])
+    ///             .sum::<f64>();
+    ///         assert_approx_eq!(f64, sum, *y, epsilon = 1e-12 * y.abs());
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_component_curves(&self, chemical_shifts: &[f64]) -> Vec<Vec<f64>> {
+        self.signals.par_component_curves(chemical_shifts)
+    }
+
+    /// Computes the residuals between the intensities of the given
+    /// [`Spectrum`] and the superposition of the deconvoluted signals within
+    /// the signal region.
+    ///
+    /// The residuals are computed as the intensity minus the superposition at
+    /// each data point between the signal region boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    /// let residuals = deconvolution.residuals(&spectrum);
+    ///
+    /// assert_eq!(residuals.len(), spectrum.signal_region_len());
+    /// residuals
+    ///     .iter()
+    ///     .zip(spectrum.signal_region_intensities().iter())
+    ///     .for_each(|(residual, intensity)| {
+    ///         assert!(residual.abs() < 1e-3 * intensity);
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn residuals(&self, spectrum: &Spectrum) -> Vec<f64> {
+        let superpositions = self.superposition_vec(spectrum.signal_region_shifts());
+
+        spectrum
+            .signal_region_intensities()
+            .iter()
+            .zip(superpositions.iter())
+            .map(|(intensity, superposition)| intensity - superposition)
+            .collect()
+    }
+
+    /// Computes the coefficient of determination (R²) of the deconvolution
+    /// within the signal region of the given [`Spectrum`].
+    ///
+    /// Unlike the MSE, R² is independent of the intensity scale of the
+    /// spectrum. A value of 1 indicates a perfect fit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    ///
+    /// assert_approx_eq!(f64, deconvolution.r_squared(&spectrum), 1.0, epsilon = 1e-6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn r_squared(&self, spectrum: &Spectrum) -> f64 {
+        self.r_squared_with_ignore_regions(spectrum, None)
+    }
+
+    /// Computes the coefficient of determination (R²) of the deconvolution
+    /// within the signal region of the given [`Spectrum`], excluding the given
+    /// ignore regions.
+    ///
+    /// The signal region is split in the same way as for the MSE computed by
+    /// the [`Deconvoluter`], so passing the ignore regions of the
+    /// [`Deconvoluter`] yields a value consistent with [`mse`].
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`mse`]: Deconvolution::mse
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let mut deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm.
+    /// deconvoluter.add_ignore_region((5.25, 5.35))?;
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// let r_squared = deconvolution
+    ///     .r_squared_with_ignore_regions(&spectrum, deconvoluter.ignore_regions());
+    ///
+    /// assert_approx_eq!(f64, r_squared, 1.0, epsilon = 1e-6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn r_squared_with_ignore_regions(
+        &self,
+        spectrum: &Spectrum,
+        ignore_regions: Option<&[(f64, f64)]>,
+    ) -> f64 {
+        let (intensities, superpositions) = self.evaluated_intensities(spectrum, ignore_regions);
+        let mean = intensities.iter().sum::<f64>() / intensities.len() as f64;
+        let residual_sum_of_squares = Self::residual_sum_of_squares(&intensities, &superpositions);
+        let total_sum_of_squares = intensities
+            .iter()
+            .map(|intensity| (intensity - mean).powi(2))
+            .sum::<f64>();
+
+        1.0 - residual_sum_of_squares / total_sum_of_squares
+    }
+
+    /// Computes the residual sum of squares (RSS) of the deconvolution within
+    /// the signal region of the given [`Spectrum`].
+    ///
+    /// The signal region is split in the same way as for the MSE computed by
+    /// the [`Deconvoluter`] without ignore regions. Unlike the [`mse`], the
+    /// RSS is not normalized by the number of points and always weights the
+    /// residuals uniformly.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`mse`]: Deconvolution::mse
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let lorentzian = Lorentzian::new(0.0006, 0.0001, 5.0);
+    /// let chemical_shifts = (0..1001)
+    ///     .map(|i| 4.5 + i as f64 * 0.001)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = chemical_shifts
+    ///     .iter()
+    ///     .map(|x| lorentzian.evaluate(*x) + 0.1)
+    ///     .collect::<Vec<f64>>();
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![lorentzian],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.0,
+    /// );
+    ///
+    /// // 801 points in the signal region with a residual of 0.1 each.
+    /// assert_approx_eq!(f64, deconvolution.rss(&spectrum), 8.01, epsilon = 1e-9);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rss(&self, spectrum: &Spectrum) -> f64 {
+        let (intensities, superpositions) = self.evaluated_intensities(spectrum, None);
+
+        Self::residual_sum_of_squares(&intensities, &superpositions)
+    }
+
+    /// Computes the chi-squared statistic of the deconvolution within the
+    /// signal region of the given [`Spectrum`].
+    ///
+    /// This is the [`rss`] divided by the variance of the noise, which has to
+    /// be known or estimated separately, for example from a region of the
+    /// baseline. If the noise variance is not positive, the result is infinite
+    /// or NaN.
+    ///
+    /// [`rss`]: Deconvolution::rss
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let lorentzian = Lorentzian::new(0.0006, 0.0001, 5.0);
+    /// let chemical_shifts = (0..1001)
+    ///     .map(|i| 4.5 + i as f64 * 0.001)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = chemical_shifts
+    ///     .iter()
+    ///     .map(|x| lorentzian.evaluate(*x) + 0.1)
+    ///     .collect::<Vec<f64>>();
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![lorentzian],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.0,
+    /// );
+    /// let chi_squared = deconvolution.chi_squared(&spectrum, 0.01);
+    ///
+    /// assert_approx_eq!(f64, chi_squared, 801.0, epsilon = 1e-6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn chi_squared(&self, spectrum: &Spectrum, noise_variance: f64) -> f64 {
+        self.rss(spectrum) / noise_variance
+    }
+
+    /// Computes the reduced chi-squared statistic of the deconvolution within
+    /// the signal region of the given [`Spectrum`].
+    ///
+    /// This is the [`chi_squared`] divided by the degrees of freedom, the
+    /// number of points in the signal region minus the parameters of the
+    /// signals, 3 per [`Lorentzian`] or [`Gaussian`] and 4 per
+    /// [`PseudoVoigt`].
+    /// Values close to 1 indicate that the model describes the data up to the
+    /// noise, while values much larger than 1 indicate underfitting and values
+    /// much smaller than 1 overfitting. Returns NaN if there are no degrees of
+    /// freedom left.
+    ///
+    /// [`chi_squared`]: Deconvolution::chi_squared
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let lorentzian = Lorentzian::new(0.0006, 0.0001, 5.0);
+    /// let chemical_shifts = (0..1001)
+    ///     .map(|i| 4.5 + i as f64 * 0.001)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = chemical_shifts
+    ///     .iter()
+    ///     .map(|x| lorentzian.evaluate(*x) + 0.1)
+    ///     .collect::<Vec<f64>>();
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![lorentzian],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.0,
+    /// );
+    /// let reduced_chi_squared = deconvolution.reduced_chi_squared(&spectrum, 0.01);
+    ///
+    /// // 801 points minus 3 parameters of the single signal.
+    /// assert_approx_eq!(f64, reduced_chi_squared, 801.0 / 798.0, epsilon = 1e-6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reduced_chi_squared(&self, spectrum: &Spectrum, noise_variance: f64) -> f64 {
+        let (intensities, superpositions) = self.evaluated_intensities(spectrum, None);
+        let parameters = self.signals.parameters_per_signal() * self.signals.len();
+        if intensities.len() <= parameters {
+            return f64::NAN;
+        }
+        let degrees_of_freedom = (intensities.len() - parameters) as f64;
+
+        Self::residual_sum_of_squares(&intensities, &superpositions)
+            / noise_variance
+            / degrees_of_freedom
+    }
+
+    /// Internal helper function to collect the intensities within the signal
+    /// region of the given [`Spectrum`], excluding the given ignore regions,
+    /// together with the superposition of the signals at the same points.
+    fn evaluated_intensities(
+        &self,
+        spectrum: &Spectrum,
+        ignore_regions: Option<&[(f64, f64)]>,
+    ) -> (Vec<f64>, Vec<f64>) {
+        let ignore_regions =
+            ignore_regions.map(|ignore_regions| ignore_region_indices(spectrum, ignore_regions));
+        let regions = evaluation_regions(spectrum, ignore_regions.as_deref());
+        let intensities = regions
+            .iter()
+            .flat_map(|(start, end)| {
+                spectrum.intensities()[*start..*end]
+                    .iter()
+                    .copied()
+            })
+            .collect::<Vec<_>>();
+        let chemical_shifts = regions
+            .iter()
+            .flat_map(|(start, end)| {
+                spectrum.chemical_shifts()[*start..*end]
+                    .iter()
+                    .copied()
+            })
+            .collect::<Vec<_>>();
+        let superpositions = self.superposition_vec(&chemical_shifts);
+
+        (intensities, superpositions)
+    }
+
+    /// Internal helper function to compute the sum of the squared differences
+    /// between the intensities and the superpositions.
+    fn residual_sum_of_squares(intensities: &[f64], superpositions: &[f64]) -> f64 {
+        intensities
+            .iter()
+            .zip(superpositions.iter())
+            .map(|(intensity, superposition)| (intensity - superposition).powi(2))
+            .sum()
+    }
+
+    /// Computes the area under each of the deconvoluted signals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    ///
+    /// deconvolution
+    ///     .signal_areas()
+    ///     .iter()
+    ///     .zip(triplet.iter())
+    ///     .for_each(|(area, lorentzian)| {
+    ///         assert_approx_eq!(f64, *area, lorentzian.integral(), epsilon = 1e-3);
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_areas(&self) -> Vec<f64> {
+        self.signals
+            .iter()
+            .map(|signal| signal.integral())
+            .collect()
+    }
+
+    /// Computes the total area under the deconvoluted signals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvoluter, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// # use metabodecon::spectrum::Spectrum;
+    /// #
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let triplet = [
+    /// #     Lorentzian::new(0.0003, 0.0001, 4.9),
+    /// #     Lorentzian::new(0.0006, 0.0001, 5.0),
+    /// #     Lorentzian::new(0.0003, 0.0001, 5.1),
+    /// # ];
+    /// # let chemical_shifts = (0..1001)
+    /// #     .map(|i| 4.5 + i as f64 * 0.001)
+    /// #     .collect::<Vec<f64>>();
+    /// # let intensities = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
+    /// # let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// # let deconvoluter = Deconvoluter::new(
+    /// #     SmoothingSettings::Identity,
+    /// #     SelectionSettings::DetectorOnly,
+    /// #     FittingSettings::Analytical { iterations: 10 },
+    /// # )?;
+    /// # let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    /// // A synthetic triplet at 4.9, 5.0 and 5.1 ppm, deconvoluted analytically.
+    /// let expected = triplet
+    ///     .iter()
+    ///     .map(|lorentzian| lorentzian.integral())
+    ///     .sum::<f64>();
+    ///
+    /// assert_approx_eq!(f64, deconvolution.total_area(), expected, epsilon = 1e-3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn total_area(&self) -> f64 {
+        self.signal_areas().iter().sum()
+    }
+
+    /// Computes the area under each deconvoluted signal within the interval
+    /// `[a, b]`, e.g. the detection window of a metabolite.
+    ///
+    /// The areas are in the same order as the signals. See
+    /// [`Lorentzian::integral_between`], [`Gaussian::integral_between`] and
+    /// [`PseudoVoigt::integral_between`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.0003, 0.0001, 4.9),
+    ///         Lorentzian::new(0.0006, 0.0001, 5.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// let areas = deconvolution.signal_areas_between(4.95, 5.05);
+    ///
+    /// // The window is centered on the second signal, 5 half widths wide.
+    /// assert!(areas[0] < 0.05 * deconvolution.lorentzians()[0].integral());
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     areas[1],
+    ///     2.0 * 0.06 * f64::atan(5.0),
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn signal_areas_between(&self, a: f64, b: f64) -> Vec<f64> {
+        self.signals
+            .iter()
+            .map(|signal| signal.integral_between(a, b))
+            .collect()
+    }
+
+    /// Computes the number of deconvoluted signals per ppm within the signal
+    /// region of the given [`Spectrum`].
+    ///
+    /// The signal region is where the signals are expected to be found, so its
+    /// width is used rather than the full range of the [`Spectrum`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     (0..=1000).map(|i| i as f64 * 0.01).collect(), // Chemical shifts
+    ///     vec![1.0; 1001],                               // Intensities
+    ///     (2.0, 8.0),                                    // Signal boundaries
+    /// )?;
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 3.0),
+    ///         Lorentzian::new(0.1, 0.01, 5.0),
+    ///         Lorentzian::new(0.15, 0.0625, 7.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    ///
+    /// assert_approx_eq!(f64, deconvolution.peak_density_per_ppm(&spectrum), 0.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peak_density_per_ppm(&self, spectrum: &Spectrum) -> f64 {
+        let signal_boundaries = spectrum.signal_boundaries();
+        let width = f64::abs(signal_boundaries.1 - signal_boundaries.0);
+
+        self.len() as f64 / width
+    }
+
+    /// Computes the mean full width at half maximum of the deconvoluted
+    /// signals in ppm.
+    ///
+    /// Returns NaN if there are no signals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// #
+    /// # let deconvolution = Deconvolution::new(
+    /// #     vec![
+    /// #         Lorentzian::new(0.045, 0.0225, 3.0),
+    /// #         Lorentzian::new(0.1, 0.01, 5.0),
+    /// #         Lorentzian::new(0.15, 0.0625, 7.0),
+    /// #     ],
+    /// #     SmoothingSettings::default(),
+    /// #     SelectionSettings::default(),
+    /// #     FittingSettings::default(),
+    /// #     0.5,
+    /// # );
+    /// // Signals at 3, 5 and 7 ppm with half widths of 0.15, 0.1 and 0.25 ppm.
+    /// assert_approx_eq!(f64, deconvolution.mean_fwhm(), 1.0 / 3.0);
+    /// ```
+    pub fn mean_fwhm(&self) -> f64 {
+        self.signals
+            .iter()
+            .map(|signal| signal.fwhm())
+            .sum::<f64>()
+            / self.len() as f64
+    }
+
+    /// Returns the deconvoluted signal with the smallest full width at half
+    /// maximum, or `None` if there are no signals.
+    ///
+    /// Like [`get`] and [`sorted_by_position`], this covers signals of any
+    /// peak shape and returns the signal by value as a [`Signal`].
+    ///
+    /// [`get`]: Deconvolution::get
+    /// [`sorted_by_position`]: Deconvolution::sorted_by_position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvolution, FittingSettings, Lorentzian, PeakShape, SelectionSettings,
+    /// #     SmoothingSettings,
+    /// # };
+    /// #
+    /// # let deconvolution = Deconvolution::new(
+    /// #     vec![
+    /// #         Lorentzian::new(0.045, 0.0225, 3.0),
+    /// #         Lorentzian::new(0.1, 0.01, 5.0),
+    /// #         Lorentzian::new(0.15, 0.0625, 7.0),
+    /// #     ],
+    /// #     SmoothingSettings::default(),
+    /// #     SelectionSettings::default(),
+    /// #     FittingSettings::default(),
+    /// #     0.5,
+    /// # );
+    /// // Signals at 3, 5 and 7 ppm with half widths of 0.15, 0.1 and 0.25 ppm.
+    /// let narrowest = deconvolution.narrowest_peak().unwrap();
+    ///
+    /// assert_approx_eq!(f64, narrowest.maxp(), 5.0);
+    /// ```
+    pub fn narrowest_peak(&self) -> Option<Signal> {
+        self.signals
+            .iter()
+            .min_by(|a, b| a.hw().total_cmp(&b.hw()))
+    }
+
+    /// Returns the deconvoluted signal with the largest full width at half
+    /// maximum, or `None` if there are no signals.
+    ///
+    /// Like [`get`] and [`sorted_by_position`], this covers signals of any
+    /// peak shape and returns the signal by value as a [`Signal`].
+    ///
+    /// [`get`]: Deconvolution::get
+    /// [`sorted_by_position`]: Deconvolution::sorted_by_position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use float_cmp::assert_approx_eq;
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvolution, FittingSettings, Lorentzian, PeakShape, SelectionSettings,
+    /// #     SmoothingSettings,
+    /// # };
+    /// #
+    /// # let deconvolution = Deconvolution::new(
+    /// #     vec![
+    /// #         Lorentzian::new(0.045, 0.0225, 3.0),
+    /// #         Lorentzian::new(0.1, 0.01, 5.0),
+    /// #         Lorentzian::new(0.15, 0.0625, 7.0),
+    /// #     ],
+    /// #     SmoothingSettings::default(),
+    /// #     SelectionSettings::default(),
+    /// #     FittingSettings::default(),
+    /// #     0.5,
+    /// # );
+    /// // Signals at 3, 5 and 7 ppm with half widths of 0.15, 0.1 and 0.25 ppm.
+    /// let widest = deconvolution.widest_peak().unwrap();
+    ///
+    /// assert_approx_eq!(f64, widest.maxp(), 7.0);
+    /// ```
+    pub fn widest_peak(&self) -> Option<Signal> {
+        self.signals
+            .iter()
+            .max_by(|a, b| a.hw().total_cmp(&b.hw()))
+    }
+
+    /// Returns the deconvoluted signals sorted by the position of their
+    /// maximum in ascending order.
+    ///
+    /// The signals are stored in the order they were fitted, which generally
+    /// follows the order of the detected peaks, but is not guaranteed to be
+    /// monotonic in `maxp`. Sorting by position makes it easier to compare the
+    /// results of different runs or to align peaks across spectra. The sort is
+    /// stable, so signals with the same position keep their relative order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, PeakShape, SelectionSettings,
+    ///     SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 5.0),
+    ///         Lorentzian::new(0.1, 0.01, 3.0),
+    ///         Lorentzian::new(0.15, 0.0625, 7.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// let positions = deconvolution
+    ///     .sorted_by_position()
+    ///     .iter()
+    ///     .map(|signal| signal.maxp())
+    ///     .collect::<Vec<f64>>();
+    ///
+    /// assert_eq!(positions, [3.0, 5.0, 7.0]);
+    /// ```
+    pub fn sorted_by_position(&self) -> Vec<Signal> {
+        let mut signals = self.signals.iter().collect::<Vec<Signal>>();
+        signals.sort_by(|a, b| a.maxp().total_cmp(&b.maxp()));
+
+        signals
+    }
+
+    /// Computes the [overlap] of each pair of adjacent deconvoluted
+    /// [`Lorentzian`]s, sorted by their position.
+    ///
+    /// The result has one element less than there are [`Lorentzian`]s. Values
+    /// close to 1 indicate that a single signal may have been split into two
+    /// by the fitting. The overlap is only defined for [`Lorentzian`]s, so
+    /// `None` is returned if the signals were fitted with another peak shape.
+    ///
+    /// [overlap]: Lorentzian::overlap
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 5.0),
+    ///         Lorentzian::new(0.045, 0.0225, 3.0),
+    ///         Lorentzian::new(0.045, 0.0225, 5.01),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// let overlaps = deconvolution.adjacent_overlaps().unwrap();
+    ///
+    /// assert_eq!(overlaps.len(), 2);
+    /// assert!(overlaps[0] < 0.2);
+    /// assert!(overlaps[1] > 0.9);
+    /// ```
+    pub fn adjacent_overlaps(&self) -> Option<Vec<f64>> {
+        let mut lorentzians = match &self.signals {
+            FittedSignals::Lorentzian(lorentzians) => lorentzians.to_vec(),
+            _ => return None,
+        };
+        lorentzians.sort_by(|a, b| a.maxp().total_cmp(&b.maxp()));
+
+        Some(
+            lorentzians
+                .windows(2)
+                .map(|w| w[0].overlap(&w[1]))
+                .collect(),
+        )
+    }
+
+    /// Merges deconvoluted signals that are closer to each other than the
+    /// given separation into single signals of the same peak shape.
+    ///
+    /// The signals are sorted by their position and each one that is closer
+    /// than `min_separation` (in ppm) to its predecessor is merged into the
+    /// same group, such that chains of close peaks form one group. Each group
+    /// is replaced by a single signal, whose position and half-width (and
+    /// mixing parameter of [`PseudoVoigt`]s) are the averages weighted by the
+    /// maximum intensities, and whose scale factor is chosen such that the
+    /// total area of the group is preserved. For [`Lorentzian`]s, this is the
+    /// sum of the scale factors of the group.
+    ///
+    /// The MSE of the original deconvolution is kept, use
+    /// [`merge_close_peaks_with_mse`] to recompute it. The distinct labels of
+    /// the group are joined with commas.
+    ///
+    /// [`merge_close_peaks_with_mse`]: Deconvolution::merge_close_peaks_with_mse
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.045, 0.0225, 5.0),
+    ///         Lorentzian::new(0.045, 0.0225, 5.001),
+    ///         Lorentzian::new(0.1, 0.01, 3.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// let merged = deconvolution.merge_close_peaks(0.01);
+    ///
+    /// assert_eq!(merged.len(), 2);
+    /// assert_approx_eq!(f64, merged.total_area(), deconvolution.total_area());
+    /// ```
+    pub fn merge_close_peaks(&self, min_separation: f64) -> Deconvolution {
+        let mut signals = self
+            .signals
+            .iter()
+            .zip(self.labels.iter().cloned())
+            .collect::<Vec<(Signal, Option<String>)>>();
+        signals.sort_by(|a, b| a.0.maxp().total_cmp(&b.0.maxp()));
+        let mut groups: Vec<Vec<Signal>> = Vec::new();
+        let mut group_labels: Vec<Vec<String>> = Vec::new();
+        for (signal, label) in signals {
+            match groups.last_mut() {
+                Some(group) if signal.maxp() - group.last().unwrap().maxp() < min_separation => {
+                    group.push(signal)
+                }
+                _ => {
+                    groups.push(vec![signal]);
+                    group_labels.push(Vec::new());
+                }
+            }
+            let labels = group_labels.last_mut().unwrap();
+            if let Some(label) = label.filter(|label| !labels.contains(label)) {
+                labels.push(label);
+            }
+        }
+        let signals = self.signals.with_signals(
+            groups
+                .iter()
+                .map(|group| Self::merged_signal(group)),
+        );
+        let labels = group_labels
+            .into_iter()
+            .map(|labels| (!labels.is_empty()).then(|| labels.join(", ")))
+            .collect();
+
+        Self {
+            signals,
+            fit_diagnostics: None,
+            parameter_uncertainties: None,
+            labels,
+            warnings: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Internal helper function to merge a group of signals of the same peak
+    /// shape into a single signal with the same total area, whose parameters
+    /// are the averages weighted by the maximum intensities.
+    fn merged_signal(group: &[Signal]) -> Signal {
+        if group.len() == 1 {
+            return group[0];
+        }
+        let weights = group
+            .iter()
+            .map(|signal| signal.max_intensity())
+            .collect::<Vec<f64>>();
+        let total_weight = weights.iter().sum::<f64>();
+        let weighted_average = |value: &dyn Fn(&Signal) -> f64| {
+            group
+                .iter()
+                .zip(weights.iter())
+                .map(|(signal, weight)| weight * value(signal))
+                .sum::<f64>()
+                / total_weight
+        };
+        let area = group
+            .iter()
+            .map(|signal| signal.integral())
+            .sum::<f64>();
+        let hw = weighted_average(&|signal| signal.hw());
+        let maxp = weighted_average(&|signal| signal.maxp());
+
+        match group[0] {
+            Signal::Lorentzian(_) => {
+                Lorentzian::new(area / std::f64::consts::PI * hw, hw.powi(2), maxp).into()
+            }
+            Signal::Gaussian(_) => {
+                let sf = area / Gaussian::new(1.0, hw, maxp).integral();
+                Gaussian::new(sf, hw, maxp).into()
+            }
+            Signal::PseudoVoigt(_) => {
+                let eta = weighted_average(&|signal| match signal {
+                    Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.eta(),
+                    _ => 0.0,
+                });
+                let sf = area / PseudoVoigt::new(eta, hw, maxp, 1.0).integral();
+                PseudoVoigt::new(eta, hw, maxp, sf).into()
+            }
+        }
+    }
+
+    /// Merges deconvoluted signals that are closer to each other than the
+    /// given separation into single signals and recomputes the MSE with
+    /// respect to the given [`Spectrum`].
+    ///
+    /// See [`merge_close_peaks`] for how the signals are merged. The
+    /// MSE is computed within the signal region of the [`Spectrum`], in the
+    /// same way as by the [`Deconvoluter`] without ignore regions, using the
+    /// same [`MseWeighting`] as the original `Deconvolution`.
+    ///
+    /// [`merge_close_peaks`]: Deconvolution::merge_close_peaks
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let signal = Lorentzian::new(0.0006, 0.0001, 5.0);
+    /// let chemical_shifts = (0..1001)
+    ///     .map(|i| 4.5 + i as f64 * 0.001)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = signal.evaluate_vec(&chemical_shifts);
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4))?;
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.0003, 0.0001, 4.9995),
+    ///         Lorentzian::new(0.0003, 0.0001, 5.0005),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.0,
+    /// );
+    /// let merged = deconvolution.merge_close_peaks_with_mse(&spectrum, 0.01);
+    ///
+    /// assert_eq!(merged.len(), 1);
+    /// assert!(merged.mse() < 1e-6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge_close_peaks_with_mse(
+        &self,
+        spectrum: &Spectrum,
+        min_separation: f64,
+    ) -> Deconvolution {
+        let merged = self.merge_close_peaks(min_separation);
+        let regions = evaluation_regions(spectrum, None);
+        let superpositions = merged.superposition_vec(spectrum.chemical_shifts());
+
+        Self {
+            mse: self
+                .mse_weighting
+                .mse(&superpositions, spectrum.intensities(), &regions),
+            ..merged
+        }
+    }
+
+    /// Formats a human-readable summary of the `Deconvolution`.
+    ///
+    /// The summary contains the number of deconvoluted signals, the mean
+    /// squared error and a table of the `top_n` most intense signals,
+    /// sorted by decreasing intensity, with their position, intensity, full
+    /// width at half maximum and area. The table is omitted if `top_n` is 0 or
+    /// there are no signals. The [`Display`] implementation uses a `top_n` of
+    /// 10.
+    ///
+    /// [`Display`]: std::fmt::Display
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use metabodecon::deconvolution::{
+    /// #     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// # };
+    /// #
+    /// # let deconvolution = Deconvolution::new(
+    /// #     vec![
+    /// #         Lorentzian::new(0.045, 0.0225, 3.0),
+    /// #         Lorentzian::new(0.1, 0.01, 5.0),
+    /// #         Lorentzian::new(0.15, 0.0625, 7.0),
+    /// #     ],
+    /// #     SmoothingSettings::default(),
+    /// #     SelectionSettings::default(),
+    /// #     FittingSettings::default(),
+    /// #     0.5,
+    /// # );
+    /// // Signals at 3, 5 and 7 ppm, the one at 5 ppm being the most intense,
+    /// // with an MSE of 0.5.
+    /// let summary = deconvolution.summary_string(1);
+    /// let mut lines = summary.lines();
+    ///
+    /// assert_eq!(lines.next(), Some("Deconvolution with 3 peaks (MSE: 5.000000e-1)"));
+    /// assert_eq!(lines.next(), Some("Top 1 peaks by intensity:"));
+    /// assert!(lines.nth(1).unwrap().trim_start().starts_with("5.000000"));
+    /// ```
+    pub fn summary_string(&self, top_n: usize) -> String {
+        let mut summary = format!(
+            "Deconvolution with {} peaks (MSE: {:.6e})",
+            self.signals.len(),
+            self.mse
+        );
+        let top_n = usize::min(top_n, self.signals.len());
+        if top_n == 0 {
+            return summary;
+        }
+        let mut peaks = self.signals.iter().collect::<Vec<Signal>>();
+        peaks.sort_unstable_by(|a, b| b.max_intensity().total_cmp(&a.max_intensity()));
+        summary.push_str(&format!("\nTop {} peaks by intensity:", top_n));
+        summary.push_str(&format!(
+            "\n{:>16} {:>14} {:>14} {:>14}",
+            "position (ppm)", "intensity", "fwhm (ppm)", "area"
+        ));
+        peaks.into_iter().take(top_n).for_each(|peak| {
+            summary.push_str(&format!(
+                "\n{:>16.6} {:>14.6e} {:>14.6} {:>14.6e}",
+                peak.maxp(),
+                peak.max_intensity(),
+                peak.fwhm(),
+                peak.integral()
+            ));
+        });
+
+        summary
+    }
+
+    /// Formats the deconvoluted signals as a comma separated table.
+    ///
+    /// The table has a header row and one row per signal with the columns
+    /// `maxp`, `hw`, `sf`, and `integral`, followed by the columns specific to
+    /// the peak shape: `sfhw` and `hw2` for [`Lorentzian`]s, and `eta` for
+    /// [`PseudoVoigt`]s. [`Gaussian`]s have no additional columns. If any
+    /// signal is labeled, a `label` column is appended, which is empty for
+    /// unlabeled signals. Labels containing commas, quotes or line breaks are
+    /// quoted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    ///
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.5, 0.25, 3.0),
+    ///         Lorentzian::new(0.6, 0.16, 5.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// let csv = deconvolution.to_csv_string();
+    /// let mut lines = csv.lines();
+    ///
+    /// assert_eq!(lines.next(), Some("maxp,hw,sf,integral,sfhw,hw2"));
+    /// assert_eq!(lines.next(), Some("3,0.5,1,3.141592653589793,0.5,0.25"));
+    /// assert_eq!(lines.count(), 1);
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn to_csv_string(&self) -> String {
+        let labeled = self.labels.iter().any(Option::is_some);
+        let header = match &self.signals {
+            FittedSignals::Lorentzian(_) => "maxp,hw,sf,integral,sfhw,hw2",
+            FittedSignals::Gaussian(_) => "maxp,hw,sf,integral",
+            FittedSignals::PseudoVoigt(_) => "maxp,hw,sf,integral,eta",
+        };
+        let header = match labeled {
+            true => header.to_string() + ",label",
+            false => header.to_string(),
+        };
+        std::iter::once(header)
+            .chain(
+                self.signals
+                    .iter()
+                    .zip(self.labels.iter())
+                    .map(|(signal, label)| {
+                        let row = Self::csv_row(signal);
+                        match labeled {
+                            true => row + "," + &Self::csv_field(label.as_deref().unwrap_or("")),
+                            false => row,
+                        }
+                    }),
+            )
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Internal helper function to format the parameters of a signal as a row
+    /// of the CSV table.
+    #[cfg(feature = "csv")]
+    fn csv_row(signal: Signal) -> String {
+        match signal {
+            Signal::Lorentzian(lorentzian) => {
+                let lorentzian = lorentzian.precompute();
+                format!(
+                    "{},{},{},{},{},{}",
+                    lorentzian.maxp(),
+                    lorentzian.hw(),
+                    lorentzian.sf(),
+                    lorentzian.integral(),
+                    lorentzian.sfhw(),
+                    lorentzian.hw2()
+                )
+            }
+            Signal::Gaussian(gaussian) => format!(
+                "{},{},{},{}",
+                gaussian.maxp(),
+                gaussian.hw(),
+                gaussian.sf(),
+                gaussian.integral()
+            ),
+            Signal::PseudoVoigt(pseudo_voigt) => format!(
+                "{},{},{},{},{}",
+                pseudo_voigt.maxp(),
+                pseudo_voigt.hw(),
+                pseudo_voigt.sf(),
+                pseudo_voigt.integral(),
+                pseudo_voigt.eta()
+            ),
+        }
+    }
+
+    /// Internal helper function to quote a field of the CSV table if it
+    /// contains commas, quotes or line breaks.
+    #[cfg(feature = "csv")]
+    fn csv_field(field: &str) -> String {
+        match field.contains([',', '"', '\n', '\r']) {
+            true => format!("\"{}\"", field.replace('"', "\"\"")),
+            false => field.to_string(),
+        }
+    }
+
+    /// Writes the deconvoluted signals as a comma separated table to the file
+    /// at the given path.
+    ///
+    /// See [`to_csv_string`] for the format of the table. An existing file is
+    /// overwritten.
+    ///
+    /// [`to_csv_string`]: Deconvolution::to_csv_string
+    ///
+    /// # Errors
+    ///
+    /// If any [`I/O`] errors occur, an error variant containing the original
+    /// error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.35, 3.55))?;
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    ///
+    /// let path = "path/to/peaks.csv";
+    /// # let path = std::env::temp_dir().join("metabodecon_write_csv_doc.csv");
+    /// deconvolution.write_csv(path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_csv_string())?;
+
+        Ok(())
+    }
+
+    /// Formats the deconvoluted signals as a peak list in the text layout of
+    /// Bruker TopSpin (`peak.txt`).
+    ///
+    /// The peak list has two header lines and one line per signal, sorted by
+    /// decreasing chemical shift, with the columns:
+    ///
+    /// - `#`: 1-based index of the peak
+    /// - `ADDRESS`: position in data points, counted from the high ppm edge of
+    ///   the [`Spectrum`] starting at 1
+    /// - `FREQUENCY [Hz]`: position in Hz, relative to 0 ppm
+    /// - `FREQUENCY [PPM]`: position in ppm
+    /// - `INTENSITY`: height of the fitted signal
+    ///
+    /// The [`Spectrum`] the deconvolution was computed from provides the
+    /// spectrometer frequency and the axis for the addresses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spectrometer frequency of the [`Spectrum`] is
+    /// not finite and positive, or if it is still set to the default value of
+    /// 1 MHz.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     Deconvolution, FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings,
+    /// };
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![10.0, 7.5, 5.0, 2.5, 0.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0],  // Intensities
+    ///     (7.5, 2.5),                     // Signal boundaries
+    /// )?;
+    /// spectrum.set_frequency(600.0);
+    /// let deconvolution = Deconvolution::new(
+    ///     vec![
+    ///         Lorentzian::new(0.5, 0.25, 3.0),
+    ///         Lorentzian::new(0.6, 0.16, 5.0),
+    ///     ],
+    ///     SmoothingSettings::default(),
+    ///     SelectionSettings::default(),
+    ///     FittingSettings::default(),
+    ///     0.5,
+    /// );
+    /// let peaklist = deconvolution.to_bruker_peaklist_string(&spectrum)?;
+    /// let mut lines = peaklist.lines().skip(2);
+    /// let first = lines
+    ///     .next()
+    ///     .unwrap()
+    ///     .split_whitespace()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(first, ["1", "3.00", "3000.0000", "5.000000", "3.750000e0"]);
+    /// assert_eq!(lines.count(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bruker")]
+    pub fn to_bruker_peaklist_string(&self, spectrum: &Spectrum) -> Result<String> {
+        let frequency = spectrum.validated_frequency()?;
+        let step = spectrum.step().abs();
+        let edge = spectrum.range().0.max(spectrum.range().1);
+        let mut peaks = self
+            .signals
+            .iter()
+            .map(|signal| (signal.maxp(), signal.max_intensity()))
+            .collect::<Vec<(f64, f64)>>();
+        peaks.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+        let header = format!(
+            "{:>6} {:>10} {:>22} {:>16}\n{:>6} {:>10} {:>12} {:>12}\n",
+            "#", "ADDRESS", "FREQUENCY", "INTENSITY", "", "", "[Hz]", "[PPM]"
+        );
+
+        Ok(peaks.into_iter().enumerate().fold(
+            header,
+            |mut peaklist, (index, (maxp, intensity))| {
+                peaklist.push_str(&format!(
+                    "{:>6} {:>10.2} {:>12.4} {:>12.6} {:>16.6e}\n",
+                    index + 1,
+                    (edge - maxp) / step + 1.0,
+                    maxp * frequency,
+                    maxp,
+                    intensity
+                ));
+                peaklist
+            },
+        ))
+    }
+
+    /// Writes the deconvoluted signals as a peak list in the text layout of
+    /// Bruker TopSpin to the file at the given path.
+    ///
+    /// See [`to_bruker_peaklist_string`] for the format of the peak list. An
+    /// existing file is overwritten.
+    ///
+    /// [`to_bruker_peaklist_string`]: Deconvolution::to_bruker_peaklist_string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spectrometer frequency of the [`Spectrum`] is
+    /// not finite and positive, or if it is still set to the default value of
+    /// 1 MHz. If any [`I/O`] errors occur, an error variant containing the
+    /// original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Deconvoluter;
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let path = "../data/bruker/sim/sim_01";
+    /// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.35, 3.55))?;
+    /// let deconvoluter = Deconvoluter::default();
+    /// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+    ///
+    /// let path = "path/to/peak.txt";
+    /// # let path = std::env::temp_dir().join("metabodecon_write_bruker_peaklist_doc.txt");
+    /// deconvolution.write_bruker_peaklist(path, &spectrum)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bruker")]
+    pub fn write_bruker_peaklist<P: AsRef<Path>>(
+        &self,
+        path: P,
+        spectrum: &Spectrum,
+    ) -> Result<()> {
+        std::fs::write(path, self.to_bruker_peaklist_string(spectrum)?)?;
+
+        Ok(())
+    }
+
+    /// Internal helper function to match the deconvoluted signals to the given
+    /// template of expected positions.
+    ///
+    /// Closer pairs of positions and signals within the tolerance are matched
+    /// first, such that each is matched at most once. The result contains one
+    /// signal per position in the order of the template, where unmatched
+    /// positions are filled with placeholders of the same peak shape and zero
+    /// area.
+    pub(crate) fn matched_to_template(&self, template: &[f64], tolerance: f64) -> Deconvolution {
+        let mut pairs = template
+            .iter()
+            .enumerate()
+            .flat_map(|(position_index, position)| {
+                self.signals
+                    .iter()
+                    .enumerate()
+                    .map(move |(signal_index, signal)| {
+                        let distance = f64::abs(signal.maxp() - position);
+                        (position_index, signal_index, distance)
+                    })
+            })
+            .filter(|(_, _, distance)| *distance <= tolerance)
+            .collect::<Vec<(usize, usize, f64)>>();
+        pairs.sort_by(|a, b| a.2.total_cmp(&b.2));
+        let mut matches = vec![None; template.len()];
+        let mut matched = vec![false; self.signals.len()];
+        pairs
+            .into_iter()
+            .for_each(|(position_index, signal_index, _)| {
+                if matches[position_index].is_none() && !matched[signal_index] {
+                    matches[position_index] = Some(signal_index);
+                    matched[signal_index] = true;
+                }
+            });
+        let signals =
+            self.signals
+                .with_signals(matches.iter().zip(template.iter()).map(
+                    |(signal_index, position)| match signal_index {
+                        Some(signal_index) => self.signals.get(*signal_index).unwrap(),
+                        None => self.signals.placeholder(tolerance, *position),
+                    },
+                ));
+        let labels = matches
+            .iter()
+            .map(|signal_index| signal_index.and_then(|index| self.labels[index].clone()))
+            .collect();
+
+        Self {
+            signals,
+            fit_diagnostics: None,
+            parameter_uncertainties: None,
+            labels,
+            warnings: Vec::new(),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::Deconvoluter;
+    #[cfg(feature = "serde")]
+    use crate::deconvolution::{NoiseEstimator, ScoringMethod};
+    use crate::macros::{noisy_lorentzian_spectrum, uniform_noise};
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Deconvolution);
+        assert_sync!(Deconvolution);
+    }
+
+    #[test]
+    fn collection_access() {
+        let lorentzians = vec![
+            Lorentzian::new(5.5, 0.25, 3.0),
+            Lorentzian::new(7.0, 0.16, 5.0),
+            Lorentzian::new(5.5, 0.25, 7.0),
+        ];
+        let deconvolution = Deconvolution::new(
+            lorentzians.clone(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        assert_eq!(deconvolution.len(), 3);
+        assert!(!deconvolution.is_empty());
+        let mut count = 0;
+        for (lorentzian, expected) in (&deconvolution)
+            .into_iter()
+            .zip(lorentzians.iter())
+        {
+            assert_approx_eq!(f64, lorentzian.sfhw(), expected.sfhw());
+            count += 1;
+        }
+        assert_eq!(count, deconvolution.len());
+        (0..deconvolution.len()).for_each(|i| {
+            assert_approx_eq!(f64, deconvolution[i].sfhw(), lorentzians[i].sfhw());
+            assert_approx_eq!(f64, deconvolution[i].hw2(), lorentzians[i].hw2());
+            assert_approx_eq!(f64, deconvolution[i].maxp(), lorentzians[i].maxp());
+        });
+        (0..deconvolution.len()).for_each(|i| match deconvolution.get(i) {
+            Some(Signal::Lorentzian(lorentzian)) => {
+                assert_approx_eq!(f64, lorentzian.sfhw(), lorentzians[i].sfhw());
+                assert_approx_eq!(f64, lorentzian.hw2(), lorentzians[i].hw2());
+                assert_approx_eq!(f64, lorentzian.maxp(), lorentzians[i].maxp());
+            }
+            signal => panic!("unexpected signal: {:?}", signal),
+        });
+        let gaussians = Deconvolution::from_gaussians(
+            vec![Gaussian::new(1.0, 0.1, 5.0), Gaussian::new(2.0, 0.1, 3.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        assert_eq!(gaussians.len(), 2);
+        assert!(!gaussians.is_empty());
+        assert_eq!((&gaussians).into_iter().count(), 0);
+        assert_eq!(gaussians.signals().iter().count(), 2);
+        assert_approx_eq!(f64, gaussians.get(1).unwrap().maxp(), 3.0);
+        let empty = Deconvolution::new(
+            Vec::new(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert_eq!((&empty).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn sorted_by_position() {
+        let lorentzians = uniform_noise(100, 42)
+            .into_iter()
+            .enumerate()
+            .map(|(i, noise)| {
+                let maxp = ((noise + 0.5) * 20.0).floor() * 0.5;
+                Lorentzian::new(i as f64, 0.01, maxp)
+            })
+            .collect::<Vec<Lorentzian>>();
+        let deconvolution = Deconvolution::new(
+            lorentzians,
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let sorted = deconvolution.sorted_by_position();
+        assert_eq!(sorted.len(), deconvolution.len());
+        sorted.windows(2).for_each(|w| {
+            assert!(w[0].maxp() <= w[1].maxp());
+            if w[0].maxp() == w[1].maxp() {
+                assert!(w[0].max_intensity() < w[1].max_intensity());
+            }
+        });
+    }
+
+    #[test]
+    fn get_out_of_bounds() {
+        let deconvolution = Deconvolution::new(
+            vec![Lorentzian::new(5.5, 0.25, 3.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        assert!(deconvolution.get(0).is_some());
+        assert!(deconvolution.get(1).is_none());
+    }
+
+    #[test]
+    fn get_and_index() {
+        let deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(5.5, 0.25, 3.0),
+                Lorentzian::new(7.0, 0.16, 5.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let mut sfhw = 0.0;
+        for lorentzian in &deconvolution {
+            sfhw += lorentzian.sfhw();
+        }
+        assert_approx_eq!(f64, sfhw, 12.5);
+        (0..deconvolution.len()).for_each(|i| match deconvolution.get(i) {
+            Some(Signal::Lorentzian(lorentzian)) => {
+                assert_approx_eq!(f64, lorentzian.sfhw(), deconvolution[i].sfhw());
+                assert_approx_eq!(f64, lorentzian.hw2(), deconvolution[i].hw2());
+                assert_approx_eq!(f64, lorentzian.maxp(), deconvolution[i].maxp());
+            }
+            signal => panic!("unexpected signal: {:?}", signal),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let deconvolution = Deconvolution::new(
+            vec![Lorentzian::new(5.5, 0.25, 3.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let _ = deconvolution[1];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_other_peak_shape() {
+        let deconvolution = Deconvolution::from_gaussians(
+            vec![Gaussian::new(1.0, 0.1, 5.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        let _ = deconvolution[0];
+    }
+
+    #[test]
+    fn validate() {
+        let valid = Lorentzian::new(5.5, 0.25, 3.0);
+        let invalid = [
+            Lorentzian::new(-5.5, 0.25, 5.0),
+            Lorentzian::new(5.5, 0.0, 5.0),
+            Lorentzian::new(5.5, -0.25, 5.0),
+            Lorentzian::new(5.5, 0.25, f64::NAN),
+            Lorentzian::new(f64::INFINITY, 0.25, 5.0),
+        ];
+        let deconvolution = |lorentzians: Vec<Lorentzian>| {
+            Deconvolution::new(
+                lorentzians,
+                SmoothingSettings::default(),
+                SelectionSettings::default(),
+                FittingSettings::default(),
+                0.5,
+            )
+        };
+        assert!(
+            deconvolution(vec![valid, valid])
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            deconvolution(vec![valid, Lorentzian::new(0.0, 0.25, 5.0)])
+                .validate()
+                .is_ok()
+        );
+        invalid.into_iter().for_each(|invalid| {
+            match deconvolution(vec![valid, invalid]).validate() {
+                Err(crate::Error::Deconvolution(error)) => match error.kind() {
+                    Kind::InvalidSignal { index } => assert_eq!(*index, 1),
+                    _ => panic!("unexpected kind: {:?}", error),
+                },
+                result => panic!("unexpected result: {:?}", result),
+            }
+        });
+        let gaussians = Deconvolution::from_gaussians(
+            vec![Gaussian::new(1.0, 0.1, 5.0), Gaussian::new(1.0, -0.1, 5.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        assert!(gaussians.validate().is_err());
+        let pseudo_voigts = Deconvolution::from_pseudo_voigts(
+            vec![PseudoVoigt::new(1.5, 0.1, 5.0, 1.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::PseudoVoigt {
+                iterations: 10,
+                eta_init: 0.5,
+            },
+            0.5,
+        );
+        assert!(pseudo_voigts.validate().is_err());
+    }
+
+    #[test]
+    fn adjacent_overlaps() {
+        let deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(0.045, 0.0225, 9.0),
+                Lorentzian::new(0.045, 0.0225, 1.0),
+                Lorentzian::new(0.09, 0.0225, 9.0),
+                Lorentzian::new(0.045, 0.0225, 5.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let overlaps = deconvolution.adjacent_overlaps().unwrap();
+        assert_eq!(overlaps.len(), 3);
+        assert!(overlaps[0] < 0.1);
+        assert!(overlaps[1] < 0.1);
+        assert_approx_eq!(f64, overlaps[2], 1.0);
+        let single = Deconvolution::new(
+            vec![Lorentzian::new(0.045, 0.0225, 5.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        assert!(single.adjacent_overlaps().unwrap().is_empty());
+        let gaussians = Deconvolution::from_gaussians(
+            vec![Gaussian::new(1.0, 0.1, 5.0), Gaussian::new(1.0, 0.1, 5.01)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        assert!(gaussians.adjacent_overlaps().is_none());
+    }
+
+    #[test]
+    fn chi_squared() {
+        let signals = (0..3)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0 + 2.0 * i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (1.0, 9.0));
+        let noise_variance = 0.1_f64.powi(2) / 12.0;
+        let (start, end) = spectrum.signal_boundaries_indices();
+        let correct = Deconvolution::new(
+            signals.clone(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        let degrees_of_freedom = (end - start - 3 * signals.len()) as f64;
+        let chi_squared = correct.chi_squared(&spectrum, noise_variance);
+        assert_approx_eq!(f64, chi_squared, correct.rss(&spectrum) / noise_variance);
+        assert!((chi_squared / degrees_of_freedom - 1.0).abs() < 0.1);
+        assert_approx_eq!(
+            f64,
+            correct.reduced_chi_squared(&spectrum, noise_variance),
+            chi_squared / degrees_of_freedom
+        );
+        let underfitted = Deconvolution::new(
+            signals[..2].to_vec(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        assert!(underfitted.reduced_chi_squared(&spectrum, noise_variance) > 2.0);
+        let deconvoluter = Deconvoluter::new(
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::LevenbergMarquardt {
+                max_iterations: 100,
+                tolerance: 1e-12,
+                lambda_init: 1e-3,
+            },
+        )
+        .unwrap();
+        let fitted = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        assert_approx_eq!(
+            f64,
+            fitted.rss(&spectrum) / (end - start) as f64,
+            fitted.mse(),
+            epsilon = 1e-12
+        );
+        let small = Spectrum::new(vec![1.0, 2.0, 3.0, 4.0, 5.0], vec![1.0; 5], (1.5, 4.5)).unwrap();
+        assert!(
+            correct
+                .reduced_chi_squared(&small, noise_variance)
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn pseudo_voigt_chi_squared() {
+        let signals = (0..3)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0 + 2.0 * i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (1.0, 9.0));
+        let noise_variance = 0.1_f64.powi(2) / 12.0;
+        let (start, end) = spectrum.signal_boundaries_indices();
+        let pseudo_voigts = Deconvolution::from_pseudo_voigts(
+            signals
+                .iter()
+                .map(|signal| {
+                    PseudoVoigt::new(
+                        1.0,
+                        signal.hw(),
+                        signal.maxp(),
+                        signal.sfhw() / signal.hw2(),
+                    )
+                })
+                .collect(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        let degrees_of_freedom = (end - start - 4 * signals.len()) as f64;
+        assert_approx_eq!(
+            f64,
+            pseudo_voigts.reduced_chi_squared(&spectrum, noise_variance),
+            pseudo_voigts.chi_squared(&spectrum, noise_variance) / degrees_of_freedom
+        );
+        let small = Spectrum::new(
+            (0..14).map(|i| i as f64).collect(),
+            vec![1.0; 14],
+            (1.0, 13.0),
+        )
+        .unwrap();
+        let lorentzians = Deconvolution::new(
+            signals,
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        // 12 points are enough for 3 Lorentzians but not for 3 pseudo-Voigts.
+        assert!(
+            lorentzians
+                .reduced_chi_squared(&small, noise_variance)
+                .is_finite()
+        );
+        assert!(
+            pseudo_voigts
+                .reduced_chi_squared(&small, noise_variance)
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn peak_statistics() {
+        let spectrum = Spectrum::new(
+            (0..=1000).map(|i| i as f64 * 0.01).collect(),
+            vec![1.0; 1001],
+            (2.0, 8.0),
+        )
+        .unwrap();
+        let gaussians = Deconvolution::from_gaussians(
+            vec![
+                Gaussian::new(1.0, 0.15, 3.0),
+                Gaussian::new(2.0, 0.1, 5.0),
+                Gaussian::new(3.0, 0.25, 7.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        assert_eq!(gaussians.len(), 3);
+        assert_approx_eq!(f64, gaussians.peak_density_per_ppm(&spectrum), 0.5);
+        assert_approx_eq!(f64, gaussians.mean_fwhm(), 1.0 / 3.0);
+        assert_approx_eq!(f64, gaussians.narrowest_peak().unwrap().maxp(), 5.0);
+        assert_approx_eq!(f64, gaussians.widest_peak().unwrap().maxp(), 7.0);
+        let empty = Deconvolution::from_gaussians(
+            Vec::new(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        assert_eq!(empty.len(), 0);
+        assert!(empty.mean_fwhm().is_nan());
+        assert!(empty.narrowest_peak().is_none());
+        assert!(empty.widest_peak().is_none());
+    }
+
+    #[test]
+    fn merge_close_peaks() {
+        let signal = Lorentzian::new(0.0006, 0.0001, 5.0);
+        let chemical_shifts = (0..1001)
+            .map(|i| 4.5 + i as f64 * 0.001)
+            .collect::<Vec<f64>>();
+        let intensities = signal.evaluate_vec(&chemical_shifts);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (4.6, 5.4)).unwrap();
+        let split = [
+            Lorentzian::new(0.0002, 0.0001, 4.998),
+            Lorentzian::new(0.0004, 0.0001, 5.001),
+            Lorentzian::new(0.0006, 0.0001, 4.7),
+        ];
+        let deconvolution = Deconvolution::new(
+            split.to_vec(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let unchanged = deconvolution.merge_close_peaks(0.001);
+        assert_eq!(unchanged.len(), 3);
+        assert_approx_eq!(f64, unchanged.mse(), 0.5);
+        let merged = deconvolution.merge_close_peaks(0.01);
+        assert_eq!(merged.len(), 2);
+        assert_approx_eq!(f64, merged.mse(), 0.5);
+        assert_approx_eq!(f64, merged.lorentzians()[0].maxp(), 4.7);
+        assert_approx_eq!(f64, merged.lorentzians()[1].sf(), signal.sf());
+        assert_approx_eq!(f64, merged.lorentzians()[1].hw(), signal.hw());
+        assert_approx_eq!(f64, merged.lorentzians()[1].maxp(), 5.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, merged.total_area(), deconvolution.total_area());
+        let chained = deconvolution.merge_close_peaks(0.3);
+        assert_eq!(chained.len(), 1);
+        let single = Deconvolution::new(
+            split[..2].to_vec(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        let merged = single.merge_close_peaks_with_mse(&spectrum, 0.01);
+        assert_eq!(merged.len(), 1);
+        assert!(merged.mse() < 1e-20);
+        let pseudo_voigts = Deconvolution::from_pseudo_voigts(
+            vec![
+                PseudoVoigt::new(0.2, 0.01, 4.998, 1.0),
+                PseudoVoigt::new(0.8, 0.01, 5.001, 3.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::PseudoVoigt {
+                iterations: 10,
+                eta_init: 0.5,
+            },
+            0.5,
+        );
+        let merged = pseudo_voigts.merge_close_peaks(0.01);
+        assert_eq!(merged.pseudo_voigts().len(), 1);
+        assert_approx_eq!(f64, merged.pseudo_voigts()[0].eta(), 0.65);
+        assert_approx_eq!(f64, merged.pseudo_voigts()[0].maxp(), 5.00025);
+        assert_approx_eq!(
+            f64,
+            merged.total_area(),
+            pseudo_voigts.total_area(),
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let mut deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(0.045, 0.0225, 3.0),
+                Lorentzian::new(0.045, 0.0225, 1.0),
+                Lorentzian::new(0.045, 0.0225, 1.005),
+                Lorentzian::new(0.045, 0.0225, 5.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        assert_eq!(deconvolution.labels(), [None, None, None, None]);
+        deconvolution.label_peak(0, "creatine");
+        deconvolution.label_peak(0, "creatinine");
+        assert_eq!(
+            deconvolution.label_by_position(1.006, 0.01, "lactate"),
+            Some(2)
+        );
+        assert_eq!(
+            deconvolution.label_by_position(0.999, 0.01, "valine"),
+            Some(1)
+        );
+        assert_eq!(deconvolution.label_by_position(4.0, 0.5, "unknown"), None);
+        assert_eq!(
+            deconvolution.labels(),
+            [
+                Some("creatinine".to_string()),
+                Some("valine".to_string()),
+                Some("lactate".to_string()),
+                None,
+            ]
+        );
+        let merged = deconvolution.merge_close_peaks(0.01);
+        assert_eq!(
+            merged.labels(),
+            [
+                Some("valine, lactate".to_string()),
+                Some("creatinine".to_string()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn label_out_of_bounds() {
+        let mut deconvolution = Deconvolution::new(
+            vec![Lorentzian::new(5.5, 0.25, 3.0)],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        deconvolution.label_peak(1, "alanine");
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_labels() {
+        let mut deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(0.5, 0.25, 3.0),
+                Lorentzian::new(0.6, 0.16, 5.0),
+                Lorentzian::new(0.5, 0.25, 7.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        deconvolution.label_peak(0, "alanine");
+        deconvolution.label_peak(2, "glucose, \"alpha\"");
+        let csv = deconvolution.to_csv_string();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("maxp,hw,sf,integral,sfhw,hw2,label"));
+        assert!(lines.next().unwrap().ends_with(",alanine"));
+        assert!(lines.next().unwrap().ends_with(",0.16,"));
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .ends_with(",\"glucose, \"\"alpha\"\"\"")
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_round_trip() {
+        let lorentzians = vec![
+            Lorentzian::new(5.5, 0.25, 3.0),
+            Lorentzian::new(7.0, 0.16, 5.0),
+            Lorentzian::new(5.5, 0.25, 7.0),
+        ];
+        let deconvolution = Deconvolution::new(
+            lorentzians.clone(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let path = std::env::temp_dir().join("metabodecon_csv_round_trip.csv");
+        deconvolution.write_csv(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(csv, deconvolution.to_csv_string());
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), "maxp,hw,sf,integral,sfhw,hw2");
+        let rows = lines
+            .map(|line| {
+                line.split(',')
+                    .map(|value| value.parse::<f64>().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), lorentzians.len());
+        rows.iter()
+            .zip(lorentzians.iter())
+            .for_each(|(row, lorentzian)| {
+                assert_eq!(row.len(), 6);
+                assert_approx_eq!(f64, row[0], lorentzian.maxp());
+                assert_approx_eq!(f64, row[1], lorentzian.hw());
+                assert_approx_eq!(f64, row[2], lorentzian.sf());
+                assert_approx_eq!(f64, row[3], lorentzian.integral());
+                assert_approx_eq!(f64, row[4], lorentzian.sfhw());
+                assert_approx_eq!(f64, row[5], lorentzian.hw2());
+            });
+    }
+
+    #[cfg(feature = "bruker")]
+    #[test]
+    fn bruker_peaklist() {
+        let chemical_shifts = (0..1001)
+            .map(|i| 10.0 - i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let intensities = vec![0.0; chemical_shifts.len()];
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        let gaussians = vec![
+            Gaussian::new(5.5, 0.25, 3.0),
+            Gaussian::new(7.0, 0.16, 5.0),
+            Gaussian::new(5.5, 0.25, 7.0),
+        ];
+        let deconvolution = Deconvolution::from_gaussians(
+            gaussians.clone(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        let path = std::env::temp_dir().join("metabodecon_bruker_peaklist.txt");
+        assert!(
+            deconvolution
+                .write_bruker_peaklist(&path, &spectrum)
+                .is_err()
+        );
+        spectrum.set_frequency(600.0);
+        deconvolution
+            .write_bruker_peaklist(&path, &spectrum)
+            .unwrap();
+        let peaklist = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            peaklist,
+            deconvolution
+                .to_bruker_peaklist_string(&spectrum)
+                .unwrap()
+        );
+        let rows = peaklist
+            .lines()
+            .skip(2)
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|value| value.parse::<f64>().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), gaussians.len());
+        rows.iter()
+            .zip(gaussians.iter().rev())
+            .enumerate()
+            .for_each(|(index, (row, gaussian))| {
+                assert_eq!(row.len(), 5);
+                assert_approx_eq!(f64, row[0], (index + 1) as f64);
+                assert_approx_eq!(
+                    f64,
+                    row[1],
+                    (10.0 - gaussian.maxp()) / 0.01 + 1.0,
+                    epsilon = 1e-6
+                );
+                assert_approx_eq!(f64, row[2], gaussian.maxp() * 600.0);
+                assert_approx_eq!(f64, row[3], gaussian.maxp());
+                assert_approx_eq!(
+                    f64,
+                    row[4],
+                    gaussian.evaluate(gaussian.maxp()),
+                    epsilon = 1e-6
+                );
+            });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialization_round_trip() {
+        let lorentzians = vec![
+            Lorentzian::new(5.5, 0.25, 3.0),
+            Lorentzian::new(7.0, 0.16, 5.0),
+            Lorentzian::new(5.5, 0.25, 7.0),
+        ];
+        let deconvolution = Deconvolution::new(
+            lorentzians.clone(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let serialized = serde_json::to_string(&deconvolution).unwrap();
+        let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
+        deconvolution
+            .lorentzians()
+            .iter()
+            .zip(deserialized.lorentzians())
+            .for_each(|(init, rec)| {
+                assert_approx_eq!(f64, init.sfhw(), rec.sfhw());
+                assert_approx_eq!(f64, init.hw2(), rec.hw2());
+                assert_approx_eq!(f64, init.maxp(), rec.maxp());
+            });
+        match deserialized.smoothing_settings() {
+            SmoothingSettings::MovingAverage {
+                iterations,
+                window_size,
+            } => {
+                assert_eq!(iterations, 3);
+                assert_eq!(window_size, 3);
+            }
+            _ => panic!("expected moving average"),
+        };
+        match deserialized.selection_settings() {
+            SelectionSettings::DetectorOnly => panic!("expected noise score filter"),
+            SelectionSettings::NoiseScoreFilter {
+                scoring_method,
+                threshold,
+                max_peaks,
+                ..
+            } => {
+                match scoring_method {
+                    ScoringMethod::MinimumSum => {}
+                }
+                assert_approx_eq!(f64, threshold, 5.0);
+                assert_eq!(max_peaks, None);
+            }
+        };
+        match deserialized.fitting_settings() {
+            FittingSettings::Analytical { iterations } => {
+                assert_eq!(iterations, 10);
+            }
+            _ => panic!("expected analytical fitting"),
+        };
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn labels_serialization_round_trip() {
+        let mut deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(5.5, 0.25, 3.0),
+                Lorentzian::new(7.0, 0.16, 5.0),
+                Lorentzian::new(5.5, 0.25, 7.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let serialized = serde_json::to_string(&deconvolution).unwrap();
+        assert!(!serialized.contains("labels"));
+        let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
+        assert_eq!(deserialized.labels(), [None, None, None]);
+        deconvolution.label_peak(0, "alanine");
+        deconvolution.label_peak(2, "lactate");
+        let serialized = serde_json::to_string(&deconvolution).unwrap();
+        let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
+        assert_eq!(deserialized.labels(), deconvolution.labels());
+        let serialized = rmp_serde::to_vec_named(&deconvolution).unwrap();
+        let deserialized = rmp_serde::from_slice::<Deconvolution>(&serialized).unwrap();
+        assert_eq!(deserialized.labels(), deconvolution.labels());
+    }
+
+    #[test]
+    fn gaussian_labels() {
+        let mut deconvolution = Deconvolution::from_gaussians(
+            vec![
+                Gaussian::new(5.5, 0.25, 3.0),
+                Gaussian::new(7.0, 0.16, 5.0),
+                Gaussian::new(5.5, 0.25, 7.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        assert_eq!(deconvolution.labels(), [None, None, None]);
+        deconvolution.label_peak(0, "alanine");
+        assert_eq!(
+            deconvolution.label_by_position(6.95, 0.1, "lactate"),
+            Some(2)
+        );
+        assert_eq!(
+            deconvolution.labels(),
+            [
+                Some("alanine".to_string()),
+                None,
+                Some("lactate".to_string()),
+            ]
+        );
+        #[cfg(feature = "serde")]
+        {
+            let serialized = serde_json::to_string(&deconvolution).unwrap();
+            let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
+            assert_eq!(deserialized.labels(), deconvolution.labels());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn invalid_deserialization() {
+        let deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(5.5, 0.25, 3.0),
+                Lorentzian::new(-7.0, 0.16, 5.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let serialized = serde_json::to_string(&deconvolution).unwrap();
+        assert!(serde_json::from_str::<Deconvolution>(&serialized).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn settings_serialization_round_trip() {
+        use crate::Settings;
+
+        let lorentzians = (0..25)
+            .map(|i| Lorentzian::new(0.5 + i as f64 * 0.1, 0.01, 0.2 * i as f64))
+            .collect::<Vec<Lorentzian>>();
+        let smoothing_settings = SmoothingSettings::Whittaker {
+            lambda: 250.0,
+            order: 2,
+        };
+        let selection_settings = SelectionSettings::NoiseScoreFilter {
+            scoring_method: ScoringMethod::MinimumSum,
+            threshold: 4.5,
+            max_peaks: Some(40),
+            noise_estimator: NoiseEstimator::StdDev,
+        };
+        let fitting_settings = FittingSettings::LevenbergMarquardt {
+            max_iterations: 25,
+            tolerance: 1e-6,
+            lambda_init: 1e-2,
+        };
+        let deconvolution = Deconvolution::new(
+            lorentzians,
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+            0.0123,
+        );
+        let serialized = serde_json::to_string(&deconvolution).unwrap();
+        let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
+        assert_eq!(deserialized.len(), deconvolution.len());
+        assert_approx_eq!(f64, deserialized.mse(), deconvolution.mse());
+        assert!(
+            deserialized
+                .smoothing_settings()
+                .compare(&smoothing_settings)
+        );
+        assert!(
+            deserialized
+                .selection_settings()
+                .compare(&selection_settings)
+        );
+        assert!(
+            deserialized
+                .fitting_settings()
+                .compare(&fitting_settings)
+        );
+
+        let legacy = serialized.replace(",\"maxPeaks\":40", "");
+        assert_ne!(legacy, serialized);
+        let deserialized = serde_json::from_str::<Deconvolution>(&legacy).unwrap();
+        match deserialized.selection_settings() {
+            SelectionSettings::NoiseScoreFilter { max_peaks, .. } => assert_eq!(max_peaks, None),
+            _ => panic!("expected noise score filter"),
+        };
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn gaussian_serialization_round_trip() {
+        let gaussians = vec![
+            Gaussian::new(5.5, 0.25, 3.0),
+            Gaussian::new(7.0, 0.16, 5.0),
+            Gaussian::new(5.5, 0.25, 7.0),
+        ];
+        let deconvolution = Deconvolution::from_gaussians(
+            gaussians.clone(),
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::Gaussian { iterations: 10 },
+            0.5,
+        );
+        let serialized = serde_json::to_string(&deconvolution).unwrap();
+        let deserialized = serde_json::from_str::<Deconvolution>(&serialized).unwrap();
+        assert!(deserialized.lorentzians().is_empty());
+        gaussians
+            .iter()
+            .zip(deserialized.gaussians())
+            .for_each(|(init, rec)| {
+                assert_approx_eq!(f64, init.sf(), rec.sf());
+                assert_approx_eq!(f64, init.hw(), rec.hw());
+                assert_approx_eq!(f64, init.maxp(), rec.maxp());
+            });
+        match deserialized.fitting_settings() {
+            FittingSettings::Gaussian { iterations } => {
+                assert_eq!(iterations, 10);
+            }
+            _ => panic!("expected gaussian fitting"),
+        };
+    }
+
+    #[test]
+    fn summary() {
+        let deconvolution = Deconvolution::new(
+            vec![
+                Lorentzian::new(0.045, 0.0225, 3.0),
+                Lorentzian::new(0.1, 0.01, 5.0),
+                Lorentzian::new(0.15, 0.0625, 7.0),
+            ],
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.25,
+        );
+        let summary = deconvolution.to_string();
+        assert!(summary.contains("3 peaks"));
+        assert!(summary.contains("2.500000e-1"));
+        assert_eq!(summary.lines().count(), 6);
+        let positions = summary
+            .lines()
+            .skip(3)
+            .map(|line| {
+                line.split_whitespace()
+                    .next()
+                    .unwrap()
+                    .parse::<f64>()
+                    .unwrap()
+            })
+            .collect::<Vec<f64>>();
+        assert_eq!(positions, [5.0, 7.0, 3.0]);
+        assert_eq!(deconvolution.summary_string(0).lines().count(), 1);
+        assert_eq!(deconvolution.summary_string(2).lines().count(), 5);
     }
 }
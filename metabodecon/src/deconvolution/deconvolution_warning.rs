@@ -0,0 +1,238 @@
+use crate::deconvolution::Deconvolution;
+use crate::spectrum::Spectrum;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Quality issue of a [`Deconvolution`] that doesn't prevent it from being
+/// used, but indicates that its signals may be unreliable.
+///
+/// Warnings are collected by the deconvolution functions of the
+/// [`Deconvoluter`] from the peak count, the MSE and the [`FitDiagnostics`] of
+/// the fitted signals, as well as from the intensities of the [`Spectrum`].
+/// See [`Deconvolution::warnings`].
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+/// [`FitDiagnostics`]: crate::deconvolution::FitDiagnostics
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase", rename_all_fields = "camelCase")
+)]
+pub enum DeconvolutionWarning {
+    /// More signals were fitted than the resolution of the [`Spectrum`]
+    /// plausibly supports, i.e. there are fewer than
+    /// [`MIN_POINTS_PER_PEAK`] data points in the signal region per signal.
+    /// This usually means that noise was fitted, e.g. because the smoothing is
+    /// too weak or the peak selection threshold too low.
+    ///
+    /// [`MIN_POINTS_PER_PEAK`]: DeconvolutionWarning::MIN_POINTS_PER_PEAK
+    HighPeakCount {
+        /// Number of fitted signals.
+        peak_count: usize,
+        /// Number of data points in the signal region.
+        points: usize,
+    },
+    /// The fit of some signals did not converge. See
+    /// [`FitDiagnostics::converged`].
+    ///
+    /// [`FitDiagnostics::converged`]: crate::deconvolution::FitDiagnostics::converged
+    Unconverged {
+        /// Number of signals whose fit did not converge.
+        count: usize,
+    },
+    /// The half-width of some signals would have become negative during the
+    /// refinement and was clamped to its bound. See
+    /// [`FitDiagnostics::hit_bound`].
+    ///
+    /// [`FitDiagnostics::hit_bound`]: crate::deconvolution::FitDiagnostics::hit_bound
+    ClampedWidth {
+        /// Number of signals whose half-width was clamped.
+        count: usize,
+    },
+    /// The MSE relative to the variance of the intensities within the signal
+    /// region, i.e. `1 - R²`, is above [`MAX_RELATIVE_MSE`].
+    ///
+    /// [`MAX_RELATIVE_MSE`]: DeconvolutionWarning::MAX_RELATIVE_MSE
+    HighMse {
+        /// Mean squared error of the deconvolution.
+        mse: f64,
+        /// Uniformly weighted MSE relative to the variance of the intensities.
+        relative_mse: f64,
+    },
+    /// The [`Spectrum`] contains flat-topped peaks, which indicates that the
+    /// receiver gain was too high and the intensities were clipped. Clipped
+    /// peaks can't be fitted accurately. See [`Spectrum::detect_clipping`].
+    ClippedIntensities {
+        /// Number of runs of clipped intensities.
+        count: usize,
+    },
+}
+
+impl std::fmt::Display for DeconvolutionWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeconvolutionWarning::HighPeakCount { peak_count, points } => write!(
+                f,
+                "peak count suspiciously high: {peak_count} signals fitted to {points} data \
+                 points in the signal region"
+            ),
+            DeconvolutionWarning::Unconverged { count } => {
+                write!(f, "fit did not converge for {count} signals")
+            }
+            DeconvolutionWarning::ClampedWidth { count } => {
+                write!(f, "negative half-width clamped for {count} signals")
+            }
+            DeconvolutionWarning::HighMse { mse, relative_mse } => write!(
+                f,
+                "MSE above threshold: {mse:.3e} ({:.2}% of the variance of the \
+                 intensities, threshold {:.2}%)",
+                100.0 * relative_mse,
+                100.0 * Self::MAX_RELATIVE_MSE
+            ),
+            DeconvolutionWarning::ClippedIntensities { count } => {
+                write!(f, "intensities clipped at the maximum in {count} places")
+            }
+        }
+    }
+}
+
+impl DeconvolutionWarning {
+    /// Minimum number of data points in the signal region per fitted signal
+    /// below which the peak count is considered suspiciously high.
+    pub const MIN_POINTS_PER_PEAK: usize = 32;
+
+    /// Relative MSE above which the fit is considered poor.
+    pub const MAX_RELATIVE_MSE: f64 = 0.05;
+
+    /// Internal helper function to check the given `Deconvolution` of the
+    /// given `Spectrum` for quality issues.
+    pub(crate) fn check(
+        deconvolution: &Deconvolution,
+        spectrum: &Spectrum,
+        ignore_regions: Option<&[(f64, f64)]>,
+    ) -> Vec<Self> {
+        let mut warnings = Vec::new();
+        let peak_count = deconvolution.len();
+        let points = spectrum.signal_region_len();
+        if peak_count * Self::MIN_POINTS_PER_PEAK > points {
+            warnings.push(Self::HighPeakCount { peak_count, points });
+        }
+        if let Some(diagnostics) = deconvolution.fit_diagnostics() {
+            let count = diagnostics
+                .iter()
+                .filter(|diagnostics| !diagnostics.converged())
+                .count();
+            if count > 0 {
+                warnings.push(Self::Unconverged { count });
+            }
+            let count = diagnostics
+                .iter()
+                .filter(|diagnostics| diagnostics.hit_bound())
+                .count();
+            if count > 0 {
+                warnings.push(Self::ClampedWidth { count });
+            }
+        }
+        if peak_count > 0 {
+            let relative_mse =
+                1.0 - deconvolution.r_squared_with_ignore_regions(spectrum, ignore_regions);
+            if relative_mse > Self::MAX_RELATIVE_MSE {
+                warnings.push(Self::HighMse {
+                    mse: deconvolution.mse(),
+                    relative_mse,
+                });
+            }
+        }
+        if let Some(runs) = spectrum.detect_clipping() {
+            warnings.push(Self::ClippedIntensities { count: runs.len() });
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::{FittingSettings, Lorentzian, SelectionSettings, SmoothingSettings};
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(DeconvolutionWarning);
+        assert_sync!(DeconvolutionWarning);
+    }
+
+    #[test]
+    fn check() {
+        let signals = vec![
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        let chemical_shifts = (0..1001)
+            .map(|i| i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let intensities = Lorentzian::superposition_vec(&chemical_shifts, &signals);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (2.0, 8.0)).unwrap();
+        let deconvolution = |signals: Vec<Lorentzian>| {
+            Deconvolution::new(
+                signals,
+                SmoothingSettings::default(),
+                SelectionSettings::default(),
+                FittingSettings::default(),
+                0.0,
+            )
+        };
+        let exact = deconvolution(signals.clone());
+        assert!(DeconvolutionWarning::check(&exact, &spectrum, None).is_empty());
+        let missing = deconvolution(signals[..1].to_vec());
+        match DeconvolutionWarning::check(&missing, &spectrum, None)[..] {
+            [DeconvolutionWarning::HighMse { relative_mse, .. }] => {
+                assert!(relative_mse > DeconvolutionWarning::MAX_RELATIVE_MSE)
+            }
+            ref warnings => panic!("unexpected warnings: {:?}", warnings),
+        }
+        assert!(DeconvolutionWarning::check(&missing, &spectrum, Some(&[(6.5, 7.5)])).is_empty());
+        let crowded = deconvolution(
+            (0..20)
+                .map(|i| Lorentzian::new(1e-9, 1e-6, 2.0 + i as f64 * 0.3))
+                .chain(signals)
+                .collect(),
+        );
+        match DeconvolutionWarning::check(&crowded, &spectrum, None)[..] {
+            [DeconvolutionWarning::HighPeakCount { peak_count, points }] => {
+                assert_eq!(peak_count, 22);
+                assert_eq!(points, 601);
+            }
+            ref warnings => panic!("unexpected warnings: {:?}", warnings),
+        }
+    }
+
+    #[test]
+    fn check_clipping() {
+        let signals = vec![
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        let chemical_shifts = (0..1001)
+            .map(|i| i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let intensities = Lorentzian::superposition_vec(&chemical_shifts, &signals)
+            .into_iter()
+            .map(|intensity| intensity.min(5.0))
+            .collect();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (2.0, 8.0)).unwrap();
+        let deconvolution = Deconvolution::new(
+            signals,
+            SmoothingSettings::default(),
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.0,
+        );
+        let warnings = DeconvolutionWarning::check(&deconvolution, &spectrum, None);
+        assert!(warnings.contains(&DeconvolutionWarning::ClippedIntensities { count: 2 }));
+    }
+}
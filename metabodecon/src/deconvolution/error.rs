@@ -1,6 +1,8 @@
 //! Error types for the deconvolution process.
 
-use crate::deconvolution::{FittingSettings, SelectionSettings, SmoothingSettings};
+use crate::deconvolution::{
+    BaselineSettings, FittingSettings, SelectionSettings, SmoothingSettings,
+};
 
 /// An `Error` that occurred during the deconvolution process.
 ///
@@ -37,10 +39,19 @@ impl Error {
 #[non_exhaustive]
 #[derive(Clone, Debug)]
 pub enum Kind {
+    /// The provided baseline correction settings are invalid.
+    ///
+    /// Some configurations, such as a non-positive `lambda` for asymmetric
+    /// least squares, are invalid.
+    InvalidBaselineSettings {
+        /// The provided baseline correction settings.
+        settings: BaselineSettings,
+    },
     /// The provided smoothing settings are invalid.
     ///
     /// Some configurations, such as a `window_size` of 0 for a moving
-    /// average filter, are invalid.
+    /// average filter, are invalid. Settings of a custom smoother are valid,
+    /// but can't be used to construct the smoother.
     InvalidSmoothingSettings {
         /// The provided smoothing settings.
         settings: SmoothingSettings,
@@ -70,6 +81,50 @@ pub enum Kind {
         /// The provided ignore region.
         region: (f64, f64),
     },
+    /// The provided noise region is invalid.
+    ///
+    /// The region must be a tuple of two finite floating point numbers, with an
+    /// absolute difference is greater than a small multiple of the floating
+    /// point precision.
+    InvalidNoiseRegion {
+        /// The provided noise region.
+        region: (f64, f64),
+    },
+    /// The provided boost region or its factor is invalid.
+    ///
+    /// The region must be a tuple of two finite floating point numbers, with an
+    /// absolute difference is greater than a small multiple of the floating
+    /// point precision. The factor must be finite and at least 1.
+    InvalidBoostRegion {
+        /// The provided boost region.
+        region: (f64, f64),
+        /// The provided factor.
+        factor: f64,
+    },
+    /// The provided region to be deconvoluted is invalid.
+    ///
+    /// The region must be a tuple of two finite floating point numbers, with an
+    /// absolute difference is greater than a small multiple of the floating
+    /// point precision.
+    InvalidDeconvolutionRegion {
+        /// The provided region to be deconvoluted.
+        region: (f64, f64),
+    },
+    /// The region of a [`Spectrum`] to be updated doesn't match the provided
+    /// intensities.
+    ///
+    /// The region must contain at least one data point of the [`Spectrum`],
+    /// and exactly one intensity has to be provided for each of them.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidRegionUpdate {
+        /// The provided region to be updated.
+        region: (f64, f64),
+        /// Number of data points within the region.
+        points: usize,
+        /// Number of provided intensities.
+        intensities: usize,
+    },
     /// No peaks were detected in the input data.
     ///
     /// Most of the time this will happen if the intensities of the [`Spectrum`]
@@ -88,13 +143,93 @@ pub enum Kind {
     /// No peaks were found in the part of the spectrum where random signals due
     /// to noise would be expected.
     ///
-    /// This is an error because the deconvolution process uses the noise peaks
-    /// to estimate the noise level in the spectrum and filter out peaks that
-    /// are likely to be random noise.
+    /// This is either the signal free region or, if one was set, the noise
+    /// region. This is an error because the deconvolution process uses the
+    /// noise peaks to estimate the noise level in the spectrum and filter out
+    /// peaks that are likely to be random noise.
     EmptySignalFreeRegion,
+    /// An [`OptimizationGrid`] was constructed without candidates for one of
+    /// the steps of the deconvolution.
+    ///
+    /// Every combination of the candidate settings is evaluated, so each of
+    /// the smoothing, peak selection, and fitting steps requires at least one
+    /// candidate.
+    ///
+    /// [`OptimizationGrid`]: crate::deconvolution::OptimizationGrid
+    EmptyOptimizationGrid,
+    /// A signal of a [`Deconvolution`] doesn't represent a valid peak shape.
+    ///
+    /// The scale factor of each signal must be finite and non-negative, its
+    /// half-width finite and positive, and its maximum position finite.
+    /// Otherwise, the signal evaluates to NaN or negative values, and corrupts
+    /// its serialization. This is returned by [`Deconvolution::validate`].
+    ///
+    /// [`Deconvolution`]: crate::deconvolution::Deconvolution
+    /// [`Deconvolution::validate`]: crate::deconvolution::Deconvolution::validate
+    InvalidSignal {
+        /// Position of the invalid signal within the `Deconvolution`.
+        index: usize,
+    },
+    /// The deconvolution of a [`Spectrum`] within a batch failed.
+    ///
+    /// This wraps the `Error` of the failed deconvolution with the position of
+    /// the [`Spectrum`] within the batch, such that it can be identified and
+    /// skipped or reported.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    SpectrumInBatch {
+        /// Position of the spectrum within the batch.
+        index: usize,
+        /// The `Error` that occurred while deconvoluting the spectrum.
+        source: Box<Error>,
+    },
+    /// The deconvolution was cancelled.
+    ///
+    /// This is returned by the cancellable batch deconvolution methods if the
+    /// cancellation flag was set before the fit of the last spectrum was
+    /// completed. The flag is checked between spectra and between the
+    /// iterations of the fit.
+    Cancelled,
+    /// The fitting settings of a [`Deconvoluter`] don't support refining a
+    /// previous [`Deconvolution`].
+    ///
+    /// Refinement resumes the fit from the signals of the previous
+    /// `Deconvolution`, which is only implemented for Lorentzian signals
+    /// fitted with the analytical or Levenberg-Marquardt method. This is
+    /// returned by [`Deconvoluter::refine`] if the fitting settings use a
+    /// different method, or if the previous `Deconvolution` doesn't contain
+    /// Lorentzian signals.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`Deconvolution`]: crate::deconvolution::Deconvolution
+    /// [`Deconvoluter::refine`]: crate::deconvolution::Deconvoluter::refine
+    UnsupportedRefinement {
+        /// The fitting settings used for the refinement.
+        settings: FittingSettings,
+    },
+    /// The provided template of expected signal positions is invalid.
+    ///
+    /// The positions must be finite, and the tolerance within which the
+    /// signals are matched to them must be finite and positive. This is
+    /// returned by [`Deconvoluter::deconvolute_spectra_templated`].
+    ///
+    /// [`Deconvoluter::deconvolute_spectra_templated`]: crate::deconvolution::Deconvoluter::deconvolute_spectra_templated
+    InvalidTemplate {
+        /// The provided positions.
+        template: Vec<f64>,
+        /// The provided tolerance.
+        tolerance: f64,
+    },
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            Kind::SpectrumInBatch { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl From<Kind> for Error {
     fn from(value: Kind) -> Self {
@@ -105,8 +240,35 @@ impl From<Kind> for Error {
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let description = match &self.kind {
+            Kind::InvalidBaselineSettings { settings } => match settings {
+                BaselineSettings::None => unreachable!("no baseline correction is always valid"),
+                BaselineSettings::AsymmetricLeastSquares {
+                    lambda,
+                    p,
+                    iterations,
+                } => {
+                    let reasons = [
+                        (
+                            !(lambda.is_finite() && *lambda > 0.0),
+                            "lambda must be finite and positive",
+                        ),
+                        (!(*p > 0.0 && *p < 1.0), "p must be within (0, 1)"),
+                        (*iterations == 0, "iterations must be greater than 0"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(invalid, reason)| invalid.then_some(reason))
+                    .collect::<Vec<_>>();
+                    match reasons.is_empty() {
+                        true => unreachable!("valid settings falsely detected as invalid"),
+                        false => reasons.join(" and "),
+                    }
+                }
+            },
             Kind::InvalidSmoothingSettings { settings } => match settings {
                 SmoothingSettings::Identity => unreachable!("no smoothing is always valid"),
+                SmoothingSettings::Custom => {
+                    "custom smoothers can't be constructed from their settings".to_string()
+                }
                 SmoothingSettings::MovingAverage {
                     iterations,
                     window_size,
@@ -121,39 +283,128 @@ impl core::fmt::Display for Error {
                         unreachable!("valid settings falsely detected as invalid")
                     }
                 },
+                SmoothingSettings::Whittaker { lambda, order } => {
+                    let reasons = [
+                        (
+                            !(lambda.is_finite() && *lambda > 0.0),
+                            "lambda must be finite and positive",
+                        ),
+                        (!(1..=3).contains(order), "order must be within [1, 3]"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(invalid, reason)| invalid.then_some(reason))
+                    .collect::<Vec<_>>();
+                    match reasons.is_empty() {
+                        true => unreachable!("valid settings falsely detected as invalid"),
+                        false => reasons.join(" and "),
+                    }
+                }
             },
             Kind::InvalidSelectionSettings { settings } => match settings {
                 SelectionSettings::DetectorOnly => unreachable!("detector only is always valid"),
-                SelectionSettings::NoiseScoreFilter { threshold, .. } => {
-                    match (threshold.is_finite(), *threshold <= 0.0) {
-                        (false, _) => "threshold must be a finite number".to_string(),
-                        (true, true) => "threshold must be greater than 0".to_string(),
-                        (true, false) => unreachable!("valid settings falsely detected as invalid"),
+                SelectionSettings::NoiseScoreFilter {
+                    threshold,
+                    max_peaks,
+                    ..
+                } => {
+                    let reasons = [
+                        (!threshold.is_finite(), "threshold must be a finite number"),
+                        (
+                            threshold.is_finite() && *threshold <= 0.0,
+                            "threshold must be greater than 0",
+                        ),
+                        (
+                            *max_peaks == Some(0),
+                            "maximum number of peaks must be greater than 0",
+                        ),
+                    ]
+                    .into_iter()
+                    .filter_map(|(invalid, reason)| invalid.then_some(reason))
+                    .collect::<Vec<_>>();
+                    match reasons.is_empty() {
+                        true => unreachable!("valid settings falsely detected as invalid"),
+                        false => reasons.join(" and "),
                     }
                 }
             },
             Kind::InvalidFittingSettings { settings } => match settings {
-                FittingSettings::Analytical { iterations } => match *iterations == 0 {
+                FittingSettings::Analytical { iterations }
+                | FittingSettings::Gaussian { iterations } => match *iterations == 0 {
                     true => "iterations must be greater than 0".to_string(),
                     false => unreachable!("valid settings falsely detected as invalid"),
                 },
+                FittingSettings::LevenbergMarquardt {
+                    max_iterations,
+                    tolerance,
+                    lambda_init,
+                } => {
+                    let reasons = [
+                        (
+                            *max_iterations == 0,
+                            "maximum number of iterations must be greater than 0",
+                        ),
+                        (
+                            !(tolerance.is_finite() && *tolerance >= 0.0),
+                            "tolerance must be finite and non-negative",
+                        ),
+                        (
+                            !(lambda_init.is_finite() && *lambda_init > 0.0),
+                            "initial damping factor must be finite and positive",
+                        ),
+                    ]
+                    .into_iter()
+                    .filter_map(|(invalid, reason)| invalid.then_some(reason))
+                    .collect::<Vec<_>>();
+                    match reasons.is_empty() {
+                        true => unreachable!("valid settings falsely detected as invalid"),
+                        false => reasons.join(" and "),
+                    }
+                }
+                FittingSettings::PseudoVoigt {
+                    iterations,
+                    eta_init,
+                } => match (*iterations == 0, (0.0..=1.0).contains(eta_init)) {
+                    (true, false) => {
+                        "iterations must be greater than 0 and initial eta must be within [0, 1]"
+                            .to_string()
+                    }
+                    (true, true) => "iterations must be greater than 0".to_string(),
+                    (false, false) => "initial eta must be within [0, 1]".to_string(),
+                    (false, true) => unreachable!("valid settings falsely detected as invalid"),
+                },
             },
-            Kind::InvalidIgnoreRegion { region } => {
-                match (
-                    region.0.is_finite() && region.1.is_finite(),
-                    f64::abs(region.0 - region.1) > crate::CHECK_PRECISION,
-                ) {
-                    (false, _) => format!(
-                        "ignore region boundaries [{}, {}] contain non-finite values",
-                        region.0, region.1
-                    ),
-                    (true, false) => format!(
-                        "ignore region boundaries [{}, {}] are (almost) equal",
-                        region.0, region.1
+            Kind::InvalidIgnoreRegion { region } => invalid_region_description("ignore", region),
+            Kind::InvalidNoiseRegion { region } => invalid_region_description("noise", region),
+            Kind::InvalidBoostRegion { region, factor } => {
+                match region.0.is_finite()
+                    && region.1.is_finite()
+                    && f64::abs(region.0 - region.1) > crate::CHECK_PRECISION
+                {
+                    true => format!(
+                        "boost factor {} of region [{}, {}] is not finite or smaller than 1",
+                        factor, region.0, region.1
                     ),
-                    (true, true) => unreachable!("valid ignore region falsely detected as invalid"),
+                    false => invalid_region_description("boost", region),
                 }
             }
+            Kind::InvalidDeconvolutionRegion { region } => {
+                invalid_region_description("deconvolution", region)
+            }
+            Kind::InvalidRegionUpdate {
+                region,
+                points,
+                intensities,
+            } => match points {
+                0 => format!(
+                    "update region [{}, {}] contains no data points of the spectrum",
+                    region.0, region.1
+                ),
+                _ => format!(
+                    "update region [{}, {}] contains [{}] data points, \
+                     but [{}] intensities were provided",
+                    region.0, region.1, points, intensities
+                ),
+            },
             Kind::NoPeaksDetected => "no peaks detected in the spectrum".to_string(),
             Kind::EmptySignalRegion => {
                 "no peaks found in the signal region of the spectrum".to_string()
@@ -161,8 +412,59 @@ impl core::fmt::Display for Error {
             Kind::EmptySignalFreeRegion => {
                 "no peaks found in the signal-free region of the spectrum".to_string()
             }
+            Kind::EmptyOptimizationGrid => {
+                "optimization grid requires at least one candidate for each of \
+                 the smoothing, selection, and fitting settings"
+                    .to_string()
+            }
+            Kind::InvalidSignal { index } => format!(
+                "signal at index {} has a negative scale factor, a non-positive \
+                 half-width, or a non-finite position",
+                index
+            ),
+            Kind::SpectrumInBatch { index, source } => {
+                format!(
+                    "failed to deconvolute spectrum at index {}: {}",
+                    index, source
+                )
+            }
+            Kind::Cancelled => "the deconvolution was cancelled".to_string(),
+            Kind::UnsupportedRefinement { settings } => format!(
+                "refinement is only supported for Lorentzian signals fitted with \
+                 the analytical or Levenberg-Marquardt method ({})",
+                settings
+            ),
+            Kind::InvalidTemplate {
+                template,
+                tolerance,
+            } => match tolerance.is_finite() && *tolerance > 0.0 {
+                true => format!("template positions {:?} are not all finite", template),
+                false => format!(
+                    "template tolerance {} is not finite and positive",
+                    tolerance
+                ),
+            },
         };
 
         write!(f, "{}", description)
     }
 }
+
+/// Internal helper function to describe why the boundaries of a region, such
+/// as an ignore region, are invalid.
+fn invalid_region_description(name: &str, region: &(f64, f64)) -> String {
+    match (
+        region.0.is_finite() && region.1.is_finite(),
+        f64::abs(region.0 - region.1) > crate::CHECK_PRECISION,
+    ) {
+        (false, _) => format!(
+            "{} region boundaries [{}, {}] contain non-finite values",
+            name, region.0, region.1
+        ),
+        (true, false) => format!(
+            "{} region boundaries [{}, {}] are (almost) equal",
+            name, region.0, region.1
+        ),
+        (true, true) => unreachable!("valid {} region falsely detected as invalid", name),
+    }
+}
@@ -0,0 +1,117 @@
+use crate::spectrum::Spectrum;
+
+/// Internal helper function to convert the ignore regions to indices.
+///
+/// Ignore regions entirely outside the signal region are discarded, and the
+/// remaining ones are clamped to the signal region boundaries.
+pub(crate) fn ignore_region_indices(
+    spectrum: &Spectrum,
+    ignore_regions: &[(f64, f64)],
+) -> Vec<(usize, usize)> {
+    let step = spectrum.step();
+    let first = spectrum.chemical_shifts()[0];
+    let boundaries = spectrum.signal_boundaries();
+    let (lower_boundary, upper_boundary) = (
+        f64::min(boundaries.0, boundaries.1),
+        f64::max(boundaries.0, boundaries.1),
+    );
+    let boundary_indices = spectrum.signal_boundaries_indices();
+    let (lower, upper) = (
+        usize::min(boundary_indices.0, boundary_indices.1),
+        usize::max(boundary_indices.0, boundary_indices.1),
+    );
+
+    ignore_regions
+        .iter()
+        .filter(|(start, end)| {
+            !(*start < lower_boundary && *end < lower_boundary
+                || *start > upper_boundary && *end > upper_boundary)
+        })
+        .filter_map(|(start, end)| {
+            let first_index = usize::max(((*start - first) / step).floor() as usize, lower);
+            let second_index = usize::min(((*end - first) / step).ceil() as usize, upper);
+            let boundaries = (
+                usize::min(first_index, second_index),
+                usize::max(first_index, second_index),
+            );
+            if boundaries.0 < boundaries.1 - 1 {
+                Some(boundaries)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Internal helper function to convert a region, such as the noise region, to
+/// indices.
+///
+/// The region is clamped to the range of the chemical shifts.
+pub(crate) fn region_indices(spectrum: &Spectrum, region: (f64, f64)) -> (usize, usize) {
+    let step = spectrum.step();
+    let first = spectrum.chemical_shifts()[0];
+    let positions = ((region.0 - first) / step, (region.1 - first) / step);
+    let (lower, upper) = (
+        f64::min(positions.0, positions.1).floor(),
+        f64::max(positions.0, positions.1).ceil(),
+    );
+
+    (
+        usize::min(lower.max(0.0) as usize, spectrum.len() - 1),
+        usize::min(upper.max(0.0) as usize, spectrum.len() - 1),
+    )
+}
+
+/// Internal helper function to split the signal region into the index ranges
+/// used to evaluate the quality of a deconvolution, excluding the ignore
+/// regions.
+pub(crate) fn evaluation_regions(
+    spectrum: &Spectrum,
+    ignore_regions: Option<&[(usize, usize)]>,
+) -> Vec<(usize, usize)> {
+    match ignore_regions {
+        Some(ignore_regions) => {
+            let iter = std::iter::once(spectrum.signal_boundaries_indices().0)
+                .chain(
+                    ignore_regions
+                        .iter()
+                        .flat_map(|(start, end)| vec![*start, *end]),
+                )
+                .chain(std::iter::once(spectrum.signal_boundaries_indices().1));
+
+            iter.clone()
+                .step_by(2)
+                .zip(iter.skip(1).step_by(2))
+                .collect::<Vec<(usize, usize)>>()
+        }
+        None => vec![spectrum.signal_boundaries_indices()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_signal_region() {
+        let chemical_shifts = (0..11).map(|i| i as f64).collect::<Vec<_>>();
+        let intensities = vec![1.0; 11];
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        let ignore_regions = ignore_region_indices(&spectrum, &[(3.5, 5.5), (20.0, 30.0)]);
+        assert_eq!(ignore_regions, vec![(3, 6)]);
+        assert_eq!(evaluation_regions(&spectrum, None), vec![(1, 9)]);
+        assert_eq!(
+            evaluation_regions(&spectrum, Some(&ignore_regions)),
+            vec![(1, 3), (6, 9)]
+        );
+        assert_eq!(region_indices(&spectrum, (9.5, 7.5)), (7, 10));
+        assert_eq!(region_indices(&spectrum, (-5.0, 2.5)), (0, 3));
+        let reversed = Spectrum::new(
+            (0..11).rev().map(|i| i as f64).collect(),
+            vec![1.0; 11],
+            (1.0, 9.0),
+        )
+        .unwrap();
+        assert_eq!(region_indices(&reversed, (9.5, 7.5)), (0, 3));
+    }
+}
@@ -0,0 +1,385 @@
+use crate::deconvolution::Deconvolution;
+use crate::deconvolution::fitting::FittingSettings;
+use crate::deconvolution::gaussian::Gaussian;
+use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::peak_selection::SelectionSettings;
+use crate::deconvolution::peak_shape::{PeakShape, component_curves, superposition_vec};
+use crate::deconvolution::pseudo_voigt::PseudoVoigt;
+use crate::deconvolution::signal::Signal;
+use crate::deconvolution::smoothing::SmoothingSettings;
+use crate::spectrum::Spectrum;
+use std::sync::Arc;
+
+#[cfg(feature = "parallel")]
+use crate::deconvolution::peak_shape::{par_component_curves, par_superposition_vec};
+
+/// Deconvoluted signals, stored as the peak shape they were fitted with.
+///
+/// All signals of a [`Deconvolution`] share the same peak shape, which depends
+/// on the fitting method used. `FittedSignals` stores them as a slice of the
+/// respective type, which can be accessed by matching on the variants, and
+/// provides shape-agnostic access to the individual signals as [`Signal`]s.
+///
+/// # Thread Safety
+///
+/// The `FittedSignals` type is both [`Send`] and [`Sync`], allowing safe
+/// sharing and access across threads.
+///
+/// # Example
+///
+/// ```
+/// use float_cmp::assert_approx_eq;
+/// use metabodecon::deconvolution::{
+///     Deconvolution, FittedSignals, FittingSettings, Gaussian, PeakShape, SelectionSettings,
+///     SmoothingSettings,
+/// };
+///
+/// let deconvolution = Deconvolution::from_gaussians(
+///     vec![Gaussian::new(2.0, 0.15, 3.0), Gaussian::new(1.0, 0.1, 5.0)],
+///     SmoothingSettings::default(),
+///     SelectionSettings::default(),
+///     FittingSettings::Gaussian { iterations: 10 },
+///     0.5,
+/// );
+/// let signals = deconvolution.signals();
+///
+/// assert_eq!(signals.len(), 2);
+/// assert_approx_eq!(f64, signals.get(1).unwrap().maxp(), 5.0);
+/// match signals {
+///     FittedSignals::Gaussian(gaussians) => assert_approx_eq!(f64, gaussians[0].sf(), 2.0),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum FittedSignals {
+    /// Signals fitted as [`Lorentzian`]s.
+    Lorentzian(Arc<[Lorentzian]>),
+    /// Signals fitted as [`Gaussian`]s.
+    Gaussian(Arc<[Gaussian]>),
+    /// Signals fitted as [`PseudoVoigt`]s.
+    PseudoVoigt(Arc<[PseudoVoigt]>),
+}
+
+impl<'a> IntoIterator for &'a FittedSignals {
+    type Item = Signal;
+    type IntoIter = SignalIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FittedSignals {
+    /// Returns the number of signals.
+    pub fn len(&self) -> usize {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => lorentzians.len(),
+            FittedSignals::Gaussian(gaussians) => gaussians.len(),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => pseudo_voigts.len(),
+        }
+    }
+
+    /// Returns `true` if there are no signals.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the signal at the given index, or `None` if the index is out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<Signal> {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => lorentzians
+                .get(index)
+                .map(|lorentzian| Signal::Lorentzian(*lorentzian)),
+            FittedSignals::Gaussian(gaussians) => gaussians
+                .get(index)
+                .map(|gaussian| Signal::Gaussian(*gaussian)),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => pseudo_voigts
+                .get(index)
+                .map(|pseudo_voigt| Signal::PseudoVoigt(*pseudo_voigt)),
+        }
+    }
+
+    /// Returns an iterator over the signals.
+    pub fn iter(&self) -> SignalIter<'_> {
+        SignalIter {
+            signals: self,
+            index: 0,
+        }
+    }
+
+    /// Internal helper function to construct signals of the same peak shape
+    /// from the given signals. Signals of other peak shapes are discarded.
+    pub(crate) fn with_signals(&self, signals: impl IntoIterator<Item = Signal>) -> Self {
+        let signals = signals.into_iter();
+        match self {
+            FittedSignals::Lorentzian(_) => FittedSignals::Lorentzian(
+                signals
+                    .filter_map(|signal| match signal {
+                        Signal::Lorentzian(lorentzian) => Some(lorentzian),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            FittedSignals::Gaussian(_) => FittedSignals::Gaussian(
+                signals
+                    .filter_map(|signal| match signal {
+                        Signal::Gaussian(gaussian) => Some(gaussian),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            FittedSignals::PseudoVoigt(_) => FittedSignals::PseudoVoigt(
+                signals
+                    .filter_map(|signal| match signal {
+                        Signal::PseudoVoigt(pseudo_voigt) => Some(pseudo_voigt),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Internal helper function to construct a signal of the same peak shape
+    /// with the given half-width and position and an area of zero, used as a
+    /// placeholder for missing signals.
+    pub(crate) fn placeholder(&self, hw: f64, maxp: f64) -> Signal {
+        match self {
+            FittedSignals::Lorentzian(_) => Lorentzian::new(0.0, hw.powi(2), maxp).into(),
+            FittedSignals::Gaussian(_) => Gaussian::new(0.0, hw, maxp).into(),
+            FittedSignals::PseudoVoigt(_) => PseudoVoigt::new(0.5, hw, maxp, 0.0).into(),
+        }
+    }
+
+    /// Returns the number of free parameters of each signal of this peak
+    /// shape.
+    pub(crate) fn parameters_per_signal(&self) -> usize {
+        match self {
+            FittedSignals::Lorentzian(_) | FittedSignals::Gaussian(_) => 3,
+            FittedSignals::PseudoVoigt(_) => 4,
+        }
+    }
+
+    /// Evaluates the superposition of the signals at the given positions `x`.
+    pub(crate) fn superposition_vec(&self, x: &[f64]) -> Vec<f64> {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => Lorentzian::superposition_vec(x, lorentzians),
+            FittedSignals::Gaussian(gaussians) => superposition_vec(x, gaussians),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => superposition_vec(x, pseudo_voigts),
+        }
+    }
+
+    /// Evaluates the superposition of the signals at the given positions `x`
+    /// in parallel.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_superposition_vec(&self, x: &[f64]) -> Vec<f64> {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => {
+                Lorentzian::par_superposition_vec(x, lorentzians)
+            }
+            FittedSignals::Gaussian(gaussians) => par_superposition_vec(x, gaussians),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => par_superposition_vec(x, pseudo_voigts),
+        }
+    }
+
+    /// Evaluates each signal separately at the given positions `x`.
+    pub(crate) fn component_curves(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => component_curves(x, lorentzians),
+            FittedSignals::Gaussian(gaussians) => component_curves(x, gaussians),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => component_curves(x, pseudo_voigts),
+        }
+    }
+
+    /// Evaluates each signal separately at the given positions `x` in
+    /// parallel.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_component_curves(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => par_component_curves(x, lorentzians),
+            FittedSignals::Gaussian(gaussians) => par_component_curves(x, gaussians),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => par_component_curves(x, pseudo_voigts),
+        }
+    }
+
+    /// Keeps only the signals that are located outside the given region of
+    /// indices into the spectrum.
+    pub(crate) fn outside(&self, spectrum: &Spectrum, region: (usize, usize)) -> Self {
+        self.with_signals(
+            self.iter()
+                .filter(|signal| is_outside(spectrum, signal, region)),
+        )
+    }
+
+    /// Adds the signals of the previous [`Deconvolution`] that are located
+    /// outside the given region of indices into the spectrum, and orders all
+    /// signals by their position. The previous [`Deconvolution`] must have been
+    /// fitted with the same peak shape.
+    pub(crate) fn with_previous_outside(
+        self,
+        spectrum: &Spectrum,
+        previous: &Deconvolution,
+        region: (usize, usize),
+    ) -> Self {
+        match self {
+            FittedSignals::Lorentzian(lorentzians) => FittedSignals::Lorentzian(merge_outside(
+                spectrum,
+                lorentzians.to_vec(),
+                previous.lorentzians(),
+                region,
+            )),
+            FittedSignals::Gaussian(gaussians) => FittedSignals::Gaussian(merge_outside(
+                spectrum,
+                gaussians.to_vec(),
+                previous.gaussians(),
+                region,
+            )),
+            FittedSignals::PseudoVoigt(pseudo_voigts) => FittedSignals::PseudoVoigt(merge_outside(
+                spectrum,
+                pseudo_voigts.to_vec(),
+                previous.pseudo_voigts(),
+                region,
+            )),
+        }
+    }
+
+    /// Wraps the signals into a [`Deconvolution`] with the given settings and
+    /// mean squared error.
+    pub(crate) fn into_deconvolution(
+        self,
+        smoothing_settings: SmoothingSettings,
+        selection_settings: SelectionSettings,
+        fitting_settings: FittingSettings,
+        mse: f64,
+    ) -> Deconvolution {
+        Deconvolution::from_signals(
+            self,
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+            mse,
+        )
+    }
+}
+
+/// Iterator over the [`Signal`]s of [`FittedSignals`].
+///
+/// Returned by [`FittedSignals::iter`] and by iterating over a reference to a
+/// [`Deconvolution`].
+#[derive(Clone, Debug)]
+pub struct SignalIter<'a> {
+    /// Signals iterated over.
+    signals: &'a FittedSignals,
+    /// Index of the next signal.
+    index: usize,
+}
+
+impl Iterator for SignalIter<'_> {
+    type Item = Signal;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let signal = self.signals.get(self.index)?;
+        self.index += 1;
+
+        Some(signal)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.signals.len() - self.index;
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for SignalIter<'_> {}
+
+/// Internal helper function to compute the position of the maximum of the
+/// given signal in terms of (fractional) indices into the spectrum.
+fn index_position<P: PeakShape>(spectrum: &Spectrum, signal: &P) -> f64 {
+    (signal.maxp() - spectrum.chemical_shifts()[0]) / spectrum.step()
+}
+
+/// Internal helper function to check whether the maximum of the given signal
+/// is closest to a point outside the given region of indices into the
+/// spectrum.
+fn is_outside<P: PeakShape>(spectrum: &Spectrum, signal: &P, region: (usize, usize)) -> bool {
+    let index = index_position(spectrum, signal).round();
+
+    index < region.0 as f64 || index >= region.1 as f64
+}
+
+/// Internal helper function to add the previous signals whose maximum is
+/// closest to a point outside the given region of indices into the spectrum,
+/// and to sort the signals by their position.
+fn merge_outside<P: PeakShape>(
+    spectrum: &Spectrum,
+    mut signals: Vec<P>,
+    previous: &[P],
+    region: (usize, usize),
+) -> Arc<[P]> {
+    signals.extend(
+        previous
+            .iter()
+            .copied()
+            .filter(|signal| is_outside(spectrum, signal, region)),
+    );
+    signals.sort_by(|a, b| index_position(spectrum, a).total_cmp(&index_position(spectrum, b)));
+
+    signals.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(FittedSignals, SignalIter<'_>);
+        assert_sync!(FittedSignals, SignalIter<'_>);
+    }
+
+    #[test]
+    fn collection_access() {
+        let gaussians = [Gaussian::new(2.0, 0.15, 3.0), Gaussian::new(1.0, 0.1, 5.0)];
+        let signals = FittedSignals::Gaussian(gaussians.into());
+        assert_eq!(signals.len(), 2);
+        assert!(!signals.is_empty());
+        assert!(signals.get(2).is_none());
+        assert_eq!(signals.iter().len(), 2);
+        signals
+            .iter()
+            .zip(gaussians.iter())
+            .for_each(|(signal, gaussian)| match signal {
+                Signal::Gaussian(signal) => {
+                    assert_approx_eq!(f64, signal.sf(), gaussian.sf());
+                    assert_approx_eq!(f64, signal.hw(), gaussian.hw());
+                    assert_approx_eq!(f64, signal.maxp(), gaussian.maxp());
+                }
+                _ => panic!("expected a Gaussian"),
+            });
+        let empty = FittedSignals::Lorentzian(Arc::new([]));
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().count(), 0);
+    }
+
+    #[test]
+    fn with_signals() {
+        let signals = FittedSignals::PseudoVoigt(Arc::new([]));
+        let rebuilt = signals.with_signals([
+            Signal::from(PseudoVoigt::new(0.5, 0.15, 3.0, 2.0)),
+            Signal::from(Lorentzian::new(0.045, 0.0225, 5.0)),
+            signals.placeholder(0.1, 7.0),
+        ]);
+        match rebuilt {
+            FittedSignals::PseudoVoigt(pseudo_voigts) => {
+                assert_eq!(pseudo_voigts.len(), 2);
+                assert_approx_eq!(f64, pseudo_voigts[1].integral(), 0.0);
+                assert_approx_eq!(f64, pseudo_voigts[1].maxp(), 7.0);
+            }
+            _ => panic!("expected pseudo-Voigt functions"),
+        }
+    }
+}
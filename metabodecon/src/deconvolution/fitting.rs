@@ -1,9 +1,27 @@
+mod fit_diagnostics;
+pub use fit_diagnostics::FitDiagnostics;
+
+mod parameter_uncertainty;
+pub use parameter_uncertainty::ParameterUncertainty;
+
 mod fitter;
-pub(crate) use fitter::Fitter;
 pub use fitter::FittingSettings;
+pub(crate) use fitter::{Fitter, is_cancelled, peaks_from_lorentzians, unless_cancelled};
 
 mod fitter_analytical;
-pub(crate) use fitter_analytical::FitterAnalytical;
+pub use fitter_analytical::FitterAnalytical;
+
+mod fitter_gaussian;
+pub use fitter_gaussian::FitterGaussian;
+
+mod fitter_levenberg_marquardt;
+pub use fitter_levenberg_marquardt::FitterLevenbergMarquardt;
+
+mod fitter_pseudo_voigt;
+pub use fitter_pseudo_voigt::FitterPseudoVoigt;
+
+mod linear_system;
+pub(crate) use linear_system::solve_linear_system;
 
 mod peak_stencil;
 pub(crate) use peak_stencil::PeakStencil;
@@ -0,0 +1,97 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Diagnostics of the fit of a single signal.
+///
+/// Fitting methods that support diagnostics report them for each fitted
+/// signal, in the same order as the signals of the [`Deconvolution`]. See
+/// [`Deconvolution::fit_diagnostics`].
+///
+/// For [`FittingSettings::Analytical`], the residual is computed at the 3
+/// points that represent the peak, as the largest absolute difference between
+/// the intensities of the [`Spectrum`] and the superposition of all fitted
+/// signals, relative to the largest of the 3 intensities. The fit of a signal
+/// is considered converged if the residual is below
+/// [`FitDiagnostics::CONVERGENCE_TOLERANCE`].
+///
+/// [`Deconvolution`]: crate::deconvolution::Deconvolution
+/// [`Deconvolution::fit_diagnostics`]: crate::deconvolution::Deconvolution::fit_diagnostics
+/// [`FittingSettings::Analytical`]: crate::deconvolution::FittingSettings::Analytical
+/// [`Spectrum`]: crate::spectrum::Spectrum
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct FitDiagnostics {
+    /// Number of refinement iterations performed.
+    iterations: usize,
+    /// Relative residual after the last iteration.
+    residual: f64,
+    /// Whether the residual is below the convergence tolerance.
+    converged: bool,
+    /// Whether a parameter was clamped to its bound during the refinement.
+    hit_bound: bool,
+}
+
+impl FitDiagnostics {
+    /// Relative residual below which the fit of a signal is considered
+    /// converged.
+    pub const CONVERGENCE_TOLERANCE: f64 = 1e-2;
+
+    /// Internal helper function to construct diagnostics from the iterations
+    /// performed, the final relative residual, and whether a parameter hit its
+    /// bound. Convergence is determined from the residual.
+    pub(crate) fn new(iterations: usize, residual: f64, hit_bound: bool) -> Self {
+        Self {
+            iterations,
+            residual,
+            converged: residual < Self::CONVERGENCE_TOLERANCE,
+            hit_bound,
+        }
+    }
+
+    /// Returns the number of refinement iterations performed.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the relative residual of the fit after the last iteration.
+    pub fn residual(&self) -> f64 {
+        self.residual
+    }
+
+    /// Returns `true` if the residual is below the
+    /// [`CONVERGENCE_TOLERANCE`](FitDiagnostics::CONVERGENCE_TOLERANCE).
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns `true` if a parameter was clamped to its bound during the
+    /// refinement, e.g. a half-width that collapsed to zero. Such signals are
+    /// often artifacts of noise or of strongly overlapping peaks.
+    pub fn hit_bound(&self) -> bool {
+        self.hit_bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(FitDiagnostics);
+        assert_sync!(FitDiagnostics);
+    }
+
+    #[test]
+    fn converged() {
+        let tolerance = FitDiagnostics::CONVERGENCE_TOLERANCE;
+        assert!(FitDiagnostics::new(10, 0.1 * tolerance, false).converged());
+        assert!(!FitDiagnostics::new(10, 10.0 * tolerance, false).converged());
+        assert!(!FitDiagnostics::new(10, f64::NAN, true).converged());
+    }
+}
@@ -1,27 +1,146 @@
 use crate::Settings;
 use crate::deconvolution::error::{Error, Kind};
+use crate::deconvolution::fitted_signals::FittedSignals;
+use crate::deconvolution::fitting::{FitDiagnostics, ParameterUncertainty};
 use crate::deconvolution::lorentzian::Lorentzian;
 use crate::deconvolution::peak_selection::Peak;
 use crate::spectrum::Spectrum;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Trait interface for fitting algorithms.
 pub(crate) trait Fitter: Send + Sync + std::fmt::Debug {
-    /// Fits Lorentzian functions to a spectrum using the given peaks.
-    fn fit_lorentzian(&self, spectrum: &Spectrum, peaks: &[Peak]) -> Vec<Lorentzian>;
+    /// Fits peak shapes to a spectrum using the given peaks.
+    fn fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals;
 
-    /// Fits Lorentzian functions to a spectrum using the given peaks in
-    /// parallel.
+    /// Fits peak shapes to a spectrum using the given peaks in parallel.
     #[cfg(feature = "parallel")]
-    fn par_fit_lorentzian(&self, spectrum: &Spectrum, peaks: &[Peak]) -> Vec<Lorentzian>;
+    fn par_fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals;
+
+    /// Fits peak shapes to a spectrum using the given peaks and reports the
+    /// diagnostics of the fit of each signal, if supported by the fitter.
+    fn fit_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (FittedSignals, Option<Vec<FitDiagnostics>>) {
+        (self.fit(spectrum, peaks), None)
+    }
+
+    /// Fits peak shapes to a spectrum using the given peaks in parallel and
+    /// reports the diagnostics of the fit of each signal, if supported by the
+    /// fitter.
+    #[cfg(feature = "parallel")]
+    fn par_fit_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (FittedSignals, Option<Vec<FitDiagnostics>>) {
+        (self.par_fit(spectrum, peaks), None)
+    }
+
+    /// Fits peak shapes to a spectrum using the given peaks and reports the
+    /// diagnostics of the fit of each signal, if supported by the fitter.
+    ///
+    /// The cancellation flag is checked between the iterations of the fit,
+    /// and [`Kind::Cancelled`] is returned once it is set.
+    fn fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> crate::Result<(FittedSignals, Option<Vec<FitDiagnostics>>)>;
+
+    /// Fits peak shapes to a spectrum using the given peaks in parallel and
+    /// reports the diagnostics of the fit of each signal, if supported by the
+    /// fitter.
+    ///
+    /// The cancellation flag is checked between the iterations of the fit,
+    /// and [`Kind::Cancelled`] is returned once it is set.
+    #[cfg(feature = "parallel")]
+    fn par_fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> crate::Result<(FittedSignals, Option<Vec<FitDiagnostics>>)>;
+
+    /// Refines the given [`Lorentzian`]s, which are used as the starting
+    /// parameters instead of the initial guess derived from detected peaks,
+    /// and reports the diagnostics of the fit of each signal, if supported by
+    /// the fitter. Returns `None` if the fitter doesn't fit [`Lorentzian`]s.
+    fn refine(
+        &self,
+        _spectrum: &Spectrum,
+        _lorentzians: &[Lorentzian],
+    ) -> Option<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        None
+    }
+
+    /// Estimates the standard errors of the parameters of the given fitted
+    /// signals, in the same order as the signals. Returns `None` if the fitter
+    /// doesn't support uncertainty estimates.
+    fn parameter_uncertainties(
+        &self,
+        _spectrum: &Spectrum,
+        _signals: &FittedSignals,
+    ) -> Option<Vec<ParameterUncertainty>> {
+        None
+    }
 
     /// Returns the settings of the trait object.
     fn settings(&self) -> FittingSettings;
 }
 
-/// Lorentzian function fitting settings for configuring the [`Deconvoluter`].
+/// Internal helper function to check whether the optional cancellation flag
+/// of a fit is set.
+pub(crate) fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+}
+
+/// Internal helper function to discard the result of a fit that was stopped
+/// early because the cancellation flag was set.
+pub(crate) fn unless_cancelled<T>(fit: T, cancel: &AtomicBool) -> crate::Result<T> {
+    match cancel.load(Ordering::Relaxed) {
+        true => Err(Error::new(Kind::Cancelled).into()),
+        false => Ok(fit),
+    }
+}
+
+/// Internal helper function to construct the peaks that represent the given
+/// [`Lorentzian`]s, for refining them with a fitter. The center of each peak is
+/// the data point closest to the maximum position, and the left and right
+/// boundaries are the data points about one half-width away from it. The
+/// peaks and [`Lorentzian`]s are returned in the order of the data points.
+pub(crate) fn peaks_from_lorentzians(
+    spectrum: &Spectrum,
+    lorentzians: &[Lorentzian],
+) -> (Vec<Peak>, Vec<Lorentzian>) {
+    let first = spectrum.chemical_shifts()[0];
+    let step = spectrum.step();
+    let last = spectrum.len() - 1;
+    let mut signals = lorentzians
+        .iter()
+        .map(|lorentzian| {
+            let position = ((lorentzian.maxp() - first) / step).round();
+            let center = usize::max(usize::min(position.max(0.0) as usize, last - 1), 1);
+            let offset = usize::max((lorentzian.hw() / step.abs()).round() as usize, 1);
+            let peak = Peak::new(
+                center - usize::min(offset, center),
+                center,
+                usize::min(center + offset, last),
+            );
+            (peak, *lorentzian)
+        })
+        .collect::<Vec<_>>();
+    signals.sort_by_key(|(peak, _)| peak.center());
+
+    signals.into_iter().unzip()
+}
+
+/// Peak shape fitting settings for configuring the [`Deconvoluter`].
 ///
 /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
 #[non_exhaustive]
@@ -50,10 +169,86 @@ pub enum FittingSettings {
     /// 2. Updates the extracted intensities of the peak points by multiplying
     ///    them by the ratio.
     /// 3. Solves system of equations for the parameters of the [`Lorentzian`]s.
+    ///
+    /// As the fit is not a least squares solution, the standard errors of the
+    /// parameters are not estimated. Use [`LevenbergMarquardt`] if they are
+    /// needed.
+    ///
+    /// [`LevenbergMarquardt`]: FittingSettings::LevenbergMarquardt
     Analytical {
         /// The number of iterations to refine the fit.
         iterations: usize,
     },
+    /// Fitting of [`Gaussian`]s by interpolating the logarithm of the peaks.
+    ///
+    /// The logarithm of a [`Gaussian`] is a parabola, so the parameters of
+    /// the [`Gaussian`]s for each peak can be found by solving the system of 3
+    /// equations
+    /// ```text
+    /// ln(y_1) = ln(sf) - ln(2) * (x_1 - maxp)^2 / hw^2
+    /// ln(y_2) = ln(sf) - ln(2) * (x_2 - maxp)^2 / hw^2
+    /// ln(y_3) = ln(sf) - ln(2) * (x_3 - maxp)^2 / hw^2
+    /// ```
+    ///
+    /// The fit is refined iteratively in the same way as for the
+    /// [`Analytical`] method, using the superposition of the [`Gaussian`]s.
+    ///
+    /// [`Analytical`]: FittingSettings::Analytical
+    /// [`Gaussian`]: crate::deconvolution::Gaussian
+    Gaussian {
+        /// The number of iterations to refine the fit.
+        iterations: usize,
+    },
+    /// Fitting of [`Lorentzian`]s by refining the analytical solution with the
+    /// Levenberg-Marquardt algorithm.
+    ///
+    /// The [`Analytical`] fitting method only considers 3 data points per
+    /// peak, which may not be sufficient for strongly overlapping multiplets.
+    /// This method uses its result as initial guess and then minimizes the sum
+    /// of squared residuals over the data points around the peaks as follows:
+    /// 1. Groups overlapping [`Lorentzian`]s into signal regions.
+    /// 2. Updates the parameters of all [`Lorentzian`]s within each signal
+    ///    region simultaneously with a damped Gauss-Newton step.
+    /// 3. Decreases the damping factor if the step reduced the residuals,
+    ///    otherwise increases it and retries the step.
+    ///
+    /// The refinement stops once the relative improvement of the residuals is
+    /// below `tolerance` or after `max_iterations`. As the least squares
+    /// solution is found, the standard errors of the parameters are estimated
+    /// from the Jacobian as well, see [`ParameterUncertainty`].
+    ///
+    /// [`Analytical`]: FittingSettings::Analytical
+    /// [`ParameterUncertainty`]: crate::deconvolution::ParameterUncertainty
+    LevenbergMarquardt {
+        /// The maximum number of iterations.
+        max_iterations: usize,
+        /// The relative improvement of the residuals below which the
+        /// refinement stops.
+        tolerance: f64,
+        /// The initial damping factor.
+        lambda_init: f64,
+    },
+    /// Fitting of [`PseudoVoigt`]s by damped least squares.
+    ///
+    /// The pseudo-Voigt function mixes a Lorentzian and a Gaussian with the
+    /// mixing parameter `eta`, which is optimized together with the other
+    /// parameters. Since the 4 parameters can't be determined from the 3
+    /// points that represent each peak, the initial guess is obtained from the
+    /// analytical Lorentzian solution and then refined iteratively as follows:
+    /// 1. For each peak, subtracts the contributions of all other peaks from
+    ///    the spectrum within the peak region.
+    /// 2. Performs a Levenberg-Marquardt step on the parameters of the peak to
+    ///    reduce the squared residuals within the peak region.
+    /// 3. Updates the superposition before moving on to the next peak.
+    ///
+    /// [`PseudoVoigt`]: crate::deconvolution::PseudoVoigt
+    PseudoVoigt {
+        /// The number of iterations to refine the fit.
+        iterations: usize,
+        /// The initial value of the mixing parameter, between 0 (Gaussian) and
+        /// 1 (Lorentzian).
+        eta_init: f64,
+    },
 }
 
 impl Default for FittingSettings {
@@ -72,6 +267,30 @@ impl std::fmt::Display for FittingSettings {
                     iterations
                 )
             }
+            FittingSettings::Gaussian { iterations } => {
+                write!(f, "Gaussian Fitter [number of iterations: {}]", iterations)
+            }
+            FittingSettings::LevenbergMarquardt {
+                max_iterations,
+                tolerance,
+                lambda_init,
+            } => {
+                write!(
+                    f,
+                    "Levenberg-Marquardt Fitter [maximum number of iterations: {}, tolerance: {}, initial damping factor: {}]",
+                    max_iterations, tolerance, lambda_init
+                )
+            }
+            FittingSettings::PseudoVoigt {
+                iterations,
+                eta_init,
+            } => {
+                write!(
+                    f,
+                    "Pseudo-Voigt Fitter [number of iterations: {}, initial eta: {}]",
+                    iterations, eta_init
+                )
+            }
         }
     }
 }
@@ -79,11 +298,32 @@ impl std::fmt::Display for FittingSettings {
 impl Settings for FittingSettings {
     fn validate(&self) -> crate::Result<()> {
         match self {
-            FittingSettings::Analytical { iterations } => {
+            FittingSettings::Analytical { iterations }
+            | FittingSettings::Gaussian { iterations } => {
                 if *iterations == 0 {
                     return Err(Error::new(Kind::InvalidFittingSettings { settings: *self }).into());
                 }
             }
+            FittingSettings::LevenbergMarquardt {
+                max_iterations,
+                tolerance,
+                lambda_init,
+            } => {
+                if *max_iterations == 0
+                    || !(tolerance.is_finite() && *tolerance >= 0.0)
+                    || !(lambda_init.is_finite() && *lambda_init > 0.0)
+                {
+                    return Err(Error::new(Kind::InvalidFittingSettings { settings: *self }).into());
+                }
+            }
+            FittingSettings::PseudoVoigt {
+                iterations,
+                eta_init,
+            } => {
+                if *iterations == 0 || !(0.0..=1.0).contains(eta_init) {
+                    return Err(Error::new(Kind::InvalidFittingSettings { settings: *self }).into());
+                }
+            }
         }
 
         Ok(())
@@ -100,6 +340,41 @@ impl Settings for FittingSettings {
                     iterations: iterations2,
                 },
             ) => *iterations1 == *iterations2,
+            (
+                FittingSettings::Gaussian {
+                    iterations: iterations1,
+                },
+                FittingSettings::Gaussian {
+                    iterations: iterations2,
+                },
+            ) => *iterations1 == *iterations2,
+            (
+                FittingSettings::LevenbergMarquardt {
+                    max_iterations: max_iterations1,
+                    tolerance: tolerance1,
+                    lambda_init: lambda_init1,
+                },
+                FittingSettings::LevenbergMarquardt {
+                    max_iterations: max_iterations2,
+                    tolerance: tolerance2,
+                    lambda_init: lambda_init2,
+                },
+            ) => {
+                *max_iterations1 == *max_iterations2
+                    && float_cmp::approx_eq!(f64, *tolerance1, *tolerance2)
+                    && float_cmp::approx_eq!(f64, *lambda_init1, *lambda_init2)
+            }
+            (
+                FittingSettings::PseudoVoigt {
+                    iterations: iterations1,
+                    eta_init: eta_init1,
+                },
+                FittingSettings::PseudoVoigt {
+                    iterations: iterations2,
+                    eta_init: eta_init2,
+                },
+            ) => *iterations1 == *iterations2 && float_cmp::approx_eq!(f64, *eta_init1, *eta_init2),
+            _ => false,
         }
     }
 }
@@ -1,24 +1,191 @@
-use crate::deconvolution::fitting::{Fitter, FittingSettings, PeakStencil, ReducedSpectrum};
+use crate::deconvolution::fitted_signals::FittedSignals;
+use crate::deconvolution::fitting::{
+    FitDiagnostics, Fitter, FittingSettings, PeakStencil, ReducedSpectrum, is_cancelled,
+    peaks_from_lorentzians, unless_cancelled,
+};
 use crate::deconvolution::lorentzian::Lorentzian;
 use crate::deconvolution::peak_selection::Peak;
 use crate::spectrum::Spectrum;
+use crate::{Result, Settings};
+use std::sync::atomic::AtomicBool;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 /// Fitting algorithm based on the analytical solution of a system of equations
 /// using a 3-point peak stencil.
-#[derive(Debug)]
-pub(crate) struct FitterAnalytical {
+///
+/// Equivalent to [`FittingSettings::Analytical`] for the fitting step of a
+/// [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct FitterAnalytical {
     /// The number of iterations to refine the Lorentzian parameters.
     iterations: usize,
 }
 
 impl Fitter for FitterAnalytical {
+    fn fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::Lorentzian(self.fit_lorentzian(spectrum, peaks).into())
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::Lorentzian(self.par_fit_lorentzian(spectrum, peaks).into())
+    }
+
+    fn fit_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (FittedSignals, Option<Vec<FitDiagnostics>>) {
+        let (lorentzians, diagnostics) = self.fit_lorentzian_with_diagnostics(spectrum, peaks);
+
+        (
+            FittedSignals::Lorentzian(lorentzians.into()),
+            Some(diagnostics),
+        )
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (FittedSignals, Option<Vec<FitDiagnostics>>) {
+        let (lorentzians, diagnostics) = self.par_fit_lorentzian_with_diagnostics(spectrum, peaks);
+
+        (
+            FittedSignals::Lorentzian(lorentzians.into()),
+            Some(diagnostics),
+        )
+    }
+
+    fn fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let fit = self.fit_lorentzian_unfiltered(spectrum, peaks, Some(cancel));
+        let (lorentzians, diagnostics) = unless_cancelled(fit, cancel)?;
+        let (lorentzians, diagnostics) =
+            Self::retain_valid_with_diagnostics(lorentzians, diagnostics);
+
+        Ok((
+            FittedSignals::Lorentzian(lorentzians.into()),
+            Some(diagnostics),
+        ))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let fit = self.par_fit_lorentzian_unfiltered(spectrum, peaks, Some(cancel));
+        let (lorentzians, diagnostics) = unless_cancelled(fit, cancel)?;
+        let (lorentzians, diagnostics) =
+            Self::retain_valid_with_diagnostics(lorentzians, diagnostics);
+
+        Ok((
+            FittedSignals::Lorentzian(lorentzians.into()),
+            Some(diagnostics),
+        ))
+    }
+
+    fn refine(
+        &self,
+        spectrum: &Spectrum,
+        lorentzians: &[Lorentzian],
+    ) -> Option<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let (lorentzians, diagnostics) =
+            self.refine_lorentzian_with_diagnostics(spectrum, lorentzians);
+
+        Some((
+            FittedSignals::Lorentzian(lorentzians.into()),
+            Some(diagnostics),
+        ))
+    }
+
+    fn settings(&self) -> FittingSettings {
+        FittingSettings::Analytical {
+            iterations: self.iterations,
+        }
+    }
+}
+
+impl FitterAnalytical {
+    /// Constructs a new `FitterAnalytical` with the given number of iterations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of iterations is 0.
+    pub fn new(iterations: usize) -> Result<Self> {
+        FittingSettings::Analytical { iterations }.validate()?;
+
+        Ok(Self { iterations })
+    }
+
     /// Fits a set of Lorentzians to the spectrum using the given peaks.
-    fn fit_lorentzian(&self, spectrum: &Spectrum, peaks: &[Peak]) -> Vec<Lorentzian> {
+    pub(crate) fn fit_lorentzian(&self, spectrum: &Spectrum, peaks: &[Peak]) -> Vec<Lorentzian> {
+        self.fit_lorentzian_with_diagnostics(spectrum, peaks)
+            .0
+    }
+
+    /// Fits a set of Lorentzians to the spectrum using the given peaks in
+    /// parallel.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_fit_lorentzian(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> Vec<Lorentzian> {
+        self.par_fit_lorentzian_with_diagnostics(spectrum, peaks)
+            .0
+    }
+
+    /// Fits a set of Lorentzians to the spectrum using the given peaks and
+    /// reports the diagnostics of the fit of each Lorentzian.
+    pub(crate) fn fit_lorentzian_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
+        let (lorentzians, diagnostics) = self.fit_lorentzian_unfiltered(spectrum, peaks, None);
+
+        Self::retain_valid_with_diagnostics(lorentzians, diagnostics)
+    }
+
+    /// Fits a set of Lorentzians to the spectrum using the given peaks in
+    /// parallel and reports the diagnostics of the fit of each Lorentzian.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_fit_lorentzian_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
+        let (lorentzians, diagnostics) = self.par_fit_lorentzian_unfiltered(spectrum, peaks, None);
+
+        Self::retain_valid_with_diagnostics(lorentzians, diagnostics)
+    }
+
+    /// Fits a set of Lorentzians to the spectrum using the given peaks without
+    /// removing invalid Lorentzians, such that the result and the diagnostics
+    /// correspond to the peaks one to one.
+    ///
+    /// If the optional cancellation flag is set, the iterations stop early.
+    pub(crate) fn fit_lorentzian_unfiltered(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
         let reduced_spectrum = ReducedSpectrum::new(spectrum, peaks);
-        let mut peak_data = peaks
+        let peak_data = peaks
             .iter()
             .map(|peak| {
                 let mut stencil = PeakStencil::new(spectrum, peak);
@@ -26,17 +193,69 @@ impl Fitter for FitterAnalytical {
                 stencil
             })
             .collect::<Vec<_>>();
-        let mut lorentzians = peak_data
+        let mut hit_bound = vec![false; peaks.len()];
+        let lorentzians = peak_data
             .iter()
-            .map(|peak| {
+            .zip(hit_bound.iter_mut())
+            .map(|(peak, hit_bound)| {
                 let maxp = Self::maximum_position(peak);
                 let hw2 = Self::half_width2(peak, maxp);
                 let sfhw = Self::scale_factor_half_width(peak, maxp, hw2);
+                *hit_bound |= hw2 <= f64::EPSILON;
                 Lorentzian::new(sfhw, hw2, maxp)
             })
             .collect::<Vec<_>>();
 
+        self.iterate(&reduced_spectrum, peak_data, lorentzians, hit_bound, cancel)
+    }
+
+    /// Refines the given Lorentzians, which are used as the starting
+    /// parameters, and reports the diagnostics of the fit of each Lorentzian.
+    ///
+    /// Each Lorentzian is represented by the 3 points closest to its maximum
+    /// and to one half-width on either side of it, with the intensities of the
+    /// Lorentzian itself at these points. The iterations then proceed as if
+    /// they were resumed from a previous fit.
+    pub(crate) fn refine_lorentzian_with_diagnostics(
+        &self,
+        spectrum: &Spectrum,
+        lorentzians: &[Lorentzian],
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
+        let (peaks, lorentzians) = peaks_from_lorentzians(spectrum, lorentzians);
+        let reduced_spectrum = ReducedSpectrum::new(spectrum, &peaks);
+        let peak_data = peaks
+            .iter()
+            .zip(lorentzians.iter())
+            .map(|(peak, lorentzian)| {
+                let mut stencil = PeakStencil::new(spectrum, peak);
+                stencil.set_y_1(lorentzian.evaluate(stencil.x_1()));
+                stencil.set_y_2(lorentzian.evaluate(stencil.x_2()));
+                stencil.set_y_3(lorentzian.evaluate(stencil.x_3()));
+                stencil
+            })
+            .collect::<Vec<_>>();
+        let hit_bound = vec![false; peaks.len()];
+        let (lorentzians, diagnostics) =
+            self.iterate(&reduced_spectrum, peak_data, lorentzians, hit_bound, None);
+
+        Self::retain_valid_with_diagnostics(lorentzians, diagnostics)
+    }
+
+    /// Internal helper function to iteratively refine the Lorentzians and
+    /// their peak stencils, and to compute the diagnostics of the final fit.
+    /// The iterations stop early if the optional cancellation flag is set.
+    fn iterate(
+        &self,
+        reduced_spectrum: &ReducedSpectrum,
+        mut peak_data: Vec<PeakStencil>,
+        mut lorentzians: Vec<Lorentzian>,
+        mut hit_bound: Vec<bool>,
+        cancel: Option<&AtomicBool>,
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
         for _ in 0..self.iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
             let superpositions =
                 Lorentzian::superposition_vec(reduced_spectrum.chemical_shifts(), &lorentzians);
             let ratios = reduced_spectrum
@@ -57,24 +276,34 @@ impl Fitter for FitterAnalytical {
             lorentzians
                 .iter_mut()
                 .zip(peak_data.iter())
-                .for_each(|(lorentzian, stencil)| {
+                .zip(hit_bound.iter_mut())
+                .for_each(|((lorentzian, stencil), hit_bound)| {
                     let maxp = Self::maximum_position(stencil);
                     let hw2 = Self::half_width2(stencil, maxp);
                     let sfhw = Self::scale_factor_half_width(stencil, maxp, hw2);
+                    *hit_bound |= hw2 <= f64::EPSILON;
                     lorentzian.set_parameters(sfhw, hw2, maxp);
                 });
         }
-        lorentzians.retain(|lorentzian| {
-            lorentzian.sfhw() > crate::CHECK_PRECISION && lorentzian.hw2() > crate::CHECK_PRECISION
-        });
+        let superpositions =
+            Lorentzian::superposition_vec(reduced_spectrum.chemical_shifts(), &lorentzians);
+        let diagnostics = self.diagnostics(reduced_spectrum, &superpositions, &hit_bound);
 
-        lorentzians
+        (lorentzians, diagnostics)
     }
 
     /// Fits a set of Lorentzians to the spectrum using the given peaks in
-    /// parallel.
+    /// parallel without removing invalid Lorentzians, such that the result and
+    /// the diagnostics correspond to the peaks one to one.
+    ///
+    /// If the optional cancellation flag is set, the iterations stop early.
     #[cfg(feature = "parallel")]
-    fn par_fit_lorentzian(&self, spectrum: &Spectrum, peaks: &[Peak]) -> Vec<Lorentzian> {
+    pub(crate) fn par_fit_lorentzian_unfiltered(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
         let reduced_spectrum = ReducedSpectrum::new(spectrum, peaks);
         let mut peak_data = peaks
             .iter()
@@ -84,17 +313,23 @@ impl Fitter for FitterAnalytical {
                 stencil
             })
             .collect::<Vec<_>>();
+        let mut hit_bound = vec![false; peaks.len()];
         let mut lorentzians = peak_data
             .iter()
-            .map(|peak| {
+            .zip(hit_bound.iter_mut())
+            .map(|(peak, hit_bound)| {
                 let maxp = Self::maximum_position(peak);
                 let hw2 = Self::half_width2(peak, maxp);
                 let sfhw = Self::scale_factor_half_width(peak, maxp, hw2);
+                *hit_bound |= hw2 <= f64::EPSILON;
                 Lorentzian::new(sfhw, hw2, maxp)
             })
             .collect::<Vec<_>>();
 
         for _ in 0..self.iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
             let superpositions =
                 Lorentzian::par_superposition_vec(reduced_spectrum.chemical_shifts(), &lorentzians);
             let ratios = reduced_spectrum
@@ -115,36 +350,83 @@ impl Fitter for FitterAnalytical {
             lorentzians
                 .par_iter_mut()
                 .zip(peak_data.par_iter())
-                .for_each(|(lorentzian, stencil)| {
+                .zip(hit_bound.par_iter_mut())
+                .for_each(|((lorentzian, stencil), hit_bound)| {
                     let maxp = Self::maximum_position(stencil);
                     let hw2 = Self::half_width2(stencil, maxp);
                     let sfhw = Self::scale_factor_half_width(stencil, maxp, hw2);
+                    *hit_bound |= hw2 <= f64::EPSILON;
                     lorentzian.set_parameters(sfhw, hw2, maxp);
                 });
         }
-        lorentzians.retain(|lorentzian| {
-            lorentzian.sfhw() > crate::CHECK_PRECISION && lorentzian.hw2() > crate::CHECK_PRECISION
-        });
+        let superpositions =
+            Lorentzian::par_superposition_vec(reduced_spectrum.chemical_shifts(), &lorentzians);
+        let diagnostics = self.diagnostics(&reduced_spectrum, &superpositions, &hit_bound);
+
+        (lorentzians, diagnostics)
+    }
+
+    /// Internal helper function to remove Lorentzians with non-positive or
+    /// non-finite scale factor or half-width, or a non-finite maximum position,
+    /// which can't represent a peak.
+    pub(crate) fn retain_valid(lorentzians: &mut Vec<Lorentzian>) {
+        lorentzians.retain(Self::is_valid);
+    }
 
+    /// Internal helper function to remove invalid Lorentzians together with
+    /// their diagnostics. See [`retain_valid`](Self::retain_valid).
+    pub(crate) fn retain_valid_with_diagnostics(
+        lorentzians: Vec<Lorentzian>,
+        diagnostics: Vec<FitDiagnostics>,
+    ) -> (Vec<Lorentzian>, Vec<FitDiagnostics>) {
         lorentzians
+            .into_iter()
+            .zip(diagnostics)
+            .filter(|(lorentzian, _)| Self::is_valid(lorentzian))
+            .unzip()
     }
 
-    fn settings(&self) -> FittingSettings {
-        FittingSettings::Analytical {
-            iterations: self.iterations,
-        }
+    /// Internal helper function to check whether a fitted Lorentzian is valid.
+    /// Scale factors and half-widths below the precision threshold are
+    /// considered degenerate.
+    fn is_valid(lorentzian: &Lorentzian) -> bool {
+        lorentzian.is_valid()
+            && lorentzian.sfhw() > crate::CHECK_PRECISION
+            && lorentzian.hw2() > crate::CHECK_PRECISION
     }
-}
 
-impl FitterAnalytical {
-    /// Constructs a new `FitterAnalytical` with the given number of iterations.
-    pub(crate) fn new(iterations: usize) -> Self {
-        Self { iterations }
+    /// Internal helper function to compute the diagnostics of each peak from
+    /// the superposition of the fitted Lorentzians at the 3 points of the
+    /// reduced spectrum that represent it.
+    fn diagnostics(
+        &self,
+        reduced_spectrum: &ReducedSpectrum,
+        superpositions: &[f64],
+        hit_bound: &[bool],
+    ) -> Vec<FitDiagnostics> {
+        reduced_spectrum
+            .intensities()
+            .chunks(3)
+            .zip(superpositions.chunks(3))
+            .zip(hit_bound.iter())
+            .map(|((intensities, superpositions), hit_bound)| {
+                let scale = intensities
+                    .iter()
+                    .fold(0.0, |max, intensity| f64::max(max, intensity.abs()));
+                let residual = intensities
+                    .iter()
+                    .zip(superpositions.iter())
+                    .map(|(intensity, superposition)| (intensity - superposition).abs())
+                    .fold(0.0, f64::max)
+                    / scale;
+                FitDiagnostics::new(self.iterations, residual, *hit_bound)
+            })
+            .collect()
     }
 
     /// Internal helper function to analytically compute the maximum position of
     /// the peak in ppm by solving the system of 3 equations.
-    fn maximum_position(p: &PeakStencil) -> f64 {
+    pub(crate) fn maximum_position(p: &PeakStencil) -> f64 {
         let numerator = p.x_1().powi(2) * p.y_1() * (p.y_2() - p.y_3())
             + p.x_2().powi(2) * p.y_2() * (p.y_3() - p.y_1())
             + p.x_3().powi(2) * p.y_3() * (p.y_1() - p.y_2());
@@ -156,7 +438,7 @@ impl FitterAnalytical {
 
     /// Internal helper function to analytically compute the half width at half
     /// maximum of the peak in ppm^2 by solving the system of 3 equations.
-    fn half_width2(p: &PeakStencil, maxp: f64) -> f64 {
+    pub(crate) fn half_width2(p: &PeakStencil, maxp: f64) -> f64 {
         let left = (p.y_1() * (p.x_1() - maxp).powi(2) - p.y_2() * (p.x_2() - maxp).powi(2))
             / (p.y_2() - p.y_1());
         let right = (p.y_2() * (p.x_2() - maxp).powi(2) - p.y_3() * (p.x_3() - maxp).powi(2))
@@ -167,7 +449,7 @@ impl FitterAnalytical {
     /// Internal helper function to analytically compute the scale factor times
     /// the half width at half maximum of the peak in ppm^2 by solving the
     /// system of 3 equations.
-    fn scale_factor_half_width(p: &PeakStencil, maxp: f64, hw2: f64) -> f64 {
+    pub(crate) fn scale_factor_half_width(p: &PeakStencil, maxp: f64, hw2: f64) -> f64 {
         p.y_2() * (hw2 + (p.x_2() - maxp).powi(2))
     }
 }
@@ -194,4 +476,32 @@ mod tests {
         assert_approx_eq!(f64, hw2.sqrt(), 4.0);
         assert_approx_eq!(f64, sfhw / hw2.sqrt(), 40.0);
     }
+
+    #[test]
+    fn degenerate_peaks() {
+        let chemical_shifts = (0..100)
+            .map(|i| i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let mut intensities = vec![0.0; 100];
+        intensities[30] = 1.0;
+        intensities[59..=61].fill(1.0);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (0.1, 0.9)).unwrap();
+        let peaks = [Peak::new(29, 30, 31), Peak::new(59, 60, 61)];
+        let fitter = FitterAnalytical::new(10).unwrap();
+        let (unfiltered, _) = fitter.fit_lorentzian_unfiltered(&spectrum, &peaks, None);
+        assert!(
+            unfiltered
+                .iter()
+                .all(|lorentzian| !lorentzian.is_valid())
+        );
+        let (lorentzians, diagnostics) = fitter.fit_lorentzian_with_diagnostics(&spectrum, &peaks);
+        assert!(lorentzians.is_empty());
+        assert!(diagnostics.is_empty());
+        #[cfg(feature = "parallel")]
+        assert!(
+            fitter
+                .par_fit_lorentzian(&spectrum, &peaks)
+                .is_empty()
+        );
+    }
 }
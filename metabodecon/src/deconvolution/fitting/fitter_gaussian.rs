@@ -0,0 +1,316 @@
+use crate::deconvolution::fitted_signals::FittedSignals;
+use crate::deconvolution::fitting::{
+    FitDiagnostics, Fitter, FittingSettings, PeakStencil, ReducedSpectrum, is_cancelled,
+    unless_cancelled,
+};
+use crate::deconvolution::gaussian::Gaussian;
+use crate::deconvolution::peak_selection::Peak;
+use crate::spectrum::Spectrum;
+use crate::{Result, Settings};
+use std::f64::consts::LN_2;
+use std::sync::atomic::AtomicBool;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Fitting algorithm for Gaussian peak shapes based on the analytical solution
+/// of a system of equations using a 3-point peak stencil.
+///
+/// Equivalent to [`FittingSettings::Gaussian`] for the fitting step of a
+/// [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct FitterGaussian {
+    /// The number of iterations to refine the Gaussian parameters.
+    iterations: usize,
+}
+
+impl Fitter for FitterGaussian {
+    fn fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::Gaussian(self.fit_gaussian(spectrum, peaks, None).into())
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::Gaussian(
+            self.par_fit_gaussian(spectrum, peaks, None)
+                .into(),
+        )
+    }
+
+    fn fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let gaussians = unless_cancelled(self.fit_gaussian(spectrum, peaks, Some(cancel)), cancel)?;
+
+        Ok((FittedSignals::Gaussian(gaussians.into()), None))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let gaussians =
+            unless_cancelled(self.par_fit_gaussian(spectrum, peaks, Some(cancel)), cancel)?;
+
+        Ok((FittedSignals::Gaussian(gaussians.into()), None))
+    }
+
+    fn settings(&self) -> FittingSettings {
+        FittingSettings::Gaussian {
+            iterations: self.iterations,
+        }
+    }
+}
+
+impl FitterGaussian {
+    /// Constructs a new `FitterGaussian` with the given number of iterations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of iterations is 0.
+    pub fn new(iterations: usize) -> Result<Self> {
+        FittingSettings::Gaussian { iterations }.validate()?;
+
+        Ok(Self { iterations })
+    }
+
+    /// Fits a set of Gaussians to the spectrum using the given peaks.
+    ///
+    /// If the optional cancellation flag is set, the iterations stop early.
+    pub(crate) fn fit_gaussian(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<Gaussian> {
+        let reduced_spectrum = ReducedSpectrum::new(spectrum, peaks);
+        let mut peak_data = Self::peak_data(spectrum, peaks);
+        let mut gaussians = Self::initial_gaussians(&peak_data);
+
+        for _ in 0..self.iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
+            let superpositions =
+                Gaussian::superposition_vec(reduced_spectrum.chemical_shifts(), &gaussians);
+            Self::update_peak_data(&mut peak_data, &reduced_spectrum, &superpositions);
+            gaussians
+                .iter_mut()
+                .zip(peak_data.iter())
+                .for_each(|(gaussian, stencil)| {
+                    let (sf, hw, maxp) = Self::parameters(stencil);
+                    gaussian.set_parameters(sf, hw, maxp);
+                });
+        }
+        Self::retain_valid(&mut gaussians);
+
+        gaussians
+    }
+
+    /// Fits a set of Gaussians to the spectrum using the given peaks in
+    /// parallel.
+    ///
+    /// If the optional cancellation flag is set, the iterations stop early.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_fit_gaussian(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<Gaussian> {
+        let reduced_spectrum = ReducedSpectrum::new(spectrum, peaks);
+        let mut peak_data = Self::peak_data(spectrum, peaks);
+        let mut gaussians = Self::initial_gaussians(&peak_data);
+
+        for _ in 0..self.iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
+            let superpositions =
+                Gaussian::par_superposition_vec(reduced_spectrum.chemical_shifts(), &gaussians);
+            Self::update_peak_data(&mut peak_data, &reduced_spectrum, &superpositions);
+            gaussians
+                .par_iter_mut()
+                .zip(peak_data.par_iter())
+                .for_each(|(gaussian, stencil)| {
+                    let (sf, hw, maxp) = Self::parameters(stencil);
+                    gaussian.set_parameters(sf, hw, maxp);
+                });
+        }
+        Self::retain_valid(&mut gaussians);
+
+        gaussians
+    }
+
+    /// Internal helper function to construct the peak stencils of the given
+    /// peaks, with shoulders mirrored to give them a peak-like shape.
+    fn peak_data(spectrum: &Spectrum, peaks: &[Peak]) -> Vec<PeakStencil> {
+        peaks
+            .iter()
+            .map(|peak| {
+                let mut stencil = PeakStencil::new(spectrum, peak);
+                stencil.mirror_shoulder();
+                stencil
+            })
+            .collect()
+    }
+
+    /// Internal helper function to compute the starting Gaussians from the
+    /// peak stencils.
+    fn initial_gaussians(peak_data: &[PeakStencil]) -> Vec<Gaussian> {
+        peak_data
+            .iter()
+            .map(|stencil| {
+                let (sf, hw, maxp) = Self::parameters(stencil);
+                Gaussian::new(sf, hw, maxp)
+            })
+            .collect()
+    }
+
+    /// Internal helper function to scale the data points of the peak stencils
+    /// by the ratios of the intensities of the reduced spectrum to the
+    /// superposition of the Gaussians.
+    fn update_peak_data(
+        peak_data: &mut [PeakStencil],
+        reduced_spectrum: &ReducedSpectrum,
+        superpositions: &[f64],
+    ) {
+        let ratios = Self::ratios(reduced_spectrum.intensities(), superpositions);
+        peak_data
+            .iter_mut()
+            .zip(ratios.chunks(3))
+            .for_each(|(stencil, ratio)| {
+                stencil.set_y_1(stencil.y_1() * ratio[0]);
+                stencil.set_y_2(stencil.y_2() * ratio[1]);
+                stencil.set_y_3(stencil.y_3() * ratio[2]);
+                stencil.mirror_shoulder();
+            });
+    }
+
+    /// Internal helper function to compute the ratios of the intensities to
+    /// the superposition of the Gaussians.
+    ///
+    /// Gaussians decay much faster than Lorentzians, so the superposition can
+    /// underflow to 0 far from any maximum. In that case, the data point is
+    /// left unchanged.
+    fn ratios(intensities: &[f64], superpositions: &[f64]) -> Vec<f64> {
+        intensities
+            .iter()
+            .zip(superpositions.iter())
+            .map(|(intensity, superposition)| {
+                let ratio = intensity / superposition;
+                match ratio.is_finite() {
+                    true => ratio,
+                    false => 1.0,
+                }
+            })
+            .collect()
+    }
+
+    /// Internal helper function to remove Gaussians with non-positive or
+    /// non-finite scale factor or half-width, or a non-finite maximum position,
+    /// which can't represent a peak. Scale factors and half-widths below the
+    /// precision threshold are considered degenerate.
+    fn retain_valid(gaussians: &mut Vec<Gaussian>) {
+        gaussians.retain(|gaussian| {
+            gaussian.is_valid()
+                && gaussian.sf() > crate::CHECK_PRECISION
+                && gaussian.hw() > crate::CHECK_PRECISION
+        });
+    }
+
+    /// Internal helper function to analytically compute the parameters
+    /// `(sf, hw, maxp)` of the Gaussian by fitting a parabola to the logarithm
+    /// of the 3 data points.
+    ///
+    /// If the logarithm of the data points is not concave, no Gaussian can
+    /// represent the peak. A Gaussian with a scale factor of 0 is returned in
+    /// that case, which is filtered out after fitting.
+    fn parameters(p: &PeakStencil) -> (f64, f64, f64) {
+        let l_1 = p.y_1().max(f64::MIN_POSITIVE).ln();
+        let l_2 = p.y_2().max(f64::MIN_POSITIVE).ln();
+        let l_3 = p.y_3().max(f64::MIN_POSITIVE).ln();
+        let d_1 = (l_2 - l_1) / (p.x_2() - p.x_1());
+        let d_2 = (l_3 - l_2) / (p.x_3() - p.x_2());
+        let curvature = (d_2 - d_1) / (p.x_3() - p.x_1());
+        if curvature.is_nan() || curvature >= 0.0 {
+            return (0.0, f64::EPSILON, p.x_2());
+        }
+        let maxp = 0.5 * (p.x_1() + p.x_2()) - d_1 / (2.0 * curvature);
+        let hw = f64::sqrt(-LN_2 / curvature);
+        let sf = f64::exp(
+            l_1 + d_1 * (maxp - p.x_1()) + curvature * (maxp - p.x_1()) * (maxp - p.x_2()),
+        );
+
+        (sf, hw, maxp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::peak_selection::{DetectorOnly, Selector};
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(FitterGaussian);
+        assert_sync!(FitterGaussian);
+    }
+
+    #[test]
+    fn approximations() {
+        let peak = PeakStencil::from_data(4.0, 8.0, 12.0, 5.0, 10.0, 5.0);
+        let (sf, hw, maxp) = FitterGaussian::parameters(&peak);
+        assert_approx_eq!(f64, sf, 10.0);
+        assert_approx_eq!(f64, hw, 4.0);
+        assert_approx_eq!(f64, maxp, 8.0);
+    }
+
+    #[test]
+    fn fit_triplet() {
+        let triplet = [
+            Gaussian::new(1.0, 0.03, 4.9),
+            Gaussian::new(2.0, 0.02, 5.0),
+            Gaussian::new(1.0, 0.03, 5.1),
+        ];
+        let chemical_shifts = (0..2001)
+            .map(|i| 4.0 + i as f64 * 0.001)
+            .collect::<Vec<f64>>();
+        let intensities = Gaussian::superposition_vec(&chemical_shifts, &triplet);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (4.5, 5.5)).unwrap();
+        let peaks = DetectorOnly::new()
+            .select_peaks(
+                spectrum.intensities(),
+                spectrum.signal_boundaries_indices(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let fitter = FitterGaussian::new(10).unwrap();
+        let mut fits = vec![fitter.fit_gaussian(&spectrum, &peaks, None)];
+        #[cfg(feature = "parallel")]
+        fits.push(fitter.par_fit_gaussian(&spectrum, &peaks, None));
+        fits.into_iter().for_each(|gaussians| {
+            assert_eq!(gaussians.len(), 3);
+            gaussians
+                .iter()
+                .zip(triplet.iter())
+                .for_each(|(fitted, expected)| {
+                    assert_approx_eq!(f64, fitted.maxp(), expected.maxp(), epsilon = 1e-3);
+                    assert_approx_eq!(f64, fitted.hw(), expected.hw(), epsilon = 1e-3);
+                    assert_approx_eq!(f64, fitted.sf(), expected.sf(), epsilon = 1e-2);
+                });
+        });
+    }
+}
@@ -0,0 +1,684 @@
+use crate::deconvolution::fitted_signals::FittedSignals;
+use crate::deconvolution::fitting::{
+    FitDiagnostics, Fitter, FitterAnalytical, FittingSettings, ParameterUncertainty, is_cancelled,
+    peaks_from_lorentzians, solve_linear_system, unless_cancelled,
+};
+use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::peak_selection::Peak;
+use crate::spectrum::Spectrum;
+use crate::{Result, Settings};
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Fitting algorithm that refines the analytical solution by minimizing the sum
+/// of squared residuals with the Levenberg-Marquardt algorithm.
+///
+/// Equivalent to [`FittingSettings::LevenbergMarquardt`] for the fitting step
+/// of a [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct FitterLevenbergMarquardt {
+    /// The maximum number of Levenberg-Marquardt iterations.
+    max_iterations: usize,
+    /// The relative improvement of the residuals below which the refinement
+    /// stops.
+    tolerance: f64,
+    /// The initial damping factor.
+    lambda_init: f64,
+    /// The analytical fit used as initial guess.
+    initial: FitterAnalytical,
+}
+
+/// Group of overlapping Lorentzians that are refined simultaneously.
+#[derive(Clone, Debug)]
+struct SignalRegion {
+    /// The range of the Lorentzians belonging to the signal region.
+    signals: Range<usize>,
+    /// The range of the data points used to refine the Lorentzians.
+    data: Range<usize>,
+}
+
+impl Fitter for FitterLevenbergMarquardt {
+    fn fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::Lorentzian(self.fit_lorentzian(spectrum, peaks, None).into())
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::Lorentzian(
+            self.par_fit_lorentzian(spectrum, peaks, None)
+                .into(),
+        )
+    }
+
+    fn fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let lorentzians =
+            unless_cancelled(self.fit_lorentzian(spectrum, peaks, Some(cancel)), cancel)?;
+
+        Ok((FittedSignals::Lorentzian(lorentzians.into()), None))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let lorentzians = unless_cancelled(
+            self.par_fit_lorentzian(spectrum, peaks, Some(cancel)),
+            cancel,
+        )?;
+
+        Ok((FittedSignals::Lorentzian(lorentzians.into()), None))
+    }
+
+    fn refine(
+        &self,
+        spectrum: &Spectrum,
+        lorentzians: &[Lorentzian],
+    ) -> Option<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let (peaks, lorentzians) = peaks_from_lorentzians(spectrum, lorentzians);
+        let (peaks, lorentzians) = Self::valid_signals(&peaks, lorentzians);
+        let lorentzians = self.refine_lorentzian(spectrum, &peaks, lorentzians, None);
+
+        Some((FittedSignals::Lorentzian(lorentzians.into()), None))
+    }
+
+    fn parameter_uncertainties(
+        &self,
+        spectrum: &Spectrum,
+        signals: &FittedSignals,
+    ) -> Option<Vec<ParameterUncertainty>> {
+        match signals {
+            FittedSignals::Lorentzian(lorentzians) => {
+                Some(Self::standard_errors(spectrum, lorentzians))
+            }
+            _ => None,
+        }
+    }
+
+    fn settings(&self) -> FittingSettings {
+        FittingSettings::LevenbergMarquardt {
+            max_iterations: self.max_iterations,
+            tolerance: self.tolerance,
+            lambda_init: self.lambda_init,
+        }
+    }
+}
+
+impl FitterLevenbergMarquardt {
+    /// The number of iterations of the analytical fit used as initial guess.
+    const INITIAL_ITERATIONS: usize = 10;
+
+    /// Lorentzians whose maxima are closer than this many times the sum of
+    /// their half-widths are considered overlapping.
+    const OVERLAP_FACTOR: f64 = 4.0;
+
+    /// The maximum number of times the damping factor is increased within a
+    /// single iteration before the step is rejected.
+    const MAX_DAMPING_STEPS: usize = 10;
+
+    /// Constructs a new `FitterLevenbergMarquardt` with the given maximum
+    /// number of iterations, tolerance and initial damping factor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the maximum number of iterations is 0, the
+    /// tolerance is negative or not finite, or the initial damping factor is
+    /// not positive and finite.
+    pub fn new(max_iterations: usize, tolerance: f64, lambda_init: f64) -> Result<Self> {
+        FittingSettings::LevenbergMarquardt {
+            max_iterations,
+            tolerance,
+            lambda_init,
+        }
+        .validate()?;
+
+        Ok(Self {
+            max_iterations,
+            tolerance,
+            lambda_init,
+            initial: FitterAnalytical::new(Self::INITIAL_ITERATIONS)?,
+        })
+    }
+
+    /// Fits a set of Lorentzians to the spectrum using the given peaks.
+    ///
+    /// The Lorentzians are initialized with the analytical solution of the
+    /// 3-point peak stencil and refined as follows:
+    ///
+    /// 1. Overlapping Lorentzians are grouped into signal regions, which span
+    ///    the data points from the left inflection point of the first to the
+    ///    right inflection point of the last peak in the group. The data points
+    ///    between neighboring signal regions are split at the midpoint, such
+    ///    that the tails of the Lorentzians are considered as well.
+    /// 2. In each iteration, the transformed parameters `(sfhw, hw2, maxp)` of
+    ///    all Lorentzians within a signal region are updated simultaneously by
+    ///    a Levenberg-Marquardt step, which minimizes the sum of squared
+    ///    residuals within the signal region. The normal equations are damped
+    ///    with the diagonal of the approximated Hessian, which accounts for the
+    ///    different scales of the parameters. The analytical Jacobian is used,
+    ///    and the contributions of Lorentzians from other signal regions are
+    ///    kept fixed during the step.
+    /// 3. Steps that don't reduce the residuals or lead to non-positive `sfhw`
+    ///    or `hw2` are rejected and the damping factor is increased, otherwise
+    ///    it is decreased.
+    /// 4. The refinement stops once the relative improvement of the sum of
+    ///    squared residuals drops below the tolerance, or after the maximum
+    ///    number of iterations.
+    ///
+    /// If the optional cancellation flag is set, both the analytical fit and
+    /// the refinement stop early.
+    pub(crate) fn fit_lorentzian(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<Lorentzian> {
+        let initial = self
+            .initial
+            .fit_lorentzian_unfiltered(spectrum, peaks, cancel)
+            .0;
+        let (peaks, lorentzians) = Self::valid_signals(peaks, initial);
+
+        self.refine_lorentzian(spectrum, &peaks, lorentzians, cancel)
+    }
+
+    /// Refines the given Lorentzians, which belong to the given peaks, with
+    /// steps 1 to 4 of [`fit_lorentzian`](Self::fit_lorentzian).
+    pub(crate) fn refine_lorentzian(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[&Peak],
+        mut lorentzians: Vec<Lorentzian>,
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<Lorentzian> {
+        let regions = Self::signal_regions(peaks, &lorentzians);
+        let mut lambdas = vec![self.lambda_init; regions.len()];
+        let mut residuals = Self::sum_of_squares(spectrum, &regions, &lorentzians);
+
+        for _ in 0..self.max_iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
+            let steps = regions
+                .iter()
+                .zip(lambdas.iter())
+                .map(|(region, lambda)| Self::step(spectrum, region, &lorentzians, *lambda))
+                .collect::<Vec<_>>();
+            let previous = lorentzians.clone();
+            regions
+                .iter()
+                .zip(steps)
+                .zip(lambdas.iter_mut())
+                .for_each(|((region, (refined, next_lambda)), lambda)| {
+                    lorentzians[region.signals.clone()].copy_from_slice(&refined);
+                    *lambda = next_lambda;
+                });
+            let next_residuals = Self::sum_of_squares(spectrum, &regions, &lorentzians);
+            if next_residuals > residuals {
+                lorentzians = previous;
+                break;
+            }
+            if Self::converged(residuals, next_residuals, self.tolerance) {
+                break;
+            }
+            residuals = next_residuals;
+        }
+        FitterAnalytical::retain_valid(&mut lorentzians);
+
+        lorentzians
+    }
+
+    /// Fits a set of Lorentzians to the spectrum using the given peaks in
+    /// parallel.
+    ///
+    /// Uses the same algorithm as the sequential version, but computes the
+    /// steps of the signal regions in parallel.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_fit_lorentzian(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<Lorentzian> {
+        let initial = self
+            .initial
+            .par_fit_lorentzian_unfiltered(spectrum, peaks, cancel)
+            .0;
+        let (peaks, mut lorentzians) = Self::valid_signals(peaks, initial);
+        let regions = Self::signal_regions(&peaks, &lorentzians);
+        let mut lambdas = vec![self.lambda_init; regions.len()];
+        let mut residuals = Self::par_sum_of_squares(spectrum, &regions, &lorentzians);
+
+        for _ in 0..self.max_iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
+            let steps = regions
+                .par_iter()
+                .zip(lambdas.par_iter())
+                .map(|(region, lambda)| Self::step(spectrum, region, &lorentzians, *lambda))
+                .collect::<Vec<_>>();
+            let previous = lorentzians.clone();
+            regions
+                .iter()
+                .zip(steps)
+                .zip(lambdas.iter_mut())
+                .for_each(|((region, (refined, next_lambda)), lambda)| {
+                    lorentzians[region.signals.clone()].copy_from_slice(&refined);
+                    *lambda = next_lambda;
+                });
+            let next_residuals = Self::par_sum_of_squares(spectrum, &regions, &lorentzians);
+            if next_residuals > residuals {
+                lorentzians = previous;
+                break;
+            }
+            if Self::converged(residuals, next_residuals, self.tolerance) {
+                break;
+            }
+            residuals = next_residuals;
+        }
+        FitterAnalytical::retain_valid(&mut lorentzians);
+
+        lorentzians
+    }
+
+    /// Internal helper function to remove peaks for which the analytical
+    /// solution is invalid, as they can't be refined.
+    fn valid_signals(
+        peaks: &[Peak],
+        lorentzians: Vec<Lorentzian>,
+    ) -> (Vec<&Peak>, Vec<Lorentzian>) {
+        peaks
+            .iter()
+            .zip(lorentzians)
+            .filter(|(_, lorentzian)| {
+                lorentzian.sfhw() > crate::CHECK_PRECISION
+                    && lorentzian.hw2() > crate::CHECK_PRECISION
+                    && lorentzian.maxp().is_finite()
+            })
+            .unzip()
+    }
+
+    /// Internal helper function to group consecutive overlapping Lorentzians
+    /// into signal regions.
+    ///
+    /// The data points between neighboring signal regions are assigned to the
+    /// closer one.
+    fn signal_regions(peaks: &[&Peak], lorentzians: &[Lorentzian]) -> Vec<SignalRegion> {
+        let mut regions: Vec<SignalRegion> = Vec::new();
+        for (i, (peak, lorentzian)) in peaks.iter().zip(lorentzians.iter()).enumerate() {
+            let overlapping = match regions.last() {
+                Some(region) => {
+                    let previous = &lorentzians[region.signals.end - 1];
+                    f64::abs(lorentzian.maxp() - previous.maxp())
+                        < Self::OVERLAP_FACTOR * (lorentzian.hw() + previous.hw())
+                }
+                None => false,
+            };
+            match (overlapping, regions.last_mut()) {
+                (true, Some(region)) => {
+                    region.signals.end = i + 1;
+                    region.data.end = peak.right() + 1;
+                }
+                _ => regions.push(SignalRegion {
+                    signals: i..i + 1,
+                    data: peak.left()..peak.right() + 1,
+                }),
+            }
+        }
+        (1..regions.len()).for_each(|k| {
+            let boundary = (regions[k - 1].data.end + regions[k].data.start) / 2;
+            regions[k - 1].data.end = boundary;
+            regions[k].data.start = boundary;
+        });
+
+        regions
+    }
+
+    /// Internal helper function to compute the sum of squared residuals within
+    /// the signal regions.
+    fn sum_of_squares(
+        spectrum: &Spectrum,
+        regions: &[SignalRegion],
+        lorentzians: &[Lorentzian],
+    ) -> f64 {
+        regions
+            .iter()
+            .map(|region| Self::region_sum_of_squares(spectrum, region, lorentzians))
+            .sum()
+    }
+
+    /// Internal helper function to compute the sum of squared residuals within
+    /// the signal regions in parallel.
+    #[cfg(feature = "parallel")]
+    fn par_sum_of_squares(
+        spectrum: &Spectrum,
+        regions: &[SignalRegion],
+        lorentzians: &[Lorentzian],
+    ) -> f64 {
+        regions
+            .par_iter()
+            .map(|region| Self::region_sum_of_squares(spectrum, region, lorentzians))
+            .sum()
+    }
+
+    /// Internal helper function to compute the sum of squared residuals within
+    /// a single signal region.
+    fn region_sum_of_squares(
+        spectrum: &Spectrum,
+        region: &SignalRegion,
+        lorentzians: &[Lorentzian],
+    ) -> f64 {
+        spectrum.chemical_shifts()[region.data.clone()]
+            .iter()
+            .zip(spectrum.intensities()[region.data.clone()].iter())
+            .map(|(x, y)| (y - Lorentzian::superposition(*x, lorentzians)).powi(2))
+            .sum()
+    }
+
+    /// Internal helper function to check whether the relative improvement of
+    /// the sum of squared residuals is below the tolerance.
+    fn converged(residuals: f64, next_residuals: f64, tolerance: f64) -> bool {
+        residuals <= f64::MIN_POSITIVE || (residuals - next_residuals) / residuals < tolerance
+    }
+
+    /// Internal helper function to perform a single Levenberg-Marquardt step
+    /// on the Lorentzians of a signal region.
+    ///
+    /// Returns the refined Lorentzians of the signal region and the damping
+    /// factor for the next iteration. If no step reduces the residuals, the
+    /// Lorentzians are returned unchanged.
+    fn step(
+        spectrum: &Spectrum,
+        region: &SignalRegion,
+        lorentzians: &[Lorentzian],
+        lambda: f64,
+    ) -> (Vec<Lorentzian>, f64) {
+        let current = &lorentzians[region.signals.clone()];
+        let x = &spectrum.chemical_shifts()[region.data.clone()];
+        let y = &spectrum.intensities()[region.data.clone()];
+        let fixed = x
+            .iter()
+            .map(|x| {
+                Lorentzian::superposition(*x, lorentzians) - Lorentzian::superposition(*x, current)
+            })
+            .collect::<Vec<_>>();
+        let residuals = |candidate: &[Lorentzian]| -> f64 {
+            x.iter()
+                .zip(y.iter())
+                .zip(fixed.iter())
+                .map(|((x, y), fixed)| {
+                    (y - fixed - Lorentzian::superposition(*x, candidate)).powi(2)
+                })
+                .sum()
+        };
+        let n = 3 * current.len();
+        let mut hessian = vec![0.0; n * n];
+        let mut gradient = vec![0.0; n];
+        x.iter()
+            .zip(y.iter())
+            .zip(fixed.iter())
+            .for_each(|((x, y), fixed)| {
+                let residual = y - fixed - Lorentzian::superposition(*x, current);
+                let row = current
+                    .iter()
+                    .flat_map(|lorentzian| Self::jacobian(lorentzian, *x))
+                    .collect::<Vec<_>>();
+                for i in 0..n {
+                    gradient[i] += row[i] * residual;
+                    for j in 0..n {
+                        hessian[i * n + j] += row[i] * row[j];
+                    }
+                }
+            });
+        let initial_residuals = residuals(current);
+        let mut lambda = lambda;
+        for _ in 0..Self::MAX_DAMPING_STEPS {
+            let mut damped = hessian.clone();
+            (0..n).for_each(|i| damped[i * n + i] += lambda * hessian[i * n + i]);
+            let candidate = solve_linear_system(damped, gradient.clone()).and_then(|delta| {
+                let candidate = current
+                    .iter()
+                    .zip(delta.chunks(3))
+                    .map(|(lorentzian, delta)| {
+                        Lorentzian::new(
+                            lorentzian.sfhw() + delta[0],
+                            lorentzian.hw2() + delta[1],
+                            lorentzian.maxp() + delta[2],
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                match candidate
+                    .iter()
+                    .all(|lorentzian| lorentzian.sfhw() > 0.0 && lorentzian.hw2() > 0.0)
+                {
+                    true => Some(candidate),
+                    false => None,
+                }
+            });
+            if let Some(candidate) = candidate {
+                if residuals(&candidate) < initial_residuals {
+                    return (candidate, lambda / 10.0);
+                }
+            }
+            lambda *= 10.0;
+        }
+
+        (current.to_vec(), lambda)
+    }
+
+    /// Estimates the standard errors of the parameters of the given
+    /// Lorentzians from the covariance matrix of the transformed parameters.
+    ///
+    /// The Lorentzians are grouped into signal regions around the data points
+    /// closest to their maxima and half-widths, as in step 1 of
+    /// [`fit_lorentzian`](Self::fit_lorentzian). The covariance matrix of each
+    /// signal region is the inverse of the approximated Hessian `J^T J`, scaled
+    /// by the residual variance within the signal region. The standard errors
+    /// are returned in the same order as the Lorentzians.
+    fn standard_errors(
+        spectrum: &Spectrum,
+        lorentzians: &[Lorentzian],
+    ) -> Vec<ParameterUncertainty> {
+        let first = spectrum.chemical_shifts()[0];
+        let step = spectrum.step();
+        let mut order = (0..lorentzians.len()).collect::<Vec<usize>>();
+        order.sort_by(|a, b| {
+            let position = |lorentzian: &Lorentzian| ((lorentzian.maxp() - first) / step).round();
+            position(&lorentzians[*a]).total_cmp(&position(&lorentzians[*b]))
+        });
+        let sorted = order
+            .iter()
+            .map(|index| lorentzians[*index])
+            .collect::<Vec<Lorentzian>>();
+        let (peaks, sorted) = peaks_from_lorentzians(spectrum, &sorted);
+        let regions = Self::signal_regions(&peaks.iter().collect::<Vec<&Peak>>(), &sorted);
+        let mut standard_errors = vec![ParameterUncertainty::undetermined(); lorentzians.len()];
+        regions
+            .iter()
+            .flat_map(|region| Self::region_standard_errors(spectrum, region, &sorted))
+            .zip(order)
+            .for_each(|(uncertainty, index)| standard_errors[index] = uncertainty);
+
+        standard_errors
+    }
+
+    /// Internal helper function to estimate the standard errors of the
+    /// parameters of the Lorentzians of a single signal region.
+    fn region_standard_errors(
+        spectrum: &Spectrum,
+        region: &SignalRegion,
+        lorentzians: &[Lorentzian],
+    ) -> Vec<ParameterUncertainty> {
+        let current = &lorentzians[region.signals.clone()];
+        let x = &spectrum.chemical_shifts()[region.data.clone()];
+        let y = &spectrum.intensities()[region.data.clone()];
+        let n = 3 * current.len();
+        if x.len() <= n {
+            return vec![ParameterUncertainty::undetermined(); current.len()];
+        }
+        let mut hessian = vec![0.0; n * n];
+        let mut residual_sum_of_squares = 0.0;
+        x.iter().zip(y.iter()).for_each(|(x, y)| {
+            residual_sum_of_squares += (y - Lorentzian::superposition(*x, lorentzians)).powi(2);
+            let row = current
+                .iter()
+                .flat_map(|lorentzian| Self::jacobian(lorentzian, *x))
+                .collect::<Vec<_>>();
+            for i in 0..n {
+                for j in 0..n {
+                    hessian[i * n + j] += row[i] * row[j];
+                }
+            }
+        });
+        let variance = residual_sum_of_squares / (x.len() - n) as f64;
+        let inverse = (0..n)
+            .map(|column| {
+                let mut unit = vec![0.0; n];
+                unit[column] = 1.0;
+                solve_linear_system(hessian.clone(), unit)
+            })
+            .collect::<Option<Vec<Vec<f64>>>>();
+        let Some(inverse) = inverse else {
+            return vec![ParameterUncertainty::undetermined(); current.len()];
+        };
+
+        current
+            .iter()
+            .enumerate()
+            .map(|(k, lorentzian)| {
+                let covariance = |i: usize, j: usize| variance * inverse[3 * k + j][3 * k + i];
+                let propagate = |gradient: [f64; 3]| {
+                    (0..3)
+                        .flat_map(|i| (0..3).map(move |j| (i, j)))
+                        .map(|(i, j)| gradient[i] * gradient[j] * covariance(i, j))
+                        .sum::<f64>()
+                        .max(0.0)
+                        .sqrt()
+                };
+                let hw = lorentzian.hw();
+                let sf_gradient = [
+                    1.0 / hw,
+                    -lorentzian.sfhw() / (2.0 * lorentzian.hw2() * hw),
+                    0.0,
+                ];
+                let hw_gradient = [0.0, 1.0 / (2.0 * hw), 0.0];
+                let maxp_gradient = [0.0, 0.0, 1.0];
+
+                ParameterUncertainty::new(
+                    propagate(sf_gradient),
+                    propagate(hw_gradient),
+                    propagate(maxp_gradient),
+                )
+            })
+            .collect()
+    }
+
+    /// Internal helper function to compute the partial derivatives of the
+    /// Lorentzian at `x` with respect to the transformed parameters
+    /// `(sfhw, hw2, maxp)`.
+    fn jacobian(lorentzian: &Lorentzian, x: f64) -> [f64; 3] {
+        let distance = x - lorentzian.maxp();
+        let denominator = lorentzian.hw2() + distance.powi(2);
+        let denominator2 = denominator.powi(2);
+
+        [
+            1.0 / denominator,
+            -lorentzian.sfhw() / denominator2,
+            2.0 * lorentzian.sfhw() * distance / denominator2,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::peak_selection::{DetectorOnly, Selector};
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(FitterLevenbergMarquardt);
+        assert_sync!(FitterLevenbergMarquardt);
+    }
+
+    #[test]
+    fn jacobian() {
+        let lorentzian = Lorentzian::new(0.5, 0.0004, 5.0);
+        let h = 1e-9;
+        let x = 5.013;
+        let jacobian = FitterLevenbergMarquardt::jacobian(&lorentzian, x);
+        let shifted = [
+            Lorentzian::new(0.5 + h, 0.0004, 5.0),
+            Lorentzian::new(0.5, 0.0004 + h, 5.0),
+            Lorentzian::new(0.5, 0.0004, 5.0 + h),
+        ];
+        jacobian
+            .iter()
+            .zip(shifted.iter())
+            .for_each(|(derivative, shifted)| {
+                let numerical = (shifted.evaluate(x) - lorentzian.evaluate(x)) / h;
+                assert_approx_eq!(f64, *derivative / numerical, 1.0, epsilon = 1e-3);
+            });
+    }
+
+    #[test]
+    fn fit_overlapping() {
+        let multiplet = [
+            Lorentzian::new(0.002, 0.01f64.powi(2), 4.98),
+            Lorentzian::new(0.006, 0.015f64.powi(2), 5.0),
+            Lorentzian::new(0.002, 0.01f64.powi(2), 5.03),
+        ];
+        let chemical_shifts = (0..2001)
+            .map(|i| 4.0 + i as f64 * 0.001)
+            .collect::<Vec<f64>>();
+        let intensities = Lorentzian::superposition_vec(&chemical_shifts, &multiplet);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (4.5, 5.5)).unwrap();
+        let peaks = DetectorOnly::new()
+            .select_peaks(
+                spectrum.intensities(),
+                spectrum.signal_boundaries_indices(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let analytical = FitterAnalytical::new(10)
+            .unwrap()
+            .fit_lorentzian(&spectrum, &peaks);
+        let fitter = FitterLevenbergMarquardt::new(100, 1e-12, 1e-3).unwrap();
+        let mut fits = vec![fitter.fit_lorentzian(&spectrum, &peaks, None)];
+        #[cfg(feature = "parallel")]
+        fits.push(fitter.par_fit_lorentzian(&spectrum, &peaks, None));
+        let sum_of_squares = |lorentzians: &[Lorentzian]| -> f64 {
+            Lorentzian::superposition_vec(spectrum.chemical_shifts(), lorentzians)
+                .iter()
+                .zip(spectrum.intensities().iter())
+                .map(|(superposition, intensity)| (superposition - intensity).powi(2))
+                .sum()
+        };
+        fits.into_iter().for_each(|lorentzians| {
+            assert_eq!(lorentzians.len(), analytical.len());
+            assert!(sum_of_squares(&lorentzians) < sum_of_squares(&analytical));
+            lorentzians
+                .iter()
+                .zip(multiplet.iter())
+                .for_each(|(fitted, expected)| {
+                    assert_approx_eq!(f64, fitted.maxp(), expected.maxp(), epsilon = 1e-4);
+                });
+        });
+    }
+}
@@ -0,0 +1,464 @@
+use crate::deconvolution::fitted_signals::FittedSignals;
+use crate::deconvolution::fitting::{
+    FitDiagnostics, Fitter, FitterAnalytical, FittingSettings, PeakStencil, is_cancelled,
+    solve_linear_system, unless_cancelled,
+};
+use crate::deconvolution::peak_selection::Peak;
+use crate::deconvolution::pseudo_voigt::PseudoVoigt;
+use crate::spectrum::Spectrum;
+use crate::{Result, Settings};
+use std::f64::consts::LN_2;
+use std::sync::atomic::AtomicBool;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Fitting algorithm for pseudo-Voigt peak shapes based on damped least
+/// squares refinement of each peak within its peak region.
+///
+/// Equivalent to [`FittingSettings::PseudoVoigt`] for the fitting step of a
+/// [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct FitterPseudoVoigt {
+    /// The number of iterations to refine the pseudo-Voigt parameters.
+    iterations: usize,
+    /// The initial value of the mixing parameter.
+    eta_init: f64,
+}
+
+impl Fitter for FitterPseudoVoigt {
+    fn fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::PseudoVoigt(
+            self.fit_pseudo_voigt(spectrum, peaks, None)
+                .into(),
+        )
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit(&self, spectrum: &Spectrum, peaks: &[Peak]) -> FittedSignals {
+        FittedSignals::PseudoVoigt(
+            self.par_fit_pseudo_voigt(spectrum, peaks, None)
+                .into(),
+        )
+    }
+
+    fn fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let pseudo_voigts =
+            unless_cancelled(self.fit_pseudo_voigt(spectrum, peaks, Some(cancel)), cancel)?;
+
+        Ok((FittedSignals::PseudoVoigt(pseudo_voigts.into()), None))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_fit_cancellable(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: &AtomicBool,
+    ) -> Result<(FittedSignals, Option<Vec<FitDiagnostics>>)> {
+        let pseudo_voigts = unless_cancelled(
+            self.par_fit_pseudo_voigt(spectrum, peaks, Some(cancel)),
+            cancel,
+        )?;
+
+        Ok((FittedSignals::PseudoVoigt(pseudo_voigts.into()), None))
+    }
+
+    fn settings(&self) -> FittingSettings {
+        FittingSettings::PseudoVoigt {
+            iterations: self.iterations,
+            eta_init: self.eta_init,
+        }
+    }
+}
+
+impl FitterPseudoVoigt {
+    /// Constructs a new `FitterPseudoVoigt` with the given number of
+    /// iterations and initial mixing parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of iterations is 0 or the initial mixing
+    /// parameter is not within `[0, 1]`.
+    pub fn new(iterations: usize, eta_init: f64) -> Result<Self> {
+        FittingSettings::PseudoVoigt {
+            iterations,
+            eta_init,
+        }
+        .validate()?;
+
+        Ok(Self {
+            iterations,
+            eta_init,
+        })
+    }
+
+    /// Fits a set of pseudo-Voigt functions to the spectrum using the given
+    /// peaks.
+    ///
+    /// Unlike the Lorentzian and Gaussian, the pseudo-Voigt function has 4
+    /// parameters, so it can't be determined analytically from the 3-point
+    /// peak stencil. Instead, the parameters are refined numerically:
+    ///
+    /// 1. The position, half-width and height of each peak are initialized
+    ///    from the analytical Lorentzian solution of the 3-point peak stencil,
+    ///    and the mixing parameter is set to `eta_init`.
+    /// 2. In each iteration, the peaks are visited in order. For each peak, the
+    ///    contributions of all other peaks are subtracted from the spectrum
+    ///    within its peak region (from the left to the right inflection point).
+    /// 3. A single Levenberg-Marquardt step is performed on the 4 parameters
+    ///    `(eta, hw, maxp, sf)` to minimize the sum of squared residuals
+    ///    within the peak region, using the analytical Jacobian. The damping
+    ///    factor is increased until the step reduces the residuals. `eta` is
+    ///    clamped to `[0, 1]` and steps leading to non-positive `hw` or `sf`
+    ///    are rejected.
+    /// 4. The superposition is updated with the refined peak before moving on
+    ///    to the next peak (Gauss-Seidel style), which keeps overlapping peaks
+    ///    from competing for the same residuals.
+    ///
+    /// If the optional cancellation flag is set, the iterations stop early.
+    pub(crate) fn fit_pseudo_voigt(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<PseudoVoigt> {
+        let (x, y, regions) = Self::peak_regions(spectrum, peaks);
+        let mut pseudo_voigts = peaks
+            .iter()
+            .map(|peak| self.initial_guess(spectrum, peak))
+            .collect::<Vec<_>>();
+        let mut superpositions = PseudoVoigt::superposition_vec(&x, &pseudo_voigts);
+
+        for _ in 0..self.iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
+            for (pseudo_voigt, (start, end)) in pseudo_voigts.iter_mut().zip(regions.iter()) {
+                let refined = Self::refine(
+                    pseudo_voigt,
+                    &x[*start..*end],
+                    &y[*start..*end],
+                    &superpositions[*start..*end],
+                );
+                superpositions
+                    .iter_mut()
+                    .zip(x.iter())
+                    .for_each(|(superposition, &x)| {
+                        *superposition += refined.evaluate(x) - pseudo_voigt.evaluate(x)
+                    });
+                *pseudo_voigt = refined;
+            }
+        }
+        pseudo_voigts.retain(|pseudo_voigt| {
+            pseudo_voigt.is_valid()
+                && pseudo_voigt.sf() > crate::CHECK_PRECISION
+                && pseudo_voigt.hw() > crate::CHECK_PRECISION
+        });
+
+        pseudo_voigts
+    }
+
+    /// Fits a set of pseudo-Voigt functions to the spectrum using the given
+    /// peaks in parallel.
+    ///
+    /// Uses the same algorithm as the sequential version, but updates the
+    /// superposition in parallel.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_fit_pseudo_voigt(
+        &self,
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+        cancel: Option<&AtomicBool>,
+    ) -> Vec<PseudoVoigt> {
+        let (x, y, regions) = Self::peak_regions(spectrum, peaks);
+        let mut pseudo_voigts = peaks
+            .par_iter()
+            .map(|peak| self.initial_guess(spectrum, peak))
+            .collect::<Vec<_>>();
+        let mut superpositions = PseudoVoigt::par_superposition_vec(&x, &pseudo_voigts);
+
+        for _ in 0..self.iterations {
+            if is_cancelled(cancel) {
+                break;
+            }
+            for (pseudo_voigt, (start, end)) in pseudo_voigts.iter_mut().zip(regions.iter()) {
+                let refined = Self::refine(
+                    pseudo_voigt,
+                    &x[*start..*end],
+                    &y[*start..*end],
+                    &superpositions[*start..*end],
+                );
+                superpositions
+                    .par_iter_mut()
+                    .zip(x.par_iter())
+                    .for_each(|(superposition, &x)| {
+                        *superposition += refined.evaluate(x) - pseudo_voigt.evaluate(x)
+                    });
+                *pseudo_voigt = refined;
+            }
+        }
+        pseudo_voigts.retain(|pseudo_voigt| {
+            pseudo_voigt.is_valid()
+                && pseudo_voigt.sf() > crate::CHECK_PRECISION
+                && pseudo_voigt.hw() > crate::CHECK_PRECISION
+        });
+
+        pseudo_voigts
+    }
+
+    /// Internal helper function to extract the data points within the peak
+    /// regions.
+    ///
+    /// Returns the chemical shifts and intensities of the union of all peak
+    /// regions, as well as the range of each peak region within them.
+    #[allow(clippy::type_complexity)]
+    fn peak_regions(
+        spectrum: &Spectrum,
+        peaks: &[Peak],
+    ) -> (Vec<f64>, Vec<f64>, Vec<(usize, usize)>) {
+        let mut indices = peaks
+            .iter()
+            .flat_map(|peak| peak.left()..=peak.right())
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        let regions = peaks
+            .iter()
+            .map(|peak| {
+                let start = indices.binary_search(&peak.left()).unwrap();
+                let end = indices.binary_search(&peak.right()).unwrap() + 1;
+                (start, end)
+            })
+            .collect();
+        let x = indices
+            .iter()
+            .map(|&i| spectrum.chemical_shifts()[i])
+            .collect();
+        let y = indices
+            .iter()
+            .map(|&i| spectrum.intensities()[i])
+            .collect();
+
+        (x, y, regions)
+    }
+
+    /// Internal helper function to compute the initial guess for a peak from
+    /// the analytical Lorentzian solution of the 3-point peak stencil.
+    ///
+    /// Falls back to the center data point and the distance between the
+    /// inflection points if the analytical solution is degenerate.
+    fn initial_guess(&self, spectrum: &Spectrum, peak: &Peak) -> PseudoVoigt {
+        let mut stencil = PeakStencil::new(spectrum, peak);
+        stencil.mirror_shoulder();
+        let width = f64::abs(stencil.x_3() - stencil.x_1());
+        let maxp = FitterAnalytical::maximum_position(&stencil);
+        let hw2 = FitterAnalytical::half_width2(&stencil, maxp);
+        let sf = FitterAnalytical::scale_factor_half_width(&stencil, maxp, hw2) / hw2;
+        let hw = hw2.sqrt();
+        let valid = f64::abs(maxp - stencil.x_2()) < width
+            && hw > crate::CHECK_PRECISION
+            && hw < width
+            && sf.is_finite()
+            && sf > 0.0;
+
+        match valid {
+            true => PseudoVoigt::new(self.eta_init, hw, maxp, sf),
+            false => PseudoVoigt::new(
+                self.eta_init,
+                0.5 * width,
+                stencil.x_2(),
+                stencil.y_2().max(f64::EPSILON),
+            ),
+        }
+    }
+
+    /// Internal helper function to perform a single Levenberg-Marquardt step
+    /// for one peak within its peak region.
+    ///
+    /// The superpositions include the contribution of the peak itself, which
+    /// is subtracted to obtain the target values for the peak.
+    fn refine(
+        pseudo_voigt: &PseudoVoigt,
+        x: &[f64],
+        y: &[f64],
+        superpositions: &[f64],
+    ) -> PseudoVoigt {
+        let targets = x
+            .iter()
+            .zip(y.iter())
+            .zip(superpositions.iter())
+            .map(|((&x, &y), &superposition)| y - superposition + pseudo_voigt.evaluate(x))
+            .collect::<Vec<f64>>();
+        let cost = |candidate: &PseudoVoigt| {
+            x.iter()
+                .zip(targets.iter())
+                .map(|(&x, &target)| (target - candidate.evaluate(x)).powi(2))
+                .sum::<f64>()
+        };
+        let initial_cost = cost(pseudo_voigt);
+        let mut normal_matrix = [0.0; 16];
+        let mut gradient = [0.0; 4];
+        x.iter()
+            .zip(targets.iter())
+            .for_each(|(&x, &target)| {
+                let jacobian = Self::jacobian(pseudo_voigt, x);
+                let residual = target - pseudo_voigt.evaluate(x);
+                for i in 0..4 {
+                    gradient[i] += jacobian[i] * residual;
+                    for j in 0..4 {
+                        normal_matrix[i * 4 + j] += jacobian[i] * jacobian[j];
+                    }
+                }
+            });
+
+        let mut damping = 1e-3;
+        for _ in 0..10 {
+            let mut damped = normal_matrix.to_vec();
+            for i in 0..4 {
+                damped[i * 4 + i] += damping * normal_matrix[i * 4 + i] + f64::MIN_POSITIVE;
+            }
+            if let Some(step) = solve_linear_system(damped, gradient.to_vec()) {
+                let (eta, hw, maxp, sf) = pseudo_voigt.parameters();
+                let candidate = PseudoVoigt::new(
+                    (eta + step[0]).clamp(0.0, 1.0),
+                    hw + step[1],
+                    maxp + step[2],
+                    sf + step[3],
+                );
+                if candidate.hw() > 0.0 && candidate.sf() > 0.0 && cost(&candidate) < initial_cost {
+                    return candidate;
+                }
+            }
+            damping *= 10.0;
+        }
+
+        *pseudo_voigt
+    }
+
+    /// Internal helper function to compute the partial derivatives of the
+    /// pseudo-Voigt function with respect to `(eta, hw, maxp, sf)` at the
+    /// position `x`.
+    fn jacobian(pseudo_voigt: &PseudoVoigt, x: f64) -> [f64; 4] {
+        let (eta, hw, maxp, sf) = pseudo_voigt.parameters();
+        let (lorentzian, gaussian) = pseudo_voigt.components(x);
+        let distance = x - maxp;
+        let hw2 = hw.powi(2);
+        let denominator = (hw2 + distance.powi(2)).powi(2);
+        let d_lorentzian_d_hw = 2.0 * hw * distance.powi(2) / denominator;
+        let d_lorentzian_d_maxp = 2.0 * hw2 * distance / denominator;
+        let d_gaussian_d_hw = gaussian * 2.0 * LN_2 * distance.powi(2) / (hw2 * hw);
+        let d_gaussian_d_maxp = gaussian * 2.0 * LN_2 * distance / hw2;
+
+        [
+            sf * (lorentzian - gaussian),
+            sf * (eta * d_lorentzian_d_hw + (1.0 - eta) * d_gaussian_d_hw),
+            sf * (eta * d_lorentzian_d_maxp + (1.0 - eta) * d_gaussian_d_maxp),
+            eta * lorentzian + (1.0 - eta) * gaussian,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::peak_selection::{DetectorOnly, Selector};
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(FitterPseudoVoigt);
+        assert_sync!(FitterPseudoVoigt);
+    }
+
+    #[test]
+    fn jacobian() {
+        let pseudo_voigt = PseudoVoigt::new(0.4, 0.2, 1.0, 3.0);
+        let x = 1.13;
+        let h = 1e-7;
+        let analytical = FitterPseudoVoigt::jacobian(&pseudo_voigt, x);
+        let (eta, hw, maxp, sf) = pseudo_voigt.parameters();
+        let shifted = [
+            PseudoVoigt::new(eta + h, hw, maxp, sf),
+            PseudoVoigt::new(eta, hw + h, maxp, sf),
+            PseudoVoigt::new(eta, hw, maxp + h, sf),
+            PseudoVoigt::new(eta, hw, maxp, sf + h),
+        ];
+        analytical
+            .iter()
+            .zip(shifted.iter())
+            .for_each(|(derivative, shifted)| {
+                let numerical = (shifted.evaluate(x) - pseudo_voigt.evaluate(x)) / h;
+                assert_approx_eq!(f64, *derivative, numerical, epsilon = 1e-5);
+            });
+    }
+
+    #[test]
+    fn lower_mse_than_lorentzian() {
+        // Apodization with a Gaussian window turns the Lorentzian line shapes
+        // into Voigt profiles, which are approximated by pseudo-Voigts here.
+        let signals = [
+            PseudoVoigt::new(0.4, 0.010, 4.60, 1.0),
+            PseudoVoigt::new(0.4, 0.012, 4.75, 2.0),
+            PseudoVoigt::new(0.4, 0.012, 4.80, 1.5),
+            PseudoVoigt::new(0.4, 0.015, 5.20, 3.0),
+            PseudoVoigt::new(0.4, 0.010, 5.40, 0.5),
+        ];
+        let chemical_shifts = (0..2001)
+            .map(|i| 4.0 + i as f64 * 0.001)
+            .collect::<Vec<f64>>();
+        let intensities = PseudoVoigt::superposition_vec(&chemical_shifts, &signals);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (4.5, 5.5)).unwrap();
+        let peaks = DetectorOnly::new()
+            .select_peaks(
+                spectrum.intensities(),
+                spectrum.signal_boundaries_indices(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let mse = |superpositions: Vec<f64>| {
+            superpositions
+                .iter()
+                .zip(spectrum.intensities().iter())
+                .map(|(superposition, intensity)| (superposition - intensity).powi(2))
+                .sum::<f64>()
+                / spectrum.len() as f64
+        };
+        let lorentzians = FitterAnalytical::new(10)
+            .unwrap()
+            .fit_lorentzian(&spectrum, &peaks);
+        let lorentzian_mse = mse(crate::deconvolution::Lorentzian::superposition_vec(
+            spectrum.chemical_shifts(),
+            &lorentzians,
+        ));
+        let fitter = FitterPseudoVoigt::new(10, 0.5).unwrap();
+        let mut fits = vec![fitter.fit_pseudo_voigt(&spectrum, &peaks, None)];
+        #[cfg(feature = "parallel")]
+        fits.push(fitter.par_fit_pseudo_voigt(&spectrum, &peaks, None));
+        fits.into_iter().for_each(|pseudo_voigts| {
+            assert_eq!(pseudo_voigts.len(), signals.len());
+            let pseudo_voigt_mse = mse(PseudoVoigt::superposition_vec(
+                spectrum.chemical_shifts(),
+                &pseudo_voigts,
+            ));
+            assert!(pseudo_voigt_mse < lorentzian_mse);
+            pseudo_voigts
+                .iter()
+                .zip(signals.iter())
+                .for_each(|(fitted, expected)| {
+                    assert_approx_eq!(f64, fitted.maxp(), expected.maxp(), epsilon = 1e-3);
+                    assert_approx_eq!(f64, fitted.eta(), expected.eta(), epsilon = 5e-2);
+                });
+        });
+    }
+}
@@ -0,0 +1,73 @@
+/// Solves the dense linear system `matrix * x = rhs` for `x`.
+///
+/// The matrix is stored in row-major order and must be square with the same
+/// dimension as `rhs`. Gaussian elimination with partial pivoting is used,
+/// which is sufficient for the small, symmetric positive definite systems that
+/// arise from the normal equations of the iterative fitting algorithms.
+///
+/// Returns `None` if the matrix is (numerically) singular.
+pub(crate) fn solve_linear_system(mut matrix: Vec<f64>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let n = rhs.len();
+    debug_assert_eq!(matrix.len(), n * n);
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|a, b| {
+            f64::abs(matrix[a * n + column]).total_cmp(&f64::abs(matrix[b * n + column]))
+        })?;
+        if f64::abs(matrix[pivot * n + column]) < f64::MIN_POSITIVE {
+            return None;
+        }
+        if pivot != column {
+            for k in 0..n {
+                matrix.swap(pivot * n + k, column * n + k);
+            }
+            rhs.swap(pivot, column);
+        }
+        for row in (column + 1)..n {
+            let factor = matrix[row * n + column] / matrix[column * n + column];
+            if factor == 0.0 {
+                continue;
+            }
+            for k in column..n {
+                matrix[row * n + k] -= factor * matrix[column * n + k];
+            }
+            rhs[row] -= factor * rhs[column];
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum = ((row + 1)..n)
+            .map(|k| matrix[row * n + k] * solution[k])
+            .sum::<f64>();
+        solution[row] = (rhs[row] - sum) / matrix[row * n + row];
+    }
+
+    match solution.iter().all(|value| value.is_finite()) {
+        true => Some(solution),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn solve() {
+        let matrix = vec![0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0];
+        let rhs = vec![5.0, 5.0, 12.0];
+        let solution = solve_linear_system(matrix, rhs).unwrap();
+        assert_approx_eq!(f64, solution[0], 1.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, solution[1], 1.0, epsilon = 1e-12);
+        assert_approx_eq!(f64, solution[2], 3.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn singular() {
+        let matrix = vec![1.0, 2.0, 2.0, 4.0];
+        let rhs = vec![1.0, 2.0];
+        assert!(solve_linear_system(matrix, rhs).is_none());
+    }
+}
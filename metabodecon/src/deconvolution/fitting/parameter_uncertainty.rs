@@ -0,0 +1,84 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Standard errors of the parameters of a single fitted signal.
+///
+/// Fitting methods that minimize the sum of squared residuals estimate the
+/// covariance matrix of the transformed parameters `(sfhw, hw2, maxp)` of each
+/// group of overlapping signals as
+///
+/// ```text
+/// covariance = inverse(J^T J) * residual_sum_of_squares / (n - p)
+/// ```
+///
+/// where `J` is the Jacobian of the superposition with respect to the
+/// parameters at the data points of the group, `n` the number of data points
+/// and `p` the number of parameters. The standard errors of the parameters of
+/// the signal are then propagated from the covariance matrix to first order.
+/// They are reported in the same order as the signals of the
+/// [`Deconvolution`]. See [`Deconvolution::parameter_uncertainties`].
+///
+/// The estimate assumes independent noise of constant variance and a fit that
+/// converged to the least squares solution. Strongly overlapping signals are
+/// correlated, which shows up as larger standard errors. If the data points
+/// don't determine the parameters, e.g. because there are fewer data points
+/// than parameters, the standard errors are infinite.
+///
+/// [`Deconvolution`]: crate::deconvolution::Deconvolution
+/// [`Deconvolution::parameter_uncertainties`]: crate::deconvolution::Deconvolution::parameter_uncertainties
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ParameterUncertainty {
+    /// Standard error of the scale factor.
+    sf: f64,
+    /// Standard error of the half-width in ppm.
+    hw: f64,
+    /// Standard error of the maximum position in ppm.
+    maxp: f64,
+}
+
+impl ParameterUncertainty {
+    /// Internal helper function to construct the uncertainties from the
+    /// standard errors of the scale factor, the half-width and the maximum
+    /// position.
+    pub(crate) fn new(sf: f64, hw: f64, maxp: f64) -> Self {
+        Self { sf, hw, maxp }
+    }
+
+    /// Internal helper function to construct uncertainties for a signal whose
+    /// parameters are not determined by the data.
+    pub(crate) fn undetermined() -> Self {
+        Self::new(f64::INFINITY, f64::INFINITY, f64::INFINITY)
+    }
+
+    /// Returns the standard error of the scale factor.
+    pub fn sf(&self) -> f64 {
+        self.sf
+    }
+
+    /// Returns the standard error of the half-width in ppm.
+    pub fn hw(&self) -> f64 {
+        self.hw
+    }
+
+    /// Returns the standard error of the maximum position in ppm.
+    pub fn maxp(&self) -> f64 {
+        self.maxp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(ParameterUncertainty);
+        assert_sync!(ParameterUncertainty);
+    }
+}
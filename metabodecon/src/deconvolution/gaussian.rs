@@ -0,0 +1,701 @@
+use crate::deconvolution::peak_shape::PeakShape;
+use std::f64::consts::{LN_2, PI};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Data structure that represents a [Gaussian function].
+///
+/// # Definition
+///
+/// The [Gaussian function] is typically defined as:
+///
+/// ```text
+/// f(x) = a * exp(-(x - x₀)² / (2σ²))
+/// ```
+///
+/// - `a` is the height of the maximum.
+/// - `x₀` is the position of the maximum (`maxp`).
+/// - `σ` is the standard deviation.
+///
+/// To make the parameters comparable to those of a [`Lorentzian`], the
+/// standard deviation is replaced by the half-width at half-maximum `hw`,
+/// using `2σ² = hw² / ln(2)`. With a scale factor `sf` in place of `a`, this
+/// results in:
+///
+/// ```text
+/// f(x) = sf * exp(-ln(2) * (x - maxp)² / hw²)
+/// ```
+///
+/// NMR signals are usually Lorentzian in shape, but apodization with a
+/// Gaussian window function changes the line shape, in which case the signals
+/// are better described by Gaussian functions.
+///
+/// [Gaussian function]: https://en.wikipedia.org/wiki/Gaussian_function
+/// [`Lorentzian`]: crate::deconvolution::Lorentzian
+///
+/// # Negative Parameters
+///
+/// For `Gaussian` to represent a valid peak shape, the parameters `sf` and
+/// `hw` must be positive. This is not enforced to avoid unnecessary overhead.
+/// Instances created by the library are guaranteed to have valid values, but
+/// if you construct a `Gaussian` manually, you are responsible for ensuring the
+/// parameters are valid.
+///
+/// # Thread Safety
+///
+/// The `Gaussian` type is both [`Send`] and [`Sync`], allowing safe sharing
+/// and access across threads.
+///
+/// # Serialization with [Serde]
+///
+/// [Serde]: https://serde.rs/
+///
+/// When the `serde` feature is enabled, `Gaussian` can be serialized and
+/// deserialized using `serde`. The parameters `(sf, hw, maxp)` are stored as
+/// they are.
+///
+/// # Example
+///
+/// ```
+/// use float_cmp::assert_approx_eq;
+/// use metabodecon::deconvolution::Gaussian;
+///
+/// // Create a Gaussian peak centered at 5.0 ppm with maximum intensity 2.0 and hw 0.15.
+/// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+///
+/// // Evaluate the Gaussian at its maximum position and at the half-width.
+/// assert_approx_eq!(f64, gaussian.evaluate(5.0), 2.0);
+/// assert_approx_eq!(f64, gaussian.evaluate(5.15), 1.0, epsilon = 1e-12);
+///
+/// // Generate 100 chemical shifts between 0.0 and 10.0 ppm.
+/// let chemical_shifts = (0..100)
+///     .map(|x| x as f64 * 10.0 / 99.0)
+///     .collect::<Vec<f64>>();
+///
+/// // Evaluate the Gaussian at the chemical shifts.
+/// let intensities = gaussian.evaluate_vec(&chemical_shifts);
+///
+/// // Create a peak triplet centered at 5.0 ppm with shorter side peaks.
+/// let triplet = [
+///     Gaussian::new(1.0, 0.03, 4.8),
+///     Gaussian::new(2.0, 0.02, 5.0),
+///     Gaussian::new(1.0, 0.03, 5.2),
+/// ];
+///
+/// // Evaluate the superposition of the Gaussians at the maximum.
+/// assert_approx_eq!(
+///     f64,
+///     Gaussian::superposition(5.0, &triplet),
+///     2.0,
+///     epsilon = 1e-6
+/// );
+///
+/// // Evaluate the superposition of the Gaussians at the chemical shifts.
+/// let sup1 = Gaussian::superposition_vec(&chemical_shifts, &triplet);
+/// // ...or in parallel (less efficient for small datasets).
+/// let sup2 = Gaussian::par_superposition_vec(&chemical_shifts, &triplet);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename = "Gaussian")
+)]
+pub struct Gaussian {
+    /// Scale factor, the height of the maximum (`sf`).
+    sf: f64,
+    /// Half-width at half-maximum (`hw`).
+    hw: f64,
+    /// Position of the maximum (`maxp`).
+    maxp: f64,
+}
+
+impl AsRef<Gaussian> for Gaussian {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl PeakShape for Gaussian {
+    fn evaluate(&self, x: f64) -> f64 {
+        Gaussian::evaluate(self, x)
+    }
+
+    fn integral(&self) -> f64 {
+        Gaussian::integral(self)
+    }
+
+    fn integral_between(&self, a: f64, b: f64) -> f64 {
+        Gaussian::integral_between(self, a, b)
+    }
+
+    fn maxp(&self) -> f64 {
+        self.maxp
+    }
+
+    fn hw(&self) -> f64 {
+        self.hw
+    }
+}
+
+impl Gaussian {
+    /// Constructs a new `Gaussian` from the given parameters.
+    ///
+    /// - `sf`: The scale factor, which is the height of the maximum.
+    /// - `hw`: The half-width at half-maximum.
+    /// - `maxp`: The position of the maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// // Signal centered at 5 ppm with maximum intensity 2.0 and a half width
+    /// // of 0.15 ppm.
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// ```
+    pub fn new(sf: f64, hw: f64, maxp: f64) -> Self {
+        Self { sf, hw, maxp }
+    }
+
+    /// Returns the scale factor (`sf`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.sf(), 2.0);
+    /// ```
+    pub fn sf(&self) -> f64 {
+        self.sf
+    }
+
+    /// Returns the half-width at half-maximum (`hw`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.hw(), 0.15);
+    /// ```
+    pub fn hw(&self) -> f64 {
+        self.hw
+    }
+
+    /// Returns the position of the maximum (`maxp`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.maxp(), 5.0);
+    /// ```
+    pub fn maxp(&self) -> f64 {
+        self.maxp
+    }
+
+    /// Returns the parameters as a tuple `(sf, hw, maxp)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// let (sf, hw, maxp) = gaussian.parameters();
+    ///
+    /// assert_approx_eq!(f64, sf, 2.0);
+    /// assert_approx_eq!(f64, hw, 0.15);
+    /// assert_approx_eq!(f64, maxp, 5.0);
+    /// ```
+    pub fn parameters(&self) -> (f64, f64, f64) {
+        (self.sf, self.hw, self.maxp)
+    }
+
+    /// Internal helper function to check whether the `Gaussian` represents a
+    /// valid peak shape, i.e. `sf` is finite and non-negative, `hw` is finite
+    /// and positive and `maxp` is finite.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.sf.is_finite()
+            && self.sf >= 0.0
+            && self.hw.is_finite()
+            && self.hw > 0.0
+            && self.maxp.is_finite()
+    }
+
+    /// Sets the scale factor (`sf`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let mut gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// gaussian.set_sf(3.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.sf(), 3.0);
+    /// ```
+    pub fn set_sf(&mut self, sf: f64) {
+        self.sf = sf;
+    }
+
+    /// Sets the half-width at half-maximum (`hw`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let mut gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// gaussian.set_hw(0.3);
+    ///
+    /// assert_approx_eq!(f64, gaussian.hw(), 0.3);
+    /// ```
+    pub fn set_hw(&mut self, hw: f64) {
+        self.hw = hw;
+    }
+
+    /// Sets the position of the maximum (`maxp`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let mut gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// gaussian.set_maxp(-5.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.maxp(), -5.0);
+    /// ```
+    pub fn set_maxp(&mut self, maxp: f64) {
+        self.maxp = maxp;
+    }
+
+    /// Sets the parameters `(sf, hw, maxp)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let mut gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// gaussian.set_parameters(3.0, 0.3, -5.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.sf(), 3.0);
+    /// assert_approx_eq!(f64, gaussian.hw(), 0.3);
+    /// assert_approx_eq!(f64, gaussian.maxp(), -5.0);
+    /// ```
+    pub fn set_parameters(&mut self, sf: f64, hw: f64, maxp: f64) {
+        self.sf = sf;
+        self.hw = hw;
+        self.maxp = maxp;
+    }
+
+    /// Evaluates the `Gaussian` function at a given position `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    ///
+    /// assert_approx_eq!(f64, gaussian.evaluate(5.0), 2.0);
+    /// ```
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.sf * f64::exp(-LN_2 * (x - self.maxp).powi(2) / self.hw.powi(2))
+    }
+
+    /// Evaluates the `Gaussian` function at the given positions `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = gaussian.evaluate_vec(&chemical_shifts);
+    /// ```
+    pub fn evaluate_vec(&self, x: &[f64]) -> Vec<f64> {
+        x.iter().map(|&x| self.evaluate(x)).collect()
+    }
+
+    /// Computes the integral of the `Gaussian` over the entire domain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// let integral = gaussian.integral();
+    ///
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     integral,
+    ///     2.0 * 0.15 * (std::f64::consts::PI / std::f64::consts::LN_2).sqrt()
+    /// );
+    /// ```
+    pub fn integral(&self) -> f64 {
+        self.sf * self.hw * f64::sqrt(PI / LN_2)
+    }
+
+    /// Computes the integral of the `Gaussian` over the interval `[a, b]`.
+    ///
+    /// The closed form of the integral is
+    ///
+    /// ```text
+    /// integral / 2 * (erf(sqrt(ln(2)) * (b - maxp) / hw) - erf(sqrt(ln(2)) * (a - maxp) / hw))
+    /// ```
+    ///
+    /// where the difference of the error functions is evaluated with the
+    /// complementary error function if both bounds lie on the same side of
+    /// `maxp`, to remain accurate for intervals in the tails. If `a > b`, the
+    /// result is negated, as is usual for definite integrals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// let (a, b) = (4.8, 5.5);
+    ///
+    /// // Composite Simpson's rule with a fine grid.
+    /// let n = 10_000;
+    /// let step = (b - a) / n as f64;
+    /// let quadrature = (0..=n)
+    ///     .map(|i| {
+    ///         let weight = match i {
+    ///             0 => 1.0,
+    ///             i if i == n => 1.0,
+    ///             i if i % 2 == 1 => 4.0,
+    ///             _ => 2.0,
+    ///         };
+    ///         weight * gaussian.evaluate(a + i as f64 * step)
+    ///     })
+    ///     .sum::<f64>()
+    ///     * step
+    ///     / 3.0;
+    ///
+    /// assert_approx_eq!(f64, gaussian.integral_between(a, b), quadrature, epsilon = 1e-9);
+    /// assert_approx_eq!(f64, gaussian.integral_between(b, a), -quadrature, epsilon = 1e-9);
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     gaussian.integral_between(f64::NEG_INFINITY, f64::INFINITY),
+    ///     gaussian.integral(),
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn integral_between(&self, a: f64, b: f64) -> f64 {
+        let scale = LN_2.sqrt() / self.hw;
+        let (u, v) = (scale * (a - self.maxp), scale * (b - self.maxp));
+        let difference = match (u >= 0.0, v >= 0.0) {
+            (true, true) => erfc(u) - erfc(v),
+            (false, false) => erfc(-v) - erfc(-u),
+            (false, true) => (1.0 - erfc(v)) + (1.0 - erfc(-u)),
+            (true, false) => -(1.0 - erfc(u)) - (1.0 - erfc(-v)),
+        };
+
+        0.5 * self.integral() * difference
+    }
+
+    /// Evaluates the superposition of the given `Gaussian`s at the given
+    /// position `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let triplet = [
+    ///     Gaussian::new(1.0, 0.1, 4.8),
+    ///     Gaussian::new(2.0, 0.1, 5.0),
+    ///     Gaussian::new(1.0, 0.1, 5.2),
+    /// ];
+    ///
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     Gaussian::superposition(4.9, &triplet),
+    ///     1.501953125,
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn superposition<G: AsRef<Gaussian>>(x: f64, gaussians: &[G]) -> f64 {
+        gaussians
+            .iter()
+            .map(|gaussian| gaussian.as_ref().evaluate(x))
+            .sum()
+    }
+
+    /// Evaluates the superposition of the given `Gaussian`s at the given
+    /// positions `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let triplet = [
+    ///     Gaussian::new(1.0, 0.03, 4.8),
+    ///     Gaussian::new(2.0, 0.02, 5.0),
+    ///     Gaussian::new(1.0, 0.03, 5.2),
+    /// ];
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let sup = Gaussian::superposition_vec(&chemical_shifts, &triplet);
+    /// ```
+    pub fn superposition_vec<G: AsRef<Gaussian>>(x: &[f64], gaussians: &[G]) -> Vec<f64> {
+        x.iter()
+            .map(|&x| Self::superposition(x, gaussians))
+            .collect()
+    }
+
+    /// Evaluates the superposition of the given `Gaussian`s at the given
+    /// positions `x` in parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Gaussian;
+    ///
+    /// let triplet = [
+    ///     Gaussian::new(1.0, 0.03, 4.8),
+    ///     Gaussian::new(2.0, 0.02, 5.0),
+    ///     Gaussian::new(1.0, 0.03, 5.2),
+    /// ];
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let sup = Gaussian::par_superposition_vec(&chemical_shifts, &triplet);
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_superposition_vec<G: AsRef<Gaussian> + Send + Sync>(
+        x: &[f64],
+        gaussians: &[G],
+    ) -> Vec<f64> {
+        x.par_iter()
+            .map(|&x| Self::superposition(x, gaussians))
+            .collect()
+    }
+}
+
+/// Internal helper function to evaluate the complementary error function for
+/// non-negative `x`, with a relative error below `1e-12`.
+///
+/// Small arguments use the power series of the error function, large ones the
+/// continued fraction of the complementary error function, which converges
+/// quickly there and keeps the relative accuracy in the tail.
+fn erfc(x: f64) -> f64 {
+    if x < 2.0 {
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term > 1e-17 * sum {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        return 1.0 - 2.0 / PI.sqrt() * f64::exp(-x * x) * sum;
+    }
+    let fraction = (1..=60)
+        .rev()
+        .fold(x, |fraction, n| x + 0.5 * n as f64 / fraction);
+
+    f64::exp(-x * x) / (PI.sqrt() * fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Gaussian);
+        assert_sync!(Gaussian);
+    }
+
+    #[test]
+    fn accessors() {
+        let gaussian = Gaussian::new(2.0, 0.5, 1.0);
+        assert_approx_eq!(f64, gaussian.sf(), 2.0);
+        assert_approx_eq!(f64, gaussian.hw(), 0.5);
+        assert_approx_eq!(f64, gaussian.maxp(), 1.0);
+    }
+
+    #[test]
+    fn mutators() {
+        let mut gaussian = Gaussian::new(1.0, 1.0, 0.0);
+        gaussian.set_sf(1.5);
+        gaussian.set_hw(2.5);
+        gaussian.set_maxp(1.0);
+        assert_approx_eq!(f64, gaussian.sf(), 1.5);
+        assert_approx_eq!(f64, gaussian.hw(), 2.5);
+        assert_approx_eq!(f64, gaussian.maxp(), 1.0);
+        gaussian.set_parameters(1.0, 1.0, 0.0);
+        assert_approx_eq!(f64, gaussian.sf(), 1.0);
+        assert_approx_eq!(f64, gaussian.hw(), 1.0);
+        assert_approx_eq!(f64, gaussian.maxp(), 0.0);
+    }
+
+    #[test]
+    fn evaluate() {
+        let gaussian = Gaussian::new(1.0, 1.0, 0.0);
+        let chemical_shifts = (0..11)
+            .map(|x| -5.0 + x as f64)
+            .collect::<Vec<f64>>();
+        let expected_intensities = chemical_shifts
+            .iter()
+            .map(|x| 0.5_f64.powf(x * x))
+            .collect::<Vec<f64>>();
+        chemical_shifts
+            .iter()
+            .zip(expected_intensities.iter())
+            .for_each(|(&x, &y)| {
+                assert_approx_eq!(f64, gaussian.evaluate(x), y);
+            });
+        let computed_intensities = gaussian.evaluate_vec(&chemical_shifts);
+        computed_intensities
+            .iter()
+            .zip(expected_intensities.iter())
+            .for_each(|(&yc, &ye)| {
+                assert_approx_eq!(f64, yc, ye);
+            });
+    }
+
+    #[test]
+    fn integral() {
+        let gaussian = Gaussian::new(3.0, 0.2, 1.0);
+        let step = 1e-4;
+        let numerical = (0..20_000)
+            .map(|i| gaussian.evaluate(i as f64 * step) * step)
+            .sum::<f64>();
+        assert_approx_eq!(f64, gaussian.integral(), numerical, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn integral_between() {
+        let gaussian = Gaussian::new(1.0, LN_2.sqrt(), 0.0);
+        let half = 0.5 * PI.sqrt();
+        assert_approx_eq!(
+            f64,
+            gaussian.integral_between(0.0, 1.0),
+            half * 0.8427007929497149,
+            epsilon = 1e-15
+        );
+        assert_approx_eq!(
+            f64,
+            gaussian.integral_between(-1.0, 1.0),
+            2.0 * half * 0.8427007929497149,
+            epsilon = 1e-15
+        );
+        assert_approx_eq!(
+            f64,
+            gaussian.integral_between(-4.0, -3.0),
+            half * 2.207507974068516e-5,
+            epsilon = 1e-16
+        );
+        assert_approx_eq!(
+            f64,
+            gaussian.integral_between(4.0, 3.0),
+            -half * 2.207507974068516e-5,
+            epsilon = 1e-16
+        );
+        assert_approx_eq!(f64, gaussian.integral_between(2.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn superposition() {
+        let gaussians = vec![
+            Gaussian::new(1.0, 1.0, -2.0),
+            Gaussian::new(2.0, 1.0, 0.0),
+            Gaussian::new(1.0, 1.0, 2.0),
+        ];
+        let chemical_shifts = (0..11)
+            .map(|x| -5.0 + x as f64)
+            .collect::<Vec<f64>>();
+        let expected_intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                0.5_f64.powf((x + 2.0).powi(2))
+                    + 2.0 * 0.5_f64.powf(x.powi(2))
+                    + 0.5_f64.powf((x - 2.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        chemical_shifts
+            .iter()
+            .zip(expected_intensities.iter())
+            .for_each(|(&x, &y)| {
+                assert_approx_eq!(f64, Gaussian::superposition(x, &gaussians), y);
+            });
+        let computed_intensities = Gaussian::superposition_vec(&chemical_shifts, &gaussians);
+        computed_intensities
+            .iter()
+            .zip(expected_intensities.iter())
+            .for_each(|(&yc, &ye)| {
+                assert_approx_eq!(f64, yc, ye);
+            });
+        #[cfg(feature = "parallel")]
+        {
+            let computed_intensities =
+                Gaussian::par_superposition_vec(&chemical_shifts, &gaussians);
+            computed_intensities
+                .iter()
+                .zip(expected_intensities.iter())
+                .for_each(|(&yc, &ye)| {
+                    assert_approx_eq!(f64, yc, ye);
+                });
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialization_round_trip() {
+        let gaussians = vec![
+            Gaussian::new(5.5, 0.25, 3.0),
+            Gaussian::new(7.0, 0.16, 5.0),
+            Gaussian::new(5.5, 0.25, 7.0),
+        ];
+        let serialized = serde_json::to_string(&gaussians).unwrap();
+        let deserialized = serde_json::from_str::<Vec<Gaussian>>(&serialized).unwrap();
+        gaussians
+            .iter()
+            .zip(deserialized.iter())
+            .for_each(|(init, rec)| {
+                assert_approx_eq!(f64, init.sf(), rec.sf());
+                assert_approx_eq!(f64, init.hw(), rec.hw());
+                assert_approx_eq!(f64, init.maxp(), rec.maxp());
+            });
+    }
+}
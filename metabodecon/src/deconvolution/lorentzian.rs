@@ -1,6 +1,12 @@
+use crate::deconvolution::peak_shape::PeakShape;
+use crate::deconvolution::precomputed_lorentzian::PrecomputedLorentzian;
+
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+#[cfg(feature = "simd")]
+use wide::f64x4;
+
 #[cfg(feature = "serde")]
 use crate::deconvolution::SerializedLorentzian;
 #[cfg(feature = "serde")]
@@ -49,13 +55,13 @@ use serde::{Deserialize, Serialize};
 ///
 /// # Negative Transformed Parameters
 ///
-/// For `Lorentzian` to represent a valid peak shape, the transformed parameters
-/// `sfhw` and `hw2` must be positive. Additionally, if `hw2` is negative,
-/// attempting to recover `hw` by taking the square root will corrupt the data.
-/// This is not enforced to avoid unnecessary overhead. Instances created by the
-/// library are guaranteed to have valid values, but if you construct a
-/// `Lorentzian` manually, you are responsible for ensuring the parameters are
-/// valid.
+/// For `Lorentzian` to represent a valid peak shape, the transformed parameter
+/// `sfhw` must be non-negative and `hw2` must be positive. Additionally, if
+/// `hw2` is negative, attempting to recover `hw` by taking the square root will
+/// corrupt the data. This is not enforced to avoid unnecessary overhead.
+/// Instances created by the library are guaranteed to have valid values, but if
+/// you construct a `Lorentzian` manually, you are responsible for ensuring the
+/// parameters are valid.
 ///
 /// # Thread Safety
 ///
@@ -150,6 +156,28 @@ impl AsRef<Lorentzian> for Lorentzian {
     }
 }
 
+impl PeakShape for Lorentzian {
+    fn evaluate(&self, x: f64) -> f64 {
+        Lorentzian::evaluate(self, x)
+    }
+
+    fn integral(&self) -> f64 {
+        Lorentzian::integral(self)
+    }
+
+    fn integral_between(&self, a: f64, b: f64) -> f64 {
+        Lorentzian::integral_between(self, a, b)
+    }
+
+    fn maxp(&self) -> f64 {
+        self.maxp
+    }
+
+    fn hw(&self) -> f64 {
+        Lorentzian::hw(self)
+    }
+}
+
 impl Lorentzian {
     /// Constructs a new `Lorentzian` from the given parameters.
     ///
@@ -174,12 +202,12 @@ impl Lorentzian {
     /// # Negative Transformed Parameters
     ///
     /// For `Lorentzian` to represent a valid peak shape, the transformed
-    /// parameters `sfhw` and `hw2` must be positive. Additionally, if `hw2` is
-    /// negative, attempting to recover `hw` by taking the square root will
-    /// corrupt the data. This is not enforced to avoid unnecessary overhead.
-    /// Instances created by the library are guaranteed to have valid values,
-    /// but if you construct a `Lorentzian` manually, you are responsible for
-    /// ensuring the parameters are valid.
+    /// parameter `sfhw` must be non-negative and `hw2` must be positive.
+    /// Additionally, if `hw2` is negative, attempting to recover `hw` by taking
+    /// the square root will corrupt the data. This is not enforced to avoid
+    /// unnecessary overhead. Instances created by the library are guaranteed to
+    /// have valid values, but if you construct a `Lorentzian` manually, you are
+    /// responsible for ensuring the parameters are valid.
     ///
     /// # Example
     ///
@@ -293,6 +321,20 @@ impl Lorentzian {
         (self.sfhw, self.hw2, self.maxp)
     }
 
+    /// Internal helper function to check whether the `Lorentzian` represents a
+    /// valid peak shape, i.e. `sfhw` is finite and non-negative, `hw2` is
+    /// finite and positive and `maxp` is finite. See [Negative Transformed
+    /// Parameters].
+    ///
+    /// [Negative Transformed Parameters]: Lorentzian#negative-transformed-parameters
+    pub(crate) fn is_valid(&self) -> bool {
+        self.sfhw.is_finite()
+            && self.sfhw >= 0.0
+            && self.hw2.is_finite()
+            && self.hw2 > 0.0
+            && self.maxp.is_finite()
+    }
+
     /// Sets the scale factor multiplied by the half-width (`sfhw`).
     ///
     /// This is part of the transformed parameter interface. Modifying `sfhw`
@@ -454,6 +496,34 @@ impl Lorentzian {
         (self.sf(), self.hw(), self.maxp())
     }
 
+    /// Computes the untransformed half-width (`hw`) and scale factor (`sf`)
+    /// once and returns them cached alongside the `Lorentzian`.
+    ///
+    /// This avoids repeating the square root of [`hw`] and [`sf`] when these,
+    /// or quantities derived from them like the [`integral`], are needed
+    /// multiple times. See [`PrecomputedLorentzian`] for details.
+    ///
+    /// [`hw`]: Lorentzian::hw
+    /// [`sf`]: Lorentzian::sf
+    /// [`integral`]: Lorentzian::integral
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let precomputed = lorentzian.precompute();
+    ///
+    /// assert_approx_eq!(f64, precomputed.sf(), 0.3);
+    /// assert_approx_eq!(f64, precomputed.hw(), 0.15);
+    /// assert_approx_eq!(f64, precomputed.integral(), lorentzian.integral());
+    /// ```
+    pub fn precompute(&self) -> PrecomputedLorentzian {
+        PrecomputedLorentzian::from(*self)
+    }
+
     /// Sets the untransformed scale factor (`sf`).
     ///
     /// This is part of the untransformed parameter interface. Modifying `sf`
@@ -531,6 +601,72 @@ impl Lorentzian {
         self.maxp = maxp;
     }
 
+    /// Returns the full width at half maximum (`fwhm = 2 * hw`).
+    ///
+    /// Like [`hw`], computing `fwhm` requires a square root.
+    ///
+    /// [`hw`]: Lorentzian::hw
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// // Half width of 0.15 ppm.
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    ///
+    /// assert_approx_eq!(f64, lorentzian.fwhm(), 0.3);
+    /// ```
+    pub fn fwhm(&self) -> f64 {
+        2.0 * self.hw()
+    }
+
+    /// Returns the intensity at the position of the maximum
+    /// (`sfhw / hw2 = sf / hw`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    ///
+    /// assert_approx_eq!(f64, lorentzian.max_intensity(), 2.0);
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     lorentzian.max_intensity(),
+    ///     lorentzian.evaluate(lorentzian.maxp())
+    /// );
+    /// ```
+    pub fn max_intensity(&self) -> f64 {
+        self.sfhw / self.hw2
+    }
+
+    /// Returns the intensity at half maximum, which is reached at a distance of
+    /// `hw` from the position of the maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    ///
+    /// assert_approx_eq!(f64, lorentzian.value_at_half_max(), 1.0);
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     lorentzian.value_at_half_max(),
+    ///     lorentzian.evaluate(lorentzian.maxp() + lorentzian.hw()),
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn value_at_half_max(&self) -> f64 {
+        0.5 * self.max_intensity()
+    }
+
     /// Evaluates the `Lorentzian` function at a given position `x`.
     ///
     /// # Example
@@ -564,6 +700,101 @@ impl Lorentzian {
         x.iter().map(|&x| self.evaluate(x)).collect()
     }
 
+    /// Evaluates the first derivative of the `Lorentzian` function at a given
+    /// position `x`.
+    ///
+    /// ```text
+    /// f'(x) = -2 * sfhw * (x - maxp) / (hw2 + (x - maxp)²)²
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let h = 1e-6;
+    ///
+    /// assert_approx_eq!(f64, lorentzian.derivative(5.0), 0.0);
+    /// [4.7, 4.9, 5.05, 5.3].into_iter().for_each(|x| {
+    ///     let central = (lorentzian.evaluate(x + h) - lorentzian.evaluate(x - h)) / (2.0 * h);
+    ///     assert_approx_eq!(f64, lorentzian.derivative(x), central, epsilon = 1e-6);
+    /// });
+    /// ```
+    pub fn derivative(&self, x: f64) -> f64 {
+        let dx = x - self.maxp;
+
+        -2.0 * self.sfhw * dx / (self.hw2 + dx.powi(2)).powi(2)
+    }
+
+    /// Evaluates the first derivative of the `Lorentzian` function at the
+    /// given positions `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let derivatives = lorentzian.derivative_vec(&chemical_shifts);
+    /// ```
+    pub fn derivative_vec(&self, x: &[f64]) -> Vec<f64> {
+        x.iter().map(|&x| self.derivative(x)).collect()
+    }
+
+    /// Evaluates the second derivative of the `Lorentzian` function at a given
+    /// position `x`.
+    ///
+    /// ```text
+    /// f''(x) = 2 * sfhw * (3 * (x - maxp)² - hw2) / (hw2 + (x - maxp)²)³
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let h = 1e-4;
+    ///
+    /// [4.7, 4.9, 5.0, 5.05, 5.3].into_iter().for_each(|x| {
+    ///     let central = (lorentzian.evaluate(x + h) - 2.0 * lorentzian.evaluate(x)
+    ///         + lorentzian.evaluate(x - h))
+    ///         / h.powi(2);
+    ///     assert_approx_eq!(f64, lorentzian.second_derivative(x), central, epsilon = 1e-3);
+    /// });
+    /// ```
+    pub fn second_derivative(&self, x: f64) -> f64 {
+        let dx2 = (x - self.maxp).powi(2);
+
+        2.0 * self.sfhw * (3.0 * dx2 - self.hw2) / (self.hw2 + dx2).powi(3)
+    }
+
+    /// Evaluates the second derivative of the `Lorentzian` function at the
+    /// given positions `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let second_derivatives = lorentzian.second_derivative_vec(&chemical_shifts);
+    /// ```
+    pub fn second_derivative_vec(&self, x: &[f64]) -> Vec<f64> {
+        x.iter()
+            .map(|&x| self.second_derivative(x))
+            .collect()
+    }
+
     /// Computes the integral of the `Lorentzian` over the entire domain.
     ///
     /// # Example
@@ -581,6 +812,132 @@ impl Lorentzian {
         std::f64::consts::PI * self.sf()
     }
 
+    /// Computes the integral of the `Lorentzian` over the interval `[a, b]`.
+    ///
+    /// The closed form of the integral is
+    ///
+    /// ```text
+    /// sf * (atan((b - maxp) / hw) - atan((a - maxp) / hw))
+    /// ```
+    ///
+    /// which is evaluated as a single `atan2` that doesn't divide by `hw`, to
+    /// remain accurate for narrow signals and intervals far from `maxp`. If
+    /// `a > b`, the result is negated, as is usual for definite integrals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let (a, b) = (4.8, 5.5);
+    ///
+    /// // Composite Simpson's rule with a fine grid.
+    /// let n = 10_000;
+    /// let step = (b - a) / n as f64;
+    /// let quadrature = (0..=n)
+    ///     .map(|i| {
+    ///         let weight = match i {
+    ///             0 => 1.0,
+    ///             i if i == n => 1.0,
+    ///             i if i % 2 == 1 => 4.0,
+    ///             _ => 2.0,
+    ///         };
+    ///         weight * lorentzian.evaluate(a + i as f64 * step)
+    ///     })
+    ///     .sum::<f64>()
+    ///     * step
+    ///     / 3.0;
+    ///
+    /// assert_approx_eq!(f64, lorentzian.integral_between(a, b), quadrature, epsilon = 1e-9);
+    /// assert_approx_eq!(f64, lorentzian.integral_between(b, a), -quadrature, epsilon = 1e-9);
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     lorentzian.integral_between(f64::NEG_INFINITY, f64::INFINITY),
+    ///     lorentzian.integral(),
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn integral_between(&self, a: f64, b: f64) -> f64 {
+        self.precompute().integral_between(a, b)
+    }
+
+    /// Computes the overlap of the `Lorentzian` with another `Lorentzian`.
+    ///
+    /// The overlap is the area below both curves, i.e. the integral of the
+    /// pointwise minimum of the two functions, normalized by the smaller of the
+    /// two integrals. The result lies within `[0, 1]`, where `0` means the
+    /// signals are completely separated and `1` means one signal lies entirely
+    /// below the other, which is the case for identical signals and for signals
+    /// that only differ in their scale factor.
+    ///
+    /// The curves intersect at most twice, so the integral is computed exactly
+    /// by splitting the domain at the intersections and integrating the lower
+    /// curve over each part with [`Lorentzian::integral_between`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let close = Lorentzian::new(0.045, 0.0225, 5.02);
+    /// let distant = Lorentzian::new(0.045, 0.0225, 8.0);
+    ///
+    /// assert_approx_eq!(f64, lorentzian.overlap(&lorentzian), 1.0);
+    /// assert!(lorentzian.overlap(&close) > 0.9);
+    /// assert!(lorentzian.overlap(&distant) < 0.1);
+    /// ```
+    pub fn overlap(&self, other: &Lorentzian) -> f64 {
+        let (lhs, rhs) = (self.precompute(), other.precompute());
+        let (a, b) = (self.sfhw, other.sfhw);
+        let shift = other.maxp - self.maxp;
+        let quadratic = a - b;
+        let linear = -2.0 * a * shift;
+        let constant = a * (other.hw2 + shift.powi(2)) - b * self.hw2;
+        let mut intersections = if quadratic == 0.0 {
+            match linear {
+                0.0 => Vec::new(),
+                _ => vec![-constant / linear],
+            }
+        } else {
+            let discriminant = linear.powi(2) - 4.0 * quadratic * constant;
+            match discriminant > 0.0 {
+                true => {
+                    let q = -0.5 * (linear + linear.signum() * discriminant.sqrt());
+                    vec![q / quadratic, constant / q]
+                }
+                false => Vec::new(),
+            }
+        };
+        intersections
+            .iter_mut()
+            .for_each(|x| *x += self.maxp);
+        intersections.sort_by(f64::total_cmp);
+        let mut bounds = vec![f64::NEG_INFINITY];
+        bounds.extend(intersections);
+        bounds.push(f64::INFINITY);
+        let shared = bounds
+            .windows(2)
+            .map(|w| {
+                let sample = match (w[0].is_finite(), w[1].is_finite()) {
+                    (true, true) => 0.5 * (w[0] + w[1]),
+                    (true, false) => w[0] + 1.0,
+                    (false, true) => w[1] - 1.0,
+                    (false, false) => self.maxp,
+                };
+                match self.evaluate(sample) <= other.evaluate(sample) {
+                    true => lhs.integral_between(w[0], w[1]),
+                    false => rhs.integral_between(w[0], w[1]),
+                }
+            })
+            .sum::<f64>();
+
+        (shared / f64::min(lhs.integral(), rhs.integral())).clamp(0.0, 1.0)
+    }
+
     /// Evaluates the superposition of the given `Lorentzian`s at the given
     /// position `x`.
     ///
@@ -629,9 +986,12 @@ impl Lorentzian {
     /// let sup = Lorentzian::superposition_vec(&chemical_shifts, &triplet);
     /// ```
     pub fn superposition_vec<L: AsRef<Lorentzian>>(x: &[f64], lorentzians: &[L]) -> Vec<f64> {
-        x.iter()
-            .map(|&x| Self::superposition(x, lorentzians))
-            .collect()
+        #[cfg(feature = "simd")]
+        let superposition = Self::simd_superposition_vec(x, lorentzians);
+        #[cfg(not(feature = "simd"))]
+        let superposition = Self::scalar_superposition_vec(x, lorentzians);
+
+        superposition
     }
 
     /// Evaluates the superposition of the given `Lorentzian`s at the given
@@ -657,10 +1017,66 @@ impl Lorentzian {
         x: &[f64],
         lorentzians: &[L],
     ) -> Vec<f64> {
-        x.par_iter()
+        #[cfg(feature = "simd")]
+        let superposition = x
+            .par_chunks(Self::PAR_CHUNK_SIZE)
+            .flat_map_iter(|x| Self::simd_superposition_vec(x, lorentzians))
+            .collect();
+        #[cfg(not(feature = "simd"))]
+        let superposition = x
+            .par_iter()
+            .map(|&x| Self::superposition(x, lorentzians))
+            .collect();
+
+        superposition
+    }
+
+    /// Number of positions evaluated by each task of the parallel
+    /// superposition when it is vectorized.
+    #[cfg(all(feature = "parallel", feature = "simd"))]
+    const PAR_CHUNK_SIZE: usize = 1024;
+
+    /// Internal helper function to evaluate the superposition of the given
+    /// `Lorentzian`s at the given positions `x`, one position at a time.
+    #[cfg(any(test, not(feature = "simd")))]
+    fn scalar_superposition_vec<L: AsRef<Lorentzian>>(x: &[f64], lorentzians: &[L]) -> Vec<f64> {
+        x.iter()
             .map(|&x| Self::superposition(x, lorentzians))
             .collect()
     }
+
+    /// Internal helper function to evaluate the superposition of the given
+    /// `Lorentzian`s at the given positions `x`, processing 4 positions at a
+    /// time in SIMD lanes. The remaining positions are evaluated one at a time.
+    ///
+    /// The operations per lane are the same as in [`Lorentzian::evaluate`] and
+    /// the signals are summed up in the same order, so the result agrees with
+    /// the scalar evaluation up to rounding.
+    #[cfg(feature = "simd")]
+    fn simd_superposition_vec<L: AsRef<Lorentzian>>(x: &[f64], lorentzians: &[L]) -> Vec<f64> {
+        let chunks = x.chunks_exact(4);
+        let remainder = chunks.remainder();
+        let mut superposition = Vec::with_capacity(x.len());
+        chunks.for_each(|chunk| {
+            let x = f64x4::new([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let sum = lorentzians
+                .iter()
+                .fold(f64x4::ZERO, |sum, lorentzian| {
+                    let lorentzian = lorentzian.as_ref();
+                    let diff = x - f64x4::splat(lorentzian.maxp);
+                    sum + f64x4::splat(lorentzian.sfhw)
+                        / (f64x4::splat(lorentzian.hw2) + diff * diff)
+                });
+            superposition.extend_from_slice(&sum.to_array());
+        });
+        superposition.extend(
+            remainder
+                .iter()
+                .map(|&x| Self::superposition(x, lorentzians)),
+        );
+
+        superposition
+    }
 }
 
 #[cfg(test)]
@@ -704,6 +1120,63 @@ mod tests {
         assert_approx_eq!(f64, lorentzian.hw(), 1.0);
     }
 
+    #[test]
+    fn integral_between() {
+        let lorentzian = Lorentzian::new(2.0, 1.0, 0.0);
+        let quarter = std::f64::consts::FRAC_PI_4;
+        assert_approx_eq!(f64, lorentzian.integral_between(0.0, 1.0), 2.0 * quarter);
+        assert_approx_eq!(f64, lorentzian.integral_between(-1.0, 1.0), 4.0 * quarter);
+        assert_approx_eq!(f64, lorentzian.integral_between(1.0, -1.0), -4.0 * quarter);
+        assert_approx_eq!(f64, lorentzian.integral_between(1.0, 1.0), 0.0);
+        assert_approx_eq!(
+            f64,
+            lorentzian.integral_between(f64::NEG_INFINITY, 0.0),
+            4.0 * quarter
+        );
+        let narrow = Lorentzian::new(1e-6, 1e-12, 0.0);
+        let (a, b) = (1.0, 1.001);
+        let expected = 1e-6 * (1.0 / a - 1.0 / b);
+        assert_approx_eq!(
+            f64,
+            narrow.integral_between(a, b),
+            expected,
+            epsilon = 1e-9 * expected
+        );
+    }
+
+    #[test]
+    fn overlap() {
+        let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+        assert_approx_eq!(f64, lorentzian.overlap(&lorentzian), 1.0);
+        let scaled = Lorentzian::new(0.09, 0.0225, 5.0);
+        assert_approx_eq!(f64, lorentzian.overlap(&scaled), 1.0);
+        assert_approx_eq!(f64, scaled.overlap(&lorentzian), 1.0);
+        let distant = Lorentzian::new(0.045, 0.0225, 500.0);
+        assert!(lorentzian.overlap(&distant) < 1e-3);
+        let shifted = Lorentzian::new(0.045, 0.0225, 5.3);
+        let expected = 1.0 - 2.0 / std::f64::consts::PI * f64::atan(0.15 / 0.15);
+        assert_approx_eq!(f64, lorentzian.overlap(&shifted), expected, epsilon = 1e-12);
+        assert_approx_eq!(
+            f64,
+            lorentzian.overlap(&shifted),
+            shifted.overlap(&lorentzian),
+            epsilon = 1e-12
+        );
+        let broad = Lorentzian::new(0.09, 0.09, 5.1);
+        let (a, b, n) = (-1e3, 1e3, 2_000_000);
+        let step = (b - a) / n as f64;
+        let shared = (0..n)
+            .map(|i| {
+                let x = a + (i as f64 + 0.5) * step;
+                f64::min(lorentzian.evaluate(x), broad.evaluate(x))
+            })
+            .sum::<f64>()
+            * step;
+        let expected = shared / f64::min(lorentzian.integral(), broad.integral());
+        assert_approx_eq!(f64, lorentzian.overlap(&broad), expected, epsilon = 1e-3);
+        assert_approx_eq!(f64, broad.overlap(&lorentzian), expected, epsilon = 1e-3);
+    }
+
     #[test]
     fn evaluate() {
         let lorentzian = Lorentzian::new(1.0, 1.0, 0.0);
@@ -787,6 +1260,36 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "simd")]
+    fn simd_superposition() {
+        let lorentzians = (0..200)
+            .map(|i| {
+                let hw = 0.001 + 0.0001 * (i % 7) as f64;
+                Lorentzian::new((1.0 + (i % 5) as f64) * hw, hw.powi(2), 0.05 * i as f64)
+            })
+            .collect::<Vec<Lorentzian>>();
+        [0, 1, 3, 4, 5, 1023, 4097]
+            .into_iter()
+            .for_each(|len| {
+                let chemical_shifts = (0..len)
+                    .map(|x| x as f64 * 10.0 / 4096.0)
+                    .collect::<Vec<f64>>();
+                let expected = Lorentzian::scalar_superposition_vec(&chemical_shifts, &lorentzians);
+                let computed = Lorentzian::simd_superposition_vec(&chemical_shifts, &lorentzians);
+                assert_eq!(computed.len(), expected.len());
+                computed
+                    .iter()
+                    .zip(expected.iter())
+                    .for_each(|(&yc, &ye)| assert_approx_eq!(f64, yc, ye, ulps = 4));
+                #[cfg(feature = "parallel")]
+                Lorentzian::par_superposition_vec(&chemical_shifts, &lorentzians)
+                    .iter()
+                    .zip(expected.iter())
+                    .for_each(|(&yc, &ye)| assert_approx_eq!(f64, yc, ye, ulps = 4));
+            });
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serialization_round_trip() {
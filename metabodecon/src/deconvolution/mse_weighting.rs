@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weighting of the residuals when computing the MSE of a [`Deconvolution`].
+///
+/// The MSE is computed within the signal region, which often contains wide
+/// stretches of baseline between the peaks. With uniform weights, these
+/// dominate the MSE, so a deconvolution that misses the peak tops can still
+/// have a low MSE. Weighting the residuals by the intensity emphasizes the
+/// peak regions instead.
+///
+/// [`Deconvolution`]: crate::deconvolution::Deconvolution
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MseWeighting {
+    /// All residuals are weighted equally.
+    #[default]
+    Uniform,
+    /// Each squared residual is weighted by the absolute intensity of the
+    /// spectrum at that point, and the weighted sum is divided by the sum of
+    /// the weights. If all intensities are zero, uniform weights are used.
+    IntensityWeighted,
+}
+
+impl std::fmt::Display for MseWeighting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MseWeighting::Uniform => write!(f, "Uniform"),
+            MseWeighting::IntensityWeighted => write!(f, "Intensity Weighted"),
+        }
+    }
+}
+
+impl MseWeighting {
+    /// Internal helper function to compute the MSE between the superposition
+    /// and the intensities within the given regions.
+    pub(crate) fn mse(
+        &self,
+        superpositions: &[f64],
+        intensities: &[f64],
+        regions: &[(usize, usize)],
+    ) -> f64 {
+        let points = || {
+            regions.iter().flat_map(|(start, end)| {
+                superpositions[*start..*end]
+                    .iter()
+                    .zip(intensities[*start..*end].iter())
+            })
+        };
+        let length = regions
+            .iter()
+            .map(|(start, end)| end - start)
+            .sum::<usize>();
+        let uniform = || {
+            points()
+                .map(|(superposition, intensity)| (superposition - intensity).powi(2))
+                .sum::<f64>()
+                / (length as f64)
+        };
+
+        match self {
+            MseWeighting::Uniform => uniform(),
+            MseWeighting::IntensityWeighted => {
+                let total_weight = points()
+                    .map(|(_, intensity)| intensity.abs())
+                    .sum::<f64>();
+                if total_weight == 0.0 {
+                    return uniform();
+                }
+                points()
+                    .map(|(superposition, intensity)| {
+                        intensity.abs() * (superposition - intensity).powi(2)
+                    })
+                    .sum::<f64>()
+                    / total_weight
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(MseWeighting);
+        assert_sync!(MseWeighting);
+    }
+
+    #[test]
+    fn mse() {
+        let intensities = [0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 2.0, 0.0];
+        let superpositions = [0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        let regions = [(0, 4), (5, 8)];
+        let uniform = MseWeighting::Uniform.mse(&superpositions, &intensities, &regions);
+        let weighted = MseWeighting::IntensityWeighted.mse(&superpositions, &intensities, &regions);
+        assert_approx_eq!(f64, uniform, 2.0 / 7.0);
+        assert_approx_eq!(f64, weighted, 1.0);
+        let zeros = [0.0; 8];
+        assert_approx_eq!(
+            f64,
+            MseWeighting::IntensityWeighted.mse(&superpositions, &zeros, &regions),
+            MseWeighting::Uniform.mse(&superpositions, &zeros, &regions)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::deconvolution::error::{Error, Kind};
+use crate::deconvolution::fitting::FittingSettings;
+use crate::deconvolution::peak_selection::{NoiseEstimator, ScoringMethod, SelectionSettings};
+use crate::deconvolution::smoothing::SmoothingSettings;
+use crate::{Result, Settings};
+
+/// Candidate settings evaluated when optimizing the settings of a
+/// [`Deconvoluter`].
+///
+/// The optimization deconvolutes a reference spectrum with every combination
+/// of the candidate smoothing, peak selection, and fitting settings, and
+/// selects the combination with the lowest MSE. The number of deconvolutions
+/// is the product of the numbers of candidates, see [`len`].
+///
+/// The [`Default`] grid is used by [`Deconvoluter::optimize_settings`]. It
+/// consists of settings that were assessed on spectra with varying
+/// resolutions, noise levels, and peak counts:
+/// - [`SmoothingSettings::MovingAverage`] with 2 to 10 iterations and window
+///   sizes of 3, 5, and 7.
+/// - [`SelectionSettings::NoiseScoreFilter`] with 10 evenly spaced thresholds
+///   from 5.0 to 8.0.
+/// - [`FittingSettings::Analytical`] with 5, 10, and 15 iterations.
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+/// [`Deconvoluter::optimize_settings`]: crate::deconvolution::Deconvoluter::optimize_settings
+/// [`len`]: OptimizationGrid::len
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::{
+///     FittingSettings, NoiseEstimator, OptimizationGrid, ScoringMethod, SelectionSettings,
+///     SmoothingSettings,
+/// };
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let grid = OptimizationGrid::new(
+///     vec![
+///         SmoothingSettings::MovingAverage {
+///             iterations: 2,
+///             window_size: 5,
+///         },
+///         SmoothingSettings::Whittaker {
+///             lambda: 100.0,
+///             order: 2,
+///         },
+///     ],
+///     vec![SelectionSettings::NoiseScoreFilter {
+///         scoring_method: ScoringMethod::MinimumSum,
+///         threshold: 6.4,
+///         max_peaks: None,
+///         noise_estimator: NoiseEstimator::StdDev,
+///     }],
+///     vec![FittingSettings::Analytical { iterations: 10 }],
+/// )?;
+///
+/// assert_eq!(grid.len(), 2);
+/// assert_eq!(OptimizationGrid::default().len(), 810);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct OptimizationGrid {
+    /// Candidate smoothing settings.
+    smoothing_settings: Vec<SmoothingSettings>,
+    /// Candidate peak selection settings.
+    selection_settings: Vec<SelectionSettings>,
+    /// Candidate fitting settings.
+    fitting_settings: Vec<FittingSettings>,
+}
+
+impl Default for OptimizationGrid {
+    fn default() -> Self {
+        let smoothing_settings = (2..=10)
+            .flat_map(|iterations| {
+                (3..=7)
+                    .step_by(2)
+                    .map(move |window_size| SmoothingSettings::MovingAverage {
+                        iterations,
+                        window_size,
+                    })
+            })
+            .collect::<Vec<SmoothingSettings>>();
+        let selection_settings = (0..10)
+            .map(|coefficient| SelectionSettings::NoiseScoreFilter {
+                scoring_method: ScoringMethod::MinimumSum,
+                threshold: 5.0 + (coefficient as f64) * (8.0 - 5.0) / 9.0,
+                max_peaks: None,
+                noise_estimator: NoiseEstimator::StdDev,
+            })
+            .collect::<Vec<SelectionSettings>>();
+        let fitting_settings = (5..=15)
+            .step_by(5)
+            .map(|iterations| FittingSettings::Analytical { iterations })
+            .collect::<Vec<FittingSettings>>();
+
+        Self {
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+        }
+    }
+}
+
+impl OptimizationGrid {
+    /// Constructs a new `OptimizationGrid` from the given candidate settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the candidate vectors is empty, or if any of
+    /// the candidate settings is invalid. [`SmoothingSettings::Custom`] is
+    /// rejected as well, as custom smoothers can't be constructed from the
+    /// settings.
+    pub fn new(
+        smoothing_settings: Vec<SmoothingSettings>,
+        selection_settings: Vec<SelectionSettings>,
+        fitting_settings: Vec<FittingSettings>,
+    ) -> Result<Self> {
+        if smoothing_settings.is_empty()
+            || selection_settings.is_empty()
+            || fitting_settings.is_empty()
+        {
+            return Err(Error::new(Kind::EmptyOptimizationGrid).into());
+        }
+        smoothing_settings
+            .iter()
+            .try_for_each(Settings::validate)?;
+        selection_settings
+            .iter()
+            .try_for_each(Settings::validate)?;
+        fitting_settings
+            .iter()
+            .try_for_each(Settings::validate)?;
+
+        Ok(Self {
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+        })
+    }
+
+    /// Returns the candidate smoothing settings.
+    pub fn smoothing_settings(&self) -> &[SmoothingSettings] {
+        &self.smoothing_settings
+    }
+
+    /// Returns the candidate peak selection settings.
+    pub fn selection_settings(&self) -> &[SelectionSettings] {
+        &self.selection_settings
+    }
+
+    /// Returns the candidate fitting settings.
+    pub fn fitting_settings(&self) -> &[FittingSettings] {
+        &self.fitting_settings
+    }
+
+    /// Returns the number of combinations of settings in the grid, which is
+    /// the number of deconvolutions performed during the optimization.
+    pub fn len(&self) -> usize {
+        self.smoothing_settings.len() * self.selection_settings.len() * self.fitting_settings.len()
+    }
+
+    /// Returns `true` if the grid contains no combinations of settings. This
+    /// is never the case for grids constructed with [`new`] or [`default`].
+    ///
+    /// [`new`]: OptimizationGrid::new
+    /// [`default`]: OptimizationGrid::default
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(OptimizationGrid);
+        assert_sync!(OptimizationGrid);
+    }
+
+    #[test]
+    fn invalid_grid() {
+        let smoothing = vec![SmoothingSettings::default()];
+        let selection = vec![SelectionSettings::default()];
+        let fitting = vec![FittingSettings::default()];
+        let errors = [
+            OptimizationGrid::new(Vec::new(), selection.clone(), fitting.clone()).unwrap_err(),
+            OptimizationGrid::new(smoothing.clone(), Vec::new(), fitting.clone()).unwrap_err(),
+            OptimizationGrid::new(smoothing.clone(), selection.clone(), Vec::new()).unwrap_err(),
+            OptimizationGrid::new(
+                smoothing.clone(),
+                selection.clone(),
+                vec![FittingSettings::Analytical { iterations: 0 }],
+            )
+            .unwrap_err(),
+            OptimizationGrid::new(
+                vec![SmoothingSettings::default(), SmoothingSettings::Custom],
+                selection.clone(),
+                fitting.clone(),
+            )
+            .unwrap_err(),
+        ];
+        errors
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, error)| match error {
+                crate::Error::Deconvolution(inner) => match (i, inner.kind()) {
+                    (0..=2, Kind::EmptyOptimizationGrid) => {}
+                    (3, Kind::InvalidFittingSettings { .. }) => {}
+                    (4, Kind::InvalidSmoothingSettings { .. }) => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            });
+        assert_eq!(
+            OptimizationGrid::new(smoothing, selection, fitting)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::deconvolution::fitting::FittingSettings;
+use crate::deconvolution::peak_selection::SelectionSettings;
+use crate::deconvolution::smoothing::SmoothingSettings;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Combination of settings evaluated during the optimization of a
+/// [`Deconvoluter`], together with the resulting MSE.
+///
+/// See [`Deconvoluter::optimization_report`].
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+/// [`Deconvoluter::optimization_report`]: crate::deconvolution::Deconvoluter::optimization_report
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct OptimizationResult {
+    /// The evaluated smoothing settings.
+    smoothing_settings: SmoothingSettings,
+    /// The evaluated peak selection settings.
+    selection_settings: SelectionSettings,
+    /// The evaluated fitting settings.
+    fitting_settings: FittingSettings,
+    /// MSE of the deconvolution with the evaluated settings.
+    mse: f64,
+}
+
+impl OptimizationResult {
+    /// Internal helper function to construct a new `OptimizationResult`.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn new(
+        smoothing_settings: SmoothingSettings,
+        selection_settings: SelectionSettings,
+        fitting_settings: FittingSettings,
+        mse: f64,
+    ) -> Self {
+        Self {
+            smoothing_settings,
+            selection_settings,
+            fitting_settings,
+            mse,
+        }
+    }
+
+    /// Returns the evaluated smoothing settings.
+    pub fn smoothing_settings(&self) -> SmoothingSettings {
+        self.smoothing_settings
+    }
+
+    /// Returns the evaluated peak selection settings.
+    pub fn selection_settings(&self) -> SelectionSettings {
+        self.selection_settings
+    }
+
+    /// Returns the evaluated fitting settings.
+    pub fn fitting_settings(&self) -> FittingSettings {
+        self.fitting_settings
+    }
+
+    /// Returns the MSE of the deconvolution with the evaluated settings.
+    pub fn mse(&self) -> f64 {
+        self.mse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(OptimizationResult);
+        assert_sync!(OptimizationResult);
+    }
+}
@@ -1,18 +1,26 @@
 mod common;
+#[cfg(feature = "parallel")]
+pub(crate) use common::ignore_region_segments;
 pub(crate) use common::{peak_region_boundaries, second_derivative};
 
 mod detector;
 pub(crate) use detector::Detector;
 
 mod detector_only;
-pub(crate) use detector_only::DetectorOnly;
+pub use detector_only::DetectorOnly;
+
+mod noise_estimator;
+pub use noise_estimator::NoiseEstimator;
 
 mod noise_score_filter;
-pub(crate) use noise_score_filter::NoiseScoreFilter;
+pub use noise_score_filter::NoiseScoreFilter;
 
 mod peak;
 pub(crate) use peak::Peak;
 
+mod peak_info;
+pub use peak_info::PeakInfo;
+
 mod scorer;
 pub use scorer::ScoringMethod;
 pub(crate) use scorer::{Scorer, ScorerMinimumSum};
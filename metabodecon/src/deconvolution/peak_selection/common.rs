@@ -39,6 +39,42 @@ pub(crate) fn peak_region_boundaries(
     (left, right)
 }
 
+/// Splits the signal indices into independent segments at the boundaries of
+/// the ignore regions.
+///
+/// The segments cover the signal without overlapping and are ordered by their
+/// position. Each ignore region forms its own segment, such that the segments
+/// between them can be processed independently, e.g. in parallel. Note that
+/// the lower boundary is included in and the upper boundary is excluded from
+/// each segment.
+///
+/// |  Index          | 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 |
+/// | --------------- | - | - | - | - | - | - | - | - | - | - |
+/// | Ignore Region   |   |   |   | x | x |   |   |   |   |   |
+/// | Segment         | 0 | 0 | 0 | 1 | 1 | 2 | 2 | 2 | 2 | 2 |
+#[cfg(feature = "parallel")]
+pub(crate) fn ignore_region_segments(
+    len: usize,
+    ignore_regions: Option<&[(usize, usize)]>,
+) -> Vec<(usize, usize)> {
+    let mut boundaries = vec![0, len];
+    if let Some(ignore_regions) = ignore_regions {
+        boundaries.extend(
+            ignore_regions
+                .iter()
+                .flat_map(|(start, end)| [*start, *end])
+                .map(|boundary| usize::min(boundary, len)),
+        );
+    }
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    boundaries
+        .windows(2)
+        .map(|w| (w[0], w[1]))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +102,18 @@ mod tests {
             .collect::<Vec<Peak>>();
         assert_eq!(prb(&peaks, signal_region_boundaries), (1, 3));
     }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn ignore_region_segments() {
+        assert_eq!(super::ignore_region_segments(10, None), vec![(0, 10)]);
+        assert_eq!(
+            super::ignore_region_segments(10, Some(&[(3, 5)])),
+            vec![(0, 3), (3, 5), (5, 10)]
+        );
+        assert_eq!(
+            super::ignore_region_segments(10, Some(&[(0, 2), (6, 12)])),
+            vec![(0, 2), (2, 6), (6, 10)]
+        );
+    }
 }
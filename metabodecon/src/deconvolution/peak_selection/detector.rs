@@ -2,6 +2,9 @@ use crate::deconvolution::error::{Error, Kind};
 use crate::deconvolution::peak_selection::Peak;
 use crate::error::Result;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 /// Peak detection algorithm based on the second derivative of the signal.
 ///
 /// This algorithm detects peaks in a signal by analyzing its second derivative,
@@ -97,32 +100,70 @@ impl<'a> Detector<'a> {
     /// The following errors are possible:
     /// - [`NoPeaksDetected`](Kind::NoPeaksDetected)
     pub(crate) fn detect_peaks(&self) -> Result<Vec<Peak>> {
-        let peak_centers = self.find_peak_centers();
-        let peak_borders = self.find_peak_borders(&peak_centers);
-        let peaks = peak_centers
-            .into_iter()
-            .zip(peak_borders)
-            .filter(|(_, (left, right))| *left != 0 && *right != self.second_derivative.len() + 1)
-            .map(|(center, (left, right))| Peak::new(left, center, right))
+        let peaks = self.detect_peaks_in_segment((0, self.second_derivative.len() + 2));
+        if peaks.is_empty() {
+            return Err(Error::new(Kind::NoPeaksDetected).into());
+        }
+
+        Ok(peaks)
+    }
+
+    /// Detects the peaks in the signal in parallel, processing the given
+    /// segments of signal indices independently. The segments must cover the
+    /// signal without overlapping. Peak borders may extend beyond the segment
+    /// of their center, such that the result is identical to
+    /// [`detect_peaks`](Detector::detect_peaks).
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`NoPeaksDetected`](Kind::NoPeaksDetected)
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_detect_peaks(&self, segments: &[(usize, usize)]) -> Result<Vec<Peak>> {
+        let mut peaks = segments
+            .par_iter()
+            .flat_map_iter(|segment| self.detect_peaks_in_segment(*segment))
             .collect::<Vec<Peak>>();
         if peaks.is_empty() {
             return Err(Error::new(Kind::NoPeaksDetected).into());
         }
+        peaks.sort_unstable_by_key(|peak| peak.center());
 
         Ok(peaks)
     }
 
-    /// Internal helper function to finds the indices of the peak centers.
-    /// Indices are offset by +2 to account for the fact that the second
-    /// derivative can only be computed for the inner points of the signal
-    /// intensities, and the sliding window approach requires neighbors on
-    /// both sides.
-    fn find_peak_centers(&self) -> Vec<usize> {
-        self.second_derivative
+    /// Internal helper function to detect the peaks with their centers within
+    /// the given segment of signal indices, where the lower boundary is
+    /// included and the upper boundary is excluded.
+    fn detect_peaks_in_segment(&self, segment: (usize, usize)) -> Vec<Peak> {
+        let peak_centers = self.find_peak_centers(segment);
+        let peak_borders = self.find_peak_borders(&peak_centers);
+
+        peak_centers
+            .into_iter()
+            .zip(peak_borders)
+            .filter(|(_, (left, right))| *left != 0 && *right != self.second_derivative.len() + 1)
+            .map(|(center, (left, right))| Peak::new(left, center, right))
+            .collect()
+    }
+
+    /// Internal helper function to finds the indices of the peak centers
+    /// within the given segment of signal indices. Indices are offset by +2 to
+    /// account for the fact that the second derivative can only be computed for
+    /// the inner points of the signal intensities, and the sliding window
+    /// approach requires neighbors on both sides.
+    fn find_peak_centers(&self, segment: (usize, usize)) -> Vec<usize> {
+        let start = usize::max(segment.0, 2);
+        let end = usize::min(segment.1, self.second_derivative.len());
+        if start >= end {
+            return Vec::new();
+        }
+
+        self.second_derivative[start - 2..end]
             .windows(3)
             .enumerate()
             .filter(|(_, w)| w[1] < 0. && w[1] < w[0] && w[1] < w[2])
-            .map(|(i, _)| i + 2)
+            .map(|(i, _)| i + start)
             .collect()
     }
 
@@ -172,8 +213,38 @@ mod tests {
     fn find_peak_centers() {
         let second_derivative = vec![0.0, -2.0, 0.0];
         let detector = Detector::new(&second_derivative);
-        let peak_centers = detector.find_peak_centers();
+        let peak_centers = detector.find_peak_centers((0, 5));
         assert_eq!(peak_centers, vec![2]);
+        assert!(detector.find_peak_centers((3, 5)).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn par_detect_peaks() {
+        let intensities = (0..1000)
+            .map(|i| f64::sin(i as f64 * 0.1) + 0.3 * f64::sin(i as f64 * 0.7))
+            .collect::<Vec<f64>>();
+        let second_derivative =
+            crate::deconvolution::peak_selection::second_derivative(&intensities);
+        let detector = Detector::new(&second_derivative);
+        let expected = detector.detect_peaks().unwrap();
+        let segmentations = [
+            vec![(0, 1000)],
+            vec![(0, 100), (100, 101), (101, 512), (512, 1000)],
+            vec![(0, 1), (1, 2), (2, 3), (3, 999), (999, 1000)],
+        ];
+        segmentations.iter().for_each(|segments| {
+            let peaks = detector.par_detect_peaks(segments).unwrap();
+            assert_eq!(peaks.len(), expected.len());
+            peaks
+                .iter()
+                .zip(expected.iter())
+                .for_each(|(peak, expected)| {
+                    assert_eq!(peak.left(), expected.left());
+                    assert_eq!(peak.center(), expected.center());
+                    assert_eq!(peak.right(), expected.right());
+                });
+        });
     }
 
     #[test]
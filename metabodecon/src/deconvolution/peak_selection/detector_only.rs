@@ -3,6 +3,9 @@ use crate::deconvolution::peak_selection::{
     Detector, Peak, SelectionSettings, Selector, second_derivative,
 };
 
+#[cfg(feature = "parallel")]
+use crate::deconvolution::peak_selection::ignore_region_segments;
+
 /// Detects peaks in a spectrum without any filtering.
 ///
 /// Peaks are detected using the curvature of the signal through the second
@@ -10,19 +13,92 @@ use crate::deconvolution::peak_selection::{
 ///
 /// Optionally, regions to be ignored can be provided. Peaks within these
 /// regions are also filtered out.
-#[derive(Debug)]
-pub(crate) struct DetectorOnly;
+///
+/// Equivalent to [`SelectionSettings::DetectorOnly`] for the peak selection
+/// step of a [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DetectorOnly;
 
 impl Selector for DetectorOnly {
-    fn select_peaks(
+    fn select_peaks_with_noise_estimate(
         &self,
         intensities: &[f64],
         signal_boundaries: (usize, usize),
         ignore_regions: Option<&[(usize, usize)]>,
-    ) -> Result<Vec<Peak>> {
+        _boost_regions: Option<&[((usize, usize), f64)]>,
+        _noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)> {
+        let second_derivative = second_derivative(intensities);
+        let detector = Detector::new(&second_derivative);
+        let peaks = detector.detect_peaks()?;
+
+        Ok((
+            Self::retain_peaks(peaks, signal_boundaries, ignore_regions),
+            None,
+        ))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_select_peaks_with_noise_estimate(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        _boost_regions: Option<&[((usize, usize), f64)]>,
+        _noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)> {
         let second_derivative = second_derivative(intensities);
         let detector = Detector::new(&second_derivative);
-        let mut peaks = detector.detect_peaks()?;
+        let segments = ignore_region_segments(intensities.len(), ignore_regions);
+        let peaks = detector.par_detect_peaks(&segments)?;
+
+        Ok((
+            Self::retain_peaks(peaks, signal_boundaries, ignore_regions),
+            None,
+        ))
+    }
+
+    fn noise_statistics(
+        &self,
+        _intensities: &[f64],
+        _signal_boundaries: (usize, usize),
+        _ignore_regions: Option<&[(usize, usize)]>,
+        _noise_region: Option<(usize, usize)>,
+    ) -> Result<Option<(f64, f64)>> {
+        Ok(None)
+    }
+
+    fn select_peaks_with_noise_statistics(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        _boost_regions: Option<&[((usize, usize), f64)]>,
+        _noise_statistics: Option<(f64, f64)>,
+    ) -> Result<Vec<Peak>> {
+        self.select_peaks(intensities, signal_boundaries, ignore_regions, None, None)
+    }
+
+    fn settings(&self) -> SelectionSettings {
+        SelectionSettings::DetectorOnly
+    }
+}
+
+impl DetectorOnly {
+    /// Creates a new `DetectorOnly`.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Removes the peaks outside the signal region and within the ignore
+    /// regions.
+    fn retain_peaks(
+        mut peaks: Vec<Peak>,
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+    ) -> Vec<Peak> {
         peaks.retain(|peak| {
             peak.left() >= signal_boundaries.0 && peak.right() <= signal_boundaries.1
         });
@@ -35,18 +111,7 @@ impl Selector for DetectorOnly {
             });
         }
 
-        Ok(peaks)
-    }
-
-    fn settings(&self) -> SelectionSettings {
-        SelectionSettings::DetectorOnly
-    }
-}
-
-impl DetectorOnly {
-    /// Creates a new `DetectorOnly`.
-    pub(crate) fn new() -> Self {
-        Self
+        peaks
     }
 }
 
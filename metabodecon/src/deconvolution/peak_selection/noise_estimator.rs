@@ -0,0 +1,73 @@
+use crate::{Result, Settings};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Methods to estimate the noise level from the scores of the noise peaks.
+///
+/// The [`NoiseScoreFilter`] computes the location and the scale of the scores
+/// of the peaks where only noise is expected, and keeps the peaks in the
+/// signal region whose score exceeds `location + threshold * scale`.
+///
+/// [`NoiseScoreFilter`]: crate::deconvolution::SelectionSettings::NoiseScoreFilter
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "method", rename_all_fields = "camelCase")
+)]
+pub enum NoiseEstimator {
+    /// Mean and standard deviation of the scores.
+    ///
+    /// Efficient for normally distributed scores, but a single residual signal
+    /// within the noise region inflates the estimate, and with it the
+    /// threshold for the selection.
+    #[default]
+    StdDev,
+    /// Median and median absolute deviation (MAD) of the scores.
+    ///
+    /// The MAD is scaled by 1.4826, which makes it comparable to the standard
+    /// deviation for normally distributed scores. Up to half of the scores may
+    /// be outliers, such as residual signals within the noise region, without
+    /// affecting the estimate.
+    Mad,
+}
+
+impl std::fmt::Display for NoiseEstimator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoiseEstimator::StdDev => write!(f, "Standard Deviation"),
+            NoiseEstimator::Mad => write!(f, "Median Absolute Deviation"),
+        }
+    }
+}
+
+impl Settings for NoiseEstimator {
+    fn validate(&self) -> Result<()> {
+        match self {
+            NoiseEstimator::StdDev | NoiseEstimator::Mad => Ok(()),
+        }
+    }
+
+    #[cfg(test)]
+    fn compare(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (NoiseEstimator::StdDev, NoiseEstimator::StdDev)
+                | (NoiseEstimator::Mad, NoiseEstimator::Mad)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(NoiseEstimator);
+        assert_sync!(NoiseEstimator);
+    }
+}
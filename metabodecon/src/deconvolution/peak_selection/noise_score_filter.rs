@@ -1,9 +1,16 @@
-use crate::Result;
 use crate::deconvolution::error::{Error, Kind};
 use crate::deconvolution::peak_selection::{
-    Detector, Peak, Scorer, ScorerMinimumSum, ScoringMethod, SelectionSettings, Selector,
-    peak_region_boundaries, second_derivative,
+    Detector, NoiseEstimator, Peak, Scorer, ScorerMinimumSum, ScoringMethod, SelectionSettings,
+    Selector, peak_region_boundaries, second_derivative,
 };
+use crate::{Result, Settings};
+
+#[cfg(feature = "parallel")]
+use crate::deconvolution::peak_selection::ignore_region_segments;
+
+/// Scale factor that makes the median absolute deviation a consistent
+/// estimator of the standard deviation of normally distributed scores.
+const MAD_SCALE: f64 = 1.4826;
 
 /// Detects peaks in a spectrum and returns the ones that pass a filter.
 ///
@@ -18,56 +25,223 @@ use crate::deconvolution::peak_selection::{
 /// score > mean + threshold * std_dev
 /// ```
 ///
+/// With [`NoiseEstimator::Mad`], the median and the scaled median absolute
+/// deviation of the scores are used instead, which are robust to residual
+/// signals within the signal free region.
+///
 /// Optionally, regions to be ignored can be provided. Peaks within these
-/// regions are also filtered out.
-#[derive(Debug)]
-pub(crate) struct NoiseScoreFilter {
+/// regions are also filtered out. Boost regions can be provided to divide the
+/// threshold for the peaks centered within them by a factor, such that weak
+/// signals that are expected there are kept. Additionally, a noise region can
+/// be provided, in which case the scores of the peaks within it are used to
+/// compute the mean and standard deviation instead.
+///
+/// Equivalent to [`SelectionSettings::NoiseScoreFilter`] for the peak
+/// selection step of a [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct NoiseScoreFilter {
     /// The scoring method to use.
     scoring_method: ScoringMethod,
     /// The threshold for filtering peaks.
     threshold: f64,
+    /// The maximum number of peaks to keep.
+    max_peaks: Option<usize>,
+    /// The method to estimate the noise level from the noise scores.
+    noise_estimator: NoiseEstimator,
 }
 
 impl Selector for NoiseScoreFilter {
-    fn select_peaks(
+    fn select_peaks_with_noise_estimate(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)> {
+        let (peaks, abs_second_derivative) = Self::detect_peaks(intensities, ignore_regions)?;
+
+        self.filter_peaks(
+            peaks,
+            &abs_second_derivative,
+            signal_boundaries,
+            boost_regions,
+            noise_region,
+        )
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_select_peaks_with_noise_estimate(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)> {
+        let (peaks, abs_second_derivative) = Self::par_detect_peaks(intensities, ignore_regions)?;
+
+        self.filter_peaks(
+            peaks,
+            &abs_second_derivative,
+            signal_boundaries,
+            boost_regions,
+            noise_region,
+        )
+    }
+
+    fn noise_statistics(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<Option<(f64, f64)>> {
+        let (peaks, abs_second_derivative) = Self::detect_peaks(intensities, ignore_regions)?;
+        let scorer = self.scorer(&abs_second_derivative);
+        let boundaries = peak_region_boundaries(&peaks, signal_boundaries);
+
+        self.noise_scores_statistics(&peaks, &scorer, boundaries, noise_region)
+            .map(Some)
+    }
+
+    fn select_peaks_with_noise_statistics(
         &self,
         intensities: &[f64],
         signal_boundaries: (usize, usize),
         ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_statistics: Option<(f64, f64)>,
     ) -> Result<Vec<Peak>> {
-        let mut second_derivative = second_derivative(intensities);
-        let detector = Detector::new(&second_derivative);
-        let mut peaks = detector.detect_peaks()?;
-        if let Some(ignore_regions) = ignore_regions {
-            peaks.retain(|peak| {
-                !ignore_regions.iter().any(|(start, end)| {
-                    (peak.left() >= *start && peak.left() < *end)
-                        || (peak.right() >= *start && peak.right() < *end)
-                })
-            });
+        let (peaks, abs_second_derivative) = Self::detect_peaks(intensities, ignore_regions)?;
+        match noise_statistics {
+            Some(noise_statistics) => {
+                let scorer = self.scorer(&abs_second_derivative);
+                let boundaries = peak_region_boundaries(&peaks, signal_boundaries);
+                self.apply_filter(peaks, &scorer, boundaries, boost_regions, noise_statistics)
+            }
+            None => self
+                .filter_peaks(
+                    peaks,
+                    &abs_second_derivative,
+                    signal_boundaries,
+                    boost_regions,
+                    None,
+                )
+                .map(|(peaks, _)| peaks),
         }
-        second_derivative
-            .iter_mut()
-            .for_each(|d| *d = d.abs());
-
-        self.filter_peaks(peaks, &second_derivative, signal_boundaries)
     }
 
     fn settings(&self) -> SelectionSettings {
         SelectionSettings::NoiseScoreFilter {
             scoring_method: self.scoring_method,
             threshold: self.threshold,
+            max_peaks: self.max_peaks,
+            noise_estimator: self.noise_estimator,
         }
     }
 }
 
 impl NoiseScoreFilter {
-    /// Creates a new `NoiseScoreFilter` with the given scoring algorithm and
-    /// threshold.
-    pub(crate) fn new(scoring_method: ScoringMethod, threshold: f64) -> Self {
-        Self {
+    /// Creates a new `NoiseScoreFilter` with the given scoring algorithm,
+    /// threshold, maximum number of peaks and noise estimator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the threshold is not positive and finite or the
+    /// maximum number of peaks is 0, see [`SelectionSettings::NoiseScoreFilter`].
+    pub fn new(
+        scoring_method: ScoringMethod,
+        threshold: f64,
+        max_peaks: Option<usize>,
+        noise_estimator: NoiseEstimator,
+    ) -> Result<Self> {
+        SelectionSettings::NoiseScoreFilter {
             scoring_method,
             threshold,
+            max_peaks,
+            noise_estimator,
+        }
+        .validate()?;
+
+        Ok(Self {
+            scoring_method,
+            threshold,
+            max_peaks,
+            noise_estimator,
+        })
+    }
+
+    /// Detects peaks, removes the ones within the ignore regions and returns
+    /// them together with the absolute second derivative of the intensities.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`NoPeaksDetected`](Kind::NoPeaksDetected)
+    pub(crate) fn detect_peaks(
+        intensities: &[f64],
+        ignore_regions: Option<&[(usize, usize)]>,
+    ) -> Result<(Vec<Peak>, Vec<f64>)> {
+        let mut second_derivative = second_derivative(intensities);
+        let detector = Detector::new(&second_derivative);
+        let peaks = detector.detect_peaks()?;
+        let peaks = Self::retain_outside_ignore_regions(peaks, ignore_regions);
+        second_derivative
+            .iter_mut()
+            .for_each(|d| *d = d.abs());
+
+        Ok((peaks, second_derivative))
+    }
+
+    /// Detects peaks in parallel within the segments delimited by the ignore
+    /// regions, removes the ones within the ignore regions and returns them
+    /// together with the absolute second derivative of the intensities.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`NoPeaksDetected`](Kind::NoPeaksDetected)
+    #[cfg(feature = "parallel")]
+    fn par_detect_peaks(
+        intensities: &[f64],
+        ignore_regions: Option<&[(usize, usize)]>,
+    ) -> Result<(Vec<Peak>, Vec<f64>)> {
+        let mut second_derivative = second_derivative(intensities);
+        let detector = Detector::new(&second_derivative);
+        let segments = ignore_region_segments(intensities.len(), ignore_regions);
+        let peaks = detector.par_detect_peaks(&segments)?;
+        let peaks = Self::retain_outside_ignore_regions(peaks, ignore_regions);
+        second_derivative
+            .iter_mut()
+            .for_each(|d| *d = d.abs());
+
+        Ok((peaks, second_derivative))
+    }
+
+    /// Removes the peaks with a border within one of the ignore regions.
+    fn retain_outside_ignore_regions(
+        mut peaks: Vec<Peak>,
+        ignore_regions: Option<&[(usize, usize)]>,
+    ) -> Vec<Peak> {
+        if let Some(ignore_regions) = ignore_regions {
+            peaks.retain(|peak| {
+                !ignore_regions.iter().any(|(start, end)| {
+                    (peak.left() >= *start && peak.left() < *end)
+                        || (peak.right() >= *start && peak.right() < *end)
+                })
+            });
+        }
+
+        peaks
+    }
+
+    /// Creates the scorer of the selected scoring method.
+    fn scorer<'a>(&self, abs_second_derivative: &'a [f64]) -> ScorerMinimumSum<'a> {
+        match self.scoring_method {
+            ScoringMethod::MinimumSum => ScorerMinimumSum::new(abs_second_derivative),
         }
     }
 
@@ -83,6 +257,15 @@ impl NoiseScoreFilter {
     /// score > mean + threshold * std_dev
     /// ```
     ///
+    /// If a noise region is provided, the peaks within it are used instead of
+    /// the peaks in the SFR. With [`NoiseEstimator::Mad`], the median and the
+    /// scaled median absolute deviation are used instead of the mean and
+    /// standard deviation. For peaks centered within a boost region, the
+    /// threshold is divided by the factor of the boost region. If a maximum
+    /// number of peaks is set, only the highest scoring peaks are kept, in the
+    /// order of their position. The estimated spread of the noise scores is
+    /// returned alongside the peaks as the noise level.
+    ///
     /// # Errors
     ///
     /// The following errors are possible:
@@ -90,41 +273,112 @@ impl NoiseScoreFilter {
     /// - [`EmptySignalFreeRegion`](Kind::EmptySignalFreeRegion)
     fn filter_peaks(
         &self,
-        mut peaks: Vec<Peak>,
+        peaks: Vec<Peak>,
         abs_second_derivative: &[f64],
         signal_boundaries: (usize, usize),
-    ) -> Result<Vec<Peak>> {
-        let scorer = match self.scoring_method {
-            ScoringMethod::MinimumSum => ScorerMinimumSum::new(abs_second_derivative),
-        };
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)> {
+        let scorer = self.scorer(abs_second_derivative);
         let boundaries = peak_region_boundaries(&peaks, signal_boundaries);
+        let (mean, sd) = self.noise_scores_statistics(&peaks, &scorer, boundaries, noise_region)?;
+        let peaks = self.apply_filter(peaks, &scorer, boundaries, boost_regions, (mean, sd))?;
+
+        Ok((peaks, Some(sd)))
+    }
+
+    /// Computes the location and spread of the scores of the peaks in the SFR
+    /// or, if provided, the noise region, using the selected noise estimator.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`EmptySignalFreeRegion`](Kind::EmptySignalFreeRegion)
+    fn noise_scores_statistics(
+        &self,
+        peaks: &[Peak],
+        scorer: &ScorerMinimumSum,
+        boundaries: (usize, usize),
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<(f64, f64)> {
+        let scores_sfr = match noise_region {
+            Some((start, end)) => peaks
+                .iter()
+                .filter(|peak| peak.center() >= start && peak.center() < end)
+                .map(|peak| scorer.score_peak(peak))
+                .collect::<Vec<f64>>(),
+            None => peaks[0..boundaries.0]
+                .iter()
+                .chain(peaks[boundaries.1..].iter())
+                .map(|peak| scorer.score_peak(peak))
+                .collect::<Vec<f64>>(),
+        };
 
-        if peaks[..boundaries.0].is_empty() && peaks[boundaries.1..].is_empty() {
+        if scores_sfr.is_empty() {
             return Err(Error::new(Kind::EmptySignalFreeRegion).into());
         }
+
+        match self.noise_estimator {
+            NoiseEstimator::StdDev => Ok(Self::mean_sd_scores(scores_sfr)),
+            NoiseEstimator::Mad => Ok(Self::median_mad_scores(scores_sfr)),
+        }
+    }
+
+    /// Filters the peaks in the signal region, delimited by the given indices
+    /// into the peaks, with the given mean and standard deviation of the noise
+    /// scores. The threshold is divided by the factor of the boost region the
+    /// center of a peak lies within, if any.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`EmptySignalRegion`](Kind::EmptySignalRegion)
+    fn apply_filter(
+        &self,
+        mut peaks: Vec<Peak>,
+        scorer: &ScorerMinimumSum,
+        boundaries: (usize, usize),
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        (mean, sd): (f64, f64),
+    ) -> Result<Vec<Peak>> {
         if peaks[boundaries.0..boundaries.1].is_empty() {
             return Err(Error::new(Kind::EmptySignalRegion).into());
         }
 
-        let scores_sfr = peaks[0..boundaries.0]
-            .iter()
-            .chain(peaks[boundaries.1..].iter())
-            .map(|peak| scorer.score_peak(peak))
-            .collect();
-        let (mean, sd) = Self::mean_sd_scores(scores_sfr);
-
         peaks = peaks
             .drain(boundaries.0..boundaries.1)
-            .filter(|peak| scorer.score_peak(peak) >= mean + self.threshold * sd)
+            .filter_map(|peak| {
+                let score = scorer.score_peak(&peak);
+                let threshold = self.threshold / Self::boost_factor(&peak, boost_regions);
+                (score >= mean + threshold * sd).then(|| peak.with_score(score))
+            })
             .collect();
 
         if peaks.is_empty() {
             return Err(Error::new(Kind::EmptySignalRegion).into());
         }
+        if let Some(max_peaks) = self.max_peaks {
+            if peaks.len() > max_peaks {
+                peaks.sort_unstable_by(|a, b| b.score().partial_cmp(&a.score()).unwrap());
+                peaks.truncate(max_peaks);
+                peaks.sort_unstable_by_key(|peak| peak.center());
+            }
+        }
 
         Ok(peaks)
     }
 
+    /// Returns the largest factor of the boost regions the center of the peak
+    /// lies within, or 1 if it doesn't lie within any boost region.
+    fn boost_factor(peak: &Peak, boost_regions: Option<&[((usize, usize), f64)]>) -> f64 {
+        boost_regions
+            .into_iter()
+            .flatten()
+            .filter(|((start, end), _)| peak.center() >= *start && peak.center() < *end)
+            .map(|(_, factor)| *factor)
+            .fold(1.0, f64::max)
+    }
+
     /// Computes the mean and standard deviation of a vector of scores.
     fn mean_sd_scores(scores: Vec<f64>) -> (f64, f64) {
         let mean: f64 = scores.iter().sum::<f64>() / scores.len() as f64;
@@ -136,11 +390,37 @@ impl NoiseScoreFilter {
 
         (mean, variance.sqrt())
     }
+
+    /// Computes the median and the median absolute deviation of a vector of
+    /// scores. The median absolute deviation is scaled to be comparable to the
+    /// standard deviation for normally distributed scores.
+    fn median_mad_scores(mut scores: Vec<f64>) -> (f64, f64) {
+        let median = Self::median(&mut scores);
+        scores
+            .iter_mut()
+            .for_each(|score| *score = (*score - median).abs());
+        let mad = Self::median(&mut scores);
+
+        (median, MAD_SCALE * mad)
+    }
+
+    /// Computes the median of a non-empty slice of values, reordering them
+    /// in the process.
+    fn median(values: &mut [f64]) -> f64 {
+        let len = values.len();
+        let (lower, median, _) = values.select_nth_unstable_by(len / 2, f64::total_cmp);
+        match len % 2 {
+            0 => 0.5 * (*median + lower.iter().copied().fold(f64::MIN, f64::max)),
+            _ => *median,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Settings;
+    use crate::macros::uniform_noise;
     use crate::{assert_send, assert_sync};
     use float_cmp::assert_approx_eq;
 
@@ -168,4 +448,181 @@ mod tests {
         assert_approx_eq!(f64, mean, 4.0);
         assert_approx_eq!(f64, sd, 1.0);
     }
+
+    #[test]
+    fn median_mad_scores() {
+        let (median, mad) = NoiseScoreFilter::median_mad_scores(vec![6.0, 1.0, 4.0, 2.0, 5.0, 3.0]);
+        assert_approx_eq!(f64, median, 3.5);
+        assert_approx_eq!(f64, mad, 1.5 * MAD_SCALE);
+        let (median, mad) = NoiseScoreFilter::median_mad_scores(vec![2.0, 100.0, 1.0]);
+        assert_approx_eq!(f64, median, 2.0);
+        assert_approx_eq!(f64, mad, MAD_SCALE);
+    }
+
+    #[test]
+    fn noise_estimator_spike() {
+        let mut scores = uniform_noise(200, 42)
+            .into_iter()
+            .map(|noise| 1.0 + 0.1 * noise)
+            .collect::<Vec<f64>>();
+        let (_, sd) = NoiseScoreFilter::mean_sd_scores(scores.clone());
+        let (_, mad) = NoiseScoreFilter::median_mad_scores(scores.clone());
+        scores[100] = 1000.0;
+        let (_, sd_spike) = NoiseScoreFilter::mean_sd_scores(scores.clone());
+        let (_, mad_spike) = NoiseScoreFilter::median_mad_scores(scores);
+        assert!(sd_spike > 100.0 * sd);
+        assert!((mad_spike - mad).abs() < 0.05 * mad);
+    }
+
+    #[test]
+    fn noise_estimator_spike_in_noise_region() {
+        let intensities = uniform_noise(4000, 42)
+            .into_iter()
+            .enumerate()
+            .map(|(i, noise)| {
+                [2500.0, 3000.0]
+                    .iter()
+                    .map(|center| 1.0 / (1.0 + ((i as f64 - center) / 8.0).powi(2)))
+                    .sum::<f64>()
+                    + 0.01 * noise
+            })
+            .collect::<Vec<f64>>();
+        let mut spiked = intensities.clone();
+        spiked[500] += 1.0;
+        let filters = [NoiseEstimator::StdDev, NoiseEstimator::Mad].map(|noise_estimator| {
+            NoiseScoreFilter::new(ScoringMethod::MinimumSum, 5.0, None, noise_estimator).unwrap()
+        });
+        let [sd_ratio, mad_ratio] = filters.each_ref().map(|filter| {
+            let spread = |intensities: &[f64]| {
+                filter
+                    .noise_statistics(intensities, (2000, 3500), None, Some((100, 1900)))
+                    .unwrap()
+                    .unwrap()
+                    .1
+            };
+            spread(&spiked) / spread(&intensities)
+        });
+        assert!(sd_ratio > 2.0, "standard deviation ratio {sd_ratio}");
+        assert!((mad_ratio - 1.0).abs() < 0.05, "MAD ratio {mad_ratio}");
+        let (peaks, _) = filters[1]
+            .select_peaks_with_noise_estimate(&spiked, (2000, 3500), None, None, Some((100, 1900)))
+            .unwrap();
+        assert!(!peaks.is_empty());
+        assert!(peaks.iter().all(|peak| {
+            peak.center()
+                .abs_diff(2500)
+                .min(peak.center().abs_diff(3000))
+                <= 2
+        }));
+    }
+
+    #[test]
+    fn max_peaks() {
+        let centers = [2, 5, 8, 11, 14, 17, 20, 23, 26];
+        let values = [1.0, 4.0, 2.0, 9.0, 6.0, 1.0, 8.0, 5.0, 1.5];
+        let mut abs_second_derivative = vec![0.0; 27];
+        centers
+            .iter()
+            .zip(values.iter())
+            .for_each(|(&center, &value)| {
+                abs_second_derivative[center - 2..=center].fill(value);
+            });
+        let peaks = || {
+            centers
+                .iter()
+                .map(|&center| Peak::new(center - 1, center, center + 1))
+                .collect::<Vec<Peak>>()
+        };
+        let expected = [
+            (None, vec![5, 8, 11, 14, 20, 23]),
+            (Some(10), vec![5, 8, 11, 14, 20, 23]),
+            (Some(3), vec![11, 14, 20]),
+            (Some(1), vec![11]),
+        ];
+        expected
+            .into_iter()
+            .for_each(|(max_peaks, expected)| {
+                let filter = NoiseScoreFilter::new(
+                    ScoringMethod::MinimumSum,
+                    1.0,
+                    max_peaks,
+                    NoiseEstimator::StdDev,
+                )
+                .unwrap();
+                let (selected, noise_estimate) = filter
+                    .filter_peaks(peaks(), &abs_second_derivative, (3, 24), None, None)
+                    .unwrap();
+                assert_approx_eq!(f64, noise_estimate.unwrap(), 0.5);
+                assert_eq!(
+                    selected
+                        .iter()
+                        .map(|peak| peak.center())
+                        .collect::<Vec<usize>>(),
+                    expected
+                );
+                assert!(selected.iter().all(|peak| peak.score().is_some()));
+                assert!(SelectionSettings::compare(
+                    &filter.settings(),
+                    &SelectionSettings::NoiseScoreFilter {
+                        scoring_method: ScoringMethod::MinimumSum,
+                        threshold: 1.0,
+                        max_peaks,
+                        noise_estimator: NoiseEstimator::StdDev,
+                    }
+                ));
+            });
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn par_select_peaks() {
+        let intensities = uniform_noise(4000, 42)
+            .into_iter()
+            .enumerate()
+            .map(|(i, noise)| {
+                [500.0, 1200.0, 1900.0, 2600.0, 3300.0]
+                    .iter()
+                    .map(|center| 1.0 / (1.0 + ((i as f64 - center) / 8.0).powi(2)))
+                    .sum::<f64>()
+                    + 0.01 * noise
+            })
+            .collect::<Vec<f64>>();
+        let filter =
+            NoiseScoreFilter::new(ScoringMethod::MinimumSum, 5.0, None, NoiseEstimator::StdDev)
+                .unwrap();
+        let ignore_regions = [(1000, 1100), (1850, 1950), (2400, 2450)];
+        [None, Some(&ignore_regions[..])]
+            .into_iter()
+            .for_each(|ignore_regions| {
+                let (expected, expected_noise) = filter
+                    .select_peaks_with_noise_estimate(
+                        &intensities,
+                        (400, 3600),
+                        ignore_regions,
+                        None,
+                        None,
+                    )
+                    .unwrap();
+                let (peaks, noise) = filter
+                    .par_select_peaks_with_noise_estimate(
+                        &intensities,
+                        (400, 3600),
+                        ignore_regions,
+                        None,
+                        None,
+                    )
+                    .unwrap();
+                assert_eq!(noise, expected_noise);
+                assert_eq!(peaks.len(), expected.len());
+                peaks
+                    .iter()
+                    .zip(expected.iter())
+                    .for_each(|(peak, expected)| {
+                        assert_eq!(peak.left(), expected.left());
+                        assert_eq!(peak.center(), expected.center());
+                        assert_eq!(peak.right(), expected.right());
+                        assert_eq!(peak.score(), expected.score());
+                    });
+            });
+    }
 }
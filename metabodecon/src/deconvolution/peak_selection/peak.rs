@@ -7,6 +7,8 @@ pub(crate) struct Peak {
     center: usize,
     /// Index of the right boundary of the peak.
     right: usize,
+    /// Score assigned by the selector, if it scores peaks.
+    score: Option<f64>,
 }
 
 impl Peak {
@@ -16,6 +18,25 @@ impl Peak {
             left,
             center,
             right,
+            score: None,
+        }
+    }
+
+    /// Returns the `Peak` with the given score assigned.
+    pub(crate) fn with_score(self, score: f64) -> Self {
+        Self {
+            score: Some(score),
+            ..self
+        }
+    }
+
+    /// Returns the `Peak` with all indices shifted by the given offset.
+    pub(crate) fn shifted(self, offset: usize) -> Self {
+        Self {
+            left: self.left + offset,
+            center: self.center + offset,
+            right: self.right + offset,
+            ..self
         }
     }
 
@@ -33,6 +54,11 @@ impl Peak {
     pub(crate) fn right(&self) -> usize {
         self.right
     }
+
+    /// Returns the score of the peak or `None` if it was not scored.
+    pub(crate) fn score(&self) -> Option<f64> {
+        self.score
+    }
 }
 
 #[cfg(test)]
@@ -45,5 +71,11 @@ mod tests {
         assert_eq!(peak.left(), 1);
         assert_eq!(peak.center(), 2);
         assert_eq!(peak.right(), 3);
+        assert_eq!(peak.score(), None);
+        let peak = peak.with_score(1.5);
+        assert_eq!(peak.score(), Some(1.5));
+        let peak = peak.shifted(10);
+        assert_eq!((peak.left(), peak.center(), peak.right()), (11, 12, 13));
+        assert_eq!(peak.score(), Some(1.5));
     }
 }
@@ -0,0 +1,86 @@
+use crate::deconvolution::peak_selection::Peak;
+use crate::spectrum::Spectrum;
+
+/// Data structure that describes a peak selected by the peak selection
+/// algorithm, before any peak shape is fitted to it.
+///
+/// Returned by [`Deconvoluter::detect_peaks`], and together with the raw peak
+/// areas by [`Deconvoluter::integrate_peaks`].
+///
+/// [`Deconvoluter::detect_peaks`]: crate::deconvolution::Deconvoluter::detect_peaks
+/// [`Deconvoluter::integrate_peaks`]: crate::deconvolution::Deconvoluter::integrate_peaks
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::Deconvoluter;
+/// use metabodecon::spectrum::Bruker;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/spectrum";
+/// # let path = "../data/bruker/sim/sim_01";
+/// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+///
+/// let deconvoluter = Deconvoluter::default();
+/// let peaks = deconvoluter.detect_peaks(&spectrum)?;
+///
+/// for peak in peaks {
+///     assert!(peak.chemical_shift() > 3.339 && peak.chemical_shift() < 3.553);
+///     assert_eq!(peak.chemical_shift(), spectrum.chemical_shifts()[peak.center()]);
+///     assert!(peak.score().is_some());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct PeakInfo {
+    /// Index of the center of the peak.
+    center: usize,
+    /// Chemical shift of the center of the peak in ppm.
+    chemical_shift: f64,
+    /// Intensity at the center of the peak.
+    intensity: f64,
+    /// Score assigned by the peak selection algorithm, if it scores peaks.
+    score: Option<f64>,
+}
+
+impl PeakInfo {
+    /// Internal helper function to construct a `PeakInfo` from a selected
+    /// `Peak` and the `Spectrum` it was selected from.
+    pub(crate) fn new(peak: &Peak, spectrum: &Spectrum) -> Self {
+        Self {
+            center: peak.center(),
+            chemical_shift: spectrum.chemical_shifts()[peak.center()],
+            intensity: spectrum.intensities()[peak.center()],
+            score: peak.score(),
+        }
+    }
+
+    /// Returns the index of the center of the peak within the [`Spectrum`].
+    pub fn center(&self) -> usize {
+        self.center
+    }
+
+    /// Returns the chemical shift of the center of the peak in ppm.
+    pub fn chemical_shift(&self) -> f64 {
+        self.chemical_shift
+    }
+
+    /// Returns the intensity of the [`Spectrum`] at the center of the peak.
+    ///
+    /// This is the intensity after baseline correction, if enabled, but before
+    /// smoothing.
+    pub fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /// Returns the score assigned to the peak by the peak selection algorithm.
+    ///
+    /// This is `None` if the selection method does not score peaks, as with
+    /// [`SelectionSettings::DetectorOnly`].
+    ///
+    /// [`SelectionSettings::DetectorOnly`]: crate::deconvolution::SelectionSettings::DetectorOnly
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+}
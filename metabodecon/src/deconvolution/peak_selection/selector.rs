@@ -1,5 +1,5 @@
 use crate::deconvolution::error::{Error, Kind};
-use crate::deconvolution::peak_selection::{Peak, ScoringMethod};
+use crate::deconvolution::peak_selection::{NoiseEstimator, Peak, ScoringMethod};
 use crate::{Result, Settings};
 
 #[cfg(feature = "serde")]
@@ -8,11 +8,83 @@ use serde::{Deserialize, Serialize};
 /// Trait interface for peak selection algorithms.
 pub(crate) trait Selector: Send + Sync + std::fmt::Debug {
     /// Detects peaks in a spectrum and returns the ones that pass a filter.
+    ///
+    /// If a noise region is provided, selectors that estimate the noise level
+    /// use the peaks within it instead of the peaks outside the signal region.
+    /// Selectors that filter the peaks by a threshold divide it by the factor
+    /// of the boost region the center of a peak lies within, if any.
     fn select_peaks(
         &self,
         intensities: &[f64],
         signal_boundaries: (usize, usize),
         ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<Vec<Peak>> {
+        self.select_peaks_with_noise_estimate(
+            intensities,
+            signal_boundaries,
+            ignore_regions,
+            boost_regions,
+            noise_region,
+        )
+        .map(|(peaks, _)| peaks)
+    }
+
+    /// Detects peaks in a spectrum and returns the ones that pass a filter,
+    /// together with the estimated noise level.
+    ///
+    /// The noise level is the estimated spread of the scores of the noise
+    /// peaks, or `None` if the selector doesn't estimate it.
+    fn select_peaks_with_noise_estimate(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)>;
+
+    /// Detects peaks in a spectrum in parallel and returns the ones that pass
+    /// a filter, together with the estimated noise level.
+    ///
+    /// The ignore regions split the spectrum into segments in which the peaks
+    /// are detected independently. The result is identical to
+    /// [`select_peaks_with_noise_estimate`].
+    ///
+    /// [`select_peaks_with_noise_estimate`]: Selector::select_peaks_with_noise_estimate
+    #[cfg(feature = "parallel")]
+    fn par_select_peaks_with_noise_estimate(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<(Vec<Peak>, Option<f64>)>;
+
+    /// Estimates the noise level as the location and spread of the scores of
+    /// the noise peaks, or returns `None` if the selector doesn't estimate it.
+    fn noise_statistics(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        noise_region: Option<(usize, usize)>,
+    ) -> Result<Option<(f64, f64)>>;
+
+    /// Detects peaks in a spectrum and returns the ones that pass a filter,
+    /// using noise statistics previously obtained from [`noise_statistics`]
+    /// instead of estimating them from the intensities.
+    ///
+    /// [`noise_statistics`]: Selector::noise_statistics
+    fn select_peaks_with_noise_statistics(
+        &self,
+        intensities: &[f64],
+        signal_boundaries: (usize, usize),
+        ignore_regions: Option<&[(usize, usize)]>,
+        boost_regions: Option<&[((usize, usize), f64)]>,
+        noise_statistics: Option<(f64, f64)>,
     ) -> Result<Vec<Peak>>;
 
     /// Returns the settings of the trait object.
@@ -21,6 +93,11 @@ pub(crate) trait Selector: Send + Sync + std::fmt::Debug {
 
 /// Peak selection settings for configuring the [`Deconvoluter`].
 ///
+/// All methods detect peaks as negative local minima of the second derivative
+/// of the smoothed intensities rather than as local maxima of the intensities.
+/// This way, shoulders of overlapping signals are detected as well, even if
+/// they don't form a local maximum of their own.
+///
 /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug)]
@@ -41,18 +118,34 @@ pub enum SelectionSettings {
     /// Finds peaks in the spectrum by analyzing the curvature of the signal
     /// through the second derivative and scores them based on the selected
     /// scoring method. Mean and standard deviation are calculated for the
-    /// scores of peaks in the signal free region (where only noise is present).
-    /// Finally, peaks in the signal region are filtered according to the
-    /// following criterion:
+    /// scores of peaks in the signal free region (where only noise is present),
+    /// or in the noise region if one is set on the [`Deconvoluter`]. Finally,
+    /// peaks in the signal region are filtered according to the following
+    /// criterion:
     ///
     /// ```text
     /// score > mean + threshold * std_dev
     /// ```
+    ///
+    /// Optionally, the number of selected peaks can be capped, in which case
+    /// only the highest scoring peaks that pass the filter are kept. This
+    /// limits the runtime of the fitting on very dense spectra. With
+    /// [`NoiseEstimator::Mad`], the median and the median absolute deviation
+    /// replace the mean and standard deviation, such that residual signals
+    /// among the noise peaks don't inflate the threshold.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
     NoiseScoreFilter {
         /// Scoring method to use.
         scoring_method: ScoringMethod,
         /// Score threshold to use for filtering peaks.
         threshold: f64,
+        /// Maximum number of peaks to keep, or `None` to keep all of them.
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_peaks: Option<usize>,
+        /// Method to estimate the noise level from the noise scores.
+        #[cfg_attr(feature = "serde", serde(default))]
+        noise_estimator: NoiseEstimator,
     },
 }
 
@@ -61,6 +154,8 @@ impl Default for SelectionSettings {
         SelectionSettings::NoiseScoreFilter {
             scoring_method: ScoringMethod::default(),
             threshold: 5.0,
+            max_peaks: None,
+            noise_estimator: NoiseEstimator::default(),
         }
     }
 }
@@ -72,11 +167,22 @@ impl std::fmt::Display for SelectionSettings {
             SelectionSettings::NoiseScoreFilter {
                 scoring_method,
                 threshold,
-            } => write!(
-                f,
-                "Noise Score Filter [scoring method: {}, score threshold: {}]",
-                scoring_method, threshold
-            ),
+                max_peaks,
+                noise_estimator,
+            } => {
+                write!(
+                    f,
+                    "Noise Score Filter [scoring method: {}, score threshold: {}",
+                    scoring_method, threshold
+                )?;
+                if let Some(max_peaks) = max_peaks {
+                    write!(f, ", maximum number of peaks: {}", max_peaks)?;
+                }
+                if let NoiseEstimator::Mad = noise_estimator {
+                    write!(f, ", noise estimator: {}", noise_estimator)?;
+                }
+                write!(f, "]")
+            }
         }
     }
 }
@@ -85,8 +191,12 @@ impl Settings for SelectionSettings {
     fn validate(&self) -> Result<()> {
         match self {
             SelectionSettings::DetectorOnly => {}
-            SelectionSettings::NoiseScoreFilter { threshold, .. } => {
-                if *threshold <= 0.0 || !threshold.is_finite() {
+            SelectionSettings::NoiseScoreFilter {
+                threshold,
+                max_peaks,
+                ..
+            } => {
+                if *threshold <= 0.0 || !threshold.is_finite() || *max_peaks == Some(0) {
                     return Err(
                         Error::new(Kind::InvalidSelectionSettings { settings: *self }).into(),
                     );
@@ -105,14 +215,20 @@ impl Settings for SelectionSettings {
                 SelectionSettings::NoiseScoreFilter {
                     scoring_method: scoring_method1,
                     threshold: threshold1,
+                    max_peaks: max_peaks1,
+                    noise_estimator: noise_estimator1,
                 },
                 SelectionSettings::NoiseScoreFilter {
                     scoring_method: scoring_method2,
                     threshold: threshold2,
+                    max_peaks: max_peaks2,
+                    noise_estimator: noise_estimator2,
                 },
             ) => {
                 ScoringMethod::compare(scoring_method1, scoring_method2)
                     && float_cmp::approx_eq!(f64, *threshold1, *threshold2)
+                    && max_peaks1 == max_peaks2
+                    && NoiseEstimator::compare(noise_estimator1, noise_estimator2)
             }
             _ => false,
         }
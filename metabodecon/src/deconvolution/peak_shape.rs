@@ -0,0 +1,150 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Trait interface for the peak shapes the deconvolution can produce.
+///
+/// The deconvolution algorithm models each signal of a [`Spectrum`] with a
+/// parametric peak shape. Different fitting methods produce different shapes,
+/// for example [`Lorentzian`] or [`Gaussian`]. This trait provides the common
+/// functionality needed to work with the fitted signals without knowing which
+/// shape was used.
+///
+/// [`Spectrum`]: crate::spectrum::Spectrum
+/// [`Lorentzian`]: crate::deconvolution::Lorentzian
+/// [`Gaussian`]: crate::deconvolution::Gaussian
+///
+/// # Example
+///
+/// ```
+/// use float_cmp::assert_approx_eq;
+/// use metabodecon::deconvolution::{Gaussian, Lorentzian, PeakShape};
+///
+/// fn height<P: PeakShape>(peak: &P) -> f64 {
+///     peak.evaluate(peak.maxp())
+/// }
+///
+/// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+/// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+///
+/// assert_approx_eq!(f64, height(&lorentzian), 2.0);
+/// assert_approx_eq!(f64, height(&gaussian), 2.0);
+/// ```
+pub trait PeakShape: Copy + Send + Sync + std::fmt::Debug {
+    /// Evaluates the peak shape at a given position `x`.
+    fn evaluate(&self, x: f64) -> f64;
+
+    /// Computes the integral of the peak shape over the entire domain.
+    fn integral(&self) -> f64;
+
+    /// Computes the integral of the peak shape over the interval `[a, b]`.
+    fn integral_between(&self, a: f64, b: f64) -> f64;
+
+    /// Returns the position of the maximum (`maxp`).
+    fn maxp(&self) -> f64;
+
+    /// Returns the half-width at half-maximum (`hw`).
+    fn hw(&self) -> f64;
+}
+
+/// Evaluates the superposition of the given peak shapes at the given positions
+/// `x`.
+pub(crate) fn superposition_vec<P: PeakShape>(x: &[f64], peaks: &[P]) -> Vec<f64> {
+    x.iter()
+        .map(|&x| peaks.iter().map(|peak| peak.evaluate(x)).sum())
+        .collect()
+}
+
+/// Evaluates the superposition of the given peak shapes at the given positions
+/// `x` in parallel.
+#[cfg(feature = "parallel")]
+pub(crate) fn par_superposition_vec<P: PeakShape>(x: &[f64], peaks: &[P]) -> Vec<f64> {
+    x.par_iter()
+        .map(|&x| peaks.iter().map(|peak| peak.evaluate(x)).sum())
+        .collect()
+}
+
+/// Evaluates each of the given peak shapes separately at the given positions
+/// `x`.
+pub(crate) fn component_curves<P: PeakShape>(x: &[f64], peaks: &[P]) -> Vec<Vec<f64>> {
+    peaks
+        .iter()
+        .map(|peak| x.iter().map(|&x| peak.evaluate(x)).collect())
+        .collect()
+}
+
+/// Evaluates each of the given peak shapes separately at the given positions
+/// `x` in parallel.
+#[cfg(feature = "parallel")]
+pub(crate) fn par_component_curves<P: PeakShape>(x: &[f64], peaks: &[P]) -> Vec<Vec<f64>> {
+    peaks
+        .par_iter()
+        .map(|peak| x.iter().map(|&x| peak.evaluate(x)).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::{Gaussian, Lorentzian};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn superposition() {
+        let lorentzians = [
+            Lorentzian::new(1.0, 0.5, -2.0),
+            Lorentzian::new(2.0, 0.75, 0.0),
+            Lorentzian::new(1.0, 0.5, 2.0),
+        ];
+        let gaussians = [
+            Gaussian::new(1.0, 0.5, -2.0),
+            Gaussian::new(2.0, 0.75, 0.0),
+            Gaussian::new(1.0, 0.5, 2.0),
+        ];
+        let chemical_shifts = (0..11)
+            .map(|x| -5.0 + x as f64)
+            .collect::<Vec<f64>>();
+        let expected = Lorentzian::superposition_vec(&chemical_shifts, &lorentzians);
+        superposition_vec(&chemical_shifts, &lorentzians)
+            .into_iter()
+            .zip(expected)
+            .for_each(|(computed, expected)| assert_approx_eq!(f64, computed, expected));
+        let expected = Gaussian::superposition_vec(&chemical_shifts, &gaussians);
+        superposition_vec(&chemical_shifts, &gaussians)
+            .into_iter()
+            .zip(expected)
+            .for_each(|(computed, expected)| assert_approx_eq!(f64, computed, expected));
+        #[cfg(feature = "parallel")]
+        {
+            let expected = superposition_vec(&chemical_shifts, &gaussians);
+            par_superposition_vec(&chemical_shifts, &gaussians)
+                .into_iter()
+                .zip(expected)
+                .for_each(|(computed, expected)| assert_approx_eq!(f64, computed, expected));
+        }
+    }
+
+    #[test]
+    fn component_curves() {
+        let gaussians = [
+            Gaussian::new(1.0, 0.5, -2.0),
+            Gaussian::new(2.0, 0.75, 0.0),
+            Gaussian::new(1.0, 0.5, 2.0),
+        ];
+        let chemical_shifts = (0..11)
+            .map(|x| -5.0 + x as f64)
+            .collect::<Vec<f64>>();
+        let components = super::component_curves(&chemical_shifts, &gaussians);
+        assert_eq!(components.len(), gaussians.len());
+        components
+            .iter()
+            .zip(gaussians.iter())
+            .for_each(|(component, gaussian)| {
+                assert_eq!(component, &gaussian.evaluate_vec(&chemical_shifts));
+            });
+        #[cfg(feature = "parallel")]
+        assert_eq!(
+            par_component_curves(&chemical_shifts, &gaussians),
+            components
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::peak_shape::PeakShape;
+
+/// [`Lorentzian`] with cached untransformed parameters.
+///
+/// Recovering the half-width (`hw`) and scale factor (`sf`) from the
+/// transformed parameters of a [`Lorentzian`] requires a square root and a
+/// division, which [`Lorentzian::sf`], [`Lorentzian::hw`], and
+/// [`Lorentzian::integral`] recompute on every call. `PrecomputedLorentzian`
+/// computes them once on construction, such that repeated queries, for
+/// example of the areas of many signals, are cheap. Use
+/// [`Lorentzian::precompute`] to create one.
+///
+/// The cached values are computed with the same operations as the methods of
+/// [`Lorentzian`], so the results are identical. As the cache is not updated,
+/// `PrecomputedLorentzian` is immutable. Modify the [`Lorentzian`] and
+/// precompute it again instead.
+///
+/// # Example
+///
+/// ```
+/// use float_cmp::assert_approx_eq;
+/// use metabodecon::deconvolution::Lorentzian;
+///
+/// let lorentzians = [
+///     Lorentzian::new(0.03, 0.0009, 4.8),
+///     Lorentzian::new(0.02, 0.0004, 5.0),
+///     Lorentzian::new(0.03, 0.0009, 5.2),
+/// ];
+/// let precomputed = lorentzians
+///     .iter()
+///     .map(Lorentzian::precompute)
+///     .collect::<Vec<_>>();
+///
+/// // The untransformed parameters are computed once and then only looked up.
+/// let areas = precomputed
+///     .iter()
+///     .map(|lorentzian| lorentzian.integral())
+///     .collect::<Vec<f64>>();
+/// assert_approx_eq!(f64, areas[0], lorentzians[0].integral());
+/// assert_approx_eq!(f64, precomputed[1].hw(), 0.02);
+///
+/// // It can be used wherever a Lorentzian is expected by reference.
+/// assert_approx_eq!(
+///     f64,
+///     Lorentzian::superposition(5.0, &precomputed),
+///     Lorentzian::superposition(5.0, &lorentzians)
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct PrecomputedLorentzian {
+    /// The underlying `Lorentzian`.
+    lorentzian: Lorentzian,
+    /// Cached scale factor (`sf`).
+    sf: f64,
+    /// Cached half-width (`hw`).
+    hw: f64,
+}
+
+impl From<Lorentzian> for PrecomputedLorentzian {
+    fn from(lorentzian: Lorentzian) -> Self {
+        let hw = lorentzian.hw2().sqrt();
+        let sf = lorentzian.sfhw() / hw;
+
+        Self { lorentzian, sf, hw }
+    }
+}
+
+impl From<PrecomputedLorentzian> for Lorentzian {
+    fn from(precomputed: PrecomputedLorentzian) -> Self {
+        precomputed.lorentzian
+    }
+}
+
+impl AsRef<Lorentzian> for PrecomputedLorentzian {
+    fn as_ref(&self) -> &Lorentzian {
+        &self.lorentzian
+    }
+}
+
+impl PeakShape for PrecomputedLorentzian {
+    fn evaluate(&self, x: f64) -> f64 {
+        PrecomputedLorentzian::evaluate(self, x)
+    }
+
+    fn integral(&self) -> f64 {
+        PrecomputedLorentzian::integral(self)
+    }
+
+    fn integral_between(&self, a: f64, b: f64) -> f64 {
+        PrecomputedLorentzian::integral_between(self, a, b)
+    }
+
+    fn maxp(&self) -> f64 {
+        PrecomputedLorentzian::maxp(self)
+    }
+
+    fn hw(&self) -> f64 {
+        PrecomputedLorentzian::hw(self)
+    }
+}
+
+impl PrecomputedLorentzian {
+    /// Returns the underlying [`Lorentzian`].
+    pub fn lorentzian(&self) -> &Lorentzian {
+        &self.lorentzian
+    }
+
+    /// Returns the transformed scale factor (`sfhw = sf * hw`).
+    pub fn sfhw(&self) -> f64 {
+        self.lorentzian.sfhw()
+    }
+
+    /// Returns the transformed half-width (`hw2 = hw²`).
+    pub fn hw2(&self) -> f64 {
+        self.lorentzian.hw2()
+    }
+
+    /// Returns the position of the maximum (`maxp`).
+    pub fn maxp(&self) -> f64 {
+        self.lorentzian.maxp()
+    }
+
+    /// Returns the cached scale factor (`sf`).
+    pub fn sf(&self) -> f64 {
+        self.sf
+    }
+
+    /// Returns the cached half-width (`hw`).
+    pub fn hw(&self) -> f64 {
+        self.hw
+    }
+
+    /// Returns the untransformed parameters as a tuple `(sf, hw, maxp)`.
+    pub fn untransformed_parameters(&self) -> (f64, f64, f64) {
+        (self.sf, self.hw, self.maxp())
+    }
+
+    /// Returns the full width at half maximum (`fwhm = 2 * hw`).
+    pub fn fwhm(&self) -> f64 {
+        2.0 * self.hw
+    }
+
+    /// Returns the intensity at the position of the maximum
+    /// (`sfhw / hw2 = sf / hw`).
+    pub fn max_intensity(&self) -> f64 {
+        self.lorentzian.max_intensity()
+    }
+
+    /// Evaluates the `Lorentzian` function at a given position `x`.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.lorentzian.evaluate(x)
+    }
+
+    /// Evaluates the `Lorentzian` function at the given positions `x`.
+    pub fn evaluate_vec(&self, x: &[f64]) -> Vec<f64> {
+        self.lorentzian.evaluate_vec(x)
+    }
+
+    /// Computes the integral of the `Lorentzian` over the entire domain. See
+    /// [`Lorentzian::integral`].
+    pub fn integral(&self) -> f64 {
+        std::f64::consts::PI * self.sf
+    }
+
+    /// Computes the integral of the `Lorentzian` over the interval `[a, b]`.
+    /// See [`Lorentzian::integral_between`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::Lorentzian;
+    ///
+    /// let lorentzian = Lorentzian::new(0.045, 0.0225, 5.0);
+    /// let precomputed = lorentzian.precompute();
+    ///
+    /// [(4.8, 5.5), (5.5, 4.8), (f64::NEG_INFINITY, 5.0)]
+    ///     .into_iter()
+    ///     .for_each(|(a, b)| {
+    ///         assert_approx_eq!(
+    ///             f64,
+    ///             precomputed.integral_between(a, b),
+    ///             lorentzian.integral_between(a, b)
+    ///         );
+    ///     });
+    /// ```
+    pub fn integral_between(&self, a: f64, b: f64) -> f64 {
+        let (a, b) = (a - self.maxp(), b - self.maxp());
+        if a.is_infinite() || b.is_infinite() {
+            return self.sf * (f64::atan(b / self.hw) - f64::atan(a / self.hw));
+        }
+
+        self.sf * f64::atan2(self.hw * (b - a), self.hw2() + a * b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(PrecomputedLorentzian);
+        assert_sync!(PrecomputedLorentzian);
+    }
+
+    #[test]
+    fn matches_lorentzian() {
+        let lorentzians = (1..50)
+            .map(|i| {
+                let hw = 0.0005 * i as f64;
+                Lorentzian::new((1.0 + (i % 3) as f64) * hw, hw.powi(2), 0.1 * i as f64)
+            })
+            .collect::<Vec<Lorentzian>>();
+        lorentzians.iter().for_each(|lorentzian| {
+            let precomputed = lorentzian.precompute();
+            assert_eq!(precomputed.sf(), lorentzian.sf());
+            assert_eq!(precomputed.hw(), lorentzian.hw());
+            assert_eq!(precomputed.fwhm(), lorentzian.fwhm());
+            assert_eq!(precomputed.integral(), lorentzian.integral());
+            assert_eq!(
+                precomputed.untransformed_parameters(),
+                lorentzian.untransformed_parameters()
+            );
+            [
+                (0.0, 5.0),
+                (5.0, 0.0),
+                (lorentzian.maxp() - 0.01, lorentzian.maxp() + 0.02),
+                (f64::NEG_INFINITY, lorentzian.maxp()),
+                (f64::NEG_INFINITY, f64::INFINITY),
+            ]
+            .into_iter()
+            .for_each(|(a, b)| {
+                let (sf, hw, maxp) = lorentzian.untransformed_parameters();
+                let on_the_fly = sf * (f64::atan((b - maxp) / hw) - f64::atan((a - maxp) / hw));
+                assert_approx_eq!(
+                    f64,
+                    precomputed.integral_between(a, b),
+                    on_the_fly,
+                    epsilon = 1e-12
+                );
+            });
+            assert_eq!(
+                Lorentzian::from(precomputed).parameters(),
+                lorentzian.parameters()
+            );
+        });
+        let precomputed = lorentzians
+            .iter()
+            .map(Lorentzian::precompute)
+            .collect::<Vec<PrecomputedLorentzian>>();
+        let chemical_shifts = (0..100)
+            .map(|x| x as f64 * 5.0 / 99.0)
+            .collect::<Vec<f64>>();
+        assert_eq!(
+            Lorentzian::superposition_vec(&chemical_shifts, &precomputed),
+            Lorentzian::superposition_vec(&chemical_shifts, &lorentzians)
+        );
+    }
+}
@@ -0,0 +1,648 @@
+use crate::deconvolution::gaussian::Gaussian;
+use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::peak_shape::PeakShape;
+use std::f64::consts::{LN_2, PI};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Data structure that represents a [pseudo-Voigt function].
+///
+/// # Definition
+///
+/// The [Voigt profile] is the convolution of a Lorentzian and a Gaussian
+/// function, which describes NMR signals that are broadened by both
+/// relaxation and, for example, apodization or field inhomogeneity. Since the
+/// convolution has no closed form, it is commonly approximated by the
+/// pseudo-Voigt function, a linear combination of a [`Lorentzian`] and a
+/// [`Gaussian`] with the same half-width at half-maximum and position:
+///
+/// ```text
+/// f(x) = sf * (eta * L(x) + (1 - eta) * G(x))
+/// L(x) = hw² / (hw² + (x - maxp)²)
+/// G(x) = exp(-ln(2) * (x - maxp)² / hw²)
+/// ```
+///
+/// - `eta` is the mixing parameter between 0 (Gaussian) and 1 (Lorentzian).
+/// - `hw` is the half-width at half-maximum.
+/// - `maxp` is the position of the maximum.
+/// - `sf` is the scale factor, which is the height of the maximum.
+///
+/// [pseudo-Voigt function]: https://en.wikipedia.org/wiki/Voigt_profile#Pseudo-Voigt
+/// [Voigt profile]: https://en.wikipedia.org/wiki/Voigt_profile
+/// [`Lorentzian`]: crate::deconvolution::Lorentzian
+/// [`Gaussian`]: crate::deconvolution::Gaussian
+///
+/// # Invalid Parameters
+///
+/// For `PseudoVoigt` to represent a valid peak shape, the parameters `sf` and
+/// `hw` must be positive and `eta` must be within `[0, 1]`. This is not
+/// enforced to avoid unnecessary overhead. Instances created by the library
+/// are guaranteed to have valid values, but if you construct a `PseudoVoigt`
+/// manually, you are responsible for ensuring the parameters are valid.
+///
+/// # Thread Safety
+///
+/// The `PseudoVoigt` type is both [`Send`] and [`Sync`], allowing safe sharing
+/// and access across threads.
+///
+/// # Serialization with [Serde]
+///
+/// [Serde]: https://serde.rs/
+///
+/// When the `serde` feature is enabled, `PseudoVoigt` can be serialized and
+/// deserialized using `serde`. The parameters `(eta, hw, maxp, sf)` are stored
+/// as they are.
+///
+/// # Example
+///
+/// ```
+/// use float_cmp::assert_approx_eq;
+/// use metabodecon::deconvolution::PseudoVoigt;
+///
+/// // Create an equal mix of a Lorentzian and a Gaussian centered at 5.0 ppm
+/// // with maximum intensity 2.0 and hw 0.15.
+/// let pseudo_voigt = PseudoVoigt::new(0.5, 0.15, 5.0, 2.0);
+///
+/// // Evaluate the pseudo-Voigt function at its maximum position.
+/// assert_approx_eq!(f64, pseudo_voigt.evaluate(5.0), 2.0);
+///
+/// // Generate 100 chemical shifts between 0.0 and 10.0 ppm.
+/// let chemical_shifts = (0..100)
+///     .map(|x| x as f64 * 10.0 / 99.0)
+///     .collect::<Vec<f64>>();
+///
+/// // Evaluate the pseudo-Voigt function at the chemical shifts.
+/// let intensities = pseudo_voigt.evaluate_vec(&chemical_shifts);
+///
+/// // Create a peak triplet centered at 5.0 ppm with shorter side peaks.
+/// let triplet = [
+///     PseudoVoigt::new(0.5, 0.03, 4.8, 1.0),
+///     PseudoVoigt::new(0.5, 0.02, 5.0, 2.0),
+///     PseudoVoigt::new(0.5, 0.03, 5.2, 1.0),
+/// ];
+///
+/// // Evaluate the superposition of the pseudo-Voigt functions at the chemical
+/// // shifts.
+/// let sup1 = PseudoVoigt::superposition_vec(&chemical_shifts, &triplet);
+/// // ...or in parallel (less efficient for small datasets).
+/// let sup2 = PseudoVoigt::par_superposition_vec(&chemical_shifts, &triplet);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename = "PseudoVoigt")
+)]
+pub struct PseudoVoigt {
+    /// Mixing parameter between the Gaussian (0) and Lorentzian (1) parts.
+    eta: f64,
+    /// Half-width at half-maximum (`hw`).
+    hw: f64,
+    /// Position of the maximum (`maxp`).
+    maxp: f64,
+    /// Scale factor, the height of the maximum (`sf`).
+    sf: f64,
+}
+
+impl AsRef<PseudoVoigt> for PseudoVoigt {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl PeakShape for PseudoVoigt {
+    fn evaluate(&self, x: f64) -> f64 {
+        PseudoVoigt::evaluate(self, x)
+    }
+
+    fn integral(&self) -> f64 {
+        PseudoVoigt::integral(self)
+    }
+
+    fn integral_between(&self, a: f64, b: f64) -> f64 {
+        PseudoVoigt::integral_between(self, a, b)
+    }
+
+    fn maxp(&self) -> f64 {
+        self.maxp
+    }
+
+    fn hw(&self) -> f64 {
+        self.hw
+    }
+}
+
+impl PseudoVoigt {
+    /// Constructs a new `PseudoVoigt` from the given parameters.
+    ///
+    /// - `eta`: The mixing parameter, 0 for a pure Gaussian and 1 for a pure
+    ///   Lorentzian.
+    /// - `hw`: The half-width at half-maximum.
+    /// - `maxp`: The position of the maximum.
+    /// - `sf`: The scale factor, which is the height of the maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// // Mostly Lorentzian signal centered at 5 ppm with maximum intensity 2.0
+    /// // and a half width of 0.15 ppm.
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// ```
+    pub fn new(eta: f64, hw: f64, maxp: f64, sf: f64) -> Self {
+        Self { eta, hw, maxp, sf }
+    }
+
+    /// Returns the mixing parameter (`eta`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.eta(), 0.8);
+    /// ```
+    pub fn eta(&self) -> f64 {
+        self.eta
+    }
+
+    /// Returns the half-width at half-maximum (`hw`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.hw(), 0.15);
+    /// ```
+    pub fn hw(&self) -> f64 {
+        self.hw
+    }
+
+    /// Returns the position of the maximum (`maxp`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.maxp(), 5.0);
+    /// ```
+    pub fn maxp(&self) -> f64 {
+        self.maxp
+    }
+
+    /// Returns the scale factor (`sf`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.sf(), 2.0);
+    /// ```
+    pub fn sf(&self) -> f64 {
+        self.sf
+    }
+
+    /// Returns the parameters as a tuple `(eta, hw, maxp, sf)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// let (eta, hw, maxp, sf) = pseudo_voigt.parameters();
+    ///
+    /// assert_approx_eq!(f64, eta, 0.8);
+    /// assert_approx_eq!(f64, hw, 0.15);
+    /// assert_approx_eq!(f64, maxp, 5.0);
+    /// assert_approx_eq!(f64, sf, 2.0);
+    /// ```
+    pub fn parameters(&self) -> (f64, f64, f64, f64) {
+        (self.eta, self.hw, self.maxp, self.sf)
+    }
+
+    /// Internal helper function to check whether the `PseudoVoigt` represents
+    /// a valid peak shape, i.e. `eta` is within `[0, 1]`, `sf` is finite and
+    /// non-negative, `hw` is finite and positive and `maxp` is finite.
+    pub(crate) fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.eta)
+            && self.sf.is_finite()
+            && self.sf >= 0.0
+            && self.hw.is_finite()
+            && self.hw > 0.0
+            && self.maxp.is_finite()
+    }
+
+    /// Sets the mixing parameter (`eta`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let mut pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// pseudo_voigt.set_eta(0.2);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.eta(), 0.2);
+    /// ```
+    pub fn set_eta(&mut self, eta: f64) {
+        self.eta = eta;
+    }
+
+    /// Sets the half-width at half-maximum (`hw`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let mut pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// pseudo_voigt.set_hw(0.3);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.hw(), 0.3);
+    /// ```
+    pub fn set_hw(&mut self, hw: f64) {
+        self.hw = hw;
+    }
+
+    /// Sets the position of the maximum (`maxp`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let mut pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// pseudo_voigt.set_maxp(-5.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.maxp(), -5.0);
+    /// ```
+    pub fn set_maxp(&mut self, maxp: f64) {
+        self.maxp = maxp;
+    }
+
+    /// Sets the scale factor (`sf`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let mut pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// pseudo_voigt.set_sf(3.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.sf(), 3.0);
+    /// ```
+    pub fn set_sf(&mut self, sf: f64) {
+        self.sf = sf;
+    }
+
+    /// Sets the parameters `(eta, hw, maxp, sf)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let mut pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// pseudo_voigt.set_parameters(0.2, 0.3, -5.0, 3.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.eta(), 0.2);
+    /// assert_approx_eq!(f64, pseudo_voigt.hw(), 0.3);
+    /// assert_approx_eq!(f64, pseudo_voigt.maxp(), -5.0);
+    /// assert_approx_eq!(f64, pseudo_voigt.sf(), 3.0);
+    /// ```
+    pub fn set_parameters(&mut self, eta: f64, hw: f64, maxp: f64, sf: f64) {
+        self.eta = eta;
+        self.hw = hw;
+        self.maxp = maxp;
+        self.sf = sf;
+    }
+
+    /// Evaluates the `PseudoVoigt` function at a given position `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    ///
+    /// assert_approx_eq!(f64, pseudo_voigt.evaluate(5.0), 2.0);
+    /// assert_approx_eq!(f64, pseudo_voigt.evaluate(5.15), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let (lorentzian, gaussian) = self.components(x);
+        self.sf * (self.eta * lorentzian + (1.0 - self.eta) * gaussian)
+    }
+
+    /// Evaluates the `PseudoVoigt` function at the given positions `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = pseudo_voigt.evaluate_vec(&chemical_shifts);
+    /// ```
+    pub fn evaluate_vec(&self, x: &[f64]) -> Vec<f64> {
+        x.iter().map(|&x| self.evaluate(x)).collect()
+    }
+
+    /// Computes the integral of the `PseudoVoigt` over the entire domain.
+    ///
+    /// The integral is the weighted sum of the integrals of the Lorentzian and
+    /// Gaussian parts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{Gaussian, Lorentzian, PseudoVoigt};
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// let lorentzian = Lorentzian::new(2.0 * 0.15_f64.powi(2), 0.15_f64.powi(2), 5.0);
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    ///
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     pseudo_voigt.integral(),
+    ///     0.8 * lorentzian.integral() + 0.2 * gaussian.integral()
+    /// );
+    /// ```
+    pub fn integral(&self) -> f64 {
+        self.sf * self.hw * (self.eta * PI + (1.0 - self.eta) * f64::sqrt(PI / LN_2))
+    }
+
+    /// Computes the integral of the `PseudoVoigt` over the interval `[a, b]`.
+    ///
+    /// The integral is the weighted sum of the integrals of the Lorentzian and
+    /// Gaussian parts over the interval. See [`Lorentzian::integral_between`]
+    /// and [`Gaussian::integral_between`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::{Gaussian, Lorentzian, PseudoVoigt};
+    ///
+    /// let pseudo_voigt = PseudoVoigt::new(0.8, 0.15, 5.0, 2.0);
+    /// let lorentzian = Lorentzian::new(2.0 * 0.15_f64.powi(2), 0.15_f64.powi(2), 5.0);
+    /// let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+    /// let (a, b) = (4.8, 5.5);
+    ///
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     pseudo_voigt.integral_between(a, b),
+    ///     0.8 * lorentzian.integral_between(a, b) + 0.2 * gaussian.integral_between(a, b),
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn integral_between(&self, a: f64, b: f64) -> f64 {
+        let hw2 = self.hw.powi(2);
+        let lorentzian = Lorentzian::new(self.sf * hw2, hw2, self.maxp);
+        let gaussian = Gaussian::new(self.sf, self.hw, self.maxp);
+
+        self.eta * lorentzian.integral_between(a, b)
+            + (1.0 - self.eta) * gaussian.integral_between(a, b)
+    }
+
+    /// Evaluates the superposition of the given `PseudoVoigt`s at the given
+    /// position `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let doublet = [
+    ///     PseudoVoigt::new(1.0, 0.1, 4.9, 1.0),
+    ///     PseudoVoigt::new(0.0, 0.1, 5.1, 1.0),
+    /// ];
+    ///
+    /// assert_approx_eq!(
+    ///     f64,
+    ///     PseudoVoigt::superposition(5.0, &doublet),
+    ///     1.0,
+    ///     epsilon = 1e-12
+    /// );
+    /// ```
+    pub fn superposition<P: AsRef<PseudoVoigt>>(x: f64, pseudo_voigts: &[P]) -> f64 {
+        pseudo_voigts
+            .iter()
+            .map(|pseudo_voigt| pseudo_voigt.as_ref().evaluate(x))
+            .sum()
+    }
+
+    /// Evaluates the superposition of the given `PseudoVoigt`s at the given
+    /// positions `x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let triplet = [
+    ///     PseudoVoigt::new(0.5, 0.03, 4.8, 1.0),
+    ///     PseudoVoigt::new(0.5, 0.02, 5.0, 2.0),
+    ///     PseudoVoigt::new(0.5, 0.03, 5.2, 1.0),
+    /// ];
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let sup = PseudoVoigt::superposition_vec(&chemical_shifts, &triplet);
+    /// ```
+    pub fn superposition_vec<P: AsRef<PseudoVoigt>>(x: &[f64], pseudo_voigts: &[P]) -> Vec<f64> {
+        x.iter()
+            .map(|&x| Self::superposition(x, pseudo_voigts))
+            .collect()
+    }
+
+    /// Evaluates the superposition of the given `PseudoVoigt`s at the given
+    /// positions `x` in parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::PseudoVoigt;
+    ///
+    /// let triplet = [
+    ///     PseudoVoigt::new(0.5, 0.03, 4.8, 1.0),
+    ///     PseudoVoigt::new(0.5, 0.02, 5.0, 2.0),
+    ///     PseudoVoigt::new(0.5, 0.03, 5.2, 1.0),
+    /// ];
+    /// let chemical_shifts = (0..100)
+    ///     .map(|x| x as f64 * 10.0 / 99.0)
+    ///     .collect::<Vec<f64>>();
+    /// let sup = PseudoVoigt::par_superposition_vec(&chemical_shifts, &triplet);
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_superposition_vec<P: AsRef<PseudoVoigt> + Send + Sync>(
+        x: &[f64],
+        pseudo_voigts: &[P],
+    ) -> Vec<f64> {
+        x.par_iter()
+            .map(|&x| Self::superposition(x, pseudo_voigts))
+            .collect()
+    }
+
+    /// Internal helper function to evaluate the unit height Lorentzian and
+    /// Gaussian components at a given position `x`.
+    pub(crate) fn components(&self, x: f64) -> (f64, f64) {
+        let hw2 = self.hw.powi(2);
+        let distance2 = (x - self.maxp).powi(2);
+        let lorentzian = hw2 / (hw2 + distance2);
+        let gaussian = f64::exp(-LN_2 * distance2 / hw2);
+
+        (lorentzian, gaussian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(PseudoVoigt);
+        assert_sync!(PseudoVoigt);
+    }
+
+    #[test]
+    fn accessors() {
+        let pseudo_voigt = PseudoVoigt::new(0.25, 0.5, 1.0, 2.0);
+        assert_approx_eq!(f64, pseudo_voigt.eta(), 0.25);
+        assert_approx_eq!(f64, pseudo_voigt.hw(), 0.5);
+        assert_approx_eq!(f64, pseudo_voigt.maxp(), 1.0);
+        assert_approx_eq!(f64, pseudo_voigt.sf(), 2.0);
+    }
+
+    #[test]
+    fn mutators() {
+        let mut pseudo_voigt = PseudoVoigt::new(0.5, 1.0, 0.0, 1.0);
+        pseudo_voigt.set_eta(0.75);
+        pseudo_voigt.set_hw(2.5);
+        pseudo_voigt.set_maxp(1.0);
+        pseudo_voigt.set_sf(1.5);
+        assert_approx_eq!(f64, pseudo_voigt.eta(), 0.75);
+        assert_approx_eq!(f64, pseudo_voigt.hw(), 2.5);
+        assert_approx_eq!(f64, pseudo_voigt.maxp(), 1.0);
+        assert_approx_eq!(f64, pseudo_voigt.sf(), 1.5);
+        pseudo_voigt.set_parameters(0.5, 1.0, 0.0, 1.0);
+        assert_approx_eq!(f64, pseudo_voigt.eta(), 0.5);
+        assert_approx_eq!(f64, pseudo_voigt.hw(), 1.0);
+        assert_approx_eq!(f64, pseudo_voigt.maxp(), 0.0);
+        assert_approx_eq!(f64, pseudo_voigt.sf(), 1.0);
+    }
+
+    #[test]
+    fn limiting_cases() {
+        let lorentzian = Lorentzian::new(0.5, 0.25, 1.0);
+        let gaussian = Gaussian::new(2.0, 0.5, 1.0);
+        let pure_lorentzian = PseudoVoigt::new(1.0, 0.5, 1.0, 2.0);
+        let pure_gaussian = PseudoVoigt::new(0.0, 0.5, 1.0, 2.0);
+        let chemical_shifts = (0..11)
+            .map(|x| -5.0 + x as f64)
+            .collect::<Vec<f64>>();
+        chemical_shifts.iter().for_each(|&x| {
+            assert_approx_eq!(f64, pure_lorentzian.evaluate(x), lorentzian.evaluate(x));
+            assert_approx_eq!(f64, pure_gaussian.evaluate(x), gaussian.evaluate(x));
+        });
+        assert_approx_eq!(f64, pure_lorentzian.integral(), lorentzian.integral());
+        assert_approx_eq!(f64, pure_gaussian.integral(), gaussian.integral());
+    }
+
+    #[test]
+    fn superposition() {
+        let pseudo_voigts = vec![
+            PseudoVoigt::new(0.2, 1.0, -2.0, 1.0),
+            PseudoVoigt::new(0.5, 1.0, 0.0, 2.0),
+            PseudoVoigt::new(0.8, 1.0, 2.0, 1.0),
+        ];
+        let chemical_shifts = (0..11)
+            .map(|x| -5.0 + x as f64)
+            .collect::<Vec<f64>>();
+        let expected_intensities = chemical_shifts
+            .iter()
+            .map(|&x| {
+                pseudo_voigts
+                    .iter()
+                    .map(|pseudo_voigt| pseudo_voigt.evaluate(x))
+                    .sum::<f64>()
+            })
+            .collect::<Vec<f64>>();
+        let computed_intensities = PseudoVoigt::superposition_vec(&chemical_shifts, &pseudo_voigts);
+        computed_intensities
+            .iter()
+            .zip(expected_intensities.iter())
+            .for_each(|(&yc, &ye)| {
+                assert_approx_eq!(f64, yc, ye);
+            });
+        #[cfg(feature = "parallel")]
+        {
+            let computed_intensities =
+                PseudoVoigt::par_superposition_vec(&chemical_shifts, &pseudo_voigts);
+            computed_intensities
+                .iter()
+                .zip(expected_intensities.iter())
+                .for_each(|(&yc, &ye)| {
+                    assert_approx_eq!(f64, yc, ye);
+                });
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialization_round_trip() {
+        let pseudo_voigts = vec![
+            PseudoVoigt::new(0.2, 0.25, 3.0, 5.5),
+            PseudoVoigt::new(0.5, 0.16, 5.0, 7.0),
+            PseudoVoigt::new(0.8, 0.25, 7.0, 5.5),
+        ];
+        let serialized = serde_json::to_string(&pseudo_voigts).unwrap();
+        let deserialized = serde_json::from_str::<Vec<PseudoVoigt>>(&serialized).unwrap();
+        pseudo_voigts
+            .iter()
+            .zip(deserialized.iter())
+            .for_each(|(init, rec)| {
+                assert_approx_eq!(f64, init.eta(), rec.eta());
+                assert_approx_eq!(f64, init.hw(), rec.hw());
+                assert_approx_eq!(f64, init.maxp(), rec.maxp());
+                assert_approx_eq!(f64, init.sf(), rec.sf());
+            });
+    }
+}
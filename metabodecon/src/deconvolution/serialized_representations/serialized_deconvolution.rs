@@ -1,7 +1,11 @@
 use crate::deconvolution::Deconvolution;
-use crate::deconvolution::fitting::FittingSettings;
+use crate::deconvolution::deconvolution_warning::DeconvolutionWarning;
+use crate::deconvolution::fitting::{FitDiagnostics, FittingSettings, ParameterUncertainty};
+use crate::deconvolution::gaussian::Gaussian;
 use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::mse_weighting::MseWeighting;
 use crate::deconvolution::peak_selection::SelectionSettings;
+use crate::deconvolution::pseudo_voigt::PseudoVoigt;
 use crate::deconvolution::smoothing::SmoothingSettings;
 use crate::{Error, Result, Settings};
 use serde::{Deserialize, Serialize};
@@ -28,6 +32,37 @@ pub(crate) struct SerializedDeconvolution {
     mse: f64,
     /// The deconvoluted signals.
     lorentzians: Vec<Lorentzian>,
+    /// The deconvoluted signals, if fitted as Gaussians.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    gaussians: Vec<Gaussian>,
+    /// The deconvoluted signals, if fitted as pseudo-Voigt functions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pseudo_voigts: Vec<PseudoVoigt>,
+    /// The estimated noise level used during the peak selection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    noise_estimate: Option<f64>,
+    /// The weighting of the residuals used to compute the MSE.
+    #[serde(default)]
+    mse_weighting: MseWeighting,
+    /// The diagnostics of the fit of each signal, if reported by the fitter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fit_diagnostics: Option<Vec<FitDiagnostics>>,
+    /// The standard errors of the parameters of each signal, if estimated by
+    /// the fitter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parameter_uncertainties: Option<Vec<ParameterUncertainty>>,
+    /// The labels of the deconvoluted signals, if any are labeled.
+    #[serde(default, skip_serializing_if = "is_unlabeled")]
+    labels: Vec<Option<String>>,
+    /// The quality issues found during the deconvolution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<DeconvolutionWarning>,
+}
+
+/// Internal helper function to skip serializing the labels if no signal is
+/// labeled.
+fn is_unlabeled(labels: &[Option<String>]) -> bool {
+    labels.iter().all(Option::is_none)
 }
 
 impl<D: AsRef<Deconvolution>> From<D> for SerializedDeconvolution {
@@ -40,6 +75,18 @@ impl<D: AsRef<Deconvolution>> From<D> for SerializedDeconvolution {
             fitting_settings: deconvolution.fitting_settings(),
             mse: deconvolution.mse(),
             lorentzians: deconvolution.lorentzians().to_vec(),
+            gaussians: deconvolution.gaussians().to_vec(),
+            pseudo_voigts: deconvolution.pseudo_voigts().to_vec(),
+            noise_estimate: deconvolution.noise_estimate(),
+            mse_weighting: deconvolution.mse_weighting(),
+            fit_diagnostics: deconvolution
+                .fit_diagnostics()
+                .map(|diagnostics| diagnostics.to_vec()),
+            parameter_uncertainties: deconvolution
+                .parameter_uncertainties()
+                .map(|uncertainties| uncertainties.to_vec()),
+            labels: deconvolution.labels().to_vec(),
+            warnings: deconvolution.warnings().to_vec(),
         }
     }
 }
@@ -48,17 +95,47 @@ impl TryFrom<SerializedDeconvolution> for Deconvolution {
     type Error = Error;
 
     fn try_from(value: SerializedDeconvolution) -> Result<Deconvolution> {
-        value.smoothing_settings.validate()?;
+        // Deconvolutions computed with a custom smoother record it as Custom,
+        // which is rejected by the validation but still valid to restore.
+        if !matches!(value.smoothing_settings, SmoothingSettings::Custom) {
+            value.smoothing_settings.validate()?;
+        }
         value.selection_settings.validate()?;
         value.fitting_settings.validate()?;
 
-        Ok(Deconvolution::new(
-            value.lorentzians,
-            value.smoothing_settings,
-            value.selection_settings,
-            value.fitting_settings,
-            value.mse,
-        ))
+        let deconvolution = match value.fitting_settings {
+            FittingSettings::Gaussian { .. } => Deconvolution::from_gaussians(
+                value.gaussians,
+                value.smoothing_settings,
+                value.selection_settings,
+                value.fitting_settings,
+                value.mse,
+            ),
+            FittingSettings::PseudoVoigt { .. } => Deconvolution::from_pseudo_voigts(
+                value.pseudo_voigts,
+                value.smoothing_settings,
+                value.selection_settings,
+                value.fitting_settings,
+                value.mse,
+            ),
+            _ => Deconvolution::new(
+                value.lorentzians,
+                value.smoothing_settings,
+                value.selection_settings,
+                value.fitting_settings,
+                value.mse,
+            ),
+        };
+
+        deconvolution.validate()?;
+
+        Ok(deconvolution
+            .with_noise_estimate(value.noise_estimate)
+            .with_mse_weighting(value.mse_weighting)
+            .with_fit_diagnostics(value.fit_diagnostics)
+            .with_parameter_uncertainties(value.parameter_uncertainties)
+            .with_labels(value.labels)
+            .with_warnings(value.warnings))
     }
 }
 
@@ -77,6 +154,29 @@ mod tests {
         ];
         let initial = SerializedDeconvolution {
             lorentzians,
+            gaussians: Vec::new(),
+            pseudo_voigts: Vec::new(),
+            noise_estimate: Some(0.25),
+            mse_weighting: MseWeighting::IntensityWeighted,
+            fit_diagnostics: Some(vec![
+                FitDiagnostics::new(10, 1e-5, false),
+                FitDiagnostics::new(10, 0.2, true),
+                FitDiagnostics::new(10, 1e-4, false),
+            ]),
+            parameter_uncertainties: Some(vec![
+                ParameterUncertainty::new(0.5, 0.01, 0.002),
+                ParameterUncertainty::new(0.25, 0.005, 0.001),
+                ParameterUncertainty::undetermined(),
+            ]),
+            labels: vec![
+                Some("alanine".to_string()),
+                None,
+                Some("lactate".to_string()),
+            ],
+            warnings: vec![
+                DeconvolutionWarning::Unconverged { count: 1 },
+                DeconvolutionWarning::ClampedWidth { count: 1 },
+            ],
             smoothing_settings: SmoothingSettings::default(),
             selection_settings: SelectionSettings::default(),
             fitting_settings: FittingSettings::default(),
@@ -93,8 +193,16 @@ mod tests {
                 assert_approx_eq!(f64, initial.hw2(), recovered.hw2());
                 assert_approx_eq!(f64, initial.maxp(), recovered.maxp());
             });
+        assert_eq!(recovered.noise_estimate, Some(0.25));
+        assert_eq!(recovered.mse_weighting, MseWeighting::IntensityWeighted);
+        assert_eq!(recovered.fit_diagnostics, initial.fit_diagnostics);
+        assert_eq!(
+            recovered.parameter_uncertainties,
+            initial.parameter_uncertainties
+        );
+        assert_eq!(recovered.labels, initial.labels);
+        assert_eq!(recovered.warnings, initial.warnings);
         match recovered.smoothing_settings {
-            SmoothingSettings::Identity => unreachable!(),
             SmoothingSettings::MovingAverage {
                 iterations,
                 window_size,
@@ -102,23 +210,28 @@ mod tests {
                 assert_eq!(iterations, 3);
                 assert_eq!(window_size, 3);
             }
+            _ => unreachable!(),
         };
         match recovered.selection_settings {
             SelectionSettings::DetectorOnly => unreachable!("expected noise score filter"),
             SelectionSettings::NoiseScoreFilter {
                 scoring_method,
                 threshold,
+                max_peaks,
+                ..
             } => {
                 match scoring_method {
                     ScoringMethod::MinimumSum => {}
                 }
                 assert_approx_eq!(f64, threshold, 5.0);
+                assert_eq!(max_peaks, None);
             }
         };
         match recovered.fitting_settings {
             FittingSettings::Analytical { iterations } => {
                 assert_eq!(iterations, 10);
             }
+            _ => panic!("expected analytical fitting"),
         };
     }
 
@@ -148,7 +261,6 @@ mod tests {
                 assert_approx_eq!(f64, init.maxp(), rec.maxp());
             });
         match recovered.smoothing_settings() {
-            SmoothingSettings::Identity => panic!("expected moving average"),
             SmoothingSettings::MovingAverage {
                 iterations,
                 window_size,
@@ -156,23 +268,53 @@ mod tests {
                 assert_eq!(iterations, 3);
                 assert_eq!(window_size, 3);
             }
+            _ => panic!("expected moving average"),
         };
         match recovered.selection_settings() {
             SelectionSettings::DetectorOnly => panic!("expected noise score filter"),
             SelectionSettings::NoiseScoreFilter {
                 scoring_method,
                 threshold,
+                max_peaks,
+                ..
             } => {
                 match scoring_method {
                     ScoringMethod::MinimumSum => {}
                 }
                 assert_approx_eq!(f64, threshold, 5.0);
+                assert_eq!(max_peaks, None);
             }
         };
         match recovered.fitting_settings() {
             FittingSettings::Analytical { iterations } => {
                 assert_eq!(iterations, 10);
             }
+            _ => panic!("expected analytical fitting"),
+        };
+    }
+
+    #[test]
+    fn custom_smoothing_settings() {
+        let initial = Deconvolution::new(
+            vec![Lorentzian::new(5.5, 0.25, 3.0)],
+            SmoothingSettings::Custom,
+            SelectionSettings::default(),
+            FittingSettings::default(),
+            0.5,
+        );
+        let recovered =
+            Deconvolution::try_from(SerializedDeconvolution::from(initial.clone())).unwrap();
+        assert!(matches!(
+            recovered.smoothing_settings(),
+            SmoothingSettings::Custom
+        ));
+        let invalid = SerializedDeconvolution {
+            smoothing_settings: SmoothingSettings::MovingAverage {
+                iterations: 0,
+                window_size: 3,
+            },
+            ..SerializedDeconvolution::from(initial)
         };
+        assert!(Deconvolution::try_from(invalid).is_err());
     }
 }
@@ -0,0 +1,179 @@
+use crate::deconvolution::gaussian::Gaussian;
+use crate::deconvolution::lorentzian::Lorentzian;
+use crate::deconvolution::peak_shape::PeakShape;
+use crate::deconvolution::pseudo_voigt::PseudoVoigt;
+
+/// Deconvoluted signal of any of the supported peak shapes.
+///
+/// The peak shape of the signals of a [`Deconvolution`] depends on the fitting
+/// method used. `Signal` wraps a single signal of any shape, such that the
+/// signals can be iterated over and inspected without knowing which shape was
+/// fitted. The common functionality is provided by the [`PeakShape`]
+/// implementation, while the shape specific parameters are available by
+/// matching on the variants.
+///
+/// [`Deconvolution`]: crate::deconvolution::Deconvolution
+///
+/// # Thread Safety
+///
+/// The `Signal` type is both [`Send`] and [`Sync`], allowing safe sharing and
+/// access across threads.
+///
+/// # Example
+///
+/// ```
+/// use float_cmp::assert_approx_eq;
+/// use metabodecon::deconvolution::{Gaussian, Lorentzian, PeakShape, Signal};
+///
+/// let signals = [
+///     Signal::from(Lorentzian::new(0.045, 0.0225, 3.0)),
+///     Signal::from(Gaussian::new(2.0, 0.15, 5.0)),
+/// ];
+///
+/// for signal in signals {
+///     assert_approx_eq!(f64, signal.max_intensity(), 2.0);
+///     assert_approx_eq!(f64, signal.fwhm(), 0.3);
+/// }
+/// if let Signal::Gaussian(gaussian) = signals[1] {
+///     assert_approx_eq!(f64, gaussian.sf(), 2.0);
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug)]
+pub enum Signal {
+    /// Signal fitted as a [`Lorentzian`].
+    Lorentzian(Lorentzian),
+    /// Signal fitted as a [`Gaussian`].
+    Gaussian(Gaussian),
+    /// Signal fitted as a [`PseudoVoigt`].
+    PseudoVoigt(PseudoVoigt),
+}
+
+impl From<Lorentzian> for Signal {
+    fn from(value: Lorentzian) -> Self {
+        Signal::Lorentzian(value)
+    }
+}
+
+impl From<Gaussian> for Signal {
+    fn from(value: Gaussian) -> Self {
+        Signal::Gaussian(value)
+    }
+}
+
+impl From<PseudoVoigt> for Signal {
+    fn from(value: PseudoVoigt) -> Self {
+        Signal::PseudoVoigt(value)
+    }
+}
+
+impl PeakShape for Signal {
+    fn evaluate(&self, x: f64) -> f64 {
+        match self {
+            Signal::Lorentzian(lorentzian) => lorentzian.evaluate(x),
+            Signal::Gaussian(gaussian) => gaussian.evaluate(x),
+            Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.evaluate(x),
+        }
+    }
+
+    fn integral(&self) -> f64 {
+        match self {
+            Signal::Lorentzian(lorentzian) => lorentzian.integral(),
+            Signal::Gaussian(gaussian) => gaussian.integral(),
+            Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.integral(),
+        }
+    }
+
+    fn integral_between(&self, a: f64, b: f64) -> f64 {
+        match self {
+            Signal::Lorentzian(lorentzian) => lorentzian.integral_between(a, b),
+            Signal::Gaussian(gaussian) => gaussian.integral_between(a, b),
+            Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.integral_between(a, b),
+        }
+    }
+
+    fn maxp(&self) -> f64 {
+        match self {
+            Signal::Lorentzian(lorentzian) => lorentzian.maxp(),
+            Signal::Gaussian(gaussian) => gaussian.maxp(),
+            Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.maxp(),
+        }
+    }
+
+    fn hw(&self) -> f64 {
+        match self {
+            Signal::Lorentzian(lorentzian) => lorentzian.hw(),
+            Signal::Gaussian(gaussian) => gaussian.hw(),
+            Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.hw(),
+        }
+    }
+}
+
+impl Signal {
+    /// Returns the full width at half maximum (`2 * hw`).
+    pub fn fwhm(&self) -> f64 {
+        2.0 * self.hw()
+    }
+
+    /// Returns the intensity at the position of the maximum.
+    pub fn max_intensity(&self) -> f64 {
+        self.evaluate(self.maxp())
+    }
+
+    /// Internal helper function to check whether the `Signal` represents a
+    /// valid peak shape.
+    pub(crate) fn is_valid(&self) -> bool {
+        match self {
+            Signal::Lorentzian(lorentzian) => lorentzian.is_valid(),
+            Signal::Gaussian(gaussian) => gaussian.is_valid(),
+            Signal::PseudoVoigt(pseudo_voigt) => pseudo_voigt.is_valid(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Signal);
+        assert_sync!(Signal);
+    }
+
+    #[test]
+    fn peak_shape() {
+        let lorentzian = Lorentzian::new(0.045, 0.0225, 3.0);
+        let gaussian = Gaussian::new(2.0, 0.15, 5.0);
+        let pseudo_voigt = PseudoVoigt::new(0.5, 0.15, 7.0, 2.0);
+        let signals = [
+            Signal::from(lorentzian),
+            Signal::from(gaussian),
+            Signal::from(pseudo_voigt),
+        ];
+        [
+            (lorentzian.maxp(), lorentzian.integral()),
+            (gaussian.maxp(), gaussian.integral()),
+            (pseudo_voigt.maxp(), pseudo_voigt.integral()),
+        ]
+        .iter()
+        .zip(signals.iter())
+        .for_each(|((maxp, integral), signal)| {
+            assert_approx_eq!(f64, signal.maxp(), *maxp);
+            assert_approx_eq!(f64, signal.integral(), *integral);
+            assert_approx_eq!(f64, signal.hw(), 0.15);
+            assert_approx_eq!(f64, signal.fwhm(), 0.3);
+            assert_approx_eq!(f64, signal.max_intensity(), 2.0);
+            assert_approx_eq!(f64, signal.evaluate(*maxp + 0.15), 1.0, epsilon = 1e-12);
+            assert_approx_eq!(
+                f64,
+                signal.integral_between(f64::NEG_INFINITY, f64::INFINITY),
+                *integral,
+                epsilon = 1e-12
+            );
+            assert!(signal.is_valid());
+        });
+    }
+}
@@ -1,12 +1,17 @@
 mod circular_buffer;
-pub(crate) use circular_buffer::CircularBuffer;
+pub use circular_buffer::CircularBuffer;
 
 mod identity;
-pub(crate) use identity::Identity;
+pub use identity::Identity;
 
 mod moving_average;
-pub(crate) use moving_average::MovingAverage;
+pub use moving_average::MovingAverage;
+
+mod moving_sum;
+pub use moving_sum::MovingSum;
 
 mod smoother;
-pub(crate) use smoother::Smoother;
-pub use smoother::SmoothingSettings;
+pub use smoother::{Smoother, SmoothingFloat, SmoothingSettings};
+
+mod whittaker;
+pub use whittaker::Whittaker;
@@ -1,10 +1,33 @@
-use num_traits::Zero;
 use std::collections::VecDeque;
 
 /// FIFO buffer with a fixed capacity that wraps around and overwrites old
 /// elements when full.
-#[derive(Debug)]
-pub(crate) struct CircularBuffer<T> {
+///
+/// `CircularBuffer` is the sliding window of the moving average filter (see
+/// [`SmoothingSettings::MovingAverage`]), but can be used on its own to build
+/// other streaming filters. Elements are pushed to the back and, once the
+/// buffer is full, each push evicts the oldest element from the front, which
+/// is returned to the caller.
+///
+/// [`SmoothingSettings::MovingAverage`]: crate::deconvolution::SmoothingSettings::MovingAverage
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::CircularBuffer;
+///
+/// let mut buffer = CircularBuffer::new(3);
+/// assert_eq!(buffer.push(1), None);
+/// assert_eq!(buffer.push(2), None);
+/// assert_eq!(buffer.push(3), None);
+/// assert!(buffer.is_full());
+///
+/// // The buffer is full, so the oldest element is evicted.
+/// assert_eq!(buffer.push(4), Some(1));
+/// assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CircularBuffer<T> {
     /// The underlying storage for the buffer.
     buffer: VecDeque<T>,
     /// The maximum number of elements the buffer can hold.
@@ -14,13 +37,24 @@ pub(crate) struct CircularBuffer<T> {
     capacity: usize,
 }
 
-impl<T: Copy + Zero> CircularBuffer<T> {
-    /// Creates a new `CircularBuffer` with the given capacity.
+impl<T> CircularBuffer<T> {
+    /// Creates a new, empty `CircularBuffer` with the given capacity.
     ///
     /// # Panics
     ///
     /// Panics if the capacity is zero.
-    pub(crate) fn new(capacity: usize) -> Self {
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::CircularBuffer;
+    ///
+    /// let buffer = CircularBuffer::<f64>::new(5);
+    ///
+    /// assert_eq!(buffer.capacity(), 5);
+    /// assert!(buffer.is_empty());
+    /// ```
+    pub fn new(capacity: usize) -> Self {
         assert!(capacity > 0, "capacity must be greater than zero");
 
         Self {
@@ -31,12 +65,20 @@ impl<T: Copy + Zero> CircularBuffer<T> {
 
     /// Inserts a new element into the buffer and returns the oldest element if
     /// the buffer was already full or `None` otherwise.
-    pub(crate) fn push(&mut self, value: T) -> Option<T> {
-        let popped_value = if self.buffer.len() == self.capacity {
-            self.pop()
-        } else {
-            None
-        };
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::CircularBuffer;
+    ///
+    /// let mut buffer = CircularBuffer::new(2);
+    ///
+    /// assert_eq!(buffer.push(1), None);
+    /// assert_eq!(buffer.push(2), None);
+    /// assert_eq!(buffer.push(3), Some(1));
+    /// ```
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let popped_value = if self.is_full() { self.pop() } else { None };
         self.buffer.push_back(value);
 
         popped_value
@@ -44,29 +86,101 @@ impl<T: Copy + Zero> CircularBuffer<T> {
 
     /// Removes and returns the oldest element from the buffer or `None` if the
     /// buffer was already empty.
-    pub(crate) fn pop(&mut self) -> Option<T> {
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::CircularBuffer;
+    ///
+    /// let mut buffer = CircularBuffer::new(2);
+    /// buffer.push(1);
+    ///
+    /// assert_eq!(buffer.pop(), Some(1));
+    /// assert_eq!(buffer.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
         self.buffer.pop_front()
     }
 
-    /// Resets the buffer to its initial state.
-    pub(crate) fn clear(&mut self) {
+    /// Resets the buffer to its initial state. The capacity is retained.
+    pub fn clear(&mut self) {
         self.buffer.clear();
     }
 
+    /// Returns an iterator over the elements of the buffer, from the oldest to
+    /// the newest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::CircularBuffer;
+    ///
+    /// let mut buffer = CircularBuffer::new(3);
+    /// (1..=5).for_each(|value| {
+    ///     buffer.push(value);
+    /// });
+    ///
+    /// assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+    /// ```
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.buffer.iter()
+    }
+
     /// Returns the number of elements currently in the buffer.
-    pub(crate) fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.buffer.len()
     }
+
+    /// Returns `true` if the buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns `true` if the buffer holds as many elements as its capacity,
+    /// such that the next push evicts the oldest element.
+    pub fn is_full(&self) -> bool {
+        self.buffer.len() == self.capacity
+    }
+
+    /// Returns the maximum number of elements the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(CircularBuffer<f64>);
+        assert_sync!(CircularBuffer<f64>);
+    }
 
     #[test]
     fn new() {
         let buffer: CircularBuffer<i32> = CircularBuffer::new(10);
         assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.capacity(), 10);
+        assert!(buffer.is_empty());
+        assert!(!buffer.is_full());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_capacity() {
+        CircularBuffer::<i32>::new(0);
     }
 
     #[test]
@@ -95,6 +209,28 @@ mod tests {
         assert_eq!(buffer.len(), 3);
     }
 
+    #[test]
+    fn wraparound() {
+        let mut buffer: CircularBuffer<i32> = CircularBuffer::new(3);
+        let evicted = (0..10)
+            .map(|value| buffer.push(value))
+            .collect::<Vec<Option<i32>>>();
+        assert_eq!(evicted[..3], [None, None, None]);
+        assert_eq!(evicted[3..], (0..7).map(Some).collect::<Vec<Option<i32>>>());
+        assert!(buffer.is_full());
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), [7, 8, 9]);
+        assert_eq!(buffer.pop(), Some(7));
+        assert!(!buffer.is_full());
+        assert_eq!(buffer.push(10), None);
+        assert_eq!(
+            (&buffer)
+                .into_iter()
+                .copied()
+                .collect::<Vec<i32>>(),
+            [8, 9, 10]
+        );
+    }
+
     #[test]
     fn clear() {
         let mut buffer: CircularBuffer<i32> = CircularBuffer::new(3);
@@ -103,5 +239,6 @@ mod tests {
         assert_eq!(buffer.push(3), None);
         buffer.clear();
         assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.capacity(), 3);
     }
 }
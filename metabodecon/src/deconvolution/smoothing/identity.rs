@@ -1,8 +1,13 @@
 use crate::deconvolution::smoothing::{Smoother, SmoothingSettings};
 
 /// Identity smoother that does not modify the input values.
-#[derive(Debug)]
-pub(crate) struct Identity;
+///
+/// Equivalent to [`SmoothingSettings::Identity`] for the smoothing step of a
+/// [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Identity;
 
 impl<T> Smoother<T> for Identity {
     fn smooth_values(&self, _values: &mut [T]) {}
@@ -14,7 +19,7 @@ impl<T> Smoother<T> for Identity {
 
 impl Identity {
     /// Creates a new `Identity` smoother.
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self
     }
 }
@@ -1,7 +1,6 @@
-use crate::deconvolution::smoothing::{CircularBuffer, Smoother, SmoothingSettings};
-use num_traits::{FromPrimitive, One, Zero};
+use crate::deconvolution::smoothing::{MovingSum, Smoother, SmoothingFloat, SmoothingSettings};
+use crate::{Result, Settings};
 use std::marker::PhantomData;
-use std::ops::{AddAssign, Div, Mul, SubAssign};
 
 /// Moving average filter that smooths a sequence of values by averaging them
 /// over a sliding window.
@@ -23,8 +22,13 @@ use std::ops::{AddAssign, Div, Mul, SubAssign};
 /// | Step 5 |     |     | -   | -   | x   | -   | -   |
 /// | Step 6 |     |     |     | -   | -   | x   | -   |
 /// | Step 7 |     |     |     |     | -   | -   | x   |
-#[derive(Debug)]
-pub(crate) struct MovingAverage<T> {
+///
+/// Equivalent to [`SmoothingSettings::MovingAverage`] for the smoothing step
+/// of a [`StaticDeconvoluter`], which smooths in `f64`.
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct MovingAverage<T = f64> {
     /// Number of iterations to apply the filter.
     iterations: usize,
     /// Size of the sliding window.
@@ -35,50 +39,27 @@ pub(crate) struct MovingAverage<T> {
     _marker: PhantomData<T>,
 }
 
-impl<T> Smoother<T> for MovingAverage<T>
-where
-    T: Copy
-        + FromPrimitive
-        + One
-        + Zero
-        + AddAssign
-        + SubAssign
-        + Mul<Output = T>
-        + Div<Output = T>
-        + Send
-        + Sync
-        + std::fmt::Debug
-        + 'static,
-{
+impl<T: SmoothingFloat> Smoother<T> for MovingAverage<T> {
     fn smooth_values(&self, values: &mut [T]) {
-        let mut cache = CircularBuffer::<T>::new(self.window_size);
+        let mut window = MovingSum::<T>::new(self.window_size);
         let values_len = values.len();
         for _ in 0..self.iterations {
-            let mut div = T::one();
-            let mut sum = T::zero();
             values.iter().take(self.right).for_each(|value| {
-                cache.push(*value);
-                sum += *value;
+                window.push(*value);
             });
             for i in 0..(values_len - self.right) {
-                sum += values[i + self.right];
-                if let Some(popped) = cache.push(values[i + self.right]) {
-                    sum -= popped;
-                } else {
-                    div = T::one() / T::from_usize(cache.len()).unwrap();
-                };
-                values[i] = sum * div;
+                window.push(values[i + self.right]);
+                values[i] = window.mean().unwrap();
             }
             values[(values_len - self.right)..]
                 .iter_mut()
                 .for_each(|value| {
-                    if let Some(popped) = cache.pop() {
-                        sum -= popped;
-                        div = T::one() / T::from_usize(cache.len()).unwrap();
-                        *value = sum * div;
+                    window.pop();
+                    if let Some(mean) = window.mean() {
+                        *value = mean;
                     }
                 });
-            cache.clear();
+            window.clear();
         }
     }
 
@@ -90,30 +71,27 @@ where
     }
 }
 
-impl<T> MovingAverage<T>
-where
-    T: Copy
-        + FromPrimitive
-        + One
-        + Zero
-        + AddAssign
-        + SubAssign
-        + Mul<Output = T>
-        + Div<Output = T>
-        + Send
-        + Sync
-        + std::fmt::Debug
-        + 'static,
-{
+impl<T: SmoothingFloat> MovingAverage<T> {
     /// Creates a new `MovingAverage` filter with the given number of iterations
     /// and window size.
-    pub(crate) fn new(iterations: usize, window_size: usize) -> Self {
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of iterations or the window size is 0,
+    /// see [`SmoothingSettings::MovingAverage`].
+    pub fn new(iterations: usize, window_size: usize) -> Result<Self> {
+        SmoothingSettings::MovingAverage {
+            iterations,
+            window_size,
+        }
+        .validate()?;
+
+        Ok(Self {
             iterations,
             window_size,
             right: window_size / 2,
             _marker: PhantomData,
-        }
+        })
     }
 }
 
@@ -0,0 +1,187 @@
+use crate::deconvolution::smoothing::{CircularBuffer, SmoothingFloat};
+
+/// Sum over a sliding window of values that is updated incrementally.
+///
+/// `MovingSum` caches the sum of the values in a [`CircularBuffer`], such
+/// that pushing or popping a value updates the sum in constant time instead of
+/// recomputing it over the whole window. This is the core of the moving
+/// average filter (see [`SmoothingSettings::MovingAverage`]).
+///
+/// # Precision
+///
+/// As values are added to and subtracted from the cached sum, rounding errors
+/// accumulate over the number of updates. For `f64`, the deviation from a
+/// recomputed sum is negligible for the length of typical spectra. For `f32`,
+/// see [`SmoothingFloat`]. The sum is reset exactly by [`MovingSum::clear`].
+///
+/// [`SmoothingSettings::MovingAverage`]: crate::deconvolution::SmoothingSettings::MovingAverage
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::MovingSum;
+///
+/// let mut window = MovingSum::<f64>::new(3);
+/// assert_eq!(window.push(1.0), 1.0);
+/// assert_eq!(window.push(2.0), 3.0);
+/// assert_eq!(window.push(3.0), 6.0);
+///
+/// // The window is full, so the oldest value (1.0) is removed from the sum.
+/// assert_eq!(window.push(4.0), 9.0);
+/// assert_eq!(window.mean(), Some(3.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MovingSum<T> {
+    /// Values within the sliding window.
+    window: CircularBuffer<T>,
+    /// Cached sum of the values within the sliding window.
+    sum: T,
+}
+
+impl<T: SmoothingFloat> MovingSum<T> {
+    /// Creates a new, empty `MovingSum` with the given window size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window size is zero.
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            window: CircularBuffer::new(window_size),
+            sum: T::zero(),
+        }
+    }
+
+    /// Adds a value to the window and returns the updated sum. If the window
+    /// was already full, the oldest value is removed from it first.
+    pub fn push(&mut self, value: T) -> T {
+        if let Some(popped) = self.window.push(value) {
+            self.sum -= popped;
+        }
+        self.sum += value;
+
+        self.sum
+    }
+
+    /// Removes the oldest value from the window and returns it, or `None` if
+    /// the window was already empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::MovingSum;
+    ///
+    /// let mut window = MovingSum::<f64>::new(3);
+    /// window.push(1.0);
+    /// window.push(2.0);
+    ///
+    /// assert_eq!(window.pop(), Some(1.0));
+    /// assert_eq!(window.sum(), 2.0);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        let popped = self.window.pop()?;
+        self.sum -= popped;
+
+        Some(popped)
+    }
+
+    /// Removes all values from the window and resets the sum to zero.
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.sum = T::zero();
+    }
+
+    /// Returns the cached sum of the values within the window.
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Returns the mean of the values within the window, or `None` if the
+    /// window is empty.
+    pub fn mean(&self) -> Option<T> {
+        match self.window.len() {
+            0 => None,
+            len => Some(self.sum / T::from_usize(len).unwrap()),
+        }
+    }
+
+    /// Returns the values within the window, from the oldest to the newest.
+    pub fn window(&self) -> &CircularBuffer<T> {
+        &self.window
+    }
+
+    /// Returns the number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Returns `true` if the window contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Returns `true` if the window is full, such that the next push removes
+    /// the oldest value.
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    /// Returns the maximum number of values in the window.
+    pub fn window_size(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macros::uniform_noise;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(MovingSum<f64>);
+        assert_sync!(MovingSum<f64>);
+    }
+
+    #[test]
+    fn running_sum() {
+        let values = uniform_noise(10_000, 42)
+            .into_iter()
+            .map(|noise| 1e3 * noise)
+            .collect::<Vec<f64>>();
+        let mut window = MovingSum::<f64>::new(7);
+        values.iter().enumerate().for_each(|(i, value)| {
+            let sum = window.push(*value);
+            let recomputed = values[i.saturating_sub(6)..=i]
+                .iter()
+                .sum::<f64>();
+            assert_approx_eq!(f64, sum, recomputed, epsilon = 1e-9);
+            assert_approx_eq!(
+                f64,
+                window.sum(),
+                window.window().iter().sum::<f64>(),
+                epsilon = 1e-9
+            );
+        });
+        assert!(window.is_full());
+        while window.pop().is_some() {
+            let recomputed = window.window().iter().sum::<f64>();
+            assert_approx_eq!(f64, window.sum(), recomputed, epsilon = 1e-9);
+        }
+        assert!(window.is_empty());
+        assert_eq!(window.mean(), None);
+    }
+
+    #[test]
+    fn clear() {
+        let mut window = MovingSum::<f32>::new(2);
+        window.push(0.1);
+        window.push(0.2);
+        window.push(0.3);
+        window.clear();
+        assert_eq!(window.sum(), 0.0);
+        assert_eq!(window.len(), 0);
+        assert_eq!(window.window_size(), 2);
+    }
+}
@@ -1,19 +1,108 @@
 use crate::Settings;
 use crate::deconvolution::error::{Error, Kind};
+use crate::deconvolution::smoothing::{Identity, MovingAverage, Whittaker};
 use crate::error::Result;
+use num_traits::{Float, FromPrimitive};
+use std::ops::{AddAssign, SubAssign};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Trait interface for smoothing algorithms.
-pub(crate) trait Smoother<T>: Send + Sync + std::fmt::Debug {
+///
+/// Implemented by the built-in smoothers [`Identity`], [`MovingAverage`] and
+/// [`Whittaker`]. Implement `Smoother<f64>` for a custom type to plug a
+/// different smoothing algorithm into the [`Deconvoluter`] with
+/// [`Deconvoluter::with_smoother`].
+///
+/// The smoothed intensities are only used to select the peaks and to compute
+/// their initial parameters. The fitting is performed on the unsmoothed
+/// intensities, so the smoother should preserve the positions of the peaks.
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+/// [`Deconvoluter::with_smoother`]: crate::deconvolution::Deconvoluter::with_smoother
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::{Deconvoluter, Smoother, SmoothingSettings};
+/// use metabodecon::spectrum::Bruker;
+/// use std::sync::Arc;
+///
+/// /// Replaces each value with the mean of itself and its direct neighbors.
+/// #[derive(Debug)]
+/// struct ThreePointMean;
+///
+/// impl Smoother<f64> for ThreePointMean {
+///     fn smooth_values(&self, values: &mut [f64]) {
+///         let original = values.to_vec();
+///         for i in 1..values.len().saturating_sub(1) {
+///             values[i] = (original[i - 1] + original[i] + original[i + 1]) / 3.0;
+///         }
+///     }
+/// }
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/spectrum";
+/// # let path = "../data/bruker/sim/sim_01";
+/// let spectrum = Bruker::read_spectrum(path, 10, 10, (3.339, 3.553))?;
+///
+/// let deconvoluter = Deconvoluter::with_smoother(Arc::new(ThreePointMean));
+/// let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+///
+/// assert!(matches!(
+///     deconvolution.smoothing_settings(),
+///     SmoothingSettings::Custom
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+pub trait Smoother<T>: Send + Sync + std::fmt::Debug {
     /// Smooths the given sequence of values in place.
     fn smooth_values(&self, values: &mut [T]);
 
-    /// Returns the settings of the trait object.
-    fn settings(&self) -> SmoothingSettings;
+    /// Returns the settings of the smoother.
+    ///
+    /// The settings are reported by the [`Deconvoluter`] and stored in the
+    /// resulting [`Deconvolution`]s. Custom smoothers can't be described by
+    /// the [`SmoothingSettings`], so the default implementation returns
+    /// [`SmoothingSettings::Custom`].
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`Deconvolution`]: crate::deconvolution::Deconvolution
+    fn settings(&self) -> SmoothingSettings {
+        SmoothingSettings::Custom
+    }
 }
 
+/// Floating point types that the smoothing algorithms operate on.
+///
+/// Implemented for `f32` and `f64`. The [`Deconvoluter`] always smooths in
+/// `f64`, but [`SmoothingSettings::smooth_values`] accepts either type, such
+/// that large batches of spectra can be preprocessed in `f32` to halve their
+/// memory footprint.
+///
+/// # Precision
+///
+/// `f32` has a machine epsilon of about `1.2e-7`, compared to `2.2e-16` for
+/// `f64`. For the moving average filter, the running sum accumulates rounding
+/// errors over the length of the sequence, so the results typically deviate
+/// from the `f64` results by a relative error of `1e-6` to `1e-5`. The
+/// Whittaker smoother solves a linear system whose condition number grows
+/// with `lambda` and the order of the differences, so large penalties amplify
+/// the rounding errors. Use `f64` if the smoothed values are fitted afterwards
+/// or if `lambda` is larger than about `1e4`.
+///
+/// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+pub trait SmoothingFloat:
+    Float + FromPrimitive + AddAssign + SubAssign + Send + Sync + std::fmt::Debug + 'static
+{
+}
+
+impl SmoothingFloat for f32 {}
+
+impl SmoothingFloat for f64 {}
+
 /// Signal intensity smoothing settings for configuring the [`Deconvoluter`].
 ///
 /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
@@ -53,6 +142,36 @@ pub enum SmoothingSettings {
         /// The number of values in the sliding window.
         window_size: usize,
     },
+    /// Whittaker smoother.
+    ///
+    /// The Whittaker smoother fits a smooth sequence `z` to the values `y` by
+    /// penalized least squares, minimizing
+    ///
+    /// ```text
+    /// |y - z|^2 + lambda * |D z|^2
+    /// ```
+    ///
+    /// where `D` is the difference matrix of the given order. Larger values of
+    /// `lambda` produce smoother results. Polynomials of a degree lower than the
+    /// order are preserved. Unlike the moving average filter, there is no
+    /// window that shrinks at the edges, so the ends of the sequence are
+    /// treated consistently.
+    Whittaker {
+        /// The smoothness penalty. Must be finite and positive.
+        lambda: f64,
+        /// The order of the differences. Must be within `1..=3`.
+        order: usize,
+    },
+    /// User-provided [`Smoother`].
+    ///
+    /// Reported by custom smoothers that were passed to
+    /// [`Deconvoluter::with_smoother`]. The smoother itself isn't part of the
+    /// settings, so a [`Deconvoluter`] can't be constructed from them and
+    /// they are rejected by the validation.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    /// [`Deconvoluter::with_smoother`]: crate::deconvolution::Deconvoluter::with_smoother
+    Custom,
 }
 
 impl Default for SmoothingSettings {
@@ -64,6 +183,76 @@ impl Default for SmoothingSettings {
     }
 }
 
+impl SmoothingSettings {
+    /// Smooths the given sequence of values in place with the configured
+    /// method.
+    ///
+    /// This applies the same smoothing as the [`Deconvoluter`], but can be
+    /// used independently and with `f32` as well as `f64` values. See
+    /// [`SmoothingFloat`] for the precision trade-off.
+    ///
+    /// [`Deconvoluter`]: crate::deconvolution::Deconvoluter
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the settings are invalid or describe a
+    /// [`Custom`] smoother, which can't be constructed from the settings.
+    ///
+    /// [`Custom`]: SmoothingSettings::Custom
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::SmoothingSettings;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let settings = SmoothingSettings::MovingAverage {
+    ///     iterations: 1,
+    ///     window_size: 3,
+    /// };
+    /// let mut values_f32 = vec![0.0_f32, 3.0, 0.0, 3.0, 0.0];
+    /// let mut values_f64 = vec![0.0_f64, 3.0, 0.0, 3.0, 0.0];
+    /// settings.smooth_values(&mut values_f32)?;
+    /// settings.smooth_values(&mut values_f64)?;
+    ///
+    /// assert_eq!(values_f32, [1.5, 1.0, 2.0, 1.0, 1.5]);
+    /// assert_eq!(values_f64, [1.5, 1.0, 2.0, 1.0, 1.5]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn smooth_values<T: SmoothingFloat>(&self, values: &mut [T]) -> Result<()> {
+        match *self {
+            SmoothingSettings::Identity => Identity::new().smooth_values(values),
+            SmoothingSettings::MovingAverage {
+                iterations,
+                window_size,
+            } => MovingAverage::<T>::new(iterations, window_size)?.smooth_values(values),
+            SmoothingSettings::Whittaker { lambda, order } => {
+                Whittaker::new(lambda, order)?.smooth_values(values)
+            }
+            SmoothingSettings::Custom => {
+                return Err(Error::new(Kind::InvalidSmoothingSettings { settings: *self }).into());
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Returns the number of neighboring values on each side that contribute
+    /// to a smoothed value, or `None` if every value may contribute, as for
+    /// the [`Whittaker`] smoother and custom smoothers.
+    pub(crate) fn reach(&self) -> Option<usize> {
+        match *self {
+            SmoothingSettings::Identity => Some(0),
+            SmoothingSettings::MovingAverage {
+                iterations,
+                window_size,
+            } => Some(iterations * window_size.div_ceil(2)),
+            SmoothingSettings::Whittaker { .. } | SmoothingSettings::Custom => None,
+        }
+    }
+}
+
 impl std::fmt::Display for SmoothingSettings {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -76,6 +265,12 @@ impl std::fmt::Display for SmoothingSettings {
                 "Moving Average Filter [number of iterations: {}, window size: {}]",
                 iterations, window_size
             ),
+            SmoothingSettings::Whittaker { lambda, order } => write!(
+                f,
+                "Whittaker Smoother [smoothness penalty: {}, difference order: {}]",
+                lambda, order
+            ),
+            SmoothingSettings::Custom => write!(f, "Custom Smoother"),
         }
     }
 }
@@ -84,6 +279,9 @@ impl Settings for SmoothingSettings {
     fn validate(&self) -> Result<()> {
         match self {
             SmoothingSettings::Identity => {}
+            SmoothingSettings::Custom => {
+                return Err(Error::new(Kind::InvalidSmoothingSettings { settings: *self }).into());
+            }
             SmoothingSettings::MovingAverage {
                 iterations,
                 window_size,
@@ -94,6 +292,13 @@ impl Settings for SmoothingSettings {
                     );
                 }
             }
+            SmoothingSettings::Whittaker { lambda, order } => {
+                if !(lambda.is_finite() && *lambda > 0.0 && (1..=3).contains(order)) {
+                    return Err(
+                        Error::new(Kind::InvalidSmoothingSettings { settings: *self }).into(),
+                    );
+                }
+            }
         }
 
         Ok(())
@@ -103,6 +308,7 @@ impl Settings for SmoothingSettings {
     fn compare(&self, other: &Self) -> bool {
         match (self, other) {
             (SmoothingSettings::Identity, SmoothingSettings::Identity) => true,
+            (SmoothingSettings::Custom, SmoothingSettings::Custom) => true,
             (
                 SmoothingSettings::MovingAverage {
                     iterations: iterations1,
@@ -113,7 +319,147 @@ impl Settings for SmoothingSettings {
                     window_size: window_size2,
                 },
             ) => *iterations1 == *iterations2 && *window_size1 == *window_size2,
+            (
+                SmoothingSettings::Whittaker {
+                    lambda: lambda1,
+                    order: order1,
+                },
+                SmoothingSettings::Whittaker {
+                    lambda: lambda2,
+                    order: order2,
+                },
+            ) => float_cmp::approx_eq!(f64, *lambda1, *lambda2) && *order1 == *order2,
             _ => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macros::uniform_noise;
+    use float_cmp::assert_approx_eq;
+
+    fn noisy_signal() -> Vec<f64> {
+        uniform_noise(2_usize.pow(14), 42)
+            .into_iter()
+            .enumerate()
+            .map(|(i, noise)| {
+                let x = i as f64 / 16383.0;
+                let peaks = [(0.3, 5e-3, 1e3), (0.5, 2e-3, 4e3), (0.52, 3e-3, 2e3)]
+                    .into_iter()
+                    .map(|(x0, hw, height)| height * hw.powi(2) / (hw.powi(2) + (x - x0).powi(2)))
+                    .sum::<f64>();
+                peaks + 10.0 * noise
+            })
+            .collect()
+    }
+
+    #[test]
+    fn f32_matches_f64() {
+        let settings = [
+            (SmoothingSettings::Identity, 1e-7),
+            (SmoothingSettings::default(), 1e-5),
+            (
+                SmoothingSettings::MovingAverage {
+                    iterations: 10,
+                    window_size: 7,
+                },
+                1e-5,
+            ),
+            (
+                SmoothingSettings::Whittaker {
+                    lambda: 1e2,
+                    order: 2,
+                },
+                1e-4,
+            ),
+        ];
+        let values = noisy_signal();
+        settings
+            .into_iter()
+            .for_each(|(settings, tolerance)| {
+                let mut values_f64 = values.clone();
+                let mut values_f32 = values
+                    .iter()
+                    .map(|&v| v as f32)
+                    .collect::<Vec<f32>>();
+                settings.smooth_values(&mut values_f64).unwrap();
+                settings.smooth_values(&mut values_f32).unwrap();
+                let max = values_f64
+                    .iter()
+                    .fold(0.0_f64, |acc, v| acc.max(v.abs()));
+                values_f32
+                    .iter()
+                    .zip(values_f64.iter())
+                    .for_each(|(&single, &double)| {
+                        assert_approx_eq!(f64, single as f64, double, epsilon = tolerance * max);
+                    });
+            });
+    }
+
+    #[test]
+    fn reach() {
+        let values = noisy_signal();
+        let mut smoothed = values.clone();
+        let settings = [
+            SmoothingSettings::Identity,
+            SmoothingSettings::default(),
+            SmoothingSettings::MovingAverage {
+                iterations: 2,
+                window_size: 6,
+            },
+        ];
+        settings.into_iter().for_each(|settings| {
+            let reach = settings.reach().unwrap();
+            settings.smooth_values(&mut smoothed).unwrap();
+            let mut window = values[8000 - reach..8200 + reach].to_vec();
+            settings.smooth_values(&mut window).unwrap();
+            window[reach..window.len() - reach]
+                .iter()
+                .zip(smoothed[8000..8200].iter())
+                .for_each(|(&window, &smoothed)| {
+                    assert_approx_eq!(f64, window, smoothed, epsilon = 1e-9);
+                });
+            smoothed.copy_from_slice(&values);
+        });
+        let whittaker = SmoothingSettings::Whittaker {
+            lambda: 1e2,
+            order: 2,
+        };
+        assert!(whittaker.reach().is_none());
+        assert!(SmoothingSettings::Custom.reach().is_none());
+    }
+
+    #[test]
+    fn smooth_values_invalid() {
+        let settings = SmoothingSettings::Whittaker {
+            lambda: -1.0,
+            order: 2,
+        };
+        let mut values = vec![1.0_f32; 16];
+        match settings.smooth_values(&mut values).unwrap_err() {
+            crate::Error::Deconvolution(inner) => match inner.kind() {
+                Kind::InvalidSmoothingSettings { settings: context } => {
+                    assert!(context.compare(&settings));
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            error => panic!("unexpected error: {:?}", error),
+        };
+        assert_eq!(values, vec![1.0_f32; 16]);
+    }
+
+    #[test]
+    fn validate_custom() {
+        match SmoothingSettings::Custom.validate().unwrap_err() {
+            crate::Error::Deconvolution(inner) => match inner.kind() {
+                Kind::InvalidSmoothingSettings { settings } => {
+                    assert!(settings.compare(&SmoothingSettings::Custom));
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            error => panic!("unexpected error: {:?}", error),
+        };
+    }
+}
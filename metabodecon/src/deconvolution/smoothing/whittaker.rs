@@ -0,0 +1,137 @@
+use crate::deconvolution::banded::{penalty_bands, solve_banded};
+use crate::deconvolution::smoothing::{Smoother, SmoothingFloat, SmoothingSettings};
+use crate::{Result, Settings};
+
+/// Whittaker smoother that smooths a sequence of values by penalized least
+/// squares.
+///
+/// # Algorithm
+///
+/// The smoothed values `z` are the solution of
+///
+/// ```text
+/// (I + lambda * D^T D) z = y
+/// ```
+///
+/// where `y` are the values and `D` is the difference matrix of the given
+/// order. The system matrix is symmetric positive definite and banded, with
+/// `order` bands on either side of the main diagonal, so it is solved in linear
+/// time with a banded LDL^T decomposition.
+///
+/// Equivalent to [`SmoothingSettings::Whittaker`] for the smoothing step of a
+/// [`StaticDeconvoluter`].
+///
+/// [`StaticDeconvoluter`]: crate::deconvolution::StaticDeconvoluter
+#[derive(Clone, Debug)]
+pub struct Whittaker {
+    /// Smoothness penalty.
+    lambda: f64,
+    /// Order of the differences.
+    order: usize,
+}
+
+impl<T: SmoothingFloat> Smoother<T> for Whittaker {
+    fn smooth_values(&self, values: &mut [T]) {
+        if values.len() <= self.order {
+            return;
+        }
+        let width = self.order + 1;
+        let mut bands = penalty_bands::<T>(values.len(), self.order, self.lambda);
+        (0..values.len()).for_each(|i| bands[i * width] += T::one());
+        let smoothed = solve_banded(bands, self.order, values.to_vec());
+        values.copy_from_slice(&smoothed);
+    }
+
+    fn settings(&self) -> SmoothingSettings {
+        SmoothingSettings::Whittaker {
+            lambda: self.lambda,
+            order: self.order,
+        }
+    }
+}
+
+impl Whittaker {
+    /// Creates a new `Whittaker` smoother with the given smoothness penalty and
+    /// order of the differences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lambda` is not positive and finite or the order is
+    /// not within `1..=3`, see [`SmoothingSettings::Whittaker`].
+    pub fn new(lambda: f64, order: usize) -> Result<Self> {
+        SmoothingSettings::Whittaker { lambda, order }.validate()?;
+
+        Ok(Self { lambda, order })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macros::uniform_noise;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(Whittaker);
+        assert_sync!(Whittaker);
+    }
+
+    #[test]
+    fn preserves_polynomials() {
+        (1..=3).for_each(|order| {
+            let smoother = Whittaker::new(1e4, order).unwrap();
+            let polynomial = (0..500)
+                .map(|i| {
+                    let x = i as f64 / 499.0;
+                    (0..order)
+                        .map(|p| (p + 1) as f64 * x.powi(p as i32))
+                        .sum::<f64>()
+                })
+                .collect::<Vec<f64>>();
+            let mut values = polynomial.clone();
+            smoother.smooth_values(&mut values);
+            values
+                .iter()
+                .zip(polynomial.iter())
+                .for_each(|(smoothed, expected)| {
+                    assert_approx_eq!(f64, *smoothed, *expected, epsilon = 1e-6);
+                });
+        });
+    }
+
+    #[test]
+    fn higher_lambda_is_smoother() {
+        let values = uniform_noise(2_usize.pow(12), 42)
+            .into_iter()
+            .enumerate()
+            .map(|(i, noise)| {
+                let x = i as f64 / 4095.0;
+                1.0 / (1e-4 + (x - 0.5).powi(2)) * 1e-3 + noise
+            })
+            .collect::<Vec<f64>>();
+        let total_variation = |values: &[f64]| {
+            values
+                .windows(2)
+                .map(|w| (w[1] - w[0]).abs())
+                .sum::<f64>()
+        };
+        (1..=3).for_each(|order| {
+            let variations = [1e-1, 1e1, 1e3, 1e5]
+                .into_iter()
+                .map(|lambda| {
+                    let mut smoothed = values.clone();
+                    Whittaker::new(lambda, order)
+                        .unwrap()
+                        .smooth_values(&mut smoothed);
+                    total_variation(&smoothed)
+                })
+                .collect::<Vec<f64>>();
+            assert!(variations[0] < total_variation(&values));
+            variations
+                .windows(2)
+                .for_each(|w| assert!(w[1] < w[0]));
+        });
+    }
+}
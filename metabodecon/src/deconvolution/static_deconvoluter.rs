@@ -0,0 +1,365 @@
+use crate::Result;
+use crate::deconvolution::fitting::{Fitter, FitterAnalytical};
+use crate::deconvolution::peak_selection::{
+    NoiseEstimator, NoiseScoreFilter, ScoringMethod, Selector,
+};
+use crate::deconvolution::smoothing::{MovingAverage, Smoother};
+use crate::deconvolution::{
+    Deconvoluter, Deconvolution, FittingSettings, SelectionSettings, SmoothingSettings,
+};
+use crate::spectrum::Spectrum;
+use std::sync::Arc;
+
+/// Deconvolution pipeline with the smoothing, peak selection and fitting
+/// methods fixed at compile time.
+///
+/// The [`Deconvoluter`] stores its methods as trait objects, such that they can
+/// be configured at runtime through the settings enums. `StaticDeconvoluter`
+/// instead stores the concrete types of the methods as type parameters, which
+/// allows the compiler to monomorphize and inline the entire pipeline. Both
+/// run the same algorithm, so the resulting [`Deconvolution`]s are identical.
+///
+/// The type parameters are one of the following types each:
+///
+/// - Smoothing: [`Identity`], [`MovingAverage`], [`Whittaker`] or a custom
+///   [`Smoother`]
+/// - Peak selection: [`DetectorOnly`] or [`NoiseScoreFilter`]
+/// - Fitting: [`FitterAnalytical`], [`FitterGaussian`],
+///   [`FitterLevenbergMarquardt`] or [`FitterPseudoVoigt`]
+///
+/// The methods are called once per step for each deconvoluted [`Spectrum`],
+/// while the computationally expensive loops within the methods don't depend
+/// on the dispatch. The difference is therefore only noticeable for small
+/// spectra that are deconvoluted in a tight loop. Apart from the ignore
+/// regions, the remaining options of the [`Deconvoluter`], such as the
+/// baseline correction, are left at their defaults. Convert the
+/// `StaticDeconvoluter` into a [`Deconvoluter`] to configure them.
+///
+/// [`Identity`]: crate::deconvolution::Identity
+/// [`Whittaker`]: crate::deconvolution::Whittaker
+/// [`DetectorOnly`]: crate::deconvolution::DetectorOnly
+/// [`FitterGaussian`]: crate::deconvolution::FitterGaussian
+/// [`FitterLevenbergMarquardt`]: crate::deconvolution::FitterLevenbergMarquardt
+/// [`FitterPseudoVoigt`]: crate::deconvolution::FitterPseudoVoigt
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::{
+///     FitterAnalytical, MovingAverage, NoiseEstimator, NoiseScoreFilter, ScoringMethod,
+///     StaticDeconvoluter,
+/// };
+/// use metabodecon::spectrum::Bruker;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/spectrum";
+/// # let path = "../data/bruker/sim";
+/// let spectra = Bruker::read_spectra(path, 10, 10, (3.339, 3.553))?;
+///
+/// let mut deconvoluter = StaticDeconvoluter::new(
+///     MovingAverage::new(3, 3)?,
+///     NoiseScoreFilter::new(ScoringMethod::MinimumSum, 5.0, None, NoiseEstimator::StdDev)?,
+///     FitterAnalytical::new(10)?,
+/// );
+/// deconvoluter.add_ignore_region((3.4, 3.41))?;
+///
+/// for spectrum in &spectra {
+///     let deconvolution = deconvoluter.deconvolute_spectrum(spectrum)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StaticDeconvoluter<S, Sel, F> {
+    /// Smoothing method.
+    smoother: S,
+    /// Peak selection method.
+    selector: Sel,
+    /// Fitting method.
+    fitter: F,
+    /// Deconvoluter with the same methods, holding the remaining options.
+    deconvoluter: Deconvoluter,
+}
+
+impl Default for StaticDeconvoluter<MovingAverage, NoiseScoreFilter, FitterAnalytical> {
+    fn default() -> Self {
+        Self::new(
+            MovingAverage::new(3, 3).unwrap(),
+            NoiseScoreFilter::new(
+                ScoringMethod::default(),
+                5.0,
+                None,
+                NoiseEstimator::default(),
+            )
+            .unwrap(),
+            FitterAnalytical::new(10).unwrap(),
+        )
+    }
+}
+
+impl<S, Sel, F> From<StaticDeconvoluter<S, Sel, F>> for Deconvoluter {
+    fn from(value: StaticDeconvoluter<S, Sel, F>) -> Self {
+        value.deconvoluter
+    }
+}
+
+// The traits of the peak selection and fitting methods are internal, only the
+// implementing types are public.
+#[allow(private_bounds)]
+impl<S, Sel, F> StaticDeconvoluter<S, Sel, F>
+where
+    S: Smoother<f64> + Clone + 'static,
+    Sel: Selector,
+    F: Fitter,
+{
+    /// Constructs a new `StaticDeconvoluter` with the provided methods.
+    ///
+    /// The methods are validated on construction, so this can't fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::deconvolution::{
+    ///     DetectorOnly, FitterLevenbergMarquardt, FittingSettings, StaticDeconvoluter, Whittaker,
+    /// };
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let deconvoluter = StaticDeconvoluter::new(
+    ///     Whittaker::new(100.0, 2)?,
+    ///     DetectorOnly::new(),
+    ///     FitterLevenbergMarquardt::new(20, 1e-8, 1e-3)?,
+    /// );
+    ///
+    /// match deconvoluter.fitting_settings() {
+    ///     FittingSettings::LevenbergMarquardt { max_iterations, .. } => {
+    ///         assert_eq!(max_iterations, 20);
+    ///     }
+    ///     _ => panic!("Unexpected fitting settings"),
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(smoother: S, selector: Sel, fitter: F) -> Self {
+        let mut deconvoluter = Deconvoluter::with_smoother(Arc::new(smoother.clone()));
+        deconvoluter
+            .set_selection_settings(selector.settings())
+            .unwrap();
+        deconvoluter
+            .set_fitting_settings(fitter.settings())
+            .unwrap();
+
+        Self {
+            smoother,
+            selector,
+            fitter,
+            deconvoluter,
+        }
+    }
+
+    /// Returns the smoothing settings.
+    pub fn smoothing_settings(&self) -> SmoothingSettings {
+        self.smoother.settings()
+    }
+
+    /// Returns the peak selection settings.
+    pub fn selection_settings(&self) -> SelectionSettings {
+        self.selector.settings()
+    }
+
+    /// Returns the fitting settings.
+    pub fn fitting_settings(&self) -> FittingSettings {
+        self.fitter.settings()
+    }
+
+    /// Returns the regions to ignore during deconvolution.
+    pub fn ignore_regions(&self) -> Option<&[(f64, f64)]> {
+        self.deconvoluter.ignore_regions()
+    }
+
+    /// Adds a region to ignore during deconvolution. Overlapping regions are
+    /// combined. See [`Deconvoluter::add_ignore_region`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the start or end value is not finite or if they
+    /// are (nearly) equal.
+    pub fn add_ignore_region(&mut self, new: (f64, f64)) -> Result<()> {
+        self.deconvoluter.add_ignore_region(new)
+    }
+
+    /// Clears the regions to ignore during deconvolution.
+    pub fn clear_ignore_regions(&mut self) {
+        self.deconvoluter.clear_ignore_regions();
+    }
+
+    /// Deconvolutes the provided spectrum into individual signals.
+    ///
+    /// The result is identical to [`Deconvoluter::deconvolute_spectrum`] with
+    /// the same methods and ignore regions.
+    ///
+    /// # Errors
+    ///
+    /// During the deconvolution process, the algorithm relies on finding peaks
+    /// in the `Spectrum`. If no peaks are found, an error is returned. The
+    /// peaks outside the signal boundaries of the `Spectrum` are used to filter
+    /// out noise within the signal region. If no peaks are found outside or
+    /// within the signal region, an error is returned.
+    pub fn deconvolute_spectrum(&self, spectrum: &Spectrum) -> Result<Deconvolution> {
+        self.deconvoluter.deconvolute_spectrum_using(
+            spectrum,
+            &self.smoother,
+            &self.selector,
+            &self.fitter,
+            None,
+        )
+    }
+
+    /// Deconvolutes the provided spectrum into individual signals in parallel.
+    ///
+    /// The result is identical to [`Deconvoluter::par_deconvolute_spectrum`]
+    /// with the same methods and ignore regions.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [`deconvolute_spectrum`] are possible.
+    ///
+    /// [`deconvolute_spectrum`]: StaticDeconvoluter::deconvolute_spectrum
+    #[cfg(feature = "parallel")]
+    pub fn par_deconvolute_spectrum(&self, spectrum: &Spectrum) -> Result<Deconvolution> {
+        self.deconvoluter.par_deconvolute_spectrum_using(
+            spectrum,
+            &self.smoother,
+            &self.selector,
+            &self.fitter,
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::{
+        DetectorOnly, FitterGaussian, FitterLevenbergMarquardt, FitterPseudoVoigt, Identity,
+        Lorentzian, Whittaker,
+    };
+    use crate::macros::noisy_lorentzian_spectrum;
+    use crate::{assert_send, assert_sync};
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(StaticDeconvoluter<MovingAverage, NoiseScoreFilter, FitterAnalytical>);
+        assert_sync!(StaticDeconvoluter<MovingAverage, NoiseScoreFilter, FitterAnalytical>);
+    }
+
+    #[test]
+    fn identical_to_dynamic() {
+        let signals = (0..3)
+            .map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 4.98 + 0.02 * i as f64))
+            .chain((0..3).map(|i| Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0 + i as f64)))
+            .collect::<Vec<Lorentzian>>();
+        let spectrum = noisy_lorentzian_spectrum(&signals, 4000, 0.1, 42, (1.0, 9.0));
+
+        fn assert_identical<S, Sel, F>(
+            mut deconvoluter: StaticDeconvoluter<S, Sel, F>,
+            spectrum: &Spectrum,
+        ) where
+            S: Smoother<f64> + Clone + 'static,
+            Sel: Selector + Clone,
+            F: Fitter + Clone,
+        {
+            deconvoluter
+                .add_ignore_region((3.9, 4.1))
+                .unwrap();
+            let dynamic = Deconvoluter::from(deconvoluter.clone());
+            assert_eq!(
+                format!("{:?}", dynamic.smoothing_settings()),
+                format!("{:?}", deconvoluter.smoothing_settings())
+            );
+            assert_eq!(
+                format!("{:?}", dynamic.selection_settings()),
+                format!("{:?}", deconvoluter.selection_settings())
+            );
+            assert_eq!(
+                format!("{:?}", dynamic.fitting_settings()),
+                format!("{:?}", deconvoluter.fitting_settings())
+            );
+            assert_eq!(dynamic.ignore_regions(), deconvoluter.ignore_regions());
+            let expected = dynamic.deconvolute_spectrum(spectrum).unwrap();
+            let deconvolution = deconvoluter
+                .deconvolute_spectrum(spectrum)
+                .unwrap();
+            let signals = deconvolution.len();
+            assert!(signals > 0);
+            assert_eq!(format!("{:?}", deconvolution), format!("{:?}", expected));
+            #[cfg(feature = "parallel")]
+            assert_eq!(
+                format!(
+                    "{:?}",
+                    deconvoluter
+                        .par_deconvolute_spectrum(spectrum)
+                        .unwrap()
+                ),
+                format!(
+                    "{:?}",
+                    dynamic
+                        .par_deconvolute_spectrum(spectrum)
+                        .unwrap()
+                )
+            );
+        }
+
+        assert_identical(StaticDeconvoluter::default(), &spectrum);
+        assert_identical(
+            StaticDeconvoluter::new(
+                Identity::new(),
+                DetectorOnly::new(),
+                FitterGaussian::new(10).unwrap(),
+            ),
+            &spectrum,
+        );
+        assert_identical(
+            StaticDeconvoluter::new(
+                Whittaker::new(10.0, 2).unwrap(),
+                NoiseScoreFilter::new(
+                    ScoringMethod::MinimumSum,
+                    5.0,
+                    Some(4),
+                    NoiseEstimator::StdDev,
+                )
+                .unwrap(),
+                FitterLevenbergMarquardt::new(20, 1e-8, 1e-3).unwrap(),
+            ),
+            &spectrum,
+        );
+        assert_identical(
+            StaticDeconvoluter::new(
+                MovingAverage::new(2, 5).unwrap(),
+                NoiseScoreFilter::new(ScoringMethod::MinimumSum, 5.0, None, NoiseEstimator::StdDev)
+                    .unwrap(),
+                FitterPseudoVoigt::new(10, 0.5).unwrap(),
+            ),
+            &spectrum,
+        );
+    }
+
+    #[test]
+    fn clear_ignore_regions() {
+        let mut deconvoluter = StaticDeconvoluter::default();
+        assert!(deconvoluter.ignore_regions().is_none());
+        deconvoluter
+            .add_ignore_region((4.7, 4.9))
+            .unwrap();
+        deconvoluter
+            .add_ignore_region((4.8, 5.0))
+            .unwrap();
+        assert_eq!(deconvoluter.ignore_regions().unwrap(), &[(4.7, 5.0)]);
+        deconvoluter.clear_ignore_regions();
+        assert!(deconvoluter.ignore_regions().is_none());
+        assert!(
+            deconvoluter
+                .add_ignore_region((f64::NAN, 5.0))
+                .is_err()
+        );
+    }
+}
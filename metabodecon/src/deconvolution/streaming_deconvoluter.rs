@@ -0,0 +1,389 @@
+use crate::Result;
+use crate::deconvolution::error::{Error, Kind};
+use crate::deconvolution::{Deconvoluter, Deconvolution};
+use crate::spectrum::Spectrum;
+
+/// Incremental deconvolution of a [`Spectrum`] that changes over time, such as
+/// during live acquisition.
+///
+/// `StreamingDeconvoluter` wraps a [`Deconvoluter`] and holds the current
+/// state of the [`Spectrum`] together with its latest [`Deconvolution`]. When
+/// the intensities within a region are updated, only the peaks within that
+/// region are selected and fitted again, while the signals elsewhere are kept
+/// as they are. As fitting is the most expensive step of the deconvolution,
+/// this is considerably faster than deconvoluting the entire [`Spectrum`]
+/// after every update. Smoothing and peak detection are still applied to the
+/// entire [`Spectrum`], as they are cheap in comparison.
+///
+/// The noise level used to filter the peaks is estimated once on construction
+/// and reused for all updates, such that updates to the signal free region
+/// don't change which peaks are selected elsewhere. Call
+/// [`refresh_noise_estimate`] to estimate it again from the current state.
+///
+/// Note that signals just outside an updated region are not fitted again, so
+/// their tails may slightly distort the signals at the edges of the region.
+/// Compare [`Deconvoluter::deconvolute_region`].
+///
+/// [`refresh_noise_estimate`]: StreamingDeconvoluter::refresh_noise_estimate
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::deconvolution::{Deconvoluter, StreamingDeconvoluter};
+/// use metabodecon::spectrum::Bruker;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// // Read a spectrum in Bruker TopSpin format.
+/// let path = "path/to/spectrum";
+/// # let path = "../data/bruker/sim/sim_01";
+/// let spectrum = Bruker::read_spectrum(
+///     path,
+///     // Experiment number
+///     10,
+///     // Processing number
+///     10,
+///     // Signal boundaries
+///     (3.339, 3.553),
+/// )?;
+///
+/// // Deconvolute the initial state of the spectrum.
+/// let mut streaming = StreamingDeconvoluter::new(Deconvoluter::default(), spectrum)?;
+///
+/// // Scale the intensities between 3.4 and 3.45 ppm, e.g. after more scans.
+/// let new_intensities = streaming
+///     .spectrum()
+///     .chemical_shifts()
+///     .iter()
+///     .zip(streaming.spectrum().intensities())
+///     .filter(|(chemical_shift, _)| (3.4..=3.45).contains(*chemical_shift))
+///     .map(|(_, intensity)| 1.1 * intensity)
+///     .collect::<Vec<f64>>();
+/// let deconvolution = streaming.update_region(3.4, 3.45, &new_intensities)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamingDeconvoluter {
+    /// Deconvoluter used for the initial deconvolution and all updates.
+    deconvoluter: Deconvoluter,
+    /// Current state of the spectrum.
+    spectrum: Spectrum,
+    /// Deconvolution of the current state of the spectrum.
+    deconvolution: Deconvolution,
+    /// Location and spread of the scores of the noise peaks.
+    noise_statistics: Option<(f64, f64)>,
+}
+
+impl StreamingDeconvoluter {
+    /// Constructs a new `StreamingDeconvoluter` by deconvoluting the initial
+    /// state of the [`Spectrum`] and estimating its noise level.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [`Deconvoluter::deconvolute_spectrum`] are
+    /// possible.
+    pub fn new(deconvoluter: Deconvoluter, spectrum: Spectrum) -> Result<Self> {
+        let deconvolution = deconvoluter.deconvolute_spectrum(&spectrum)?;
+        let noise_statistics = deconvoluter.noise_statistics(&spectrum)?;
+
+        Ok(Self {
+            deconvoluter,
+            spectrum,
+            deconvolution,
+            noise_statistics,
+        })
+    }
+
+    /// Returns the wrapped [`Deconvoluter`].
+    pub fn deconvoluter(&self) -> &Deconvoluter {
+        &self.deconvoluter
+    }
+
+    /// Returns the current state of the [`Spectrum`].
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the [`Deconvolution`] of the current state of the [`Spectrum`].
+    pub fn deconvolution(&self) -> &Deconvolution {
+        &self.deconvolution
+    }
+
+    /// Returns the noise level used to filter the peaks, or `None` if the peak
+    /// selection method doesn't estimate it. See
+    /// [`Deconvolution::noise_estimate`] for details.
+    pub fn noise_estimate(&self) -> Option<f64> {
+        self.noise_statistics.map(|(_, sd)| sd)
+    }
+
+    /// Replaces the intensities within the given region and deconvolutes the
+    /// region again.
+    ///
+    /// The region is given in ppm and includes its boundaries. One intensity
+    /// has to be provided for each data point within the region, in the order
+    /// of the chemical shifts. Peaks within the region are selected with the
+    /// existing noise estimate and fitted again, replacing the previous signals
+    /// within the region. All other signals are kept unchanged. The MSE is
+    /// computed for the entire signal region. The new [`Deconvolution`] is
+    /// stored and a copy of it is returned. It doesn't report
+    /// [`fit_diagnostics`], as only part of its signals were fitted again.
+    ///
+    /// [`fit_diagnostics`]: Deconvolution::fit_diagnostics
+    ///
+    /// Only the region and a margin of about one smoothing window on each side
+    /// are smoothed and searched for peaks. The margin is widened as needed to
+    /// select the same peaks as within the entire spectrum, but a maximum
+    /// number of peaks applies to the region and its margin. The entire
+    /// spectrum is processed with the [`Whittaker`] or a custom smoother, or
+    /// if the peaks aren't filtered by their noise scores.
+    ///
+    /// [`Whittaker`]: crate::deconvolution::SmoothingSettings::Whittaker
+    ///
+    /// If the update fails, the state of the `StreamingDeconvoluter` is left
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the start or end value of the region is not
+    /// finite or if they are (nearly) equal, if the region contains no data
+    /// points, or if the number of intensities doesn't match the number of
+    /// data points within it. The intensities must be finite. Otherwise, the
+    /// same errors as for [`Deconvoluter::deconvolute_spectrum`] are possible.
+    pub fn update_region(
+        &mut self,
+        start_ppm: f64,
+        end_ppm: f64,
+        new_intensities: &[f64],
+    ) -> Result<Deconvolution> {
+        let region = (start_ppm, end_ppm);
+        if !start_ppm.is_finite()
+            || !end_ppm.is_finite()
+            || f64::abs(start_ppm - end_ppm) < crate::CHECK_PRECISION
+        {
+            return Err(Error::new(Kind::InvalidDeconvolutionRegion { region }).into());
+        }
+        let (lower, upper) = (f64::min(start_ppm, end_ppm), f64::max(start_ppm, end_ppm));
+        let indices = self
+            .spectrum
+            .chemical_shifts()
+            .iter()
+            .enumerate()
+            .filter(|(_, chemical_shift)| (lower..=upper).contains(*chemical_shift))
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+        if indices.is_empty() || indices.len() != new_intensities.len() {
+            return Err(Error::new(Kind::InvalidRegionUpdate {
+                region,
+                points: indices.len(),
+                intensities: new_intensities.len(),
+            })
+            .into());
+        }
+        let start = indices[0];
+        let spectrum = self
+            .spectrum
+            .with_replaced_intensities(start, new_intensities)?;
+        let deconvolution = self.deconvoluter.redeconvolute_region(
+            &spectrum,
+            &self.deconvolution,
+            (start, start + indices.len()),
+            self.noise_statistics,
+        )?;
+        self.spectrum = spectrum;
+        self.deconvolution = deconvolution.clone();
+
+        Ok(deconvolution)
+    }
+
+    /// Estimates the noise level again from the current state of the
+    /// [`Spectrum`] and returns it. The stored [`Deconvolution`] is not
+    /// affected until the next update.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for the peak selection of
+    /// [`Deconvoluter::deconvolute_spectrum`] are possible, for example if no
+    /// peaks are found in the signal free region.
+    pub fn refresh_noise_estimate(&mut self) -> Result<Option<f64>> {
+        self.noise_statistics = self
+            .deconvoluter
+            .noise_statistics(&self.spectrum)?;
+
+        Ok(self.noise_estimate())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deconvolution::Lorentzian;
+    use crate::macros::noisy_lorentzian_spectrum;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(StreamingDeconvoluter);
+        assert_sync!(StreamingDeconvoluter);
+    }
+
+    fn acquired_spectrum(signals: &[Lorentzian], scan: u64) -> Spectrum {
+        noisy_lorentzian_spectrum(signals, 4000, 0.1, scan, (1.0, 9.0))
+    }
+
+    fn region_intensities(spectrum: &Spectrum, region: (f64, f64)) -> Vec<f64> {
+        spectrum
+            .chemical_shifts()
+            .iter()
+            .zip(spectrum.intensities())
+            .filter(|(chemical_shift, _)| (region.0..=region.1).contains(*chemical_shift))
+            .map(|(_, intensity)| *intensity)
+            .collect()
+    }
+
+    #[test]
+    fn update_region() {
+        let signal = |maxp: f64| Lorentzian::new(5.0 * 0.02, 0.02_f64.powi(2), maxp);
+        // The next acquisition differs everywhere, both in its noise and in a
+        // new signal within the region and one that grew outside of it, but
+        // only the region is updated. The signal at 4.4 ppm tails into it.
+        let initial = acquired_spectrum(&[signal(3.0), signal(4.4), signal(7.0)], 1);
+        let updated = acquired_spectrum(
+            &[
+                signal(3.0),
+                signal(4.4),
+                signal(5.0),
+                Lorentzian::new(10.0 * 0.02, 0.02_f64.powi(2), 7.0),
+            ],
+            2,
+        );
+        let mut streaming =
+            StreamingDeconvoluter::new(Deconvoluter::default(), initial.clone()).unwrap();
+        let previous = streaming.deconvolution().clone();
+        let region = (4.5, 5.5);
+        let deconvolution = streaming
+            .update_region(region.0, region.1, &region_intensities(&updated, region))
+            .unwrap();
+        let outside =
+            |lorentzian: &&Lorentzian| !(region.0..=region.1).contains(&lorentzian.maxp());
+        let kept = deconvolution
+            .lorentzians()
+            .iter()
+            .filter(outside)
+            .collect::<Vec<&Lorentzian>>();
+        let expected = previous
+            .lorentzians()
+            .iter()
+            .filter(outside)
+            .collect::<Vec<&Lorentzian>>();
+        assert_eq!(kept.len(), expected.len());
+        kept.iter()
+            .zip(expected.iter())
+            .for_each(|(kept, expected)| {
+                assert_eq!(kept.maxp(), expected.maxp());
+                assert_eq!(kept.sfhw(), expected.sfhw());
+                assert_eq!(kept.hw2(), expected.hw2());
+            });
+        assert!(
+            deconvolution
+                .lorentzians()
+                .iter()
+                .any(|lorentzian| (lorentzian.maxp() - 5.0).abs() < 0.01)
+        );
+        assert!(
+            !previous
+                .lorentzians()
+                .iter()
+                .any(|lorentzian| (lorentzian.maxp() - 5.0).abs() < 0.01)
+        );
+        assert_eq!(streaming.deconvolution().len(), deconvolution.len());
+        assert_eq!(
+            region_intensities(streaming.spectrum(), region),
+            region_intensities(&updated, region)
+        );
+        assert_eq!(
+            region_intensities(streaming.spectrum(), (6.5, 7.5)),
+            region_intensities(&initial, (6.5, 7.5))
+        );
+        assert_eq!(deconvolution.noise_estimate(), previous.noise_estimate());
+        let mse = Deconvoluter::default()
+            .deconvolute_spectrum(streaming.spectrum())
+            .unwrap()
+            .mse();
+        assert_approx_eq!(f64, deconvolution.mse(), mse, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn noise_estimate() {
+        let signal = Lorentzian::new(5.0 * 0.02, 0.02_f64.powi(2), 5.0);
+        let spectrum = acquired_spectrum(&[signal], 1);
+        let mut streaming =
+            StreamingDeconvoluter::new(Deconvoluter::default(), spectrum.clone()).unwrap();
+        let initial = streaming.noise_estimate().unwrap();
+        assert_approx_eq!(
+            f64,
+            initial,
+            streaming
+                .deconvolution()
+                .noise_estimate()
+                .unwrap()
+        );
+        let region = (0.0, 0.5);
+        let noisier = region_intensities(&spectrum, region)
+            .into_iter()
+            .map(|intensity| 10.0 * intensity)
+            .collect::<Vec<f64>>();
+        let deconvolution = streaming
+            .update_region(region.0, region.1, &noisier)
+            .unwrap();
+        assert_eq!(deconvolution.noise_estimate(), Some(initial));
+        let refreshed = streaming
+            .refresh_noise_estimate()
+            .unwrap()
+            .unwrap();
+        assert!(refreshed > initial);
+    }
+
+    #[test]
+    fn invalid_region_update() {
+        let signal = Lorentzian::new(5.0 * 0.02, 0.02_f64.powi(2), 5.0);
+        let spectrum = acquired_spectrum(&[signal], 1);
+        let mut streaming = StreamingDeconvoluter::new(Deconvoluter::default(), spectrum).unwrap();
+        let previous = streaming.deconvolution().clone();
+        let points = region_intensities(streaming.spectrum(), (4.0, 6.0)).len();
+        let errors = [
+            streaming
+                .update_region(f64::NAN, 6.0, &[])
+                .unwrap_err(),
+            streaming
+                .update_region(5.0, 5.0, &[])
+                .unwrap_err(),
+            streaming
+                .update_region(11.0, 12.0, &[])
+                .unwrap_err(),
+            streaming
+                .update_region(4.0, 6.0, &vec![0.0; points - 1])
+                .unwrap_err(),
+            streaming
+                .update_region(4.0, 6.0, &vec![f64::NAN; points])
+                .unwrap_err(),
+        ];
+        errors
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, error)| match (i, error) {
+                (0 | 1, crate::Error::Deconvolution(inner)) => match inner.kind() {
+                    Kind::InvalidDeconvolutionRegion { .. } => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                (2 | 3, crate::Error::Deconvolution(inner)) => match inner.kind() {
+                    Kind::InvalidRegionUpdate { .. } => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                (4, crate::Error::Spectrum(_)) => {}
+                (_, error) => panic!("unexpected error: {:?}", error),
+            });
+        assert_eq!(streaming.deconvolution().len(), previous.len());
+        assert_eq!(streaming.deconvolution().mse(), previous.mse());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{deconvolution, spectrum};
+use crate::{alignment, deconvolution, spectrum};
 
 /// A specialized [`Result`] type for the Metabodecon library.
 ///
@@ -10,6 +10,11 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///
 /// Marked as non-exhaustive because some variants will only be available with
 /// certain features enabled.
+///
+/// Errors from external libraries, such as [`std::io`], are exposed through
+/// [`source`], such that their root cause can be inspected by downcasting.
+///
+/// [`source`]: std::error::Error::source
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum Error {
@@ -19,11 +24,36 @@ pub enum Error {
     Spectrum(spectrum::error::Error),
     /// An error that occurred during the [`deconvolution`] process.
     Deconvolution(deconvolution::error::Error),
+    /// An error that occurred during the [`alignment`] process.
+    Alignment(alignment::error::Error),
     /// Wrapper for errors from [`std::io`].
     IoError(std::io::Error),
+    /// Wrapper for errors from encoding data as [MessagePack].
+    ///
+    /// [MessagePack]: https://docs.rs/rmp-serde
+    #[cfg(feature = "serde")]
+    MessagePackEncode(rmp_serde::encode::Error),
+    /// Wrapper for errors from decoding [MessagePack] data.
+    ///
+    /// [MessagePack]: https://docs.rs/rmp-serde
+    #[cfg(feature = "serde")]
+    MessagePackDecode(rmp_serde::decode::Error),
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Spectrum(error) => error.source(),
+            Error::Deconvolution(error) => error.source(),
+            Error::Alignment(error) => error.source(),
+            Error::IoError(error) => Some(error),
+            #[cfg(feature = "serde")]
+            Error::MessagePackEncode(error) => Some(error),
+            #[cfg(feature = "serde")]
+            Error::MessagePackDecode(error) => Some(error),
+        }
+    }
+}
 
 impl From<spectrum::error::Error> for Error {
     fn from(value: spectrum::error::Error) -> Self {
@@ -37,18 +67,43 @@ impl From<deconvolution::error::Error> for Error {
     }
 }
 
+impl From<alignment::error::Error> for Error {
+    fn from(value: alignment::error::Error) -> Self {
+        Error::Alignment(value)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(value: std::io::Error) -> Self {
         Error::IoError(value)
     }
 }
 
+#[cfg(feature = "serde")]
+impl From<rmp_serde::encode::Error> for Error {
+    fn from(value: rmp_serde::encode::Error) -> Self {
+        Error::MessagePackEncode(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<rmp_serde::decode::Error> for Error {
+    fn from(value: rmp_serde::decode::Error) -> Self {
+        Error::MessagePackDecode(value)
+    }
+}
+
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match *self {
             Error::Spectrum(ref e) => e.fmt(f),
             Error::Deconvolution(ref e) => e.fmt(f),
+            Error::Alignment(ref e) => e.fmt(f),
             Error::IoError(ref e) => e.fmt(f),
+            #[cfg(feature = "serde")]
+            Error::MessagePackEncode(ref e) => e.fmt(f),
+            #[cfg(feature = "serde")]
+            Error::MessagePackDecode(ref e) => e.fmt(f),
         }
     }
 }
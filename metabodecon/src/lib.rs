@@ -17,13 +17,22 @@
 //! following:
 //! - **Bruker**: Use the [`Bruker`] interface to parse Bruker-formatted data.
 //!   Requires the `bruker` feature.
-//! - **JCAMP-DX**: Use the [`JcampDx`] interface to parse JCAMP-DX files.
-//!   Requires the `jdx` feature.
+//! - **JCAMP-DX**: Use the [`JcampDx`] interface to parse and write JCAMP-DX
+//!   files. Requires the `jdx` feature.
+//! - **CSV/TSV**: Use the [`Csv`] interface to parse delimited text files of
+//!   chemical shift and intensity pairs. Requires the `csv` feature.
+//! - **Varian/Agilent**: Use the [`Varian`] interface to parse VnmrJ-formatted
+//!   data. Requires the `varian` feature.
+//! - **NMRPipe**: Use the [`NmrPipe`] interface to parse NMRPipe files.
+//!   Requires the `nmrpipe` feature.
 //!
 //! [`Spectrum`]: spectrum::Spectrum
 //! [`Spectrum::new`]: spectrum::Spectrum::new
 //! [`Bruker`]: spectrum::Bruker
 //! [`JcampDx`]: spectrum::JcampDx
+//! [`Csv`]: spectrum::Csv
+//! [`Varian`]: spectrum::Varian
+//! [`NmrPipe`]: spectrum::NmrPipe
 //!
 //! The [`deconvolution module`](deconvolution) provides types and functions to
 //! deconvolute a [`Spectrum`] into its constituent signals. The primary type in
@@ -85,14 +94,22 @@
 //! [MSE]: https://en.wikipedia.org/wiki/Mean_squared_error
 //! [Lorentzian function]: https://en.wikipedia.org/wiki/Cauchy_distribution
 //!
+//! The [`alignment module`](alignment) makes the deconvolutions of multiple
+//! spectra comparable. The [`Aligner`] clusters the signals of multiple
+//! [`Deconvolution`]s by their position and produces an [`Alignment`], a table
+//! of signal areas with one row per spectrum and one column per consensus
+//! signal.
+//!
+//! [`Aligner`]: alignment::Aligner
+//! [`Alignment`]: alignment::Alignment
+//! [`Deconvolution`]: deconvolution::Deconvolution
+//!
 //! # Crate Status
 //!
 //! This crate is in an early stage of development and is not yet feature
 //! complete. Features that are currently planned:
 //! - More file formats for reading spectra
-//! - More algorithms for smoothing, peak selection, and fitting
-//! - An alignment algorithm for deconvolutions of multiple spectra, to make
-//!   them comparable
+//! - More algorithms for smoothing, peak selection, fitting, and alignment
 //!
 //! # Feature flags
 //!
@@ -102,15 +119,27 @@
 //! | Flag      | Description                                         | Dependencies         |
 //! |-----------|---------------------------------------------------- |----------------------|
 //! | `bruker`  | Bruker file format interface for reading spectra.   | [Regex], [Byteorder] |
-//! | `jdx`     | JCAMP-DX file format interface for reading spectra. | [Regex]              |
+//! | `jdx`     | JCAMP-DX interface for reading and writing spectra. | [Regex]              |
+//! | `csv`     | CSV/TSV interface for spectra and deconvolutions.   |                      |
+//! | `varian`  | Varian/Agilent VnmrJ interface for reading spectra. | [Regex], [Byteorder] |
+//! | `nmrpipe` | NMRPipe file format interface for reading spectra.  | [Byteorder]          |
+//! | `gzip`    | Transparent decompression of gzipped text files.    | [Flate2]             |
+//! | `fft`     | Fourier transform of FIDs, phasing and apodization. | [RustFFT]            |
 //! | `parallel`| Parallelization of the deconvolution process.       | [Rayon]              |
-//! | `serde`   | Serialization and deserialization of data.          | [Serde]              |
+//! | `simd`    | Vectorized evaluation of Lorentzian superpositions. | [Wide]               |
+//! | `serde`   | Serialization and deserialization of data.          | [Serde], [RmpSerde]  |
 //!
 //! [Regex]: https://docs.rs/regex
 //! [Byteorder]: https://docs.rs/byteorder
+//! [Flate2]: https://docs.rs/flate2
 //! [Rayon]: https://docs.rs/rayon
+//! [Wide]: https://docs.rs/wide
+//! [RustFFT]: https://docs.rs/rustfft
+//! [RmpSerde]: https://docs.rs/rmp-serde
 //!
-//! By default, all of these features are enabled.
+//! By default, all of these features are enabled except `gzip`, `fft` and
+//! `simd`, which have to be enabled explicitly. The `full` feature enables
+//! everything.
 //!
 //! # Serialization with Serde
 //!
@@ -241,15 +270,21 @@
 //! # }
 //! ```
 //!
-//! [`Deconvoluter`] is modular and allows you to configure the smoothing, peak
-//! selection, and fitting settings independently, though currently only one
-//! method is implemented for each. Additionally, you can specify regions to be
-//! ignored during the deconvolution. This may be useful for compounds like
+//! [`Deconvoluter`] is modular and allows you to choose the smoothing, peak
+//! selection, and fitting methods independently. The available methods are
+//! the variants of [`SmoothingSettings`], [`SelectionSettings`], and
+//! [`FittingSettings`]. Additionally, you can specify regions to be ignored
+//! during the deconvolution. This may be useful for compounds like
 //! stabilizing agents or a water signal.
 //!
+//! [`SmoothingSettings`]: deconvolution::SmoothingSettings
+//! [`SelectionSettings`]: deconvolution::SelectionSettings
+//! [`FittingSettings`]: deconvolution::FittingSettings
+//!
 //! ```
 //! use metabodecon::deconvolution::{
-//!     Deconvoluter, FittingSettings, ScoringMethod, SelectionSettings, SmoothingSettings,
+//!     Deconvoluter, FittingSettings, NoiseEstimator, ScoringMethod, SelectionSettings,
+//!     SmoothingSettings,
 //! };
 //!
 //! # fn main() -> metabodecon::Result<()> {
@@ -262,6 +297,8 @@
 //!     SelectionSettings::NoiseScoreFilter {
 //!         scoring_method: ScoringMethod::MinimumSum,
 //!         threshold: 5.0,
+//!         max_peaks: None,
+//!         noise_estimator: NoiseEstimator::StdDev,
 //!     },
 //!     FittingSettings::Analytical { iterations: 20 },
 //! )?;
@@ -283,5 +320,7 @@ pub mod spectrum;
 
 pub mod deconvolution;
 
+pub mod alignment;
+
 mod error;
 pub use error::{Error, Result};
@@ -1,5 +1,10 @@
 #[cfg(test)]
 mod check_spectrum;
 
+#[cfg(test)]
+mod noisy_spectrum;
+#[cfg(test)]
+pub(crate) use noisy_spectrum::{noisy_lorentzian_spectrum, uniform_noise};
+
 #[cfg(test)]
 mod thread_safety;
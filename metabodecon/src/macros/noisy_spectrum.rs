@@ -0,0 +1,38 @@
+use crate::deconvolution::Lorentzian;
+use crate::spectrum::Spectrum;
+
+/// Test utility function to generate reproducible uniform noise within
+/// `[-0.5, 0.5)` with a linear congruential generator.
+pub(crate) fn uniform_noise(len: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1_u64 << 53) as f64 - 0.5
+        })
+        .collect()
+}
+
+/// Test utility function to simulate a spectrum of the given Lorentzians
+/// with `len` equally spaced chemical shifts from 0 to 10 ppm and uniform
+/// noise of the given amplitude.
+pub(crate) fn noisy_lorentzian_spectrum(
+    signals: &[Lorentzian],
+    len: usize,
+    amplitude: f64,
+    seed: u64,
+    signal_boundaries: (f64, f64),
+) -> Spectrum {
+    let chemical_shifts = (0..len)
+        .map(|i| i as f64 * 10.0 / (len - 1) as f64)
+        .collect::<Vec<f64>>();
+    let intensities = chemical_shifts
+        .iter()
+        .zip(uniform_noise(len, seed))
+        .map(|(x, noise)| Lorentzian::superposition(*x, signals) + amplitude * noise)
+        .collect::<Vec<f64>>();
+
+    Spectrum::new(chemical_shifts, intensities, signal_boundaries).unwrap()
+}
@@ -3,8 +3,13 @@
 //! # Key Components
 //!
 //! - [`Spectrum`]: Container for spectral 1D NMR data and metadata.
+//! - [`WindowFunction`]: Window functions for apodization of spectra.
+//! - [`IntensityStats`]: Summary statistics of the intensities of a spectrum.
 //! - [`Bruker`]: Interface for parsing spectra from Bruker TopSpin format.
-//! - [`JcampDx`]: Interface for parsing spectra from JCAMP-DX files.
+//! - [`JcampDx`]: Interface for parsing and writing spectra in JCAMP-DX format.
+//! - [`Csv`]: Interface for parsing spectra from delimited text files.
+//! - [`Varian`]: Interface for parsing spectra from Varian/Agilent VnmrJ format.
+//! - [`NmrPipe`]: Interface for parsing spectra from NMRPipe files.
 //! - [`meta`]: Data structures for NMR spectrum metadata.
 //!
 //! # Example: Reading multiple spectra from Bruker TopSpin format
@@ -55,6 +60,74 @@
 //! # }
 //! ```
 //!
+//! # Example: Reading a spectrum from a CSV file
+//!
+//! Spectra exported as simple tables of chemical shift and intensity pairs can
+//! be read from delimited text files. [Read more](Csv)
+//!
+//! ```
+//! use metabodecon::spectrum::{Csv, CsvOptions};
+//!
+//! # fn main() -> metabodecon::Result<()> {
+//! let path = "path/to/spectrum.csv";
+//! # let path = "../data/csv/test/comma.csv";
+//!
+//! // Read a spectrum from a comma separated file with a header row.
+//! let spectrum = Csv::read_spectrum(
+//!     path,
+//!     // Signal boundaries
+//!     (4.1, 4.9),
+//!     // Delimiter, header row, chemical shifts column, intensities column
+//!     CsvOptions::new(',', true, 0, 1),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Example: Reading multiple spectra from Varian/Agilent VnmrJ format
+//!
+//! Spectra acquired on Varian/Agilent instruments can be read from the VnmrJ
+//! format. [Read more](Varian)
+//!
+//! ```
+//! use metabodecon::spectrum::Varian;
+//!
+//! # fn main() -> metabodecon::Result<()> {
+//! let path = "path/to/root";
+//! # let path = "../data/varian/sim";
+//!
+//! // Read all spectra from VnmrJ format directories within the root.
+//! let spectra = Varian::read_spectra(
+//!     path,
+//!     // Signal boundaries
+//!     (-2.2, 11.8),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Example: Reading a spectrum from an NMRPipe file
+//!
+//! Spectra processed with NMRPipe can be read from the resulting `.ft` files.
+//! [Read more](NmrPipe)
+//!
+//! ```
+//! use metabodecon::spectrum::NmrPipe;
+//!
+//! # fn main() -> metabodecon::Result<()> {
+//! let path = "path/to/spectrum.ft";
+//! # let path = "../data/nmrpipe/sim/sim_01.ft";
+//!
+//! // Read a spectrum from an NMRPipe file.
+//! let spectrum = NmrPipe::read_spectrum(
+//!     path,
+//!     // Signal boundaries
+//!     (-2.2, 11.8),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Example: Constructing a `Spectrum` manually
 //!
 //! The following example demonstrates how to create a `Spectrum` object from
@@ -101,6 +174,12 @@
 mod spectrum;
 pub use spectrum::Spectrum;
 
+mod window_function;
+pub use window_function::WindowFunction;
+
+mod intensity_stats;
+pub use intensity_stats::IntensityStats;
+
 #[cfg(feature = "serde")]
 mod serialized_spectrum;
 #[cfg(feature = "serde")]
@@ -111,7 +190,13 @@ pub mod meta;
 mod formats;
 #[cfg(feature = "bruker")]
 pub use formats::Bruker;
+#[cfg(feature = "nmrpipe")]
+pub use formats::NmrPipe;
+#[cfg(feature = "varian")]
+pub use formats::Varian;
+#[cfg(feature = "csv")]
+pub use formats::{Csv, CsvOptions};
 #[cfg(feature = "jdx")]
-pub use formats::JcampDx;
+pub use formats::{JcampDx, JcampDxCompression, JcampDxDataType, JcampDxFormat, JcampDxMetadata};
 
 pub mod error;
@@ -1,5 +1,6 @@
 //! Error types for the spectrum module.
 
+use crate::spectrum::WindowFunction;
 use std::path::PathBuf;
 
 /// The `Error` type for constructing a [`Spectrum`] or parsing 1D NMR data from
@@ -112,6 +113,166 @@ pub enum Kind {
         /// Range of the chemical shifts.
         chemical_shifts_range: (f64, f64),
     },
+    /// The range to crop a [`Spectrum`] to is invalid.
+    ///
+    /// The range must consist of finite values and contain at least 2 of the
+    /// chemical shifts of the [`Spectrum`], as the step size couldn't be
+    /// computed otherwise.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidCropRange {
+        /// Range that the spectrum was supposed to be cropped to.
+        range: (f64, f64),
+        /// Range of the chemical shifts.
+        chemical_shifts_range: (f64, f64),
+    },
+    /// The parameters to resample a [`Spectrum`] with are invalid.
+    ///
+    /// The resampled [`Spectrum`] needs at least 2 points, and the step size
+    /// must be a finite value that is not (almost) zero.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidResampling {
+        /// Number of points of the resampled spectrum.
+        len: usize,
+        /// Step size of the resampled spectrum.
+        step: f64,
+    },
+    /// The width of the buckets to divide a [`Spectrum`] into is invalid.
+    ///
+    /// The bucket width must be a finite, positive value. When the number of
+    /// buckets is given instead, 0 buckets result in an infinite width.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidBucketWidth {
+        /// Width of the buckets in ppm.
+        width: f64,
+    },
+    /// The intensities of a [`Spectrum`] can't be normalized.
+    ///
+    /// The scaling factor needs to be finite and non-zero, which isn't the case
+    /// if the intensities within the signal region sum to zero or the target
+    /// is zero or not finite.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidNormalization {
+        /// Sum of the intensities within the signal region.
+        sum: f64,
+        /// Target sum of the normalized intensities.
+        target: f64,
+    },
+    /// The phase correction angles for a [`Spectrum`] are invalid.
+    ///
+    /// Both the zero- and first-order phase correction angles must be finite.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidPhaseCorrection {
+        /// Zero-order phase correction in degrees.
+        ph0: f64,
+        /// First-order phase correction in degrees.
+        ph1: f64,
+    },
+    /// The multiple of the noise level used to detect the signal region of a
+    /// [`Spectrum`] is invalid.
+    ///
+    /// The multiple must be a finite, positive value.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidNoiseMultiple {
+        /// Multiple of the noise standard deviation.
+        noise_multiple: f64,
+    },
+    /// No signal region could be detected in a [`Spectrum`].
+    ///
+    /// This is the case if no intensities consistently exceed the detection
+    /// threshold, which is derived from the noise level at the extremes of the
+    /// spectrum. Lowering the noise multiple may help.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    NoSignalRegionDetected {
+        /// Intensity threshold that was not exceeded.
+        threshold: f64,
+    },
+    /// The noise range to estimate the signal-to-noise ratio of a [`Spectrum`]
+    /// from is invalid.
+    ///
+    /// The range must consist of finite values and contain at least 2 of the
+    /// chemical shifts of the [`Spectrum`], as the noise level couldn't be
+    /// estimated otherwise.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidNoiseRange {
+        /// Range that the noise level was supposed to be estimated from.
+        range: (f64, f64),
+        /// Range of the chemical shifts.
+        chemical_shifts_range: (f64, f64),
+    },
+    /// A [`Spectrum`] could not be calibrated to a reference peak.
+    ///
+    /// The search range and the target chemical shift must be finite, and the
+    /// search range must contain a clear maximum, i.e. a point with a higher
+    /// intensity than its neighbors that is not at the edge of the range. If
+    /// the maximum is at the edge, the reference peak likely lies outside the
+    /// search range.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidCalibration {
+        /// Range that was searched for the reference peak.
+        search_range: (f64, f64),
+        /// Chemical shift that the reference peak was supposed to be moved to.
+        target_shift: f64,
+    },
+    /// A [`WindowFunction`] could not be applied to a [`Spectrum`].
+    ///
+    /// The parameters of the window function must be valid, see
+    /// [`WindowFunction`] for details, and the spectrometer frequency must be
+    /// finite and positive to convert them to the chemical shift scale.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    /// [`WindowFunction`]: crate::spectrum::WindowFunction
+    InvalidWindowFunction {
+        /// Window function that was supposed to be applied.
+        window: WindowFunction,
+        /// Spectrometer frequency of the spectrum in MHz.
+        frequency: f64,
+    },
+    /// The spectrometer frequency of a [`Spectrum`] is needed to convert
+    /// between Hz and ppm, but it is unset or invalid.
+    ///
+    /// The frequency must be finite and positive. Additionally, the default
+    /// value of 1 MHz is treated as unset to avoid silently wrong conversions.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidFrequency {
+        /// Spectrometer frequency of the spectrum in MHz.
+        frequency: f64,
+    },
+    /// Two [`Spectrum`] instances that were combined don't share the same grid.
+    ///
+    /// Arithmetic between spectra is performed point by point, which requires
+    /// both to have the same number of data points and the same chemical shift
+    /// range. Resampling one of the spectra onto the grid of the other may
+    /// help.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    GridMismatch {
+        /// Number of data points of both spectra.
+        lengths: (usize, usize),
+        /// Chemical shift ranges of both spectra.
+        ranges: ((f64, f64), (f64, f64)),
+    },
+    /// The factor to decimate a [`Spectrum`] by is invalid.
+    ///
+    /// The decimation factor must be positive, and the decimated [`Spectrum`]
+    /// needs at least 2 points.
+    ///
+    /// [`Spectrum`]: crate::spectrum::Spectrum
+    InvalidDecimation {
+        /// Factor that the spectrum was supposed to be decimated by.
+        factor: usize,
+        /// Number of data points of the spectrum.
+        len: usize,
+    },
 
     /// Metadata is missing from a file of the various formats.
     ///
@@ -146,26 +307,69 @@ pub enum Kind {
         /// Path to the file where the data block was expected.
         path: PathBuf,
     },
-    /// The data block in a JCAMP-DX file is malformed.
+    /// The data block in a JCAMP-DX file or a binary data file is malformed.
+    ///
+    /// If the malformed value could be located within a JCAMP-DX file, the
+    /// line number and byte offset are included, such that it can be found
+    /// in large files.
     MalformedData {
         /// Path to the file where the data block was expected.
         path: PathBuf,
         /// Additional details about the malformed data block.
         details: String,
+        /// Line number (starting at 1) of the malformed value, if known.
+        line: Option<usize>,
+        /// Byte offset (starting at 0) of the malformed value or its line, if
+        /// known.
+        offset: Option<usize>,
+    },
+    /// A line in a delimited text file, such as CSV or TSV, is malformed.
+    ///
+    /// This indicates that a row doesn't contain the expected columns, or that
+    /// one of its values can't be parsed as a number. Check that the delimiter
+    /// and column indices match the file.
+    MalformedLine {
+        /// Path to the file containing the malformed line.
+        path: PathBuf,
+        /// Line number (starting at 1) of the malformed line.
+        line: usize,
+        /// Additional details about the malformed line.
+        details: String,
     },
     /// The JCAMP-DX file is not supported.
     ///
     /// The JCAMP-DX format has different versions, and not all of them can be
     /// parsed at this time. Currently supported is:
     /// - Version 5.xx and 6.xx
-    /// - NMR SPECTRUM data type
-    /// - XYDATA and NTUPLES data classes
+    /// - NMR SPECTRUM and NMR FID data types
+    /// - XYDATA and NTUPLES data classes (NTUPLES only for NMR FID)
     ///
     /// If you have a file that you believe should be parsable but is not, open
     /// an [issue] and provide the file.
     ///
     /// [issue]: https://github.com/SombkeMaximilian/metabodecon-rust/issues
     UnsupportedJcampDxFile,
+    /// The JCAMP-DX file contains an FID that can't be transformed.
+    ///
+    /// Reading time-domain data requires a fourier transform, which is only
+    /// available with the `fft` feature enabled.
+    UntransformableFid {
+        /// Path to the file containing the FID.
+        path: PathBuf,
+    },
+    /// None of the processings of a Bruker TopSpin format experiment could be
+    /// read.
+    ///
+    /// This is the case if the `pdata` directory contains no processings, or
+    /// if reading each of them failed. The errors of the individual
+    /// processings are retained to help identify the problem.
+    NoReadableProcessing {
+        /// Path to the `pdata` directory of the experiment.
+        path: PathBuf,
+        /// Processing numbers that were attempted, together with the error
+        /// messages of the failed attempts.
+        failures: Vec<(u32, String)>,
+    },
 }
 
 impl std::error::Error for Error {}
@@ -282,6 +486,116 @@ impl core::fmt::Display for Error {
                     _ => unreachable!("valid signal boundaries falsely detected as invalid"),
                 }
             }
+            Kind::InvalidCropRange {
+                range,
+                chemical_shifts_range,
+            } => match range.0.is_finite() && range.1.is_finite() {
+                false => format!(
+                    "crop range [{}, {}] contains non-finite values",
+                    range.0, range.1
+                ),
+                true => format!(
+                    "crop range [{}, {}] contains fewer than 2 \
+                     of the chemical shifts within [{}, {}]",
+                    range.0, range.1, chemical_shifts_range.0, chemical_shifts_range.1
+                ),
+            },
+            Kind::InvalidResampling { len, step } => {
+                match step.is_nan() || (step.is_finite() && step.abs() > crate::CHECK_PRECISION) {
+                    true => format!(
+                        "resampling requires at least 2 points, \
+                         but [{}] were requested",
+                        len
+                    ),
+                    false => format!(
+                        "resampling step size [{}] is not finite or (almost) zero",
+                        step
+                    ),
+                }
+            }
+            Kind::InvalidBucketWidth { width } => {
+                format!("bucket width [{}] must be finite and greater than 0", width)
+            }
+            Kind::InvalidNormalization { sum, target } => format!(
+                "intensities with sum [{}] can't be normalized to [{}] \
+                 (scaling factor is not finite or zero)",
+                sum, target
+            ),
+            Kind::InvalidPhaseCorrection { ph0, ph1 } => {
+                format!("phase correction angles [{}, {}] must be finite", ph0, ph1)
+            }
+            Kind::InvalidNoiseMultiple { noise_multiple } => format!(
+                "noise multiple [{}] must be finite and greater than 0",
+                noise_multiple
+            ),
+            Kind::NoSignalRegionDetected { threshold } => format!(
+                "no signal region detected \
+                 (no intensities consistently exceed [{}])",
+                threshold
+            ),
+            Kind::InvalidNoiseRange {
+                range,
+                chemical_shifts_range,
+            } => match range.0.is_finite() && range.1.is_finite() {
+                false => format!(
+                    "noise range [{}, {}] contains non-finite values",
+                    range.0, range.1
+                ),
+                true => format!(
+                    "noise range [{}, {}] contains fewer than 2 \
+                     of the chemical shifts within [{}, {}]",
+                    range.0, range.1, chemical_shifts_range.0, chemical_shifts_range.1
+                ),
+            },
+            Kind::InvalidCalibration {
+                search_range,
+                target_shift,
+            } => match (
+                target_shift.is_finite(),
+                search_range.0.is_finite() && search_range.1.is_finite(),
+            ) {
+                (false, _) => format!(
+                    "calibration target chemical shift [{}] must be finite",
+                    target_shift
+                ),
+                (true, false) => format!(
+                    "calibration search range [{}, {}] contains non-finite values",
+                    search_range.0, search_range.1
+                ),
+                (true, true) => format!(
+                    "no clear maximum found within the calibration search range [{}, {}]",
+                    search_range.0, search_range.1
+                ),
+            },
+            Kind::InvalidWindowFunction { window, frequency } => {
+                match frequency.is_finite() && *frequency > 0.0 {
+                    true => format!("window function {} has invalid parameters", window),
+                    false => format!(
+                        "spectrometer frequency [{}] must be finite and positive \
+                         to apply window functions",
+                        frequency
+                    ),
+                }
+            }
+            Kind::InvalidFrequency { frequency } => format!(
+                "spectrometer frequency [{}] must be set to a finite and positive value \
+                 other than the default 1 MHz to convert between Hz and ppm",
+                frequency
+            ),
+            Kind::GridMismatch { lengths, ranges } => format!(
+                "spectra must share the same grid, \
+                 but have {} data points in [{}, {}] \
+                 and {} data points in [{}, {}]",
+                lengths.0, ranges.0.0, ranges.0.1, lengths.1, ranges.1.0, ranges.1.1
+            ),
+            Kind::InvalidDecimation { factor, len } => match factor {
+                0 => "decimation factor must be positive".to_string(),
+                _ => format!(
+                    "decimating [{}] data points by a factor of [{}] \
+                     leaves fewer than 2 points",
+                    len, factor
+                ),
+            },
             Kind::MissingMetadata { path, key } => format!(
                 "missing metadata \
                  expected in file at {:?} \
@@ -300,15 +614,59 @@ impl core::fmt::Display for Error {
                  expected in file at {:?}",
                 path
             ),
-            Kind::MalformedData { path, details } => format!(
-                "malformed data block \
+            Kind::MalformedData {
+                path,
+                details,
+                line,
+                offset,
+            } => match (line, offset) {
+                (Some(line), Some(offset)) => format!(
+                    "malformed data block \
+                     at {}:{} \
+                     (byte offset {}, {})",
+                    path.display(),
+                    line,
+                    offset,
+                    details
+                ),
+                _ => format!(
+                    "malformed data block \
+                     in file at {:?} \
+                     ({})",
+                    path, details
+                ),
+            },
+            Kind::MalformedLine {
+                path,
+                line,
+                details,
+            } => format!(
+                "malformed line {} \
                  in file at {:?} \
                  ({})",
-                path, details
+                line, path, details
             ),
             Kind::UnsupportedJcampDxFile => {
                 "unsupported JCAMP-DX file (see documentation for supported file types)".to_string()
             }
+            Kind::UntransformableFid { path } => format!(
+                "FID in file at {:?} \
+                 can't be transformed into a spectrum \
+                 (requires the `fft` feature)",
+                path
+            ),
+            Kind::NoReadableProcessing { path, failures } => match failures.is_empty() {
+                true => format!("no processings found in directory at {:?}", path),
+                false => format!(
+                    "none of the processings in directory at {:?} could be read ({})",
+                    path,
+                    failures
+                        .iter()
+                        .map(|(processing, error)| format!("{}: {}", processing, error))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ),
+            },
         };
 
         write!(f, "{}", description)
@@ -1,14 +1,46 @@
-#[cfg(any(feature = "bruker", feature = "jdx"))]
+#[cfg(any(feature = "bruker", feature = "jdx", feature = "varian"))]
 mod extract_capture;
-#[cfg(any(feature = "bruker", feature = "jdx"))]
-pub(crate) use extract_capture::{extract_capture, extract_row};
+#[cfg(any(feature = "bruker", feature = "jdx", feature = "varian"))]
+pub(crate) use extract_capture::{extract_capture, extract_row, number_regex};
+
+#[cfg(any(
+    feature = "bruker",
+    feature = "jdx",
+    feature = "csv",
+    feature = "varian"
+))]
+mod read_text;
+#[cfg(any(
+    feature = "bruker",
+    feature = "jdx",
+    feature = "csv",
+    feature = "varian"
+))]
+pub(crate) use read_text::read_text;
+#[cfg(any(feature = "jdx", feature = "csv"))]
+pub(crate) use read_text::text_extension;
 
 #[cfg(feature = "bruker")]
 mod bruker;
 #[cfg(feature = "bruker")]
 pub use bruker::Bruker;
 
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "csv")]
+pub use csv::{Csv, CsvOptions};
+
 #[cfg(feature = "jdx")]
 mod jcampdx;
 #[cfg(feature = "jdx")]
-pub use jcampdx::JcampDx;
+pub use jcampdx::{JcampDx, JcampDxCompression, JcampDxDataType, JcampDxFormat, JcampDxMetadata};
+
+#[cfg(feature = "varian")]
+mod varian;
+#[cfg(feature = "varian")]
+pub use varian::Varian;
+
+#[cfg(feature = "nmrpipe")]
+mod nmrpipe;
+#[cfg(feature = "nmrpipe")]
+pub use nmrpipe::NmrPipe;
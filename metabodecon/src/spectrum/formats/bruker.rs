@@ -1,10 +1,12 @@
 use crate::Result;
 use crate::spectrum::Spectrum;
-use crate::spectrum::formats::extract_capture;
+use crate::spectrum::error::{Error, Kind};
+use crate::spectrum::formats::{extract_capture, number_regex, read_text};
 use crate::spectrum::meta::Nucleus;
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use num_traits::AsPrimitive;
 use regex::Regex;
-use std::fs::{File, read_to_string};
+use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use std::sync::LazyLock;
@@ -33,13 +35,17 @@ use std::sync::LazyLock;
 /// type of experiment. Usually a lab will have a convention for which number
 /// corresponds to which type of experiment. For example 10 being a 1D NMR
 /// experiment. `pdata` is the processing data directory and `processing` is
-/// the processing number, which is an arbitrary integer.
+/// the processing number, which is an arbitrary integer. If the processing
+/// number is not known, [`Bruker::read_spectrum_any`] can be used to read the
+/// first processing that contains valid data.
 ///
 /// ## Metadata
 ///
 /// The `acqus` and `procs` files contain the acquisition and processing
 /// parameters, respectively. They are plain text files with key-value pairs,
-/// where each line starts with `##$key=`.
+/// where each line starts with `##$key=`. If the `gzip` feature is enabled,
+/// they may also be gzip-compressed, either in place or as `acqus.gz` and
+/// `procs.gz`.
 ///
 /// From the `acqus` file, the following keys are required:
 /// * `SW`: The spectral width in ppm as a floating point number.
@@ -66,10 +72,20 @@ use std::sync::LazyLock;
 ///   integers, it is scaled by 2 to the power of this value. If the data is
 ///   stored as floats, this value is unused.
 ///
+/// Additionally, the acquisition date is read from the first line of the
+/// `acqus` file that starts with `$$` followed by a timestamp, and the
+/// description from the `title` file in the processing directory. The first
+/// non-empty line of the description is used as the sample name. These are
+/// optional and left unset if they are not present.
+///
 /// ## Raw Data
 ///
 /// The raw data is stored in the `1r` file in binary format. The metadata
-/// specifies how the data has to be read.
+/// specifies how the data has to be read. The imaginary part of the processed
+/// spectrum may additionally be stored in the `1i` file, using the same layout.
+/// It is only read by [`Bruker::read_spectrum_complex`]. The intensities are
+/// converted to `f64`, or to `f32` by [`Bruker::read_intensities_f32`] to halve
+/// their memory footprint.
 ///
 /// # Example: Reading a Spectrum
 ///
@@ -156,17 +172,28 @@ struct AcquisitionParameters {
     frequency: f64,
     /// Nucleus observed in the NMR experiment.
     nucleus: Nucleus,
+    /// Date and time of the acquisition, if present.
+    acquisition_date: Option<String>,
 }
 
 /// Regex patterns to search for the acquisition parameters.
 static ACQUS_RE: LazyLock<[Regex; 3]> = LazyLock::new(|| {
     [
-        Regex::new(r"(?m)^(##\$SW=\s*)(?P<width>\d+(\.\d+)?)").unwrap(),
-        Regex::new(r"(?m)^(##\$SFO1=\s*)(?P<frequency>\d+(\.\d+)?)").unwrap(),
+        number_regex(r"##\$SW=\s*", "width"),
+        number_regex(r"##\$SFO1=\s*", "frequency"),
         Regex::new(r"(?m)^(##\$NUC1=\s*<)(?P<nucleus>\w+)").unwrap(),
     ]
 });
 
+/// Regex pattern to search for the optional acquisition date in the `$$`
+/// comment lines.
+static ACQUS_DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^\$\$\s*(?P<date>\d{4}-\d{2}-\d{2}\s+\d{2}:\d{2}:\d{2}(\.\d+)?(\s+[+-]\d{4})?)",
+    )
+    .unwrap()
+});
+
 /// Keys used in the acquisition parameter regex patterns, used for error
 /// messages
 static ACQUS_KEYS: LazyLock<[&str; 3]> = LazyLock::new(|| ["SW", "SFO1", "NUC1"]);
@@ -189,11 +216,11 @@ struct ProcessingParameters {
 /// Regex patterns to search for the processing parameters.
 static PROCS_RE: LazyLock<[Regex; 5]> = LazyLock::new(|| {
     [
-        Regex::new(r"(?m)^(##\$OFFSET=\s*)(?P<maximum>\d+(\.\d+)?)").unwrap(),
-        Regex::new(r"(?m)^(##\$NC_proc=\s*)(?P<exponent>-?\d+)").unwrap(),
+        number_regex(r"##\$OFFSET=\s*", "maximum"),
+        number_regex(r"##\$NC_proc=\s*", "exponent"),
         Regex::new(r"(?m)^(##\$BYTORDP=\s*)(?P<endian>\d)").unwrap(),
         Regex::new(r"(?m)^(##\$DTYPP=\s*)(?P<data_type>\d)").unwrap(),
-        Regex::new(r"(?m)^(##\$SI=\s*)(?P<data_size>\d+)").unwrap(),
+        number_regex(r"##\$SI=\s*", "data_size"),
     ]
 });
 
@@ -263,29 +290,166 @@ impl Bruker {
         processing: u32,
         signal_boundaries: (f64, f64),
     ) -> Result<Spectrum> {
-        let acqus_path = path
-            .as_ref()
-            .join(format!("{}/acqus", experiment));
-        let procs_path = path
-            .as_ref()
-            .join(format!("{}/pdata/{}/procs", experiment, processing));
-        let one_r_path = path
-            .as_ref()
-            .join(format!("{}/pdata/{}/1r", experiment, processing));
-
-        let acqus = Self::read_acquisition_parameters(acqus_path)?;
-        let procs = Self::read_processing_parameters(procs_path)?;
-        let chemical_shifts = (0..procs.data_size)
-            .map(|i| procs.maximum - (i as f64) * acqus.width / (procs.data_size as f64 - 1.0))
-            .collect();
-        let intensities = Self::read_one_r(one_r_path, procs)?;
-        let mut spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
-        spectrum.set_nucleus(acqus.nucleus);
-        spectrum.set_frequency(acqus.frequency);
+        let (spectrum, _) =
+            Self::read_with_imaginary(path, experiment, processing, signal_boundaries, false)?;
 
         Ok(spectrum)
     }
 
+    /// Reads the spectrum from a Bruker TopSpin format directory, along with
+    /// the imaginary part of the processed data if it is present.
+    ///
+    /// ```text
+    /// name
+    /// └── name_01 ← the path needs to point to this directory
+    ///     └── experiment
+    ///         ├── pdata
+    ///         │   └── processing
+    ///         │       ├── 1i (optional)
+    ///         │       ├── 1r
+    ///         │       └── procs
+    ///         └── acqus
+    /// ```
+    ///
+    /// The imaginary intensities are read from the `1i` file with the same
+    /// endianness, data type and scaling as the real intensities, and are
+    /// stored in the same order. If the `1i` file doesn't exist, `None` is
+    /// returned in its place. This is useful for phase correction, where the
+    /// imaginary part is otherwise reconstructed from the real intensities.
+    ///
+    /// # Errors
+    ///
+    /// The same conditions as for [`Bruker::read_spectrum`] are checked.
+    /// Additionally, if the `1i` file exists but can't be read, the original
+    /// [`I/O`] error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/blood/blood_01";
+    ///
+    /// // Read a single spectrum with its imaginary part.
+    /// let (spectrum, imaginary) = Bruker::read_spectrum_complex(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # assert_eq!(imaginary.unwrap().len(), spectrum.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectrum_complex<P: AsRef<Path>>(
+        path: P,
+        experiment: u32,
+        processing: u32,
+        signal_boundaries: (f64, f64),
+    ) -> Result<(Spectrum, Option<Vec<f64>>)> {
+        Self::read_with_imaginary(path, experiment, processing, signal_boundaries, true)
+    }
+
+    /// Reads the spectrum from a Bruker TopSpin format directory, using the
+    /// first processing that can be read successfully.
+    ///
+    /// ```text
+    /// name
+    /// └── name_01 ← the path needs to point to this directory
+    ///     └── experiment
+    ///         ├── pdata
+    ///         │   ├── processing_1
+    ///         │   │   ├── 1r
+    ///         │   │   └── procs
+    ///         │   ├── processing_2
+    ///         │   ·
+    ///         │   ·
+    ///         │   ·
+    ///         └── acqus
+    /// ```
+    ///
+    /// All subdirectories of `pdata` whose names are integers are considered
+    /// processings. They are attempted in ascending order of their processing
+    /// numbers, and the first one that yields a valid `Spectrum` is returned.
+    /// This is useful if a dataset contains several processings, some of which
+    /// are incomplete or otherwise unreadable.
+    ///
+    /// # Errors
+    ///
+    /// If none of the processings can be read, a
+    /// [`NoReadableProcessing`](crate::spectrum::error::Kind::NoReadableProcessing)
+    /// error is returned, which contains the errors of the individual
+    /// processings. See [`Bruker::read_spectrum`] for the conditions that are
+    /// checked for each of them.
+    ///
+    /// Additionally, if the `pdata` directory can't be read, the original
+    /// [`I/O`] error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/blood/blood_01";
+    ///
+    /// // Read a single spectrum from the first readable processing.
+    /// let spectrum = Bruker::read_spectrum_any(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectrum_any<P: AsRef<Path>>(
+        path: P,
+        experiment: u32,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Spectrum> {
+        let pdata_path = path
+            .as_ref()
+            .join(format!("{}/pdata", experiment));
+        let mut processings = pdata_path
+            .read_dir()?
+            .filter_map(|entry| {
+                entry.ok().and_then(|entry| {
+                    if entry.path().is_dir() {
+                        entry.file_name().to_str()?.parse::<u32>().ok()
+                    } else {
+                        None
+                    }
+                })
+            })
+            .collect::<Vec<u32>>();
+        processings.sort_unstable();
+        let mut failures = Vec::with_capacity(processings.len());
+        for processing in processings {
+            match Self::read_spectrum(path.as_ref(), experiment, processing, signal_boundaries) {
+                Ok(spectrum) => return Ok(spectrum),
+                Err(error) => failures.push((processing, error.to_string())),
+            }
+        }
+
+        Err(Error::new(Kind::NoReadableProcessing {
+            path: pdata_path,
+            failures,
+        })
+        .into())
+    }
+
     /// Reads all spectra from the Bruker TopSpin format directories under the
     /// given path.
     ///
@@ -375,6 +539,152 @@ impl Bruker {
         Ok(spectra)
     }
 
+    /// Reads the real intensities of a spectrum from a Bruker TopSpin format
+    /// directory as `f32`, without the chemical shifts and metadata.
+    ///
+    /// ```text
+    /// name
+    /// └── name_01 ← the path needs to point to this directory
+    ///     └── experiment
+    ///         └── pdata
+    ///             └── processing
+    ///                 ├── 1r
+    ///                 └── procs
+    /// ```
+    ///
+    /// The intensities are scaled and ordered in the same way as those of the
+    /// [`Spectrum`] returned by [`Bruker::read_spectrum`], but are converted to
+    /// `f32` value by value, such that they take up half the memory. This is
+    /// useful for keeping large batches of spectra in memory, for example to
+    /// preprocess them with [`SmoothingSettings::smooth_values`]. The chemical
+    /// shifts are shared by spectra that were acquired with the same
+    /// parameters, so they can be taken from a single [`Spectrum`].
+    ///
+    /// [`SmoothingSettings::smooth_values`]: crate::deconvolution::SmoothingSettings::smooth_values
+    ///
+    /// # Precision
+    ///
+    /// `f32` has a 24 bit significand, compared to 53 bits for `f64`. Bruker
+    /// TopSpin stores the processed data as 32 bit integers or as `f64`
+    /// values, so intensities with a magnitude above `2^24` in integer units
+    /// are rounded to a relative error of at most `6e-8`, which is well below
+    /// the noise level of NMR spectra. The intensities have to be converted
+    /// back to `f64` to construct a [`Spectrum`] for the deconvolution.
+    ///
+    /// # Errors
+    ///
+    /// The following conditions are checked:
+    /// - All intensity values are finite after the conversion. `f64` values
+    ///   beyond the range of `f32` are not.
+    /// - All required key-value pairs are extracted from the `procs` file.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Bruker;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/bruker/blood/blood_01";
+    ///
+    /// // Read the intensities of a single spectrum with reduced precision.
+    /// let intensities = Bruker::read_intensities_f32(
+    ///     path,
+    ///     // Experiment number
+    ///     10,
+    ///     // Processing number
+    ///     10,
+    /// )?;
+    /// # assert_eq!(intensities.len(), 2_usize.pow(17));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_intensities_f32<P: AsRef<Path>>(
+        path: P,
+        experiment: u32,
+        processing: u32,
+    ) -> Result<Vec<f32>> {
+        let procs_path = path
+            .as_ref()
+            .join(format!("{}/pdata/{}/procs", experiment, processing));
+        let one_r_path = path
+            .as_ref()
+            .join(format!("{}/pdata/{}/1r", experiment, processing));
+
+        let procs = Self::read_processing_parameters(procs_path)?;
+        let intensities = Self::read_binary_data::<f32, _>(one_r_path, &procs)?;
+        let positions = intensities
+            .iter()
+            .enumerate()
+            .filter(|(_, intensity)| !intensity.is_finite())
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        if !positions.is_empty() {
+            return Err(Error::new(Kind::InvalidIntensities { positions }).into());
+        }
+
+        Ok(intensities)
+    }
+
+    /// Internal helper function to read the spectrum and, if requested and
+    /// present, the imaginary part of the processed data.
+    ///
+    /// # Errors
+    ///
+    /// See [`Bruker::read_spectrum`] for the possible errors.
+    fn read_with_imaginary<P: AsRef<Path>>(
+        path: P,
+        experiment: u32,
+        processing: u32,
+        signal_boundaries: (f64, f64),
+        imaginary: bool,
+    ) -> Result<(Spectrum, Option<Vec<f64>>)> {
+        let acqus_path = path
+            .as_ref()
+            .join(format!("{}/acqus", experiment));
+        let procs_path = path
+            .as_ref()
+            .join(format!("{}/pdata/{}/procs", experiment, processing));
+        let one_r_path = path
+            .as_ref()
+            .join(format!("{}/pdata/{}/1r", experiment, processing));
+        let one_i_path = path
+            .as_ref()
+            .join(format!("{}/pdata/{}/1i", experiment, processing));
+        let title_path = path
+            .as_ref()
+            .join(format!("{}/pdata/{}/title", experiment, processing));
+
+        let acqus = Self::read_acquisition_parameters(acqus_path)?;
+        let procs = Self::read_processing_parameters(procs_path)?;
+        let chemical_shifts = (0..procs.data_size)
+            .map(|i| procs.maximum - (i as f64) * acqus.width / (procs.data_size as f64 - 1.0))
+            .collect();
+        let intensities = Self::read_binary_data(one_r_path, &procs)?;
+        let imaginary = match imaginary && one_i_path.is_file() {
+            true => Some(Self::read_binary_data(one_i_path, &procs)?),
+            false => None,
+        };
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
+        spectrum.set_nucleus(acqus.nucleus);
+        spectrum.set_frequency(acqus.frequency);
+        spectrum.set_acquisition_date(acqus.acquisition_date.as_deref());
+        let title = Self::read_title(title_path)?;
+        let sample_name = title
+            .as_deref()
+            .and_then(|title| title.lines().next())
+            .map(str::trim);
+        spectrum.set_sample_name(sample_name);
+        spectrum.set_description(title.as_deref());
+
+        Ok((spectrum, imaginary))
+    }
+
     /// Internal helper function to read the acquisition parameters from the
     /// `acqus` file and return them.
     ///
@@ -385,21 +695,47 @@ impl Bruker {
     /// - [`MalformedMetaData`](crate::spectrum::error::Kind::MalformedMetadata)
     /// - [`Error::IoError`](crate::Error::IoError)
     fn read_acquisition_parameters<P: AsRef<Path>>(path: P) -> Result<AcquisitionParameters> {
-        let acqus = read_to_string(path.as_ref())?;
+        let acqus = read_text(path.as_ref())?;
         let re = &*ACQUS_RE;
         let keys = &*ACQUS_KEYS;
 
         let width = extract_capture(&re[0], "width", &acqus, &path, keys[0])?;
         let frequency = extract_capture(&re[1], "frequency", &acqus, &path, keys[1])?;
         let nucleus = extract_capture(&re[2], "nucleus", &acqus, &path, keys[2])?;
+        let acquisition_date = ACQUS_DATE_RE
+            .captures(&acqus)
+            .map(|captures| captures["date"].to_string());
 
         Ok(AcquisitionParameters {
             width,
             frequency,
             nucleus,
+            acquisition_date,
         })
     }
 
+    /// Internal helper function to read the contents of the `title` file with
+    /// surrounding whitespace removed. Returns `None` if the file doesn't exist
+    /// or is blank. Invalid UTF-8 sequences are replaced, as the title is free
+    /// text entered in TopSpin.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`Error::IoError`](crate::Error::IoError)
+    fn read_title<P: AsRef<Path>>(path: P) -> Result<Option<String>> {
+        if !path.as_ref().is_file() {
+            return Ok(None);
+        }
+        let title = std::fs::read(path.as_ref())?;
+
+        let title = String::from_utf8_lossy(&title);
+
+        Ok(Some(title.trim())
+            .filter(|title| !title.is_empty())
+            .map(str::to_string))
+    }
+
     /// Internal helper function to read the processing parameters from the
     /// `procs` file and return them.
     ///
@@ -410,7 +746,7 @@ impl Bruker {
     /// - [`MalformedMetaData`](crate::spectrum::error::Kind::MalformedMetadata)
     /// - [`Error::IoError`](crate::Error::IoError)
     fn read_processing_parameters<P: AsRef<Path>>(path: P) -> Result<ProcessingParameters> {
-        let procs = read_to_string(path.as_ref())?;
+        let procs = read_text(path.as_ref())?;
         let re = &*PROCS_RE;
         let keys = &*PROCS_KEYS;
 
@@ -435,17 +771,20 @@ impl Bruker {
         })
     }
 
-    /// Internal helper function to read the raw data from the `1r` file and
-    /// return it as a vector of floating point numbers. As working with
-    /// chemical shifts in increasing order is generally simpler, the vector is
-    /// reversed before being returned.
+    /// Internal helper function to read the raw data from the `1r` or `1i`
+    /// file and return it as a vector of floating point numbers.
     ///
     /// # Errors
     ///
     /// The following errors are possible:
     /// - [`Error::IoError`](crate::Error::IoError)
-    fn read_one_r<P: AsRef<Path>>(path: P, procs: ProcessingParameters) -> Result<Vec<f64>> {
-        let mut one_r = File::open(path.as_ref())?;
+    fn read_binary_data<T, P>(path: P, procs: &ProcessingParameters) -> Result<Vec<T>>
+    where
+        T: Copy + 'static,
+        f64: AsPrimitive<T>,
+        P: AsRef<Path>,
+    {
+        let mut file = File::open(path.as_ref())?;
         let mut buffer = vec![
             0;
             procs.data_size
@@ -454,7 +793,7 @@ impl Bruker {
                     Type::F64 => 8,
                 }
         ];
-        one_r.read_exact(&mut buffer)?;
+        file.read_exact(&mut buffer)?;
 
         match procs.data_type {
             Type::I32 => {
@@ -470,8 +809,8 @@ impl Bruker {
 
                 Ok(temp
                     .into_iter()
-                    .map(|value| (value as f64) * 2_f64.powi(procs.exponent))
-                    .collect::<Vec<f64>>())
+                    .map(|value| ((value as f64) * 2_f64.powi(procs.exponent)).as_())
+                    .collect::<Vec<T>>())
             }
             Type::F64 => {
                 let mut temp = vec![0_f64; procs.data_size];
@@ -484,7 +823,10 @@ impl Bruker {
                         .read_f64_into::<BigEndian>(&mut temp)?,
                 }
 
-                Ok(temp)
+                Ok(temp
+                    .into_iter()
+                    .map(|value| value.as_())
+                    .collect::<Vec<T>>())
             }
         }
     }
@@ -506,6 +848,33 @@ mod tests {
         check_blood_spectrum!(blood);
     }
 
+    #[test]
+    fn read_intensities_f32() {
+        [
+            ("../data/bruker/sim/sim_01", (3.34, 3.56)),
+            ("../data/bruker/blood/blood_01", (-2.2, 11.8)),
+        ]
+        .into_iter()
+        .for_each(|(path, signal_boundaries)| {
+            let spectrum = Bruker::read_spectrum(path, 10, 10, signal_boundaries).unwrap();
+            let intensities = Bruker::read_intensities_f32(path, 10, 10).unwrap();
+            assert_eq!(intensities.len(), spectrum.len());
+            spectrum
+                .intensities()
+                .iter()
+                .zip(intensities.iter())
+                .for_each(|(expected, intensity)| {
+                    assert_approx_eq!(
+                        f64,
+                        *intensity as f64,
+                        *expected,
+                        epsilon = 1e-7 * expected.abs()
+                    );
+                });
+        });
+        assert!(Bruker::read_intensities_f32("../data/bruker/nonexistent", 10, 10).is_err());
+    }
+
     #[test]
     fn read_spectra() {
         let sim_path = "../data/bruker/sim";
@@ -522,6 +891,90 @@ mod tests {
         });
     }
 
+    #[test]
+    fn read_spectrum_complex() {
+        let blood_path = "../data/bruker/blood/blood_01";
+        let sim_path = "../data/bruker/sim/sim_01";
+        let (blood, imaginary) =
+            Bruker::read_spectrum_complex(blood_path, 10, 10, (-2.2, 11.8)).unwrap();
+        let imaginary = imaginary.unwrap();
+        check_blood_spectrum!(blood);
+        assert_eq!(imaginary.len(), blood.len());
+        assert!(imaginary.iter().all(|value| value.is_finite()));
+        assert!(imaginary.iter().any(|value| *value != 0.0));
+        assert_ne!(imaginary.as_slice(), blood.intensities());
+        let (sim, imaginary) =
+            Bruker::read_spectrum_complex(sim_path, 10, 10, (3.34, 3.56)).unwrap();
+        check_sim_spectrum!(sim);
+        assert!(imaginary.is_none());
+    }
+
+    #[test]
+    fn read_spectrum_any() {
+        let path = "../data/bruker/processings/sim_01";
+        let reference_path = "../data/bruker/sim/sim_01";
+        let spectrum = Bruker::read_spectrum_any(path, 10, (3.34, 3.56)).unwrap();
+        let reference = Bruker::read_spectrum(reference_path, 10, 10, (3.34, 3.56)).unwrap();
+        check_sim_spectrum!(spectrum);
+        assert_eq!(spectrum.intensities(), reference.intensities());
+        let path = "../data/bruker/processings/incomplete";
+        let error = Bruker::read_spectrum_any(path, 10, (3.34, 3.56)).unwrap_err();
+        match error {
+            crate::Error::Spectrum(inner) => match inner.kind() {
+                Kind::NoReadableProcessing { failures, .. } => {
+                    assert_eq!(
+                        failures
+                            .iter()
+                            .map(|(processing, _)| *processing)
+                            .collect::<Vec<u32>>(),
+                        vec![1, 2]
+                    );
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+        assert!(Bruker::read_spectrum_any(path, 20, (3.34, 3.56)).is_err());
+    }
+
+    #[test]
+    fn read_nonexistent() {
+        let path = "../data/bruker/nonexistent";
+        let error = Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap_err();
+        let source = std::error::Error::source(&error).unwrap();
+        match source.downcast_ref::<std::io::Error>() {
+            Some(io_error) => assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound),
+            None => panic!("unexpected source: {:?}", source),
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_gzip_spectrum() {
+        let path = "../data/bruker/gzip/sim_01";
+        let reference_path = "../data/bruker/sim/sim_01";
+        let spectrum = Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let reference = Bruker::read_spectrum(reference_path, 10, 10, (3.34, 3.56)).unwrap();
+        check_sim_spectrum!(spectrum);
+        assert_eq!(spectrum.chemical_shifts(), reference.chemical_shifts());
+        assert_eq!(spectrum.intensities(), reference.intensities());
+    }
+
+    #[test]
+    fn read_scientific_notation() {
+        let path = "../data/bruker/scientific/sim_01";
+        let reference_path = "../data/bruker/sim/sim_01";
+        let spectrum = Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let reference = Bruker::read_spectrum(reference_path, 10, 10, (3.34, 3.56)).unwrap();
+        check_sim_spectrum!(spectrum);
+        spectrum
+            .chemical_shifts()
+            .iter()
+            .zip(reference.chemical_shifts())
+            .for_each(|(x, reference)| assert_approx_eq!(f64, *x, *reference));
+        assert_eq!(spectrum.intensities(), reference.intensities());
+    }
+
     #[test]
     fn read_acquisition_parameters() {
         let path = "../data/bruker/blood/blood_01/10/acqus";
@@ -529,6 +982,27 @@ mod tests {
         assert_approx_eq!(f64, acqus.width, 20.0236139622347);
         assert_approx_eq!(f64, acqus.frequency, 600.252821089118);
         assert_eq!(acqus.nucleus, Nucleus::Hydrogen1);
+        assert_eq!(
+            acqus.acquisition_date.as_deref(),
+            Some("2021-01-22 18:04:32.025 +0100")
+        );
+    }
+
+    #[test]
+    fn read_metadata() {
+        let blood_path = "../data/bruker/blood/blood_01";
+        let sim_path = "../data/bruker/sim/sim_01";
+        let blood = Bruker::read_spectrum(blood_path, 10, 10, (-2.2, 11.8)).unwrap();
+        assert_eq!(blood.sample_name(), Some("Blood_1"));
+        assert_eq!(blood.description(), Some("Blood_1"));
+        assert_eq!(
+            blood.acquisition_date(),
+            Some("2021-01-22 18:04:32.025 +0100")
+        );
+        let sim = Bruker::read_spectrum(sim_path, 10, 10, (3.34, 3.56)).unwrap();
+        assert_eq!(sim.sample_name(), None);
+        assert_eq!(sim.acquisition_date(), None);
+        assert_eq!(sim.description(), None);
     }
 
     #[test]
@@ -0,0 +1,490 @@
+use crate::Result;
+use crate::spectrum::Spectrum;
+use crate::spectrum::error::{Error, Kind};
+use crate::spectrum::formats::{read_text, text_extension};
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// Interface for reading 1D NMR spectra from delimited text files, such as CSV
+/// or TSV.
+///
+/// Many programs can export spectra as a simple table of chemical shift and
+/// intensity pairs. Each non-empty line of the file is a row of the table, and
+/// the values within a row are separated by a delimiter. The columns that hold
+/// the chemical shifts (in ppm) and the intensities, the delimiter, and whether
+/// the first row is a header can be configured with [`CsvOptions`].
+///
+/// Since such files contain no metadata, the nucleus, spectrometer frequency,
+/// and reference compound of the resulting [`Spectrum`] are set to their
+/// defaults.
+///
+/// # Example: Reading a Spectrum
+///
+/// ```
+/// use metabodecon::spectrum::{Csv, CsvOptions};
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/spectrum.csv";
+/// # let path = "../data/csv/test/comma.csv";
+///
+/// // Read a spectrum from a CSV file with a header row.
+/// let mut options = CsvOptions::default();
+/// options.set_header(true);
+/// let spectrum = Csv::read_spectrum(
+///     path,
+///     // Signal boundaries
+///     (4.1, 4.9),
+///     options,
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Example: Reading Multiple Spectra
+///
+/// ```
+/// use metabodecon::spectrum::{Csv, CsvOptions};
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/root";
+/// # let dir = std::env::temp_dir().join("metabodecon_csv_read_spectra_doc");
+/// # std::fs::create_dir_all(&dir)?;
+/// # std::fs::copy("../data/csv/test/comma.csv", dir.join("spectrum.csv"))?;
+/// # let path = &dir;
+///
+/// // Read all spectra from a directory of CSV files with a header row.
+/// let mut options = CsvOptions::default();
+/// options.set_header(true);
+/// let spectra = Csv::read_spectra(
+///     path,
+///     // Signal boundaries
+///     (4.1, 4.9),
+///     options,
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum Csv {}
+
+/// Options for reading spectra from delimited text files with [`Csv`].
+///
+/// By default, values are separated by commas, there is no header row, and the
+/// chemical shifts and intensities are in the first and second column.
+///
+/// # Example
+///
+/// ```
+/// use metabodecon::spectrum::CsvOptions;
+///
+/// // Tab separated file with a header row, where the intensities are in the
+/// // second and the chemical shifts in the third column.
+/// let options = CsvOptions::new('\t', true, 2, 1);
+///
+/// assert_eq!(options.delimiter(), '\t');
+/// assert!(options.header());
+/// assert_eq!(options.chemical_shifts_column(), 2);
+/// assert_eq!(options.intensities_column(), 1);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Character separating the values within a row.
+    delimiter: char,
+    /// Whether the first non-empty line is a header row.
+    header: bool,
+    /// Zero-based index of the column holding the chemical shifts.
+    chemical_shifts_column: usize,
+    /// Zero-based index of the column holding the intensities.
+    intensities_column: usize,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self::new(',', false, 0, 1)
+    }
+}
+
+impl CsvOptions {
+    /// Constructs new `CsvOptions` from the delimiter, whether a header row is
+    /// present, and the zero-based column indices of the chemical shifts and
+    /// intensities.
+    pub fn new(
+        delimiter: char,
+        header: bool,
+        chemical_shifts_column: usize,
+        intensities_column: usize,
+    ) -> Self {
+        Self {
+            delimiter,
+            header,
+            chemical_shifts_column,
+            intensities_column,
+        }
+    }
+
+    /// Returns the character separating the values within a row.
+    pub fn delimiter(&self) -> char {
+        self.delimiter
+    }
+
+    /// Returns whether the first non-empty line is a header row.
+    pub fn header(&self) -> bool {
+        self.header
+    }
+
+    /// Returns the zero-based index of the column holding the chemical shifts.
+    pub fn chemical_shifts_column(&self) -> usize {
+        self.chemical_shifts_column
+    }
+
+    /// Returns the zero-based index of the column holding the intensities.
+    pub fn intensities_column(&self) -> usize {
+        self.intensities_column
+    }
+
+    /// Sets the character separating the values within a row.
+    pub fn set_delimiter(&mut self, delimiter: char) {
+        self.delimiter = delimiter;
+    }
+
+    /// Sets whether the first non-empty line is a header row.
+    pub fn set_header(&mut self, header: bool) {
+        self.header = header;
+    }
+
+    /// Sets the zero-based index of the column holding the chemical shifts.
+    pub fn set_chemical_shifts_column(&mut self, column: usize) {
+        self.chemical_shifts_column = column;
+    }
+
+    /// Sets the zero-based index of the column holding the intensities.
+    pub fn set_intensities_column(&mut self, column: usize) {
+        self.intensities_column = column;
+    }
+}
+
+impl Csv {
+    /// Reads the spectrum from a delimited text file.
+    ///
+    /// # Errors
+    ///
+    /// The read data is checked for validity to ensure that the `Spectrum` is
+    /// well-formed and in a consistent state. The following conditions are
+    /// checked:
+    /// - Every non-empty row (except the header) contains the configured
+    ///   columns, and their values can be parsed as numbers. Otherwise, a
+    ///   [`MalformedLine`](Kind::MalformedLine) error with the line number is
+    ///   returned.
+    /// - The Intensities are not empty.
+    /// - The chemical shifts are uniformly spaced.
+    /// - All intensity values are finite.
+    /// - The signal region boundaries are within the range of the chemical
+    ///   shifts.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::{Csv, CsvOptions};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum.tsv";
+    /// # let path = "../data/csv/test/tab.tsv";
+    ///
+    /// // Read a spectrum from a TSV file with a header row, where the
+    /// // intensities are in the second and the chemical shifts in the third
+    /// // column.
+    /// let spectrum = Csv::read_spectrum(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (4.1, 4.9),
+    ///     CsvOptions::new('\t', true, 2, 1),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectrum<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+        options: CsvOptions,
+    ) -> Result<Spectrum> {
+        let path = path.as_ref();
+        let csv = read_text(path)?;
+        let (chemical_shifts, intensities) = Self::parse_rows(&csv, path, options)?;
+
+        Spectrum::new(chemical_shifts, intensities, signal_boundaries)
+    }
+
+    /// Reads all spectra from a directory of delimited text files.
+    ///
+    /// Skips any files that do not have the `.csv` or `.tsv` extension. If the
+    /// `gzip` feature is enabled, compressed files with an additional `.gz`
+    /// extension are read as well.
+    ///
+    /// # Errors
+    ///
+    /// The read data is checked for validity to ensure that the `Spectrum` is
+    /// well-formed and in a consistent state. The following conditions are
+    /// checked:
+    /// - Every non-empty row (except the header) contains the configured
+    ///   columns, and their values can be parsed as numbers. Otherwise, a
+    ///   [`MalformedLine`](Kind::MalformedLine) error with the line number is
+    ///   returned.
+    /// - The Intensities are not empty.
+    /// - The chemical shifts are uniformly spaced.
+    /// - All intensity values are finite.
+    /// - The signal region boundaries are within the range of the chemical
+    ///   shifts.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::{Csv, CsvOptions};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/root";
+    /// # let dir = std::env::temp_dir().join("metabodecon_csv_read_spectra_method_doc");
+    /// # std::fs::create_dir_all(&dir)?;
+    /// # std::fs::copy("../data/csv/test/comma.csv", dir.join("spectrum_1.csv"))?;
+    /// # std::fs::copy("../data/csv/test/comma.csv", dir.join("spectrum_2.csv"))?;
+    /// # let path = &dir;
+    ///
+    /// // Read all spectra from a directory of CSV files with a header row.
+    /// let spectra = Csv::read_spectra(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (4.1, 4.9),
+    ///     CsvOptions::new(',', true, 0, 1),
+    /// )?;
+    /// assert_eq!(spectra.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectra<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+        options: CsvOptions,
+    ) -> Result<Vec<Spectrum>> {
+        let spectra = path
+            .as_ref()
+            .read_dir()?
+            .filter_map(|entry| {
+                entry.ok().and_then(|entry| {
+                    text_extension(&entry.path())
+                        .and_then(OsStr::to_str)
+                        .and_then(|extension| match extension.to_lowercase().as_str() {
+                            "csv" | "tsv" => Some(entry.path()),
+                            _ => None,
+                        })
+                })
+            })
+            .map(|entry| Self::read_spectrum(entry, signal_boundaries, options))
+            .collect::<Result<Vec<Spectrum>>>()?;
+
+        Ok(spectra)
+    }
+
+    /// Internal helper function to parse the chemical shifts and intensities
+    /// from the rows of the file.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedLine`](Kind::MalformedLine)
+    fn parse_rows(csv: &str, path: &Path, options: CsvOptions) -> Result<(Vec<f64>, Vec<f64>)> {
+        let skip = match options.header {
+            true => 1,
+            false => 0,
+        };
+
+        csv.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .skip(skip)
+            .map(|(index, line)| {
+                let values = line
+                    .split(options.delimiter)
+                    .map(str::trim)
+                    .collect::<Vec<_>>();
+                let chemical_shift = Self::parse_value(
+                    &values,
+                    options.chemical_shifts_column,
+                    "chemical shift",
+                    path,
+                    index + 1,
+                )?;
+                let intensity = Self::parse_value(
+                    &values,
+                    options.intensities_column,
+                    "intensity",
+                    path,
+                    index + 1,
+                )?;
+
+                Ok((chemical_shift, intensity))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(|rows| rows.into_iter().unzip())
+    }
+
+    /// Internal helper function to parse a single value of a row.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedLine`](Kind::MalformedLine)
+    fn parse_value(
+        values: &[&str],
+        column: usize,
+        name: &str,
+        path: &Path,
+        line: usize,
+    ) -> Result<f64> {
+        let value = values.get(column).ok_or_else(|| {
+            Error::new(Kind::MalformedLine {
+                path: path.to_path_buf(),
+                line,
+                details: format!(
+                    "expected {} in column {}, but found only {} columns",
+                    name,
+                    column,
+                    values.len()
+                ),
+            })
+        })?;
+
+        value.parse::<f64>().map_err(|error| {
+            Error::new(Kind::MalformedLine {
+                path: path.to_path_buf(),
+                line,
+                details: format!("could not parse {} \"{}\" ({})", name, value, error),
+            })
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn read_spectrum() {
+        let spectra = [
+            Csv::read_spectrum(
+                "../data/csv/test/comma.csv",
+                (4.1, 4.9),
+                CsvOptions::new(',', true, 0, 1),
+            )
+            .unwrap(),
+            Csv::read_spectrum(
+                "../data/csv/test/tab.tsv",
+                (4.1, 4.9),
+                CsvOptions::new('\t', true, 2, 1),
+            )
+            .unwrap(),
+            Csv::read_spectrum(
+                "../data/csv/test/semicolon_no_header.txt",
+                (4.1, 4.9),
+                CsvOptions::new(';', false, 0, 1),
+            )
+            .unwrap(),
+        ];
+        spectra.iter().for_each(|spectrum| {
+            assert_eq!(spectrum.len(), 201);
+            assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 4.0);
+            assert_approx_eq!(f64, spectrum.chemical_shifts()[200], 5.0);
+            assert_approx_eq!(f64, spectrum.step(), 0.005, epsilon = 1e-12);
+            assert_approx_eq!(f64, spectrum.intensities()[0], 12.822443);
+            assert_approx_eq!(f64, spectrum.intensities()[100], 2024.752475);
+            assert_approx_eq!(f64, spectrum.signal_boundaries().0, 4.1);
+            assert_approx_eq!(f64, spectrum.signal_boundaries().1, 4.9);
+        });
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_gzip_spectrum() {
+        let options = CsvOptions::new(',', true, 0, 1);
+        let spectrum =
+            Csv::read_spectrum("../data/csv/gzip/comma.csv.gz", (4.1, 4.9), options).unwrap();
+        let reference =
+            Csv::read_spectrum("../data/csv/test/comma.csv", (4.1, 4.9), options).unwrap();
+        assert_eq!(spectrum.chemical_shifts(), reference.chemical_shifts());
+        assert_eq!(spectrum.intensities(), reference.intensities());
+        let spectra = Csv::read_spectra("../data/csv/gzip", (4.1, 4.9), options).unwrap();
+        assert_eq!(spectra.len(), 1);
+    }
+
+    #[test]
+    fn read_spectra() {
+        let directory = std::env::temp_dir().join("metabodecon_csv_read_spectra_test");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::copy("../data/csv/test/comma.csv", directory.join("comma.csv")).unwrap();
+        std::fs::copy("../data/csv/test/comma.csv", directory.join("comma.CSV")).unwrap();
+        std::fs::write(directory.join("ignored.txt"), "not a spectrum").unwrap();
+        let spectra =
+            Csv::read_spectra(&directory, (4.1, 4.9), CsvOptions::new(',', true, 0, 1)).unwrap();
+        assert_eq!(spectra.len(), 2);
+        spectra
+            .iter()
+            .for_each(|spectrum| assert_eq!(spectrum.len(), 201));
+    }
+
+    #[test]
+    fn malformed_line() {
+        let error = Csv::read_spectrum(
+            "../data/csv/test/malformed.csv",
+            (4.1, 4.9),
+            CsvOptions::new(',', true, 0, 1),
+        )
+        .unwrap_err();
+        match error {
+            crate::Error::Spectrum(error) => match error.kind() {
+                Kind::MalformedLine { line, .. } => assert_eq!(*line, 43),
+                _ => panic!("unexpected kind: {:?}", error),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+        let error = Csv::read_spectrum(
+            "../data/csv/test/comma.csv",
+            (4.1, 4.9),
+            CsvOptions::new(',', true, 0, 2),
+        )
+        .unwrap_err();
+        match error {
+            crate::Error::Spectrum(error) => match error.kind() {
+                Kind::MalformedLine { line, .. } => assert_eq!(*line, 2),
+                _ => panic!("unexpected kind: {:?}", error),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn wrong_delimiter() {
+        let error = Csv::read_spectrum(
+            "../data/csv/test/tab.tsv",
+            (4.1, 4.9),
+            CsvOptions::new(',', true, 0, 1),
+        )
+        .unwrap_err();
+        match error {
+            crate::Error::Spectrum(error) => match error.kind() {
+                Kind::MalformedLine { line, .. } => assert_eq!(*line, 2),
+                _ => panic!("unexpected kind: {:?}", error),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+}
@@ -3,6 +3,22 @@ use crate::spectrum::error::{Error, Kind};
 use regex::Regex;
 use std::path::Path;
 
+/// Regex pattern matching a decimal number with an optional sign, fractional
+/// part, and exponent, e.g. `600`, `-0.5`, `.5`, or `6.00E+02`.
+const NUMBER_PATTERN: &str = r"[-+]?(\d+(\.\d*)?|\.\d+)([eE][-+]?\d+)?";
+
+/// Internal helper function to build a multi-line regex that matches a line
+/// starting with the given `prefix` pattern, followed by a number that is
+/// captured under the given `name`. The number may be signed and written in
+/// scientific notation.
+pub(crate) fn number_regex(prefix: &str, name: &str) -> Regex {
+    Regex::new(&format!(
+        r"(?m)^({})(?P<{}>{})",
+        prefix, name, NUMBER_PATTERN
+    ))
+    .unwrap()
+}
+
 /// Internal helper function to extract a single capture group from a regex
 /// match and parse it into a desired type.
 pub(crate) fn extract_capture<T, P>(
@@ -36,9 +52,8 @@ where
         .ok_or_else(missing_error)?
         .name(name)
         .ok_or_else(missing_error)?
-        .as_str()
-        .parse::<T>()
-        .map_err(malformed_error)?;
+        .as_str();
+    let result = parse_value::<T>(result).map_err(malformed_error)?;
 
     Ok(result)
 }
@@ -69,13 +84,62 @@ where
     let raw = extract_capture::<String, _>(regex, name, text, &path, key)?;
     let row = raw
         .split(",")
-        .map(|value| {
-            value
-                .trim()
-                .parse::<T>()
-                .map_err(|error| malformed_error(error).into())
-        })
+        .map(|value| parse_value::<T>(value.trim()).map_err(|error| malformed_error(error).into()))
         .collect::<Result<Vec<T>>>()?;
 
     Ok(row)
 }
+
+/// Internal helper function to parse a value into the desired type. Integers
+/// are sometimes written in scientific notation or with a fractional part,
+/// e.g. `3.2768E+04` or `1024.0`, which the integer parsers reject. If parsing
+/// fails, the value is therefore parsed as `f64`, and if it is integral, its
+/// integer representation is parsed instead. The original error is returned
+/// otherwise.
+fn parse_value<T>(value: &str) -> std::result::Result<T, <T as std::str::FromStr>::Err>
+where
+    T: std::str::FromStr,
+{
+    value
+        .parse::<T>()
+        .or_else(|error| match value.parse::<f64>() {
+            Ok(float) if float.is_finite() && float.fract() == 0.0 => {
+                format!("{:.0}", float).parse::<T>()
+            }
+            _ => Err(error),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn number_pattern() {
+        let re = Regex::new(&format!(r"^(?P<number>{})$", NUMBER_PATTERN)).unwrap();
+        ["600", "-0.5", "+12.", ".5", "6.00E+02", "1e-3", "-2.5E2"]
+            .into_iter()
+            .for_each(|number| assert!(re.is_match(number), "{}", number));
+        ["", "E+02", "1.0E", "--1", "1,0"]
+            .into_iter()
+            .for_each(|number| assert!(!re.is_match(number), "{}", number));
+    }
+
+    #[test]
+    fn scientific_notation() {
+        let re = number_regex(r"##VALUE=\s*", "value");
+        let value = |text: &str| extract_capture::<f64, _>(&re, "value", text, "test", "VALUE");
+        assert_approx_eq!(f64, value("##VALUE= 6.00E+02").unwrap(), 600.0);
+        assert_approx_eq!(f64, value("##VALUE= -1.5e-1").unwrap(), -0.15);
+        let size = |text: &str| extract_capture::<usize, _>(&re, "value", text, "test", "VALUE");
+        assert_eq!(size("##VALUE= 3.2768E+04").unwrap(), 32768);
+        assert_eq!(size("##VALUE= 1024.0").unwrap(), 1024);
+        assert!(size("##VALUE= 1.5E+00").is_err());
+        assert!(size("##VALUE= -1.0E+01").is_err());
+        let row_re = Regex::new(r"(?m)^(##ROW=\s*)(?P<row>.*)").unwrap();
+        let row = extract_row::<usize, _>(&row_re, "row", "##ROW= 1.6384E4, 16384", "test", "ROW")
+            .unwrap();
+        assert_eq!(row, vec![16384, 16384]);
+    }
+}
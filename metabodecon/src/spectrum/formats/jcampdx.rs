@@ -1,21 +1,24 @@
 use crate::Result;
 use crate::spectrum::Spectrum;
 use crate::spectrum::error::{Error, Kind};
-use crate::spectrum::formats::{extract_capture, extract_row};
+use crate::spectrum::formats::{
+    extract_capture, extract_row, number_regex, read_text, text_extension,
+};
 use crate::spectrum::meta::{Nucleus, ReferenceCompound};
 use regex::{Captures, Regex};
 use std::ffi::OsStr;
-use std::fs::read_to_string;
+use std::fs::write;
 use std::path::Path;
 use std::sync::LazyLock;
 
-/// Interface for reading 1D NMR spectra in JCAMP-DX format.
+/// Interface for reading and writing 1D NMR spectra in JCAMP-DX format.
 ///
 /// The JCAMP-DX format is a text-based format for storing 1D NMR spectra. Both
 /// the metadata and the data are stored in the same file, which can be divided
 /// into the header and the data section. Since the format allows for a lot of
 /// optionally included information, this implementation is restricted to the
-/// minimally required sets to construct a 1D NMR spectrum from.
+/// minimally required sets to construct a 1D NMR spectrum from. If the `gzip`
+/// feature is enabled, gzip-compressed files are decompressed transparently.
 ///
 /// # Supported Versions and Formats
 ///
@@ -27,9 +30,10 @@ use std::sync::LazyLock;
 /// the version to 5.00 or 6.00, as long as the relevant metadata is present.
 ///
 /// NMR data can be stored in a few different ways. The `NMR Spectrum` variant
-/// is currently supported for the XYDATA and NTUPLES format. `NMR FID` may be
-/// supported in the future. `NMR PEAK TABLE` and `NMR PEAK ASSIGNMENT` will
-/// never be supported.
+/// is currently supported for the XYDATA and NTUPLES format. The `NMR FID`
+/// variant is supported for the NTUPLES format, but requires the `fft` feature
+/// to transform the time-domain data into a spectrum. `NMR PEAK TABLE` and
+/// `NMR PEAK ASSIGNMENT` will never be supported.
 ///
 /// # Header Metadata
 ///
@@ -83,12 +87,32 @@ use std::sync::LazyLock;
 /// | `LAST`    | Last value (only X).                                        |
 /// | `FACTOR`  | Scale factor of the values (only Y/R).                      |
 ///
+/// ## FID
+///
+/// FIDs are stored in the NTuples format, with the x values given in seconds
+/// and both the real (R) and imaginary (I) parts of the time-domain data in
+/// separate data tables. The `FACTOR` of the I column is required in addition
+/// to the NTuples metadata above. The carrier frequency offset is taken from
+/// `$O1` (Hz) if present, otherwise the spectrum is centered at 0 ppm. A
+/// `.SHIFT REFERENCE` takes precedence over both.
+///
+/// The FID is Fourier transformed and a zero-order phase correction is applied
+/// that maximizes the integral of the real part of the spectrum. No apodization
+/// or first-order phase correction is performed.
+///
 /// # Data Integrity Checks
 ///
 /// Officially, the JCAMP-DX format specifies some integrity checks that should
-/// be performed when parsing the files. These will not be performed by this
-/// implementation, as they often aren't adhered to due to how much freedom
-/// JCAMP-DX allows when writing the files.
+/// be performed when parsing the files. These are not performed by default, as
+/// they often aren't adhered to due to how much freedom JCAMP-DX allows when
+/// writing the files.
+///
+/// [`read_spectrum_strict`] can be used to enforce that, if a line ends with a
+/// DIF encoded value, the first y value of the next line repeats the last y
+/// value of that line (see [DIF](#dif)). The number of decoded values is always
+/// checked against the number of points specified in the metadata.
+///
+/// [`read_spectrum_strict`]: JcampDx::read_spectrum_strict
 ///
 /// # Compression
 ///
@@ -259,30 +283,28 @@ use std::sync::LazyLock;
 /// ### DIFDUP
 ///
 /// DIF and DUP are often combined for full compression. The DIF compression
-/// is performed first, and any occurrences of % are replaced by the respective
-/// DUP characters.
+/// is performed first, and repeated differences are replaced by the respective
+/// DUP characters. Note that a DUP following a DIF encoded value repeats the
+/// difference, not the y value.
 ///
 /// #### Example
 ///
 /// Uncompressed:
 ///
 /// ```text
-/// 9     23    -73     92    -12     77
-/// 4     81     19     21     21    -68
+/// 0     10     20     30     40     40     40
 /// ```
 ///
 /// Compressed (DIF):
 ///
 /// ```text
-/// 9 B3r6J65j04Q9
-/// 5 G7Mo2K%q9
+/// 0 A0J0J0J0%%
 /// ```
 ///
 /// Compressed (DUP):
 ///
 /// ```text
-/// 9 B3r6J65j04Q9
-/// 5 G7Mo2KTq9
+/// 0 A0J0U%T
 /// ```
 ///
 /// # Example: Reading a Spectrum
@@ -322,17 +344,64 @@ use std::sync::LazyLock;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Writing Spectra
+///
+/// Spectra can be written as version 5.01 files in the XYDATA format, with the
+/// x values in Hz. The intensities are scaled to integers using the `YFACTOR`,
+/// such that the largest absolute intensity is stored with 10 significant
+/// digits. The y values are either stored uncompressed as AFFN or compressed
+/// as DIFDUP, see [`JcampDxCompression`]. In the latter case, DUP is only
+/// applied to repeated `%` (i.e. runs of identical intensities).
+///
+/// # Example: Writing a Spectrum
+///
+/// ```
+/// use metabodecon::spectrum::{JcampDx, JcampDxCompression};
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// # let path = "../data/jcamp-dx/blood/blood_01.dx";
+/// # let spectrum = JcampDx::read_spectrum(path, (-2.2, 11.8))?;
+/// let path = "path/to/spectrum.dx";
+/// # let path = std::env::temp_dir().join("metabodecon_jcampdx_write_doc.dx");
+///
+/// // Write the spectrum to a JCAMP-DX file using DIFDUP compression.
+/// JcampDx::write_compressed_spectrum(path, &spectrum, JcampDxCompression::DifDup)?;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub enum JcampDx {}
 
+/// Encoding of the y values when writing spectra with [`JcampDx`].
+///
+/// See the documentation of [`JcampDx`] for details on the encoding schemes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum JcampDxCompression {
+    /// Uncompressed ASCII Free Format Numeric.
+    #[default]
+    Affn,
+    /// ASCII Squeezed Difference Form with Difference and Duplicate
+    /// Suppression compression.
+    DifDup,
+}
+
+/// Maximum number of characters in a line of the written data table.
+const LINE_LENGTH: usize = 80;
+
+/// Magnitude that the largest absolute intensity is scaled to when writing.
+const INTEGER_SCALE: f64 = 1e9;
+
 /// Type of data that is stored in the JCAMP-DX file.
 ///
-/// This can be either NMR Spectrum or NMR FID. Currently only Spectrum is
-/// supported. Other options will never be supported.
-#[derive(Debug)]
-enum DataType {
+/// This can be either NMR Spectrum or NMR FID. Other options will never be
+/// supported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JcampDxDataType {
     /// An already fourier transformed FID is a Spectrum.
     Spectrum,
+    /// Raw time-domain data, which needs to be fourier transformed.
+    Fid,
 }
 
 /// Which specific format of JCAMP-DX is used to store the data.
@@ -362,8 +431,8 @@ enum DataType {
 /// | `FIRST`   | First value (only X).                                       |
 /// | `LAST`    | Last value (only X).                                        |
 /// | `FACTOR`  | Scale factor of the values (only Y/R).                      |
-#[derive(Debug)]
-enum Format {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JcampDxFormat {
     /// File contains only one data block (one real part).
     XYData,
     /// File contains multiple data blocks (real and imaginary parts).
@@ -373,25 +442,26 @@ enum Format {
 /// Units of the x values.
 ///
 /// Usually spectra are stored using Hz and displayed using ppm, but they can
-/// also be stored using ppm.
+/// also be stored using ppm. FIDs are stored using seconds.
 #[derive(Debug)]
 enum XUnits {
     /// Hertz.
     Hz,
     /// Parts per million.
     Ppm,
+    /// Seconds.
+    Seconds,
 }
 
 /// Metadata that can be parsed irrespective of the specific JCAMP-DX format.
 #[derive(Debug)]
 struct Header {
+    /// The version of the JCAMP-DX format.
+    version: f64,
     /// The type of data (processed Spectrum or raw FID).
-    ///
-    /// Currently unused, as only Spectrum is supported.
-    #[allow(dead_code)]
-    data_type: DataType,
+    data_type: JcampDxDataType,
     /// The data format (NTuples or XYData).
-    format: Format,
+    format: JcampDxFormat,
     /// The spectrometer frequency used to calculate ppm values.
     frequency: f64,
     /// The observed nucleus (e.g. 1H, 13C).
@@ -400,21 +470,86 @@ struct Header {
     reference_compound: Option<ReferenceCompound>,
 }
 
+/// Header metadata of a JCAMP-DX file, as read by [`JcampDx::read_metadata`].
+///
+/// This is the metadata that can be parsed irrespective of the specific
+/// JCAMP-DX format, see the documentation of [`JcampDx`] for the keys it is
+/// extracted from.
+#[derive(Clone, Debug)]
+pub struct JcampDxMetadata {
+    /// The version of the JCAMP-DX format.
+    version: f64,
+    /// The type of data (processed Spectrum or raw FID).
+    data_type: JcampDxDataType,
+    /// The data format (NTuples or XYData).
+    format: JcampDxFormat,
+    /// The spectrometer frequency in MHz.
+    frequency: f64,
+    /// The observed nucleus.
+    nucleus: Nucleus,
+    /// The reference compound, if present.
+    reference_compound: Option<ReferenceCompound>,
+}
+
+impl From<Header> for JcampDxMetadata {
+    fn from(value: Header) -> Self {
+        Self {
+            version: value.version,
+            data_type: value.data_type,
+            format: value.format,
+            frequency: value.frequency,
+            nucleus: value.nucleus,
+            reference_compound: value.reference_compound,
+        }
+    }
+}
+
+impl JcampDxMetadata {
+    /// Returns the version of the JCAMP-DX format (e.g. 5.01 or 6.0).
+    pub fn version(&self) -> f64 {
+        self.version
+    }
+
+    /// Returns the type of data stored in the file.
+    pub fn data_type(&self) -> JcampDxDataType {
+        self.data_type
+    }
+
+    /// Returns the format the data is stored in.
+    pub fn format(&self) -> JcampDxFormat {
+        self.format
+    }
+
+    /// Returns the spectrometer frequency in MHz.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Returns the observed nucleus.
+    pub fn nucleus(&self) -> Nucleus {
+        self.nucleus.clone()
+    }
+
+    /// Returns the reference compound, if it is specified in the file.
+    pub fn reference_compound(&self) -> Option<ReferenceCompound> {
+        self.reference_compound.clone()
+    }
+}
+
 /// Regex patterns to search for the header metadata.
 static HEADER_RE: LazyLock<[Regex; 11]> = LazyLock::new(|| {
     [
-        Regex::new(r"(?m)^(##JCAMP(\s*|_|-)DX=\s*)(?P<version>\d+(\.\d+)?)").unwrap(),
+        number_regex(r"##JCAMP(\s*|_|-)DX=\s*", "version"),
         Regex::new(r"(?m)^(##DATA(\s|_)TYPE=\s*)(?P<type>\w+\s\w+)").unwrap(),
         Regex::new(r"(?m)^(##DATA(\s|_)CLASS=\s*)(?P<format>\w+(\s\w+)?)").unwrap(),
-        Regex::new(r"(?m)^(##\.OBSERVE(\s|_)FREQUENCY=\s*)(?P<frequency>\d+(\.\d+)?)").unwrap(),
+        number_regex(r"##\.OBSERVE(\s|_)FREQUENCY=\s*", "frequency"),
         Regex::new(r"(?m)^(##\.OBSERVE(\s|_)NUCLEUS=\s*)(?P<nucleus>\^\w+)").unwrap(),
         Regex::new(r"(?m)^(##\.SOLVENT(\s|_)NAME=\s*)(?P<name>.*)").unwrap(),
-        Regex::new(r"(?m)^(##\.SOLVENT(\s|_)REFERENCE=\s*)(?P<shift>\d+(\.\d+))?").unwrap(),
+        number_regex(r"##\.SOLVENT(\s|_)REFERENCE=\s*", "shift"),
         Regex::new(r"(?m)^(##\.SHIFT(\s|_)REFERENCE=\s*)(?P<method>[^,]*)").unwrap(),
         Regex::new(r"(?m)^(##\.SHIFT(\s|_)REFERENCE=[^,]*,\s*)(?P<name>[^,]*)").unwrap(),
         Regex::new(r"(?m)^(##\.SHIFT(\s|_)REFERENCE=[^,]*,[^,]*,\s*)(?P<index>\d+)").unwrap(),
-        Regex::new(r"(?m)^(##\.SHIFT(\s|_)REFERENCE=[^,]*,[^,]*,[^,]*,\s*)(?P<shift>\d+(\.\d+)?)")
-            .unwrap(),
+        number_regex(r"##\.SHIFT(\s|_)REFERENCE=[^,]*,[^,]*,[^,]*,\s*", "shift"),
     ]
 });
 
@@ -452,16 +587,19 @@ struct DataBlock {
     data_size: usize,
     /// The data, encoded as a JCAMP-DX string.
     data: String,
+    /// Line number (starting at 1) and byte offset of the data within the
+    /// file.
+    position: (usize, usize),
 }
 
 /// Regex patterns to search for the XYData format specific metadata.
 static XY_DATA_RE: LazyLock<[Regex; 6]> = LazyLock::new(|| {
     [
         Regex::new(r"(?m)^(##XUNITS=\s*)(?P<xunits>\w+)").unwrap(),
-        Regex::new(r"(?m)^(##YFACTOR=\s*)(?P<factor>\d+(\.\d+)?)").unwrap(),
-        Regex::new(r"(?m)^(##FIRSTX=\s*)(?P<first>\d+(\.\d+)?)").unwrap(),
-        Regex::new(r"(?m)^(##LASTX=\s*)(?P<last>\d+(\.\d+)?)").unwrap(),
-        Regex::new(r"(?m)^(##NPOINTS=\s*)(?P<data_size>\d+(\.\d+)?)").unwrap(),
+        number_regex(r"##YFACTOR=\s*", "factor"),
+        number_regex(r"##FIRSTX=\s*", "first"),
+        number_regex(r"##LASTX=\s*", "last"),
+        number_regex(r"##NPOINTS=\s*", "data_size"),
         Regex::new(r"(?m)^(##XYDATA=\s*\(X\+\+\([RY]\.\.[RY]\)\)(.*)?)(?P<data>[^#$]*)").unwrap(),
     ]
 });
@@ -497,18 +635,31 @@ static N_TUPLES_KEYS: LazyLock<[&str; 7]> = LazyLock::new(|| {
     ]
 });
 
+/// Regex patterns to search for the FID specific metadata.
+#[cfg(feature = "fft")]
+static FID_RE: LazyLock<[Regex; 2]> = LazyLock::new(|| {
+    [
+        Regex::new(r"(?m)^(##DATA(\s|_)TABLE=\s*\(X\+\+\(I\.\.I\)\)(.*)?)(?P<data>[^#$]*)")
+            .unwrap(),
+        number_regex(r"##\$O1=\s*", "offset"),
+    ]
+});
+
+/// Keys used in the FID regex patterns, used for error messages.
+#[cfg(feature = "fft")]
+static FID_KEYS: LazyLock<[&str; 2]> = LazyLock::new(|| ["DATA TABLE (I)", "$O1"]);
+
 /// Regex patterns used to find values encoded using specific schemes.
-static ENCODING: LazyLock<[Regex; 6]> = LazyLock::new(|| {
+static ENCODING: LazyLock<[Regex; 8]> = LazyLock::new(|| {
     [
         Regex::new(r"(?P<asdf>[@%A-Za-z+-])").unwrap(), // ASDF
         Regex::new(r"(?P<pac>[+-]\d)").unwrap(),        // PAC
         Regex::new(r"(?P<sqz>[@A-Ia-i])").unwrap(),     // SQZ
-        Regex::new(
-            r"\s+(?P<dif>[%J-Rj-r]\d*)\s*(?P<dup>([S-Zs]\d*)?)\s*((\r\n|\n|\r)\s*(?P<next>\d+))",
-        )
-        .unwrap(), // DIFDUP Checkpoints
-        Regex::new(r"\s+(?P<val>[+-]*\d*)\s+(?P<dif>[%J-Rj-r]\d*)").unwrap(), // DIF
-        Regex::new(r"\s+(?P<val>[+-]*\d+)\s+(?P<dup>[S-Zs]\d*)").unwrap(), // DUP
+        Regex::new(r"\s+(?P<dif>[%J-Rj-r]\d*)\s+(?P<dup>[S-Zs]\d*)").unwrap(), // DIFDUP
+        Regex::new(r"\s+(?P<dif>[%J-Rj-r]\d*)\s*((\r\n|\n|\r)\s*(?P<next>\d+))").unwrap(), // DIF Checkpoints
+        Regex::new(r"\s+(?P<val>[+-]*\d*)\s+(?P<dif>[%J-Rj-r]\d*)").unwrap(),              // DIF
+        Regex::new(r"\s+(?P<val>[+-]*\d+)\s+(?P<dup>[S-Zs]\d*)").unwrap(),                 // DUP
+        Regex::new(r"[%J-Rj-r]\d*\s*([S-Zs]\d*)?\s*$").unwrap(), // DIF Line Ending
     ]
 });
 
@@ -521,13 +672,13 @@ impl JcampDx {
     /// well-formed and in a consistent state. The following conditions are
     /// checked:
     /// - The Intensities are not empty.
-    /// - The lengths of the chemical shifts and intensities match. The data
-    ///   size is read from the metadata and used to generate the chemical
-    ///   shifts.
+    /// - The number of decoded intensities matches the data size, which is
+    ///   read from the metadata and used to generate the chemical shifts.
     /// - All intensity values are finite.
     /// - The signal region boundaries are within the range of the chemical
     ///   shifts.
     /// - All required key-value pairs are extracted from the metadata files.
+    /// - FIDs can only be read if the `fft` feature is enabled.
     ///
     /// Additionally, if any [`I/O`] errors occur, an error variant containing
     /// the original error is returned.
@@ -555,29 +706,72 @@ impl JcampDx {
     pub fn read_spectrum<P: AsRef<Path>>(
         path: P,
         signal_boundaries: (f64, f64),
+    ) -> Result<Spectrum> {
+        Self::read_spectrum_with(path, signal_boundaries, false)
+    }
+
+    /// Reads the spectrum from a JCAMP-DX file and enforces the data integrity
+    /// checks of the JCAMP-DX format.
+    ///
+    /// See [`read_spectrum`] for reading files that don't adhere to these
+    /// checks.
+    ///
+    /// [`read_spectrum`]: JcampDx::read_spectrum
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of [`read_spectrum`], a
+    /// [`MalformedData`](Kind::MalformedData) error is returned if a line
+    /// ending with a DIF encoded value isn't followed by a line repeating the
+    /// last y value. The error contains the offending line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::JcampDx;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum.dx";
+    /// # let path = "../data/jcamp-dx/blood/blood_01.dx";
+    ///
+    /// // Read a spectrum from a JCAMP-DX file and check its integrity.
+    /// let spectrum = JcampDx::read_spectrum_strict(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectrum_strict<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Spectrum> {
+        Self::read_spectrum_with(path, signal_boundaries, true)
+    }
+
+    /// Internal helper function to read the spectrum from a JCAMP-DX file,
+    /// optionally enforcing the data integrity checks.
+    fn read_spectrum_with<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+        strict: bool,
     ) -> Result<Spectrum> {
         let path = path.as_ref();
-        let dx = read_to_string(path)?;
+        let dx = read_text(path)?;
         let header = Self::read_header(&dx, path)?;
-        let block = match header.format {
-            Format::XYData => Self::read_xydata(&dx, path)?,
-            Format::NTuples => Self::read_ntuples(&dx, path)?,
-        };
-        let conversion = match block.x_units {
-            XUnits::Hz => 1.0 / header.frequency,
-            XUnits::Ppm => 1.0,
-        };
-        let step = (block.last - block.first) * conversion / (block.data_size as f64 - 1.0);
-        let offset = match &header.reference_compound {
-            Some(reference) => reference.chemical_shift() - reference.index() as f64 * step,
-            None => block.first * conversion,
+        let block = match (&header.data_type, &header.format) {
+            (JcampDxDataType::Fid, JcampDxFormat::XYData) => {
+                return Err(Error::new(Kind::UnsupportedJcampDxFile).into());
+            }
+            (_, JcampDxFormat::XYData) => Self::read_xydata(&dx, path)?,
+            (_, JcampDxFormat::NTuples) => Self::read_ntuples(&dx, path)?,
         };
-        let chemical_shifts = (0..block.data_size)
-            .map(|i| offset + (i as f64) * step)
-            .collect();
-        let intensities = match ENCODING[0].is_match(block.data.as_str()) {
-            true => Self::decode_asdf(&block.data, block.factor, path)?,
-            false => Self::decode_affn(&block.data, block.factor, path)?,
+        let (chemical_shifts, intensities) = match header.data_type {
+            JcampDxDataType::Spectrum => {
+                Self::read_frequency_domain(&header, &block, path, strict)?
+            }
+            JcampDxDataType::Fid => Self::transform_fid(&dx, &header, &block, path, strict)?,
         };
         let mut spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
         spectrum.set_nucleus(header.nucleus);
@@ -589,9 +783,54 @@ impl JcampDx {
         Ok(spectrum)
     }
 
+    /// Reads the header metadata from a JCAMP-DX file without decoding the
+    /// data.
+    ///
+    /// This is useful to inspect what is extracted from a file, or to check a
+    /// large number of files before reading them.
+    ///
+    /// # Errors
+    ///
+    /// The same metadata conditions as for [`read_spectrum`] are checked, so
+    /// an error is returned if a required key-value pair is missing or
+    /// malformed, or if the file is not supported.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`read_spectrum`]: JcampDx::read_spectrum
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::meta::Nucleus;
+    /// use metabodecon::spectrum::{JcampDx, JcampDxDataType, JcampDxFormat};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum.dx";
+    /// # let path = "../data/jcamp-dx/blood/blood_01.dx";
+    ///
+    /// // Read the metadata from a JCAMP-DX file.
+    /// let metadata = JcampDx::read_metadata(path)?;
+    ///
+    /// assert_eq!(metadata.data_type(), JcampDxDataType::Spectrum);
+    /// assert_eq!(metadata.nucleus(), Nucleus::Hydrogen1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_metadata<P: AsRef<Path>>(path: P) -> Result<JcampDxMetadata> {
+        let path = path.as_ref();
+        let dx = read_text(path)?;
+
+        Ok(Self::read_header(&dx, path)?.into())
+    }
+
     /// Reads all spectra from a directory of JCAMP-DX files.
     ///
-    /// Skips any files that do not have the `.dx` extension.
+    /// Skips any files that do not have the `.dx` extension. If the `gzip`
+    /// feature is enabled, compressed files with the `.dx.gz` extension are
+    /// read as well.
     ///
     /// # Errors
     ///
@@ -599,9 +838,8 @@ impl JcampDx {
     /// well-formed and in a consistent state. The following conditions are
     /// checked:
     /// - The Intensities are not empty.
-    /// - The lengths of the chemical shifts and intensities match. The data
-    ///   size is read from the metadata and used to generate the chemical
-    ///   shifts.
+    /// - The number of decoded intensities matches the data size, which is
+    ///   read from the metadata and used to generate the chemical shifts.
     /// - All intensity values are finite.
     /// - The signal region boundaries are within the range of the chemical
     ///   shifts.
@@ -639,9 +877,7 @@ impl JcampDx {
             .read_dir()?
             .filter_map(|entry| {
                 entry.ok().and_then(|entry| {
-                    entry
-                        .path()
-                        .extension()
+                    text_extension(&entry.path())
                         .and_then(OsStr::to_str)
                         .and_then(|extension| match extension.to_lowercase().as_str() {
                             "dx" => Some(entry.path()),
@@ -655,6 +891,228 @@ impl JcampDx {
         Ok(spectra)
     }
 
+    /// Writes the spectrum to a JCAMP-DX file using uncompressed AFFN.
+    ///
+    /// An existing file is overwritten. See [`write_compressed_spectrum`] for
+    /// writing compressed data.
+    ///
+    /// [`write_compressed_spectrum`]: JcampDx::write_compressed_spectrum
+    ///
+    /// # Errors
+    ///
+    /// If any [`I/O`] errors occur, an error variant containing the original
+    /// error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::JcampDx;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let path = "../data/jcamp-dx/blood/blood_01.dx";
+    /// # let spectrum = JcampDx::read_spectrum(path, (-2.2, 11.8))?;
+    /// let path = "path/to/spectrum.dx";
+    /// # let path = std::env::temp_dir().join("metabodecon_jcampdx_write_affn_doc.dx");
+    ///
+    /// // Write the spectrum to a JCAMP-DX file.
+    /// JcampDx::write_spectrum(path, &spectrum)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_spectrum<P: AsRef<Path>>(path: P, spectrum: &Spectrum) -> Result<()> {
+        Self::write_compressed_spectrum(path, spectrum, JcampDxCompression::Affn)
+    }
+
+    /// Writes the spectrum to a JCAMP-DX file, encoding the y values with the
+    /// given compression scheme.
+    ///
+    /// An existing file is overwritten.
+    ///
+    /// # Errors
+    ///
+    /// If any [`I/O`] errors occur, an error variant containing the original
+    /// error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::{JcampDx, JcampDxCompression};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let path = "../data/jcamp-dx/blood/blood_01.dx";
+    /// # let spectrum = JcampDx::read_spectrum(path, (-2.2, 11.8))?;
+    /// let path = "path/to/spectrum.dx";
+    /// # let path = std::env::temp_dir().join("metabodecon_jcampdx_write_difdup_doc.dx");
+    ///
+    /// // Write the spectrum to a JCAMP-DX file using DIFDUP compression.
+    /// JcampDx::write_compressed_spectrum(path, &spectrum, JcampDxCompression::DifDup)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_compressed_spectrum<P: AsRef<Path>>(
+        path: P,
+        spectrum: &Spectrum,
+        compression: JcampDxCompression,
+    ) -> Result<()> {
+        let title = path
+            .as_ref()
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default()
+            .to_string();
+        write(path, Self::encode_spectrum(spectrum, &title, compression))?;
+
+        Ok(())
+    }
+
+    /// Internal helper function to encode the spectrum as the contents of a
+    /// JCAMP-DX file.
+    fn encode_spectrum(
+        spectrum: &Spectrum,
+        title: &str,
+        compression: JcampDxCompression,
+    ) -> String {
+        let frequency = spectrum.frequency();
+        let chemical_shifts = spectrum.chemical_shifts();
+        let intensities = spectrum.intensities();
+        let reference = spectrum.reference_compound();
+        let x_factor = f64::abs(spectrum.step() * frequency);
+        let maximum = intensities
+            .iter()
+            .fold(0.0_f64, |maximum, intensity| maximum.max(intensity.abs()));
+        let y_factor = match maximum > 0.0 {
+            true => maximum / INTEGER_SCALE,
+            false => 1.0,
+        };
+        let x = chemical_shifts
+            .iter()
+            .map(|shift| (shift * frequency / x_factor).round() as i64)
+            .collect::<Vec<_>>();
+        let y = intensities
+            .iter()
+            .map(|intensity| (intensity / y_factor).round() as i64)
+            .collect::<Vec<_>>();
+        let data = match compression {
+            JcampDxCompression::Affn => Self::encode_affn(&x, &y),
+            JcampDxCompression::DifDup => Self::encode_difdup(&x, &y),
+        };
+        let method = reference
+            .method()
+            .map(|method| method.to_string().to_uppercase())
+            .unwrap_or_default();
+
+        [
+            format!("##TITLE= {}", title),
+            "##JCAMPDX= 5.01".to_string(),
+            "##DATA TYPE= NMR SPECTRUM".to_string(),
+            "##DATA CLASS= XYDATA".to_string(),
+            "##ORIGIN= metabodecon".to_string(),
+            "##OWNER=".to_string(),
+            format!("##.OBSERVE FREQUENCY= {}", frequency),
+            format!("##.OBSERVE NUCLEUS= ^{}", spectrum.nucleus()),
+            format!(
+                "##.SHIFT REFERENCE= {}, {}, {}, {}",
+                method,
+                reference.name().unwrap_or_default(),
+                reference.index() + 1,
+                reference.chemical_shift()
+            ),
+            "##XUNITS= HZ".to_string(),
+            "##YUNITS= ARBITRARY UNITS".to_string(),
+            format!("##XFACTOR= {}", x_factor),
+            format!("##YFACTOR= {}", y_factor),
+            format!("##FIRSTX= {}", chemical_shifts[0] * frequency),
+            format!(
+                "##LASTX= {}",
+                chemical_shifts[spectrum.len() - 1] * frequency
+            ),
+            format!("##DELTAX= {}", spectrum.step() * frequency),
+            format!("##NPOINTS= {}", spectrum.len()),
+            format!("##FIRSTY= {}", y[0] as f64 * y_factor),
+            "##XYDATA=(X++(Y..Y))".to_string(),
+            data,
+            "##END=".to_string(),
+        ]
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect()
+    }
+
+    /// Internal helper function to encode the scaled x and y values as AFFN.
+    fn encode_affn(x: &[i64], y: &[i64]) -> String {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while start < y.len() {
+            let mut line = x[start].to_string();
+            let mut end = start;
+            while end < y.len() {
+                let value = format!(" {}", y[end]);
+                if end > start && line.len() + value.len() > LINE_LENGTH {
+                    break;
+                }
+                line.push_str(&value);
+                end += 1;
+            }
+            lines.push(line);
+            start = end;
+        }
+
+        lines.join("\n")
+    }
+
+    /// Internal helper function to encode the scaled x and y values as
+    /// DIFDUP.
+    ///
+    /// Each line starts with the SQZ encoded absolute value, followed by the
+    /// DIF encoded differences. As a data integrity check, the last value of
+    /// each line is repeated at the start of the next line. Runs of `%` are
+    /// suppressed using DUP.
+    fn encode_difdup(x: &[i64], y: &[i64]) -> String {
+        let render = |groups: &[(String, usize)]| {
+            groups
+                .iter()
+                .map(|(dif, repetitions)| match repetitions {
+                    1 => dif.clone(),
+                    _ => format!("{}{}", dif, Self::do_dup(*repetitions)),
+                })
+                .collect::<String>()
+        };
+        let mut lines = Vec::new();
+        let mut start = 0;
+        loop {
+            let prefix = format!("{} {}", x[start], Self::do_sqz(y[start]));
+            let mut groups: Vec<(String, usize)> = Vec::new();
+            let mut end = start;
+            while end + 1 < y.len() {
+                let dif = Self::do_dif(y[end + 1] - y[end]);
+                let mut candidate = groups.clone();
+                match candidate.last_mut() {
+                    Some((last, repetitions)) if *last == dif && dif == "%" => *repetitions += 1,
+                    _ => candidate.push((dif, 1)),
+                }
+                if !groups.is_empty() && prefix.len() + render(&candidate).len() > LINE_LENGTH {
+                    break;
+                }
+                groups = candidate;
+                end += 1;
+            }
+            lines.push(prefix + &render(&groups));
+            if end + 1 == y.len() {
+                if !groups.is_empty() {
+                    lines.push(format!("{} {}", x[end], Self::do_sqz(y[end])));
+                }
+                break;
+            }
+            start = end;
+        }
+
+        lines.join("\n")
+    }
+
     /// Internal helper function to read the metadata from the general file
     /// header.
     ///
@@ -667,7 +1125,8 @@ impl JcampDx {
         let re = &*HEADER_RE;
         let keys = &*HEADER_KEYS;
 
-        match extract_capture::<f64, _>(&re[0], "version", dx, &path, keys[0])?.trunc() {
+        let version = extract_capture::<f64, _>(&re[0], "version", dx, &path, keys[0])?;
+        match version.trunc() {
             5.0 | 6.0 => (),
             _ => return Err(Error::new(Kind::UnsupportedJcampDxFile).into()),
         };
@@ -675,15 +1134,16 @@ impl JcampDx {
             .to_uppercase()
             .as_str()
         {
-            "NMR SPECTRUM" => DataType::Spectrum,
+            "NMR SPECTRUM" => JcampDxDataType::Spectrum,
+            "NMR FID" => JcampDxDataType::Fid,
             _ => return Err(Error::new(Kind::UnsupportedJcampDxFile).into()),
         };
         let format = match extract_capture::<String, _>(&re[2], "format", dx, &path, keys[2])?
             .to_uppercase()
             .as_str()
         {
-            "XYDATA" => Format::XYData,
-            "NTUPLES" => Format::NTuples,
+            "XYDATA" => JcampDxFormat::XYData,
+            "NTUPLES" => JcampDxFormat::NTuples,
             _ => return Err(Error::new(Kind::UnsupportedJcampDxFile).into()),
         };
         let frequency = extract_capture(&re[3], "frequency", dx, &path, keys[3])?;
@@ -705,6 +1165,7 @@ impl JcampDx {
         };
 
         Ok(Header {
+            version,
             data_type,
             format,
             frequency,
@@ -743,6 +1204,7 @@ impl JcampDx {
             .as_str()
             .trim()
             .to_string();
+        let position = Self::data_position(&re[5], dx);
 
         if data.is_empty() {
             return Err(Error::new(Kind::MissingData {
@@ -758,6 +1220,7 @@ impl JcampDx {
             last,
             data_size,
             data,
+            position,
         })
     }
 
@@ -822,6 +1285,7 @@ impl JcampDx {
         {
             "HZ" => XUnits::Hz,
             "PPM" => XUnits::Ppm,
+            "SECONDS" | "S" => XUnits::Seconds,
             unit => {
                 return Err(Error::new(Kind::MalformedMetadata {
                     key: keys[2].to_string(),
@@ -865,6 +1329,7 @@ impl JcampDx {
             .as_str()
             .trim()
             .to_string();
+        let position = Self::data_position(&re[6], dx);
 
         if data.is_empty() {
             return Err(Error::new(Kind::MissingData {
@@ -880,26 +1345,293 @@ impl JcampDx {
             last,
             data_size,
             data,
+            position,
         })
     }
 
+    /// Internal helper function to compute the chemical shifts and decode the
+    /// intensities of frequency-domain data.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedMetadata`](Kind::MalformedMetadata)
+    /// - [`MalformedData`](Kind::MalformedData)
+    fn read_frequency_domain<P: AsRef<Path>>(
+        header: &Header,
+        block: &DataBlock,
+        path: P,
+        strict: bool,
+    ) -> Result<(Vec<f64>, Vec<f64>)> {
+        let conversion = match block.x_units {
+            XUnits::Hz => 1.0 / header.frequency,
+            XUnits::Ppm => 1.0,
+            XUnits::Seconds => {
+                return Err(Error::new(Kind::MalformedMetadata {
+                    key: N_TUPLES_KEYS[2].to_string(),
+                    path: path.as_ref().to_path_buf(),
+                    details: "Unsupported x unit for NMR SPECTRUM: SECONDS".to_string(),
+                })
+                .into());
+            }
+        };
+        let step = (block.last - block.first) * conversion / (block.data_size as f64 - 1.0);
+        let offset = match &header.reference_compound {
+            Some(reference) => reference.chemical_shift() - reference.index() as f64 * step,
+            None => block.first * conversion,
+        };
+        let chemical_shifts = (0..block.data_size)
+            .map(|i| offset + (i as f64) * step)
+            .collect();
+        let intensities = Self::decode(&block.data, block.factor, &path, block.position, strict)?;
+        if intensities.len() != block.data_size {
+            return Err(Error::new(Kind::MalformedData {
+                path: path.as_ref().to_path_buf(),
+                details: format!(
+                    "expected {} points as specified by NPOINTS, decoded {}",
+                    block.data_size,
+                    intensities.len()
+                ),
+                line: None,
+                offset: None,
+            })
+            .into());
+        }
+
+        Ok((chemical_shifts, intensities))
+    }
+
+    /// Internal helper function to decode the real and imaginary parts of an
+    /// FID, fourier transform it and apply a zero-order phase correction.
+    ///
+    /// The zero-order phase is chosen to maximize the integral of the real
+    /// part of the spectrum, which is the case if the sum of the complex
+    /// spectrum is rotated onto the positive real axis.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MissingMetadata`](Kind::MissingMetadata)
+    /// - [`MalformedMetadata`](Kind::MalformedMetadata)
+    /// - [`MissingData`](Kind::MissingData)
+    /// - [`MalformedData`](Kind::MalformedData)
+    #[cfg(feature = "fft")]
+    fn transform_fid<P: AsRef<Path>>(
+        dx: &str,
+        header: &Header,
+        block: &DataBlock,
+        path: P,
+        strict: bool,
+    ) -> Result<(Vec<f64>, Vec<f64>)> {
+        use rustfft::FftPlanner;
+        use rustfft::num_complex::Complex;
+
+        let dwell_time = match block.x_units {
+            XUnits::Seconds => (block.last - block.first) / (block.data_size as f64 - 1.0),
+            _ => {
+                return Err(Error::new(Kind::MalformedMetadata {
+                    key: N_TUPLES_KEYS[2].to_string(),
+                    path: path.as_ref().to_path_buf(),
+                    details: "Unsupported x unit for NMR FID (expected SECONDS)".to_string(),
+                })
+                .into());
+            }
+        };
+        let (imaginary_factor, imaginary_data, imaginary_position) =
+            Self::read_imaginary(dx, &path)?;
+        let real = Self::decode(&block.data, block.factor, &path, block.position, strict)?;
+        let imaginary = Self::decode(
+            &imaginary_data,
+            imaginary_factor,
+            &path,
+            imaginary_position,
+            strict,
+        )?;
+        if real.len() != block.data_size || imaginary.len() != block.data_size {
+            return Err(Error::new(Kind::MalformedData {
+                path: path.as_ref().to_path_buf(),
+                details: format!(
+                    "expected {} real and imaginary values, found {} and {}",
+                    block.data_size,
+                    real.len(),
+                    imaginary.len()
+                ),
+                line: None,
+                offset: None,
+            })
+            .into());
+        }
+
+        let mut buffer = real
+            .into_iter()
+            .zip(imaginary)
+            .map(|(re, im)| Complex::new(re, im))
+            .collect::<Vec<_>>();
+        FftPlanner::new()
+            .plan_fft_forward(buffer.len())
+            .process(&mut buffer);
+        buffer.rotate_right(block.data_size / 2);
+        let phase = Complex::from_polar(1.0, -buffer.iter().sum::<Complex<f64>>().arg());
+        let intensities = buffer
+            .into_iter()
+            .map(|value| (value * phase).re)
+            .collect();
+
+        let width = 1.0 / dwell_time;
+        let step = width / (block.data_size as f64 * header.frequency);
+        let offset = match &header.reference_compound {
+            Some(reference) => reference.chemical_shift() - reference.index() as f64 * step,
+            None => {
+                let re = &*FID_RE;
+                let keys = &*FID_KEYS;
+                let carrier =
+                    extract_capture::<f64, _>(&re[1], "offset", dx, &path, keys[1]).unwrap_or(0.0);
+                carrier / header.frequency - (block.data_size / 2) as f64 * step
+            }
+        };
+        let chemical_shifts = (0..block.data_size)
+            .map(|i| offset + (i as f64) * step)
+            .collect();
+
+        Ok((chemical_shifts, intensities))
+    }
+
+    /// Internal helper function that rejects FIDs, as they can't be
+    /// transformed into a spectrum without the `fft` feature.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`UntransformableFid`](Kind::UntransformableFid)
+    #[cfg(not(feature = "fft"))]
+    fn transform_fid<P: AsRef<Path>>(
+        _dx: &str,
+        _header: &Header,
+        _block: &DataBlock,
+        path: P,
+        _strict: bool,
+    ) -> Result<(Vec<f64>, Vec<f64>)> {
+        Err(Error::new(Kind::UntransformableFid {
+            path: path.as_ref().to_path_buf(),
+        })
+        .into())
+    }
+
+    /// Internal helper function to read the scale factor, the data table and
+    /// the position of the data table of the imaginary part of an FID.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MissingMetadata`](Kind::MissingMetadata)
+    /// - [`MalformedMetadata`](Kind::MalformedMetadata)
+    /// - [`MissingData`](Kind::MissingData)
+    #[cfg(feature = "fft")]
+    fn read_imaginary<P: AsRef<Path>>(dx: &str, path: P) -> Result<(f64, String, (usize, usize))> {
+        let re = &*N_TUPLES_RE;
+        let keys = &*N_TUPLES_KEYS;
+
+        let i_column = extract_row::<String, _>(&re[0], "symbols", dx, &path, keys[0])?
+            .iter()
+            .position(|symbol| symbol.to_uppercase() == "I")
+            .ok_or_else(|| {
+                Error::new(Kind::MissingMetadata {
+                    key: keys[0].to_string(),
+                    path: path.as_ref().to_path_buf(),
+                })
+            })?;
+        let factor = extract_row::<f64, _>(&re[5], "factor", dx, &path, keys[5])?
+            .get(i_column)
+            .copied()
+            .ok_or_else(|| {
+                Error::new(Kind::MalformedMetadata {
+                    key: keys[5].to_string(),
+                    path: path.as_ref().to_path_buf(),
+                    details: "Could not find I column".to_string(),
+                })
+            })?;
+        let data = extract_capture::<String, _>(&FID_RE[0], "data", dx, &path, FID_KEYS[0])?
+            .as_str()
+            .trim()
+            .to_string();
+        let position = Self::data_position(&FID_RE[0], dx);
+
+        if data.is_empty() {
+            return Err(Error::new(Kind::MissingData {
+                path: path.as_ref().to_path_buf(),
+            })
+            .into());
+        }
+
+        Ok((factor, data, position))
+    }
+
+    /// Internal helper function to find the line number (starting at 1) and
+    /// the byte offset of the trimmed data table captured by the given regex
+    /// within the file.
+    fn data_position(regex: &Regex, dx: &str) -> (usize, usize) {
+        let offset = regex
+            .captures(dx)
+            .and_then(|captures| captures.name("data"))
+            .map(|data| data.start() + data.as_str().len() - data.as_str().trim_start().len())
+            .unwrap_or(0);
+
+        (dx[..offset].matches('\n').count() + 1, offset)
+    }
+
+    /// Internal helper function to decode a data table using either AFFN or
+    /// ASDF, depending on which characters it contains. If `strict` is set,
+    /// the DIF checkpoints of ASDF data are verified. The position is the line
+    /// number and byte offset of the data table within the file, which are
+    /// used to locate malformed values.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedData`](Kind::MalformedData)
+    fn decode<P: AsRef<Path>>(
+        data: &str,
+        factor: f64,
+        path: P,
+        position: (usize, usize),
+        strict: bool,
+    ) -> Result<Vec<f64>> {
+        match (ENCODING[0].is_match(data), strict) {
+            (true, true) => Self::decode_asdf_strict(data, factor, path, position),
+            (true, false) => Self::decode_asdf(data, factor, path, Some(position)),
+            (false, _) => Self::decode_affn(data, factor, path, Some(position)),
+        }
+    }
+
     /// Internal helper function to convert an AFFN string into a `Vec<f64>`.
     ///
+    /// If the position of the string within the file is provided, malformed
+    /// values are reported with their line number and byte offset.
+    ///
     /// # Errors
     ///
     /// The following errors are possible:
     /// - [`MalformedData`](Kind::MalformedData)
-    fn decode_affn<P: AsRef<Path>>(data: &str, factor: f64, path: P) -> Result<Vec<f64>> {
+    fn decode_affn<P: AsRef<Path>>(
+        data: &str,
+        factor: f64,
+        path: P,
+        position: Option<(usize, usize)>,
+    ) -> Result<Vec<f64>> {
         let intensities = data
             .lines()
-            .map(|line| {
+            .enumerate()
+            .map(|(number, line)| {
                 line.split_whitespace()
                     .skip(1)
                     .map(|value| {
                         value.parse::<f64>().map_err(|error| {
+                            let offset = value.as_ptr() as usize - data.as_ptr() as usize;
                             Error::new(Kind::MalformedData {
                                 path: path.as_ref().to_path_buf(),
                                 details: format!("{} ({})", value, error),
+                                line: position.map(|position| position.0 + number),
+                                offset: position.map(|position| position.1 + offset),
                             })
                             .into()
                         })
@@ -918,11 +1650,65 @@ impl JcampDx {
     /// Internal helper function to convert a string encoded using any
     /// combination of ASDF schemes into a `Vec<f64>`.
     ///
+    /// If the position of the string within the file is provided, malformed
+    /// values are reported with the line number and byte offset of the first
+    /// line that can't be decoded.
+    ///
     /// # Errors
     ///
     /// The following errors are possible:
     /// - [`MalformedData`](Kind::MalformedData)
-    fn decode_asdf<P: AsRef<Path>>(data: &str, factor: f64, path: P) -> Result<Vec<f64>> {
+    fn decode_asdf<P: AsRef<Path>>(
+        data: &str,
+        factor: f64,
+        path: P,
+        position: Option<(usize, usize)>,
+    ) -> Result<Vec<f64>> {
+        Self::decode_affn(&Self::expand_asdf(data), factor, &path, None).map_err(|error| {
+            position
+                .and_then(|position| Self::locate_malformed_asdf(data, &path, position))
+                .unwrap_or(error)
+        })
+    }
+
+    /// Internal helper function to find the first line of an ASDF string
+    /// containing a value that can't be decoded, and to construct the error
+    /// with its line number and byte offset within the file.
+    fn locate_malformed_asdf<P: AsRef<Path>>(
+        data: &str,
+        path: P,
+        position: (usize, usize),
+    ) -> Option<crate::Error> {
+        data.lines()
+            .enumerate()
+            .find_map(|(number, line)| {
+                let expanded = Self::expand_asdf(line);
+                let details = expanded
+                    .split_whitespace()
+                    .skip(1)
+                    .find_map(|value| {
+                        value
+                            .parse::<f64>()
+                            .err()
+                            .map(|error| format!("{} ({})", value, error))
+                    })?;
+                let offset = line.as_ptr() as usize - data.as_ptr() as usize;
+
+                Some(
+                    Error::new(Kind::MalformedData {
+                        path: path.as_ref().to_path_buf(),
+                        details,
+                        line: Some(position.0 + number),
+                        offset: Some(position.1 + offset),
+                    })
+                    .into(),
+                )
+            })
+    }
+
+    /// Internal helper function to expand a string encoded using any
+    /// combination of ASDF schemes into an AFFN string.
+    fn expand_asdf(data: &str) -> String {
         let re = &*ENCODING;
 
         let data = re[0].replace_all(data, " $asdf");
@@ -930,26 +1716,27 @@ impl JcampDx {
         let data = re[2].replace_all(&data, |captures: &Captures| {
             Self::undo_sqz(captures.name("sqz").unwrap().as_str())
         });
-        let mut data = re[3]
+        let data = re[3].replace_all(&data, |captures: &Captures| {
+            let dif = captures.name("dif").unwrap().as_str();
+            let encoded = captures.name("dup").unwrap().as_str();
+
+            Self::undo_dup(dif, encoded)
+        });
+        let mut data = re[4]
             .replace_all(&data, |captures: &Captures| {
-                let dif = captures.name("dif").unwrap().as_str();
-                let dup = captures.name("dup").unwrap().as_str();
                 let next = captures.name("next").unwrap().as_str();
 
-                match dup {
-                    "" | "S" => format!(" \n{}", next),
-                    _ => format!(" {} {} \n{}", dif, Self::decrement_dup(dup), next),
-                }
+                format!(" \n{}", next)
             })
             .to_string();
         loop {
-            let tmp_data_dif = re[4].replace_all(&data, |captures: &Captures| {
+            let tmp_data_dif = re[5].replace_all(&data, |captures: &Captures| {
                 let value = captures.name("val").unwrap().as_str();
                 let encoded = captures.name("dif").unwrap().as_str();
 
                 Self::undo_dif(value, encoded)
             });
-            let tmp_data_dup = re[5].replace_all(&tmp_data_dif, |captures: &Captures| {
+            let tmp_data_dup = re[6].replace_all(&tmp_data_dif, |captures: &Captures| {
                 let value = captures.name("val").unwrap().as_str();
                 let encoded = captures.name("dup").unwrap().as_str();
 
@@ -957,12 +1744,71 @@ impl JcampDx {
             });
             data = tmp_data_dup.to_string();
 
-            if !re[4].is_match(&data) && !re[5].is_match(&data) {
+            if !re[5].is_match(&data) && !re[6].is_match(&data) {
                 break;
             }
         }
 
-        Self::decode_affn(&data, factor, path)
+        data
+    }
+
+    /// Internal helper function to convert a string encoded using any
+    /// combination of ASDF schemes into a `Vec<f64>`, while verifying the DIF
+    /// checkpoints.
+    ///
+    /// Every line is decoded separately. If a line ends with a DIF encoded
+    /// value, the first y value of the next line has to repeat the last y
+    /// value of that line, and is skipped afterwards.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedData`](Kind::MalformedData)
+    fn decode_asdf_strict<P: AsRef<Path>>(
+        data: &str,
+        factor: f64,
+        path: P,
+        position: (usize, usize),
+    ) -> Result<Vec<f64>> {
+        let mut intensities = Vec::new();
+        let mut checkpoint = false;
+        for (number, line) in data
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+        {
+            let line_position = (
+                position.0 + number,
+                position.1 + (line.as_ptr() as usize - data.as_ptr() as usize),
+            );
+            let decoded = Self::decode_asdf(line, 1.0, &path, Some(line_position))?;
+            match (checkpoint, decoded.first(), intensities.last()) {
+                (true, Some(first), Some(last)) if first != last => {
+                    return Err(Error::new(Kind::MalformedData {
+                        path: path.as_ref().to_path_buf(),
+                        details: format!(
+                            "DIF checkpoint mismatch on line {} of the data table, \
+                             expected {} but found {} ({})",
+                            number + 1,
+                            last,
+                            first,
+                            line.trim()
+                        ),
+                        line: Some(line_position.0),
+                        offset: Some(line_position.1),
+                    })
+                    .into());
+                }
+                (true, _, _) => intensities.extend(decoded.into_iter().skip(1)),
+                (false, _, _) => intensities.extend(decoded),
+            }
+            checkpoint = ENCODING[7].is_match(line);
+        }
+
+        Ok(intensities
+            .into_iter()
+            .map(|intensity| intensity * factor)
+            .collect())
     }
 
     /// Maps the SQZ characters onto the respective digit and returns it as a
@@ -994,7 +1840,8 @@ impl JcampDx {
     }
 
     /// Maps the DUP characters onto the respective repetitions and returns
-    /// the value repeated that many times as a string.
+    /// the value, or the DIF encoded difference, repeated that many times as
+    /// a string.
     fn undo_dup(value: &str, encoded: &str) -> String {
         let mut decoded = match encoded.chars().next().unwrap() {
             'S' => "1",
@@ -1048,44 +1895,89 @@ impl JcampDx {
         format!(" {} {}", value, value + difference)
     }
 
-    /// Internal helper function to decrement a DUP encoded value by one.
-    ///
-    /// This is used to remove the data integrity checkpoints in the encoded
-    /// data if the last value in a line is a DUP of a previous DIF.
-    fn decrement_dup(encoded: &str) -> String {
-        let mut decoded = match encoded.chars().next().unwrap() {
-            'S' => "1",
-            'T' => "2",
-            'U' => "3",
-            'V' => "4",
-            'W' => "5",
-            'X' => "6",
-            'Y' => "7",
-            'Z' => "8",
-            's' => "9",
-            _ => unreachable!("Invalid DUP character: {}", encoded),
+    /// Maps the leading digit of the value onto the respective SQZ character
+    /// and returns the encoded value as a string.
+    fn do_sqz(value: i64) -> String {
+        let digits = value.unsigned_abs().to_string();
+        let mut encoded = match (value.is_negative(), &digits[..1]) {
+            (_, "0") => "@",
+            (false, "1") => "A",
+            (false, "2") => "B",
+            (false, "3") => "C",
+            (false, "4") => "D",
+            (false, "5") => "E",
+            (false, "6") => "F",
+            (false, "7") => "G",
+            (false, "8") => "H",
+            (false, "9") => "I",
+            (true, "1") => "a",
+            (true, "2") => "b",
+            (true, "3") => "c",
+            (true, "4") => "d",
+            (true, "5") => "e",
+            (true, "6") => "f",
+            (true, "7") => "g",
+            (true, "8") => "h",
+            (true, "9") => "i",
+            _ => unreachable!("Non-numeric leading character in formatted i64: {}", digits),
         }
         .to_string();
-        decoded.extend(encoded.chars().skip(1));
-        let decremented = (decoded.parse::<usize>().unwrap() - 1).to_string();
-        let mut encoded = match decremented.chars().next().unwrap() {
-            '0' => "",
-            '1' => "S",
-            '2' => "T",
-            '3' => "U",
-            '4' => "V",
-            '5' => "W",
-            '6' => "X",
-            '7' => "Y",
-            '8' => "Z",
-            '9' => "s",
-            _ => unreachable!(
-                "Non-numeric leading character in parsed usize: {}",
-                decremented
-            ),
+        encoded.push_str(&digits[1..]);
+
+        encoded
+    }
+
+    /// Maps the leading digit of the difference onto the respective DIF
+    /// character and returns the encoded difference as a string.
+    fn do_dif(difference: i64) -> String {
+        let digits = difference.unsigned_abs().to_string();
+        let mut encoded = match (difference.is_negative(), &digits[..1]) {
+            (_, "0") => "%",
+            (false, "1") => "J",
+            (false, "2") => "K",
+            (false, "3") => "L",
+            (false, "4") => "M",
+            (false, "5") => "N",
+            (false, "6") => "O",
+            (false, "7") => "P",
+            (false, "8") => "Q",
+            (false, "9") => "R",
+            (true, "1") => "j",
+            (true, "2") => "k",
+            (true, "3") => "l",
+            (true, "4") => "m",
+            (true, "5") => "n",
+            (true, "6") => "o",
+            (true, "7") => "p",
+            (true, "8") => "q",
+            (true, "9") => "r",
+            _ => unreachable!("Non-numeric leading character in formatted i64: {}", digits),
         }
         .to_string();
-        encoded.extend(decremented.chars().skip(1));
+        encoded.push_str(&digits[1..]);
+
+        encoded
+    }
+
+    /// Maps the leading digit of the number of repetitions onto the
+    /// respective DUP character and returns the encoded repetitions as a
+    /// string.
+    fn do_dup(repetitions: usize) -> String {
+        let digits = repetitions.to_string();
+        let mut encoded = match &digits[..1] {
+            "1" => "S",
+            "2" => "T",
+            "3" => "U",
+            "4" => "V",
+            "5" => "W",
+            "6" => "X",
+            "7" => "Y",
+            "8" => "Z",
+            "9" => "s",
+            _ => unreachable!("Invalid number of repetitions: {}", digits),
+        }
+        .to_string();
+        encoded.push_str(&digits[1..]);
 
         encoded
     }
@@ -1095,7 +1987,9 @@ impl JcampDx {
 mod tests {
     use super::*;
     use crate::check_blood_spectrum;
+    use crate::spectrum::meta::Monotonicity;
     use float_cmp::assert_approx_eq;
+    use std::fs::read_to_string;
 
     #[test]
     fn read_affn_spectrum() {
@@ -1165,17 +2059,163 @@ mod tests {
             });
     }
 
+    #[test]
+    fn read_strict() {
+        let difdup_paths = [
+            "../data/jcamp-dx/test/v5/xydata_difdup.dx",
+            "../data/jcamp-dx/test/v6/xydata_difdup.dx",
+            "../data/jcamp-dx/test/v5/ntuples_difdup.dx",
+            "../data/jcamp-dx/test/v6/ntuples_difdup.dx",
+        ];
+        difdup_paths.into_iter().for_each(|path| {
+            let strict = JcampDx::read_spectrum_strict(path, (1.0, 1.1)).unwrap();
+            let lenient = JcampDx::read_spectrum(path, (1.0, 1.1)).unwrap();
+            assert_eq!(strict.intensities(), lenient.intensities());
+        });
+        let path = "../data/jcamp-dx/test/v5/xydata_difdup_corrupted.dx";
+        let spectrum = JcampDx::read_spectrum(path, (2.0, 4.0)).unwrap();
+        assert_eq!(spectrum.len(), 65);
+        assert_approx_eq!(f64, spectrum.intensities()[24], 4951.0);
+        match JcampDx::read_spectrum_strict(path, (2.0, 4.0)).unwrap_err() {
+            crate::Error::Spectrum(inner) => match inner.kind() {
+                Kind::MalformedData { details, .. } => {
+                    assert!(details.contains("line 4"));
+                    assert!(details.contains("I902K992"));
+                }
+                _ => panic!("Unexpected kind: {:?}", inner),
+            },
+            error => panic!("Unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn read_truncated_data() {
+        let path = "../data/jcamp-dx/test/v5/xydata_truncated.dx";
+        match JcampDx::read_spectrum(path, (2.0, 4.0)).unwrap_err() {
+            crate::Error::Spectrum(inner) => match inner.kind() {
+                Kind::MalformedData { path, details, .. } => {
+                    assert!(path.ends_with("xydata_truncated.dx"));
+                    assert!(details.contains("expected 65"));
+                    assert!(details.contains("decoded 62"));
+                }
+                _ => panic!("Unexpected kind: {:?}", inner),
+            },
+            error => panic!("Unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn read_malformed_data() {
+        let expected = [
+            (
+                "../data/jcamp-dx/test/v5/xydata_malformed_affn.dx",
+                20,
+                "174.6.7",
+            ),
+            (
+                "../data/jcamp-dx/test/v5/xydata_malformed_asdf.dx",
+                18,
+                "72 A110",
+            ),
+        ];
+        expected
+            .into_iter()
+            .for_each(|(path, expected_line, token)| {
+                let error = JcampDx::read_spectrum(path, (2.0, 4.0)).unwrap_err();
+                match &error {
+                    crate::Error::Spectrum(inner) => match inner.kind() {
+                        Kind::MalformedData { line, offset, .. } => {
+                            let dx = read_to_string(path).unwrap();
+                            let offset = offset.unwrap();
+                            assert_eq!(*line, Some(expected_line));
+                            assert_eq!(dx[..offset].matches('\n').count() + 1, expected_line);
+                            assert!(dx[offset..].starts_with(token));
+                        }
+                        _ => panic!("Unexpected kind: {:?}", inner),
+                    },
+                    error => panic!("Unexpected error: {:?}", error),
+                }
+                assert!(
+                    error
+                        .to_string()
+                        .contains(&format!(".dx:{}", expected_line))
+                );
+            });
+        let path = "../data/jcamp-dx/test/v5/xydata_malformed_asdf.dx";
+        match JcampDx::read_spectrum_strict(path, (2.0, 4.0)).unwrap_err() {
+            crate::Error::Spectrum(inner) => match inner.kind() {
+                Kind::MalformedData { line, .. } => assert_eq!(*line, Some(18)),
+                _ => panic!("Unexpected kind: {:?}", inner),
+            },
+            error => panic!("Unexpected error: {:?}", error),
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_gzip_spectrum() {
+        let path = "../data/jcamp-dx/test/gzip/xydata_scientific.dx.gz";
+        let reference_path = "../data/jcamp-dx/test/v5/xydata_scientific.dx";
+        let spectrum = JcampDx::read_spectrum(path, (2.0, 4.0)).unwrap();
+        let reference = JcampDx::read_spectrum(reference_path, (2.0, 4.0)).unwrap();
+        assert_eq!(spectrum.chemical_shifts(), reference.chemical_shifts());
+        assert_eq!(spectrum.intensities(), reference.intensities());
+        let spectra = JcampDx::read_spectra("../data/jcamp-dx/test/gzip", (2.0, 4.0)).unwrap();
+        assert_eq!(spectra.len(), 1);
+        assert_eq!(spectra[0].intensities(), reference.intensities());
+    }
+
+    #[test]
+    fn read_scientific_notation() {
+        let path = "../data/jcamp-dx/test/v5/xydata_scientific.dx";
+        let dx = read_to_string(path).unwrap();
+        let xy_data = JcampDx::read_xydata(&dx, path).unwrap();
+        assert_approx_eq!(f64, xy_data.factor, 0.5);
+        assert_approx_eq!(f64, xy_data.first, 3000.0);
+        assert_approx_eq!(f64, xy_data.last, 600.0);
+        let spectrum = JcampDx::read_spectrum(path, (2.0, 4.0)).unwrap();
+        assert_eq!(spectrum.len(), 65);
+        assert_approx_eq!(f64, spectrum.frequency(), 600.0);
+        assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 5.0);
+        assert_approx_eq!(f64, spectrum.chemical_shifts()[64], 1.0);
+        assert_approx_eq!(f64, spectrum.chemical_shifts()[32], 3.0);
+        assert_approx_eq!(f64, spectrum.intensities()[32], 500000.0);
+    }
+
+    #[test]
+    fn read_negative_chemical_shifts() {
+        let xy_data =
+            JcampDx::read_spectrum("../data/jcamp-dx/test/v5/xydata_negative.dx", (-1.0, 3.0))
+                .unwrap();
+        assert_eq!(xy_data.len(), 65);
+        assert_eq!(xy_data.monotonicity(), Monotonicity::Increasing);
+        assert_approx_eq!(f64, xy_data.range().0, -2.0);
+        assert_approx_eq!(f64, xy_data.range().1, 4.0);
+        assert_approx_eq!(f64, xy_data.chemical_shifts()[32], 1.0);
+        assert_approx_eq!(f64, xy_data.intensities()[32], 1000000.0);
+        let n_tuples =
+            JcampDx::read_spectrum("../data/jcamp-dx/test/v5/ntuples_negative.dx", (1.0, -3.0))
+                .unwrap();
+        assert_eq!(n_tuples.len(), 65);
+        assert_eq!(n_tuples.monotonicity(), Monotonicity::Decreasing);
+        assert_approx_eq!(f64, n_tuples.range().0, 2.0);
+        assert_approx_eq!(f64, n_tuples.range().1, -4.0);
+        assert_approx_eq!(f64, n_tuples.chemical_shifts()[32], -1.0);
+        assert_approx_eq!(f64, n_tuples.intensities()[32], 1000000.0);
+    }
+
     #[test]
     fn read_header() {
         let path = "../data/jcamp-dx/test/v6/ntuples_difdup.dx";
         let dx = read_to_string(path).unwrap();
         let header = JcampDx::read_header(&dx, path).unwrap();
         match header.data_type {
-            DataType::Spectrum => (),
+            JcampDxDataType::Spectrum => (),
+            JcampDxDataType::Fid => panic!("Expected Spectrum"),
         };
         match header.format {
-            Format::XYData => panic!("Expected NTuples"),
-            Format::NTuples => (),
+            JcampDxFormat::XYData => panic!("Expected NTuples"),
+            JcampDxFormat::NTuples => (),
         };
         assert_approx_eq!(f64, header.frequency, 600.252821089118);
         assert_eq!(header.nucleus, Nucleus::Hydrogen1);
@@ -1192,6 +2232,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_metadata() {
+        let path = "../data/jcamp-dx/test/v6/ntuples_difdup.dx";
+        let metadata = JcampDx::read_metadata(path).unwrap();
+        assert_approx_eq!(f64, metadata.version(), 6.0);
+        assert_eq!(metadata.data_type(), JcampDxDataType::Spectrum);
+        assert_eq!(metadata.format(), JcampDxFormat::NTuples);
+        assert_approx_eq!(f64, metadata.frequency(), 600.252821089118);
+        assert_eq!(metadata.nucleus(), Nucleus::Hydrogen1);
+        let reference = metadata.reference_compound().unwrap();
+        assert_approx_eq!(f64, reference.chemical_shift(), 14.81146);
+        assert_eq!(reference.index(), 0);
+        assert_eq!(reference.name(), Some("Plasma"));
+        let path = "../data/jcamp-dx/test/fid/fid_affn.dx";
+        let metadata = JcampDx::read_metadata(path).unwrap();
+        assert_eq!(metadata.data_type(), JcampDxDataType::Fid);
+        assert_eq!(metadata.format(), JcampDxFormat::NTuples);
+    }
+
+    #[test]
+    fn read_fid_header() {
+        let path = "../data/jcamp-dx/test/fid/fid_affn.dx";
+        let dx = read_to_string(path).unwrap();
+        let header = JcampDx::read_header(&dx, path).unwrap();
+        match header.data_type {
+            JcampDxDataType::Spectrum => panic!("Expected Fid"),
+            JcampDxDataType::Fid => (),
+        };
+        let n_tuples = JcampDx::read_ntuples(&dx, path).unwrap();
+        match n_tuples.x_units {
+            XUnits::Seconds => (),
+            _ => panic!("Expected Seconds"),
+        }
+        assert_eq!(n_tuples.data_size, 512);
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn read_fid() {
+        let path = "../data/jcamp-dx/test/fid/fid_affn.dx";
+        let spectrum = JcampDx::read_spectrum(path, (0.5, 9.0)).unwrap();
+        let chemical_shifts = spectrum.chemical_shifts();
+        let intensities = spectrum.intensities();
+        assert_eq!(spectrum.len(), 512);
+        assert_approx_eq!(f64, spectrum.frequency(), 600.0);
+        assert_approx_eq!(f64, chemical_shifts[0], -0.25, epsilon = 1e-6);
+        assert_approx_eq!(f64, chemical_shifts[1] - chemical_shifts[0], 10.0 / 512.0);
+        let maximum = |range: (f64, f64)| {
+            (0..spectrum.len())
+                .filter(|&i| chemical_shifts[i] > range.0 && chemical_shifts[i] < range.1)
+                .max_by(|&a, &b| intensities[a].total_cmp(&intensities[b]))
+                .unwrap()
+        };
+        [(1.33, 1000.0), (3.41, 600.0)]
+            .into_iter()
+            .for_each(|(position, amplitude)| {
+                let peak = maximum((position - 0.5, position + 0.5));
+                assert_approx_eq!(f64, chemical_shifts[peak], position, epsilon = 0.01);
+                assert!(intensities[peak] > amplitude);
+            });
+    }
+
+    #[cfg(not(feature = "fft"))]
+    #[test]
+    fn read_fid() {
+        let path = "../data/jcamp-dx/test/fid/fid_affn.dx";
+        match JcampDx::read_spectrum(path, (0.5, 9.0)).unwrap_err() {
+            crate::Error::Spectrum(inner) => match inner.kind() {
+                Kind::UntransformableFid { .. } => (),
+                _ => panic!("Unexpected kind: {:?}", inner),
+            },
+            error => panic!("Unexpected error: {:?}", error),
+        }
+    }
+
     #[test]
     fn read_xydata() {
         let path = "../data/jcamp-dx/test/v6/xydata_difdup.dx";
@@ -1199,7 +2314,7 @@ mod tests {
         let xy_data = JcampDx::read_xydata(&dx, path).unwrap();
         match xy_data.x_units {
             XUnits::Hz => (),
-            XUnits::Ppm => panic!("Expected Hz"),
+            XUnits::Ppm | XUnits::Seconds => panic!("Expected Hz"),
         }
         assert_approx_eq!(f64, xy_data.factor, 1.0);
         assert_approx_eq!(f64, xy_data.first, 12019.1390697773);
@@ -1214,7 +2329,7 @@ mod tests {
         let n_tuples = JcampDx::read_ntuples(&dx, path).unwrap();
         match n_tuples.x_units {
             XUnits::Hz => (),
-            XUnits::Ppm => panic!("Expected Hz"),
+            XUnits::Ppm | XUnits::Seconds => panic!("Expected Hz"),
         }
         assert_approx_eq!(f64, n_tuples.factor, 1.0);
         assert_approx_eq!(f64, n_tuples.first, 12019.1390697773);
@@ -1222,6 +2337,98 @@ mod tests {
         assert_eq!(n_tuples.data_size, 2_usize.pow(17));
     }
 
+    fn write_test_spectrum() -> Spectrum {
+        let chemical_shifts = (0..2000)
+            .map(|i| 12.0 - (i as f64) * 0.005)
+            .collect::<Vec<_>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|&shift| match shift {
+                5.0..7.0 => 0.0,
+                _ => {
+                    1e6 / (1.0 + ((shift - 3.4) / 0.01).powi(2))
+                        + 4e5 / (1.0 + ((shift - 9.1) / 0.02).powi(2))
+                        - 25.0 * f64::sin(shift * 40.0)
+                }
+            })
+            .collect();
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (10.0, 3.0)).unwrap();
+        spectrum.set_frequency(600.252821089118);
+        spectrum.set_reference_compound(ReferenceCompound::new(
+            12.0,
+            0,
+            Some("TSP"),
+            Some(crate::spectrum::meta::ReferencingMethod::Internal),
+        ));
+
+        spectrum
+    }
+
+    #[test]
+    fn write_other_nucleus() {
+        let mut spectrum = write_test_spectrum();
+        spectrum.set_nucleus("2H");
+        let path = std::env::temp_dir().join("metabodecon_jcampdx_other_nucleus.dx");
+        JcampDx::write_spectrum(&path, &spectrum).unwrap();
+        let dx = read_to_string(&path).unwrap();
+        assert!(dx.contains("##.OBSERVE NUCLEUS= ^2H"));
+        let read = JcampDx::read_spectrum(&path, (10.0, 3.0)).unwrap();
+        assert_eq!(read.nucleus(), Nucleus::Other("2H".to_string()));
+    }
+
+    #[test]
+    fn write_spectrum() {
+        let spectrum = write_test_spectrum();
+        let tolerance = 1e6 / INTEGER_SCALE;
+        [
+            ("affn", JcampDxCompression::Affn),
+            ("difdup", JcampDxCompression::DifDup),
+        ]
+        .into_iter()
+        .for_each(|(name, compression)| {
+            let path = std::env::temp_dir().join(format!("metabodecon_jcampdx_{}.dx", name));
+            JcampDx::write_compressed_spectrum(&path, &spectrum, compression).unwrap();
+            let dx = read_to_string(&path).unwrap();
+            assert!(dx.lines().all(|line| line.len() <= LINE_LENGTH));
+            let read = JcampDx::read_spectrum(&path, (10.0, 3.0)).unwrap();
+            assert_eq!(read.len(), spectrum.len());
+            assert_eq!(read.nucleus(), spectrum.nucleus());
+            assert_approx_eq!(f64, read.frequency(), spectrum.frequency());
+            assert_eq!(read.reference_compound().name(), Some("TSP"));
+            read.chemical_shifts()
+                .iter()
+                .zip(spectrum.chemical_shifts())
+                .for_each(|(read, written)| {
+                    assert_approx_eq!(f64, *read, *written, epsilon = 1e-9);
+                });
+            read.intensities()
+                .iter()
+                .zip(spectrum.intensities())
+                .for_each(|(read, written)| {
+                    assert_approx_eq!(f64, *read, *written, epsilon = tolerance);
+                });
+        });
+    }
+
+    #[test]
+    fn encode_difdup() {
+        let x = (0..20).rev().collect::<Vec<i64>>();
+        let y = [
+            482, -763, 215, -632, -924, 357, 357, 357, 357, 841, 512, -194, 321, -467, -689, 278,
+            278, 0, 835, 835,
+        ];
+        let encoded = JcampDx::encode_difdup(&x, &y);
+        assert!(encoded.contains("%U"));
+        let decoded = JcampDx::decode_asdf(&encoded, 1.0, "encode_difdup_test", None).unwrap();
+        assert_eq!(decoded.len(), y.len());
+        decoded
+            .into_iter()
+            .zip(y)
+            .for_each(|(decoded, expected)| {
+                assert_approx_eq!(f64, decoded, expected as f64);
+            });
+    }
+
     #[test]
     fn decode_affn() {
         let data = "\
@@ -1234,7 +2441,7 @@ mod tests {
             482.0, -763.0, 215.0, -632.0, -924.0, 357.0, -678.0, 841.0, 512.0, -194.0, 321.0,
             -467.0, -689.0, 278.0, 278.0, 732.0, 835.0, -619.0, 247.0, -193.0,
         ];
-        let decoded = JcampDx::decode_affn(data, 1.0, "decode_affn_test").unwrap();
+        let decoded = JcampDx::decode_affn(data, 1.0, "decode_affn_test", None).unwrap();
         decoded
             .into_iter()
             .zip(expected)
@@ -1252,7 +2459,7 @@ mod tests {
             482.0, -763.0, 215.0, -632.0, -924.0, 357.0, -678.0, 841.0, 512.0, -194.0, 321.0,
             -467.0, -689.0, 278.0, 278.0, 732.0, 835.0, -619.0, 247.0, -193.0,
         ];
-        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_pac_test").unwrap();
+        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_pac_test", None).unwrap();
         decoded
             .into_iter()
             .zip(expected)
@@ -1270,7 +2477,7 @@ mod tests {
             482.0, -763.0, 215.0, -632.0, -924.0, 357.0, -678.0, 841.0, 512.0, -194.0, 321.0,
             -467.0, -689.0, 278.0, 278.0, 732.0, 835.0, -619.0, 247.0, -193.0,
         ];
-        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_sqz_test").unwrap();
+        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_sqz_test", None).unwrap();
         decoded
             .into_iter()
             .zip(expected)
@@ -1283,12 +2490,24 @@ mod tests {
     fn decode_dif_dup() {
         let data = "\
             19 D82j245R78q47k92J281j035J519l29p06\n\
-            10 a94N15p88k22R67TM54J03j454Q66m40";
+            10 a94N15p88k22R67%M54J03j454Q66m40";
         let expected = [
             482.0, -763.0, 215.0, -632.0, -924.0, 357.0, -678.0, 841.0, 512.0, -194.0, 321.0,
             -467.0, -689.0, 278.0, 278.0, 732.0, 835.0, -619.0, 247.0, -193.0,
         ];
-        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_sqz_test").unwrap();
+        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_sqz_test", None).unwrap();
+        decoded
+            .into_iter()
+            .zip(expected)
+            .for_each(|(decoded, expected)| {
+                assert_approx_eq!(f64, decoded, expected);
+            });
+        let data = "\
+            6 A0J0U%T\n\
+            1 D0J0TK0";
+        let expected = [10.0, 20.0, 30.0, 40.0, 40.0, 40.0, 50.0, 60.0, 80.0];
+        let decoded = JcampDx::decode_asdf(data, 1.0, "decode_dif_dup_test", None).unwrap();
+        assert_eq!(decoded.len(), expected.len());
         decoded
             .into_iter()
             .zip(expected)
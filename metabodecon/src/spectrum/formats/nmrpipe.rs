@@ -0,0 +1,450 @@
+use crate::Result;
+use crate::spectrum::Spectrum;
+use crate::spectrum::error::{Error, Kind};
+use crate::spectrum::meta::Nucleus;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use std::ffi::OsStr;
+use std::fs::read;
+use std::path::Path;
+
+/// Interface for reading 1D NMR spectra in the NMRPipe format.
+///
+/// # Format
+///
+/// NMRPipe stores a spectrum in a single binary file, usually with the `.ft`
+/// extension. The file starts with a header of 512 32-bit floating point
+/// numbers, followed by the data as 32-bit floating point numbers. Text fields
+/// of the header, such as the axis label, are stored as raw characters within
+/// the floating point slots.
+///
+/// ## Metadata
+///
+/// The following header fields are required, given with their positions in
+/// the header:
+/// * `FDFLTORDER` (2): The constant `2.345`, which is used to detect the byte
+///   order of the file.
+/// * `FDSIZE` (99): The number of points of the spectrum.
+/// * `FDF2SW` (100): The sweep width in Hz.
+/// * `FDF2ORIG` (101): The frequency of the last point of the spectrum in Hz.
+/// * `FDF2OBS` (119): The observe frequency in MHz.
+///
+/// Additionally, the observed nucleus is read from the `FDF2LABEL` (16-17)
+/// field, if it is set.
+///
+/// ## Raw Data
+///
+/// The data is stored directly after the header in the byte order detected
+/// from the `FDFLTORDER` field. If the data is complex, the real part is
+/// stored first and only it is read.
+///
+/// # Example: Reading a Spectrum
+///
+/// ```
+/// use metabodecon::spectrum::NmrPipe;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/spectrum.ft";
+/// # let path = "../data/nmrpipe/sim/sim_01.ft";
+///
+/// // Read a single spectrum from an NMRPipe file.
+/// let spectrum = NmrPipe::read_spectrum(
+///     path,
+///     // Signal boundaries
+///     (-2.2, 11.8),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Example: Reading Multiple Spectra
+///
+/// ```
+/// use metabodecon::spectrum::NmrPipe;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/root";
+/// # let path = "../data/nmrpipe/sim";
+///
+/// // Read all spectra from NMRPipe files within the root.
+/// let spectra = NmrPipe::read_spectra(
+///     path,
+///     // Signal boundaries
+///     (-2.2, 11.8),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum NmrPipe {}
+
+/// Endianness of the file. Detected from the `FDFLTORDER` header field.
+#[derive(Debug)]
+enum Endian {
+    /// Little-endian byte order.
+    Little,
+    /// Big-endian byte order.
+    Big,
+}
+
+/// Number of 32-bit values in the header.
+const HEADER_SIZE: usize = 512;
+
+/// Expected value of the `FDFLTORDER` header field.
+const FLOAT_ORDER: f32 = 2.345;
+
+/// Positions and names of the header fields.
+mod field {
+    /// Byte order check value.
+    pub(super) const FLTORDER: (usize, &str) = (2, "FDFLTORDER");
+    /// Axis label of the direct dimension.
+    pub(super) const F2LABEL: (usize, &str) = (16, "FDF2LABEL");
+    /// Number of points.
+    pub(super) const SIZE: (usize, &str) = (99, "FDSIZE");
+    /// Sweep width in Hz.
+    pub(super) const F2SW: (usize, &str) = (100, "FDF2SW");
+    /// Frequency of the last point in Hz.
+    pub(super) const F2ORIG: (usize, &str) = (101, "FDF2ORIG");
+    /// Observe frequency in MHz.
+    pub(super) const F2OBS: (usize, &str) = (119, "FDF2OBS");
+}
+
+/// Parameters extracted from the header.
+#[derive(Debug)]
+struct Header {
+    /// Endianness of the file.
+    endian: Endian,
+    /// Spectral width in ppm.
+    width: f64,
+    /// Maximum chemical shift in ppm.
+    maximum: f64,
+    /// Frequency of the spectrometer in MHz.
+    frequency: f64,
+    /// Nucleus observed in the NMR experiment, if the label is set.
+    nucleus: Option<Nucleus>,
+    /// Number of data points.
+    data_size: usize,
+}
+
+impl NmrPipe {
+    /// Reads the spectrum from an NMRPipe file.
+    ///
+    /// # Errors
+    ///
+    /// The read data is checked for validity to ensure that the `Spectrum` is
+    /// well-formed and in a consistent state. The following conditions are
+    /// checked:
+    /// - The Intensities are not empty.
+    /// - All intensity values are finite.
+    /// - The signal region boundaries are within the range of the chemical
+    ///   shifts.
+    /// - The header is complete, the byte order can be detected, and the
+    ///   required fields contain valid values.
+    /// - The file contains as many data points as specified in the header.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::NmrPipe;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum.ft";
+    /// # let path = "../data/nmrpipe/sim/sim_01.ft";
+    ///
+    /// // Read a single spectrum from an NMRPipe file.
+    /// let spectrum = NmrPipe::read_spectrum(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectrum<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Spectrum> {
+        let bytes = read(path.as_ref())?;
+        let header = Self::read_header(&path, &bytes)?;
+        let chemical_shifts = (0..header.data_size)
+            .map(|i| header.maximum - (i as f64) * header.width / (header.data_size as f64))
+            .collect();
+        let intensities = Self::read_data(&path, &bytes, &header)?;
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
+        if let Some(nucleus) = header.nucleus {
+            spectrum.set_nucleus(nucleus);
+        }
+        spectrum.set_frequency(header.frequency);
+
+        Ok(spectrum)
+    }
+
+    /// Reads all spectra from the NMRPipe files in the given directory.
+    ///
+    /// Only files with the `.ft` or `.ft1` extension are read.
+    ///
+    /// # Errors
+    ///
+    /// The read data is checked for validity to ensure that the `Spectrum` is
+    /// well-formed and in a consistent state. The following conditions are
+    /// checked:
+    /// - The Intensities are not empty.
+    /// - All intensity values are finite.
+    /// - The signal region boundaries are within the range of the chemical
+    ///   shifts.
+    /// - The header is complete, the byte order can be detected, and the
+    ///   required fields contain valid values.
+    /// - The file contains as many data points as specified in the header.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::NmrPipe;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/root";
+    /// # let path = "../data/nmrpipe/sim";
+    ///
+    /// // Read all spectra from NMRPipe files within the root.
+    /// let spectra = NmrPipe::read_spectra(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectra<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Vec<Spectrum>> {
+        let spectra = path
+            .as_ref()
+            .read_dir()?
+            .filter_map(|entry| {
+                entry.ok().and_then(|entry| {
+                    entry
+                        .path()
+                        .extension()
+                        .and_then(OsStr::to_str)
+                        .and_then(|extension| match extension.to_lowercase().as_str() {
+                            "ft" | "ft1" => Some(entry.path()),
+                            _ => None,
+                        })
+                })
+            })
+            .map(|entry| Self::read_spectrum(entry, signal_boundaries))
+            .collect::<Result<Vec<Spectrum>>>()?;
+
+        Ok(spectra)
+    }
+
+    /// Internal helper function to read the parameters from the header.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedMetaData`](crate::spectrum::error::Kind::MalformedMetadata)
+    fn read_header<P: AsRef<Path>>(path: P, bytes: &[u8]) -> Result<Header> {
+        let malformed_error = |key: &str, details: String| {
+            Error::new(Kind::MalformedMetadata {
+                path: path.as_ref().to_path_buf(),
+                key: key.to_string(),
+                details,
+            })
+        };
+        if bytes.len() < HEADER_SIZE * 4 {
+            return Err(malformed_error(
+                "header",
+                format!(
+                    "file contains {} bytes, but the header requires {}",
+                    bytes.len(),
+                    HEADER_SIZE * 4
+                ),
+            )
+            .into());
+        }
+        let order = |endian: &Endian| Self::field(bytes, field::FLTORDER.0, endian);
+        let endian = [Endian::Little, Endian::Big]
+            .into_iter()
+            .find(|endian| f32::abs(order(endian) - FLOAT_ORDER) < 1e-5)
+            .ok_or_else(|| {
+                malformed_error(
+                    field::FLTORDER.1,
+                    format!("byte order check value is not {}", FLOAT_ORDER),
+                )
+            })?;
+        let value = |(position, key): (usize, &str)| {
+            let value = Self::field(bytes, position, &endian) as f64;
+            match value.is_finite() {
+                true => Ok(value),
+                false => Err(malformed_error(key, format!("{} is not finite", value))),
+            }
+        };
+        let data_size = value(field::SIZE)?;
+        let width = value(field::F2SW)?;
+        let origin = value(field::F2ORIG)?;
+        let frequency = value(field::F2OBS)?;
+        if data_size < 1.0 || data_size.fract() != 0.0 {
+            return Err(malformed_error(
+                field::SIZE.1,
+                format!("{} is not a positive integer", data_size),
+            )
+            .into());
+        }
+        if frequency <= 0.0 {
+            return Err(
+                malformed_error(field::F2OBS.1, format!("{} is not positive", frequency)).into(),
+            );
+        }
+        let label = String::from_utf8_lossy(&bytes[field::F2LABEL.0 * 4..field::F2LABEL.0 * 4 + 8])
+            .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+            .to_string();
+        let data_size = data_size as usize;
+
+        Ok(Header {
+            endian,
+            width: width / frequency,
+            maximum: (origin + width * (data_size as f64 - 1.0) / data_size as f64) / frequency,
+            frequency,
+            nucleus: (!label.is_empty()).then(|| Nucleus::from(label)),
+            data_size,
+        })
+    }
+
+    /// Internal helper function to read a single header field in the given
+    /// byte order.
+    fn field(bytes: &[u8], position: usize, endian: &Endian) -> f32 {
+        let bytes = &bytes[position * 4..position * 4 + 4];
+        match endian {
+            Endian::Little => LittleEndian::read_f32(bytes),
+            Endian::Big => BigEndian::read_f32(bytes),
+        }
+    }
+
+    /// Internal helper function to read the data following the header and
+    /// return it as a vector of floating point numbers.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedData`](crate::spectrum::error::Kind::MalformedData)
+    fn read_data<P: AsRef<Path>>(path: P, bytes: &[u8], header: &Header) -> Result<Vec<f64>> {
+        let mut data = &bytes[HEADER_SIZE * 4..];
+        if data.len() < header.data_size * 4 {
+            return Err(Error::new(Kind::MalformedData {
+                path: path.as_ref().to_path_buf(),
+                details: format!(
+                    "file contains {} data points, but the header specifies {}",
+                    data.len() / 4,
+                    header.data_size
+                ),
+                line: None,
+                offset: None,
+            })
+            .into());
+        }
+        let mut temp = vec![0_f32; header.data_size];
+        match header.endian {
+            Endian::Little => data.read_f32_into::<LittleEndian>(&mut temp)?,
+            Endian::Big => data.read_f32_into::<BigEndian>(&mut temp)?,
+        }
+
+        Ok(temp
+            .into_iter()
+            .map(|value| value as f64)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use float_cmp::assert_approx_eq;
+
+    fn check_spectrum(spectrum: &Spectrum) {
+        assert_eq!(spectrum.len(), 2048);
+        assert_approx_eq!(
+            f64,
+            spectrum.chemical_shifts()[0],
+            15.013836806928364,
+            epsilon = 1e-6
+        );
+        assert_approx_eq!(f64, spectrum.chemical_shifts()[2047], -5.0, epsilon = 1e-6);
+        assert_approx_eq!(f64, spectrum.frequency(), 600.252821089118, epsilon = 1e-4);
+        assert_eq!(spectrum.nucleus(), Nucleus::Hydrogen1);
+        let maximum = spectrum
+            .intensities()
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert_approx_eq!(
+            f64,
+            spectrum.chemical_shifts()[maximum],
+            3.41,
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn read_spectrum() {
+        let little_endian =
+            NmrPipe::read_spectrum("../data/nmrpipe/sim/sim_01.ft", (-2.2, 11.8)).unwrap();
+        let big_endian =
+            NmrPipe::read_spectrum("../data/nmrpipe/sim/sim_02.ft", (-2.2, 11.8)).unwrap();
+        check_spectrum(&little_endian);
+        check_spectrum(&big_endian);
+    }
+
+    #[test]
+    fn read_spectra() {
+        let spectra = NmrPipe::read_spectra("../data/nmrpipe/sim", (-2.2, 11.8)).unwrap();
+        assert_eq!(spectra.len(), 2);
+        spectra.iter().for_each(check_spectrum);
+    }
+
+    #[test]
+    fn malformed_header() {
+        let path = "../data/nmrpipe/sim/sim_01.ft";
+        let bytes = read(path).unwrap();
+        let mut wrong_order = bytes.clone();
+        wrong_order[8..12].copy_from_slice(&1.0_f32.to_le_bytes());
+        let mut wrong_size = bytes.clone();
+        wrong_size[396..400].copy_from_slice(&(-3.0_f32).to_le_bytes());
+        let errors = [
+            (NmrPipe::read_header(path, &bytes[..1024]), "header"),
+            (NmrPipe::read_header(path, &wrong_order), "FDFLTORDER"),
+            (NmrPipe::read_header(path, &wrong_size), "FDSIZE"),
+        ];
+        errors
+            .into_iter()
+            .for_each(|(result, expected)| match result.unwrap_err() {
+                Error::Spectrum(inner) => match inner.kind() {
+                    Kind::MalformedMetadata { key, .. } => assert_eq!(key, expected),
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                error => panic!("unexpected error: {:?}", error),
+            });
+        let header = NmrPipe::read_header(path, &bytes).unwrap();
+        let error = NmrPipe::read_data(path, &bytes[..4096], &header).unwrap_err();
+        match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::MalformedData { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+}
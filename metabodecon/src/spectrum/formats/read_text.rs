@@ -0,0 +1,107 @@
+use std::path::Path;
+
+#[cfg(any(feature = "gzip", feature = "jdx", feature = "csv"))]
+use std::ffi::OsStr;
+
+#[cfg(feature = "gzip")]
+use flate2::read::GzDecoder;
+#[cfg(feature = "gzip")]
+use std::io::{Error, ErrorKind, Read};
+#[cfg(feature = "gzip")]
+use std::path::PathBuf;
+
+/// Magic bytes at the start of every gzip-compressed file.
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Internal helper function to read the contents of a text file into a string.
+///
+/// If the `gzip` feature is enabled, gzip-compressed files are decompressed
+/// transparently. They are detected by the gzip magic bytes or the `.gz`
+/// extension. If the file doesn't exist, but a compressed copy with an
+/// appended `.gz` extension does, the copy is read instead.
+#[cfg(feature = "gzip")]
+pub(crate) fn read_text<P: AsRef<Path>>(path: P) -> std::io::Result<String> {
+    let path = compressed_fallback(path.as_ref());
+    let bytes = std::fs::read(&path)?;
+
+    match bytes.starts_with(&GZIP_MAGIC) || path.extension() == Some(OsStr::new("gz")) {
+        true => {
+            let mut text = String::new();
+            GzDecoder::new(bytes.as_slice()).read_to_string(&mut text)?;
+
+            Ok(text)
+        }
+        false => {
+            String::from_utf8(bytes).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+        }
+    }
+}
+
+/// Internal helper function to read the contents of a text file into a string.
+#[cfg(not(feature = "gzip"))]
+pub(crate) fn read_text<P: AsRef<Path>>(path: P) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+/// Internal helper function to return the extension of a file, ignoring a
+/// trailing `.gz` extension if the `gzip` feature is enabled.
+#[cfg(any(feature = "jdx", feature = "csv"))]
+pub(crate) fn text_extension(path: &Path) -> Option<&OsStr> {
+    #[cfg(feature = "gzip")]
+    if path.extension() == Some(OsStr::new("gz")) {
+        return path
+            .file_stem()
+            .map(Path::new)
+            .and_then(Path::extension);
+    }
+
+    path.extension()
+}
+
+/// Internal helper function to return the path of the compressed copy of a
+/// file (with an appended `.gz` extension) if the file itself doesn't exist
+/// but the copy does. Otherwise, the path is returned unchanged.
+#[cfg(feature = "gzip")]
+fn compressed_fallback(path: &Path) -> PathBuf {
+    let mut compressed = path.as_os_str().to_owned();
+    compressed.push(".gz");
+    let compressed = PathBuf::from(compressed);
+
+    match !path.exists() && compressed.is_file() {
+        true => compressed,
+        false => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "jdx", feature = "csv"))]
+    #[test]
+    fn text_extension() {
+        fn extension(path: &str) -> Option<&OsStr> {
+            super::text_extension(Path::new(path))
+        }
+        assert_eq!(extension("spectrum.dx"), Some(OsStr::new("dx")));
+        assert_eq!(extension("spectrum"), None);
+        #[cfg(feature = "gzip")]
+        {
+            assert_eq!(extension("spectrum.dx.gz"), Some(OsStr::new("dx")));
+            assert_eq!(extension("spectrum.gz"), None);
+        }
+        #[cfg(not(feature = "gzip"))]
+        assert_eq!(extension("spectrum.dx.gz"), Some(OsStr::new("gz")));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_compressed() {
+        let uncompressed = read_text("../data/jcamp-dx/test/v5/xydata_scientific.dx").unwrap();
+        let compressed = read_text("../data/jcamp-dx/test/gzip/xydata_scientific.dx.gz").unwrap();
+        let fallback = read_text("../data/jcamp-dx/test/gzip/xydata_scientific.dx").unwrap();
+        assert_eq!(compressed, uncompressed);
+        assert_eq!(fallback, uncompressed);
+    }
+}
@@ -0,0 +1,587 @@
+use crate::Result;
+use crate::spectrum::Spectrum;
+use crate::spectrum::error::{Error, Kind};
+use crate::spectrum::formats::{extract_capture, number_regex, read_text};
+use crate::spectrum::meta::Nucleus;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use regex::Regex;
+use std::fs::read;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Interface for reading 1D NMR spectra in the Varian/Agilent VnmrJ format.
+///
+/// # Format
+///
+/// The VnmrJ file format stores the parameters and data of an experiment in a
+/// directory. Only the following files are required to read a spectrum:
+///
+/// ```text
+/// name
+/// └── name_01
+///     ├── datdir
+///     │   └── phasefile
+///     └── procpar
+/// ```
+///
+/// `name` is the name of the dataset, which can be any string. `name_01` is
+/// the directory of the sample, for example an experiment directory or a saved
+/// `.fid` directory that also contains the processed data.
+///
+/// ## Metadata
+///
+/// The `procpar` file contains the acquisition and processing parameters. It is
+/// a plain text file, where each parameter is stored as a line with the name
+/// and 10 attributes of the parameter, followed by a line with the number of
+/// values and the values themselves, and a line with the enumeration values.
+///
+/// ```text
+/// sw 1 1 1e+09 -1e+09 0 2 1 8 1 64
+/// 1 12019.2307692
+/// 0
+/// ```
+///
+/// The following parameters are required:
+/// * `sw`: The spectral width in Hz as a floating point number.
+/// * `sfrq`: The transmitter frequency of the observed nucleus in MHz.
+/// * `tn`: The observed nucleus, e.g. `"H1"`.
+/// * `fn`: The Fourier number, which is twice the number of real data points.
+/// * `rfl`: The position of the reference signal in Hz, measured from the
+///   right (lowest chemical shift) edge of the spectrum.
+/// * `rfp`: The frequency of the reference signal in Hz.
+///
+/// ## Raw Data
+///
+/// The phased real spectrum is stored in the `datdir/phasefile` file in binary
+/// format. The file starts with a 32 byte file header, followed by a block that
+/// consists of one or more 28 byte block headers and the data. The file header
+/// specifies the number of points, the number of bytes per point and how the
+/// data is stored. Whether the data is stored as 16-bit integers, 32-bit
+/// integers, or 32-bit floating point numbers is read from the status bits of
+/// the file header. The data is usually stored in big-endian byte order, but
+/// files written in little-endian byte order are detected and read as well.
+///
+/// # Example: Reading a Spectrum
+///
+/// ```
+/// use metabodecon::spectrum::Varian;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/spectrum";
+/// # let path = "../data/varian/sim/sim_01";
+///
+/// // Read a single spectrum from a VnmrJ format directory.
+/// let spectrum = Varian::read_spectrum(
+///     path,
+///     // Signal boundaries
+///     (-2.2, 11.8),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Example: Reading Multiple Spectra
+///
+/// ```
+/// use metabodecon::spectrum::Varian;
+///
+/// # fn main() -> metabodecon::Result<()> {
+/// let path = "path/to/root";
+/// # let path = "../data/varian/sim";
+///
+/// // Read all spectra from VnmrJ format directories within the root.
+/// let spectra = Varian::read_spectra(
+///     path,
+///     // Signal boundaries
+///     (-2.2, 11.8),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum Varian {}
+
+/// Endianness of the raw data. Detected from the file header of the
+/// `phasefile`.
+#[derive(Debug)]
+enum Endian {
+    /// Little-endian byte order.
+    Little,
+    /// Big-endian byte order.
+    Big,
+}
+
+/// Data type of the raw data. Extracted from the status bits and the number of
+/// bytes per point in the file header of the `phasefile`.
+///
+/// | Status Bits | Bytes | Type |
+/// | ----------- | ----- | ---- |
+/// | `S_FLOAT`   | 4     | f32  |
+/// | `S_32`      | 4     | i32  |
+/// | none        | 2     | i16  |
+#[derive(Debug)]
+enum Type {
+    /// Data stored as 16-bit integers.
+    I16,
+    /// Data stored as 32-bit integers.
+    I32,
+    /// Data stored as 32-bit floating point numbers.
+    F32,
+}
+
+/// Status bit of the file header indicating 32-bit integer data.
+const S_32: i16 = 0x4;
+
+/// Status bit of the file header indicating floating point data.
+const S_FLOAT: i16 = 0x8;
+
+/// Size of the file header of the `phasefile` in bytes.
+const FILE_HEADER_SIZE: usize = 32;
+
+/// Size of a block header of the `phasefile` in bytes.
+const BLOCK_HEADER_SIZE: usize = 28;
+
+/// Parameters extracted from the `procpar` file.
+#[derive(Debug)]
+struct Parameters {
+    /// Spectral width in ppm.
+    width: f64,
+    /// Maximum chemical shift in ppm.
+    maximum: f64,
+    /// Frequency of the spectrometer in MHz.
+    frequency: f64,
+    /// Nucleus observed in the NMR experiment.
+    nucleus: Nucleus,
+    /// Number of real data points.
+    data_size: usize,
+}
+
+/// Layout of the raw data extracted from the file header of the `phasefile`.
+#[derive(Debug)]
+struct FileHeader {
+    /// Endianness of the data.
+    endian: Endian,
+    /// Data type of the raw signal intensities.
+    data_type: Type,
+    /// Number of data points.
+    data_size: usize,
+    /// Offset of the first data point in bytes.
+    offset: usize,
+}
+
+/// Regex patterns to search for the parameters.
+static PROCPAR_RE: LazyLock<[Regex; 6]> = LazyLock::new(|| {
+    let real = |name: &str, capture: &str| {
+        number_regex(&format!(r"{}(\s+\S+){{10}}\s*\n\s*\d+\s+", name), capture)
+    };
+    [
+        real("sw", "width"),
+        real("sfrq", "frequency"),
+        Regex::new(r#"(?m)^(tn(\s+\S+){10}\s*\n\s*\d+\s+")(?P<nucleus>\w+)"#).unwrap(),
+        real("fn", "data_size"),
+        real("rfl", "reference_position"),
+        real("rfp", "reference_frequency"),
+    ]
+});
+
+/// Keys used in the parameter regex patterns, used for error messages
+static PROCPAR_KEYS: LazyLock<[&str; 6]> =
+    LazyLock::new(|| ["sw", "sfrq", "tn", "fn", "rfl", "rfp"]);
+
+impl Varian {
+    /// Reads the spectrum from a VnmrJ format directory.
+    ///
+    /// ```text
+    /// name
+    /// └── name_01 ← the path needs to point to this directory
+    ///     ├── datdir
+    ///     │   └── phasefile
+    ///     └── procpar
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The read data is checked for validity to ensure that the `Spectrum` is
+    /// well-formed and in a consistent state. The following conditions are
+    /// checked:
+    /// - The Intensities are not empty.
+    /// - The lengths of the chemical shifts and intensities match. The data
+    ///   size is read from the `procpar` file and used to generate the chemical
+    ///   shifts. It also has to match the number of points in the file header
+    ///   of the `phasefile`.
+    /// - All intensity values are finite.
+    /// - The signal region boundaries are within the range of the chemical
+    ///   shifts.
+    /// - All required parameters are extracted from the `procpar` file.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Varian;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/spectrum";
+    /// # let path = "../data/varian/sim/sim_01";
+    ///
+    /// // Read a single spectrum from a VnmrJ format directory.
+    /// let spectrum = Varian::read_spectrum(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectrum<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Spectrum> {
+        let procpar_path = path.as_ref().join("procpar");
+        let phasefile_path = path.as_ref().join("datdir/phasefile");
+
+        let parameters = Self::read_parameters(procpar_path)?;
+        let chemical_shifts = (0..parameters.data_size)
+            .map(|i| {
+                parameters.maximum
+                    - (i as f64) * parameters.width / (parameters.data_size as f64 - 1.0)
+            })
+            .collect();
+        let intensities = Self::read_phasefile(phasefile_path, parameters.data_size)?;
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
+        spectrum.set_nucleus(parameters.nucleus);
+        spectrum.set_frequency(parameters.frequency);
+
+        Ok(spectrum)
+    }
+
+    /// Reads all spectra from the VnmrJ format directories under the given
+    /// path.
+    ///
+    /// ```text
+    /// name ← the path needs to point to this directory
+    /// ├── name_01
+    /// │   ├── datdir
+    /// │   │   └── phasefile
+    /// │   └── procpar
+    /// ├── name_02
+    /// │   ├── datdir
+    /// │   │   └── phasefile
+    /// │   └── procpar
+    /// ·
+    /// ·
+    /// ·
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The read data is checked for validity to ensure that the `Spectrum` is
+    /// well-formed and in a consistent state. The following conditions are
+    /// checked:
+    /// - The Intensities are not empty.
+    /// - The lengths of the chemical shifts and intensities match. The data
+    ///   size is read from the `procpar` file and used to generate the chemical
+    ///   shifts. It also has to match the number of points in the file header
+    ///   of the `phasefile`.
+    /// - All intensity values are finite.
+    /// - The signal region boundaries are within the range of the chemical
+    ///   shifts.
+    /// - All required parameters are extracted from the `procpar` file.
+    ///
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Varian;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let path = "path/to/root";
+    /// # let path = "../data/varian/sim";
+    ///
+    /// // Read all spectra from VnmrJ format directories within the root.
+    /// let spectra = Varian::read_spectra(
+    ///     path,
+    ///     // Signal boundaries
+    ///     (-2.2, 11.8),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_spectra<P: AsRef<Path>>(
+        path: P,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Vec<Spectrum>> {
+        let spectra = path
+            .as_ref()
+            .read_dir()?
+            .filter_map(|entry| {
+                entry.ok().and_then(|entry| {
+                    if entry.path().is_dir() {
+                        Some(entry.path())
+                    } else {
+                        None
+                    }
+                })
+            })
+            .map(|entry| Self::read_spectrum(entry, signal_boundaries))
+            .collect::<Result<Vec<Spectrum>>>()?;
+
+        Ok(spectra)
+    }
+
+    /// Internal helper function to read the parameters from the `procpar` file
+    /// and return them.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MissingMetaData`](crate::spectrum::error::Kind::MissingMetadata)
+    /// - [`MalformedMetaData`](crate::spectrum::error::Kind::MalformedMetadata)
+    /// - [`Error::IoError`](crate::Error::IoError)
+    fn read_parameters<P: AsRef<Path>>(path: P) -> Result<Parameters> {
+        let procpar = read_text(path.as_ref())?;
+        let re = &*PROCPAR_RE;
+        let keys = &*PROCPAR_KEYS;
+
+        let width: f64 = extract_capture(&re[0], "width", &procpar, &path, keys[0])?;
+        let frequency: f64 = extract_capture(&re[1], "frequency", &procpar, &path, keys[1])?;
+        let nucleus = extract_capture(&re[2], "nucleus", &procpar, &path, keys[2])?;
+        let fourier_number: f64 = extract_capture(&re[3], "data_size", &procpar, &path, keys[3])?;
+        let reference_position: f64 =
+            extract_capture(&re[4], "reference_position", &procpar, &path, keys[4])?;
+        let reference_frequency: f64 =
+            extract_capture(&re[5], "reference_frequency", &procpar, &path, keys[5])?;
+
+        Ok(Parameters {
+            width: width / frequency,
+            maximum: (width - reference_position + reference_frequency) / frequency,
+            frequency,
+            nucleus,
+            data_size: fourier_number as usize / 2,
+        })
+    }
+
+    /// Internal helper function to read the file header of the `phasefile` and
+    /// determine the layout of the raw data.
+    ///
+    /// The file header is read in big-endian byte order first. If the number
+    /// of bytes per point and the number of bytes per trace are inconsistent,
+    /// little-endian byte order is tried instead.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedData`](crate::spectrum::error::Kind::MalformedData)
+    fn read_file_header<P: AsRef<Path>>(path: P, bytes: &[u8]) -> Result<FileHeader> {
+        let malformed_error = |details: &str| {
+            Error::new(Kind::MalformedData {
+                path: path.as_ref().to_path_buf(),
+                details: details.to_string(),
+                line: None,
+                offset: None,
+            })
+        };
+        if bytes.len() < FILE_HEADER_SIZE {
+            return Err(malformed_error("file header is incomplete").into());
+        }
+        let (endian, header) = [Endian::Big, Endian::Little]
+            .into_iter()
+            .map(|endian| {
+                let header = match endian {
+                    Endian::Little => Self::parse_file_header::<LittleEndian>(bytes),
+                    Endian::Big => Self::parse_file_header::<BigEndian>(bytes),
+                };
+                (endian, header)
+            })
+            .find(|(_, (points, point_bytes, trace_bytes, _, _))| {
+                matches!(point_bytes, 2 | 4) && *trace_bytes == points * point_bytes
+            })
+            .ok_or_else(|| malformed_error("file header is inconsistent"))?;
+        let (points, point_bytes, _, status, block_headers) = header;
+        let data_type = match (status & S_FLOAT != 0, status & S_32 != 0, point_bytes) {
+            (true, _, 4) => Type::F32,
+            (false, true, 4) => Type::I32,
+            (false, false, 2) => Type::I16,
+            _ => return Err(malformed_error("data type is not supported").into()),
+        };
+
+        Ok(FileHeader {
+            endian,
+            data_type,
+            data_size: points as usize,
+            offset: FILE_HEADER_SIZE + BLOCK_HEADER_SIZE * block_headers.max(1) as usize,
+        })
+    }
+
+    /// Internal helper function to extract the number of points, the bytes per
+    /// point, the bytes per trace, the status bits and the number of block
+    /// headers from the file header in the given byte order.
+    fn parse_file_header<B: ByteOrder>(bytes: &[u8]) -> (i32, i32, i32, i16, i32) {
+        (
+            B::read_i32(&bytes[8..12]),
+            B::read_i32(&bytes[12..16]),
+            B::read_i32(&bytes[16..20]),
+            B::read_i16(&bytes[26..28]),
+            B::read_i32(&bytes[28..32]),
+        )
+    }
+
+    /// Internal helper function to read the raw data from the `phasefile` and
+    /// return it as a vector of floating point numbers.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`MalformedData`](crate::spectrum::error::Kind::MalformedData)
+    /// - [`Error::IoError`](crate::Error::IoError)
+    fn read_phasefile<P: AsRef<Path>>(path: P, data_size: usize) -> Result<Vec<f64>> {
+        let bytes = read(path.as_ref())?;
+        let header = Self::read_file_header(&path, &bytes)?;
+        if header.data_size != data_size {
+            return Err(Error::new(Kind::MalformedData {
+                path: path.as_ref().to_path_buf(),
+                details: format!(
+                    "file header specifies {} points, but procpar specifies {}",
+                    header.data_size, data_size
+                ),
+                line: None,
+                offset: None,
+            })
+            .into());
+        }
+        let mut data = bytes.get(header.offset..).unwrap_or_default();
+
+        match header.data_type {
+            Type::I16 => {
+                let mut temp = vec![0_i16; data_size];
+                match header.endian {
+                    Endian::Little => data.read_i16_into::<LittleEndian>(&mut temp)?,
+                    Endian::Big => data.read_i16_into::<BigEndian>(&mut temp)?,
+                }
+
+                Ok(temp
+                    .into_iter()
+                    .map(|value| value as f64)
+                    .collect())
+            }
+            Type::I32 => {
+                let mut temp = vec![0_i32; data_size];
+                match header.endian {
+                    Endian::Little => data.read_i32_into::<LittleEndian>(&mut temp)?,
+                    Endian::Big => data.read_i32_into::<BigEndian>(&mut temp)?,
+                }
+
+                Ok(temp
+                    .into_iter()
+                    .map(|value| value as f64)
+                    .collect())
+            }
+            Type::F32 => {
+                let mut temp = vec![0_f32; data_size];
+                match header.endian {
+                    Endian::Little => data.read_f32_into::<LittleEndian>(&mut temp)?,
+                    Endian::Big => data.read_f32_into::<BigEndian>(&mut temp)?,
+                }
+
+                Ok(temp
+                    .into_iter()
+                    .map(|value| value as f64)
+                    .collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use float_cmp::assert_approx_eq;
+
+    fn check_spectrum(spectrum: &Spectrum) {
+        assert_eq!(spectrum.len(), 4096);
+        assert_approx_eq!(
+            f64,
+            spectrum.chemical_shifts()[0],
+            15.02361396217599,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            spectrum.range().0 - spectrum.range().1,
+            20.023613962183337,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(f64, spectrum.frequency(), 600.252821089118);
+        assert_eq!(spectrum.nucleus(), Nucleus::Hydrogen1);
+        let maximum = spectrum
+            .intensities()
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert_approx_eq!(
+            f64,
+            spectrum.chemical_shifts()[maximum],
+            3.41,
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn read_spectrum() {
+        let big_endian = Varian::read_spectrum("../data/varian/sim/sim_01", (-2.2, 11.8)).unwrap();
+        let little_endian =
+            Varian::read_spectrum("../data/varian/sim/sim_02", (-2.2, 11.8)).unwrap();
+        check_spectrum(&big_endian);
+        check_spectrum(&little_endian);
+    }
+
+    #[test]
+    fn read_spectra() {
+        let spectra = Varian::read_spectra("../data/varian/sim", (-2.2, 11.8)).unwrap();
+        assert_eq!(spectra.len(), 2);
+        spectra.iter().for_each(check_spectrum);
+    }
+
+    #[test]
+    fn read_parameters() {
+        let path = "../data/varian/sim/sim_01/procpar";
+        let parameters = Varian::read_parameters(path).unwrap();
+        assert_approx_eq!(f64, parameters.width, 20.023613962183337, epsilon = 1e-9);
+        assert_approx_eq!(f64, parameters.maximum, 15.02361396217599, epsilon = 1e-9);
+        assert_approx_eq!(f64, parameters.frequency, 600.252821089118);
+        assert_eq!(parameters.nucleus, Nucleus::Hydrogen1);
+        assert_eq!(parameters.data_size, 4096);
+    }
+
+    #[test]
+    fn malformed_phasefile() {
+        let path = "../data/varian/sim/sim_01/datdir/phasefile";
+        let errors = [
+            Varian::read_phasefile(path, 2048).unwrap_err(),
+            Varian::read_file_header(path, &[0; 16])
+                .map(|_| ())
+                .unwrap_err(),
+            Varian::read_file_header(path, &[0xff; 32])
+                .map(|_| ())
+                .unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::MalformedData { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Summary statistics of the intensities of a [`Spectrum`].
+///
+/// See [`Spectrum::intensity_stats`] and [`Spectrum::signal_region_stats`].
+///
+/// [`Spectrum`]: crate::spectrum::Spectrum
+/// [`Spectrum::intensity_stats`]: crate::spectrum::Spectrum::intensity_stats
+/// [`Spectrum::signal_region_stats`]: crate::spectrum::Spectrum::signal_region_stats
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct IntensityStats {
+    /// Minimum intensity.
+    min: f64,
+    /// Maximum intensity.
+    max: f64,
+    /// Arithmetic mean of the intensities.
+    mean: f64,
+    /// Population standard deviation of the intensities.
+    std: f64,
+    /// Index of the maximum intensity within the `Spectrum`.
+    max_index: usize,
+}
+
+impl IntensityStats {
+    /// Internal helper function to compute the statistics of the given
+    /// intensities. `offset` is the index of the first intensity within the
+    /// `Spectrum`, and is added to the index of the maximum.
+    ///
+    /// The intensities must not be empty.
+    pub(crate) fn compute(intensities: &[f64], offset: usize) -> Self {
+        let (max_index, max) = intensities.iter().copied().enumerate().fold(
+            (0, f64::NEG_INFINITY),
+            |(i_max, max), (i, intensity)| match intensity > max {
+                true => (i, intensity),
+                false => (i_max, max),
+            },
+        );
+        let min = intensities
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let n = intensities.len() as f64;
+        let mean = intensities.iter().sum::<f64>() / n;
+        let variance = intensities
+            .iter()
+            .map(|intensity| (intensity - mean).powi(2))
+            .sum::<f64>()
+            / n;
+
+        Self {
+            min,
+            max,
+            mean,
+            std: variance.sqrt(),
+            max_index: offset + max_index,
+        }
+    }
+
+    /// Returns the minimum intensity.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the maximum intensity.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the arithmetic mean of the intensities.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the population standard deviation of the intensities.
+    pub fn std(&self) -> f64 {
+        self.std
+    }
+
+    /// Returns the index of the maximum intensity within the `Spectrum`. If
+    /// the maximum occurs multiple times, the first index is returned.
+    pub fn max_index(&self) -> usize {
+        self.max_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(IntensityStats);
+        assert_sync!(IntensityStats);
+    }
+
+    #[test]
+    fn compute() {
+        let stats = IntensityStats::compute(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], 3);
+        assert_approx_eq!(f64, stats.min(), 2.0);
+        assert_approx_eq!(f64, stats.max(), 9.0);
+        assert_approx_eq!(f64, stats.mean(), 5.0);
+        assert_approx_eq!(f64, stats.std(), 2.0);
+        assert_eq!(stats.max_index(), 10);
+        let stats = IntensityStats::compute(&[-1.0, 3.0, 3.0], 0);
+        assert_eq!(stats.max_index(), 1);
+    }
+}
@@ -19,6 +19,24 @@ pub enum Monotonicity {
 }
 
 impl Monotonicity {
+    /// Returns the opposite `Monotonicity`, i.e. the ordering of the data
+    /// after reversing it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::meta::Monotonicity;
+    ///
+    /// assert_eq!(Monotonicity::Increasing.reversed(), Monotonicity::Decreasing);
+    /// assert_eq!(Monotonicity::Decreasing.reversed(), Monotonicity::Increasing);
+    /// ```
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Increasing => Self::Decreasing,
+            Self::Decreasing => Self::Increasing,
+        }
+    }
+
     /// Helper function to determine the `Monotonicity` from 2 floating point
     /// numbers.
     ///
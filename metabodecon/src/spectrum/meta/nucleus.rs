@@ -9,6 +9,10 @@ use serde::{Deserialize, Serialize};
 /// for easy conversion from string representations of nuclei. Whitespace,
 /// dashes, underscores and carets are removed from the input string before
 /// case-insensitively converting from a string to a `Nucleus` enum variant.
+/// Strings that don't match any of the typed variants are preserved in a
+/// `Nucleus::Other` variant, with only surrounding whitespace and carets (as
+/// used for superscripts in JCAMP-DX files) removed. This allows uncommon
+/// nuclei, such as `2H`, to be read from files without losing information.
 /// Note that there aren't any checks for the validity of a nucleus, so any
 /// string can be converted to a `Nucleus::Other` variant, even if it doesn't
 /// correspond to a real nucleus. As such, [`from_str`] will never fail.
@@ -25,10 +29,12 @@ use serde::{Deserialize, Serialize};
 /// let proton = Nucleus::from("proton");
 /// let carbon13 = "Carbon-13".parse::<Nucleus>();
 /// let nitrogen15 = Nucleus::from("   15n  ");
+/// let deuterium = Nucleus::from(" ^2H ");
 ///
 /// assert_eq!(proton, Nucleus::Hydrogen1);
 /// assert_eq!(carbon13, Ok(Nucleus::Carbon13));
 /// assert_eq!(nitrogen15, Nucleus::Nitrogen15);
+/// assert_eq!(deuterium, Nucleus::Other("2H".to_string()));
 /// ```
 ///
 /// # Display
@@ -90,6 +96,108 @@ pub enum Nucleus {
     Other(String),
 }
 
+impl Nucleus {
+    /// Returns the symbol of the nucleus in IUPAC notation (e.g., `1H`, `13C`).
+    ///
+    /// For `Nucleus::Other` variants, the stored string is returned as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::meta::Nucleus;
+    ///
+    /// assert_eq!(Nucleus::Carbon13.symbol(), "13C");
+    /// assert_eq!(Nucleus::Other("207Pb".to_string()).symbol(), "207Pb");
+    /// ```
+    pub fn symbol(&self) -> &str {
+        match self {
+            Self::Hydrogen1 => "1H",
+            Self::Boron11 => "11B",
+            Self::Carbon13 => "13C",
+            Self::Nitrogen15 => "15N",
+            Self::Fluorine19 => "19F",
+            Self::Silicon29 => "29Si",
+            Self::Phosphorus31 => "31P",
+            Self::Other(value) => value.as_str(),
+        }
+    }
+
+    /// Returns the gyromagnetic ratio of the nucleus in rad s⁻¹ T⁻¹.
+    ///
+    /// The values are taken from the IUPAC recommendations on NMR nomenclature
+    /// (Harris et al., 2001). Returns `None` for `Nucleus::Other` variants, as
+    /// their gyromagnetic ratio is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::meta::Nucleus;
+    ///
+    /// let proton = Nucleus::Hydrogen1.gyromagnetic_ratio().unwrap();
+    /// let carbon13 = Nucleus::Carbon13.gyromagnetic_ratio().unwrap();
+    ///
+    /// assert_approx_eq!(f64, proton / carbon13, 3.976, epsilon = 1e-3);
+    /// assert_eq!(Nucleus::Other("207Pb".to_string()).gyromagnetic_ratio(), None);
+    /// ```
+    pub fn gyromagnetic_ratio(&self) -> Option<f64> {
+        match self {
+            Self::Hydrogen1 => Some(26.7522128e7),
+            Self::Boron11 => Some(8.5847044e7),
+            Self::Carbon13 => Some(6.728284e7),
+            Self::Nitrogen15 => Some(-2.7116e7),
+            Self::Fluorine19 => Some(25.18148e7),
+            Self::Silicon29 => Some(-5.3190e7),
+            Self::Phosphorus31 => Some(10.8394e7),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Returns the reference frequency ratio of the nucleus relative to 1H.
+    ///
+    /// This is the ratio Ξ of the resonance frequency of the reference compound
+    /// of the nucleus to that of the 1H signal of TMS, as recommended by IUPAC
+    /// (Harris et al., 2001). It allows converting between the frequency scales
+    /// of different nuclei on the same spectrometer. The reference compounds
+    /// are:
+    ///
+    /// | Nucleus | Reference Compound |
+    /// | ------- | ------------------ |
+    /// | 1H      | TMS                |
+    /// | 11B     | BF₃·Et₂O           |
+    /// | 13C     | TMS                |
+    /// | 15N     | Liquid NH₃         |
+    /// | 19F     | CCl₃F              |
+    /// | 29Si    | TMS                |
+    /// | 31P     | 85% H₃PO₄          |
+    ///
+    /// Returns `None` for `Nucleus::Other` variants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::meta::Nucleus;
+    ///
+    /// // 13C frequency of a 600 MHz spectrometer.
+    /// let ratio = Nucleus::Carbon13.reference_frequency_ratio().unwrap();
+    ///
+    /// assert_approx_eq!(f64, 600.0 * ratio, 150.87, epsilon = 1e-2);
+    /// ```
+    pub fn reference_frequency_ratio(&self) -> Option<f64> {
+        match self {
+            Self::Hydrogen1 => Some(1.0),
+            Self::Boron11 => Some(0.32083974),
+            Self::Carbon13 => Some(0.25145020),
+            Self::Nitrogen15 => Some(0.10136767),
+            Self::Fluorine19 => Some(0.94094011),
+            Self::Silicon29 => Some(0.19867187),
+            Self::Phosphorus31 => Some(0.40480742),
+            Self::Other(_) => None,
+        }
+    }
+}
+
 impl<T: AsRef<str>> From<T> for Nucleus {
     fn from(value: T) -> Self {
         let nucleus = value
@@ -103,14 +211,14 @@ impl<T: AsRef<str>> From<T> for Nucleus {
             .to_uppercase();
 
         match nucleus.as_str() {
-            "1H" | "PROTON" | "HYDROGEN1" => Self::Hydrogen1,
-            "11B" | "BORON11" => Self::Boron11,
-            "13C" | "CARBON13" => Self::Carbon13,
-            "15N" | "NITROGEN15" => Self::Nitrogen15,
-            "19F" | "FLUORINE19" => Self::Fluorine19,
-            "29SI" | "SILICON29" => Self::Silicon29,
-            "31P" | "PHOSPHORUS31" => Self::Phosphorus31,
-            _ => Self::Other(value.as_ref().to_string()),
+            "1H" | "H1" | "PROTON" | "HYDROGEN1" => Self::Hydrogen1,
+            "11B" | "B11" | "BORON11" => Self::Boron11,
+            "13C" | "C13" | "CARBON13" => Self::Carbon13,
+            "15N" | "N15" | "NITROGEN15" => Self::Nitrogen15,
+            "19F" | "F19" | "FLUORINE19" => Self::Fluorine19,
+            "29SI" | "SI29" | "SILICON29" => Self::Silicon29,
+            "31P" | "P31" | "PHOSPHORUS31" => Self::Phosphorus31,
+            _ => Self::Other(value.as_ref().replace("^", "").trim().to_string()),
         }
     }
 }
@@ -125,17 +233,7 @@ impl std::str::FromStr for Nucleus {
 
 impl std::fmt::Display for Nucleus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let nucleus = match self {
-            Self::Hydrogen1 => "1H",
-            Self::Boron11 => "11B",
-            Self::Carbon13 => "13C",
-            Self::Nitrogen15 => "15N",
-            Self::Fluorine19 => "19F",
-            Self::Silicon29 => "29Si",
-            Self::Phosphorus31 => "31P",
-            Self::Other(value) => value.as_str(),
-        };
-        write!(f, "{}", nucleus)
+        write!(f, "{}", self.symbol())
     }
 }
 
@@ -149,6 +247,7 @@ impl From<Nucleus> for String {
 mod tests {
     use super::*;
     use crate::{assert_send, assert_sync};
+    use float_cmp::assert_approx_eq;
 
     #[test]
     fn thread_safety() {
@@ -166,6 +265,8 @@ mod tests {
             "Boron-11".into(),
             "FluoRine_19".into(),
             "29Si".into(),
+            "H1".into(),
+            "C13".into(),
         ];
         let expected = [
             Nucleus::Hydrogen1,
@@ -175,10 +276,61 @@ mod tests {
             Nucleus::Boron11,
             Nucleus::Fluorine19,
             Nucleus::Silicon29,
+            Nucleus::Hydrogen1,
+            Nucleus::Carbon13,
         ];
         assert_eq!(expected, nuclei);
     }
 
+    #[test]
+    fn from_str_other() {
+        let nuclei = ["2H", " ^2H\t", "207Pb", "Deuterium"].map(Nucleus::from);
+        let expected = ["2H", "2H", "207Pb", "Deuterium"];
+        nuclei
+            .iter()
+            .zip(expected)
+            .for_each(|(nucleus, expected)| {
+                assert_eq!(*nucleus, Nucleus::Other(expected.to_string()));
+                assert_eq!(nucleus.to_string(), expected);
+                assert_eq!(Nucleus::from(nucleus.to_string()), *nucleus);
+            });
+        assert_eq!(Nucleus::from("13C"), Nucleus::Carbon13);
+        assert_eq!(Nucleus::from("^11B"), Nucleus::Boron11);
+    }
+
+    #[test]
+    fn physical_constants() {
+        let nuclei = [
+            Nucleus::Hydrogen1,
+            Nucleus::Boron11,
+            Nucleus::Carbon13,
+            Nucleus::Nitrogen15,
+            Nucleus::Fluorine19,
+            Nucleus::Silicon29,
+            Nucleus::Phosphorus31,
+        ];
+        let proton = Nucleus::Hydrogen1.gyromagnetic_ratio().unwrap();
+        nuclei.iter().for_each(|nucleus| {
+            let ratio = nucleus.gyromagnetic_ratio().unwrap().abs() / proton;
+            let reference = nucleus.reference_frequency_ratio().unwrap();
+            assert_approx_eq!(f64, ratio, reference, epsilon = 1e-3);
+        });
+        assert_approx_eq!(f64, proton, 2.675e8, epsilon = 1e5);
+        assert_approx_eq!(
+            f64,
+            Nucleus::Phosphorus31
+                .reference_frequency_ratio()
+                .unwrap(),
+            0.4048,
+            epsilon = 1e-4
+        );
+        assert!(Nucleus::Nitrogen15.gyromagnetic_ratio().unwrap() < 0.0);
+        assert_eq!(
+            Nucleus::Other("2H".to_string()).reference_frequency_ratio(),
+            None
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serialization_round_trip() {
@@ -191,6 +343,7 @@ mod tests {
             Nucleus::Silicon29,
             Nucleus::Phosphorus31,
             Nucleus::Other("207Pb".to_string()),
+            Nucleus::Other("2H".to_string()),
         ];
         let serialized = nuclei
             .clone()
@@ -25,6 +25,15 @@ pub(crate) struct SerializedSpectrum {
     nucleus: Nucleus,
     /// Spectrometer frequency in MHz.
     frequency: f64,
+    /// Name of the measured sample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sample_name: Option<String>,
+    /// Date and time of the acquisition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    acquisition_date: Option<String>,
+    /// Free text description of the sample or experiment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     /// Chemical shift reference.
     reference_compound: ReferenceCompound,
     /// Intensities in arbitrary units.
@@ -42,6 +51,9 @@ impl<S: AsRef<Spectrum>> From<S> for SerializedSpectrum {
             signal_boundaries: spectrum.signal_boundaries(),
             nucleus: spectrum.nucleus(),
             frequency: spectrum.frequency(),
+            sample_name: spectrum.sample_name().map(str::to_string),
+            acquisition_date: spectrum.acquisition_date().map(str::to_string),
+            description: spectrum.description().map(str::to_string),
             reference_compound: spectrum.reference_compound().clone(),
         }
     }
@@ -62,6 +74,9 @@ impl TryFrom<SerializedSpectrum> for Spectrum {
         let mut spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
         spectrum.set_nucleus(value.nucleus);
         spectrum.set_frequency(value.frequency);
+        spectrum.set_sample_name(value.sample_name.as_deref());
+        spectrum.set_acquisition_date(value.acquisition_date.as_deref());
+        spectrum.set_description(value.description.as_deref());
         spectrum.set_reference_compound(value.reference_compound);
 
         Ok(spectrum)
@@ -77,6 +92,9 @@ impl PartialEq for SerializedSpectrum {
             && approx_eq!(f64, self.spectrum_boundaries.1, other.spectrum_boundaries.1)
             && approx_eq!(f64, self.signal_boundaries.0, other.signal_boundaries.0)
             && approx_eq!(f64, self.signal_boundaries.1, other.signal_boundaries.1)
+            && self.sample_name == other.sample_name
+            && self.acquisition_date == other.acquisition_date
+            && self.description == other.description
             && self
                 .intensities
                 .iter()
@@ -104,6 +122,9 @@ impl SerializedSpectrum {
             signal_boundaries: (1.0, 9.0),
             nucleus: Nucleus::Hydrogen1,
             frequency: 400.0,
+            sample_name: None,
+            acquisition_date: None,
+            description: None,
             reference_compound: ReferenceCompound::default(),
         }
     }
@@ -1,6 +1,11 @@
 use crate::Result;
+use crate::deconvolution::SmoothingSettings;
+use crate::spectrum::IntensityStats;
+#[cfg(feature = "fft")]
+use crate::spectrum::WindowFunction;
 use crate::spectrum::error::{Error, Kind};
 use crate::spectrum::meta::{Monotonicity, Nucleus, ReferenceCompound};
+use std::ops::{Add, Sub};
 use std::sync::Arc;
 
 #[cfg(feature = "serde")]
@@ -40,7 +45,13 @@ use serde::{Deserialize, Serialize};
 /// While the chemical shifts are encoded efficiently as a range and a size
 /// (rather than storing all values individually), the resulting files can still
 /// take up a lot of storage. For better performance, consider using binary
-/// formats like [MessagePack].
+/// formats like [MessagePack], for which [`to_msgpack`], [`from_msgpack`],
+/// [`write_msgpack`] and [`read_msgpack`] are provided for convenience.
+///
+/// [`to_msgpack`]: Spectrum::to_msgpack
+/// [`from_msgpack`]: Spectrum::from_msgpack
+/// [`write_msgpack`]: Spectrum::write_msgpack
+/// [`read_msgpack`]: Spectrum::read_msgpack
 ///
 /// [`Serialize`]: serde::Serialize
 /// [`Deserialize`]: serde::Deserialize
@@ -89,6 +100,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// // Create a Spectrum object.
 /// let spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries)?;
+///
+/// // Compute summary statistics of the intensities.
+/// let stats = spectrum.intensity_stats();
+/// let signal_region_stats = spectrum.signal_region_stats();
+/// assert!((stats.max() - 4.0).abs() < 0.1);
+/// assert!(signal_region_stats.min() > stats.min());
 /// # Ok(())
 /// # }
 /// ```
@@ -109,6 +126,12 @@ pub struct Spectrum {
     nucleus: Nucleus,
     /// Spectrometer frequency in MHz.
     frequency: f64,
+    /// Name of the measured sample.
+    sample_name: Option<String>,
+    /// Date and time of the acquisition.
+    acquisition_date: Option<String>,
+    /// Free text description of the sample or experiment.
+    description: Option<String>,
     /// Chemical shift reference.
     reference_compound: ReferenceCompound,
     /// Monotonicity of the data.
@@ -195,11 +218,109 @@ impl Spectrum {
             signal_boundaries,
             nucleus: Nucleus::default(),
             frequency: 1.0,
+            sample_name: None,
+            acquisition_date: None,
+            description: None,
             reference_compound: first.into(),
             monotonicity,
         })
     }
 
+    /// Constructs a `Spectrum` from chemical shifts that are only approximately
+    /// uniformly spaced, for example due to rounding during export.
+    ///
+    /// If the chemical shifts are already uniformly spaced, this is equivalent
+    /// to [`Spectrum::new`]. Otherwise, a uniform grid of the same length is
+    /// fitted to the chemical shifts through linear least squares, and the
+    /// intensities are linearly interpolated onto it. Chemical shifts are only
+    /// accepted if they are strictly monotonic and each of them lies within a
+    /// quarter step of its point on the fitted grid. The signal boundaries are
+    /// validated against the original chemical shifts and then clamped to the
+    /// range of the fitted grid.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Spectrum::new`]. In particular, chemical
+    /// shifts that deviate too much from a uniform grid result in a
+    /// [`NonUniformSpacing`] error, where the step size is that of the fitted
+    /// grid.
+    ///
+    /// [`NonUniformSpacing`]: crate::spectrum::error::Kind::NonUniformSpacing
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// // Chemical shifts with a step size of 1/3, rounded to 2 decimals.
+    /// let chemical_shifts = vec![1.0, 1.33, 1.67, 2.0, 2.33, 2.67, 3.0];
+    /// let intensities = vec![1.0, 2.0, 3.0, 4.0, 3.0, 2.0, 1.0];
+    /// let signal_boundaries = (1.5, 2.5);
+    /// assert!(
+    ///     Spectrum::new(
+    ///         chemical_shifts.clone(),
+    ///         intensities.clone(),
+    ///         signal_boundaries
+    ///     )
+    ///     .is_err()
+    /// );
+    ///
+    /// let spectrum =
+    ///     Spectrum::from_nonuniform(chemical_shifts, intensities, signal_boundaries)?;
+    ///
+    /// assert_eq!(spectrum.len(), 7);
+    /// assert_approx_eq!(f64, spectrum.step(), 1.0 / 3.0, epsilon = 1e-3);
+    /// assert_approx_eq!(f64, spectrum.intensities()[3], 4.0, epsilon = 1e-3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_nonuniform(
+        chemical_shifts: Vec<f64>,
+        intensities: Vec<f64>,
+        signal_boundaries: (f64, f64),
+    ) -> Result<Self> {
+        Self::validate_lengths(&chemical_shifts, &intensities)?;
+        if Self::validate_spacing(&chemical_shifts).is_ok() {
+            return Self::new(chemical_shifts, intensities, signal_boundaries);
+        }
+        let (first, step) = Self::fit_uniform_grid(&chemical_shifts)?;
+        Self::validate_intensities(&intensities)?;
+        let monotonicity = Monotonicity::from_f64s(first, first + step).unwrap();
+        let signal_boundaries =
+            Self::validate_boundaries(monotonicity, &chemical_shifts, signal_boundaries)?;
+        let len = chemical_shifts.len();
+        let grid = (0..len)
+            .map(|i| first + i as f64 * step)
+            .collect::<Vec<f64>>();
+        let positions = chemical_shifts
+            .iter()
+            .map(|chemical_shift| (chemical_shift - first) / step)
+            .collect::<Vec<f64>>();
+        let mut left = 0;
+        let resampled = (0..len)
+            .map(|i| {
+                let position = i as f64;
+                while left + 2 < len && positions[left + 1] < position {
+                    left += 1;
+                }
+                let fraction = ((position - positions[left])
+                    / (positions[left + 1] - positions[left]))
+                    .clamp(0.0, 1.0);
+                intensities[left] * (1.0 - fraction) + intensities[left + 1] * fraction
+            })
+            .collect::<Vec<f64>>();
+        let last = grid[len - 1];
+        let clamp = |boundary: f64| match monotonicity {
+            Monotonicity::Increasing => boundary.clamp(first, last),
+            Monotonicity::Decreasing => boundary.clamp(last, first),
+        };
+        let signal_boundaries = (clamp(signal_boundaries.0), clamp(signal_boundaries.1));
+
+        Self::new(grid, resampled, signal_boundaries)
+    }
+
     /// Returns the chemical shifts of the `Spectrum` as a slice.
     ///
     /// # Example
@@ -276,6 +397,146 @@ impl Spectrum {
         self.signal_boundaries
     }
 
+    /// Returns the signal region boundaries of the `Spectrum` in Hz.
+    ///
+    /// The boundaries are converted from ppm using the spectrometer frequency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spectrometer frequency is not finite and
+    /// positive, or if it is still set to the default value of 1 MHz.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    /// spectrum.set_frequency(600.0);
+    ///
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries_hz()?.0, 600.0);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries_hz()?.1, 1800.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_boundaries_hz(&self) -> Result<(f64, f64)> {
+        let frequency = self.validated_frequency()?;
+
+        Ok((
+            self.signal_boundaries.0 * frequency,
+            self.signal_boundaries.1 * frequency,
+        ))
+    }
+
+    /// Returns the chemical shifts of the `Spectrum` in Hz.
+    ///
+    /// The chemical shifts are converted from ppm using the spectrometer
+    /// frequency, i.e. they are relative to 0 ppm, like the boundaries returned
+    /// by [`signal_boundaries_hz`].
+    ///
+    /// [`signal_boundaries_hz`]: Spectrum::signal_boundaries_hz
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spectrometer frequency is not finite and
+    /// positive, or if it is still set to the default value of 1 MHz.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    /// assert!(spectrum.chemical_shifts_hz().is_err());
+    ///
+    /// spectrum.set_frequency(600.0);
+    /// let chemical_shifts = spectrum.chemical_shifts_hz()?;
+    ///
+    /// assert_approx_eq!(f64, chemical_shifts[0], 600.0);
+    /// assert_approx_eq!(f64, chemical_shifts[2], 1800.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn chemical_shifts_hz(&self) -> Result<Vec<f64>> {
+        let frequency = self.validated_frequency()?;
+
+        Ok(self
+            .chemical_shifts
+            .iter()
+            .map(|chemical_shift| chemical_shift * frequency)
+            .collect())
+    }
+
+    /// Constructs a copy of the `Spectrum` whose chemical shifts, signal
+    /// boundaries and reference compound are given in Hz instead of ppm.
+    ///
+    /// The conversion is the same as for [`chemical_shifts_hz`]. As a value in
+    /// Hz equals a value in ppm at 1 MHz, the spectrometer frequency of the
+    /// copy is set to 1 MHz, which marks its axis as already converted. This
+    /// means that the Hz conversions of the copy return an error, which
+    /// prevents converting the axis twice. All other metadata is kept.
+    ///
+    /// [`chemical_shifts_hz`]: Spectrum::chemical_shifts_hz
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spectrometer frequency is not finite and
+    /// positive, or if it is still set to the default value of 1 MHz.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    /// spectrum.set_frequency(600.0);
+    /// let converted = spectrum.in_hz()?;
+    ///
+    /// assert_approx_eq!(f64, converted.step(), 600.0);
+    /// assert_approx_eq!(f64, converted.signal_boundaries().1, 1800.0);
+    /// assert_approx_eq!(f64, converted.frequency(), 1.0);
+    /// assert!(converted.in_hz().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn in_hz(&self) -> Result<Self> {
+        let chemical_shifts = self.chemical_shifts_hz()?;
+        let signal_boundaries = self.signal_boundaries_hz()?;
+        let mut reference_compound = self.reference_compound.clone();
+        reference_compound.set_chemical_shift(reference_compound.chemical_shift() * self.frequency);
+
+        Ok(Self {
+            chemical_shifts: chemical_shifts.into(),
+            intensities: self.intensities.clone(),
+            signal_boundaries,
+            nucleus: self.nucleus.clone(),
+            frequency: 1.0,
+            sample_name: self.sample_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            description: self.description.clone(),
+            reference_compound,
+            monotonicity: self.monotonicity,
+        })
+    }
+
     /// Returns the observed nucleus of the `Spectrum`.
     ///
     /// By default, this is set to [`Hydrogen1`].
@@ -328,6 +589,91 @@ impl Spectrum {
         self.frequency
     }
 
+    /// Returns the name of the measured sample, if known.
+    ///
+    /// By default, this is not set. [`Bruker::read_spectrum`] reads it from the
+    /// `title` file of the processed data. It is only used as metadata.
+    ///
+    /// [`Bruker::read_spectrum`]: crate::spectrum::Bruker::read_spectrum
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.sample_name(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sample_name(&self) -> Option<&str> {
+        self.sample_name.as_deref()
+    }
+
+    /// Returns the date and time of the acquisition, if known.
+    ///
+    /// The timestamp is kept as it was stored by the spectrometer software,
+    /// for example `2021-01-22 18:04:32.025 +0100` for Bruker TopSpin. By
+    /// default, this is not set. [`Bruker::read_spectrum`] reads it from the
+    /// `$$` date line of the `acqus` file. It is only used as metadata.
+    ///
+    /// [`Bruker::read_spectrum`]: crate::spectrum::Bruker::read_spectrum
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.acquisition_date(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn acquisition_date(&self) -> Option<&str> {
+        self.acquisition_date.as_deref()
+    }
+
+    /// Returns the free text description of the sample or experiment, if
+    /// known.
+    ///
+    /// By default, this is not set. [`Bruker::read_spectrum`] reads it from the
+    /// `title` file of the processed data, which may span multiple lines. It
+    /// is only used as metadata.
+    ///
+    /// [`Bruker::read_spectrum`]: crate::spectrum::Bruker::read_spectrum
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.description(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
     /// Returns the chemical shift reference of the `Spectrum`.
     ///
     /// By default, this is set to the first chemical shift, with no name or
@@ -423,9 +769,52 @@ impl Spectrum {
         Ok(())
     }
 
+    /// Sets the signal region boundaries of the `Spectrum` in Hz.
+    ///
+    /// The boundaries are converted to ppm using the spectrometer frequency and
+    /// then validated as in [`Spectrum::set_signal_boundaries`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spectrometer frequency is not finite and
+    /// positive, or if it is still set to the default value of 1 MHz, as the
+    /// conversion would silently be wrong otherwise. Additionally, the same
+    /// checks as for [`Spectrum::set_signal_boundaries`] are performed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    /// spectrum.set_frequency(600.0);
+    /// spectrum.set_signal_boundaries_hz((750.0, 1650.0))?;
+    ///
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 1.25);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 2.75);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_signal_boundaries_hz(&mut self, signal_boundaries: (f64, f64)) -> Result<()> {
+        let frequency = self.validated_frequency()?;
+
+        self.set_signal_boundaries((
+            signal_boundaries.0 / frequency,
+            signal_boundaries.1 / frequency,
+        ))
+    }
+
     /// Sets the observed nucleus of the `Spectrum`.
     ///
-    /// This has no effect on the data itself.
+    /// This has no effect on the data itself. Strings are converted as
+    /// described for [`Nucleus`], so nuclei without a typed variant are kept
+    /// as [`Nucleus::Other`] instead of being discarded.
     ///
     /// # Example
     ///
@@ -448,6 +837,9 @@ impl Spectrum {
     ///
     /// spectrum.set_nucleus("31P");
     /// assert_eq!(spectrum.nucleus(), Nucleus::Phosphorus31);
+    ///
+    /// spectrum.set_nucleus("2H");
+    /// assert_eq!(spectrum.nucleus(), Nucleus::Other("2H".to_string()));
     /// # Ok(())
     /// # }
     /// ```
@@ -485,72 +877,152 @@ impl Spectrum {
         self.frequency = frequency;
     }
 
-    /// Sets the reference compound of the `Spectrum`.
-    ///
-    /// The reference compound is used to set the chemical shift reference of
-    /// the `Spectrum`. The chemical shifts are adjusted such that the reference
-    /// compound is at the correct index. Signal boundaries are shifted
-    /// accordingly. Note that there is no bounds checking for the index, and
-    /// using an index outside the valid range will result in the reference
-    /// compound being outside the chemical shift range.
-    ///
-    /// [`ReferenceCompound`] implements [`From<f64>`] and [`From<(f64,
-    /// usize)>`] to allow for easy conversion from a chemical shift or a
-    /// chemical shift and index pair. In the former case, the index is set
-    /// to 0, meaning that the leftmost chemical shift is the reference. In
-    /// the latter case, the chemical shift at the index will be equal to
-    /// the reference.
-    ///
-    /// [`From<f64>`]: From
-    /// [`From<(f64, usize)>`]: From
+    /// Sets or clears the name of the measured sample.
     ///
     /// # Example
     ///
     /// ```
     /// use metabodecon::spectrum::Spectrum;
-    /// use metabodecon::spectrum::meta::{Nucleus, ReferenceCompound};
     ///
     /// # fn main() -> metabodecon::Result<()> {
-    /// use float_cmp::assert_approx_eq;
     /// let mut spectrum = Spectrum::new(
     ///     vec![1.0, 2.0, 3.0], // Chemical shifts
     ///     vec![1.0, 2.0, 3.0], // Intensities
     ///     (1.0, 3.0),          // Signal boundaries
     /// )?;
     ///
-    /// spectrum.set_reference_compound(10.0);
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 10.0); // Reference
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[1], 11.0);
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[2], 12.0);
-    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 10.0);
-    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 12.0);
-    ///
-    /// spectrum.set_reference_compound((20.0, 1));
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 19.0);
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[1], 20.0); // Reference
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[2], 21.0);
-    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 19.0);
-    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 21.0);
+    /// spectrum.set_sample_name(Some("Blood_1"));
+    /// assert_eq!(spectrum.sample_name(), Some("Blood_1"));
     ///
-    /// let reference = ReferenceCompound::new(4.8, 2, Some("H2O"), None);
-    /// spectrum.set_reference_compound(reference);
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 2.8);
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[1], 3.8);
-    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[2], 4.8); // Reference
-    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 2.8);
-    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 4.8);
+    /// spectrum.set_sample_name(None);
+    /// assert_eq!(spectrum.sample_name(), None);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_reference_compound<T: Into<ReferenceCompound>>(&mut self, reference: T) {
-        let reference = reference.into();
-        let difference = self.chemical_shifts[reference.index()] - reference.chemical_shift();
-        if f64::abs(difference) < crate::CHECK_PRECISION {
-            self.reference_compound = reference;
-            return;
-        }
-        let first_before = self.chemical_shifts[0];
-        let step = self.step();
+    pub fn set_sample_name(&mut self, sample_name: Option<&str>) {
+        self.sample_name = sample_name.map(str::to_string);
+    }
+
+    /// Sets or clears the date and time of the acquisition.
+    ///
+    /// The timestamp is stored as is and not validated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    ///
+    /// spectrum.set_acquisition_date(Some("2021-01-22 18:04:32.025 +0100"));
+    /// assert_eq!(
+    ///     spectrum.acquisition_date(),
+    ///     Some("2021-01-22 18:04:32.025 +0100")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_acquisition_date(&mut self, acquisition_date: Option<&str>) {
+        self.acquisition_date = acquisition_date.map(str::to_string);
+    }
+
+    /// Sets or clears the free text description of the sample or experiment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    ///
+    /// spectrum.set_description(Some("Blood_1\nEDTA plasma, 1D NOESY"));
+    /// assert_eq!(
+    ///     spectrum.description(),
+    ///     Some("Blood_1\nEDTA plasma, 1D NOESY")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_description(&mut self, description: Option<&str>) {
+        self.description = description.map(str::to_string);
+    }
+
+    /// Sets the reference compound of the `Spectrum`.
+    ///
+    /// The reference compound is used to set the chemical shift reference of
+    /// the `Spectrum`. The chemical shifts are adjusted such that the reference
+    /// compound is at the correct index. Signal boundaries are shifted
+    /// accordingly. Note that there is no bounds checking for the index, and
+    /// using an index outside the valid range will result in the reference
+    /// compound being outside the chemical shift range.
+    ///
+    /// [`ReferenceCompound`] implements [`From<f64>`] and [`From<(f64,
+    /// usize)>`] to allow for easy conversion from a chemical shift or a
+    /// chemical shift and index pair. In the former case, the index is set
+    /// to 0, meaning that the leftmost chemical shift is the reference. In
+    /// the latter case, the chemical shift at the index will be equal to
+    /// the reference.
+    ///
+    /// [`From<f64>`]: From
+    /// [`From<(f64, usize)>`]: From
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    /// use metabodecon::spectrum::meta::{Nucleus, ReferenceCompound};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// use float_cmp::assert_approx_eq;
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.0, 3.0),          // Signal boundaries
+    /// )?;
+    ///
+    /// spectrum.set_reference_compound(10.0);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 10.0); // Reference
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[1], 11.0);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[2], 12.0);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 10.0);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 12.0);
+    ///
+    /// spectrum.set_reference_compound((20.0, 1));
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 19.0);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[1], 20.0); // Reference
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[2], 21.0);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 19.0);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 21.0);
+    ///
+    /// let reference = ReferenceCompound::new(4.8, 2, Some("H2O"), None);
+    /// spectrum.set_reference_compound(reference);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], 2.8);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[1], 3.8);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[2], 4.8); // Reference
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().0, 2.8);
+    /// assert_approx_eq!(f64, spectrum.signal_boundaries().1, 4.8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_reference_compound<T: Into<ReferenceCompound>>(&mut self, reference: T) {
+        let reference = reference.into();
+        let difference = self.chemical_shifts[reference.index()] - reference.chemical_shift();
+        if f64::abs(difference) < crate::CHECK_PRECISION {
+            self.reference_compound = reference;
+            return;
+        }
+        let first_before = self.chemical_shifts[0];
+        let step = self.step();
         let offset = reference.chemical_shift() - reference.index() as f64 * step;
         self.chemical_shifts = (0..self.len())
             .map(|i| offset + (i as f64) * step)
@@ -563,6 +1035,86 @@ impl Spectrum {
         self.reference_compound = reference;
     }
 
+    /// Calibrates the chemical shifts of the `Spectrum` to a reference peak.
+    ///
+    /// The point with the maximum intensity within `search_range` is taken as
+    /// the reference peak, and the chemical shifts are shifted such that it
+    /// lands at `target_shift`. The reference compound is updated accordingly
+    /// with the given name, as with [`set_reference_compound`]. This is useful
+    /// when the exact index of the reference peak, for example of TSP or DSS,
+    /// is not known.
+    ///
+    /// [`set_reference_compound`]: Spectrum::set_reference_compound
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the search range or the target chemical shift
+    /// contain non-finite values, or if there is no clear maximum within the
+    /// search range. This is the case if the maximum is at the edge of the
+    /// search range, or if it is not higher than its neighbors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let mut spectrum = Spectrum::new(
+    ///     vec![-0.9, -0.8, -0.7, -0.6, -0.5, -0.4, -0.3], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0, 10.0, 3.0, 2.0, 1.0],       // Intensities
+    ///     (-0.8, -0.4),                                  // Signal boundaries
+    /// )?;
+    /// spectrum.calibrate_to_peak((-0.8, -0.4), 0.0, Some("TSP"))?;
+    ///
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[0], -0.3);
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[3], 0.0); // Reference
+    /// assert_approx_eq!(f64, spectrum.chemical_shifts()[6], 0.3);
+    /// assert_eq!(spectrum.reference_compound().index(), 3);
+    /// assert_eq!(spectrum.reference_compound().name(), Some("TSP"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn calibrate_to_peak(
+        &mut self,
+        search_range: (f64, f64),
+        target_shift: f64,
+        name: Option<&str>,
+    ) -> Result<()> {
+        let error = || {
+            Error::new(Kind::InvalidCalibration {
+                search_range,
+                target_shift,
+            })
+        };
+        if !(search_range.0.is_finite() && search_range.1.is_finite() && target_shift.is_finite()) {
+            return Err(error().into());
+        }
+        let lower = f64::min(search_range.0, search_range.1) - crate::CHECK_PRECISION;
+        let upper = f64::max(search_range.0, search_range.1) + crate::CHECK_PRECISION;
+        let contained = |chemical_shift: &f64| (lower..=upper).contains(chemical_shift);
+        let (start, end) = match (
+            self.chemical_shifts.iter().position(contained),
+            self.chemical_shifts.iter().rposition(contained),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Err(error().into()),
+        };
+        let index = (start..=end)
+            .max_by(|a, b| self.intensities[*a].total_cmp(&self.intensities[*b]))
+            .unwrap();
+        if index == start
+            || index == end
+            || self.intensities[index] <= self.intensities[index - 1]
+            || self.intensities[index] <= self.intensities[index + 1]
+        {
+            return Err(error().into());
+        }
+        self.set_reference_compound(ReferenceCompound::new(target_shift, index, name, None));
+
+        Ok(())
+    }
+
     /// Returns the number of chemical shift-intensity pairs in the `Spectrum`.
     ///
     /// # Example
@@ -721,6 +1273,11 @@ impl Spectrum {
     /// Computes the indices of the chemical shifts that are closest to the
     /// signal region boundaries.
     ///
+    /// The indices refer to the order in which the chemical shifts are stored,
+    /// such that the first index is smaller than the second one regardless of
+    /// the [`Monotonicity`] of the `Spectrum`. Both indices are within the
+    /// bounds of the `Spectrum`.
+    ///
     /// # Example
     ///
     /// ```
@@ -739,101 +1296,1628 @@ impl Spectrum {
     /// # }
     /// ```
     pub fn signal_boundaries_indices(&self) -> (usize, usize) {
+        // The boundaries are stored in the order of the chemical shifts, so the
+        // offsets from the first chemical shift have the same sign as the step
+        // for both monotonicities. Rounding errors may still push a boundary at
+        // the edge of the spectrum beyond the last index.
+        let last = (self.len() - 1) as f64;
+        let position = |boundary: f64| (boundary - self.chemical_shifts[0]) / self.step();
+
         (
-            ((self.signal_boundaries.0 - self.chemical_shifts[0]) / self.step()).floor() as usize,
-            ((self.signal_boundaries.1 - self.chemical_shifts[0]) / self.step()).ceil() as usize,
+            position(self.signal_boundaries.0)
+                .floor()
+                .clamp(0.0, last) as usize,
+            position(self.signal_boundaries.1)
+                .ceil()
+                .clamp(0.0, last) as usize,
         )
     }
 
-    /// Internal helper function to validate the lengths of the input data and
-    /// return an error if the checks fail.
+    /// Returns the number of data points within the signal region.
     ///
-    /// # Errors
+    /// A data point lies within the signal region if its chemical shift is
+    /// between the signal boundaries, boundaries included. This is the length
+    /// of the slices returned by [`signal_region_shifts`] and
+    /// [`signal_region_intensities`].
     ///
-    /// The following errors are possible:
-    /// - [`EmptyData`](Kind::EmptyData)
-    /// - [`DataLengthMismatch`](Kind::DataLengthMismatch)
-    fn validate_lengths(chemical_shifts: &[f64], intensities: &[f64]) -> Result<()> {
-        if chemical_shifts.is_empty() || intensities.is_empty() {
-            return Err(Error::new(Kind::EmptyData {
-                chemical_shifts: chemical_shifts.len(),
-                intensities: intensities.len(),
-            })
-            .into());
-        }
-
-        if chemical_shifts.len() != intensities.len() {
-            return Err(Error::new(Kind::DataLengthMismatch {
-                chemical_shifts: chemical_shifts.len(),
-                intensities: intensities.len(),
-            })
-            .into());
-        }
-
-        Ok(())
+    /// [`signal_region_shifts`]: Spectrum::signal_region_shifts
+    /// [`signal_region_intensities`]: Spectrum::signal_region_intensities
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Intensities
+    ///     (1.5, 4.0),                    // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.signal_region_len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_region_len(&self) -> usize {
+        self.signal_region_range().len()
     }
 
-    /// Internal helper function to validate the spacing of the chemical shifts
-    /// and return an error if the checks fail.
+    /// Returns the chemical shifts within the signal region, in the order in
+    /// which they are stored.
     ///
-    /// # Errors
+    /// Only chemical shifts between the signal boundaries are included, with
+    /// chemical shifts on a boundary counting as inside.
     ///
-    /// The following errors are possible:
-    /// - [`NonUniformSpacing`](Kind::NonUniformSpacing)
-    fn validate_spacing(chemical_shifts: &[f64]) -> Result<()> {
-        let step_size = chemical_shifts[1] - chemical_shifts[0];
-        if step_size.abs() < crate::CHECK_PRECISION {
-            return Err(Error::new(Kind::NonUniformSpacing {
-                step_size,
-                positions: (0, 1),
-            })
-            .into());
-        }
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![5.0, 4.0, 3.0, 2.0, 1.0], // Intensities
+    ///     (1.5, 4.0),                    // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.signal_region_shifts(), &[2.0, 3.0, 4.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_region_shifts(&self) -> &[f64] {
+        &self.chemical_shifts[self.signal_region_range()]
+    }
 
-        if let Some(position) = chemical_shifts.windows(2).position(|w| {
-            (w[1] - w[0] - step_size).abs() > crate::CHECK_PRECISION || !(w[1] - w[0]).is_finite()
-        }) {
-            Err(Error::new(Kind::NonUniformSpacing {
-                step_size,
-                positions: (position, position + 1),
-            })
-            .into())
-        } else {
-            Ok(())
-        }
+    /// Returns the intensities within the signal region, in the order in which
+    /// they are stored.
+    ///
+    /// These are the intensities at the chemical shifts returned by
+    /// [`signal_region_shifts`].
+    ///
+    /// [`signal_region_shifts`]: Spectrum::signal_region_shifts
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![5.0, 4.0, 3.0, 2.0, 1.0], // Intensities
+    ///     (1.5, 4.0),                    // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.signal_region_intensities(), &[4.0, 3.0, 2.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_region_intensities(&self) -> &[f64] {
+        &self.intensities[self.signal_region_range()]
     }
 
-    /// Internal helper function to validate the intensities and return an error
-    /// if the checks fail.
+    /// Computes the range of indices of the data points within the signal
+    /// region, boundaries included.
     ///
-    /// # Errors
+    /// Unlike [`signal_boundaries_indices`], which rounds outwards to the
+    /// closest data points, this only covers data points whose chemical shifts
+    /// lie between the boundaries. A small tolerance keeps data points that
+    /// sit exactly on a boundary inside despite rounding errors.
     ///
-    /// The following errors are possible:
-    /// - [`InvalidIntensities`](Kind::InvalidIntensities)
-    fn validate_intensities(intensities: &[f64]) -> Result<()> {
-        let positions = intensities
-            .iter()
-            .enumerate()
-            .filter_map(|(i, intensity)| {
-                if !intensity.is_finite() {
-                    Some(i)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<usize>>();
+    /// [`signal_boundaries_indices`]: Spectrum::signal_boundaries_indices
+    fn signal_region_range(&self) -> std::ops::Range<usize> {
+        let len = self.len() as f64;
+        let position = |boundary: f64| (boundary - self.chemical_shifts[0]) / self.step();
+        let tolerance = |position: f64| crate::CHECK_PRECISION * position.abs().max(1.0);
+        let lower = position(self.signal_boundaries.0);
+        let upper = position(self.signal_boundaries.1);
+        let start = (lower - tolerance(lower)).ceil().clamp(0.0, len) as usize;
+        let end = ((upper + tolerance(upper)).floor() + 1.0).clamp(0.0, len) as usize;
 
-        match positions.is_empty() {
-            true => Ok(()),
-            false => Err(Error::new(Kind::InvalidIntensities { positions }).into()),
-        }
+        start..end.max(start)
     }
 
-    /// Internal helper function to validate the boundaries and return an error
-    /// if the checks fail and return the validated boundaries. Reorders the
-    /// boundaries to match `chemical_shifts` if necessary.
+    /// Computes the minimum, maximum, mean, and standard deviation of the
+    /// intensities, as well as the index of the maximum.
     ///
-    /// # Errors
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![9.0, 2.0, 3.0, 5.0, 1.0], // Intensities
+    ///     (1.5, 4.5),                    // Signal boundaries
+    /// )?;
+    /// let stats = spectrum.intensity_stats();
+    ///
+    /// assert_approx_eq!(f64, stats.min(), 1.0);
+    /// assert_approx_eq!(f64, stats.max(), 9.0);
+    /// assert_approx_eq!(f64, stats.mean(), 4.0);
+    /// assert_approx_eq!(f64, stats.std(), 2.8284271247461903);
+    /// assert_eq!(stats.max_index(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intensity_stats(&self) -> IntensityStats {
+        IntensityStats::compute(&self.intensities, 0)
+    }
+
+    /// Computes the minimum, maximum, mean, and standard deviation of the
+    /// intensities within the signal region, as well as the index of the
+    /// maximum.
+    ///
+    /// The statistics are computed over the intensities returned by
+    /// [`signal_region_intensities`]. The index of the maximum refers to the
+    /// whole `Spectrum`, not to the signal region.
+    ///
+    /// [`signal_region_intensities`]: Spectrum::signal_region_intensities
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![9.0, 2.0, 3.0, 5.0, 1.0], // Intensities
+    ///     (1.5, 4.0),                    // Signal boundaries
+    /// )?;
+    /// let stats = spectrum.signal_region_stats();
+    ///
+    /// assert_approx_eq!(f64, stats.min(), 2.0);
+    /// assert_approx_eq!(f64, stats.max(), 5.0);
+    /// assert_approx_eq!(f64, stats.mean(), 10.0 / 3.0);
+    /// assert_eq!(stats.max_index(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_region_stats(&self) -> IntensityStats {
+        let range = self.signal_region_range();
+
+        IntensityStats::compute(&self.intensities[range.clone()], range.start)
+    }
+
+    /// Estimates the signal-to-noise ratio of the `Spectrum`.
+    ///
+    /// The noise level is the standard deviation of the intensities within the
+    /// given noise range, which should be a region of the baseline that
+    /// contains no signals. The signal-to-noise ratio is the maximum intensity
+    /// of the signal region divided by the noise level. If the intensities
+    /// within the noise range are constant, the noise level is zero and the
+    /// ratio is infinite.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the noise range contains non-finite values or fewer
+    /// than 2 of the chemical shifts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], // Chemical shifts
+    ///     vec![1.0, 3.0, 8.0, 5.0, 1.0, 3.0], // Intensities
+    ///     (2.5, 4.5),                         // Signal boundaries
+    /// )?;
+    /// let snr = spectrum.signal_to_noise((5.0, 6.0))?;
+    ///
+    /// assert_approx_eq!(f64, snr, 8.0);
+    /// assert!(spectrum.signal_to_noise((6.5, 7.0)).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn signal_to_noise(&self, noise_range: (f64, f64)) -> Result<f64> {
+        let lower = f64::min(noise_range.0, noise_range.1) - crate::CHECK_PRECISION;
+        let upper = f64::max(noise_range.0, noise_range.1) + crate::CHECK_PRECISION;
+        let contained = |chemical_shift: &f64| (lower..=upper).contains(chemical_shift);
+        let (start, end) = match (
+            self.chemical_shifts.iter().position(contained),
+            self.chemical_shifts.iter().rposition(contained),
+        ) {
+            (Some(start), Some(end)) if noise_range.0.is_finite() && noise_range.1.is_finite() => {
+                (start, end + 1)
+            }
+            _ => (0, 0),
+        };
+        if end - start < 2 {
+            return Err(Error::new(Kind::InvalidNoiseRange {
+                range: noise_range,
+                chemical_shifts_range: self.range(),
+            })
+            .into());
+        }
+        let noise = IntensityStats::compute(&self.intensities[start..end], start).std();
+
+        Ok(self.signal_region_stats().max() / noise)
+    }
+
+    /// Estimates the boundaries of the signal region from the intensities.
+    ///
+    /// The noise level is estimated from the outer 5% of the points on either
+    /// side of the `Spectrum`, where no signals are expected. To be robust to
+    /// isolated outliers, the baseline and the standard deviation of the noise
+    /// are computed from the median and the median absolute deviation. Points
+    /// exceed the detection threshold if their intensity is greater than the
+    /// baseline plus `noise_multiple` times the standard deviation, and only
+    /// runs of at least 3 consecutive points exceeding it count as signals.
+    ///
+    /// Returns the chemical shifts of the first and last point of the widest
+    /// range that is bounded by such runs. The boundaries are ordered like the
+    /// chemical shifts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `noise_multiple` is not finite or not positive, or
+    /// if no intensities consistently exceed the detection threshold.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let chemical_shifts = (0..1000)
+    ///     .map(|i| 10.0 - i as f64 * 0.01)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = chemical_shifts
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, x)| {
+    ///         let noise = 0.01 * ((i * 7919) % 13) as f64;
+    ///         noise + 0.05 / (0.05_f64.powi(2) + (x - 6.0).powi(2))
+    ///             + 0.05 / (0.05_f64.powi(2) + (x - 4.0).powi(2))
+    ///     })
+    ///     .collect::<Vec<f64>>();
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0))?;
+    /// let boundaries = spectrum.detect_signal_boundaries(5.0)?;
+    ///
+    /// assert!(boundaries.0 > 6.0 && boundaries.0 < 9.0);
+    /// assert!(boundaries.1 < 4.0 && boundaries.1 > 1.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_signal_boundaries(&self, noise_multiple: f64) -> Result<(f64, f64)> {
+        const NOISE_FRACTION: f64 = 0.05;
+        const MIN_RUN_LENGTH: usize = 3;
+        const MAD_TO_STD: f64 = 1.4826;
+
+        if !noise_multiple.is_finite() || noise_multiple <= 0.0 {
+            return Err(Error::new(Kind::InvalidNoiseMultiple { noise_multiple }).into());
+        }
+        let len = self.len();
+        let noise_len = usize::max((len as f64 * NOISE_FRACTION) as usize, 1);
+        let mut noise = self.intensities[..noise_len]
+            .iter()
+            .chain(self.intensities[len.saturating_sub(noise_len)..].iter())
+            .copied()
+            .collect::<Vec<f64>>();
+        let baseline = Self::median(&mut noise);
+        let mut deviations = noise
+            .iter()
+            .map(|intensity| (intensity - baseline).abs())
+            .collect::<Vec<f64>>();
+        let std = MAD_TO_STD * Self::median(&mut deviations);
+        let threshold = baseline + noise_multiple * std;
+        let mut first = None;
+        let mut last = None;
+        let mut run_start = 0;
+        for (i, intensity) in self.intensities.iter().enumerate() {
+            if *intensity <= threshold {
+                run_start = i + 1;
+            } else if i + 1 - run_start >= MIN_RUN_LENGTH {
+                first.get_or_insert(run_start);
+                last = Some(i);
+            }
+        }
+
+        match (first, last) {
+            (Some(first), Some(last)) => {
+                Ok((self.chemical_shifts[first], self.chemical_shifts[last]))
+            }
+            _ => Err(Error::new(Kind::NoSignalRegionDetected { threshold }).into()),
+        }
+    }
+
+    /// Returns a copy of the `Spectrum` with automatically detected signal
+    /// boundaries.
+    ///
+    /// See [`Spectrum::detect_signal_boundaries`] for details on the detection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signal region can't be detected or if the
+    /// detected boundaries are invalid, which is the case if they are (almost)
+    /// identical.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let chemical_shifts = (0..1000)
+    ///     .map(|i| 10.0 - i as f64 * 0.01)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = chemical_shifts
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, x)| {
+    ///         let noise = 0.01 * ((i * 7919) % 13) as f64;
+    ///         noise + 0.05 / (0.05_f64.powi(2) + (x - 5.0).powi(2))
+    ///     })
+    ///     .collect::<Vec<f64>>();
+    /// let spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0))?;
+    /// let spectrum = spectrum.with_auto_boundaries(5.0)?;
+    ///
+    /// assert!(spectrum.signal_boundaries().0 > 5.0);
+    /// assert!(spectrum.signal_boundaries().1 < 5.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_auto_boundaries(&self, noise_multiple: f64) -> Result<Self> {
+        let signal_boundaries = self.detect_signal_boundaries(noise_multiple)?;
+        let mut spectrum = self.clone();
+        spectrum.set_signal_boundaries(signal_boundaries)?;
+
+        Ok(spectrum)
+    }
+
+    /// Detects runs of clipped intensities, which indicate that the receiver
+    /// gain was set too high during the acquisition.
+    ///
+    /// A saturated analog-to-digital converter cuts off the tops of the
+    /// strongest peaks, which then consist of several consecutive points at
+    /// the same maximum intensity. Such peaks can't be fitted well by any peak
+    /// shape. Runs of at least 3 consecutive points that are equal to the
+    /// maximum intensity of the `Spectrum` are considered clipped.
+    ///
+    /// Returns the index ranges of the clipped runs as `(start, end)` pairs,
+    /// where `end` is exclusive, or `None` if no clipping was detected. A
+    /// constant `Spectrum` is not considered clipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], // Chemical shifts
+    ///     vec![1.0, 3.0, 8.0, 8.0, 8.0, 3.0, 1.0], // Intensities
+    ///     (1.5, 6.5),                              // Signal boundaries
+    /// )?;
+    ///
+    /// assert_eq!(spectrum.detect_clipping(), Some(vec![(2, 5)]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_clipping(&self) -> Option<Vec<(usize, usize)>> {
+        const MIN_RUN_LENGTH: usize = 3;
+
+        let stats = self.intensity_stats();
+        if stats.max() <= stats.min() {
+            return None;
+        }
+        let mut start = 0;
+        let runs = self
+            .intensities
+            .chunk_by(|a, b| a == b)
+            .filter_map(|run| {
+                let range = (start, start + run.len());
+                start += run.len();
+                (run[0] == stats.max() && run.len() >= MIN_RUN_LENGTH).then_some(range)
+            })
+            .collect::<Vec<(usize, usize)>>();
+
+        (!runs.is_empty()).then_some(runs)
+    }
+
+    /// Returns a copy of the `Spectrum` with the order of the data reversed.
+    ///
+    /// Both the chemical shifts and the intensities are reversed, such that
+    /// each intensity stays at its chemical shift, and the [`Monotonicity`] is
+    /// flipped. The signal boundaries and the index of the reference compound
+    /// are updated accordingly. This is useful for tools that require the
+    /// chemical shifts in a specific order, e.g. plotting libraries that only
+    /// accept increasing data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    /// use metabodecon::spectrum::meta::Monotonicity;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![5.0, 4.0, 3.0, 2.0, 1.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 9.0, 4.0, 5.0], // Intensities
+    ///     (4.5, 1.5),                    // Signal boundaries
+    /// )?;
+    /// let reversed = spectrum.reversed();
+    ///
+    /// assert_eq!(reversed.monotonicity(), Monotonicity::Increasing);
+    /// assert_eq!(reversed.chemical_shifts(), &[1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// assert_eq!(reversed.intensities(), &[5.0, 4.0, 9.0, 2.0, 1.0]);
+    /// assert_eq!(reversed.signal_boundaries(), (1.5, 4.5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reversed(&self) -> Self {
+        let mut reference_compound = self.reference_compound.clone();
+        reference_compound.set_index(self.len() - 1 - reference_compound.index());
+
+        Self {
+            chemical_shifts: self
+                .chemical_shifts
+                .iter()
+                .rev()
+                .copied()
+                .collect(),
+            intensities: self.intensities.iter().rev().copied().collect(),
+            signal_boundaries: (self.signal_boundaries.1, self.signal_boundaries.0),
+            nucleus: self.nucleus.clone(),
+            frequency: self.frequency,
+            sample_name: self.sample_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            description: self.description.clone(),
+            reference_compound,
+            monotonicity: self.monotonicity.reversed(),
+        }
+    }
+
+    /// Crops the `Spectrum` to the given chemical shift range.
+    ///
+    /// Returns a new `Spectrum` that only contains the points within the range
+    /// (inclusive). The step size, nucleus and spectrometer frequency are
+    /// preserved, and the signal boundaries are set to the intersection of the
+    /// original signal region and the range. The order of the range doesn't
+    /// matter. The data of the cropped `Spectrum` is not shared with the
+    /// original.
+    ///
+    /// The reference compound is kept if it lies within the range, with its
+    /// index adjusted to the cropped data. Otherwise, the first chemical shift
+    /// of the cropped `Spectrum` becomes the reference, as is the case for
+    /// [`Spectrum::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range contains non-finite values, if it contains
+    /// fewer than 2 chemical shifts, or if the signal region lies (almost)
+    /// entirely outside the range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], // Intensities
+    ///     (1.5, 4.5),                         // Signal boundaries
+    /// )?;
+    /// let cropped = spectrum.crop((2.5, 5.5))?;
+    ///
+    /// assert_eq!(cropped.len(), 3);
+    /// assert_approx_eq!(f64, cropped.chemical_shifts()[0], 3.0);
+    /// assert_approx_eq!(f64, cropped.intensities()[2], 5.0);
+    /// assert_approx_eq!(f64, cropped.signal_boundaries().0, 3.0);
+    /// assert_approx_eq!(f64, cropped.signal_boundaries().1, 4.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn crop(&self, range: (f64, f64)) -> Result<Self> {
+        let lower = f64::min(range.0, range.1) - crate::CHECK_PRECISION;
+        let upper = f64::max(range.0, range.1) + crate::CHECK_PRECISION;
+        let contained = |chemical_shift: &f64| (lower..=upper).contains(chemical_shift);
+        let (start, end) = match (
+            self.chemical_shifts.iter().position(contained),
+            self.chemical_shifts.iter().rposition(contained),
+        ) {
+            (Some(start), Some(end)) if range.0.is_finite() && range.1.is_finite() => {
+                (start, end + 1)
+            }
+            _ => (0, 0),
+        };
+        if end - start < 2 {
+            return Err(Error::new(Kind::InvalidCropRange {
+                range,
+                chemical_shifts_range: self.range(),
+            })
+            .into());
+        }
+        let chemical_shifts = self.chemical_shifts[start..end].to_vec();
+        let intensities = self.intensities[start..end].to_vec();
+        let cropped_range = (chemical_shifts[0], *chemical_shifts.last().unwrap());
+        let signal_boundaries = (
+            f64::max(
+                f64::min(self.signal_boundaries.0, self.signal_boundaries.1),
+                f64::min(cropped_range.0, cropped_range.1),
+            ),
+            f64::min(
+                f64::max(self.signal_boundaries.0, self.signal_boundaries.1),
+                f64::max(cropped_range.0, cropped_range.1),
+            ),
+        );
+        if signal_boundaries.1 - signal_boundaries.0 < crate::CHECK_PRECISION {
+            return Err(Error::new(Kind::InvalidSignalBoundaries {
+                signal_boundaries: self.signal_boundaries,
+                chemical_shifts_range: cropped_range,
+            })
+            .into());
+        }
+        let signal_boundaries =
+            Self::validate_boundaries(self.monotonicity, &chemical_shifts, signal_boundaries)?;
+        let reference_compound = match self.reference_compound.index() {
+            index if (start..end).contains(&index) => {
+                let mut reference_compound = self.reference_compound.clone();
+                reference_compound.set_index(index - start);
+                reference_compound
+            }
+            _ => chemical_shifts[0].into(),
+        };
+
+        Ok(Self {
+            chemical_shifts: chemical_shifts.into(),
+            intensities: intensities.into(),
+            signal_boundaries,
+            nucleus: self.nucleus.clone(),
+            frequency: self.frequency,
+            sample_name: self.sample_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            description: self.description.clone(),
+            reference_compound,
+            monotonicity: self.monotonicity,
+        })
+    }
+
+    /// Resamples the `Spectrum` to the given number of points.
+    ///
+    /// Returns a new `Spectrum` with `len` uniformly spaced chemical shifts
+    /// spanning the same range as the original. The intensities are linearly
+    /// interpolated between the two nearest original points. The signal
+    /// boundaries and metadata are preserved, except that the index of the
+    /// reference compound is moved to the closest point of the new grid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `len` is less than 2, as the step size can't be
+    /// computed otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 3.0, 2.0], // Intensities
+    ///     (1.5, 2.5),          // Signal boundaries
+    /// )?;
+    /// let resampled = spectrum.resample(5)?;
+    ///
+    /// assert_eq!(resampled.len(), 5);
+    /// assert_approx_eq!(f64, resampled.step(), 0.5);
+    /// assert_approx_eq!(f64, resampled.intensities()[1], 2.0);
+    /// assert_approx_eq!(f64, resampled.intensities()[3], 2.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resample(&self, len: usize) -> Result<Self> {
+        if len < 2 {
+            return Err(Error::new(Kind::InvalidResampling {
+                len,
+                step: f64::NAN,
+            })
+            .into());
+        }
+        let (first, last) = self.range();
+        let step = (last - first) / (len - 1) as f64;
+
+        Ok(self.resample_unchecked(len, step))
+    }
+
+    /// Resamples the `Spectrum` to the given step size in ppm.
+    ///
+    /// Returns a new `Spectrum` with uniformly spaced chemical shifts, starting
+    /// at the first chemical shift of the original. As the width of the
+    /// `Spectrum` is generally not a multiple of the step size, the new grid
+    /// may end up to one step short of the last original chemical shift. The
+    /// sign of `step` is ignored, as the ordering of the chemical shifts is
+    /// preserved. See [`Spectrum::resample`] for details on the interpolation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the step size is not finite, (almost) zero, or so
+    /// large that fewer than 2 points would remain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![3.0, 2.0, 1.0], // Chemical shifts
+    ///     vec![2.0, 3.0, 1.0], // Intensities
+    ///     (2.5, 1.5),          // Signal boundaries
+    /// )?;
+    /// let resampled = spectrum.resample_to_step(0.25)?;
+    ///
+    /// assert_eq!(resampled.len(), 9);
+    /// assert_approx_eq!(f64, resampled.step(), -0.25);
+    /// assert_approx_eq!(f64, resampled.intensities()[2], 2.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resample_to_step(&self, step: f64) -> Result<Self> {
+        let len = match step.is_finite() && step.abs() > crate::CHECK_PRECISION {
+            true => (self.width() / step.abs() + crate::CHECK_PRECISION).floor() as usize + 1,
+            false => 0,
+        };
+        if len < 2 {
+            return Err(Error::new(Kind::InvalidResampling { len, step }).into());
+        }
+        let step = step.abs() * self.step().signum();
+
+        Ok(self.resample_unchecked(len, step))
+    }
+
+    /// Decimates the `Spectrum` by keeping every `factor`-th point.
+    ///
+    /// To reduce aliasing of the noise, the intensities are first smoothed with
+    /// a single pass of a moving average filter, whose window is the smallest
+    /// odd number of points not narrower than `factor`. The window is centered
+    /// on each point, so the positions of the signals are preserved, while
+    /// signals narrower than the window are broadened. The decimated `Spectrum`
+    /// starts at the first chemical shift of the original and has a step size
+    /// `factor` times larger, such that up to `factor - 1` points at the end
+    /// are dropped. The signal boundaries and metadata are preserved, except
+    /// that the signal boundaries are clamped to the new range and the index of
+    /// the reference compound is moved to the closest point of the new grid. A
+    /// `factor` of 1 returns an unchanged copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `factor` is 0 or if fewer than 2 points would
+    /// remain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], // Chemical shifts
+    ///     vec![0.0, 0.0, 3.0, 6.0, 3.0, 0.0, 0.0], // Intensities
+    ///     (1.5, 6.5),                              // Signal boundaries
+    /// )?;
+    /// let decimated = spectrum.decimate(3)?;
+    ///
+    /// assert_eq!(decimated.len(), 3);
+    /// assert_approx_eq!(f64, decimated.step(), 3.0);
+    /// assert_approx_eq!(f64, decimated.intensities()[1], 4.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decimate(&self, factor: usize) -> Result<Self> {
+        if factor == 0 || self.len() < factor + 1 {
+            return Err(Error::new(Kind::InvalidDecimation {
+                factor,
+                len: self.len(),
+            })
+            .into());
+        }
+        if factor == 1 {
+            return Ok(self.clone());
+        }
+        let mut intensities = self.intensities.to_vec();
+        SmoothingSettings::MovingAverage {
+            iterations: 1,
+            window_size: factor | 1,
+        }
+        .smooth_values(&mut intensities)?;
+        let len = (self.len() - 1) / factor + 1;
+        let step = self.step() * factor as f64;
+
+        Ok(self
+            .with_intensities(intensities)
+            .resample_unchecked(len, step))
+    }
+
+    /// Divides the signal region into buckets of the given width in ppm and
+    /// sums the intensities within each bucket.
+    ///
+    /// Returns `(bucket_center, summed_intensity)` pairs, ordered like the
+    /// chemical shifts. The buckets start at the first signal boundary, and the
+    /// last bucket is narrower if the width of the signal region is not a
+    /// multiple of the bucket width. Each point is treated as a cell of one
+    /// step size centered at its chemical shift, and contributes to a bucket
+    /// proportionally to the overlap with it. Points at the edge of a bucket or
+    /// the signal region are therefore partially included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bucket width is not finite or not positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], // Intensities
+    ///     (1.5, 5.5),                         // Signal boundaries
+    /// )?;
+    /// let buckets = spectrum.bucket(2.0)?;
+    ///
+    /// assert_eq!(buckets.len(), 2);
+    /// assert_approx_eq!(f64, buckets[0].0, 2.5);
+    /// assert_approx_eq!(f64, buckets[0].1, 5.0);
+    /// assert_approx_eq!(f64, buckets[1].0, 4.5);
+    /// assert_approx_eq!(f64, buckets[1].1, 9.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bucket(&self, bucket_width: f64) -> Result<Vec<(f64, f64)>> {
+        if !bucket_width.is_finite() || bucket_width < crate::CHECK_PRECISION {
+            return Err(Error::new(Kind::InvalidBucketWidth {
+                width: bucket_width,
+            })
+            .into());
+        }
+        let lower = f64::min(self.signal_boundaries.0, self.signal_boundaries.1);
+        let upper = f64::max(self.signal_boundaries.0, self.signal_boundaries.1);
+        let count = usize::max(
+            ((upper - lower) / bucket_width - crate::CHECK_PRECISION).ceil() as usize,
+            1,
+        );
+        let bucket = |k: usize| {
+            (
+                lower + k as f64 * bucket_width,
+                f64::min(lower + (k + 1) as f64 * bucket_width, upper),
+            )
+        };
+        let cell_width = self.step().abs();
+        let mut sums = vec![0.0; count];
+        self.chemical_shifts
+            .iter()
+            .zip(self.intensities.iter())
+            .for_each(|(chemical_shift, intensity)| {
+                let cell = (
+                    f64::max(chemical_shift - 0.5 * cell_width, lower),
+                    f64::min(chemical_shift + 0.5 * cell_width, upper),
+                );
+                if cell.1 <= cell.0 {
+                    return;
+                }
+                let first = usize::min(((cell.0 - lower) / bucket_width).floor() as usize, count);
+                let last = usize::min(((cell.1 - lower) / bucket_width).ceil() as usize, count);
+                (first..last).for_each(|k| {
+                    let (start, end) = bucket(k);
+                    let overlap = f64::min(cell.1, end) - f64::max(cell.0, start);
+                    if overlap > 0.0 {
+                        sums[k] += intensity * overlap / cell_width;
+                    }
+                });
+            });
+        let mut buckets = sums
+            .into_iter()
+            .enumerate()
+            .map(|(k, sum)| {
+                let (start, end) = bucket(k);
+                (0.5 * (start + end), sum)
+            })
+            .collect::<Vec<(f64, f64)>>();
+        if self.monotonicity == Monotonicity::Decreasing {
+            buckets.reverse();
+        }
+
+        Ok(buckets)
+    }
+
+    /// Divides the signal region into the given number of equally wide buckets
+    /// and sums the intensities within each bucket.
+    ///
+    /// See [`Spectrum::bucket`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `count` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![6.0, 5.0, 4.0, 3.0, 2.0, 1.0], // Chemical shifts
+    ///     vec![6.0, 5.0, 4.0, 3.0, 2.0, 1.0], // Intensities
+    ///     (5.5, 1.5),                         // Signal boundaries
+    /// )?;
+    /// let buckets = spectrum.bucket_count(4)?;
+    ///
+    /// assert_eq!(buckets.len(), 4);
+    /// assert_approx_eq!(f64, buckets[0].0, 5.0);
+    /// assert_approx_eq!(f64, buckets[0].1, 5.0);
+    /// assert_approx_eq!(f64, buckets[3].0, 2.0);
+    /// assert_approx_eq!(f64, buckets[3].1, 2.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bucket_count(&self, count: usize) -> Result<Vec<(f64, f64)>> {
+        let width = (self.signal_boundaries.1 - self.signal_boundaries.0).abs();
+
+        self.bucket(width / count as f64)
+    }
+
+    /// Normalizes the intensities such that their sum within the signal region
+    /// is 1.
+    ///
+    /// Returns a new `Spectrum` with all intensities scaled by the same factor,
+    /// which preserves the relative intensities of the signals. All other data
+    /// and metadata is kept as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scaling factor is not finite or zero, which is
+    /// the case if the intensities within the signal region sum to zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![0.0, 2.0, 3.0, 5.0, 2.0], // Intensities
+    ///     (1.5, 4.5),                    // Signal boundaries
+    /// )?;
+    /// let normalized = spectrum.normalize_total_area()?;
+    ///
+    /// assert_approx_eq!(f64, normalized.intensities()[1], 0.2);
+    /// assert_approx_eq!(f64, normalized.intensities()[2], 0.3);
+    /// assert_approx_eq!(f64, normalized.intensities()[3], 0.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn normalize_total_area(&self) -> Result<Self> {
+        self.normalize_to(1.0)
+    }
+
+    /// Normalizes the intensities such that their sum within the signal region
+    /// equals the given target.
+    ///
+    /// See [`Spectrum::normalize_total_area`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scaling factor is not finite or zero, which is
+    /// the case if the intensities within the signal region sum to zero or if
+    /// the target is not finite or zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0, 5.0], // Chemical shifts
+    ///     vec![0.0, 2.0, 3.0, 5.0, 2.0], // Intensities
+    ///     (1.5, 4.5),                    // Signal boundaries
+    /// )?;
+    /// let normalized = spectrum.normalize_to(100.0)?;
+    ///
+    /// assert_approx_eq!(f64, normalized.intensities()[3], 50.0);
+    /// assert_approx_eq!(f64, normalized.intensities()[4], 20.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn normalize_to(&self, target: f64) -> Result<Self> {
+        let sum = self
+            .signal_region_intensities()
+            .iter()
+            .sum::<f64>();
+        let factor = target / sum;
+        if !factor.is_finite() || factor == 0.0 {
+            return Err(Error::new(Kind::InvalidNormalization { sum, target }).into());
+        }
+        let intensities = self
+            .intensities
+            .iter()
+            .map(|intensity| intensity * factor)
+            .collect::<Vec<f64>>();
+
+        Ok(self.with_intensities(intensities))
+    }
+
+    /// Adds the intensities of another `Spectrum` point by point.
+    ///
+    /// Returns a new `Spectrum` with the chemical shifts, signal boundaries and
+    /// metadata of `self`. Both spectra must share the same grid, i.e. the same
+    /// number of data points and the same chemical shift range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grids of the two spectra don't match or if any
+    /// of the resulting intensities is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let a = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5))?;
+    /// let b = Spectrum::new(vec![1.0, 2.0, 3.0], vec![0.5, 0.5, 0.5], (1.0, 3.0))?;
+    /// let sum = a.add(&b)?;
+    ///
+    /// assert_eq!(sum.intensities(), [1.5, 2.5, 3.5]);
+    /// assert_eq!(sum.signal_boundaries(), (1.5, 2.5));
+    /// assert_eq!((&a + &b)?.intensities(), sum.intensities());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(&self, other: &Spectrum) -> Result<Self> {
+        self.combine(other, |a, b| a + b)
+    }
+
+    /// Subtracts the intensities of another `Spectrum` point by point.
+    ///
+    /// Useful for difference spectroscopy, e.g. to remove a blank. See
+    /// [`Spectrum::add`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grids of the two spectra don't match or if any
+    /// of the resulting intensities is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let sample = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 4.0, 1.0], (1.5, 2.5))?;
+    /// let blank = Spectrum::new(vec![1.0, 2.0, 3.0], vec![0.5, 1.0, 0.5], (1.5, 2.5))?;
+    /// let difference = sample.subtract(&blank)?;
+    ///
+    /// assert_eq!(difference.intensities(), [0.5, 3.0, 0.5]);
+    /// assert_eq!((&sample - &blank)?.intensities(), difference.intensities());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subtract(&self, other: &Spectrum) -> Result<Self> {
+        self.combine(other, |a, b| a - b)
+    }
+
+    /// Applies a zero- and first-order phase correction to the intensities.
+    ///
+    /// Only the real part of the spectrum is stored, so the imaginary part is
+    /// reconstructed as the discrete [Hilbert transform] of the intensities,
+    /// which is computed by zeroing the negative frequencies of their Fourier
+    /// transform. Each point `k` of the resulting complex spectrum is then
+    /// rotated by the angle `ph0 + ph1 * k / n`, where `n` is the number of
+    /// points, and the real part is kept as the corrected intensities. This
+    /// follows the convention of NMRPipe, where `ph1` is the total phase change
+    /// across the spectrum in storage order.
+    ///
+    /// Returns a new `Spectrum` with the same chemical shifts and metadata.
+    /// Because the reconstruction treats the intensities as periodic, points
+    /// close to the edges of the spectrum are less accurate, and the mean
+    /// intensity can't be rotated, as it has no imaginary counterpart.
+    ///
+    /// [Hilbert transform]: https://en.wikipedia.org/wiki/Hilbert_transform
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either of the phase correction angles, which are
+    /// given in degrees, is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use float_cmp::assert_approx_eq;
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0, 4.0],  // Chemical shifts
+    ///     vec![0.0, 1.0, 0.0, -1.0], // Intensities
+    ///     (1.5, 3.5),                // Signal boundaries
+    /// )?;
+    /// let corrected = spectrum.apply_phase_correction(90.0, 0.0)?;
+    ///
+    /// assert_approx_eq!(f64, corrected.intensities()[0], 1.0, epsilon = 1e-12);
+    /// assert_approx_eq!(f64, corrected.intensities()[1], 0.0, epsilon = 1e-12);
+    /// assert_approx_eq!(f64, corrected.intensities()[2], -1.0, epsilon = 1e-12);
+    /// assert_approx_eq!(f64, corrected.intensities()[3], 0.0, epsilon = 1e-12);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "fft")]
+    pub fn apply_phase_correction(&self, ph0: f64, ph1: f64) -> Result<Self> {
+        use rustfft::FftPlanner;
+        use rustfft::num_complex::Complex;
+
+        if !ph0.is_finite() || !ph1.is_finite() {
+            return Err(Error::new(Kind::InvalidPhaseCorrection { ph0, ph1 }).into());
+        }
+        let len = self.len();
+        let mut planner = FftPlanner::<f64>::new();
+        let mut analytic = self
+            .intensities
+            .iter()
+            .map(|&intensity| Complex::new(intensity, 0.0))
+            .collect::<Vec<_>>();
+        planner
+            .plan_fft_forward(len)
+            .process(&mut analytic);
+        analytic
+            .iter_mut()
+            .enumerate()
+            .for_each(|(k, value)| match k {
+                0 => {}
+                k if 2 * k == len => {}
+                k if 2 * k < len => *value *= 2.0,
+                _ => *value = Complex::new(0.0, 0.0),
+            });
+        planner
+            .plan_fft_inverse(len)
+            .process(&mut analytic);
+        let (ph0, ph1) = (ph0.to_radians(), ph1.to_radians());
+        let intensities = analytic
+            .iter()
+            .enumerate()
+            .map(|(k, value)| {
+                let phase = ph0 + ph1 * k as f64 / len as f64;
+                (value * Complex::from_polar(1.0, phase)).re / len as f64
+            })
+            .collect::<Vec<f64>>();
+
+        Ok(self.with_intensities(intensities))
+    }
+
+    /// Applies a window function to the time-domain signal of the intensities.
+    ///
+    /// As only the frequency domain is stored, the time-domain signal is
+    /// obtained implicitly through an inverse Fourier transform of the
+    /// intensities. Since the intensities are real, the resulting signal is
+    /// symmetric in time, so the window is applied to the absolute time of each
+    /// point before transforming back. For the exponential and Gaussian
+    /// windows, this is equivalent to convolving the spectrum with a Lorentzian
+    /// or Gaussian line shape, respectively.
+    ///
+    /// The time axis is derived from the step size and the spectrometer
+    /// frequency, which therefore needs to be set correctly. Returns a new
+    /// `Spectrum` with the same chemical shifts and metadata. Because the
+    /// transform treats the intensities as periodic, signals close to the edges
+    /// of the spectrum may leak into the opposite edge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters of the window function are invalid
+    /// or if the spectrometer frequency is not finite and positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::{Spectrum, WindowFunction};
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let chemical_shifts = (0..1024)
+    ///     .map(|i| i as f64 * 10.0 / 1023.0)
+    ///     .collect::<Vec<f64>>();
+    /// let intensities = chemical_shifts
+    ///     .iter()
+    ///     .map(|x| 0.01 / (0.01_f64.powi(2) + (x - 5.0).powi(2)))
+    ///     .collect::<Vec<f64>>();
+    /// let mut spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0))?;
+    /// spectrum.set_frequency(600.0);
+    /// let broadened = spectrum.apply_window(WindowFunction::Exponential { lb: 5.0 })?;
+    ///
+    /// assert!(broadened.intensities()[512] < spectrum.intensities()[512]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "fft")]
+    pub fn apply_window(&self, window: WindowFunction) -> Result<Self> {
+        use rustfft::FftPlanner;
+        use rustfft::num_complex::Complex;
+
+        let frequency = self.frequency;
+        if !window.is_valid() || !frequency.is_finite() || frequency <= 0.0 {
+            return Err(Error::new(Kind::InvalidWindowFunction { window, frequency }).into());
+        }
+        let len = self.len();
+        let bandwidth = self.step().abs() * frequency * len as f64;
+        let t_max = (len / 2) as f64 / bandwidth;
+        let mut planner = FftPlanner::<f64>::new();
+        let mut signal = self
+            .intensities
+            .iter()
+            .map(|&intensity| Complex::new(intensity, 0.0))
+            .collect::<Vec<_>>();
+        planner.plan_fft_inverse(len).process(&mut signal);
+        signal
+            .iter_mut()
+            .enumerate()
+            .for_each(|(k, value)| {
+                let t = usize::min(k, len - k) as f64 / bandwidth;
+                *value *= window.weight(t, t_max);
+            });
+        planner.plan_fft_forward(len).process(&mut signal);
+        let intensities = signal
+            .iter()
+            .map(|value| value.re / len as f64)
+            .collect::<Vec<f64>>();
+        Self::validate_intensities(&intensities)?;
+
+        Ok(self.with_intensities(intensities))
+    }
+
+    /// Serializes the `Spectrum` into [MessagePack] bytes.
+    ///
+    /// The chemical shifts are stored compactly as their range and the number
+    /// of points, as with any other [Serde] format.
+    ///
+    /// [MessagePack]: https://docs.rs/rmp-serde
+    /// [Serde]: https://serde.rs/
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Spectrum` can't be encoded as MessagePack.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.5, 2.5),          // Signal boundaries
+    /// )?;
+    /// let bytes = spectrum.to_msgpack()?;
+    /// let deserialized = Spectrum::from_msgpack(&bytes)?;
+    ///
+    /// assert_eq!(deserialized.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>> {
+        Ok(rmp_serde::to_vec_named(self)?)
+    }
+
+    /// Deserializes a `Spectrum` from [MessagePack] bytes.
+    ///
+    /// [MessagePack]: https://docs.rs/rmp-serde
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes can't be decoded as a `Spectrum`, which
+    /// includes the case where the decoded data violates the invariants of the
+    /// `Spectrum`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.5, 2.5),          // Signal boundaries
+    /// )?;
+    /// let bytes = spectrum.to_msgpack()?;
+    /// let deserialized = Spectrum::from_msgpack(&bytes)?;
+    ///
+    /// assert_eq!(deserialized.intensities(), spectrum.intensities());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+
+    /// Writes the `Spectrum` as [MessagePack] to the file at the given path.
+    ///
+    /// An existing file is overwritten.
+    ///
+    /// [MessagePack]: https://docs.rs/rmp-serde
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Spectrum` can't be encoded as MessagePack.
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// let spectrum = Spectrum::new(
+    ///     vec![1.0, 2.0, 3.0], // Chemical shifts
+    ///     vec![1.0, 2.0, 3.0], // Intensities
+    ///     (1.5, 2.5),          // Signal boundaries
+    /// )?;
+    /// let path = "path/to/spectrum.msgpack";
+    /// # let path = std::env::temp_dir().join("metabodecon_write_msgpack_doc.msgpack");
+    /// spectrum.write_msgpack(path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn write_msgpack<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_msgpack()?)?;
+
+        Ok(())
+    }
+
+    /// Reads a `Spectrum` from a [MessagePack] file at the given path.
+    ///
+    /// [MessagePack]: https://docs.rs/rmp-serde
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file contents can't be decoded as a `Spectrum`.
+    /// Additionally, if any [`I/O`] errors occur, an error variant containing
+    /// the original error is returned.
+    ///
+    /// [`I/O`]: std::io
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use metabodecon::spectrum::Spectrum;
+    ///
+    /// # fn main() -> metabodecon::Result<()> {
+    /// # let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5))?;
+    /// # let path = std::env::temp_dir().join("metabodecon_read_msgpack_doc.msgpack");
+    /// # spectrum.write_msgpack(&path)?;
+    /// let path = "path/to/spectrum.msgpack";
+    /// # let path = std::env::temp_dir().join("metabodecon_read_msgpack_doc.msgpack");
+    /// let spectrum = Spectrum::read_msgpack(path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn read_msgpack<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::from_msgpack(&std::fs::read(path)?)
+    }
+
+    /// Internal helper function to construct a resampled copy of the `Spectrum`
+    /// with the given number of points and step size. The signal boundaries
+    /// are clamped to the new range of chemical shifts.
+    fn resample_unchecked(&self, len: usize, step: f64) -> Self {
+        let first = self.chemical_shifts[0];
+        let old_step = self.step();
+        let chemical_shifts = (0..len)
+            .map(|i| first + i as f64 * step)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|chemical_shift| {
+                let position = f64::max((chemical_shift - first) / old_step, 0.0);
+                let left = usize::min(position.floor() as usize, self.len() - 2);
+                let fraction = f64::min(position - left as f64, 1.0);
+                self.intensities[left] * (1.0 - fraction) + self.intensities[left + 1] * fraction
+            })
+            .collect::<Vec<f64>>();
+        let last = *chemical_shifts.last().unwrap();
+        let clamp = |boundary: f64| match self.monotonicity {
+            Monotonicity::Increasing => boundary.clamp(first, last),
+            Monotonicity::Decreasing => boundary.clamp(last, first),
+        };
+        let signal_boundaries = (
+            clamp(self.signal_boundaries.0),
+            clamp(self.signal_boundaries.1),
+        );
+        let mut reference_compound = self.reference_compound.clone();
+        let reference_position = ((reference_compound.index() as f64 * old_step) / step).round();
+        reference_compound.set_index(usize::min(reference_position as usize, len - 1));
+
+        Self {
+            chemical_shifts: chemical_shifts.into(),
+            intensities: intensities.into(),
+            signal_boundaries,
+            nucleus: self.nucleus.clone(),
+            frequency: self.frequency,
+            sample_name: self.sample_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            description: self.description.clone(),
+            reference_compound,
+            monotonicity: self.monotonicity,
+        }
+    }
+
+    /// Internal helper function to construct a copy of the `Spectrum` with the
+    /// given intensities and the same metadata. The caller is responsible for
+    /// providing finite intensities of the same length as the chemical shifts.
+    pub(crate) fn with_intensities(&self, intensities: Vec<f64>) -> Self {
+        debug_assert_eq!(intensities.len(), self.chemical_shifts.len());
+
+        Self {
+            chemical_shifts: self.chemical_shifts.clone(),
+            intensities: intensities.into(),
+            signal_boundaries: self.signal_boundaries,
+            nucleus: self.nucleus.clone(),
+            frequency: self.frequency,
+            sample_name: self.sample_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            description: self.description.clone(),
+            reference_compound: self.reference_compound.clone(),
+            monotonicity: self.monotonicity,
+        }
+    }
+
+    /// Internal helper function to construct a copy of the `Spectrum` where the
+    /// intensities starting at index `start` are replaced by the given ones.
+    /// The caller is responsible for ensuring that they fit into the spectrum.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`InvalidIntensities`](Kind::InvalidIntensities)
+    pub(crate) fn with_replaced_intensities(
+        &self,
+        start: usize,
+        replacement: &[f64],
+    ) -> Result<Self> {
+        debug_assert!(start + replacement.len() <= self.len());
+        let mut intensities = self.intensities.to_vec();
+        intensities[start..start + replacement.len()].copy_from_slice(replacement);
+        Self::validate_intensities(&intensities)?;
+
+        Ok(self.with_intensities(intensities))
+    }
+
+    /// Internal helper function to combine the intensities of two spectra point
+    /// by point with the given operation.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`GridMismatch`](Kind::GridMismatch)
+    /// - [`InvalidIntensities`](Kind::InvalidIntensities)
+    fn combine<F: Fn(f64, f64) -> f64>(&self, other: &Spectrum, operation: F) -> Result<Self> {
+        let (range, other_range) = (self.range(), other.range());
+        let is_matching = self.len() == other.len()
+            && f64::abs(self.step() - other.step()) < crate::CHECK_PRECISION
+            && f64::abs(range.0 - other_range.0) < crate::CHECK_PRECISION
+            && f64::abs(range.1 - other_range.1) < crate::CHECK_PRECISION;
+        if !is_matching {
+            return Err(Error::new(Kind::GridMismatch {
+                lengths: (self.len(), other.len()),
+                ranges: (range, other_range),
+            })
+            .into());
+        }
+        let intensities = self
+            .intensities
+            .iter()
+            .zip(other.intensities.iter())
+            .map(|(a, b)| operation(*a, *b))
+            .collect::<Vec<f64>>();
+        Self::validate_intensities(&intensities)?;
+
+        Ok(self.with_intensities(intensities))
+    }
+
+    /// Internal helper function to return the spectrometer frequency if it can
+    /// be used to convert between Hz and ppm.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`InvalidFrequency`](Kind::InvalidFrequency)
+    pub(crate) fn validated_frequency(&self) -> Result<f64> {
+        let frequency = self.frequency;
+        let is_unset = f64::abs(frequency - 1.0) < crate::CHECK_PRECISION;
+        if !frequency.is_finite() || frequency <= 0.0 || is_unset {
+            return Err(Error::new(Kind::InvalidFrequency { frequency }).into());
+        }
+
+        Ok(frequency)
+    }
+
+    /// Internal helper function to compute the median of the given values. The
+    /// values are reordered in the process and must not be empty.
+    fn median(values: &mut [f64]) -> f64 {
+        let len = values.len();
+        let (lower, median, _) = values.select_nth_unstable_by(len / 2, f64::total_cmp);
+        match len % 2 {
+            0 => {
+                (*median
+                    + lower
+                        .iter()
+                        .copied()
+                        .fold(f64::NEG_INFINITY, f64::max))
+                    / 2.0
+            }
+            _ => *median,
+        }
+    }
+
+    /// Internal helper function to validate the lengths of the input data and
+    /// return an error if the checks fail.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`EmptyData`](Kind::EmptyData)
+    /// - [`DataLengthMismatch`](Kind::DataLengthMismatch)
+    fn validate_lengths(chemical_shifts: &[f64], intensities: &[f64]) -> Result<()> {
+        if chemical_shifts.is_empty() || intensities.is_empty() {
+            return Err(Error::new(Kind::EmptyData {
+                chemical_shifts: chemical_shifts.len(),
+                intensities: intensities.len(),
+            })
+            .into());
+        }
+
+        if chemical_shifts.len() != intensities.len() {
+            return Err(Error::new(Kind::DataLengthMismatch {
+                chemical_shifts: chemical_shifts.len(),
+                intensities: intensities.len(),
+            })
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Internal helper function to validate the spacing of the chemical shifts
+    /// and return an error if the checks fail.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`NonUniformSpacing`](Kind::NonUniformSpacing)
+    fn validate_spacing(chemical_shifts: &[f64]) -> Result<()> {
+        let step_size = chemical_shifts[1] - chemical_shifts[0];
+        if step_size.abs() < crate::CHECK_PRECISION {
+            return Err(Error::new(Kind::NonUniformSpacing {
+                step_size,
+                positions: (0, 1),
+            })
+            .into());
+        }
+
+        if let Some(position) = chemical_shifts.windows(2).position(|w| {
+            (w[1] - w[0] - step_size).abs() > crate::CHECK_PRECISION || !(w[1] - w[0]).is_finite()
+        }) {
+            Err(Error::new(Kind::NonUniformSpacing {
+                step_size,
+                positions: (position, position + 1),
+            })
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Internal helper function to fit a uniform grid to the chemical shifts
+    /// through linear least squares and return its first value and step size.
+    /// The chemical shifts must be strictly monotonic and lie within a quarter
+    /// step of the fitted grid.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`NonUniformSpacing`](Kind::NonUniformSpacing)
+    fn fit_uniform_grid(chemical_shifts: &[f64]) -> Result<(f64, f64)> {
+        if let Some(position) = chemical_shifts
+            .iter()
+            .position(|chemical_shift| !chemical_shift.is_finite())
+        {
+            let position = usize::min(position, chemical_shifts.len() - 2);
+            return Err(Error::new(Kind::NonUniformSpacing {
+                step_size: f64::NAN,
+                positions: (position, position + 1),
+            })
+            .into());
+        }
+        let len = chemical_shifts.len() as f64;
+        let index_mean = (len - 1.0) / 2.0;
+        let mean = chemical_shifts.iter().sum::<f64>() / len;
+        let (covariance, variance) = chemical_shifts
+            .iter()
+            .enumerate()
+            .map(|(i, chemical_shift)| {
+                let deviation = i as f64 - index_mean;
+                (deviation * (chemical_shift - mean), deviation.powi(2))
+            })
+            .fold((0.0, 0.0), |acc, (c, v)| (acc.0 + c, acc.1 + v));
+        let step = covariance / variance;
+        let first = mean - step * index_mean;
+        let tolerance = 0.25 * step.abs();
+        let residual = |i: usize| f64::abs(chemical_shifts[i] - (first + i as f64 * step));
+        if let Some(position) = chemical_shifts
+            .windows(2)
+            .enumerate()
+            .position(|(i, w)| {
+                (w[1] - w[0]) * step <= 0.0
+                    || step.abs() < crate::CHECK_PRECISION
+                    || residual(i) > tolerance
+                    || residual(i + 1) > tolerance
+            })
+        {
+            return Err(Error::new(Kind::NonUniformSpacing {
+                step_size: step,
+                positions: (position, position + 1),
+            })
+            .into());
+        }
+
+        Ok((first, step))
+    }
+
+    /// Internal helper function to validate the intensities and return an error
+    /// if the checks fail.
+    ///
+    /// # Errors
+    ///
+    /// The following errors are possible:
+    /// - [`InvalidIntensities`](Kind::InvalidIntensities)
+    fn validate_intensities(intensities: &[f64]) -> Result<()> {
+        let positions = intensities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, intensity)| {
+                if !intensity.is_finite() {
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<usize>>();
+
+        match positions.is_empty() {
+            true => Ok(()),
+            false => Err(Error::new(Kind::InvalidIntensities { positions }).into()),
+        }
+    }
+
+    /// Internal helper function to validate the boundaries and return an error
+    /// if the checks fail and return the validated boundaries. Reorders the
+    /// boundaries to match `chemical_shifts` if necessary.
+    ///
+    /// # Errors
     ///
     /// The following errors are possible:
     /// - [`InvalidSignalBoundaries`](Kind::InvalidSignalBoundaries)
@@ -890,6 +2974,25 @@ impl Spectrum {
     }
 }
 
+impl Add for &Spectrum {
+    type Output = Result<Spectrum>;
+
+    /// Adds two spectra point by point. See [`Spectrum::add`] for details.
+    fn add(self, other: &Spectrum) -> Self::Output {
+        Spectrum::add(self, other)
+    }
+}
+
+impl Sub for &Spectrum {
+    type Output = Result<Spectrum>;
+
+    /// Subtracts two spectra point by point. See [`Spectrum::subtract`] for
+    /// details.
+    fn sub(self, other: &Spectrum) -> Self::Output {
+        self.subtract(other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1147,58 +3250,644 @@ mod tests {
     }
 
     #[test]
-    fn accessors() {
-        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.0, 3.0)).unwrap();
+    fn accessors() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.0, 3.0)).unwrap();
+        spectrum
+            .chemical_shifts()
+            .iter()
+            .zip([1.0, 2.0, 3.0])
+            .for_each(|(&xc, xe)| assert_approx_eq!(f64, xc, xe));
+        spectrum
+            .intensities()
+            .iter()
+            .zip([1.0, 2.0, 3.0])
+            .for_each(|(&ic, ie)| assert_approx_eq!(f64, ic, ie));
+        assert_eq!(spectrum.chemical_shifts().len(), 3);
+        assert_eq!(spectrum.intensities().len(), 3);
+        assert_approx_eq!(f64, spectrum.signal_boundaries().0, 1.0);
+        assert_approx_eq!(f64, spectrum.signal_boundaries().1, 3.0);
+        assert_eq!(spectrum.monotonicity(), Monotonicity::Increasing);
+    }
+
+    #[test]
+    fn mutators() {
+        let mut spectrum =
+            Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.0, 3.0)).unwrap();
+        spectrum
+            .set_signal_boundaries((2.5, 1.5))
+            .unwrap();
+        assert_approx_eq!(f64, spectrum.signal_boundaries().0, 1.5);
+        assert_approx_eq!(f64, spectrum.signal_boundaries().1, 2.5);
+    }
+
+    #[test]
+    fn properties() {
+        let spectrum = Spectrum::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            (1.5, 4.5),
+        )
+        .unwrap();
+        assert_eq!(spectrum.len(), 5);
+        assert_approx_eq!(f64, spectrum.step(), 1.0);
+        assert_approx_eq!(f64, spectrum.width(), 4.0);
+        assert_approx_eq!(f64, spectrum.range().0, 1.0);
+        assert_approx_eq!(f64, spectrum.range().1, 5.0);
+        assert_approx_eq!(f64, spectrum.center(), 3.0);
+        assert_eq!(spectrum.signal_boundaries_indices(), (0, 4));
+        assert_eq!(spectrum.signal_region_len(), 3);
+        assert_eq!(spectrum.signal_region_shifts(), &[2.0, 3.0, 4.0]);
+        assert_eq!(spectrum.signal_region_intensities(), &[2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn signal_boundaries_indices_decreasing() {
+        let chemical_shifts = (0..101)
+            .map(|i| 10.0 - i as f64 * 0.1)
+            .collect::<Vec<f64>>();
+        let intensities = vec![1.0; chemical_shifts.len()];
+        let spectrum = Spectrum::new(chemical_shifts.clone(), intensities, (2.05, 7.45)).unwrap();
+        assert_eq!(spectrum.monotonicity(), Monotonicity::Decreasing);
+        assert_eq!(spectrum.signal_boundaries_indices(), (25, 80));
+        assert_eq!(spectrum.signal_region_len(), 54);
+        assert_eq!(spectrum.signal_region_shifts(), &chemical_shifts[26..80]);
+        assert_eq!(spectrum.signal_region_intensities().len(), 54);
+        let chemical_shifts = (0..7)
+            .map(|i| 10.0 - i as f64 * 10.0 / 6.0)
+            .collect::<Vec<f64>>();
+        let intensities = vec![1.0; chemical_shifts.len()];
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (0.0, 10.0)).unwrap();
+        assert_eq!(spectrum.signal_boundaries_indices(), (0, 6));
+        let chemical_shifts = (0..62)
+            .map(|i| i as f64 * 10.0 / 61.0)
+            .collect::<Vec<f64>>();
+        let intensities = vec![1.0; chemical_shifts.len()];
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (0.0, 10.0)).unwrap();
+        assert_eq!(spectrum.signal_boundaries_indices(), (0, 61));
+    }
+
+    #[test]
+    fn signal_region_between_grid_points() {
+        let spectrum = Spectrum::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            (2.25, 3.75),
+        )
+        .unwrap();
+        assert_eq!(spectrum.signal_region_len(), 1);
+        assert_eq!(spectrum.signal_region_shifts(), &[3.0]);
+        assert_eq!(spectrum.signal_region_intensities(), &[3.0]);
+        let reversed = spectrum.reversed();
+        assert_eq!(reversed.signal_region_shifts(), &[3.0]);
+        let spectrum = Spectrum::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            (1.5, 4.0),
+        )
+        .unwrap();
+        assert_eq!(spectrum.signal_region_shifts(), &[2.0, 3.0, 4.0]);
+        let reversed = spectrum.reversed();
+        assert_eq!(reversed.signal_region_shifts(), &[4.0, 3.0, 2.0]);
+        let spectrum = Spectrum::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            (2.4, 2.6),
+        )
+        .unwrap();
+        assert_eq!(spectrum.signal_region_len(), 0);
+        assert!(spectrum.signal_region_intensities().is_empty());
+        let chemical_shifts = (0..101)
+            .map(|i| i as f64 * 0.1)
+            .collect::<Vec<f64>>();
+        let intensities = vec![1.0; chemical_shifts.len()];
+        let spectrum = Spectrum::new(chemical_shifts.clone(), intensities, (0.0, 10.0)).unwrap();
+        assert_eq!(spectrum.signal_region_shifts(), &chemical_shifts[..]);
+    }
+
+    #[test]
+    fn reversed() {
+        use crate::deconvolution::{Deconvoluter, Lorentzian};
+        use crate::macros::uniform_noise;
+
+        let signals = [
+            Lorentzian::new(0.5 * 0.01, 0.01_f64.powi(2), 3.0),
+            Lorentzian::new(0.8 * 0.01, 0.01_f64.powi(2), 5.5),
+            Lorentzian::new(0.3 * 0.01, 0.01_f64.powi(2), 7.0),
+        ];
+        // Decreasing like the axis of a spectrum read from Bruker files.
+        let chemical_shifts = (0..4000)
+            .map(|i| 10.0 - i as f64 * 10.0 / 3999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .zip(uniform_noise(4000, 42))
+            .map(|(x, noise)| Lorentzian::superposition(*x, &signals) + 0.1 * noise)
+            .collect::<Vec<f64>>();
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (8.0, 2.0)).unwrap();
+        spectrum.set_reference_compound((spectrum.chemical_shifts()[100], 100));
+        let reversed = spectrum.reversed();
+        assert_eq!(spectrum.monotonicity(), Monotonicity::Decreasing);
+        assert_eq!(reversed.monotonicity(), Monotonicity::Increasing);
+        assert!(reversed.step() > 0.0);
+        assert_eq!(reversed.len(), spectrum.len());
+        reversed
+            .chemical_shifts()
+            .iter()
+            .rev()
+            .zip(spectrum.chemical_shifts())
+            .for_each(|(reversed, original)| assert_eq!(reversed, original));
+        reversed
+            .intensities()
+            .iter()
+            .rev()
+            .zip(spectrum.intensities())
+            .for_each(|(reversed, original)| assert_eq!(reversed, original));
+        assert_eq!(reversed.signal_boundaries(), (2.0, 8.0));
+        assert_eq!(reversed.signal_region_len(), spectrum.signal_region_len());
+        assert_eq!(reversed.reference_compound().index(), 3899);
+        assert_approx_eq!(
+            f64,
+            reversed.chemical_shifts()[reversed.reference_compound().index()],
+            reversed.reference_compound().chemical_shift()
+        );
+        let deconvoluter = Deconvoluter::default();
+        let expected = deconvoluter
+            .deconvolute_spectrum(&spectrum)
+            .unwrap();
+        let deconvolution = deconvoluter
+            .deconvolute_spectrum(&reversed)
+            .unwrap();
+        assert_eq!(deconvolution.len(), expected.len());
+        assert!(deconvolution.len() >= signals.len());
+        deconvolution
+            .lorentzians()
+            .iter()
+            .zip(expected.lorentzians().iter().rev())
+            .for_each(|(lorentzian, expected)| {
+                assert_approx_eq!(f64, lorentzian.maxp(), expected.maxp(), epsilon = 1e-9);
+                assert_approx_eq!(f64, lorentzian.sfhw(), expected.sfhw(), epsilon = 1e-9);
+                assert_approx_eq!(f64, lorentzian.hw2(), expected.hw2(), epsilon = 1e-9);
+            });
+        let round_trip = reversed.reversed();
+        assert_eq!(round_trip.monotonicity(), Monotonicity::Decreasing);
+        assert_eq!(round_trip.chemical_shifts(), spectrum.chemical_shifts());
+        assert_eq!(round_trip.intensities(), spectrum.intensities());
+        assert_eq!(round_trip.signal_boundaries(), spectrum.signal_boundaries());
+        assert_eq!(round_trip.reference_compound().index(), 100);
+    }
+
+    #[test]
+    fn detect_clipping() {
+        let chemical_shifts = (0..1001)
+            .map(|i| i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                0.005 / (0.01_f64.powi(2) + (x - 3.0).powi(2))
+                    + 0.005 / (0.01_f64.powi(2) + (x - 7.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        let clean =
+            Spectrum::new(chemical_shifts.clone(), intensities.clone(), (2.0, 8.0)).unwrap();
+        assert_eq!(clean.detect_clipping(), None);
+        let clipped = intensities
+            .iter()
+            .map(|intensity| intensity.min(5.0))
+            .collect::<Vec<f64>>();
+        let clipped = Spectrum::new(chemical_shifts.clone(), clipped, (2.0, 8.0)).unwrap();
+        let runs = clipped.detect_clipping().unwrap();
+        assert_eq!(runs.len(), 2);
+        runs.iter()
+            .zip([300, 700])
+            .for_each(|(&(start, end), center)| {
+                assert!(start < center && end > center + 1);
+                assert!(end - start >= 3);
+                assert!(
+                    clipped.intensities()[start..end]
+                        .iter()
+                        .all(|&intensity| intensity == 5.0)
+                );
+                assert!(clipped.intensities()[start - 1] < 5.0);
+                assert!(clipped.intensities()[end] < 5.0);
+            });
+        let constant = Spectrum::new(chemical_shifts, vec![1.0; 1001], (2.0, 8.0)).unwrap();
+        assert_eq!(constant.detect_clipping(), None);
+    }
+
+    #[test]
+    fn intensity_stats() {
+        let spectrum = Spectrum::new(
+            vec![5.0, 4.0, 3.0, 2.0, 1.0],
+            vec![1.0, 5.0, 3.0, 2.0, 9.0],
+            (2.5, 4.5),
+        )
+        .unwrap();
+        let stats = spectrum.intensity_stats();
+        assert_approx_eq!(f64, stats.min(), 1.0);
+        assert_approx_eq!(f64, stats.max(), 9.0);
+        assert_approx_eq!(f64, stats.mean(), 4.0);
+        assert_approx_eq!(f64, stats.std(), 8.0_f64.sqrt());
+        assert_eq!(stats.max_index(), 4);
+        let stats = spectrum.signal_region_stats();
+        assert_eq!(spectrum.signal_region_intensities(), &[5.0, 3.0]);
+        assert_approx_eq!(f64, stats.min(), 3.0);
+        assert_approx_eq!(f64, stats.max(), 5.0);
+        assert_approx_eq!(f64, stats.mean(), 4.0);
+        assert_eq!(stats.max_index(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialization_round_trip() {
+        let chemical_shifts = (0..2_u32.pow(8))
+            .map(|i| i as f64 * 10.0 / (2_f64.powi(8) - 1.0))
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                1.0 * 0.25 / (0.25_f64.powi(2) + (x - 3.0).powi(2))
+                    + 1.0 * 0.25 / (0.25_f64.powi(2) + (x - 7.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        let signal_boundaries = (1.0, 9.0);
+        let spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries).unwrap();
+        let serialized = serde_json::to_string(&spectrum).unwrap();
+        let deserialized = serde_json::from_str::<Spectrum>(&serialized).unwrap();
+        assert_approx_eq!(
+            f64,
+            spectrum.signal_boundaries().0,
+            deserialized.signal_boundaries().0
+        );
+        assert_approx_eq!(
+            f64,
+            spectrum.signal_boundaries().1,
+            deserialized.signal_boundaries().1
+        );
+        spectrum
+            .chemical_shifts()
+            .iter()
+            .zip(deserialized.chemical_shifts())
+            .for_each(|(init, rec)| assert_approx_eq!(f64, *init, *rec));
+        spectrum
+            .intensities()
+            .iter()
+            .zip(deserialized.intensities())
+            .for_each(|(init, rec)| assert_approx_eq!(f64, *init, *rec));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn metadata_serialization_round_trip() {
+        let mut spectrum = Spectrum::new(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            vec![1.0, 2.0, 3.0, 2.0, 1.0],
+            (2.0, 4.0),
+        )
+        .unwrap();
+        let serialized = serde_json::to_string(&spectrum).unwrap();
+        assert!(!serialized.contains("sampleName"));
+        assert!(!serialized.contains("acquisitionDate"));
+        assert!(!serialized.contains("description"));
+        let deserialized = serde_json::from_str::<Spectrum>(&serialized).unwrap();
+        assert_eq!(deserialized.sample_name(), None);
+        assert_eq!(deserialized.acquisition_date(), None);
+        spectrum.set_sample_name(Some("Blood_1"));
+        spectrum.set_acquisition_date(Some("2021-01-22 18:04:32.025 +0100"));
+        spectrum.set_description(Some("Blood_1\nEDTA plasma"));
+        let serialized = serde_json::to_string(&spectrum).unwrap();
+        let deserialized = serde_json::from_str::<Spectrum>(&serialized).unwrap();
+        assert_eq!(deserialized.sample_name(), Some("Blood_1"));
+        assert_eq!(
+            deserialized.acquisition_date(),
+            Some("2021-01-22 18:04:32.025 +0100")
+        );
+        assert_eq!(deserialized.description(), Some("Blood_1\nEDTA plasma"));
+        let deserialized = Spectrum::from_msgpack(&spectrum.to_msgpack().unwrap()).unwrap();
+        assert_eq!(deserialized.description(), Some("Blood_1\nEDTA plasma"));
+        assert_eq!(deserialized.sample_name(), Some("Blood_1"));
+        assert_eq!(
+            deserialized.acquisition_date(),
+            Some("2021-01-22 18:04:32.025 +0100")
+        );
+        let copy = spectrum.with_intensities(vec![0.0; 5]);
+        assert_eq!(copy.sample_name(), Some("Blood_1"));
+    }
+
+    #[test]
+    #[cfg(feature = "bruker")]
+    fn detect_signal_boundaries() {
+        let path = "../data/bruker/sim/sim_01";
+        let spectrum = crate::spectrum::Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let peaks = std::fs::read_to_string(format!("{}/lorentzians.csv", path))
+            .unwrap()
+            .lines()
+            .skip(1)
+            .map(|line| {
+                line.split(',')
+                    .nth(2)
+                    .unwrap()
+                    .parse::<f64>()
+                    .unwrap()
+            })
+            .collect::<Vec<f64>>();
+        let lowest = peaks
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let highest = peaks
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let boundaries = spectrum.detect_signal_boundaries(5.0).unwrap();
+        let (lower, upper) = (
+            f64::min(boundaries.0, boundaries.1),
+            f64::max(boundaries.0, boundaries.1),
+        );
+        assert!(lower < lowest);
+        assert!(upper > highest);
+        assert!(lower > spectrum.range().0.min(spectrum.range().1));
+        assert!(upper < spectrum.range().0.max(spectrum.range().1));
+        let spectrum = spectrum.with_auto_boundaries(5.0).unwrap();
+        assert_approx_eq!(f64, spectrum.signal_boundaries().0, boundaries.0);
+        assert_approx_eq!(f64, spectrum.signal_boundaries().1, boundaries.1);
+    }
+
+    #[test]
+    #[cfg(feature = "bruker")]
+    fn signal_to_noise() {
+        let path = "../data/bruker/sim/sim_01";
+        let spectrum = crate::spectrum::Bruker::read_spectrum(path, 10, 10, (3.34, 3.56)).unwrap();
+        let clean = spectrum.signal_to_noise((3.58, 3.6)).unwrap();
+        let reversed = spectrum.signal_to_noise((3.6, 3.58)).unwrap();
+        let signal = spectrum.signal_to_noise((3.45, 3.52)).unwrap();
+        assert!(clean.is_finite());
+        assert_approx_eq!(f64, clean, reversed);
+        assert!(clean > 100.0 * signal);
+        let errors = [
+            spectrum.signal_to_noise((3.7, 3.8)).unwrap_err(),
+            spectrum
+                .signal_to_noise((f64::NAN, 3.6))
+                .unwrap_err(),
+            spectrum
+                .signal_to_noise((3.5, f64::INFINITY))
+                .unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| match error {
+            crate::Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidNoiseRange { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+    }
+
+    #[test]
+    fn detect_signal_boundaries_outliers() {
+        let chemical_shifts = (0..1000)
+            .map(|i| 10.0 - i as f64 * 0.01)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let outlier = if [20, 150, 900, 980].contains(&i) {
+                    100.0
+                } else {
+                    0.0
+                };
+                let noise = 0.01 * ((i * 7919) % 13) as f64;
+                outlier + noise + 0.05 / (0.05_f64.powi(2) + (x - 5.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        let boundaries = spectrum.detect_signal_boundaries(5.0).unwrap();
+        assert!(boundaries.0 > 5.0 && boundaries.0 < 8.5);
+        assert!(boundaries.1 < 5.0 && boundaries.1 > 1.5);
+    }
+
+    #[test]
+    fn invalid_signal_detection() {
+        let spectrum = Spectrum::new(
+            (0..100).map(|i| i as f64).collect(),
+            (0..100).map(|i| (i % 3) as f64).collect(),
+            (10.0, 90.0),
+        )
+        .unwrap();
+        let errors = [
+            spectrum
+                .detect_signal_boundaries(0.0)
+                .unwrap_err(),
+            spectrum
+                .detect_signal_boundaries(-1.0)
+                .unwrap_err(),
+            spectrum
+                .detect_signal_boundaries(f64::NAN)
+                .unwrap_err(),
+            spectrum
+                .detect_signal_boundaries(5.0)
+                .unwrap_err(),
+        ];
+        errors
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, error)| {
+                match error {
+                    Error::Spectrum(inner) => match (i, inner.kind()) {
+                        (0..3, Kind::InvalidNoiseMultiple { .. }) => {}
+                        (3, Kind::NoSignalRegionDetected { .. }) => {}
+                        _ => panic!("unexpected kind: {:?}", inner),
+                    },
+                    _ => panic!("unexpected error: {:?}", error),
+                };
+            });
+    }
+
+    fn calibration_test_spectrum() -> Spectrum {
+        let chemical_shifts = (0..2000)
+            .map(|i| 10.0 - i as f64 * 11.0 / 1999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                [(0.004, 0.037, 2.0), (0.01, 3.2, 1.0), (0.02, 7.5, 0.5)]
+                    .iter()
+                    .map(|(hw, maxp, height)| height * hw * hw / (hw * hw + (x - maxp).powi(2)))
+                    .sum::<f64>()
+            })
+            .collect::<Vec<f64>>();
+
+        Spectrum::new(chemical_shifts, intensities, (9.0, 0.5)).unwrap()
+    }
+
+    #[test]
+    fn calibrate_to_peak() {
+        let mut spectrum = calibration_test_spectrum();
+        let before = spectrum.chemical_shifts().to_vec();
+        let boundaries = spectrum.signal_boundaries();
+        let index = (0..before.len())
+            .min_by(|a, b| f64::abs(before[*a] - 0.037).total_cmp(&f64::abs(before[*b] - 0.037)))
+            .unwrap();
+        let offset = -before[index];
+        spectrum
+            .calibrate_to_peak((-0.2, 0.3), 0.0, Some("TSP"))
+            .unwrap();
+        assert_approx_eq!(f64, spectrum.chemical_shifts()[index], 0.0);
         spectrum
             .chemical_shifts()
             .iter()
-            .zip([1.0, 2.0, 3.0])
-            .for_each(|(&xc, xe)| assert_approx_eq!(f64, xc, xe));
+            .zip(before.iter())
+            .for_each(|(after, before)| {
+                assert_approx_eq!(f64, *after, before + offset, epsilon = 1e-9);
+            });
+        assert_approx_eq!(
+            f64,
+            spectrum.signal_boundaries().0,
+            boundaries.0 + offset,
+            epsilon = 1e-9
+        );
+        assert_approx_eq!(
+            f64,
+            spectrum.signal_boundaries().1,
+            boundaries.1 + offset,
+            epsilon = 1e-9
+        );
+        let reference = spectrum.reference_compound();
+        assert_eq!(reference.index(), index);
+        assert_approx_eq!(f64, reference.chemical_shift(), 0.0);
+        assert_eq!(reference.name(), Some("TSP"));
+    }
+
+    #[test]
+    fn invalid_calibration() {
+        let mut spectrum = calibration_test_spectrum();
+        let before = spectrum.chemical_shifts().to_vec();
+        let errors = [
+            spectrum
+                .calibrate_to_peak((f64::NAN, 0.3), 0.0, None)
+                .unwrap_err(),
+            spectrum
+                .calibrate_to_peak((-0.2, 0.3), f64::INFINITY, None)
+                .unwrap_err(),
+            spectrum
+                .calibrate_to_peak((0.1, 0.3), 0.0, None)
+                .unwrap_err(),
+            spectrum
+                .calibrate_to_peak((20.0, 30.0), 0.0, None)
+                .unwrap_err(),
+            spectrum
+                .calibrate_to_peak((0.037, 0.037), 0.0, None)
+                .unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| {
+            match error {
+                Error::Spectrum(inner) => match inner.kind() {
+                    Kind::InvalidCalibration { .. } => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            };
+        });
+        assert_eq!(spectrum.chemical_shifts(), before.as_slice());
+    }
+
+    #[cfg(feature = "fft")]
+    fn phase_test_data() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let chemical_shifts = (0..2_u32.pow(12))
+            .map(|i| 10.0 - i as f64 * 10.0 / (2_f64.powi(12) - 1.0))
+            .collect::<Vec<f64>>();
+        let (absorption, dispersion) = chemical_shifts
+            .iter()
+            .map(|x| {
+                [(0.02, 3.0), (0.02, 7.0)]
+                    .iter()
+                    .map(|(w, c)| {
+                        let t = c - x;
+                        let d = w * w + t * t;
+                        (w / d, t / d)
+                    })
+                    .fold((0.0, 0.0), |acc, (a, d)| (acc.0 + a, acc.1 + d))
+            })
+            .unzip();
+
+        (chemical_shifts, absorption, dispersion)
+    }
+
+    #[test]
+    #[cfg(feature = "fft")]
+    fn phase_correction_identity() {
+        let (chemical_shifts, absorption, _) = phase_test_data();
+        let mut spectrum = Spectrum::new(chemical_shifts, absorption, (9.0, 1.0)).unwrap();
+        spectrum.set_nucleus(Nucleus::Carbon13);
+        spectrum.set_frequency(150.0);
+        let corrected = spectrum.apply_phase_correction(0.0, 0.0).unwrap();
+        assert_eq!(corrected.len(), spectrum.len());
+        assert_approx_eq!(f64, corrected.step(), spectrum.step());
+        assert_eq!(corrected.nucleus(), Nucleus::Carbon13);
+        assert_approx_eq!(f64, corrected.frequency(), 150.0);
         spectrum
             .intensities()
             .iter()
-            .zip([1.0, 2.0, 3.0])
-            .for_each(|(&ic, ie)| assert_approx_eq!(f64, ic, ie));
-        assert_eq!(spectrum.chemical_shifts().len(), 3);
-        assert_eq!(spectrum.intensities().len(), 3);
-        assert_approx_eq!(f64, spectrum.signal_boundaries().0, 1.0);
-        assert_approx_eq!(f64, spectrum.signal_boundaries().1, 3.0);
-        assert_eq!(spectrum.monotonicity(), Monotonicity::Increasing);
+            .zip(corrected.intensities())
+            .for_each(|(init, corr)| assert_approx_eq!(f64, *init, *corr, epsilon = 1e-9));
     }
 
     #[test]
-    fn mutators() {
-        let mut spectrum =
-            Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.0, 3.0)).unwrap();
-        spectrum
-            .set_signal_boundaries((2.5, 1.5))
-            .unwrap();
-        assert_approx_eq!(f64, spectrum.signal_boundaries().0, 1.5);
-        assert_approx_eq!(f64, spectrum.signal_boundaries().1, 2.5);
+    #[cfg(feature = "fft")]
+    fn phase_correction_reversal() {
+        let (chemical_shifts, absorption, dispersion) = phase_test_data();
+        let len = chemical_shifts.len() as f64;
+        let errors = [(30.0, 0.0), (-45.0, 0.0), (0.0, 60.0), (20.0, -40.0)];
+        errors.into_iter().for_each(|(ph0, ph1)| {
+            let distorted = absorption
+                .iter()
+                .zip(dispersion.iter())
+                .enumerate()
+                .map(|(k, (a, d))| {
+                    let phase = (ph0 + ph1 * k as f64 / len).to_radians();
+                    a * phase.cos() - d * phase.sin()
+                })
+                .collect::<Vec<f64>>();
+            let spectrum = Spectrum::new(chemical_shifts.clone(), distorted, (9.0, 1.0)).unwrap();
+            let corrected = spectrum
+                .apply_phase_correction(-ph0, -ph1)
+                .unwrap();
+            let (start, end) = corrected.signal_boundaries_indices();
+            corrected.intensities()[start..end]
+                .iter()
+                .zip(absorption[start..end].iter())
+                .for_each(|(corr, init)| assert_approx_eq!(f64, *corr, *init, epsilon = 1.0));
+        });
     }
 
     #[test]
-    fn properties() {
-        let spectrum = Spectrum::new(
-            vec![1.0, 2.0, 3.0, 4.0, 5.0],
-            vec![1.0, 2.0, 3.0, 4.0, 5.0],
-            (1.5, 4.5),
-        )
-        .unwrap();
-        assert_eq!(spectrum.len(), 5);
-        assert_approx_eq!(f64, spectrum.step(), 1.0);
-        assert_approx_eq!(f64, spectrum.width(), 4.0);
-        assert_approx_eq!(f64, spectrum.range().0, 1.0);
-        assert_approx_eq!(f64, spectrum.range().1, 5.0);
-        assert_approx_eq!(f64, spectrum.center(), 3.0);
-        assert_eq!(spectrum.signal_boundaries_indices(), (0, 4));
+    #[cfg(feature = "fft")]
+    fn invalid_phase_correction() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let angles = [
+            (f64::NAN, 0.0),
+            (0.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 0.0),
+        ];
+        angles.into_iter().for_each(|(ph0, ph1)| {
+            let error = spectrum
+                .apply_phase_correction(ph0, ph1)
+                .unwrap_err();
+            match error {
+                Error::Spectrum(inner) => match inner.kind() {
+                    Kind::InvalidPhaseCorrection { .. } => {}
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            };
+        });
     }
 
-    #[cfg(feature = "serde")]
     #[test]
-    fn serialization_round_trip() {
-        let chemical_shifts = (0..2_u32.pow(8))
-            .map(|i| i as f64 * 10.0 / (2_f64.powi(8) - 1.0))
+    #[cfg(feature = "serde")]
+    fn msgpack_round_trip() {
+        let chemical_shifts = (0..2_u32.pow(12))
+            .map(|i| 10.0 - i as f64 * 10.0 / (2_f64.powi(12) - 1.0))
             .collect::<Vec<f64>>();
         let intensities = chemical_shifts
             .iter()
@@ -1207,19 +3896,27 @@ mod tests {
                     + 1.0 * 0.25 / (0.25_f64.powi(2) + (x - 7.0).powi(2))
             })
             .collect::<Vec<f64>>();
-        let signal_boundaries = (1.0, 9.0);
-        let spectrum = Spectrum::new(chemical_shifts, intensities, signal_boundaries).unwrap();
-        let serialized = serde_json::to_string(&spectrum).unwrap();
-        let deserialized = serde_json::from_str::<Spectrum>(&serialized).unwrap();
-        assert_approx_eq!(
-            f64,
-            spectrum.signal_boundaries().0,
-            deserialized.signal_boundaries().0
-        );
-        assert_approx_eq!(
-            f64,
-            spectrum.signal_boundaries().1,
-            deserialized.signal_boundaries().1
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        spectrum.set_nucleus(Nucleus::Carbon13);
+        spectrum.set_frequency(150.0);
+        spectrum.set_reference_compound(ReferenceCompound::new(
+            4.8,
+            100,
+            None::<String>,
+            Some(crate::spectrum::meta::ReferencingMethod::External),
+        ));
+        let path = std::env::temp_dir().join("metabodecon_msgpack_round_trip.msgpack");
+        spectrum.write_msgpack(&path).unwrap();
+        let deserialized = Spectrum::read_msgpack(&path).unwrap();
+        assert_eq!(deserialized.len(), spectrum.len());
+        assert_eq!(deserialized.monotonicity(), Monotonicity::Decreasing);
+        assert_eq!(deserialized.nucleus(), Nucleus::Carbon13);
+        assert_approx_eq!(f64, deserialized.frequency(), 150.0);
+        assert_eq!(deserialized.reference_compound().index(), 100);
+        assert_eq!(deserialized.reference_compound().name(), None);
+        assert_eq!(
+            deserialized.reference_compound().method(),
+            Some(crate::spectrum::meta::ReferencingMethod::External)
         );
         spectrum
             .chemical_shifts()
@@ -1231,5 +3928,709 @@ mod tests {
             .iter()
             .zip(deserialized.intensities())
             .for_each(|(init, rec)| assert_approx_eq!(f64, *init, *rec));
+        match Spectrum::from_msgpack(&[0xc1]).unwrap_err() {
+            crate::Error::MessagePackDecode(_) => (),
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn crop_increasing() {
+        let chemical_shifts = (0..10).map(|i| i as f64).collect::<Vec<f64>>();
+        let intensities = (0..10)
+            .map(|i| 10.0 * i as f64)
+            .collect::<Vec<f64>>();
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (1.5, 7.5)).unwrap();
+        spectrum.set_reference_compound((4.0, 4));
+        let cropped = spectrum.crop((5.2, 2.8)).unwrap();
+        assert_eq!(cropped.len(), 3);
+        assert_eq!(Arc::strong_count(&cropped.chemical_shifts), 1);
+        assert_eq!(Arc::strong_count(&cropped.intensities), 1);
+        assert_eq!(cropped.monotonicity(), Monotonicity::Increasing);
+        assert_approx_eq!(f64, cropped.step(), spectrum.step());
+        assert_approx_eq!(f64, cropped.range().0, 3.0);
+        assert_approx_eq!(f64, cropped.range().1, 5.0);
+        assert_approx_eq!(f64, cropped.intensities()[0], 30.0);
+        assert_approx_eq!(f64, cropped.intensities()[2], 50.0);
+        assert_approx_eq!(f64, cropped.signal_boundaries().0, 3.0);
+        assert_approx_eq!(f64, cropped.signal_boundaries().1, 5.0);
+        assert_eq!(cropped.reference_compound().index(), 1);
+        let cropped = spectrum.crop((0.0, 3.0)).unwrap();
+        assert_eq!(cropped.len(), 4);
+        assert_approx_eq!(f64, cropped.signal_boundaries().0, 1.5);
+        assert_approx_eq!(f64, cropped.signal_boundaries().1, 3.0);
+        assert_eq!(cropped.reference_compound().index(), 0);
+        assert_approx_eq!(f64, cropped.reference_compound().chemical_shift(), 0.0);
+    }
+
+    #[test]
+    fn crop_decreasing() {
+        let chemical_shifts = (0..10)
+            .map(|i| 9.0 - i as f64)
+            .collect::<Vec<f64>>();
+        let intensities = (0..10)
+            .map(|i| 10.0 * i as f64)
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.5, 7.5)).unwrap();
+        let cropped = spectrum.crop((2.8, 5.2)).unwrap();
+        assert_eq!(cropped.len(), 3);
+        assert_eq!(cropped.monotonicity(), Monotonicity::Decreasing);
+        assert_approx_eq!(f64, cropped.step(), spectrum.step());
+        assert_approx_eq!(f64, cropped.range().0, 5.0);
+        assert_approx_eq!(f64, cropped.range().1, 3.0);
+        assert_approx_eq!(f64, cropped.intensities()[0], 40.0);
+        assert_approx_eq!(f64, cropped.intensities()[2], 60.0);
+        assert_approx_eq!(f64, cropped.signal_boundaries().0, 5.0);
+        assert_approx_eq!(f64, cropped.signal_boundaries().1, 3.0);
+        let cropped = spectrum.crop((6.0, 9.0)).unwrap();
+        assert_eq!(cropped.len(), 4);
+        assert_approx_eq!(f64, cropped.signal_boundaries().0, 7.5);
+        assert_approx_eq!(f64, cropped.signal_boundaries().1, 6.0);
+    }
+
+    #[test]
+    fn invalid_crop_range() {
+        let chemical_shifts = (0..10).map(|i| i as f64).collect::<Vec<f64>>();
+        let intensities = (0..10).map(|i| i as f64).collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.5, 7.5)).unwrap();
+        let ranges = [
+            (f64::NAN, 5.0),
+            (2.0, f64::INFINITY),
+            (4.2, 4.8),
+            (4.0, 4.0),
+            (12.0, 15.0),
+        ];
+        ranges.into_iter().for_each(|range| {
+            let error = spectrum.crop(range).unwrap_err();
+            match error {
+                Error::Spectrum(inner) => match inner.kind() {
+                    Kind::InvalidCropRange {
+                        range: error_range,
+                        chemical_shifts_range,
+                    } => {
+                        assert!(
+                            error_range.0.to_bits() == range.0.to_bits()
+                                && error_range.1.to_bits() == range.1.to_bits()
+                        );
+                        assert_approx_eq!(f64, chemical_shifts_range.0, 0.0);
+                        assert_approx_eq!(f64, chemical_shifts_range.1, 9.0);
+                    }
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            }
+        });
+        let error = spectrum.crop((8.0, 9.0)).unwrap_err();
+        match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidSignalBoundaries {
+                    signal_boundaries,
+                    chemical_shifts_range,
+                } => {
+                    assert_approx_eq!(f64, signal_boundaries.0, 1.5);
+                    assert_approx_eq!(f64, signal_boundaries.1, 7.5);
+                    assert_approx_eq!(f64, chemical_shifts_range.0, 8.0);
+                    assert_approx_eq!(f64, chemical_shifts_range.1, 9.0);
+                }
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn resample_identity() {
+        let chemical_shifts = (0..256)
+            .map(|i| 10.0 - i as f64 * 10.0 / 255.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| 0.25 / (0.25_f64.powi(2) + (x - 3.0).powi(2)))
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        let resampled = spectrum.resample(spectrum.len()).unwrap();
+        assert_eq!(resampled.len(), spectrum.len());
+        assert_eq!(resampled.monotonicity(), Monotonicity::Decreasing);
+        assert_approx_eq!(f64, resampled.signal_boundaries().0, 9.0);
+        assert_approx_eq!(f64, resampled.signal_boundaries().1, 1.0);
+        spectrum
+            .chemical_shifts()
+            .iter()
+            .zip(resampled.chemical_shifts())
+            .for_each(|(init, rec)| assert_approx_eq!(f64, *init, *rec, epsilon = 1e-12));
+        spectrum
+            .intensities()
+            .iter()
+            .zip(resampled.intensities())
+            .for_each(|(init, rec)| assert_approx_eq!(f64, *init, *rec, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn resample_peak_position() {
+        let chemical_shifts = (0..1000)
+            .map(|i| i as f64 * 10.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                0.1 / (0.1_f64.powi(2) + (x - 3.0).powi(2))
+                    + 0.1 / (0.1_f64.powi(2) + (x - 7.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        let resampled = [
+            spectrum.resample(1501).unwrap(),
+            spectrum.resample(401).unwrap(),
+            spectrum.resample_to_step(0.004).unwrap(),
+        ];
+        resampled.iter().for_each(|resampled| {
+            [(0.0, 5.0), (5.0, 10.0)]
+                .iter()
+                .zip([3.0, 7.0])
+                .for_each(|((lower, upper), expected)| {
+                    let maximum = resampled
+                        .chemical_shifts()
+                        .iter()
+                        .zip(resampled.intensities())
+                        .filter(|(x, _)| (*lower..*upper).contains(*x))
+                        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                        .unwrap();
+                    assert!((maximum.0 - expected).abs() <= resampled.step().abs());
+                });
+        });
+        assert_eq!(resampled[2].len(), 2501);
+        assert_approx_eq!(f64, resampled[2].step(), 0.004);
+    }
+
+    #[test]
+    fn decimate_peak_position() {
+        let chemical_shifts = (0..1000)
+            .map(|i| 10.0 - i as f64 * 10.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                0.1 / (0.1_f64.powi(2) + (x - 3.0).powi(2))
+                    + 0.1 / (0.1_f64.powi(2) + (x - 7.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        spectrum.set_frequency(600.0);
+        spectrum.set_nucleus(Nucleus::Carbon13);
+        let decimated = spectrum.decimate(2).unwrap();
+        assert_eq!(decimated.len(), 500);
+        assert_approx_eq!(f64, decimated.step(), 2.0 * spectrum.step());
+        assert_approx_eq!(f64, decimated.chemical_shifts()[0], 10.0);
+        assert_eq!(decimated.signal_boundaries(), spectrum.signal_boundaries());
+        assert_eq!(decimated.monotonicity(), Monotonicity::Decreasing);
+        assert_eq!(decimated.nucleus(), Nucleus::Carbon13);
+        assert_approx_eq!(f64, decimated.frequency(), 600.0);
+        [(0.0, 5.0), (5.0, 10.0)]
+            .iter()
+            .zip([3.0, 7.0])
+            .for_each(|((lower, upper), expected)| {
+                let maximum = decimated
+                    .chemical_shifts()
+                    .iter()
+                    .zip(decimated.intensities())
+                    .filter(|(x, _)| (*lower..*upper).contains(*x))
+                    .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                    .unwrap();
+                assert!((maximum.0 - expected).abs() <= decimated.step().abs());
+            });
+        let identity = spectrum.decimate(1).unwrap();
+        assert_eq!(identity.chemical_shifts(), spectrum.chemical_shifts());
+        assert_eq!(identity.intensities(), spectrum.intensities());
+    }
+
+    #[test]
+    fn invalid_decimation() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        assert_eq!(spectrum.decimate(2).unwrap().len(), 2);
+        [0, 3, 10]
+            .into_iter()
+            .for_each(|factor| match spectrum.decimate(factor).unwrap_err() {
+                Error::Spectrum(inner) => match inner.kind() {
+                    Kind::InvalidDecimation { factor: f, len } => {
+                        assert_eq!(*f, factor);
+                        assert_eq!(*len, 3);
+                    }
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                error => panic!("unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn invalid_resampling() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let errors = [
+            spectrum.resample(0).unwrap_err(),
+            spectrum.resample(1).unwrap_err(),
+            spectrum.resample_to_step(0.0).unwrap_err(),
+            spectrum.resample_to_step(f64::NAN).unwrap_err(),
+            spectrum.resample_to_step(5.0).unwrap_err(),
+        ];
+        let expected_context = [0, 1, 0, 0, 1];
+        errors
+            .into_iter()
+            .zip(expected_context)
+            .for_each(|(error, context)| match error {
+                Error::Spectrum(inner) => match inner.kind() {
+                    Kind::InvalidResampling { len, .. } => assert_eq!(*len, context),
+                    _ => panic!("unexpected kind: {:?}", inner),
+                },
+                _ => panic!("unexpected error: {:?}", error),
+            });
+    }
+
+    #[test]
+    fn bucket_sum() {
+        let chemical_shifts = (0..1000)
+            .map(|i| i as f64 * 10.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| 0.1 / (0.1_f64.powi(2) + (x - 3.0).powi(2)))
+            .collect::<Vec<f64>>();
+        let step = 10.0 / 999.0;
+        let boundaries = (100.5 * step, 900.5 * step);
+        let increasing = Spectrum::new(chemical_shifts, intensities, boundaries).unwrap();
+        let expected = increasing.intensities()[101..=900]
+            .iter()
+            .sum::<f64>();
+        let decreasing = Spectrum::new(
+            increasing
+                .chemical_shifts()
+                .iter()
+                .rev()
+                .copied()
+                .collect(),
+            increasing
+                .intensities()
+                .iter()
+                .rev()
+                .copied()
+                .collect(),
+            boundaries,
+        )
+        .unwrap();
+        [&increasing, &decreasing]
+            .into_iter()
+            .for_each(|spectrum| {
+                [0.01, 0.05, 0.3, 1.0]
+                    .into_iter()
+                    .for_each(|width| {
+                        let buckets = spectrum.bucket(width).unwrap();
+                        let sum = buckets.iter().map(|(_, sum)| sum).sum::<f64>();
+                        assert_approx_eq!(f64, sum, expected, epsilon = 1e-9 * expected);
+                    });
+                let buckets = spectrum.bucket_count(7).unwrap();
+                let sum = buckets.iter().map(|(_, sum)| sum).sum::<f64>();
+                assert_eq!(buckets.len(), 7);
+                assert_approx_eq!(f64, sum, expected, epsilon = 1e-9 * expected);
+            });
+        let increasing = increasing.bucket(0.3).unwrap();
+        let decreasing = decreasing.bucket(0.3).unwrap();
+        increasing
+            .iter()
+            .zip(decreasing.iter().rev())
+            .for_each(|(a, b)| {
+                assert_approx_eq!(f64, a.0, b.0);
+                assert_approx_eq!(f64, a.1, b.1, epsilon = 1e-9);
+            });
+    }
+
+    #[test]
+    fn invalid_bucket_width() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let errors = [
+            spectrum.bucket(0.0).unwrap_err(),
+            spectrum.bucket(-1.0).unwrap_err(),
+            spectrum.bucket(f64::NAN).unwrap_err(),
+            spectrum.bucket_count(0).unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidBucketWidth { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+    }
+
+    #[test]
+    fn normalize() {
+        let chemical_shifts = (0..1000)
+            .map(|i| i as f64 * 10.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| {
+                0.1 / (0.1_f64.powi(2) + (x - 3.0).powi(2))
+                    + 0.2 / (0.1_f64.powi(2) + (x - 7.0).powi(2))
+            })
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        let ratio = spectrum.intensities()[700] / spectrum.intensities()[300];
+        [1.0, 250.0, -3.0].into_iter().for_each(|target| {
+            let normalized = spectrum.normalize_to(target).unwrap();
+            let sum = normalized
+                .signal_region_intensities()
+                .iter()
+                .sum::<f64>();
+            assert_approx_eq!(f64, sum, target, epsilon = 1e-12 * target.abs());
+            assert_approx_eq!(
+                f64,
+                normalized.intensities()[700] / normalized.intensities()[300],
+                ratio,
+                epsilon = 1e-12 * ratio
+            );
+            assert_eq!(normalized.len(), spectrum.len());
+            assert_approx_eq!(f64, normalized.signal_boundaries().0, 1.0);
+            assert_approx_eq!(f64, normalized.signal_boundaries().1, 9.0);
+        });
+        let normalized = spectrum.normalize_total_area().unwrap();
+        let sum = normalized
+            .signal_region_intensities()
+            .iter()
+            .sum::<f64>();
+        assert_approx_eq!(f64, sum, 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn invalid_normalization() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![0.0, 0.0, 3.0], (1.5, 2.5)).unwrap();
+        let valid = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let errors = [
+            spectrum.normalize_total_area().unwrap_err(),
+            valid.normalize_to(0.0).unwrap_err(),
+            valid.normalize_to(f64::INFINITY).unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidNormalization { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+    }
+
+    #[test]
+    fn arithmetic() {
+        let chemical_shifts = (0..1000)
+            .map(|i| i as f64 * 10.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| 0.1 / (0.1_f64.powi(2) + (x - 3.0).powi(2)))
+            .collect::<Vec<f64>>();
+        let spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        let difference = (&spectrum - &spectrum).unwrap();
+        assert!(difference.intensities().iter().all(|&i| i == 0.0));
+        assert_eq!(difference.chemical_shifts(), spectrum.chemical_shifts());
+        assert_approx_eq!(f64, difference.signal_boundaries().0, 1.0);
+        assert_approx_eq!(f64, difference.signal_boundaries().1, 9.0);
+        let sum = (&spectrum + &spectrum).unwrap();
+        sum.intensities()
+            .iter()
+            .zip(spectrum.intensities().iter())
+            .for_each(|(&s, &i)| assert_approx_eq!(f64, s, 2.0 * i));
+        assert_eq!(
+            (&sum - &spectrum).unwrap().intensities(),
+            spectrum.intensities()
+        );
+    }
+
+    #[test]
+    fn grid_mismatch() {
+        let spectrum = Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let shorter = Spectrum::new(vec![1.0, 3.0], vec![1.0, 3.0], (1.5, 2.5)).unwrap();
+        let shifted = Spectrum::new(vec![1.1, 2.1, 3.1], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let reversed = Spectrum::new(vec![3.0, 2.0, 1.0], vec![1.0, 2.0, 3.0], (1.5, 2.5)).unwrap();
+        let errors = [
+            spectrum.add(&shorter).unwrap_err(),
+            spectrum.subtract(&shifted).unwrap_err(),
+            (&spectrum - &reversed).unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::GridMismatch { lengths, .. } => assert_eq!(lengths.0, 3),
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "fft")]
+    fn exponential_window_broadening() {
+        let (frequency, width): (f64, f64) = (600.0, 0.005);
+        let chemical_shifts = (0..8192)
+            .map(|i| i as f64 * 10.0 / 8191.0)
+            .collect::<Vec<f64>>();
+        let intensities = chemical_shifts
+            .iter()
+            .map(|x| width / (width.powi(2) + (x - 5.0).powi(2)))
+            .collect::<Vec<f64>>();
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        spectrum.set_frequency(frequency);
+        let step = spectrum.step();
+        let area = spectrum.intensities().iter().sum::<f64>();
+        let height = spectrum.intensities()[4096];
+        [1.0, 3.0, 10.0].into_iter().for_each(|lb| {
+            let broadened = spectrum
+                .apply_window(WindowFunction::Exponential { lb })
+                .unwrap();
+            let expected = width / (width + lb / frequency / 2.0);
+            assert_approx_eq!(
+                f64,
+                broadened.intensities()[4096] / height,
+                expected,
+                epsilon = 1e-2
+            );
+            assert_approx_eq!(
+                f64,
+                broadened.intensities().iter().sum::<f64>(),
+                area,
+                epsilon = 1e-9 * area
+            );
+            let half = broadened.intensities()[4096] / 2.0;
+            let fwhm = broadened
+                .intensities()
+                .iter()
+                .filter(|&&intensity| intensity >= half)
+                .count() as f64
+                * step;
+            assert_approx_eq!(
+                f64,
+                fwhm,
+                2.0 * width + lb / frequency,
+                epsilon = 2.0 * step
+            );
+            assert_eq!(broadened.chemical_shifts(), spectrum.chemical_shifts());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "fft")]
+    fn invalid_window_function() {
+        let mut spectrum = Spectrum::new(
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![0.0, 1.0, 0.0, -1.0],
+            (1.5, 3.5),
+        )
+        .unwrap();
+        spectrum.set_frequency(600.0);
+        let mut unset = spectrum.clone();
+        unset.set_frequency(f64::NAN);
+        let errors = [
+            spectrum
+                .apply_window(WindowFunction::Exponential { lb: f64::INFINITY })
+                .unwrap_err(),
+            spectrum
+                .apply_window(WindowFunction::Gaussian { gb: -1.0 })
+                .unwrap_err(),
+            spectrum
+                .apply_window(WindowFunction::SineBell { offset: 1.5 })
+                .unwrap_err(),
+            unset
+                .apply_window(WindowFunction::Exponential { lb: 1.0 })
+                .unwrap_err(),
+        ];
+        errors.into_iter().for_each(|error| match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidWindowFunction { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+    }
+
+    #[test]
+    fn signal_boundaries_hz() {
+        let chemical_shifts = (0..1000)
+            .map(|i| i as f64 * 10.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = vec![1.0; 1000];
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (1.0, 9.0)).unwrap();
+        spectrum.set_frequency(600.0);
+        let (start, end) = spectrum.signal_boundaries_hz().unwrap();
+        assert_approx_eq!(f64, start, 600.0);
+        assert_approx_eq!(f64, end, 5400.0);
+        spectrum
+            .set_signal_boundaries_hz((1200.0, 4800.0))
+            .unwrap();
+        assert_approx_eq!(f64, spectrum.signal_boundaries().0, 2.0);
+        assert_approx_eq!(f64, spectrum.signal_boundaries().1, 8.0);
+        let (start, end) = spectrum.signal_boundaries_hz().unwrap();
+        assert_approx_eq!(f64, start, 1200.0);
+        assert_approx_eq!(f64, end, 4800.0);
+        match spectrum
+            .set_signal_boundaries_hz((1200.0, 6600.0))
+            .unwrap_err()
+        {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidSignalBoundaries { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn chemical_shifts_hz() {
+        let chemical_shifts = (0..1000)
+            .map(|i| 12.0 - i as f64 * 12.0 / 999.0)
+            .collect::<Vec<f64>>();
+        let intensities = vec![1.0; 1000];
+        let mut spectrum = Spectrum::new(chemical_shifts, intensities, (9.0, 1.0)).unwrap();
+        spectrum.set_frequency(600.0);
+        spectrum.set_sample_name(Some("Blood_1"));
+        spectrum.set_reference_compound(ReferenceCompound::new(0.0, 999, Some("TMS"), None));
+        let step = spectrum.step() * 600.0;
+        assert_approx_eq!(f64, step, -12.0 * 600.0 / 999.0, epsilon = 1e-9);
+        let hz = spectrum.chemical_shifts_hz().unwrap();
+        assert_approx_eq!(f64, hz[0], 7200.0, epsilon = 1e-9);
+        assert_approx_eq!(f64, hz[999], 0.0, epsilon = 1e-9);
+        hz.windows(2)
+            .for_each(|w| assert_approx_eq!(f64, w[1] - w[0], step, epsilon = 1e-9));
+        let converted = spectrum.in_hz().unwrap();
+        assert_eq!(converted.chemical_shifts(), hz);
+        assert_eq!(converted.intensities(), spectrum.intensities());
+        assert_approx_eq!(f64, converted.step(), step, epsilon = 1e-9);
+        assert_approx_eq!(f64, converted.signal_boundaries().0, 5400.0, epsilon = 1e-9);
+        assert_approx_eq!(f64, converted.signal_boundaries().1, 600.0, epsilon = 1e-9);
+        assert_approx_eq!(f64, converted.frequency(), 1.0);
+        assert_eq!(converted.nucleus(), Nucleus::Hydrogen1);
+        assert_eq!(converted.sample_name(), Some("Blood_1"));
+        assert_eq!(converted.reference_compound().index(), 999);
+        assert_eq!(converted.reference_compound().name(), Some("TMS"));
+        assert_eq!(converted.monotonicity(), Monotonicity::Decreasing);
+        assert!(converted.in_hz().is_err());
+    }
+
+    #[test]
+    fn invalid_frequency() {
+        let mut spectrum =
+            Spectrum::new(vec![1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0], (1.0, 3.0)).unwrap();
+        let mut errors = vec![
+            spectrum.signal_boundaries_hz().unwrap_err(),
+            spectrum
+                .set_signal_boundaries_hz((1.5, 2.5))
+                .unwrap_err(),
+            spectrum.chemical_shifts_hz().unwrap_err(),
+            spectrum.in_hz().unwrap_err(),
+        ];
+        [f64::NAN, -600.0, 0.0]
+            .into_iter()
+            .for_each(|frequency| {
+                spectrum.set_frequency(frequency);
+                errors.push(spectrum.signal_boundaries_hz().unwrap_err());
+                errors.push(spectrum.chemical_shifts_hz().unwrap_err());
+            });
+        errors.into_iter().for_each(|error| match error {
+            Error::Spectrum(inner) => match inner.kind() {
+                Kind::InvalidFrequency { .. } => {}
+                _ => panic!("unexpected kind: {:?}", inner),
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        });
+        assert_approx_eq!(f64, spectrum.signal_boundaries().0, 1.0);
+        assert_approx_eq!(f64, spectrum.signal_boundaries().1, 3.0);
+    }
+
+    #[test]
+    fn from_nonuniform() {
+        let lorentzian = |x: f64| 0.05 / (0.05_f64.powi(2) + (x - 5.0).powi(2));
+        [(0.0, 10.0), (10.0, 0.0)]
+            .into_iter()
+            .for_each(|(start, end)| {
+                let exact = (0..1000)
+                    .map(|i| start + i as f64 * (end - start) / 999.0)
+                    .collect::<Vec<f64>>();
+                let rounded = exact
+                    .iter()
+                    .map(|x| (x * 1000.0).round() / 1000.0)
+                    .collect::<Vec<f64>>();
+                let intensities = rounded
+                    .iter()
+                    .map(|x| lorentzian(*x))
+                    .collect::<Vec<f64>>();
+                assert!(Spectrum::new(rounded.clone(), intensities.clone(), (1.0, 9.0)).is_err());
+                let spectrum = Spectrum::from_nonuniform(rounded, intensities, (1.0, 9.0)).unwrap();
+                assert_eq!(spectrum.len(), 1000);
+                assert_approx_eq!(f64, spectrum.step(), (end - start) / 999.0, epsilon = 1e-6);
+                spectrum
+                    .chemical_shifts()
+                    .iter()
+                    .zip(exact.iter())
+                    .for_each(|(chemical_shift, exact)| {
+                        assert_approx_eq!(f64, *chemical_shift, *exact, epsilon = 1e-3);
+                    });
+                let maximum = spectrum
+                    .intensities()
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .unwrap()
+                    .0;
+                assert_approx_eq!(
+                    f64,
+                    spectrum.chemical_shifts()[maximum],
+                    5.0,
+                    epsilon = spectrum.step().abs()
+                );
+                spectrum
+                    .chemical_shifts()
+                    .iter()
+                    .zip(spectrum.intensities().iter())
+                    .for_each(|(chemical_shift, intensity)| {
+                        assert_approx_eq!(
+                            f64,
+                            *intensity,
+                            lorentzian(*chemical_shift),
+                            epsilon = 0.05
+                        );
+                    });
+            });
+        let uniform = (0..10).map(|i| i as f64).collect::<Vec<f64>>();
+        let spectrum =
+            Spectrum::from_nonuniform(uniform.clone(), uniform.clone(), (1.0, 8.0)).unwrap();
+        assert_eq!(spectrum.chemical_shifts(), uniform);
+        assert_eq!(spectrum.intensities(), uniform);
+    }
+
+    #[test]
+    fn from_nonuniform_irregular() {
+        let d = (0..10).map(|i| i as f64).collect::<Vec<f64>>();
+        let s = (2.0, 7.0);
+        let shifted = (0..10)
+            .map(|i| if i != 5 { i as f64 } else { 5.6 })
+            .collect::<Vec<f64>>();
+        let reversed = (0..10)
+            .map(|i| match i {
+                4 => 5.0,
+                5 => 4.0,
+                _ => i as f64,
+            })
+            .collect::<Vec<f64>>();
+        let one_nan = (0..10)
+            .map(|i| if i != 5 { i as f64 } else { f64::NAN })
+            .collect::<Vec<f64>>();
+        let quadratic = (0..10)
+            .map(|i| (i * i) as f64)
+            .collect::<Vec<f64>>();
+        [shifted, reversed, one_nan, quadratic]
+            .into_iter()
+            .for_each(|chemical_shifts| {
+                match Spectrum::from_nonuniform(chemical_shifts, d.clone(), s).unwrap_err() {
+                    Error::Spectrum(inner) => match inner.kind() {
+                        Kind::NonUniformSpacing { .. } => {}
+                        _ => panic!("unexpected kind: {:?}", inner),
+                    },
+                    error => panic!("unexpected error: {:?}", error),
+                }
+            });
     }
 }
@@ -0,0 +1,122 @@
+/// Window functions for apodization of a [`Spectrum`].
+///
+/// Window functions weight the time-domain signal to trade resolution for
+/// signal-to-noise ratio or vice versa. As a [`Spectrum`] only stores the
+/// frequency domain, the window is applied to the time-domain signal obtained
+/// through an inverse Fourier transform of the intensities. See
+/// [`Spectrum::apply_window`] for details.
+///
+/// Line broadening parameters are given in Hz. They are converted to the
+/// chemical shift scale using the spectrometer frequency of the [`Spectrum`].
+///
+/// [`Spectrum`]: crate::spectrum::Spectrum
+/// [`Spectrum::apply_window`]: crate::spectrum::Spectrum::apply_window
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WindowFunction {
+    /// Exponential window `exp(-π * lb * t)`.
+    ///
+    /// Convolves the spectrum with a Lorentzian of full width at half maximum
+    /// `lb` Hz, which broadens Lorentzian signals by `lb` Hz. Negative values
+    /// narrow the signals instead (resolution enhancement), at the cost of
+    /// amplified noise.
+    Exponential {
+        /// Line broadening in Hz. Must be finite.
+        lb: f64,
+    },
+    /// Gaussian window `exp(-(π * gb * t)^2 / (4 * ln(2)))`.
+    ///
+    /// Convolves the spectrum with a Gaussian of full width at half maximum
+    /// `gb` Hz.
+    Gaussian {
+        /// Gaussian broadening in Hz. Must be finite and non-negative.
+        gb: f64,
+    },
+    /// Sine bell window `sin(π * offset + π * (1 - offset) * t / t_max)`.
+    ///
+    /// The offset shifts the start of the sine bell, with `0.0` giving a pure
+    /// sine bell and `0.5` a cosine bell. Offsets below `0.5` suppress the
+    /// start of the time-domain signal and therefore reduce the total area.
+    SineBell {
+        /// Offset as a fraction of π. Must be within `[0, 1)`.
+        offset: f64,
+    },
+}
+
+impl std::fmt::Display for WindowFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFunction::Exponential { lb } => write!(f, "Exponential(lb = {})", lb),
+            WindowFunction::Gaussian { gb } => write!(f, "Gaussian(gb = {})", gb),
+            WindowFunction::SineBell { offset } => write!(f, "SineBell(offset = {})", offset),
+        }
+    }
+}
+
+#[cfg(feature = "fft")]
+impl WindowFunction {
+    /// Internal helper function to check if the parameters of the window
+    /// function are valid.
+    pub(crate) fn is_valid(&self) -> bool {
+        match *self {
+            WindowFunction::Exponential { lb } => lb.is_finite(),
+            WindowFunction::Gaussian { gb } => gb.is_finite() && gb >= 0.0,
+            WindowFunction::SineBell { offset } => (0.0..1.0).contains(&offset),
+        }
+    }
+
+    /// Internal helper function to compute the weight of the window function
+    /// at time `t`, where `t_max` is the largest time of the signal.
+    pub(crate) fn weight(&self, t: f64, t_max: f64) -> f64 {
+        match *self {
+            WindowFunction::Exponential { lb } => f64::exp(-std::f64::consts::PI * lb * t),
+            WindowFunction::Gaussian { gb } => {
+                f64::exp(-(std::f64::consts::PI * gb * t).powi(2) / (4.0 * f64::ln(2.0)))
+            }
+            WindowFunction::SineBell { offset } => f64::sin(
+                std::f64::consts::PI * offset + std::f64::consts::PI * (1.0 - offset) * t / t_max,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_send, assert_sync};
+    #[cfg(feature = "fft")]
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn thread_safety() {
+        assert_send!(WindowFunction);
+        assert_sync!(WindowFunction);
+    }
+
+    #[test]
+    #[cfg(feature = "fft")]
+    fn weight() {
+        let exponential = WindowFunction::Exponential { lb: 1.0 };
+        let gaussian = WindowFunction::Gaussian { gb: 1.0 };
+        let sine_bell = WindowFunction::SineBell { offset: 0.5 };
+        assert_approx_eq!(f64, exponential.weight(0.0, 1.0), 1.0);
+        assert_approx_eq!(f64, gaussian.weight(0.0, 1.0), 1.0);
+        assert_approx_eq!(f64, sine_bell.weight(0.0, 1.0), 1.0);
+        assert_approx_eq!(f64, sine_bell.weight(1.0, 1.0), 0.0, epsilon = 1e-15);
+        assert_approx_eq!(
+            f64,
+            exponential.weight(1.0, 1.0),
+            f64::exp(-std::f64::consts::PI)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fft")]
+    fn is_valid() {
+        assert!(WindowFunction::Exponential { lb: -1.0 }.is_valid());
+        assert!(!WindowFunction::Exponential { lb: f64::NAN }.is_valid());
+        assert!(WindowFunction::Gaussian { gb: 0.0 }.is_valid());
+        assert!(!WindowFunction::Gaussian { gb: -1.0 }.is_valid());
+        assert!(WindowFunction::SineBell { offset: 0.0 }.is_valid());
+        assert!(!WindowFunction::SineBell { offset: 1.0 }.is_valid());
+    }
+}
@@ -69,3 +69,55 @@ fn par_blood() {
     let filename = format!("{}_par_deconvolution.json", "blood_01");
     store_deconvolution(deconvolution, filename.as_str());
 }
+
+#[test]
+fn sim_levenberg_marquardt() {
+    let path = workspace_dir()
+        .join("data")
+        .join("bruker")
+        .join("sim")
+        .join("sim_01");
+    let spectrum = Bruker::read_spectrum(path, 10, 10, (3.35, 3.55)).unwrap();
+    let mut deconvoluter = Deconvoluter::default();
+    let analytical = deconvoluter
+        .deconvolute_spectrum(&spectrum)
+        .unwrap();
+    deconvoluter
+        .set_fitting_settings(FittingSettings::LevenbergMarquardt {
+            max_iterations: 100,
+            tolerance: 1e-6,
+            lambda_init: 1e-3,
+        })
+        .unwrap();
+    let levenberg_marquardt = deconvoluter
+        .deconvolute_spectrum(&spectrum)
+        .unwrap();
+    assert_eq!(
+        levenberg_marquardt.lorentzians().len(),
+        analytical.lorentzians().len()
+    );
+    assert!(levenberg_marquardt.mse() < analytical.mse());
+    let filename = format!("{}_levenberg_marquardt_deconvolution.json", "sim_01");
+    store_deconvolution(levenberg_marquardt, filename.as_str());
+}
+
+#[test]
+fn sim_quality_metrics() {
+    let path = workspace_dir()
+        .join("data")
+        .join("bruker")
+        .join("sim")
+        .join("sim_01");
+    let spectrum = Bruker::read_spectrum(path, 10, 10, (3.35, 3.55)).unwrap();
+    let deconvoluter = Deconvoluter::default();
+    let deconvolution = deconvoluter
+        .deconvolute_spectrum(&spectrum)
+        .unwrap();
+    let r_squared = deconvolution.r_squared(&spectrum);
+    assert!(r_squared > 0.999 && r_squared <= 1.0);
+    let signal_areas = deconvolution.signal_areas();
+    assert_eq!(signal_areas.len(), deconvolution.lorentzians().len());
+    assert!(signal_areas.iter().all(|area| *area > 0.0));
+    let total_area = signal_areas.iter().sum::<f64>();
+    assert!((deconvolution.total_area() - total_area).abs() <= 1e-12 * total_area);
+}